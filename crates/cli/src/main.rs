@@ -11,11 +11,13 @@ use aes_gcm::{
     aead::{Aead, KeyInit, OsRng, Payload},
     Aes256Gcm,
 };
+use chacha20poly1305::ChaCha20Poly1305;
 
 use anyhow::{anyhow, Context, Result};
 use bincode::{deserialize_from, serialize_into};
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+use indicatif::{ProgressBar, ProgressStyle};
 use rand_core::RngCore;
 use sealedge_core::format;
 #[cfg(feature = "audio")]
@@ -24,13 +26,15 @@ use sealedge_core::AudioCapture;
 use sealedge_core::AudioConfig;
 #[cfg(feature = "keyring")]
 use sealedge_core::KeyringBackend;
-use sealedge_core::{BackendRegistry, KeyBackend, KeyContext};
+use sealedge_core::{BackendInfo, BackendRegistry, KeyBackend, KeyContext};
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Read, Write};
+use std::io::{BufReader, BufWriter, IsTerminal, Read, Write};
 use std::path::PathBuf;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use zeroize::Zeroize;
 
+mod verify;
+
 use sealedge_core::{
     // helpers
     build_aad,
@@ -54,6 +58,7 @@ use sealedge_core::{
 #[derive(Debug)]
 enum InputSource {
     File(PathBuf),
+    Stdin,
     LiveAudio,
 }
 
@@ -62,18 +67,18 @@ trait InputReader {
     fn read_chunk(&mut self, buf: &mut [u8]) -> Result<usize>;
 }
 
-/// File-based input reader
-struct FileInputReader {
-    reader: BufReader<File>,
+/// File or stdin input reader
+struct StreamInputReader {
+    reader: BufReader<Box<dyn Read>>,
 }
 
-impl FileInputReader {
-    fn new(reader: BufReader<File>) -> Self {
+impl StreamInputReader {
+    fn new(reader: BufReader<Box<dyn Read>>) -> Self {
         Self { reader }
     }
 }
 
-impl InputReader for FileInputReader {
+impl InputReader for StreamInputReader {
     fn read_chunk(&mut self, buf: &mut [u8]) -> Result<usize> {
         self.reader.read(buf).context("read chunk")
     }
@@ -103,15 +108,15 @@ impl InputReader for AudioInputReader {
         if !self.started {
             self.capture.start()?;
             self.started = true;
-            println!("♪ Live audio capture started");
+            eprintln!("♪ Live audio capture started");
         }
 
         // Wait for audio chunk - keep trying until we get data
         loop {
             match self.capture.try_next_chunk()? {
                 Some(audio_chunk) => {
-                    println!("📦 Audio chunk: {} samples", audio_chunk.data.len());
-                    let audio_bytes = audio_chunk.to_bytes();
+                    eprintln!("📦 Audio chunk: {} samples", audio_chunk.data.len());
+                    let audio_bytes = audio_chunk.encode(self.capture.config().encoding)?;
                     let bytes_to_copy = std::cmp::min(audio_bytes.len(), buf.len());
                     buf[..bytes_to_copy].copy_from_slice(&audio_bytes[..bytes_to_copy]);
                     return Ok(bytes_to_copy);
@@ -128,13 +133,22 @@ impl InputReader for AudioInputReader {
 
 /// CLI Arguments
 #[derive(Parser, Debug)]
-#[command(name = "sealedge", version, about)]
+#[command(
+    name = "sealedge",
+    version,
+    about,
+    args_conflicts_with_subcommands = true
+)]
 struct Args {
-    /// Input file (opaque bytes)
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Input file (opaque bytes); `-` or omitted reads stdin
     #[arg(short, long)]
     input: Option<PathBuf>,
 
-    /// Output file for round-tripped plaintext (encrypt mode) or decrypt target (decrypt mode)
+    /// Output file for round-tripped plaintext (encrypt mode) or decrypt target (decrypt mode);
+    /// `-` or omitted writes stdout
     #[arg(short, long)]
     out: Option<PathBuf>,
 
@@ -142,7 +156,7 @@ struct Args {
     #[arg(long, default_value_t = 4096)]
     chunk: usize,
 
-    /// Optional: write envelope (header + records) to this .trst file
+    /// Optional: write envelope (header + records) to this .trst file (`-` for stdout)
     #[arg(long)]
     envelope: Option<PathBuf>,
 
@@ -154,6 +168,14 @@ struct Args {
     #[arg(long, default_value_t = false)]
     decrypt: bool,
 
+    /// Decrypt only plaintext bytes from this offset on (chunks before it are not decrypted)
+    #[arg(long, requires = "decrypt", conflicts_with = "wav")]
+    offset: Option<u64>,
+
+    /// Decrypt at most this many plaintext bytes (reading stops after the last needed chunk)
+    #[arg(long, requires = "decrypt", conflicts_with = "wav")]
+    length: Option<u64>,
+
     /// 64 hex chars (32 bytes) AES-256 key
     #[arg(long)]
     key_hex: Option<String>,
@@ -162,10 +184,22 @@ struct Args {
     #[arg(long)]
     key_out: Option<PathBuf>,
 
+    /// File holding the Ed25519 manifest signing key as 64 hex chars
+    #[arg(long, conflicts_with = "signing_key_hex")]
+    signing_key: Option<PathBuf>,
+
+    /// Ed25519 manifest signing key as 64 hex chars (prefer --signing-key)
+    #[arg(long)]
+    signing_key_hex: Option<String>,
+
     /// Store passphrase in OS keyring (one-time setup)
     #[arg(long)]
     set_passphrase: Option<String>,
 
+    /// Replace the keyring passphrase, after checking the old one
+    #[arg(long, num_args = 2, value_names = ["OLD", "NEW"])]
+    rotate_passphrase: Option<Vec<String>>,
+
     /// Salt for key derivation (32 hex chars = 16 bytes)
     #[arg(long)]
     salt_hex: Option<String>,
@@ -174,7 +208,7 @@ struct Args {
     #[arg(long)]
     use_keyring: bool,
 
-    /// Key management backend to use (keyring, tpm, hsm, matter)
+    /// Key management backend to use (keyring, file, tpm, aws-kms, hsm, matter)
     #[arg(long, default_value = "keyring")]
     backend: String,
 
@@ -211,6 +245,10 @@ struct Args {
     #[arg(long, default_value_t = 1000)]
     chunk_duration_ms: u64,
 
+    /// Audio chunk encoding (opus needs the `opus` feature and 8/12/16/24/48 kHz)
+    #[arg(long, value_enum, default_value_t = AudioEncodingChoice::PcmF32)]
+    audio_encoding: AudioEncodingChoice,
+
     /// Stream live chunks to server (requires --live-capture)
     #[arg(long)]
     stream_to_server: Option<String>,
@@ -224,19 +262,82 @@ struct Args {
     #[arg(long)]
     inspect: bool,
 
+    /// With --inspect, print every record's manifest as one JSON object per line
+    #[arg(long, requires = "inspect")]
+    all: bool,
+
     /// Force raw output regardless of data type
     #[arg(long)]
     force_raw: bool,
 
+    /// Decrypt live-audio archives into a WAV file instead of raw PCM
+    #[arg(long, conflicts_with = "force_raw")]
+    wav: bool,
+
     /// Enable verbose output for format details
     #[arg(long)]
     verbose: bool,
 
+    /// Show a progress bar on stderr (byte counter when the input size is unknown)
+    #[arg(long)]
+    progress: bool,
+
+    /// Encrypt chunks on N worker threads (output is identical in layout to N=1)
+    #[arg(long, default_value_t = 1)]
+    jobs: usize,
+
+    /// Verify each manifest and decrypt each record again after sealing it (halves throughput)
+    #[arg(long)]
+    verify_each: bool,
+
+    /// AEAD cipher for new envelopes (decryption follows the envelope header)
+    #[arg(long, value_enum, default_value_t = AeadChoice::Aes256gcm)]
+    aead: AeadChoice,
+
     /// Print the generated AES-256 encryption key to stderr (unsafe — prefer --key-out)
     #[arg(long, default_value_t = false)]
     show_key: bool,
 }
 
+/// Subcommands; without one, the flags above select encrypt or decrypt mode
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Verify a .seal archive's signature and continuity chain
+    Verify(verify::VerifyArgs),
+}
+
+/// AEAD ciphers selectable with `--aead`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum AeadChoice {
+    Aes256gcm,
+    Chacha20poly1305,
+}
+
+impl From<AeadChoice> for format::AeadAlgorithm {
+    fn from(choice: AeadChoice) -> Self {
+        match choice {
+            AeadChoice::Aes256gcm => format::AeadAlgorithm::Aes256Gcm,
+            AeadChoice::Chacha20poly1305 => format::AeadAlgorithm::ChaCha20Poly1305,
+        }
+    }
+}
+
+/// Audio encodings selectable with `--audio-encoding`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum AudioEncodingChoice {
+    PcmF32,
+    Opus,
+}
+
+impl From<AudioEncodingChoice> for sealedge_core::AudioEncoding {
+    fn from(choice: AudioEncodingChoice) -> Self {
+        match choice {
+            AudioEncodingChoice::PcmF32 => sealedge_core::AudioEncoding::PcmF32,
+            AudioEncodingChoice::Opus => sealedge_core::AudioEncoding::Opus,
+        }
+    }
+}
+
 /// Helpers
 enum Mode {
     Encrypt,
@@ -251,23 +352,28 @@ fn list_backends() -> Result<()> {
     println!("Available key management backends:");
     for backend_name in available {
         // Create backend to get info
-        if let Ok(backend) = registry.create_backend(backend_name) {
-            let info = backend.backend_info();
-            let status = if info.available { "✓" } else { "✗" };
-            println!("  {} {} - {}", status, info.name, info.description);
+        let info = match registry.create_backend(backend_name) {
+            Ok(backend) => backend.backend_info(),
+            // The file backend cannot be created without --backend-config path=...
+            Err(_) if backend_name == "file" => BackendInfo::file(),
+            Err(_) => continue,
+        };
+        let status = if info.available { "✓" } else { "✗" };
+        println!("  {} {} - {}", status, info.name, info.description);
 
-            if !info.config_requirements.is_empty() {
-                println!(
-                    "    Required config: {}",
-                    info.config_requirements.join(", ")
-                );
-            }
+        if !info.config_requirements.is_empty() {
+            println!(
+                "    Required config: {}",
+                info.config_requirements.join(", ")
+            );
         }
     }
 
     println!("\nUsage examples:");
     println!("  --backend keyring --use-keyring --salt-hex <salt>");
+    println!("  --backend file --backend-config path=/etc/sealedge/master.key --salt-hex <salt>");
     println!("  --backend tpm --backend-config device_path=/dev/tpm0");
+    println!("  --backend aws-kms --backend-config key_arn=<HMAC key ARN> --salt-hex <salt>");
     println!("  --backend hsm --backend-config pkcs11_lib=/usr/lib/libpkcs11.so");
 
     Ok(())
@@ -312,21 +418,45 @@ fn list_audio_devices() -> Result<()> {
 /// Create a backend from CLI arguments
 fn create_backend_from_args(args: &Args) -> Result<Box<dyn KeyBackend>> {
     let registry = BackendRegistry::new();
-    registry.create_backend(&args.backend).with_context(|| {
-        format!(
-            "Failed to create '{}' backend. Use --list-backends to see available options",
-            args.backend
-        )
-    })
+    registry
+        .create_backend_with_config(&args.backend, &args.backend_config)
+        .with_context(|| {
+            format!(
+                "Failed to create '{}' backend. Use --list-backends to see available options",
+                args.backend
+            )
+        })
 }
 
 /// Parse a hex string into a 32-byte array
 fn parse_key_hex(s: &str) -> Result<[u8; 32]> {
-    let bytes = hex::decode(s).context("key_hex: not valid hex")?;
-    anyhow::ensure!(bytes.len() == 32, "key_hex must be 32 bytes (64 hex chars)");
-    let mut out = [0u8; 32];
-    out.copy_from_slice(&bytes);
-    Ok(out)
+    let mut bytes = hex::decode(s).context("key_hex: not valid hex")?;
+    let out: Result<[u8; 32], _> = bytes.as_slice().try_into();
+    bytes.zeroize();
+    out.map_err(|_| anyhow!("key_hex must be 32 bytes (64 hex chars)"))
+}
+
+/// Select the Ed25519 key that signs chunk manifests
+///
+/// Without `--signing-key` or `--signing-key-hex` a fresh key is generated for
+/// this run only, so its manifests cannot be attributed to a stable identity.
+fn select_signing_key(args: &Args) -> Result<SigningKey> {
+    let mut key_hex = match (&args.signing_key, &args.signing_key_hex) {
+        (Some(path), _) => std::fs::read_to_string(path).context("read signing key")?,
+        (None, Some(key_hex)) => key_hex.clone(),
+        (None, None) => {
+            eprintln!(
+                "⚠ No --signing-key given: signing manifests with an ephemeral key for this run only"
+            );
+            return Ok(SigningKey::generate(&mut OsRng));
+        }
+    };
+    let seed = parse_key_hex(key_hex.trim());
+    key_hex.zeroize();
+    let mut seed = seed.context("invalid signing key")?;
+    let signing = SigningKey::from_bytes(&seed);
+    seed.zeroize();
+    Ok(signing)
 }
 
 /// Select the AES key to use for encryption/decryption using the new backend system
@@ -389,24 +519,78 @@ fn select_aes_key_with_backend(args: &Args, mode: Mode) -> Result<[u8; 32]> {
     }
 }
 
+/// Whether a path argument selects the standard stream (`-` or omitted)
+fn is_std_stream(path: Option<&PathBuf>) -> bool {
+    match path {
+        Some(path) => path.as_os_str() == "-",
+        None => true,
+    }
+}
+
+/// Open `path` for reading, or stdin when it is `-` or omitted
+fn open_input(path: Option<&PathBuf>, what: &'static str) -> Result<BufReader<Box<dyn Read>>> {
+    let reader: Box<dyn Read> = match path {
+        Some(path) if !is_std_stream(Some(path)) => Box::new(File::open(path).context(what)?),
+        _ => Box::new(std::io::stdin().lock()),
+    };
+    Ok(BufReader::new(reader))
+}
+
+/// Create `path` for writing, or stdout when it is `-` or omitted
+fn create_output(
+    path: Option<&PathBuf>,
+    what: &'static str,
+) -> Result<BufWriter<Box<dyn Write + Send>>> {
+    let writer: Box<dyn Write + Send> = match path {
+        Some(path) if !is_std_stream(Some(path)) => Box::new(File::create(path).context(what)?),
+        _ => Box::new(std::io::stdout()),
+    };
+    Ok(BufWriter::new(writer))
+}
+
+/// Size of `path` when it names a regular file
+fn regular_file_len(path: Option<&PathBuf>) -> Option<u64> {
+    let path = path.filter(|path| !is_std_stream(Some(path)))?;
+    std::fs::metadata(path)
+        .ok()
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+}
+
+/// Progress bar for `--progress`, drawn on stderr so stdout pipes stay clean
+///
+/// Hidden unless requested and stderr is a terminal. Inputs of unknown size get a
+/// spinner with a byte counter instead of a bar.
+fn progress_bar(args: &Args, total_bytes: Option<u64>, message: &'static str) -> ProgressBar {
+    if !args.progress || !std::io::stderr().is_terminal() {
+        return ProgressBar::hidden();
+    }
+    let (bar, template) = match total_bytes {
+        Some(total) => (
+            ProgressBar::new(total),
+            "{msg} [{bar:40}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})",
+        ),
+        None => (
+            ProgressBar::new_spinner(),
+            "{msg} {spinner} {bytes} ({bytes_per_sec})",
+        ),
+    };
+    let style = ProgressStyle::with_template(template).expect("valid progress template");
+    bar.set_style(style.progress_chars("=> "));
+    bar.set_message(message);
+    bar
+}
+
 /// Decrypt the envelope (header + records)
 fn decrypt_envelope(args: &Args) -> Result<()> {
     // key
     let mut key_bytes = select_aes_key_with_backend(args, Mode::Decrypt)?;
     let key_array: [u8; 32] = key_bytes.as_slice().try_into()?;
-    let cipher = Aes256Gcm::new((&key_array).into());
 
     // io
-    let input = args
-        .input
-        .as_ref()
-        .ok_or_else(|| anyhow!("--input is required for --decrypt"))?;
-    let out = args
-        .out
-        .as_ref()
-        .ok_or_else(|| anyhow!("--out is required for --decrypt"))?;
-    let mut r = BufReader::new(File::open(input).context("open envelope")?);
-    let mut w = BufWriter::new(File::create(out).context("create output")?);
+    let mut r = open_input(args.input.as_ref(), "open envelope")?;
+    let mut w = create_output(args.out.as_ref(), "create output")?;
+    let progress = progress_bar(args, regular_file_len(args.input.as_ref()), "decrypting");
 
     // preamble
     let mut magic = [0u8; 4];
@@ -431,6 +615,9 @@ fn decrypt_envelope(args: &Args) -> Result<()> {
     let fh =
         sealedge_core::FileHeader::from_bytes(&header_arr).context("failed to parse FileHeader")?;
 
+    // the header decides the cipher, not the command line
+    let cipher = StreamCipher::new(fh.aead_alg, &key_array)?;
+
     // extract the nonce prefix from the parsed header
     let stream_nonce_prefix = fh.nonce_prefix;
 
@@ -446,10 +633,18 @@ fn decrypt_envelope(args: &Args) -> Result<()> {
         sealedge_core::format::MAX_CHUNK_SIZE
     );
 
+    // requested plaintext byte range [range_start, range_end)
+    let range_start = args.offset.unwrap_or(0);
+    let range_end = args
+        .length
+        .map_or(u64::MAX, |length| range_start.saturating_add(length));
+    let mut plaintext_pos: u64 = 0;
+
     // records
     let mut total_out = 0usize;
     let mut expected_seq: u64 = 1;
     let mut manifest_data_type: Option<DataType> = None;
+    let mut wav: Option<WavSpec> = None;
     let mut record_count: u64 = 0;
     let mut stream_size_bytes: u64 = 0;
 
@@ -535,6 +730,17 @@ fn decrypt_envelope(args: &Args) -> Result<()> {
             if args.verbose {
                 print_format_info(&m.data_type);
             }
+
+            if args.wav {
+                wav = WavSpec::from_data_type(&m.data_type);
+                match &wav {
+                    // Sizes are unknown until the last record; patched below when possible
+                    Some(spec) => w
+                        .write_all(&spec.header(u32::MAX))
+                        .context("write WAV header")?,
+                    None => eprintln!("⚠ --wav ignored: archive is not PCM audio, writing raw"),
+                }
+            }
         }
 
         // verify invariants
@@ -561,6 +767,17 @@ fn decrypt_envelope(args: &Args) -> Result<()> {
             fh.chunk_size
         );
 
+        // the signed chunk_len places this record in the plaintext without decrypting it
+        let chunk_start = plaintext_pos;
+        plaintext_pos += u64::from(m.chunk_len);
+        if chunk_start >= range_end {
+            break;
+        }
+        if plaintext_pos <= range_start {
+            progress.inc(bincode::serialized_size(&rec).unwrap_or(0));
+            continue;
+        }
+
         // decrypt
         let mh = blake3::hash(&rec.sm.manifest);
         let aad = build_aad(
@@ -577,21 +794,21 @@ fn decrypt_envelope(args: &Args) -> Result<()> {
             .map_err(|_| anyhow!("Invalid nonce length"))?;
         let pt = cipher
             .decrypt(
-                nonce_array.into(),
+                nonce_array,
                 Payload {
                     msg: &rec.ct,
                     aad: &aad,
                 },
             )
-            .map_err(|_| anyhow!("AES-GCM decrypt/verify failed"))?;
+            .map_err(|_| anyhow!("{} decrypt/verify failed", cipher.name()))?;
 
-        // Validate decrypted length matches manifest expectation
-        anyhow::ensure!(
-            pt.len() == m.chunk_len as usize,
-            "decrypted length {} != manifest chunk_len {}",
-            pt.len(),
-            m.chunk_len
-        );
+        // v2 manifests announce zstd-compressed chunks; expansion is capped at the
+        // header's chunk size and the result must match the manifest's chunk_len
+        let pt = format::ChunkCompression::from_manifest_version(m.v)?.decompress(
+            pt,
+            m.chunk_len,
+            fh.chunk_size,
+        )?;
 
         // pt hash
         let pt_hash_rx = blake3::hash(&pt);
@@ -608,14 +825,30 @@ fn decrypt_envelope(args: &Args) -> Result<()> {
             sealedge_core::format::MAX_STREAM_SIZE_BYTES
         );
 
-        // write
-        w.write_all(&pt).context("write plaintext")?;
+        // write only the part of the chunk inside the requested range
+        let from = range_start.saturating_sub(chunk_start) as usize;
+        let to = (range_end.min(plaintext_pos) - chunk_start) as usize;
+        let pt = &pt[from..to];
+        w.write_all(pt).context("write plaintext")?;
         total_out += pt.len();
+        progress.inc(bincode::serialized_size(&rec).unwrap_or(0));
     }
 
+    progress.finish_and_clear();
+    anyhow::ensure!(
+        range_start <= plaintext_pos,
+        "offset {} is past the end of the plaintext ({} bytes)",
+        range_start,
+        plaintext_pos
+    );
     w.flush().context("flush plaintext")?;
+    drop(w);
     key_bytes.zeroize();
 
+    if let Some(spec) = &wav {
+        finalize_wav(args.out.as_ref(), spec, total_out as u64)?;
+    }
+
     // Provide format-aware completion message
     provide_completion_message(manifest_data_type.as_ref(), total_out, args);
     Ok(())
@@ -653,6 +886,10 @@ fn inspect_envelope(args: &Args) -> Result<()> {
     let hh = blake3::hash(&sh.header);
     anyhow::ensure!(hh.as_bytes() == &sh.header_hash, "header_hash mismatch");
 
+    if args.all {
+        return print_records_jsonl(&mut r);
+    }
+
     println!("Sealedge Archive Information:");
     println!("  File: {}", input.display());
     println!("  Format Version: {}", fh.version);
@@ -711,6 +948,48 @@ fn inspect_envelope(args: &Args) -> Result<()> {
     Ok(())
 }
 
+/// Emit one JSON object per record manifest (JSON Lines) for piping into jq
+fn print_records_jsonl<R: Read>(r: &mut R) -> Result<()> {
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    let mut record_count: u64 = 0;
+
+    loop {
+        let rec: Record = match deserialize_from(&mut *r) {
+            Ok(x) => x,
+            Err(err) => {
+                if let bincode::ErrorKind::Io(ref e) = *err {
+                    if e.kind() == std::io::ErrorKind::UnexpectedEof {
+                        break;
+                    }
+                }
+                return Err(err).context("read record");
+            }
+        };
+
+        record_count += 1;
+        anyhow::ensure!(
+            record_count <= sealedge_core::format::MAX_RECORDS_PER_STREAM,
+            "stream exceeds maximum record count: {} > {}",
+            record_count,
+            sealedge_core::format::MAX_RECORDS_PER_STREAM
+        );
+
+        let m: Manifest = bincode::deserialize(&rec.sm.manifest).context("manifest decode")?;
+        let line = serde_json::json!({
+            "sequence": m.seq,
+            "data_type": m.data_type,
+            "chunk_len": m.chunk_len,
+            "signer": hex::encode(&rec.sm.pubkey),
+        });
+        serde_json::to_writer(&mut out, &line).context("write manifest line")?;
+        writeln!(out).context("write manifest line")?;
+    }
+
+    out.flush().context("flush manifest lines")?;
+    Ok(())
+}
+
 fn print_manifest_info(manifest: &Manifest) {
     println!("  Sequence Start: {}", manifest.seq);
 
@@ -733,11 +1012,17 @@ fn print_manifest_info(manifest: &Manifest) {
             println!("  Sample Rate: {} Hz", sample_rate);
             println!("  Channels: {}", channels);
             println!("  Format: {:?}", format);
-            println!("  Output Behavior: Raw PCM data (requires conversion for playback)");
-            println!(
-                "  Conversion Command: ffmpeg -f f32le -ar {} -ac {} -i output.raw output.wav",
-                sample_rate, channels
-            );
+            if *format == AudioFormat::Opus {
+                println!(
+                    "  Output Behavior: Opus packets (decode with an Opus decoder for playback)"
+                );
+            } else {
+                println!("  Output Behavior: Raw PCM data (requires conversion for playback)");
+                println!(
+                    "  Conversion Command: ffmpeg -f f32le -ar {} -ac {} -i output.raw output.wav",
+                    sample_rate, channels
+                );
+            }
         }
         DataType::Video {
             width,
@@ -781,7 +1066,11 @@ fn print_format_info(data_type: &DataType) {
             eprintln!("  Sample Rate: {} Hz", sample_rate);
             eprintln!("  Channels: {}", channels);
             eprintln!("  Format: {:?}", format);
-            eprintln!("⚠ Output: Raw PCM data (requires conversion)");
+            if *format == AudioFormat::Opus {
+                eprintln!("⚠ Output: Opus packets (requires an Opus decoder)");
+            } else {
+                eprintln!("⚠ Output: Raw PCM data (requires conversion)");
+            }
         }
         DataType::Video {
             width,
@@ -804,6 +1093,93 @@ fn print_format_info(data_type: &DataType) {
     }
 }
 
+/// Length of the canonical RIFF/WAVE header written by `--wav`
+const WAV_HEADER_LEN: usize = 44;
+
+/// WAV `fmt ` fields for a live-audio archive
+struct WavSpec {
+    format_tag: u16,
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+}
+
+impl WavSpec {
+    const FORMAT_PCM: u16 = 1;
+    const FORMAT_IEEE_FLOAT: u16 = 3;
+
+    /// `None` unless the manifest describes audio in a sample format WAV can carry
+    fn from_data_type(data_type: &DataType) -> Option<Self> {
+        let DataType::Audio {
+            sample_rate,
+            channels,
+            format,
+        } = data_type
+        else {
+            return None;
+        };
+        let (format_tag, bits_per_sample) = match format {
+            AudioFormat::F32Le => (Self::FORMAT_IEEE_FLOAT, 32),
+            AudioFormat::I16Le => (Self::FORMAT_PCM, 16),
+            AudioFormat::I24Le => (Self::FORMAT_PCM, 24),
+            AudioFormat::Other(_) | AudioFormat::Opus => return None,
+        };
+        Some(Self {
+            format_tag,
+            channels: *channels,
+            sample_rate: *sample_rate,
+            bits_per_sample,
+        })
+    }
+
+    fn block_align(&self) -> u16 {
+        self.channels * self.bits_per_sample / 8
+    }
+
+    /// Header for `data_len` bytes of samples; `u32::MAX` marks an unknown length
+    fn header(&self, data_len: u32) -> [u8; WAV_HEADER_LEN] {
+        let mut h = [0u8; WAV_HEADER_LEN];
+        h[0..4].copy_from_slice(b"RIFF");
+        h[4..8].copy_from_slice(&data_len.saturating_add(36).to_le_bytes());
+        h[8..12].copy_from_slice(b"WAVE");
+        h[12..16].copy_from_slice(b"fmt ");
+        h[16..20].copy_from_slice(&16u32.to_le_bytes());
+        h[20..22].copy_from_slice(&self.format_tag.to_le_bytes());
+        h[22..24].copy_from_slice(&self.channels.to_le_bytes());
+        h[24..28].copy_from_slice(&self.sample_rate.to_le_bytes());
+        let byte_rate = self.sample_rate * u32::from(self.block_align());
+        h[28..32].copy_from_slice(&byte_rate.to_le_bytes());
+        h[32..34].copy_from_slice(&self.block_align().to_le_bytes());
+        h[34..36].copy_from_slice(&self.bits_per_sample.to_le_bytes());
+        h[36..40].copy_from_slice(b"data");
+        h[40..44].copy_from_slice(&data_len.to_le_bytes());
+        h
+    }
+}
+
+/// Rewrite the WAV header with the real sizes once all samples are written.
+///
+/// Stdout cannot be rewound, so piped output keeps the streaming placeholder sizes.
+fn finalize_wav(out: Option<&PathBuf>, spec: &WavSpec, data_len: u64) -> Result<()> {
+    let Some(path) = out.filter(|path| !is_std_stream(Some(path))) else {
+        return Ok(());
+    };
+    let Some(data_len) = u32::try_from(data_len)
+        .ok()
+        .filter(|len| len.checked_add(36).is_some())
+    else {
+        eprintln!("⚠ Audio exceeds the 4 GiB WAV limit; header sizes left unset");
+        return Ok(());
+    };
+    let mut f = std::fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .context("reopen output for WAV header")?;
+    f.write_all(&spec.header(data_len))
+        .context("write WAV header")?;
+    Ok(())
+}
+
 fn provide_completion_message(data_type: Option<&DataType>, total_bytes: usize, args: &Args) {
     eprintln!("✔ Decrypt complete. Wrote {} bytes.", total_bytes);
 
@@ -818,13 +1194,18 @@ fn provide_completion_message(data_type: Option<&DataType>, total_bytes: usize,
             DataType::Audio {
                 sample_rate,
                 channels,
-                format: _,
+                format,
             } => {
-                if args.force_raw {
+                if *format == AudioFormat::Opus {
+                    eprintln!("♪ Live audio decrypted to Opus packets (codec: Opus)");
+                    eprintln!("  Decode each chunk with an Opus decoder before playback");
+                } else if args.force_raw {
                     eprintln!("⚠ Raw PCM output (--force-raw specified)");
+                } else if args.wav && WavSpec::from_data_type(data_type).is_some() {
+                    eprintln!("♪ Live audio decrypted to WAV");
                 } else {
                     eprintln!("♪ Live audio decrypted to raw PCM format");
-                    eprintln!("  To convert to playable audio:");
+                    eprintln!("  Re-run with --wav for a playable file, or convert with:");
                     eprintln!(
                         "   ffmpeg -f f32le -ar {} -ac {} -i {} output.wav",
                         sample_rate,
@@ -898,10 +1279,11 @@ fn determine_data_type(input_source: &InputSource, args: &Args) -> DataType {
 
             DataType::File { mime_type }
         }
+        InputSource::Stdin => DataType::File { mime_type: None },
         InputSource::LiveAudio => DataType::Audio {
             sample_rate: args.sample_rate,
             channels: args.channels,
-            format: AudioFormat::F32Le, // Current implementation uses f32 samples
+            format: sealedge_core::AudioEncoding::from(args.audio_encoding).audio_format(),
         },
     }
 }
@@ -909,6 +1291,11 @@ fn determine_data_type(input_source: &InputSource, args: &Args) -> DataType {
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    if let Some(Command::Verify(verify_args)) = &args.command {
+        let code = verify::run(verify_args)?;
+        std::process::exit(code);
+    }
+
     // Handle --list-backends option
     if args.list_backends {
         return list_backends();
@@ -934,6 +1321,27 @@ fn main() -> Result<()> {
         }
     }
 
+    if let Some([old, new]) = args.rotate_passphrase.as_deref() {
+        #[cfg(feature = "keyring")]
+        {
+            let backend = KeyringBackend::new().context("Failed to create keyring backend")?;
+            backend
+                .rotate_passphrase(old, new)
+                .context("Passphrase rotation failed; the stored passphrase is unchanged")?;
+            println!("Passphrase rotated in system keyring");
+            // Salts are supplied per file, so existing envelopes cannot be re-wrapped here
+            eprintln!(
+                "Note: files encrypted with --use-keyring must be decrypted with the old passphrase and re-encrypted"
+            );
+            return Ok(());
+        }
+        #[cfg(not(feature = "keyring"))]
+        {
+            let _ = (old, new);
+            anyhow::bail!("Keyring support requires the 'keyring' feature. Build with: cargo build --features keyring");
+        }
+    }
+
     // Handle --inspect option
     if args.inspect {
         return inspect_envelope(&args);
@@ -950,30 +1358,37 @@ fn main() -> Result<()> {
         "chunk too large for header"
     );
 
-    // Determine input source: file or live audio
+    // Determine input source: file, stdin or live audio
     let input_source = if args.live_capture {
         InputSource::LiveAudio
     } else {
-        let input = args
-            .input
-            .as_ref()
-            .ok_or_else(|| anyhow!("--input is required when not using --live-capture"))?;
-        InputSource::File(input.clone())
+        match &args.input {
+            Some(path) if !is_std_stream(Some(path)) => InputSource::File(path.clone()),
+            _ => InputSource::Stdin,
+        }
     };
 
-    // outputs
-    let out = args
-        .out
+    // outputs: plaintext and envelope can't share stdout
+    let envelope_to_stdout = args
+        .envelope
         .as_ref()
-        .ok_or_else(|| anyhow!("--out is required"))?;
-    let mut fout = BufWriter::new(File::create(out).context("create output")?);
+        .is_some_and(|p| is_std_stream(Some(p)));
+    anyhow::ensure!(
+        args.no_plaintext || !is_std_stream(args.out.as_ref()) || !envelope_to_stdout,
+        "plaintext and envelope would both be written to stdout; pass --out <file> or --no-plaintext"
+    );
+    let fout = if args.no_plaintext {
+        None
+    } else {
+        Some(create_output(args.out.as_ref(), "create output")?)
+    };
 
     // keys
     let mut key_bytes = select_aes_key_with_backend(&args, Mode::Encrypt)?;
     let key_array: [u8; 32] = key_bytes.as_slice().try_into()?;
-    let cipher = Aes256Gcm::new((&key_array).into());
-    let signing = SigningKey::generate(&mut OsRng); // demo only
-    let verify: VerifyingKey = signing.verifying_key();
+    let aead: format::AeadAlgorithm = args.aead.into();
+    let cipher = StreamCipher::new(aead as u8, &key_array)?;
+    let signing = select_signing_key(&args)?;
 
     // header fields (randomly generated per session)
     let mut nonce_prefix = [0u8; 4];
@@ -993,7 +1408,7 @@ fn main() -> Result<()> {
 
     let header = FileHeader {
         version: VERSION,
-        aead_alg: sealedge_core::format::AeadAlgorithm::Aes256Gcm as u8,
+        aead_alg: aead as u8,
         sig_alg: sealedge_core::format::SignatureAlgorithm::Ed25519 as u8,
         hash_alg: sealedge_core::format::HashAlgorithm::Blake3 as u8,
         kdf_alg: sealedge_core::format::KdfAlgorithm::Pbkdf2Sha256 as u8,
@@ -1008,9 +1423,7 @@ fn main() -> Result<()> {
 
     // optional envelope writer
     let mut env_out = if let Some(path) = &args.envelope {
-        Some(BufWriter::new(
-            File::create(path).context("create envelope")?,
-        ))
+        Some(create_output(Some(path), "create envelope")?)
     } else {
         None
     };
@@ -1024,19 +1437,12 @@ fn main() -> Result<()> {
         write_stream_header(w, &sh)?;
     }
 
-    // loop
-    let mut buf = vec![0u8; args.chunk];
-    let mut total_in = 0usize;
-    let mut total_out = 0usize;
-    let mut seq: u64 = 0;
-    let mut nonce_bytes = [0u8; NONCE_LEN];
-
     // Initialize input source
     let mut input_reader: Box<dyn InputReader> = match &input_source {
-        InputSource::File(path) => {
-            let fin = BufReader::new(File::open(path).context("open input")?);
-            Box::new(FileInputReader::new(fin))
-        }
+        InputSource::File(_) | InputSource::Stdin => Box::new(StreamInputReader::new(open_input(
+            args.input.as_ref(),
+            "open input",
+        )?)),
         InputSource::LiveAudio => {
             #[cfg(feature = "audio")]
             {
@@ -1046,6 +1452,7 @@ fn main() -> Result<()> {
                     channels: args.channels,
                     chunk_duration_ms: args.chunk_duration_ms,
                     buffer_size: 8192,
+                    encoding: args.audio_encoding.into(),
                 };
                 let capture = AudioCapture::new(audio_config)?;
                 Box::new(AudioInputReader::new(capture)?)
@@ -1060,6 +1467,14 @@ fn main() -> Result<()> {
     };
 
     // loop to process input chunks
+    let progress = progress_bar(
+        &args,
+        match &input_source {
+            InputSource::File(path) => regular_file_len(Some(path)),
+            InputSource::Stdin | InputSource::LiveAudio => None,
+        },
+        "encrypting",
+    );
     let start_time = std::time::Instant::now();
     let max_duration = if args.max_duration > 0 {
         Some(Duration::from_secs(args.max_duration))
@@ -1067,83 +1482,170 @@ fn main() -> Result<()> {
         None
     };
 
-    loop {
-        // Check time limit for live audio
-        if let Some(max_dur) = max_duration {
-            if start_time.elapsed() >= max_dur {
-                println!("● Maximum duration reached, stopping capture");
-                break;
+    let mut buf = vec![0u8; args.chunk];
+    let mut next_chunk = || -> Result<Option<Vec<u8>>> {
+        loop {
+            // Check time limit for live audio
+            if let Some(max_dur) = max_duration {
+                if start_time.elapsed() >= max_dur {
+                    eprintln!("● Maximum duration reached, stopping capture");
+                    return Ok(None);
+                }
+            }
+
+            let n = input_reader.read_chunk(&mut buf)?;
+            if n > 0 {
+                return Ok(Some(buf[..n].to_vec()));
+            }
+            // For live audio, keep trying until the time limit; otherwise EOF
+            if !(matches!(input_source, InputSource::LiveAudio) && max_duration.is_some()) {
+                return Ok(None);
             }
         }
+    };
 
-        let n = input_reader.read_chunk(&mut buf)?;
-        if n == 0 {
-            // For live audio, continue if within time limit
-            if matches!(input_source, InputSource::LiveAudio) {
-                if max_duration.is_some() {
-                    continue; // Keep trying until time limit
-                } else {
-                    break; // No time limit, exit on no data
-                }
-            } else {
-                // File EOF
-                break;
+    let encryptor = ChunkEncryptor {
+        cipher,
+        signing,
+        header,
+        header_hash: *header_hash.as_bytes(),
+        data_type: determine_data_type(&input_source, &args),
+        verify_each: args.verify_each,
+    };
+    let mut sink = ChunkSink {
+        plaintext: fout,
+        envelope: env_out,
+        progress,
+        total_bytes: 0,
+    };
+
+    let jobs = args.jobs.max(1);
+    let result = if jobs == 1 {
+        encrypt_sequential(&encryptor, &mut next_chunk, &mut sink).map(|()| sink)
+    } else {
+        encrypt_parallel(&encryptor, jobs, &mut next_chunk, sink)
+    };
+    key_bytes.zeroize();
+    let mut sink = result?;
+    sink.finish()?;
+
+    // status and exit
+    eprintln!(
+        "Round-trip complete. Read {} bytes, wrote {} bytes.",
+        sink.total_bytes, sink.total_bytes
+    );
+    Ok(())
+}
+
+/// Chunk AEAD for a stream, chosen by the header's `aead_alg`
+enum StreamCipher {
+    Aes256Gcm(Box<Aes256Gcm>),
+    ChaCha20Poly1305(Box<ChaCha20Poly1305>),
+}
+
+impl StreamCipher {
+    fn new(aead_alg: u8, key: &[u8; 32]) -> Result<Self> {
+        match format::AeadAlgorithm::try_from(aead_alg) {
+            Ok(format::AeadAlgorithm::Aes256Gcm) => {
+                Ok(Self::Aes256Gcm(Box::new(Aes256Gcm::new(key.into()))))
             }
+            Ok(format::AeadAlgorithm::ChaCha20Poly1305) => Ok(Self::ChaCha20Poly1305(Box::new(
+                ChaCha20Poly1305::new(key.into()),
+            ))),
+            Ok(other) => Err(anyhow!(
+                "AEAD algorithm {:?} is not supported for chunk streams",
+                other
+            )),
+            Err(_) => Err(anyhow!("unsupported AEAD algorithm id {}", aead_alg)),
         }
+    }
 
-        seq = seq.checked_add(1).ok_or_else(|| anyhow!("seq overflow"))?;
-        nonce_bytes[..4].copy_from_slice(&header.nonce_prefix);
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Aes256Gcm(_) => "AES-GCM",
+            Self::ChaCha20Poly1305(_) => "ChaCha20-Poly1305",
+        }
+    }
+
+    fn encrypt(&self, nonce: &[u8; NONCE_LEN], payload: Payload) -> Result<Vec<u8>, aead::Error> {
+        match self {
+            Self::Aes256Gcm(c) => c.encrypt(nonce.into(), payload),
+            Self::ChaCha20Poly1305(c) => c.encrypt(nonce.into(), payload),
+        }
+    }
+
+    fn decrypt(&self, nonce: &[u8; NONCE_LEN], payload: Payload) -> Result<Vec<u8>, aead::Error> {
+        match self {
+            Self::Aes256Gcm(c) => c.decrypt(nonce.into(), payload),
+            Self::ChaCha20Poly1305(c) => c.decrypt(nonce.into(), payload),
+        }
+    }
+}
+
+/// Per-stream state for sealing chunks; shared read-only by the `--jobs` workers
+struct ChunkEncryptor {
+    cipher: StreamCipher,
+    signing: SigningKey,
+    header: FileHeader,
+    header_hash: [u8; 32],
+    data_type: DataType,
+    verify_each: bool,
+}
+
+impl ChunkEncryptor {
+    /// Sign and encrypt one chunk; the nonce is `nonce_prefix || seq`
+    fn seal(&self, seq: u64, chunk: &[u8]) -> Result<Record> {
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        nonce_bytes[..4].copy_from_slice(&self.header.nonce_prefix);
         nonce_bytes[4..].copy_from_slice(&seq.to_be_bytes());
-        let nonce = (&nonce_bytes).into();
+        let nonce = &nonce_bytes;
 
-        let pt_hash = blake3::hash(&buf[..n]);
+        let pt_hash = blake3::hash(chunk);
         let ts_ms = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis() as u64;
 
-        // Determine data type for manifest
-        let data_type = determine_data_type(&input_source, &args);
-
         let m = Manifest {
             v: 1,
             ts_ms,
             seq,
-            header_hash: *header_hash.as_bytes(),
+            header_hash: self.header_hash,
             pt_hash: *pt_hash.as_bytes(),
-            key_id: header.key_id,
+            key_id: self.header.key_id,
             ai_used: false,
             model_ids: vec![],
-            data_type,
-            chunk_len: n as u32, // Bind actual chunk length to AAD
+            data_type: self.data_type.clone(),
+            chunk_len: chunk.len() as u32, // Bind actual chunk length to AAD
         };
 
         let m_bytes = bincode::serialize(&m).expect("manifest serialize");
-        let sig: Signature = format::sign_manifest_with_domain(&signing, &m_bytes);
+        let sig: Signature = format::sign_manifest_with_domain(&self.signing, &m_bytes);
         let sm = SignedManifest {
             manifest: m_bytes.clone(),
             sig: sig.to_bytes().to_vec(),
-            pubkey: verify.to_bytes().to_vec(),
+            pubkey: self.signing.verifying_key().to_bytes().to_vec(),
         };
 
         let mhash = blake3::hash(&m_bytes);
         let aad = build_aad(
-            header_hash.as_bytes(),
+            &self.header_hash,
             seq,
             &nonce_bytes,
             mhash.as_bytes(),
             m.chunk_len,
         );
 
-        let ct = cipher
+        let ct = self
+            .cipher
             .encrypt(
                 nonce,
                 Payload {
-                    msg: &buf[..n],
+                    msg: chunk,
                     aad: &aad,
                 },
             )
-            .map_err(|_| anyhow!("AES-GCM encrypt failed"))?;
+            .map_err(|_| anyhow!("{} encrypt failed", self.cipher.name()))?;
 
         // debug-only tamper check
         #[cfg(debug_assertions)]
@@ -1152,7 +1654,7 @@ fn main() -> Result<()> {
                 let mut ct_bad = ct.clone();
                 ct_bad[0] ^= 0x01;
                 debug_assert!(
-                    cipher
+                    self.cipher
                         .decrypt(
                             nonce,
                             Payload {
@@ -1166,71 +1668,186 @@ fn main() -> Result<()> {
             }
         }
 
-        // verify manifest + round-trip decrypt (sanity)
-        let m2: Manifest = bincode::deserialize(&sm.manifest).context("manifest decode")?;
-        let pubkey_arr: [u8; 32] = sm
+        let rec = Record {
+            seq,
+            nonce: nonce_bytes,
+            sm,
+            ct,
+        };
+        if self.verify_each {
+            self.verify_record(&rec, &m)?;
+        }
+        Ok(rec)
+    }
+
+    /// Round-trip self-check (`--verify-each`): verify the manifest and decrypt the record
+    fn verify_record(&self, rec: &Record, m: &Manifest) -> Result<()> {
+        let m2: Manifest = bincode::deserialize(&rec.sm.manifest).context("manifest decode")?;
+        let pubkey_arr: [u8; 32] = rec
+            .sm
             .pubkey
             .as_slice()
             .try_into()
             .context("pubkey length != 32")?;
-        let sig_arr: [u8; 64] = sm.sig.as_slice().try_into().context("sig len != 64")?;
+        let sig_arr: [u8; 64] = rec.sm.sig.as_slice().try_into().context("sig len != 64")?;
         let verifying_key = VerifyingKey::from_bytes(&pubkey_arr).context("bad pubkey")?;
         format::verify_manifest_with_domain(
             &verifying_key,
-            &sm.manifest,
+            &rec.sm.manifest,
             &Signature::from_bytes(&sig_arr),
         )
         .context("manifest signature verify failed")?;
 
+        let mhash = blake3::hash(&rec.sm.manifest);
         let aad_rx = build_aad(
-            header_hash.as_bytes(),
-            seq,
-            &nonce_bytes,
+            &self.header_hash,
+            rec.seq,
+            &rec.nonce,
             mhash.as_bytes(),
             m2.chunk_len,
         );
-        let pt = cipher
+        let pt = self
+            .cipher
             .decrypt(
-                (&nonce_bytes).into(),
+                &rec.nonce,
                 Payload {
-                    msg: &ct,
+                    msg: &rec.ct,
                     aad: &aad_rx,
                 },
             )
-            .map_err(|_| anyhow!("AES-GCM decrypt/verify failed"))?;
+            .map_err(|_| anyhow!("{} decrypt/verify failed", self.cipher.name()))?;
         let pt_hash_rx = blake3::hash(&pt);
         anyhow::ensure!(pt_hash_rx.as_bytes() == &m.pt_hash, "pt hash mismatch");
+        Ok(())
+    }
+}
 
-        if !args.no_plaintext {
-            fout.write_all(&pt).context("write out")?;
-        }
+/// Ordered output for sealed chunks: plaintext copy, envelope records and progress
+struct ChunkSink {
+    plaintext: Option<BufWriter<Box<dyn Write + Send>>>,
+    envelope: Option<BufWriter<Box<dyn Write + Send>>>,
+    progress: ProgressBar,
+    total_bytes: usize,
+}
 
-        if let Some(w) = env_out.as_mut() {
-            let rec = Record {
-                seq,
-                nonce: nonce_bytes,
-                sm,
-                ct,
-            };
-            serialize_into(w, &rec).context("write envelope record")?;
+impl ChunkSink {
+    fn write(&mut self, chunk: &[u8], rec: &Record) -> Result<()> {
+        if let Some(w) = self.plaintext.as_mut() {
+            w.write_all(chunk).context("write out")?;
         }
-
-        total_in += n;
-        total_out += pt.len();
+        if let Some(w) = self.envelope.as_mut() {
+            serialize_into(w, rec).context("write envelope record")?;
+        }
+        self.total_bytes += chunk.len();
+        self.progress.inc(chunk.len() as u64);
+        Ok(())
     }
 
-    key_bytes.zeroize();
-    if !args.no_plaintext {
-        fout.flush().context("flush plaintext")?;
-    }
-    if let Some(w) = env_out.as_mut() {
-        w.flush().context("flush envelope")?;
+    fn finish(&mut self) -> Result<()> {
+        self.progress.finish_and_clear();
+        if let Some(w) = self.plaintext.as_mut() {
+            w.flush().context("flush plaintext")?;
+        }
+        if let Some(w) = self.envelope.as_mut() {
+            w.flush().context("flush envelope")?;
+        }
+        Ok(())
     }
+}
 
-    // status and exit
-    eprintln!(
-        "Round-trip complete. Read {} bytes, wrote {} bytes.",
-        total_in, total_out
-    );
+fn encrypt_sequential(
+    encryptor: &ChunkEncryptor,
+    next_chunk: &mut dyn FnMut() -> Result<Option<Vec<u8>>>,
+    sink: &mut ChunkSink,
+) -> Result<()> {
+    let mut seq: u64 = 0;
+    while let Some(chunk) = next_chunk()? {
+        seq = seq.checked_add(1).ok_or_else(|| anyhow!("seq overflow"))?;
+        let rec = encryptor.seal(seq, &chunk)?;
+        sink.write(&chunk, &rec)?;
+    }
     Ok(())
 }
+
+/// Encrypt chunks on `jobs` worker threads
+///
+/// The calling thread reads and numbers chunks, the workers seal them in any order,
+/// and a single writer thread reorders the records by sequence number so the
+/// output is laid out exactly as in the sequential path.
+fn encrypt_parallel(
+    encryptor: &ChunkEncryptor,
+    jobs: usize,
+    next_chunk: &mut dyn FnMut() -> Result<Option<Vec<u8>>>,
+    mut sink: ChunkSink,
+) -> Result<ChunkSink> {
+    use std::collections::BTreeMap;
+    use std::sync::mpsc::sync_channel;
+    use std::sync::{Arc, Mutex};
+
+    std::thread::scope(|scope| {
+        let (work_tx, work_rx) = sync_channel::<(u64, Vec<u8>)>(jobs * 2);
+        let (done_tx, done_rx) = sync_channel::<Result<(u64, Vec<u8>, Record)>>(jobs * 2);
+        // Workers own the receiver, so the reader stops once they have all exited
+        let work_rx = Arc::new(Mutex::new(work_rx));
+
+        for _ in 0..jobs {
+            let work_rx = Arc::clone(&work_rx);
+            let done_tx = done_tx.clone();
+            scope.spawn(move || loop {
+                let job = match work_rx.lock() {
+                    Ok(rx) => rx.recv(),
+                    Err(_) => break,
+                };
+                let Ok((seq, chunk)) = job else {
+                    break;
+                };
+                let sealed = encryptor.seal(seq, &chunk).map(|rec| (seq, chunk, rec));
+                if done_tx.send(sealed).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(work_rx);
+        drop(done_tx);
+
+        let writer = scope.spawn(move || -> Result<ChunkSink> {
+            let mut pending = BTreeMap::new();
+            let mut next_seq: u64 = 1;
+            for sealed in done_rx {
+                let (seq, chunk, rec) = sealed?;
+                pending.insert(seq, (chunk, rec));
+                while let Some((chunk, rec)) = pending.remove(&next_seq) {
+                    sink.write(&chunk, &rec)?;
+                    next_seq += 1;
+                }
+            }
+            anyhow::ensure!(pending.is_empty(), "missing sealed chunk {}", next_seq);
+            Ok(sink)
+        });
+
+        let mut seq: u64 = 0;
+        let read_result = loop {
+            match next_chunk() {
+                Ok(Some(chunk)) => {
+                    seq = match seq.checked_add(1) {
+                        Some(seq) => seq,
+                        None => break Err(anyhow!("seq overflow")),
+                    };
+                    // A send error means the workers stopped; the writer reports why
+                    if work_tx.send((seq, chunk)).is_err() {
+                        break Ok(());
+                    }
+                }
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+        drop(work_tx);
+
+        let written = writer
+            .join()
+            .map_err(|_| anyhow!("envelope writer thread panicked"))?;
+        read_result?;
+        written
+    })
+}
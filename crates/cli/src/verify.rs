@@ -0,0 +1,127 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! `sealedge verify`: check a .seal archive's signature and continuity chain
+//!
+//! Both this and `seal verify` run [`check_archive`], and the exit codes match so
+//! scripts can treat the two tools alike: 0 success, 10 signature failure,
+//! 11 continuity failure, 12 IO or schema error, 14 internal error.
+
+use anyhow::Result;
+use clap::Args;
+use sealedge_core::{check_archive, ArchiveCheckFailure, ArchiveError, ChainError};
+use sealedge_types::verify_report::VerifyReport;
+use std::path::PathBuf;
+use std::time::Instant;
+
+pub const EXIT_SIGNATURE: i32 = 10;
+pub const EXIT_CONTINUITY: i32 = 11;
+pub const EXIT_ARCHIVE: i32 = 12;
+pub const EXIT_INTERNAL: i32 = 14;
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Path to the .seal archive directory
+    #[arg(value_name = "ARCHIVE")]
+    archive: PathBuf,
+
+    /// Device public key (ed25519:<base64> or ecdsa-p256:<base64>; bare keys are ed25519)
+    #[arg(long = "device-pub", value_name = "KEY")]
+    device_pub: String,
+
+    /// Print a JSON VerifyReport on stdout instead of the summary
+    #[arg(long)]
+    json: bool,
+}
+
+/// Verify the archive and return the process exit code
+pub fn run(args: &VerifyArgs) -> Result<i32> {
+    let start_time = Instant::now();
+    let mut report = VerifyReport::default();
+    let (code, first_line) = check(args, &mut report);
+    report.verify_time_ms = start_time.elapsed().as_millis() as u64;
+
+    if args.json {
+        println!("{}", serde_json::to_string(&report)?);
+    } else if code == 0 {
+        println!("Signature: PASS");
+        println!("Continuity: PASS");
+        println!(
+            "Segments: {}  Duration(s): {:.1}  Chunk(s): {:.1}",
+            report.segments,
+            report.duration_s,
+            if report.segments > 0 {
+                report.duration_s / report.segments as f32
+            } else {
+                0.0
+            }
+        );
+    } else {
+        eprintln!("{}", first_line);
+    }
+    Ok(code)
+}
+
+/// Fill in `report` and return the exit code with a one-line failure summary
+fn check(args: &VerifyArgs, report: &mut VerifyReport) -> (i32, String) {
+    let check = match check_archive(&args.archive, &args.device_pub) {
+        Ok(check) => check,
+        Err(e) => {
+            report.error = Some(format!("Archive read failed: {}", e));
+            return (EXIT_ARCHIVE, e.summary().to_string());
+        }
+    };
+
+    let manifest = &check.manifest;
+    report.profile = manifest.profile.clone();
+    report.device_id = manifest.device.id.clone();
+    report.segments = manifest.segments.len() as u32;
+    report.duration_s = manifest
+        .segments
+        .iter()
+        .map(|s| s.duration_seconds as f32)
+        .sum();
+
+    let (code, error, first_line) = match check.failure {
+        None => {
+            report.signature = "pass".to_string();
+            report.continuity = "pass".to_string();
+            return (0, String::new());
+        }
+        Some(ArchiveCheckFailure::Continuity(e)) => {
+            report.signature = "pass".to_string();
+            report.continuity = "fail".to_string();
+            report.error = Some(e.to_string());
+            let first_line = match e {
+                ArchiveError::Chain(ChainError::Gap(index)) => {
+                    report.first_gap_index = Some(index as u32);
+                    format!("Continuity: FAIL (gap at index {})", index)
+                }
+                _ => format!("Continuity: FAIL ({})", e),
+            };
+            return (EXIT_CONTINUITY, first_line);
+        }
+        Some(ArchiveCheckFailure::MissingSignature) => (
+            EXIT_ARCHIVE,
+            "Manifest missing signature".to_string(),
+            "Manifest missing signature".to_string(),
+        ),
+        Some(ArchiveCheckFailure::Canonicalization(e)) => (
+            EXIT_INTERNAL,
+            format!("Canonical serialization failed: {}", e),
+            "Internal canonicalization error".to_string(),
+        ),
+        Some(ArchiveCheckFailure::Signature(error)) => {
+            (EXIT_SIGNATURE, error, "Signature: FAIL".to_string())
+        }
+    };
+    report.signature = "fail".to_string();
+    report.continuity = "skip".to_string();
+    report.error = Some(error);
+    (code, first_line)
+}
@@ -0,0 +1,91 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+// Allow deprecated cargo_bin usage - the replacement cargo_bin_cmd! macro
+// is not yet stable across all assert_cmd versions
+#![allow(deprecated)]
+
+use assert_cmd::Command;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const KEY_HEX: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+/// MAGIC (4) + VERSION (1) + StreamHeader.v (1) + header Vec length (8) + FileHeader.version (1)
+const AEAD_ALG_OFFSET: usize = 15;
+
+fn encrypt(input: &Path, envelope: &Path, aead: &str) {
+    Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--no-plaintext", "--key-hex", KEY_HEX, "--aead", aead])
+        .arg("--input")
+        .arg(input)
+        .arg("--envelope")
+        .arg(envelope)
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_chacha20poly1305_roundtrip() {
+    let tempdir = TempDir::new().unwrap();
+    let input = tempdir.path().join("input.bin");
+    let envelope = tempdir.path().join("input.seal");
+    let payload: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+    fs::write(&input, &payload).unwrap();
+
+    encrypt(&input, &envelope, "chacha20poly1305");
+    let sealed = fs::read(&envelope).unwrap();
+    assert_eq!(
+        sealed[AEAD_ALG_OFFSET], 2,
+        "header should record ChaCha20-Poly1305"
+    );
+
+    let plaintext = Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--decrypt", "--key-hex", KEY_HEX])
+        .arg("--input")
+        .arg(&envelope)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert_eq!(plaintext, payload);
+}
+
+#[test]
+fn test_unsupported_aead_id_in_header_is_rejected() {
+    let tempdir = TempDir::new().unwrap();
+    let input = tempdir.path().join("input.bin");
+    let envelope = tempdir.path().join("input.seal");
+    fs::write(&input, b"payload").unwrap();
+    encrypt(&input, &envelope, "aes256gcm");
+
+    let mut sealed = fs::read(&envelope).unwrap();
+    assert_eq!(sealed[AEAD_ALG_OFFSET], 1);
+    sealed[AEAD_ALG_OFFSET] = 0x7f;
+    fs::write(&envelope, &sealed).unwrap();
+
+    let output = Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--decrypt", "--key-hex", KEY_HEX])
+        .arg("--input")
+        .arg(&envelope)
+        .assert()
+        .failure()
+        .get_output()
+        .clone();
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("AEAD algorithm"),
+        "unexpected error: {stderr}"
+    );
+}
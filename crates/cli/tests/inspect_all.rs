@@ -0,0 +1,74 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+// Allow deprecated cargo_bin usage - the replacement cargo_bin_cmd! macro
+// is not yet stable across all assert_cmd versions
+#![allow(deprecated)]
+
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+
+const KEY_HEX: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+#[test]
+fn test_inspect_all_emits_one_line_per_record() {
+    let temp = TempDir::new().unwrap();
+    let payload: Vec<u8> = (0..5_000u32).map(|i| (i % 251) as u8).collect();
+    let input = temp.path().join("input.bin");
+    fs::write(&input, &payload).unwrap();
+    let envelope = temp.path().join("input.trst");
+
+    Command::cargo_bin("sealedge")
+        .unwrap()
+        .arg("--input")
+        .arg(&input)
+        .arg("--envelope")
+        .arg(&envelope)
+        .args(["--no-plaintext", "--chunk", "1024", "--key-hex", KEY_HEX])
+        .assert()
+        .success();
+
+    let output = Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--inspect", "--all", "--input"])
+        .arg(&envelope)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    // 5000 bytes in 1024-byte chunks
+    let expected_records = payload.len().div_ceil(1024);
+    assert_eq!(lines.len(), expected_records);
+
+    let first = lines[0]["sequence"].as_u64().unwrap();
+    for (i, line) in lines.iter().enumerate() {
+        assert_eq!(line["sequence"].as_u64().unwrap(), first + i as u64);
+        assert!(line["data_type"].is_object());
+        assert_eq!(line["signer"], lines[0]["signer"]);
+    }
+    assert_eq!(lines[0]["chunk_len"], 1024);
+    assert_eq!(lines[expected_records - 1]["chunk_len"], 5_000 % 1024);
+}
+
+#[test]
+fn test_all_requires_inspect() {
+    Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--all", "--input", "missing.trst"])
+        .assert()
+        .failure();
+}
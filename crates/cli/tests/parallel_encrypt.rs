@@ -0,0 +1,71 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+// Allow deprecated cargo_bin usage - the replacement cargo_bin_cmd! macro
+// is not yet stable across all assert_cmd versions
+#![allow(deprecated)]
+
+use assert_cmd::Command;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const KEY_HEX: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+fn encrypt(input: &Path, envelope: &Path, extra: &[&str]) {
+    Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--chunk", "1024", "--no-plaintext", "--key-hex", KEY_HEX])
+        .args(extra)
+        .arg("--input")
+        .arg(input)
+        .arg("--envelope")
+        .arg(envelope)
+        .assert()
+        .success();
+}
+
+fn decrypt(envelope: &Path) -> Vec<u8> {
+    Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--decrypt", "--key-hex", KEY_HEX])
+        .arg("--input")
+        .arg(envelope)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone()
+}
+
+#[test]
+fn test_parallel_output_decrypts_like_sequential() {
+    let tempdir = TempDir::new().unwrap();
+    let input = tempdir.path().join("input.bin");
+    // Not a multiple of the chunk size, so the short final chunk is covered
+    let payload: Vec<u8> = (0..300_000u32).map(|i| (i % 251) as u8).collect();
+    fs::write(&input, &payload).unwrap();
+
+    let sequential = tempdir.path().join("sequential.seal");
+    let parallel = tempdir.path().join("parallel.seal");
+    let verified = tempdir.path().join("verified.seal");
+    encrypt(&input, &sequential, &[]);
+    encrypt(&input, &parallel, &["--jobs", "4"]);
+    encrypt(&input, &verified, &["--jobs", "4", "--verify-each"]);
+
+    let expected = decrypt(&sequential);
+    assert_eq!(expected, payload);
+    assert_eq!(decrypt(&parallel), expected);
+    assert_eq!(decrypt(&verified), expected);
+
+    // Same record layout: only nonces, timestamps and signatures differ
+    assert_eq!(
+        fs::metadata(&parallel).unwrap().len(),
+        fs::metadata(&sequential).unwrap().len()
+    );
+}
@@ -0,0 +1,53 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+// Allow deprecated cargo_bin usage - the replacement cargo_bin_cmd! macro
+// is not yet stable across all assert_cmd versions
+#![allow(deprecated)]
+
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+
+const KEY_HEX: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+#[test]
+fn test_progress_flag_leaves_output_unchanged_and_hidden_without_tty() {
+    let tempdir = TempDir::new().unwrap();
+    let input = tempdir.path().join("input.bin");
+    let envelope = tempdir.path().join("input.seal");
+    let payload: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+    fs::write(&input, &payload).unwrap();
+
+    // stderr is a pipe here, so the bar must not be drawn
+    let encrypted = Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--progress", "--key-hex", KEY_HEX, "--out", "-"])
+        .arg("--input")
+        .arg(&input)
+        .arg("--envelope")
+        .arg(&envelope)
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    assert_eq!(encrypted.stdout, payload);
+    assert!(!String::from_utf8_lossy(&encrypted.stderr).contains("encrypting"));
+
+    let decrypted = Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--progress", "--decrypt", "--key-hex", KEY_HEX])
+        .arg("--input")
+        .arg(&envelope)
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    assert_eq!(decrypted.stdout, payload);
+    assert!(!String::from_utf8_lossy(&decrypted.stderr).contains("decrypting"));
+}
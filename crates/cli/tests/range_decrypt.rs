@@ -0,0 +1,69 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+// Allow deprecated cargo_bin usage - the replacement cargo_bin_cmd! macro
+// is not yet stable across all assert_cmd versions
+#![allow(deprecated)]
+
+use assert_cmd::Command;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const KEY_HEX: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+fn decrypt_range(envelope: &Path, offset: u64, length: u64) -> Vec<u8> {
+    Command::cargo_bin("sealedge")
+        .unwrap()
+        .arg("--decrypt")
+        .arg("--input")
+        .arg(envelope)
+        .args(["--key-hex", KEY_HEX])
+        .args(["--offset", &offset.to_string()])
+        .args(["--length", &length.to_string()])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone()
+}
+
+#[test]
+fn test_decrypt_mid_file_range() {
+    let temp = TempDir::new().unwrap();
+    let payload: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+    let input = temp.path().join("input.bin");
+    fs::write(&input, &payload).unwrap();
+    let envelope = temp.path().join("input.trst");
+
+    Command::cargo_bin("sealedge")
+        .unwrap()
+        .arg("--input")
+        .arg(&input)
+        .arg("--envelope")
+        .arg(&envelope)
+        .args(["--no-plaintext", "--chunk", "1024", "--key-hex", KEY_HEX])
+        .assert()
+        .success();
+
+    // Starts and ends mid-chunk, spanning three chunks
+    assert_eq!(decrypt_range(&envelope, 2500, 3000), &payload[2500..5500]);
+    // Inside a single chunk
+    assert_eq!(decrypt_range(&envelope, 1030, 10), &payload[1030..1040]);
+    // A length running past the end is cut at the end of the plaintext
+    assert_eq!(decrypt_range(&envelope, 9990, 100), &payload[9990..]);
+
+    Command::cargo_bin("sealedge")
+        .unwrap()
+        .arg("--decrypt")
+        .arg("--input")
+        .arg(&envelope)
+        .args(["--key-hex", KEY_HEX, "--offset", "10001"])
+        .assert()
+        .failure();
+}
@@ -0,0 +1,102 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+// Allow deprecated cargo_bin usage - the replacement cargo_bin_cmd! macro
+// is not yet stable across all assert_cmd versions
+#![allow(deprecated)]
+
+use assert_cmd::Command;
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+use sealedge_core::format::{read_preamble_and_header, verify_manifest_with_domain, Record};
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use tempfile::TempDir;
+
+const KEY_HEX: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+const SIGNING_KEY_HEX: &str = "4242424242424242424242424242424242424242424242424242424242424242";
+
+/// Encrypt `input` with `signing_args` and return the envelope bytes and stderr
+fn encrypt(input: &Path, envelope: &Path, signing_args: &[&str]) -> (Vec<u8>, String) {
+    let output = Command::cargo_bin("sealedge")
+        .unwrap()
+        .arg("--input")
+        .arg(input)
+        .arg("--envelope")
+        .arg(envelope)
+        .args(["--no-plaintext", "--chunk", "1024", "--key-hex", KEY_HEX])
+        .args(signing_args)
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    (
+        fs::read(envelope).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+/// Verify every record's manifest and return the signing public keys
+fn manifest_signers(envelope: &[u8]) -> Vec<VerifyingKey> {
+    let mut r = Cursor::new(envelope);
+    read_preamble_and_header(&mut r).unwrap();
+    let mut signers = Vec::new();
+    while (r.position() as usize) < envelope.len() {
+        let record: Record = bincode::deserialize_from(&mut r).unwrap();
+        let pubkey: [u8; 32] = record.sm.pubkey.as_slice().try_into().unwrap();
+        let sig: [u8; 64] = record.sm.sig.as_slice().try_into().unwrap();
+        let key = VerifyingKey::from_bytes(&pubkey).unwrap();
+        verify_manifest_with_domain(&key, &record.sm.manifest, &Signature::from_bytes(&sig))
+            .unwrap();
+        signers.push(key);
+    }
+    signers
+}
+
+#[test]
+fn test_same_signing_key_signs_both_runs() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("input.bin");
+    fs::write(&input, vec![7u8; 3000]).unwrap();
+    let key_file = temp.path().join("signing.key");
+    fs::write(&key_file, format!("{}\n", SIGNING_KEY_HEX)).unwrap();
+
+    let (first, stderr) = encrypt(
+        &input,
+        &temp.path().join("first.trst"),
+        &["--signing-key", key_file.to_str().unwrap()],
+    );
+    assert!(!stderr.contains("ephemeral"), "stderr: {}", stderr);
+    let (second, _) = encrypt(
+        &input,
+        &temp.path().join("second.trst"),
+        &["--signing-key-hex", SIGNING_KEY_HEX],
+    );
+
+    let seed: [u8; 32] = hex::decode(SIGNING_KEY_HEX).unwrap().try_into().unwrap();
+    let expected = SigningKey::from_bytes(&seed).verifying_key();
+    let signers: Vec<_> = manifest_signers(&first)
+        .into_iter()
+        .chain(manifest_signers(&second))
+        .collect();
+    assert_eq!(signers.len(), 6);
+    assert!(signers.iter().all(|key| *key == expected));
+}
+
+#[test]
+fn test_without_signing_key_warns_and_uses_ephemeral_key() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("input.bin");
+    fs::write(&input, vec![7u8; 1000]).unwrap();
+
+    let (first, stderr) = encrypt(&input, &temp.path().join("first.trst"), &[]);
+    assert!(stderr.contains("ephemeral key"), "stderr: {}", stderr);
+    let (second, _) = encrypt(&input, &temp.path().join("second.trst"), &[]);
+
+    assert_ne!(manifest_signers(&first), manifest_signers(&second));
+}
@@ -0,0 +1,52 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+// Allow deprecated cargo_bin usage - the replacement cargo_bin_cmd! macro
+// is not yet stable across all assert_cmd versions
+#![allow(deprecated)]
+
+use assert_cmd::Command;
+
+const KEY_HEX: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+#[test]
+fn test_stdin_stdout_pipeline_roundtrip() {
+    let payload: Vec<u8> = (0..50_000u32).map(|i| (i % 251) as u8).collect();
+
+    let envelope = Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--envelope", "-", "--no-plaintext", "--key-hex", KEY_HEX])
+        .write_stdin(payload.clone())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert!(!envelope.is_empty());
+
+    let plaintext = Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--decrypt", "--input", "-", "--key-hex", KEY_HEX])
+        .write_stdin(envelope)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert_eq!(plaintext, payload);
+}
+
+#[test]
+fn test_plaintext_and_envelope_cannot_share_stdout() {
+    Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--envelope", "-", "--key-hex", KEY_HEX])
+        .write_stdin(b"data".to_vec())
+        .assert()
+        .failure();
+}
@@ -0,0 +1,163 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+// Allow deprecated cargo_bin usage - the replacement cargo_bin_cmd! macro
+// is not yet stable across all assert_cmd versions
+#![allow(deprecated)]
+
+use assert_cmd::Command;
+use sealedge_core::{
+    chain_next, genesis, segment_hash, sign_manifest, write_archive, DeviceKeypair,
+    ProfileMetadata, SegmentInfo, TrstManifest,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const CHUNKS: [&[u8]; 3] = [b"chunk zero", b"chunk one", b"chunk two"];
+
+/// Write a signed three-segment archive under `dir`
+fn write_clip(dir: &Path, keypair: &DeviceKeypair) -> PathBuf {
+    let mut manifest = TrstManifest::new_cam_video();
+    manifest.device.id = "CAM001".to_string();
+    manifest.device.public_key = keypair.public.clone();
+    if let ProfileMetadata::CamVideo(metadata) = &mut manifest.metadata {
+        metadata.started_at = "2025-01-15T10:30:00Z".to_string();
+        metadata.ended_at = "2025-01-15T10:30:06Z".to_string();
+    }
+
+    let mut continuity = genesis();
+    for (i, chunk) in CHUNKS.iter().enumerate() {
+        let hash = segment_hash(chunk);
+        continuity = chain_next(&continuity, &hash);
+        manifest.segments.push(SegmentInfo {
+            chunk_file: format!("{:05}.bin", i),
+            blake3_hash: hex::encode(hash),
+            start_time: format!("2025-01-15T10:30:{:02}Z", i * 2),
+            duration_seconds: 2.0,
+            continuity_hash: hex::encode(continuity),
+            nonce: None,
+        });
+    }
+
+    let signature = sign_manifest(keypair, &manifest.to_canonical_bytes().unwrap()).unwrap();
+    manifest.signature = Some(signature.clone());
+    let path = dir.join("clip.seal");
+    let chunk_data = CHUNKS.iter().map(|c| c.to_vec()).collect();
+    write_archive(&path, &manifest, chunk_data, signature.as_bytes()).unwrap();
+    path
+}
+
+fn verify(archive: &Path, device_pub: &str) -> Command {
+    let mut cmd = Command::cargo_bin("sealedge").unwrap();
+    cmd.arg("verify")
+        .arg(archive)
+        .arg("--device-pub")
+        .arg(device_pub);
+    cmd
+}
+
+#[test]
+fn verify_valid_archive_exits_zero_with_summary() {
+    let temp_dir = TempDir::new().unwrap();
+    let keypair = DeviceKeypair::generate().unwrap();
+    let archive = write_clip(temp_dir.path(), &keypair);
+
+    let output = verify(&archive, &keypair.public).output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "Signature: PASS",
+            "Continuity: PASS",
+            "Segments: 3  Duration(s): 6.0  Chunk(s): 2.0",
+        ]
+    );
+}
+
+#[test]
+fn verify_json_emits_verify_report() {
+    let temp_dir = TempDir::new().unwrap();
+    let keypair = DeviceKeypair::generate().unwrap();
+    let archive = write_clip(temp_dir.path(), &keypair);
+
+    let output = verify(&archive, &keypair.public)
+        .arg("--json")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let report: sealedge_types::verify_report::VerifyReport =
+        serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report.signature, "pass");
+    assert_eq!(report.continuity, "pass");
+    assert_eq!(report.segments, 3);
+    assert_eq!(report.device_id, "CAM001");
+}
+
+#[test]
+fn verify_wrong_key_exits_10() {
+    let temp_dir = TempDir::new().unwrap();
+    let keypair = DeviceKeypair::generate().unwrap();
+    let archive = write_clip(temp_dir.path(), &keypair);
+    let other = DeviceKeypair::generate().unwrap();
+
+    let output = verify(&archive, &other.public)
+        .arg("--json")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(10));
+    let report: sealedge_types::verify_report::VerifyReport =
+        serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report.signature, "fail");
+    assert_eq!(report.continuity, "skip");
+}
+
+#[test]
+fn verify_swapped_chunk_exits_11() {
+    let temp_dir = TempDir::new().unwrap();
+    let keypair = DeviceKeypair::generate().unwrap();
+    let archive = write_clip(temp_dir.path(), &keypair);
+
+    // The manifest and its signature are untouched; only the chunk on disk changes
+    fs::write(archive.join("chunks/00001.bin"), b"replaced").unwrap();
+
+    let output = verify(&archive, &keypair.public).output().unwrap();
+    assert_eq!(output.status.code(), Some(11));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("Continuity: FAIL"), "stderr: {}", stderr);
+}
+
+#[test]
+fn verify_missing_chunk_exits_12() {
+    let temp_dir = TempDir::new().unwrap();
+    let keypair = DeviceKeypair::generate().unwrap();
+    let archive = write_clip(temp_dir.path(), &keypair);
+    fs::remove_file(archive.join("chunks/00002.bin")).unwrap();
+
+    let output = verify(&archive, &keypair.public).output().unwrap();
+    assert_eq!(output.status.code(), Some(12));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr.trim(), "Missing chunk file");
+}
+
+#[test]
+fn verify_nonexistent_archive_exits_12() {
+    let temp_dir = TempDir::new().unwrap();
+    let keypair = DeviceKeypair::generate().unwrap();
+
+    let output = verify(&temp_dir.path().join("missing.seal"), &keypair.public)
+        .arg("--json")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(12));
+    let report: sealedge_types::verify_report::VerifyReport =
+        serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report.error.unwrap().starts_with("Archive read failed"));
+}
@@ -0,0 +1,193 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+// Allow deprecated cargo_bin usage - the replacement cargo_bin_cmd! macro
+// is not yet stable across all assert_cmd versions
+#![allow(deprecated)]
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm,
+};
+use assert_cmd::Command;
+use ed25519_dalek::SigningKey;
+use sealedge_core::format::{
+    build_aad, sign_manifest_with_domain, write_stream_header, AudioFormat, DataType, FileHeader,
+    Manifest, Record, SignedManifest, StreamHeader, VERSION,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const KEY_HEX: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+const SAMPLE_RATE: u32 = 48_000;
+const CHANNELS: u16 = 2;
+
+/// Write an envelope shaped like a live-audio capture: f32le frames in `chunks` records
+fn write_audio_envelope(path: &Path, chunks: &[Vec<u8>]) {
+    let key: [u8; 32] = hex::decode(KEY_HEX).unwrap().try_into().unwrap();
+    let cipher = Aes256Gcm::new((&key).into());
+    let signing = SigningKey::from_bytes(&[7u8; 32]);
+
+    let header = FileHeader {
+        chunk_size: 4096,
+        nonce_prefix: [1, 2, 3, 4],
+        key_id: [9u8; 16],
+        ..FileHeader::new_with_defaults()
+    };
+    let header_bytes = header.to_bytes();
+    let header_hash = *blake3::hash(&header_bytes).as_bytes();
+
+    let mut out = Vec::new();
+    write_stream_header(
+        &mut out,
+        &StreamHeader {
+            v: VERSION,
+            header: header_bytes.to_vec(),
+            header_hash,
+        },
+    )
+    .unwrap();
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let seq = i as u64 + 1;
+        let mut nonce = [0u8; 12];
+        nonce[..4].copy_from_slice(&header.nonce_prefix);
+        nonce[4..].copy_from_slice(&seq.to_be_bytes());
+
+        let manifest = Manifest {
+            v: 1,
+            ts_ms: 0,
+            seq,
+            header_hash,
+            pt_hash: *blake3::hash(chunk).as_bytes(),
+            key_id: header.key_id,
+            ai_used: false,
+            model_ids: vec![],
+            data_type: DataType::Audio {
+                sample_rate: SAMPLE_RATE,
+                channels: CHANNELS,
+                format: AudioFormat::F32Le,
+            },
+            chunk_len: chunk.len() as u32,
+        };
+        let m_bytes = bincode::serialize(&manifest).unwrap();
+        let sig = sign_manifest_with_domain(&signing, &m_bytes);
+        let aad = build_aad(
+            &header_hash,
+            seq,
+            &nonce,
+            blake3::hash(&m_bytes).as_bytes(),
+            manifest.chunk_len,
+        );
+        let ct = cipher
+            .encrypt(
+                (&nonce).into(),
+                Payload {
+                    msg: chunk,
+                    aad: &aad,
+                },
+            )
+            .unwrap();
+        let record = Record {
+            seq,
+            nonce,
+            sm: SignedManifest {
+                manifest: m_bytes,
+                sig: sig.to_bytes().to_vec(),
+                pubkey: signing.verifying_key().to_bytes().to_vec(),
+            },
+            ct,
+        };
+        bincode::serialize_into(&mut out, &record).unwrap();
+    }
+    fs::write(path, out).unwrap();
+}
+
+fn u16_at(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes(bytes[at..at + 2].try_into().unwrap())
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+#[test]
+fn test_wav_output_for_audio_envelope() {
+    let tempdir = TempDir::new().unwrap();
+    let envelope = tempdir.path().join("capture.seal");
+    let wav = tempdir.path().join("capture.wav");
+
+    // Three chunks of 512 stereo f32 frames
+    let frames_per_chunk = 512;
+    let chunks: Vec<Vec<u8>> = (0..3)
+        .map(|c| {
+            (0..frames_per_chunk * CHANNELS as usize)
+                .flat_map(|i| ((c * 10_000 + i) as f32 / 1e4).to_le_bytes())
+                .collect()
+        })
+        .collect();
+    write_audio_envelope(&envelope, &chunks);
+
+    Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--decrypt", "--wav", "--key-hex", KEY_HEX])
+        .arg("--input")
+        .arg(&envelope)
+        .arg("--out")
+        .arg(&wav)
+        .assert()
+        .success();
+
+    let bytes = fs::read(&wav).unwrap();
+    let pcm: Vec<u8> = chunks.concat();
+    assert_eq!(&bytes[0..4], b"RIFF");
+    assert_eq!(u32_at(&bytes, 4) as usize, bytes.len() - 8);
+    assert_eq!(&bytes[8..16], b"WAVEfmt ");
+    assert_eq!(u16_at(&bytes, 20), 3, "IEEE float format tag");
+    assert_eq!(u16_at(&bytes, 22), CHANNELS);
+    assert_eq!(u32_at(&bytes, 24), SAMPLE_RATE);
+    assert_eq!(u32_at(&bytes, 28), SAMPLE_RATE * 8);
+    assert_eq!(u16_at(&bytes, 32), 8, "block align");
+    assert_eq!(u16_at(&bytes, 34), 32, "bits per sample");
+    assert_eq!(&bytes[36..40], b"data");
+
+    let data_len = u32_at(&bytes, 40) as usize;
+    assert_eq!(data_len / 8, 3 * frames_per_chunk, "sample frame count");
+    assert_eq!(&bytes[44..], &pcm[..]);
+}
+
+#[test]
+fn test_wav_flag_falls_back_to_raw_for_non_audio() {
+    let tempdir = TempDir::new().unwrap();
+    let input = tempdir.path().join("notes.txt");
+    let envelope = tempdir.path().join("notes.seal");
+    fs::write(&input, b"not audio").unwrap();
+
+    Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--no-plaintext", "--key-hex", KEY_HEX])
+        .arg("--input")
+        .arg(&input)
+        .arg("--envelope")
+        .arg(&envelope)
+        .assert()
+        .success();
+
+    let output = Command::cargo_bin("sealedge")
+        .unwrap()
+        .args(["--decrypt", "--wav", "--key-hex", KEY_HEX])
+        .arg("--input")
+        .arg(&envelope)
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    assert_eq!(output.stdout, b"not audio");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--wav ignored"));
+}
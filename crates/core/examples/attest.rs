@@ -10,7 +10,9 @@
 
 use anyhow::{Context, Result};
 use clap::Parser;
-use sealedge_core::{create_signed_attestation, AttestationConfig, KeySource, OutputFormat};
+use sealedge_core::{
+    create_signed_attestation, AttestationConfig, HashChoice, KeySource, OutputFormat,
+};
 use std::path::PathBuf;
 
 /// Create software attestation (birth certificate)
@@ -48,6 +50,14 @@ struct Args {
     /// Output JSON attestation without envelope (for inspection)
     #[arg(long)]
     json_only: bool,
+
+    /// Hash algorithm for the artifact (sha256, sha512, blake3)
+    #[arg(long, default_value = "sha256")]
+    hash_alg: HashChoice,
+
+    /// Write a detached signature to this path; the attestation JSON goes to --output
+    #[arg(long, conflicts_with = "json_only")]
+    detached_signature: Option<PathBuf>,
 }
 
 fn main() -> Result<()> {
@@ -64,6 +74,8 @@ fn main() -> Result<()> {
     // Determine output format
     let output_format = if args.json_only {
         OutputFormat::JsonOnly
+    } else if args.detached_signature.is_some() {
+        OutputFormat::DetachedSignature
     } else {
         OutputFormat::SealedEnvelope
     };
@@ -73,6 +85,7 @@ fn main() -> Result<()> {
         artifact_path: args.file.clone(),
         builder_id: args.builder_id.clone(),
         output_format,
+        hash_algorithm: args.hash_alg,
         key_source: KeySource::Generate, // Demo mode with ephemeral keys
     };
 
@@ -90,11 +103,30 @@ fn main() -> Result<()> {
     }
 
     // Write output to file
-    std::fs::write(&args.output, &result.serialized_output)
-        .with_context(|| format!("Failed to write output to {}", args.output.display()))?;
+    if let Some(signature_path) = &args.detached_signature {
+        let attestation_json = serde_json::to_vec_pretty(&result.attestation)
+            .context("Failed to serialize attestation")?;
+        std::fs::write(&args.output, attestation_json)
+            .with_context(|| format!("Failed to write output to {}", args.output.display()))?;
+        std::fs::write(signature_path, &result.serialized_output).with_context(|| {
+            format!(
+                "Failed to write detached signature to {}",
+                signature_path.display()
+            )
+        })?;
+    } else {
+        std::fs::write(&args.output, &result.serialized_output)
+            .with_context(|| format!("Failed to write output to {}", args.output.display()))?;
+    }
 
     // Display appropriate success message
-    if args.json_only {
+    if let Some(signature_path) = &args.detached_signature {
+        println!("✔ JSON attestation written to: {}", args.output.display());
+        println!(
+            "✔ Detached signature written to: {}",
+            signature_path.display()
+        );
+    } else if args.json_only {
         println!("✔ JSON attestation written to: {}", args.output.display());
     } else {
         println!("✔ Sealed attestation created: {}", args.output.display());
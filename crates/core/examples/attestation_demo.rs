@@ -2,7 +2,9 @@
 // MPL-2.0: https://mozilla.org/MPL/2.0/
 // Project: sealedge — Privacy and trust at the edge.
 
-use sealedge_core::{create_signed_attestation, AttestationConfig, KeySource, OutputFormat};
+use sealedge_core::{
+    create_signed_attestation, AttestationConfig, HashChoice, KeySource, OutputFormat,
+};
 use std::fs::File;
 use std::io::Write;
 use tempfile::tempdir;
@@ -27,6 +29,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         artifact_path: artifact_path.clone(),
         builder_id: "demo-builder@example.com".to_string(),
         output_format: OutputFormat::JsonOnly,
+        hash_algorithm: HashChoice::Sha256,
         key_source: KeySource::Generate,
     };
 
@@ -48,6 +51,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         artifact_path: artifact_path.clone(),
         builder_id: "demo-builder@example.com".to_string(),
         output_format: OutputFormat::SealedEnvelope,
+        hash_algorithm: HashChoice::Sha256,
         key_source: KeySource::Generate,
     };
 
@@ -32,6 +32,14 @@ struct Args {
     /// Treat attestation file as raw JSON (not envelope)
     #[arg(long)]
     json_input: bool,
+
+    /// Detached signature file for a JSON attestation
+    #[arg(long)]
+    detached_signature: Option<PathBuf>,
+
+    /// Hex-encoded Ed25519 key the detached signature must be made with
+    #[arg(long, requires = "detached_signature")]
+    expected_key: Option<String>,
 }
 
 fn main() -> Result<()> {
@@ -50,10 +58,14 @@ fn main() -> Result<()> {
         artifact_path: args.artifact.clone(),
         attestation_path: args.attestation_file.clone(),
         force_json: args.json_input,
+        detached_signature_path: args.detached_signature.clone(),
+        expected_verification_key: args.expected_key.clone(),
     };
 
     // Perform verification using the centralized library function
-    if args.json_input {
+    if args.detached_signature.is_some() {
+        println!("● Reading JSON attestation and detached signature...");
+    } else if args.json_input {
         println!("● Reading JSON attestation...");
     } else {
         println!("● Reading attestation (trying envelope first, JSON fallback)...");
@@ -86,7 +98,10 @@ fn main() -> Result<()> {
         if args.verbose {
             println!();
             println!("● Cryptographic Verification:");
-            println!("   • Hash Algorithm: SHA-256");
+            println!(
+                "   • Hash Algorithm: {}",
+                result.verification_details.hash_algorithm
+            );
             println!("   • Full Hash: {}", result.attestation.artifact_hash);
             println!(
                 "   • Computed Hash: {}",
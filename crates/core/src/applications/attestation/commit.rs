@@ -0,0 +1,179 @@
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// MPL-2.0: https://mozilla.org/MPL/2.0/
+// Project: sealedge — Privacy and trust at the edge.
+
+//! Source commit verification
+//!
+//! An attestation's `source_commit_hash` is only a claim. Verifiers that have a
+//! clone of the source repository can confirm the commit exists and is reachable
+//! from one of its refs, so a fabricated hash is caught. This is opt-in: the
+//! plain verification path never needs the repository.
+//!
+//! Checking against a repository requires the `git-attestation` feature.
+
+#[cfg(feature = "git-attestation")]
+use anyhow::{Context, Result};
+#[cfg(feature = "git-attestation")]
+use std::path::Path;
+
+#[cfg(feature = "git-attestation")]
+use super::{verify_attestation, Attestation, VerificationConfig, VerificationResult};
+
+/// Whether an attestation's source commit was found in a repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitStatus {
+    /// The commit exists and is reachable from a branch, tag, or other ref
+    Reachable {
+        /// Whether the repository's checkout is that commit with no local changes;
+        /// `None` for bare repositories
+        worktree_matches: Option<bool>,
+    },
+    /// No commit with the recorded hash is reachable, including hashes that are
+    /// not valid commit ids such as `"unknown"`
+    Unreachable,
+}
+
+/// Check that an attestation's source commit is reachable in the repository at `repo_path`
+///
+/// Also reports whether the checked-out tree is exactly that commit, which a
+/// verifier rebuilding the artifact can use to confirm it is building the same source.
+#[cfg(feature = "git-attestation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "git-attestation")))]
+pub fn verify_commit(attestation: &Attestation, repo_path: &Path) -> Result<CommitStatus> {
+    use git2::{Oid, Repository, StatusOptions};
+
+    let repo = Repository::open(repo_path)
+        .with_context(|| format!("Failed to open git repository: {}", repo_path.display()))?;
+
+    let Ok(oid) = Oid::from_str(&attestation.source_commit_hash) else {
+        return Ok(CommitStatus::Unreachable);
+    };
+    let Ok(commit) = repo.find_commit(oid) else {
+        return Ok(CommitStatus::Unreachable);
+    };
+
+    // A commit can linger in the object database after its branch is deleted
+    let mut reachable = false;
+    for reference in repo.references().context("Failed to list git references")? {
+        let Ok(tip) = reference.and_then(|r| r.peel_to_commit()) else {
+            continue;
+        };
+        if tip.id() == oid || repo.graph_descendant_of(tip.id(), oid).unwrap_or(false) {
+            reachable = true;
+            break;
+        }
+    }
+    if !reachable {
+        return Ok(CommitStatus::Unreachable);
+    }
+
+    let worktree_matches = if repo.is_bare() {
+        None
+    } else {
+        let head_tree = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .map(|head| head.tree_id())
+            .ok();
+        let clean = repo
+            .statuses(Some(StatusOptions::new().include_untracked(false)))
+            .context("Failed to read git working tree status")?
+            .is_empty();
+        Some(head_tree == Some(commit.tree_id()) && clean)
+    };
+
+    Ok(CommitStatus::Reachable { worktree_matches })
+}
+
+/// Verify an attestation and check its source commit against a repository
+///
+/// Behaves like [`verify_attestation`], except that an attestation whose commit is
+/// not reachable in `repo_path` is reported invalid with
+/// [`AttestationStatus::CommitUnreachable`](super::AttestationStatus::CommitUnreachable).
+#[cfg(feature = "git-attestation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "git-attestation")))]
+pub fn verify_attestation_with_commit(
+    config: VerificationConfig,
+    repo_path: &Path,
+) -> Result<VerificationResult> {
+    let mut result = verify_attestation(config)?;
+    let status = verify_commit(&result.attestation, repo_path)?;
+    if status == CommitStatus::Unreachable {
+        result.is_valid = false;
+    }
+    result.commit = Some(status);
+    Ok(result)
+}
+
+#[cfg(all(test, feature = "git-attestation"))]
+mod tests {
+    use super::*;
+    use git2::{Repository, Signature};
+
+    /// Create a repository with one commit and return it with the commit id
+    fn repo_with_commit(dir: &Path) -> (Repository, String) {
+        let repo = Repository::init(dir).unwrap();
+        std::fs::write(dir.join("main.rs"), b"fn main() {}").unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("main.rs")).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+
+        let commit_id = {
+            let tree = repo.find_tree(tree_id).unwrap();
+            let author = Signature::now("builder", "builder@example.com").unwrap();
+            repo.commit(Some("HEAD"), &author, &author, "initial", &tree, &[])
+                .unwrap()
+        };
+        (repo, commit_id.to_string())
+    }
+
+    fn attestation_for(commit: &str) -> Attestation {
+        Attestation {
+            artifact_hash: "00".repeat(32),
+            artifact_hash_alg: "sha256".to_string(),
+            artifact_name: "app".to_string(),
+            source_commit_hash: commit.to_string(),
+            builder_id: "ci".to_string(),
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_verify_commit_reachable() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_repo, commit) = repo_with_commit(dir.path());
+
+        let status = verify_commit(&attestation_for(&commit), dir.path()).unwrap();
+        assert_eq!(
+            status,
+            CommitStatus::Reachable {
+                worktree_matches: Some(true)
+            }
+        );
+
+        // Local edits mean the checkout no longer matches the attested source
+        std::fs::write(dir.path().join("main.rs"), b"fn main() { evil() }").unwrap();
+        let status = verify_commit(&attestation_for(&commit), dir.path()).unwrap();
+        assert_eq!(
+            status,
+            CommitStatus::Reachable {
+                worktree_matches: Some(false)
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify_commit_fabricated_hash_unreachable() {
+        let dir = tempfile::tempdir().unwrap();
+        repo_with_commit(dir.path());
+
+        let fabricated = "3f786850e387550fdab836ed7e6dc881de23001b";
+        let status = verify_commit(&attestation_for(fabricated), dir.path()).unwrap();
+        assert_eq!(status, CommitStatus::Unreachable);
+
+        let status = verify_commit(&attestation_for("unknown"), dir.path()).unwrap();
+        assert_eq!(status, CommitStatus::Unreachable);
+    }
+}
@@ -0,0 +1,316 @@
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// MPL-2.0: https://mozilla.org/MPL/2.0/
+// Project: sealedge — Privacy and trust at the edge.
+
+//! Directory-tree attestation
+//!
+//! One attestation covering every regular file under a directory. Each file is
+//! recorded by its `/`-separated relative path and SHA-256 hash, entries are sorted
+//! by path, and a Merkle-style root hash commits to the whole list.
+//!
+//! Root construction (all hashes SHA-256):
+//! - leaf  = H(0x00 || path || 0x00 || file_hash)
+//! - node  = H(0x01 || left || right), an odd node at the end of a level is carried up
+//! - empty = H("") for a directory with no files
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::{hash_file, source_commit_hash, HashChoice};
+
+/// Hash of a single file inside an attested directory
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryEntry {
+    /// Path relative to the attested directory, always `/`-separated
+    pub path: String,
+    /// SHA-256 hash of the file contents (hex encoded)
+    pub hash: String,
+}
+
+/// Attestation covering an entire directory tree
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DirectoryAttestation {
+    /// Every regular file in the tree, sorted by `path`
+    pub entries: Vec<DirectoryEntry>,
+    /// Merkle-style root over `entries` (hex encoded)
+    pub root_hash: String,
+    /// The name of the attested directory.
+    pub directory_name: String,
+    /// The Git commit hash from which the artifacts were built.
+    pub source_commit_hash: String,
+    /// An identifier for the entity that created the attestation.
+    pub builder_id: String,
+    /// The ISO 8601 timestamp of when the attestation was created.
+    pub timestamp: String,
+}
+
+/// Outcome of checking a directory against a [`DirectoryAttestation`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryVerification {
+    /// Whether the tree matches the attestation exactly
+    pub is_valid: bool,
+    /// Root hash recomputed from the current tree
+    pub computed_root: String,
+    /// Whether the recorded root matches the recorded entries
+    pub root_consistent: bool,
+    /// Files present in both whose contents changed
+    pub modified: Vec<String>,
+    /// Files present now but not in the attestation
+    pub added: Vec<String>,
+    /// Files in the attestation that are now missing
+    pub removed: Vec<String>,
+}
+
+/// Create attestation data for every regular file under `dir_path`
+///
+/// Symlinks are not followed and are left out of the attestation.
+pub fn create_attestation_data_for_dir(
+    dir_path: &Path,
+    builder_id: &str,
+) -> Result<DirectoryAttestation> {
+    let entries = hash_directory(dir_path)?;
+    let root_hash = directory_root_hash(&entries);
+
+    Ok(DirectoryAttestation {
+        entries,
+        root_hash,
+        directory_name: dir_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string(),
+        source_commit_hash: source_commit_hash(),
+        builder_id: builder_id.to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// Verify a directory tree against a [`DirectoryAttestation`]
+///
+/// Every file is re-hashed and the root recomputed; the result lists exactly which
+/// files were modified, added, or removed.
+pub fn verify_directory_attestation(
+    attestation: &DirectoryAttestation,
+    dir_path: &Path,
+) -> Result<DirectoryVerification> {
+    let current = hash_directory(dir_path)?;
+    let computed_root = directory_root_hash(&current);
+    let root_consistent = directory_root_hash(&attestation.entries) == attestation.root_hash;
+
+    let recorded: BTreeMap<&str, &str> = attestation
+        .entries
+        .iter()
+        .map(|e| (e.path.as_str(), e.hash.as_str()))
+        .collect();
+    let present: BTreeMap<&str, &str> = current
+        .iter()
+        .map(|e| (e.path.as_str(), e.hash.as_str()))
+        .collect();
+
+    let mut modified = Vec::new();
+    let mut added = Vec::new();
+    for (path, hash) in &present {
+        match recorded.get(path) {
+            Some(expected) if expected != hash => modified.push(path.to_string()),
+            Some(_) => {}
+            None => added.push(path.to_string()),
+        }
+    }
+    let removed: Vec<String> = recorded
+        .keys()
+        .filter(|path| !present.contains_key(*path))
+        .map(|path| path.to_string())
+        .collect();
+
+    let is_valid = root_consistent
+        && computed_root == attestation.root_hash
+        && modified.is_empty()
+        && added.is_empty()
+        && removed.is_empty();
+
+    Ok(DirectoryVerification {
+        is_valid,
+        computed_root,
+        root_consistent,
+        modified,
+        added,
+        removed,
+    })
+}
+
+/// Compute the Merkle-style root hash over sorted directory entries
+pub fn directory_root_hash(entries: &[DirectoryEntry]) -> String {
+    if entries.is_empty() {
+        return format!("{:x}", Sha256::digest(b""));
+    }
+
+    let mut level: Vec<[u8; 32]> = entries
+        .iter()
+        .map(|entry| {
+            let mut hasher = Sha256::new();
+            hasher.update([0x00]);
+            hasher.update(entry.path.as_bytes());
+            hasher.update([0x00]);
+            hasher.update(entry.hash.as_bytes());
+            hasher.finalize().into()
+        })
+        .collect();
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut hasher = Sha256::new();
+                    hasher.update([0x01]);
+                    hasher.update(left);
+                    hasher.update(right);
+                    hasher.finalize().into()
+                }
+                [odd] => *odd,
+                _ => unreachable!("chunks(2) yields one or two nodes"),
+            })
+            .collect();
+    }
+
+    hex::encode(level[0])
+}
+
+/// Hash every regular file under `root`, sorted by normalized relative path
+fn hash_directory(root: &Path) -> Result<Vec<DirectoryEntry>> {
+    if !root.is_dir() {
+        return Err(anyhow::anyhow!("Not a directory: {}", root.display()));
+    }
+
+    let mut entries = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let read_dir = std::fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+        for item in read_dir {
+            let item =
+                item.with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+            let file_type = item.file_type()?;
+            let path = item.path();
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file() {
+                let relative = path.strip_prefix(root)?;
+                entries.push(DirectoryEntry {
+                    path: normalize_relative_path(relative)?,
+                    hash: hash_file(&path, HashChoice::Sha256)?.0,
+                });
+            }
+        }
+    }
+
+    // Byte-wise order of the normalized path is identical on every platform
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
+/// Join path components with `/` so Windows and Unix produce the same string
+fn normalize_relative_path(relative: &Path) -> Result<String> {
+    let components = relative
+        .components()
+        .map(|c| {
+            c.as_os_str().to_str().ok_or_else(|| {
+                anyhow::anyhow!("Non UTF-8 path in directory: {}", relative.display())
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(components.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn build_tree() -> Result<TempDir> {
+        let dir = TempDir::new()?;
+        std::fs::create_dir_all(dir.path().join("bin"))?;
+        std::fs::create_dir_all(dir.path().join("lib/nested"))?;
+        std::fs::write(dir.path().join("README"), b"readme")?;
+        std::fs::write(dir.path().join("bin/tool"), b"tool binary")?;
+        std::fs::write(dir.path().join("lib/libfoo.so"), b"shared object")?;
+        std::fs::write(dir.path().join("lib/nested/data.bin"), b"data")?;
+        Ok(dir)
+    }
+
+    #[test]
+    fn test_unchanged_tree_verifies() -> Result<()> {
+        let dir = build_tree()?;
+        let attestation = create_attestation_data_for_dir(dir.path(), "dir-builder")?;
+
+        let paths: Vec<&str> = attestation
+            .entries
+            .iter()
+            .map(|e| e.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec!["README", "bin/tool", "lib/libfoo.so", "lib/nested/data.bin"]
+        );
+
+        let result = verify_directory_attestation(&attestation, dir.path())?;
+        assert!(result.is_valid);
+        assert!(result.root_consistent);
+        assert_eq!(result.computed_root, attestation.root_hash);
+        Ok(())
+    }
+
+    #[test]
+    fn test_modified_file_reported() -> Result<()> {
+        let dir = build_tree()?;
+        let attestation = create_attestation_data_for_dir(dir.path(), "dir-builder")?;
+
+        std::fs::write(dir.path().join("lib/nested/data.bin"), b"tampered")?;
+
+        let result = verify_directory_attestation(&attestation, dir.path())?;
+        assert!(!result.is_valid);
+        assert_eq!(result.modified, vec!["lib/nested/data.bin"]);
+        assert!(result.added.is_empty());
+        assert!(result.removed.is_empty());
+        assert_ne!(result.computed_root, attestation.root_hash);
+        Ok(())
+    }
+
+    #[test]
+    fn test_added_and_removed_files_reported() -> Result<()> {
+        let dir = build_tree()?;
+        let attestation = create_attestation_data_for_dir(dir.path(), "dir-builder")?;
+
+        std::fs::remove_file(dir.path().join("bin/tool"))?;
+        std::fs::write(dir.path().join("bin/implant"), b"unexpected")?;
+
+        let result = verify_directory_attestation(&attestation, dir.path())?;
+        assert!(!result.is_valid);
+        assert!(result.modified.is_empty());
+        assert_eq!(result.added, vec!["bin/implant"]);
+        assert_eq!(result.removed, vec!["bin/tool"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_root_hash_is_deterministic() -> Result<()> {
+        let first = build_tree()?;
+        let second = build_tree()?;
+
+        let a = create_attestation_data_for_dir(first.path(), "dir-builder")?;
+        let b = create_attestation_data_for_dir(second.path(), "dir-builder")?;
+        assert_eq!(a.entries, b.entries);
+        assert_eq!(a.root_hash, b.root_hash);
+
+        // Tampering with the recorded entries breaks root consistency
+        let mut forged = a.clone();
+        forged.entries[0].hash = "00".repeat(32);
+        let result = verify_directory_attestation(&forged, first.path())?;
+        assert!(!result.root_consistent);
+        assert!(!result.is_valid);
+        Ok(())
+    }
+}
@@ -11,13 +11,42 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+mod commit;
+mod directory;
+mod revocation;
+
+pub use commit::CommitStatus;
+#[cfg(feature = "git-attestation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "git-attestation")))]
+pub use commit::{verify_attestation_with_commit, verify_commit};
+pub use directory::{
+    create_attestation_data_for_dir, directory_root_hash, verify_directory_attestation,
+    DirectoryAttestation, DirectoryEntry, DirectoryVerification,
+};
+pub use revocation::{
+    attestation_hash, RevocationEntry, RevocationList, RevokedTarget, SignedRevocationList,
+};
+
+/// Read buffer size used when streaming artifacts through the hasher
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
 
 /// Simple software attestation - the "birth certificate" payload
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Attestation {
-    /// The SHA-256 hash of the artifact being attested to.
+    /// The hash of the artifact being attested to (hex encoded).
     pub artifact_hash: String,
+    /// The algorithm used for `artifact_hash`; attestations that predate this
+    /// field were always SHA-256.
+    ///
+    /// Omitted from the JSON when SHA-256, so the canonical bytes (and therefore
+    /// signatures) of SHA-256 attestations are the same as before the field existed.
+    #[serde(
+        default = "default_artifact_hash_alg",
+        skip_serializing_if = "is_default_artifact_hash_alg"
+    )]
+    pub artifact_hash_alg: String,
     /// The name of the artifact file.
     pub artifact_name: String,
     /// The Git commit hash from which the artifact was built.
@@ -28,6 +57,59 @@ pub struct Attestation {
     pub timestamp: String,
 }
 
+fn default_artifact_hash_alg() -> String {
+    HashChoice::Sha256.as_str().to_string()
+}
+
+fn is_default_artifact_hash_alg(alg: &str) -> bool {
+    alg == HashChoice::Sha256.as_str()
+}
+
+/// Hash algorithm used to fingerprint an attested artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashChoice {
+    /// SHA-256 (default, backward compatible)
+    #[default]
+    Sha256,
+    /// SHA-512
+    Sha512,
+    /// BLAKE3
+    Blake3,
+}
+
+impl HashChoice {
+    /// Identifier recorded in `Attestation::artifact_hash_alg`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HashChoice::Sha256 => "sha256",
+            HashChoice::Sha512 => "sha512",
+            HashChoice::Blake3 => "blake3",
+        }
+    }
+}
+
+impl std::fmt::Display for HashChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for HashChoice {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "sha256" => Ok(HashChoice::Sha256),
+            "sha512" => Ok(HashChoice::Sha512),
+            "blake3" => Ok(HashChoice::Blake3),
+            other => Err(anyhow::anyhow!(
+                "Unknown artifact hash algorithm: {:?} (expected sha256, sha512, or blake3)",
+                other
+            )),
+        }
+    }
+}
+
 /// Configuration for creating signed attestations
 #[derive(Debug)]
 pub struct AttestationConfig {
@@ -37,6 +119,8 @@ pub struct AttestationConfig {
     pub builder_id: String,
     /// Output format for the attestation
     pub output_format: OutputFormat,
+    /// Hash algorithm used to fingerprint the artifact
+    pub hash_algorithm: HashChoice,
     /// Source of cryptographic keys
     pub key_source: KeySource,
 }
@@ -48,6 +132,20 @@ pub enum OutputFormat {
     JsonOnly,
     /// Cryptographically sealed envelope with attestation
     SealedEnvelope,
+    /// Detached Ed25519 signature over the canonical attestation JSON.
+    ///
+    /// `serialized_output` holds only the [`DetachedSignature`] file; the attestation
+    /// itself is shipped separately as JSON (see [`canonical_attestation_bytes`]).
+    DetachedSignature,
+}
+
+/// Detached signature file: the signature and the key that made it, nothing else
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DetachedSignature {
+    /// Ed25519 signature over the canonical attestation JSON (hex encoded)
+    pub signature: String,
+    /// Public key for verification (hex encoded)
+    pub verification_key: String,
 }
 
 /// Source of cryptographic keys for signing
@@ -95,7 +193,7 @@ pub struct VerificationInfo {
 ///
 /// # Example
 /// ```rust
-/// use sealedge_core::{AttestationConfig, OutputFormat, KeySource, create_signed_attestation};
+/// use sealedge_core::{AttestationConfig, HashChoice, OutputFormat, KeySource, create_signed_attestation};
 /// use std::path::PathBuf;
 /// use std::io::Write;
 /// use tempfile::NamedTempFile;
@@ -109,6 +207,7 @@ pub struct VerificationInfo {
 ///     artifact_path,
 ///     builder_id: "ci-job-123".to_string(),
 ///     output_format: OutputFormat::SealedEnvelope,
+///     hash_algorithm: HashChoice::Sha256,
 ///     key_source: KeySource::Generate,
 /// };
 ///
@@ -117,41 +216,16 @@ pub struct VerificationInfo {
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 pub fn create_signed_attestation(config: AttestationConfig) -> Result<AttestationResult> {
-    use sha2::{Digest, Sha256};
-
     // Step 1: Read and hash the artifact
-    let artifact_data = std::fs::read(&config.artifact_path).with_context(|| {
-        format!(
-            "Failed to read artifact: {}",
-            config.artifact_path.display()
-        )
-    })?;
-
-    let artifact_hash = format!("{:x}", Sha256::digest(&artifact_data));
+    let (artifact_hash, _) = hash_file(&config.artifact_path, config.hash_algorithm)?;
 
     // Step 2: Get Git commit hash (use placeholder if not in git repo)
-    let source_commit_hash = {
-        #[cfg(feature = "git-attestation")]
-        {
-            use git2::Repository;
-            match Repository::discover(".") {
-                Ok(repo) => repo
-                    .head()
-                    .and_then(|head| head.peel_to_commit())
-                    .map(|commit| commit.id().to_string())
-                    .unwrap_or_else(|_| "unknown".to_string()),
-                Err(_) => "unknown".to_string(),
-            }
-        }
-        #[cfg(not(feature = "git-attestation"))]
-        {
-            "unknown".to_string()
-        }
-    };
+    let source_commit_hash = source_commit_hash();
 
     // Step 3: Create the attestation data structure
     let attestation = Attestation {
         artifact_hash,
+        artifact_hash_alg: config.hash_algorithm.as_str().to_string(),
         artifact_name: config
             .artifact_path
             .file_name()
@@ -176,6 +250,98 @@ pub fn create_signed_attestation(config: AttestationConfig) -> Result<Attestatio
             })
         }
         OutputFormat::SealedEnvelope => create_sealed_attestation(attestation, config.key_source),
+        OutputFormat::DetachedSignature => {
+            create_detached_attestation(attestation, config.key_source)
+        }
+    }
+}
+
+/// Hash a file, returning the hex digest and the file size in bytes
+///
+/// The file is streamed through the hasher in [`HASH_BUFFER_SIZE`] reads, so memory
+/// use stays bounded no matter how large the artifact is.
+fn hash_file(path: &Path, algorithm: HashChoice) -> Result<(String, u64)> {
+    use sha2::{Digest, Sha256, Sha512};
+    use std::io::Read;
+
+    enum Hasher {
+        Sha256(Sha256),
+        Sha512(Sha512),
+        Blake3(Box<blake3::Hasher>),
+    }
+
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to read artifact: {}", path.display()))?;
+    let mut reader = std::io::BufReader::with_capacity(HASH_BUFFER_SIZE, file);
+
+    let mut hasher = match algorithm {
+        HashChoice::Sha256 => Hasher::Sha256(Sha256::new()),
+        HashChoice::Sha512 => Hasher::Sha512(Sha512::new()),
+        HashChoice::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+    };
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+    let mut size = 0u64;
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .with_context(|| format!("Failed to read artifact: {}", path.display()))?;
+        if read == 0 {
+            break;
+        }
+        match &mut hasher {
+            Hasher::Sha256(h) => h.update(&buffer[..read]),
+            Hasher::Sha512(h) => h.update(&buffer[..read]),
+            Hasher::Blake3(h) => {
+                h.update(&buffer[..read]);
+            }
+        }
+        size += read as u64;
+    }
+
+    let digest = match hasher {
+        Hasher::Sha256(h) => hex::encode(h.finalize()),
+        Hasher::Sha512(h) => hex::encode(h.finalize()),
+        Hasher::Blake3(h) => h.finalize().to_hex().to_string(),
+    };
+    Ok((digest, size))
+}
+
+/// Git commit hash of the current repository, or `"unknown"` outside a repo
+fn source_commit_hash() -> String {
+    #[cfg(feature = "git-attestation")]
+    {
+        use git2::Repository;
+        match Repository::discover(".") {
+            Ok(repo) => repo
+                .head()
+                .and_then(|head| head.peel_to_commit())
+                .map(|commit| commit.id().to_string())
+                .unwrap_or_else(|_| "unknown".to_string()),
+            Err(_) => "unknown".to_string(),
+        }
+    }
+    #[cfg(not(feature = "git-attestation"))]
+    {
+        "unknown".to_string()
+    }
+}
+
+/// Canonical byte form of an attestation, as covered by its signature
+///
+/// Compact `serde_json` output in struct field order. Verifiers re-derive these
+/// bytes from a parsed attestation, so whitespace in the shipped JSON file does not matter.
+pub fn canonical_attestation_bytes(attestation: &Attestation) -> Result<Vec<u8>> {
+    serde_json::to_vec(attestation).context("Failed to serialize attestation")
+}
+
+/// Resolve the signing key for an attestation from its key source
+fn resolve_signing_key(key_source: KeySource) -> ed25519_dalek::SigningKey {
+    match key_source {
+        KeySource::Generate => {
+            let mut csprng = rand_core::OsRng;
+            ed25519_dalek::SigningKey::generate(&mut csprng)
+        }
+        KeySource::Provided { signing_key } => *signing_key,
     }
 }
 
@@ -191,17 +357,10 @@ fn create_sealed_attestation(
     use ed25519_dalek::Signer;
 
     // Get or generate signing key
-    let signing_key = match key_source {
-        KeySource::Generate => {
-            let mut csprng = rand_core::OsRng;
-            ed25519_dalek::SigningKey::generate(&mut csprng)
-        }
-        KeySource::Provided { signing_key } => *signing_key,
-    };
+    let signing_key = resolve_signing_key(key_source);
 
     // Serialize attestation to canonical JSON (plaintext — attestations are public records)
-    let attestation_bytes =
-        serde_json::to_vec(&attestation).context("Failed to serialize attestation")?;
+    let attestation_bytes = canonical_attestation_bytes(&attestation)?;
 
     // Sign the attestation payload
     let signature = signing_key.sign(&attestation_bytes);
@@ -236,6 +395,74 @@ fn create_sealed_attestation(
     })
 }
 
+/// Create a detached signature for an attestation
+///
+/// Only the signature and public key are serialized; the private key is never
+/// included and the attestation JSON travels separately.
+fn create_detached_attestation(
+    attestation: Attestation,
+    key_source: KeySource,
+) -> Result<AttestationResult> {
+    use ed25519_dalek::Signer;
+
+    let signing_key = resolve_signing_key(key_source);
+
+    let signature = signing_key.sign(&canonical_attestation_bytes(&attestation)?);
+
+    let detached = DetachedSignature {
+        signature: hex::encode(signature.to_bytes()),
+        verification_key: hex::encode(signing_key.verifying_key().to_bytes()),
+    };
+
+    let serialized_output =
+        serde_json::to_vec_pretty(&detached).context("Failed to serialize detached signature")?;
+
+    Ok(AttestationResult {
+        attestation,
+        serialized_output,
+        verification_info: Some(VerificationInfo {
+            verification_key: detached.verification_key,
+        }),
+    })
+}
+
+/// Export an attestation as in-toto link metadata (v0.9 `link` layout)
+///
+/// Pure format adapter: the artifact becomes the single entry in `products`, the
+/// source commit (when known) becomes a `materials` entry keyed by `git+commit:<hash>`,
+/// and the builder identity and timestamp are carried in `environment`.
+/// The returned link is unsigned; wrap it in an in-toto envelope to sign it.
+pub fn to_in_toto_link(attestation: &Attestation) -> serde_json::Value {
+    use serde_json::json;
+
+    let mut materials = serde_json::Map::new();
+    if attestation.source_commit_hash != "unknown" {
+        materials.insert(
+            format!("git+commit:{}", attestation.source_commit_hash),
+            json!({ "sha1": attestation.source_commit_hash }),
+        );
+    }
+
+    let mut products = serde_json::Map::new();
+    products.insert(
+        attestation.artifact_name.clone(),
+        json!({ attestation.artifact_hash_alg.as_str(): attestation.artifact_hash }),
+    );
+
+    json!({
+        "_type": "link",
+        "name": "build",
+        "command": [],
+        "materials": materials,
+        "products": products,
+        "byproducts": {},
+        "environment": {
+            "builder_id": attestation.builder_id,
+            "timestamp": attestation.timestamp,
+        },
+    })
+}
+
 /// Configuration for verifying attestations
 #[derive(Debug)]
 pub struct VerificationConfig {
@@ -245,6 +472,16 @@ pub struct VerificationConfig {
     pub attestation_path: PathBuf,
     /// Force treating attestation as JSON (not envelope)
     pub force_json: bool,
+    /// Detached signature file for a JSON attestation.
+    ///
+    /// When set, the attestation file is read as JSON and must carry a valid
+    /// signature from this file; `force_json` is implied.
+    pub detached_signature_path: Option<PathBuf>,
+    /// Hex-encoded Ed25519 key the detached signature must be made with.
+    ///
+    /// A detached signature file names its own verification key, so without this
+    /// anyone can re-sign an edited attestation. Requires `detached_signature_path`.
+    pub expected_verification_key: Option<String>,
 }
 
 /// Result of verifying an attestation
@@ -256,6 +493,41 @@ pub struct VerificationResult {
     pub is_valid: bool,
     /// Details about what was verified
     pub verification_details: VerificationDetails,
+    /// Revocation entry matching the attestation, if it was checked and found revoked
+    pub revocation: Option<RevocationEntry>,
+    /// Whether the source commit was found, if it was checked against a repository
+    pub commit: Option<CommitStatus>,
+}
+
+/// Overall outcome of an attestation verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttestationStatus {
+    /// Artifact matches and the attestation is not revoked
+    Valid,
+    /// Artifact hash does not match the attestation
+    HashMismatch,
+    /// Attestation is on the revocation list
+    Revoked { reason: String },
+    /// Source commit is not reachable in the repository it was checked against
+    CommitUnreachable,
+}
+
+impl VerificationResult {
+    /// Outcome of the verification; revocation takes precedence over an unreachable
+    /// commit, which takes precedence over a hash mismatch
+    pub fn status(&self) -> AttestationStatus {
+        if let Some(entry) = &self.revocation {
+            AttestationStatus::Revoked {
+                reason: entry.reason.clone(),
+            }
+        } else if self.commit == Some(CommitStatus::Unreachable) {
+            AttestationStatus::CommitUnreachable
+        } else if self.is_valid {
+            AttestationStatus::Valid
+        } else {
+            AttestationStatus::HashMismatch
+        }
+    }
 }
 
 /// Details about the verification process
@@ -267,6 +539,8 @@ pub struct VerificationDetails {
     pub expected_hash: String,
     /// Size of the artifact in bytes
     pub artifact_size: u64,
+    /// The hash algorithm used, as recorded in the attestation
+    pub hash_algorithm: HashChoice,
     /// Whether the envelope signature was verified (if applicable)
     pub envelope_verified: Option<bool>,
 }
@@ -283,7 +557,19 @@ pub struct VerificationDetails {
 /// * `VerificationResult` with verification status and details
 pub fn verify_attestation(config: VerificationConfig) -> Result<VerificationResult> {
     // Read and parse the attestation
-    let attestation = if config.force_json {
+    let mut envelope_verified = None;
+    if config.expected_verification_key.is_some() && config.detached_signature_path.is_none() {
+        anyhow::bail!("An expected verification key requires a detached signature");
+    }
+    let attestation = if let Some(signature_path) = &config.detached_signature_path {
+        let attestation = read_detached_attestation(
+            &config.attestation_path,
+            signature_path,
+            config.expected_verification_key.as_deref(),
+        )?;
+        envelope_verified = Some(true);
+        attestation
+    } else if config.force_json {
         read_json_attestation(&config.attestation_path)?
     } else {
         // Try envelope first, fallback to JSON
@@ -293,16 +579,9 @@ pub fn verify_attestation(config: VerificationConfig) -> Result<VerificationResu
         }
     };
 
-    // Compute artifact hash
-    let artifact_data = std::fs::read(&config.artifact_path).with_context(|| {
-        format!(
-            "Failed to read artifact: {}",
-            config.artifact_path.display()
-        )
-    })?;
-
-    use sha2::{Digest, Sha256};
-    let computed_hash = format!("{:x}", Sha256::digest(&artifact_data));
+    // Compute artifact hash with the algorithm recorded in the attestation
+    let hash_algorithm: HashChoice = attestation.artifact_hash_alg.parse()?;
+    let (computed_hash, artifact_size) = hash_file(&config.artifact_path, hash_algorithm)?;
 
     // Check if hashes match
     let is_valid = computed_hash == attestation.artifact_hash;
@@ -310,17 +589,37 @@ pub fn verify_attestation(config: VerificationConfig) -> Result<VerificationResu
     let verification_details = VerificationDetails {
         computed_hash: computed_hash.clone(),
         expected_hash: attestation.artifact_hash.clone(),
-        artifact_size: artifact_data.len() as u64,
-        envelope_verified: None, // Will be set by envelope reading if applicable
+        artifact_size,
+        hash_algorithm,
+        envelope_verified,
     };
 
     Ok(VerificationResult {
         attestation,
         is_valid,
         verification_details,
+        revocation: None,
+        commit: None,
     })
 }
 
+/// Verify an attestation and check it against a revocation list
+///
+/// Behaves like [`verify_attestation`], except that an attestation matching an
+/// entry in `revocations` is reported invalid with [`AttestationStatus::Revoked`]
+/// and the entry's reason, even when the artifact hash matches.
+pub fn verify_attestation_with_revocation(
+    config: VerificationConfig,
+    revocations: &RevocationList,
+) -> Result<VerificationResult> {
+    let mut result = verify_attestation(config)?;
+    if let Some(entry) = revocations.find(&result.attestation)? {
+        result.is_valid = false;
+        result.revocation = Some(entry.clone());
+    }
+    Ok(result)
+}
+
 /// Read attestation from signed file format
 fn read_envelope_attestation(path: &PathBuf) -> Result<Attestation> {
     use ed25519_dalek::{Signature, Verifier, VerifyingKey};
@@ -362,6 +661,60 @@ fn read_envelope_attestation(path: &PathBuf) -> Result<Attestation> {
     Ok(attestation)
 }
 
+/// Read a JSON attestation and check it against a detached signature file
+///
+/// With `expected_key` set, the signature file must name that key.
+fn read_detached_attestation(
+    path: &PathBuf,
+    signature_path: &PathBuf,
+    expected_key: Option<&str>,
+) -> Result<Attestation> {
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    let attestation = read_json_attestation(path)?;
+
+    let signature_data = std::fs::read_to_string(signature_path).with_context(|| {
+        format!(
+            "Failed to read detached signature file: {}",
+            signature_path.display()
+        )
+    })?;
+    let detached: DetachedSignature =
+        serde_json::from_str(&signature_data).context("Failed to parse detached signature")?;
+
+    let key_bytes: [u8; 32] = hex::decode(&detached.verification_key)
+        .context("Invalid verification key encoding in detached signature")?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid verification key length in detached signature"))?;
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .context("Invalid verification key in detached signature")?;
+
+    if let Some(expected_key) = expected_key {
+        let expected_bytes =
+            hex::decode(expected_key).context("Invalid encoding for expected verification key")?;
+        if expected_bytes != key_bytes {
+            anyhow::bail!(
+                "Detached signature was made with key {}, expected {}",
+                detached.verification_key,
+                expected_key
+            );
+        }
+    }
+
+    let sig_bytes: [u8; 64] = hex::decode(&detached.signature)
+        .context("Invalid signature encoding in detached signature")?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid signature length in detached signature"))?;
+    let signature = Signature::from_bytes(&sig_bytes);
+
+    // Recompute the canonical bytes from the parsed attestation and check the signature
+    verifying_key
+        .verify(&canonical_attestation_bytes(&attestation)?, &signature)
+        .map_err(|_| anyhow::anyhow!("Detached signature verification failed"))?;
+
+    Ok(attestation)
+}
+
 /// Read attestation from JSON format
 fn read_json_attestation(path: &PathBuf) -> Result<Attestation> {
     let json_data = std::fs::read_to_string(path)
@@ -388,6 +741,7 @@ mod tests {
             artifact_path: test_path.clone(),
             builder_id: "test-builder".to_string(),
             output_format: OutputFormat::JsonOnly,
+            hash_algorithm: HashChoice::Sha256,
             key_source: KeySource::Generate,
         };
 
@@ -421,6 +775,7 @@ mod tests {
             artifact_path: test_path.clone(),
             builder_id: "envelope-builder".to_string(),
             output_format: OutputFormat::SealedEnvelope,
+            hash_algorithm: HashChoice::Sha256,
             key_source: KeySource::Generate,
         };
 
@@ -445,6 +800,8 @@ mod tests {
             artifact_path: test_path,
             attestation_path,
             force_json: false,
+            detached_signature_path: None,
+            expected_verification_key: None,
         };
 
         let verification_result = verify_attestation(verification_config)?;
@@ -469,6 +826,7 @@ mod tests {
             artifact_path: test_path.clone(),
             builder_id: "test-builder".to_string(),
             output_format: OutputFormat::JsonOnly,
+            hash_algorithm: HashChoice::Sha256,
             key_source: KeySource::Generate,
         };
 
@@ -489,6 +847,8 @@ mod tests {
             artifact_path: modified_path,
             attestation_path,
             force_json: true,
+            detached_signature_path: None,
+            expected_verification_key: None,
         };
 
         let verification_result = verify_attestation(verification_config)?;
@@ -516,6 +876,7 @@ mod tests {
             artifact_path: test_path,
             builder_id: "provided-key-builder".to_string(),
             output_format: OutputFormat::SealedEnvelope,
+            hash_algorithm: HashChoice::Sha256,
             key_source: KeySource::Provided {
                 signing_key: Box::new(signing_key.clone()),
             },
@@ -548,6 +909,7 @@ mod tests {
             artifact_path: test_path,
             builder_id: "json-provided-key".to_string(),
             output_format: OutputFormat::JsonOnly,
+            hash_algorithm: HashChoice::Sha256,
             key_source: KeySource::Provided {
                 signing_key: Box::new(signing_key),
             },
@@ -574,6 +936,7 @@ mod tests {
             artifact_path: test_path.clone(),
             builder_id: "force-json-test".to_string(),
             output_format: OutputFormat::JsonOnly,
+            hash_algorithm: HashChoice::Sha256,
             key_source: KeySource::Generate,
         };
 
@@ -589,6 +952,8 @@ mod tests {
             artifact_path: test_path,
             attestation_path,
             force_json: true,
+            detached_signature_path: None,
+            expected_verification_key: None,
         };
 
         let verification_result = verify_attestation(verification_config)?;
@@ -612,6 +977,7 @@ mod tests {
             artifact_path: test_path.clone(),
             builder_id: "field-validator".to_string(),
             output_format: OutputFormat::JsonOnly,
+            hash_algorithm: HashChoice::Sha256,
             key_source: KeySource::Generate,
         };
 
@@ -648,6 +1014,7 @@ mod tests {
             artifact_path: PathBuf::from("/nonexistent/file.bin"),
             builder_id: "error-test".to_string(),
             output_format: OutputFormat::JsonOnly,
+            hash_algorithm: HashChoice::Sha256,
             key_source: KeySource::Generate,
         };
 
@@ -675,6 +1042,8 @@ mod tests {
             artifact_path: test_path,
             attestation_path: PathBuf::from("/nonexistent/attestation.json"),
             force_json: true,
+            detached_signature_path: None,
+            expected_verification_key: None,
         };
 
         let result = verify_attestation(verification_config);
@@ -699,6 +1068,7 @@ mod tests {
             artifact_path: test_path.clone(),
             builder_id: "details-test".to_string(),
             output_format: OutputFormat::JsonOnly,
+            hash_algorithm: HashChoice::Sha256,
             key_source: KeySource::Generate,
         };
 
@@ -714,6 +1084,8 @@ mod tests {
             artifact_path: test_path,
             attestation_path,
             force_json: true,
+            detached_signature_path: None,
+            expected_verification_key: None,
         };
 
         let verification_result = verify_attestation(verification_config)?;
@@ -739,4 +1111,498 @@ mod tests {
 
         Ok(())
     }
+
+    /// Create a detached-signature attestation and write both files to disk
+    fn write_detached_attestation(
+        artifact_path: PathBuf,
+    ) -> Result<(AttestationResult, NamedTempFile, NamedTempFile)> {
+        let config = AttestationConfig {
+            artifact_path,
+            builder_id: "detached-builder".to_string(),
+            output_format: OutputFormat::DetachedSignature,
+            hash_algorithm: HashChoice::Sha256,
+            key_source: KeySource::Generate,
+        };
+        let result = create_signed_attestation(config)?;
+
+        let mut attestation_file = NamedTempFile::new()?;
+        attestation_file.write_all(&serde_json::to_vec_pretty(&result.attestation)?)?;
+        let mut signature_file = NamedTempFile::new()?;
+        signature_file.write_all(&result.serialized_output)?;
+
+        Ok((result, attestation_file, signature_file))
+    }
+
+    #[test]
+    fn test_detached_signature_verification() -> Result<()> {
+        let mut test_file = NamedTempFile::new()?;
+        test_file.write_all(b"release artifact")?;
+        let test_path = test_file.path().to_path_buf();
+
+        let (result, attestation_file, signature_file) =
+            write_detached_attestation(test_path.clone())?;
+
+        // The detached file carries only the signature and public key
+        let detached: DetachedSignature = serde_json::from_slice(&result.serialized_output)?;
+        assert_eq!(detached.signature.len(), 128);
+        assert_eq!(
+            Some(detached.verification_key),
+            result.verification_info.map(|info| info.verification_key)
+        );
+
+        let verification_result = verify_attestation(VerificationConfig {
+            artifact_path: test_path,
+            attestation_path: attestation_file.path().to_path_buf(),
+            force_json: false,
+            detached_signature_path: Some(signature_file.path().to_path_buf()),
+            expected_verification_key: None,
+        })?;
+        assert!(verification_result.is_valid);
+        assert_eq!(
+            verification_result.verification_details.envelope_verified,
+            Some(true)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_detached_signature_tampered_attestation() -> Result<()> {
+        let mut test_file = NamedTempFile::new()?;
+        test_file.write_all(b"release artifact")?;
+        let test_path = test_file.path().to_path_buf();
+
+        let (result, _, signature_file) = write_detached_attestation(test_path.clone())?;
+
+        // Rewrite the builder id in the shipped attestation JSON
+        let mut tampered = result.attestation.clone();
+        tampered.builder_id = "attacker".to_string();
+        let mut tampered_file = NamedTempFile::new()?;
+        tampered_file.write_all(&serde_json::to_vec_pretty(&tampered)?)?;
+
+        let result = verify_attestation(VerificationConfig {
+            artifact_path: test_path,
+            attestation_path: tampered_file.path().to_path_buf(),
+            force_json: false,
+            detached_signature_path: Some(signature_file.path().to_path_buf()),
+            expected_verification_key: None,
+        });
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Detached signature verification failed"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_detached_signature_from_unexpected_key() -> Result<()> {
+        use ed25519_dalek::Signer;
+
+        let mut test_file = NamedTempFile::new()?;
+        test_file.write_all(b"release artifact")?;
+        let test_path = test_file.path().to_path_buf();
+
+        let (result, attestation_file, signature_file) =
+            write_detached_attestation(test_path.clone())?;
+        let signer_key = result.verification_info.expect("key").verification_key;
+
+        // An attacker edits the attestation and re-signs it with their own key
+        let mut forged = result.attestation.clone();
+        forged.builder_id = "attacker".to_string();
+        let attacker_key = ed25519_dalek::SigningKey::generate(&mut rand_core::OsRng);
+        let forged_signature = DetachedSignature {
+            signature: hex::encode(
+                attacker_key
+                    .sign(&canonical_attestation_bytes(&forged)?)
+                    .to_bytes(),
+            ),
+            verification_key: hex::encode(attacker_key.verifying_key().to_bytes()),
+        };
+        let mut forged_file = NamedTempFile::new()?;
+        forged_file.write_all(&serde_json::to_vec(&forged)?)?;
+        let mut forged_signature_file = NamedTempFile::new()?;
+        forged_signature_file.write_all(&serde_json::to_vec(&forged_signature)?)?;
+
+        let config =
+            |attestation: &NamedTempFile, signature: &NamedTempFile, expected| VerificationConfig {
+                artifact_path: test_path.clone(),
+                attestation_path: attestation.path().to_path_buf(),
+                force_json: false,
+                detached_signature_path: Some(signature.path().to_path_buf()),
+                expected_verification_key: expected,
+            };
+
+        // The forgery is self-consistent, so only a pinned key catches it
+        assert!(verify_attestation(config(&forged_file, &forged_signature_file, None))?.is_valid);
+        let err = verify_attestation(config(
+            &forged_file,
+            &forged_signature_file,
+            Some(signer_key.clone()),
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("expected"));
+
+        // The genuine signature passes with its key pinned
+        let genuine = verify_attestation(config(
+            &attestation_file,
+            &signature_file,
+            Some(signer_key.clone()),
+        ))?;
+        assert!(genuine.is_valid);
+
+        // Pinning a key without a detached signature is a usage error
+        let err = verify_attestation(VerificationConfig {
+            detached_signature_path: None,
+            ..config(&attestation_file, &signature_file, Some(signer_key))
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("requires a detached signature"));
+
+        Ok(())
+    }
+
+    /// Detached signature made before attestations recorded their hash algorithm
+    #[test]
+    fn test_detached_signature_from_before_hash_alg_field() -> Result<()> {
+        const ARTIFACT: &[u8] = b"sealedge attestation fixture\n";
+        const ATTESTATION: &str = r#"{"artifact_hash":"8852dc4f99605fe6105fa3e9e87a1b9fe590a706f685eb99c0bccd2583c63120","artifact_name":"artifact.bin","source_commit_hash":"unknown","builder_id":"fixture-builder","timestamp":"2026-10-17T01:57:07.075166787+00:00"}"#;
+        const SIGNATURE: &str = r#"{
+  "signature": "a96c9da24cbd6d4d28e46a62dfb8bb6496f895967d78d05fd3f6697291df1b1b26a7c959add3c22ac936d788f047c4470b95fb435fe3266e580e61c8ba36780a",
+  "verification_key": "ea4a6c63e29c520abef5507b132ec5f9954776aebebe7b92421eea691446d22c"
+}"#;
+
+        // A SHA-256 attestation still serializes to exactly the signed bytes
+        let attestation: Attestation = serde_json::from_str(ATTESTATION)?;
+        assert_eq!(attestation.artifact_hash_alg, "sha256");
+        assert_eq!(
+            canonical_attestation_bytes(&attestation)?,
+            ATTESTATION.as_bytes()
+        );
+
+        let mut artifact_file = NamedTempFile::new()?;
+        artifact_file.write_all(ARTIFACT)?;
+        let mut attestation_file = NamedTempFile::new()?;
+        attestation_file.write_all(ATTESTATION.as_bytes())?;
+        let mut signature_file = NamedTempFile::new()?;
+        signature_file.write_all(SIGNATURE.as_bytes())?;
+
+        let result = verify_attestation(VerificationConfig {
+            artifact_path: artifact_file.path().to_path_buf(),
+            attestation_path: attestation_file.path().to_path_buf(),
+            force_json: false,
+            detached_signature_path: Some(signature_file.path().to_path_buf()),
+            expected_verification_key: None,
+        })?;
+        assert!(result.is_valid);
+        assert_eq!(result.verification_details.envelope_verified, Some(true));
+        assert_eq!(
+            result.verification_details.hash_algorithm,
+            HashChoice::Sha256
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_streaming_hash_large_artifact() -> Result<()> {
+        use sha2::{Digest, Sha256};
+
+        // 100 MB, deliberately not a multiple of the read buffer size
+        let size = 100 * 1024 * 1024 + 17;
+        let mut test_file = NamedTempFile::new()?;
+        let block: Vec<u8> = (0..HASH_BUFFER_SIZE).map(|i| (i % 251) as u8).collect();
+        let mut remaining = size;
+        while remaining > 0 {
+            let n = remaining.min(block.len());
+            test_file.write_all(&block[..n])?;
+            remaining -= n;
+        }
+        test_file.flush()?;
+        let test_path = test_file.path().to_path_buf();
+
+        let config = AttestationConfig {
+            artifact_path: test_path.clone(),
+            builder_id: "large-artifact".to_string(),
+            output_format: OutputFormat::JsonOnly,
+            hash_algorithm: HashChoice::Sha256,
+            key_source: KeySource::Generate,
+        };
+        let result = create_signed_attestation(config)?;
+
+        // Reference digest computed by reading the whole file into memory
+        let reference = format!("{:x}", Sha256::digest(std::fs::read(&test_path)?));
+        assert_eq!(result.attestation.artifact_hash, reference);
+
+        let (streamed_hash, streamed_size) = hash_file(&test_path, HashChoice::Sha256)?;
+        assert_eq!(streamed_hash, reference);
+        assert_eq!(streamed_size, size as u64);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hash_algorithm_roundtrip() -> Result<()> {
+        let mut test_file = NamedTempFile::new()?;
+        test_file.write_all(b"multi-algorithm artifact")?;
+        let test_path = test_file.path().to_path_buf();
+
+        for (choice, hex_len) in [
+            (HashChoice::Sha256, 64),
+            (HashChoice::Sha512, 128),
+            (HashChoice::Blake3, 64),
+        ] {
+            let config = AttestationConfig {
+                artifact_path: test_path.clone(),
+                builder_id: "hash-alg-test".to_string(),
+                output_format: OutputFormat::SealedEnvelope,
+                hash_algorithm: choice,
+                key_source: KeySource::Generate,
+            };
+            let result = create_signed_attestation(config)?;
+            assert_eq!(result.attestation.artifact_hash_alg, choice.as_str());
+            assert_eq!(result.attestation.artifact_hash.len(), hex_len);
+
+            let mut attestation_file = NamedTempFile::new()?;
+            attestation_file.write_all(&result.serialized_output)?;
+
+            let verification_result = verify_attestation(VerificationConfig {
+                artifact_path: test_path.clone(),
+                attestation_path: attestation_file.path().to_path_buf(),
+                force_json: false,
+                detached_signature_path: None,
+                expected_verification_key: None,
+            })?;
+            assert!(verification_result.is_valid, "{choice} should verify");
+            assert_eq!(
+                verification_result.verification_details.hash_algorithm,
+                choice
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recorded_hash_algorithm_drives_verification() -> Result<()> {
+        let mut test_file = NamedTempFile::new()?;
+        test_file.write_all(b"recorded algorithm artifact")?;
+        let test_path = test_file.path().to_path_buf();
+
+        let config = AttestationConfig {
+            artifact_path: test_path.clone(),
+            builder_id: "recorded-alg".to_string(),
+            output_format: OutputFormat::JsonOnly,
+            hash_algorithm: HashChoice::Sha256,
+            key_source: KeySource::Generate,
+        };
+        let result = create_signed_attestation(config)?;
+
+        // Attestations written before the field existed default to sha256
+        let mut legacy = serde_json::to_value(&result.attestation)?;
+        legacy
+            .as_object_mut()
+            .expect("object")
+            .remove("artifact_hash_alg");
+        let mut legacy_file = NamedTempFile::new()?;
+        legacy_file.write_all(&serde_json::to_vec(&legacy)?)?;
+        let verification_result = verify_attestation(VerificationConfig {
+            artifact_path: test_path.clone(),
+            attestation_path: legacy_file.path().to_path_buf(),
+            force_json: true,
+            detached_signature_path: None,
+            expected_verification_key: None,
+        })?;
+        assert!(verification_result.is_valid);
+        assert_eq!(
+            verification_result.verification_details.hash_algorithm,
+            HashChoice::Sha256
+        );
+
+        // Swapping the recorded selector makes the verifier use sha512 and fail
+        let mut relabeled = result.attestation.clone();
+        relabeled.artifact_hash_alg = "sha512".to_string();
+        let mut relabeled_file = NamedTempFile::new()?;
+        relabeled_file.write_all(&serde_json::to_vec(&relabeled)?)?;
+        let verification_result = verify_attestation(VerificationConfig {
+            artifact_path: test_path.clone(),
+            attestation_path: relabeled_file.path().to_path_buf(),
+            force_json: true,
+            detached_signature_path: None,
+            expected_verification_key: None,
+        })?;
+        assert!(!verification_result.is_valid);
+        assert_eq!(
+            verification_result.verification_details.computed_hash.len(),
+            128
+        );
+
+        // An unknown algorithm is rejected outright
+        let mut unknown = result.attestation.clone();
+        unknown.artifact_hash_alg = "md5".to_string();
+        let mut unknown_file = NamedTempFile::new()?;
+        unknown_file.write_all(&serde_json::to_vec(&unknown)?)?;
+        let err = verify_attestation(VerificationConfig {
+            artifact_path: test_path,
+            attestation_path: unknown_file.path().to_path_buf(),
+            force_json: true,
+            detached_signature_path: None,
+            expected_verification_key: None,
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("Unknown artifact hash algorithm"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_in_toto_link_export() {
+        let attestation = Attestation {
+            artifact_hash: "ab".repeat(32),
+            artifact_hash_alg: "sha256".to_string(),
+            artifact_name: "sealedge-cli".to_string(),
+            source_commit_hash: "1f2e3d4c5b6a79881726354453627180f9e8d7c6".to_string(),
+            builder_id: "ci-job-123".to_string(),
+            timestamp: "2025-01-01T00:00:00+00:00".to_string(),
+        };
+
+        let link = to_in_toto_link(&attestation);
+
+        assert_eq!(link["_type"], "link");
+        assert_eq!(
+            link["products"]["sealedge-cli"]["sha256"],
+            attestation.artifact_hash
+        );
+        assert_eq!(
+            link["materials"]["git+commit:1f2e3d4c5b6a79881726354453627180f9e8d7c6"]["sha1"],
+            attestation.source_commit_hash
+        );
+        assert_eq!(link["environment"]["builder_id"], "ci-job-123");
+        assert_eq!(link["environment"]["timestamp"], attestation.timestamp);
+
+        // Unknown commits produce no materials
+        let unknown = Attestation {
+            source_commit_hash: "unknown".to_string(),
+            ..attestation
+        };
+        let link = to_in_toto_link(&unknown);
+        assert!(link["materials"].as_object().expect("object").is_empty());
+    }
+
+    /// Write `list` signed by `signing_key` to a temp file
+    fn write_signed_revocations(
+        list: &RevocationList,
+        signing_key: &ed25519_dalek::SigningKey,
+    ) -> Result<NamedTempFile> {
+        let mut file = NamedTempFile::new()?;
+        file.write_all(&serde_json::to_vec_pretty(&list.sign(signing_key)?)?)?;
+        Ok(file)
+    }
+
+    #[test]
+    fn test_revocation_list_passes_unrevoked_artifact() -> Result<()> {
+        let mut test_file = NamedTempFile::new()?;
+        test_file.write_all(b"release artifact")?;
+        let test_path = test_file.path().to_path_buf();
+        let (_, attestation_file, signature_file) = write_detached_attestation(test_path.clone())?;
+
+        let issuer = ed25519_dalek::SigningKey::generate(&mut rand_core::OsRng);
+        let mut list = RevocationList::new();
+        // Same builder, different artifact
+        list.revoke_builder_artifact("detached-builder", &"00".repeat(32), "unrelated build");
+        let list_file = write_signed_revocations(&list, &issuer)?;
+        let list = RevocationList::load_signed(list_file.path(), &issuer.verifying_key())?;
+
+        let result = verify_attestation_with_revocation(
+            VerificationConfig {
+                artifact_path: test_path,
+                attestation_path: attestation_file.path().to_path_buf(),
+                force_json: false,
+                detached_signature_path: Some(signature_file.path().to_path_buf()),
+                expected_verification_key: None,
+            },
+            &list,
+        )?;
+        assert!(result.is_valid);
+        assert_eq!(result.status(), AttestationStatus::Valid);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_revoked_attestation_reports_reason() -> Result<()> {
+        let mut test_file = NamedTempFile::new()?;
+        test_file.write_all(b"release artifact")?;
+        let test_path = test_file.path().to_path_buf();
+        let (result, attestation_file, signature_file) =
+            write_detached_attestation(test_path.clone())?;
+        let config = || VerificationConfig {
+            artifact_path: test_path.clone(),
+            attestation_path: attestation_file.path().to_path_buf(),
+            force_json: false,
+            detached_signature_path: Some(signature_file.path().to_path_buf()),
+            expected_verification_key: None,
+        };
+
+        // Revoked by attestation hash
+        let mut list = RevocationList::new();
+        list.revoke_attestation(
+            &attestation_hash(&result.attestation)?,
+            "build host compromised",
+        );
+        let verification = verify_attestation_with_revocation(config(), &list)?;
+        assert!(!verification.is_valid);
+        assert_eq!(
+            verification.status(),
+            AttestationStatus::Revoked {
+                reason: "build host compromised".to_string()
+            }
+        );
+        // The artifact itself still matches; revocation is not a hash mismatch
+        assert_eq!(
+            verification.verification_details.computed_hash,
+            verification.verification_details.expected_hash
+        );
+
+        // Revoked by builder+artifact pair
+        let mut list = RevocationList::new();
+        list.revoke_builder_artifact(
+            &result.attestation.builder_id,
+            &result.attestation.artifact_hash,
+            "signing key leaked",
+        );
+        let verification = verify_attestation_with_revocation(config(), &list)?;
+        assert_eq!(
+            verification.status(),
+            AttestationStatus::Revoked {
+                reason: "signing key leaked".to_string()
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tampered_revocation_list_is_rejected() -> Result<()> {
+        let issuer = ed25519_dalek::SigningKey::generate(&mut rand_core::OsRng);
+        let mut list = RevocationList::new();
+        list.revoke_attestation(&"ab".repeat(32), "build host compromised");
+        let mut signed = list.sign(&issuer)?;
+
+        // Drop the entry but keep the original signature
+        signed.list.entries.clear();
+        let mut tampered = NamedTempFile::new()?;
+        tampered.write_all(&serde_json::to_vec_pretty(&signed)?)?;
+        let err = RevocationList::load_signed(tampered.path(), &issuer.verifying_key())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Revocation list signature verification failed"));
+
+        // A list signed by any other key is not trusted
+        let other = ed25519_dalek::SigningKey::generate(&mut rand_core::OsRng);
+        let forged = write_signed_revocations(&list, &other)?;
+        assert!(RevocationList::load_signed(forged.path(), &issuer.verifying_key()).is_err());
+
+        Ok(())
+    }
 }
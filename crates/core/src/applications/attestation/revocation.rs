@@ -0,0 +1,162 @@
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// MPL-2.0: https://mozilla.org/MPL/2.0/
+// Project: sealedge — Privacy and trust at the edge.
+
+//! Attestation revocation lists
+//!
+//! A revocation list withdraws trust from attestations that were validly signed
+//! but should no longer be accepted, e.g. a build later found to be compromised.
+//! Entries match either one attestation, by the SHA-256 of its canonical bytes
+//! (see [`canonical_attestation_bytes`]), or every attestation a builder issued
+//! for an artifact hash.
+//!
+//! Lists are distributed as signed JSON:
+//!
+//! ```text
+//! { "list": { "entries": [...] }, "signature": "<hex>", "verification_key": "<hex>" }
+//! ```
+//!
+//! The signature covers the compact `serde_json` form of `list`. Loading checks it
+//! against a key the caller already trusts, not the key embedded in the file.
+
+use anyhow::{Context, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+use super::{canonical_attestation_bytes, Attestation};
+
+/// What a revocation entry matches
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RevokedTarget {
+    /// A single attestation, by SHA-256 of its canonical bytes (hex encoded)
+    Attestation { attestation_hash: String },
+    /// Every attestation `builder_id` issued for `artifact_hash`
+    BuilderArtifact {
+        builder_id: String,
+        artifact_hash: String,
+    },
+}
+
+/// One revoked attestation or builder+artifact pair
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RevocationEntry {
+    /// What is revoked
+    #[serde(flatten)]
+    pub target: RevokedTarget,
+    /// Why it was revoked
+    pub reason: String,
+}
+
+/// Set of revoked attestations
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RevocationList {
+    /// Revocation entries, in the order they were added
+    pub entries: Vec<RevocationEntry>,
+}
+
+/// Signed revocation list file
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SignedRevocationList {
+    /// The revocation list covered by `signature`
+    pub list: RevocationList,
+    /// Ed25519 signature over the canonical list JSON (hex encoded)
+    pub signature: String,
+    /// Public key of the list issuer (hex encoded)
+    pub verification_key: String,
+}
+
+/// SHA-256 of an attestation's canonical bytes, as used by [`RevokedTarget::Attestation`]
+pub fn attestation_hash(attestation: &Attestation) -> Result<String> {
+    Ok(hex::encode(Sha256::digest(canonical_attestation_bytes(
+        attestation,
+    )?)))
+}
+
+impl RevocationList {
+    /// Empty revocation list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Revoke a single attestation
+    pub fn revoke_attestation(&mut self, attestation_hash: &str, reason: &str) {
+        self.entries.push(RevocationEntry {
+            target: RevokedTarget::Attestation {
+                attestation_hash: attestation_hash.to_string(),
+            },
+            reason: reason.to_string(),
+        });
+    }
+
+    /// Revoke every attestation `builder_id` issued for `artifact_hash`
+    pub fn revoke_builder_artifact(&mut self, builder_id: &str, artifact_hash: &str, reason: &str) {
+        self.entries.push(RevocationEntry {
+            target: RevokedTarget::BuilderArtifact {
+                builder_id: builder_id.to_string(),
+                artifact_hash: artifact_hash.to_string(),
+            },
+            reason: reason.to_string(),
+        });
+    }
+
+    /// First entry revoking `attestation`, if any
+    pub fn find(&self, attestation: &Attestation) -> Result<Option<&RevocationEntry>> {
+        let hash = attestation_hash(attestation)?;
+        Ok(self.entries.iter().find(|entry| match &entry.target {
+            RevokedTarget::Attestation { attestation_hash } => *attestation_hash == hash,
+            RevokedTarget::BuilderArtifact {
+                builder_id,
+                artifact_hash,
+            } => {
+                *builder_id == attestation.builder_id && *artifact_hash == attestation.artifact_hash
+            }
+        }))
+    }
+
+    /// Sign the list for distribution
+    pub fn sign(&self, signing_key: &SigningKey) -> Result<SignedRevocationList> {
+        let bytes = serde_json::to_vec(self).context("Failed to serialize revocation list")?;
+        Ok(SignedRevocationList {
+            list: self.clone(),
+            signature: hex::encode(signing_key.sign(&bytes).to_bytes()),
+            verification_key: hex::encode(signing_key.verifying_key().to_bytes()),
+        })
+    }
+
+    /// Load a signed revocation list file, checking it was signed by `trusted_key`
+    pub fn load_signed(path: &Path, trusted_key: &VerifyingKey) -> Result<Self> {
+        let data = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read revocation list: {}", path.display()))?;
+        let signed: SignedRevocationList =
+            serde_json::from_str(&data).context("Failed to parse revocation list")?;
+        signed.verify(trusted_key)
+    }
+}
+
+impl SignedRevocationList {
+    /// Check the signature against `trusted_key` and return the list
+    pub fn verify(self, trusted_key: &VerifyingKey) -> Result<RevocationList> {
+        if hex::encode(trusted_key.to_bytes()) != self.verification_key {
+            return Err(anyhow::anyhow!(
+                "Revocation list is not signed by the trusted key"
+            ));
+        }
+
+        let sig_bytes: [u8; 64] = hex::decode(&self.signature)
+            .context("Invalid signature encoding in revocation list")?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Invalid signature length in revocation list"))?;
+        let signature = Signature::from_bytes(&sig_bytes);
+
+        let bytes =
+            serde_json::to_vec(&self.list).context("Failed to serialize revocation list")?;
+        trusted_key
+            .verify(&bytes, &signature)
+            .map_err(|_| anyhow::anyhow!("Revocation list signature verification failed"))?;
+
+        Ok(self.list)
+    }
+}
@@ -39,11 +39,26 @@
 //! let assignment_envelope = assign_receipt(&receipt_envelope, &bob_key, &charlie_key.verifying_key(), Some("Transfer to Charlie".to_string()))?;
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
+//!
+//! ## Key Comparisons
+//!
+//! Ownership checks (assigner against previous beneficiary, issuer against
+//! previous beneficiary, rotation links) compare keys in constant time. Amount
+//! sums and envelope hash lookups are structural and use plain `==`.
 
+use crate::clock::{Clock, SystemClock};
+use crate::envelope::keys_equal;
 use crate::Envelope;
 use anyhow::{Context, Result};
 use ed25519_dalek::{SigningKey, VerifyingKey};
 use serde::{Deserialize, Serialize};
+use x25519_dalek::{PublicKey as X25519PublicKey, StaticSecret};
+
+mod replay;
+mod rotation;
+
+pub use replay::{MemoryReceiptStore, SeenReceiptRegistry, SeenReceiptStore};
+pub use rotation::KeyRotation;
 
 /// Represents a transferable claim, forming the payload of a Sealedge Envelope.
 ///
@@ -64,6 +79,14 @@ pub struct OwnershipReceipt {
     pub description: Option<String>,
     /// Timestamp when this receipt was created (seconds since UNIX epoch)
     pub created_at: u64,
+    /// Optional expiry time (seconds since UNIX epoch) after which the claim is void.
+    /// Receipts serialized before this field existed deserialize as `None`.
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    /// Hashes of every envelope consolidated by [`merge_receipts`], in input order.
+    /// Empty for ordinary receipts, whose only parent is `prev_envelope_hash`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merge_parents: Vec<[u8; 32]>,
 }
 
 impl OwnershipReceipt {
@@ -74,18 +97,32 @@ impl OwnershipReceipt {
         amount: u64,
         description: Option<String>,
     ) -> Self {
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        Self::new_origin_with_clock(
+            issuer_key,
+            beneficiary_key,
+            amount,
+            description,
+            &SystemClock,
+        )
+    }
 
+    /// Like [`OwnershipReceipt::new_origin`], but stamps `created_at` from `clock`
+    pub fn new_origin_with_clock(
+        issuer_key: &SigningKey,
+        beneficiary_key: &VerifyingKey,
+        amount: u64,
+        description: Option<String>,
+        clock: &dyn Clock,
+    ) -> Self {
         OwnershipReceipt {
             issuer: issuer_key.verifying_key().to_bytes(),
             beneficiary: beneficiary_key.to_bytes(),
             amount,
             prev_envelope_hash: None,
             description,
-            created_at: timestamp,
+            created_at: clock.now_secs(),
+            expires_at: None,
+            merge_parents: Vec::new(),
         }
     }
 
@@ -97,19 +134,65 @@ impl OwnershipReceipt {
         prev_envelope_hash: [u8; 32],
         description: Option<String>,
     ) -> Self {
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        Self::new_assignment_with_clock(
+            issuer_key,
+            beneficiary_key,
+            amount,
+            prev_envelope_hash,
+            description,
+            &SystemClock,
+        )
+    }
 
+    /// Like [`OwnershipReceipt::new_assignment`], but stamps `created_at` from `clock`
+    pub fn new_assignment_with_clock(
+        issuer_key: &SigningKey,
+        beneficiary_key: &VerifyingKey,
+        amount: u64,
+        prev_envelope_hash: [u8; 32],
+        description: Option<String>,
+        clock: &dyn Clock,
+    ) -> Self {
         OwnershipReceipt {
             issuer: issuer_key.verifying_key().to_bytes(),
             beneficiary: beneficiary_key.to_bytes(),
             amount,
             prev_envelope_hash: Some(prev_envelope_hash),
             description,
-            created_at: timestamp,
+            created_at: clock.now_secs(),
+            expires_at: None,
+            merge_parents: Vec::new(),
+        }
+    }
+
+    /// Set an expiry time (seconds since UNIX epoch) on this receipt
+    pub fn with_expiry(mut self, expires_at: u64) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Check whether this receipt has expired at the given time (seconds since UNIX epoch)
+    ///
+    /// A receipt is expired from the `expires_at` second itself, the same boundary
+    /// as [`Envelope::is_expired_at`].
+    pub fn is_expired_at(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Amount left for the beneficiary after deducting a fee of `fee_bps` basis points.
+    ///
+    /// The fee is rounded down, so any remainder stays with the beneficiary.
+    pub fn amount_after_fees(&self, fee_bps: u16) -> Result<u64> {
+        if fee_bps > 10_000 {
+            return Err(anyhow::anyhow!(
+                "Fee of {} bps exceeds 10000 bps (100%)",
+                fee_bps
+            ));
         }
+
+        // u128 intermediate so amount * bps cannot overflow
+        let fee = (self.amount as u128 * fee_bps as u128 / 10_000) as u64;
+        Ok(self.amount - fee)
     }
 
     /// Get the issuer's public key
@@ -129,8 +212,22 @@ impl OwnershipReceipt {
         self.prev_envelope_hash.is_none()
     }
 
+    /// Hashes of all parent envelopes: the merged inputs, or the single previous link
+    pub fn parent_hashes(&self) -> Vec<[u8; 32]> {
+        if self.merge_parents.is_empty() {
+            self.prev_envelope_hash.into_iter().collect()
+        } else {
+            self.merge_parents.clone()
+        }
+    }
+
     /// Validate the business logic of this receipt
     pub fn validate(&self) -> Result<()> {
+        self.validate_with_clock(&SystemClock)
+    }
+
+    /// Like [`OwnershipReceipt::validate`], but takes the current time from `clock`
+    pub fn validate_with_clock(&self, clock: &dyn Clock) -> Result<()> {
         if self.amount == 0 {
             return Err(anyhow::anyhow!("OwnershipReceipt amount cannot be zero"));
         }
@@ -140,10 +237,7 @@ impl OwnershipReceipt {
         self.beneficiary_key().context("Invalid beneficiary key")?;
 
         // Check timestamp is reasonable (not too far in future)
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        let now = clock.now_secs();
 
         if self.created_at > now + 300 {
             // Not more than 5 minutes in future
@@ -152,10 +246,99 @@ impl OwnershipReceipt {
             ));
         }
 
+        if let Some(expires_at) = self.expires_at {
+            if self.is_expired_at(now) {
+                return Err(anyhow::anyhow!(
+                    "OwnershipReceipt expired at {} (now {})",
+                    expires_at,
+                    now
+                ));
+            }
+        }
+
         Ok(())
     }
 }
 
+/// Serialization format for the OwnershipReceipt payload sealed inside an Envelope.
+///
+/// Payloads produced by [`create_receipt_with_encoding`] start with a one-byte
+/// encoding tag so readers can auto-detect the format. Untagged payloads (a bare
+/// JSON object, as written by [`create_receipt`]) are still accepted as JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiptEncoding {
+    /// `serde_json` encoding (human-readable, larger)
+    Json,
+    /// CBOR encoding via `ciborium` (compact, for constrained links)
+    Cbor,
+}
+
+impl ReceiptEncoding {
+    /// Payload tag byte for this encoding
+    pub fn tag(self) -> u8 {
+        match self {
+            ReceiptEncoding::Json => 0x01,
+            ReceiptEncoding::Cbor => 0x02,
+        }
+    }
+
+    /// Look up an encoding from its payload tag byte
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0x01 => Some(ReceiptEncoding::Json),
+            0x02 => Some(ReceiptEncoding::Cbor),
+            _ => None,
+        }
+    }
+}
+
+/// Serialize a receipt into an envelope payload.
+///
+/// `None` produces the legacy untagged JSON payload; `Some` prefixes the tag byte.
+pub(crate) fn encode_receipt_payload(
+    receipt: &OwnershipReceipt,
+    encoding: Option<ReceiptEncoding>,
+) -> Result<Vec<u8>> {
+    let Some(encoding) = encoding else {
+        return serde_json::to_vec(receipt).context("Failed to serialize receipt");
+    };
+
+    let mut payload = vec![encoding.tag()];
+    match encoding {
+        ReceiptEncoding::Json => serde_json::to_writer(&mut payload, receipt)
+            .context("Failed to serialize receipt as JSON")?,
+        ReceiptEncoding::Cbor => ciborium::into_writer(receipt, &mut payload)
+            .map_err(|e| anyhow::anyhow!("Failed to serialize receipt as CBOR: {e}"))?,
+    }
+    Ok(payload)
+}
+
+/// Deserialize a receipt from an envelope payload, auto-detecting its encoding.
+///
+/// Returns the encoding that was found, or `None` for a legacy untagged JSON payload.
+fn decode_receipt_payload(payload: &[u8]) -> Result<(OwnershipReceipt, Option<ReceiptEncoding>)> {
+    let (&tag, body) = payload
+        .split_first()
+        .ok_or_else(|| anyhow::anyhow!("Receipt payload is empty"))?;
+
+    if tag == b'{' {
+        let receipt = serde_json::from_slice(payload).context("Failed to deserialize receipt")?;
+        return Ok((receipt, None));
+    }
+
+    let encoding = ReceiptEncoding::from_tag(tag)
+        .ok_or_else(|| anyhow::anyhow!("Unknown receipt encoding tag: 0x{:02x}", tag))?;
+
+    let receipt = match encoding {
+        ReceiptEncoding::Json => {
+            serde_json::from_slice(body).context("Failed to deserialize JSON receipt")?
+        }
+        ReceiptEncoding::Cbor => ciborium::from_reader(body)
+            .map_err(|e| anyhow::anyhow!("Failed to deserialize CBOR receipt: {e}"))?,
+    };
+    Ok((receipt, Some(encoding)))
+}
+
 /// Creates a new, origin OwnershipReceipt wrapped in a signed Envelope.
 ///
 /// This function initiates a new chain of ownership. The Contract Writer creates
@@ -192,6 +375,137 @@ pub fn create_receipt(
         .context("Failed to seal receipt in envelope")
 }
 
+/// Creates a batch of origin OwnershipReceipts from a single issuer.
+///
+/// Every entry is built and validated before anything is sealed, so a single bad
+/// entry (e.g. a zero amount) fails the whole batch without producing any envelopes.
+///
+/// # Arguments
+/// * `issuer_key` - The private key of the entity creating the receipts
+/// * `entries` - Beneficiary public key, amount, and optional description for each receipt
+///
+/// # Returns
+/// One secure Envelope per entry, in input order, or an error naming the first invalid entry
+pub fn create_receipts_batch(
+    issuer_key: &SigningKey,
+    entries: &[(VerifyingKey, u64, Option<String>)],
+) -> Result<Vec<Envelope>> {
+    // Build and validate every receipt before sealing any of them
+    let receipts = entries
+        .iter()
+        .enumerate()
+        .map(|(index, (beneficiary_key, amount, description))| {
+            let receipt = OwnershipReceipt::new_origin(
+                issuer_key,
+                beneficiary_key,
+                *amount,
+                description.clone(),
+            );
+            receipt
+                .validate()
+                .with_context(|| format!("Batch entry {} failed validation", index))?;
+            Ok(receipt)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    receipts
+        .iter()
+        .zip(entries)
+        .enumerate()
+        .map(|(index, (receipt, (beneficiary_key, _, _)))| {
+            let payload = serde_json::to_vec(receipt)
+                .with_context(|| format!("Failed to serialize batch entry {}", index))?;
+            Envelope::seal(&payload, issuer_key, beneficiary_key)
+                .with_context(|| format!("Failed to seal batch entry {}", index))
+        })
+        .collect()
+}
+
+/// Creates a new, origin OwnershipReceipt with an explicit payload encoding.
+///
+/// Identical to [`create_receipt`] except that the payload is serialized with the
+/// requested [`ReceiptEncoding`] and prefixed with its tag byte. The envelope
+/// sealing path is unchanged.
+pub fn create_receipt_with_encoding(
+    issuer_key: &SigningKey,
+    beneficiary_key: &VerifyingKey,
+    amount: u64,
+    description: Option<String>,
+    encoding: ReceiptEncoding,
+) -> Result<Envelope> {
+    let receipt = OwnershipReceipt::new_origin(issuer_key, beneficiary_key, amount, description);
+
+    receipt
+        .validate()
+        .context("OwnershipReceipt validation failed")?;
+
+    let payload = encode_receipt_payload(&receipt, Some(encoding))?;
+
+    Envelope::seal(&payload, issuer_key, beneficiary_key)
+        .context("Failed to seal receipt in envelope")
+}
+
+/// Creates a new, origin OwnershipReceipt sealed to the beneficiary's X25519 encryption key.
+///
+/// The receipt records `beneficiary_key` (the Ed25519 identity) as its beneficiary,
+/// so chain checks work exactly as for [`create_receipt`]. Only the envelope's
+/// encryption uses `encryption_key`; the beneficiary opens it with
+/// [`extract_receipt_with_encryption_key`].
+pub fn create_receipt_with_encryption_key(
+    issuer_key: &SigningKey,
+    beneficiary_key: &VerifyingKey,
+    encryption_key: &X25519PublicKey,
+    amount: u64,
+    description: Option<String>,
+) -> Result<Envelope> {
+    let receipt = OwnershipReceipt::new_origin(issuer_key, beneficiary_key, amount, description);
+
+    receipt
+        .validate()
+        .context("OwnershipReceipt validation failed")?;
+
+    let payload = serde_json::to_vec(&receipt).context("Failed to serialize receipt")?;
+
+    Envelope::seal_for_encryption_key(&payload, issuer_key, beneficiary_key, encryption_key)
+        .context("Failed to seal receipt in envelope")
+}
+
+/// Verify and unseal the envelope being assigned, as its current beneficiary.
+///
+/// `decryption_key` is the assigner's X25519 secret, needed when the previous
+/// envelope was sealed to an encryption key; otherwise `assigner_key` unseals it.
+/// Returns the previous receipt together with the payload encoding it was written in.
+fn open_previous_receipt(
+    previous_envelope: &Envelope,
+    assigner_key: &SigningKey,
+    decryption_key: Option<&StaticSecret>,
+) -> Result<(OwnershipReceipt, Option<ReceiptEncoding>)> {
+    // Security Guard verifies the previous envelope first
+    if !previous_envelope.verify() {
+        return Err(anyhow::anyhow!("Previous envelope signature is invalid"));
+    }
+
+    // Check that the assigner is actually the current beneficiary
+    let previous_beneficiary = previous_envelope.beneficiary()?;
+    if !keys_equal(
+        previous_beneficiary.as_bytes(),
+        assigner_key.verifying_key().as_bytes(),
+    ) {
+        return Err(anyhow::anyhow!(
+            "Assigner key does not match previous beneficiary"
+        ));
+    }
+
+    // Unseal the previous envelope to get the actual amount
+    let previous_payload = match decryption_key {
+        Some(decryption_key) => previous_envelope.unseal_with_encryption_key(decryption_key),
+        None => previous_envelope.unseal(assigner_key),
+    }
+    .context("Failed to unseal previous envelope - assigner key may be invalid")?;
+
+    decode_receipt_payload(&previous_payload).context("Failed to deserialize previous receipt")
+}
+
 /// Assigns an existing OwnershipReceipt to a new beneficiary, creating a new chained Envelope.
 ///
 /// This creates a new link in the ownership chain. The current beneficiary (assigner)
@@ -211,27 +525,68 @@ pub fn assign_receipt(
     new_beneficiary_key: &VerifyingKey,
     description: Option<String>,
 ) -> Result<Envelope> {
-    // Security Guard verifies the previous envelope first
-    if !previous_envelope.verify() {
-        return Err(anyhow::anyhow!("Previous envelope signature is invalid"));
-    }
+    assign_sealed(
+        previous_envelope,
+        assigner_key,
+        None,
+        new_beneficiary_key,
+        None,
+        description,
+    )
+}
 
-    // Check that the assigner is actually the current beneficiary
-    let previous_beneficiary = previous_envelope.beneficiary()?;
-    if previous_beneficiary != assigner_key.verifying_key() {
-        return Err(anyhow::anyhow!(
-            "Assigner key does not match previous beneficiary"
-        ));
-    }
+/// Assigns an existing OwnershipReceipt, sealing the new envelope to an X25519 encryption key.
+///
+/// Like [`assign_receipt`], the new receipt records `new_beneficiary_key` (the
+/// Ed25519 identity) as its beneficiary, while the envelope is encrypted to
+/// `new_encryption_key`. Pass `assigner_decryption_key` when the previous envelope
+/// was itself sealed to the assigner's encryption key.
+///
+/// # Arguments
+/// * `previous_envelope` - The envelope containing the previous receipt
+/// * `assigner_key` - The private key of the current beneficiary (who is assigning)
+/// * `assigner_decryption_key` - The assigner's X25519 secret, if the previous envelope needs it
+/// * `new_beneficiary_key` - The identity key of the new beneficiary
+/// * `new_encryption_key` - The X25519 encryption key of the new beneficiary
+/// * `description` - Optional description for this assignment
+///
+/// # Returns
+/// A new secure Envelope containing the assignment receipt, or an error
+pub fn assign_receipt_with_encryption_key(
+    previous_envelope: &Envelope,
+    assigner_key: &SigningKey,
+    assigner_decryption_key: Option<&StaticSecret>,
+    new_beneficiary_key: &VerifyingKey,
+    new_encryption_key: &X25519PublicKey,
+    description: Option<String>,
+) -> Result<Envelope> {
+    assign_sealed(
+        previous_envelope,
+        assigner_key,
+        assigner_decryption_key,
+        new_beneficiary_key,
+        Some(new_encryption_key),
+        description,
+    )
+}
 
-    // Unseal the previous envelope to get the actual amount
-    let previous_payload = previous_envelope
-        .unseal(assigner_key)
-        .context("Failed to unseal previous envelope - assigner key may be invalid")?;
+/// Shared assignment path for [`assign_receipt`] and [`assign_receipt_with_encryption_key`]
+fn assign_sealed(
+    previous_envelope: &Envelope,
+    assigner_key: &SigningKey,
+    assigner_decryption_key: Option<&StaticSecret>,
+    new_beneficiary_key: &VerifyingKey,
+    new_encryption_key: Option<&X25519PublicKey>,
+    description: Option<String>,
+) -> Result<Envelope> {
+    // Verify, unseal, and deserialize the previous receipt to get the amount
+    let (previous_receipt, encoding) =
+        open_previous_receipt(previous_envelope, assigner_key, assigner_decryption_key)?;
 
-    // Deserialize the previous receipt to get the amount
-    let previous_receipt: OwnershipReceipt = serde_json::from_slice(&previous_payload)
-        .context("Failed to deserialize previous receipt")?;
+    // An expired claim cannot be transferred
+    previous_receipt
+        .validate()
+        .context("Cannot assign previous receipt")?;
 
     // Use the actual amount from the previous receipt
     let amount = previous_receipt.amount;
@@ -240,7 +595,7 @@ pub fn assign_receipt(
     let prev_hash = previous_envelope.hash()?;
 
     // Create the new receipt that represents the assignment
-    let assignment_receipt = OwnershipReceipt::new_assignment(
+    let mut assignment_receipt = OwnershipReceipt::new_assignment(
         assigner_key,
         new_beneficiary_key,
         amount,
@@ -248,18 +603,287 @@ pub fn assign_receipt(
         description,
     );
 
+    // A transferred claim keeps the validity window of its parent
+    assignment_receipt.expires_at = previous_receipt.expires_at;
+
     // Validate the business rules
     assignment_receipt
         .validate()
         .context("Assignment receipt validation failed")?;
 
-    // Serialize the business logic
-    let payload = serde_json::to_vec(&assignment_receipt)
+    // Serialize the business logic, keeping the encoding of the previous receipt
+    let payload = encode_receipt_payload(&assignment_receipt, encoding)
         .context("Failed to serialize assignment receipt")?;
 
     // Hand to Security Guard to seal the new assignment
-    Envelope::seal(&payload, assigner_key, new_beneficiary_key)
-        .context("Failed to seal assignment receipt in envelope")
+    match new_encryption_key {
+        Some(encryption_key) => Envelope::seal_for_encryption_key(
+            &payload,
+            assigner_key,
+            new_beneficiary_key,
+            encryption_key,
+        ),
+        None => Envelope::seal(&payload, assigner_key, new_beneficiary_key),
+    }
+    .context("Failed to seal assignment receipt in envelope")
+}
+
+/// Splits an existing OwnershipReceipt into several new receipts, one per split.
+///
+/// The current beneficiary (assigner) divides the claim of the previous receipt
+/// across multiple new beneficiaries. Every new receipt links back to the same
+/// parent envelope, and the split amounts must add up to exactly the parent amount.
+///
+/// # Arguments
+/// * `previous_envelope` - The envelope containing the receipt being split
+/// * `assigner_key` - The private key of the current beneficiary (who is splitting)
+/// * `splits` - The new beneficiaries and the amount each one receives
+/// * `description` - Optional description applied to every new receipt
+///
+/// # Returns
+/// One secure Envelope per split, in the same order as `splits`, or an error
+pub fn split_receipt(
+    previous_envelope: &Envelope,
+    assigner_key: &SigningKey,
+    splits: &[(VerifyingKey, u64)],
+    description: Option<String>,
+) -> Result<Vec<Envelope>> {
+    if splits.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Split must contain at least one beneficiary"
+        ));
+    }
+
+    let (previous_receipt, encoding) =
+        open_previous_receipt(previous_envelope, assigner_key, None)?;
+
+    // An expired claim cannot be split
+    previous_receipt
+        .validate()
+        .context("Cannot split previous receipt")?;
+
+    // The split amounts must account for the whole claim, no more and no less
+    let provided_total = splits
+        .iter()
+        .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+        .ok_or_else(|| anyhow::anyhow!("Split amounts overflow u64"))?;
+
+    if provided_total != previous_receipt.amount {
+        return Err(anyhow::anyhow!(
+            "Split amounts do not match previous receipt: expected total {}, provided {}",
+            previous_receipt.amount,
+            provided_total
+        ));
+    }
+
+    seal_splits(
+        previous_envelope,
+        &previous_receipt,
+        encoding,
+        assigner_key,
+        splits,
+        description,
+    )
+}
+
+/// Seal one chained receipt per split, all linked to the same parent envelope.
+///
+/// Every split is validated before any of them is sealed.
+fn seal_splits(
+    previous_envelope: &Envelope,
+    previous_receipt: &OwnershipReceipt,
+    encoding: Option<ReceiptEncoding>,
+    assigner_key: &SigningKey,
+    splits: &[(VerifyingKey, u64)],
+    description: Option<String>,
+) -> Result<Vec<Envelope>> {
+    // Every split links back to the same parent envelope
+    let prev_hash = previous_envelope.hash()?;
+
+    let receipts = splits
+        .iter()
+        .map(|(beneficiary_key, amount)| {
+            let mut split = OwnershipReceipt::new_assignment(
+                assigner_key,
+                beneficiary_key,
+                *amount,
+                prev_hash,
+                description.clone(),
+            );
+            split.expires_at = previous_receipt.expires_at;
+
+            split
+                .validate()
+                .context("Split receipt validation failed")?;
+            Ok(split)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    receipts
+        .iter()
+        .zip(splits)
+        .map(|(split, (beneficiary_key, _))| {
+            let payload = encode_receipt_payload(split, encoding)
+                .context("Failed to serialize split receipt")?;
+
+            Envelope::seal(&payload, assigner_key, beneficiary_key)
+                .context("Failed to seal split receipt in envelope")
+        })
+        .collect()
+}
+
+/// Merges several receipts held by the same beneficiary into one new receipt.
+///
+/// Every input must name `owner_key` as its beneficiary and still be valid. The
+/// new receipt carries the sum of the input amounts, links to the first input
+/// through `prev_envelope_hash`, and records every input hash in `merge_parents`.
+/// It expires with the earliest-expiring input.
+///
+/// # Arguments
+/// * `envelopes` - The envelopes being merged, at least two and all distinct
+/// * `owner_key` - The private key of the beneficiary of every input
+/// * `new_beneficiary_key` - The public key of the beneficiary of the merged receipt
+/// * `description` - Optional description for the merged receipt
+///
+/// # Returns
+/// A new secure Envelope containing the merged receipt, or an error naming the
+/// offending input
+pub fn merge_receipts(
+    envelopes: &[Envelope],
+    owner_key: &SigningKey,
+    new_beneficiary_key: &VerifyingKey,
+    description: Option<String>,
+) -> Result<Envelope> {
+    if envelopes.len() < 2 {
+        return Err(anyhow::anyhow!("Merge requires at least two receipts"));
+    }
+
+    let mut parents: Vec<[u8; 32]> = Vec::with_capacity(envelopes.len());
+    let mut total = 0u64;
+    let mut expires_at: Option<u64> = None;
+    let mut encoding = None;
+    for (i, envelope) in envelopes.iter().enumerate() {
+        let (receipt, found) = open_previous_receipt(envelope, owner_key, None)
+            .with_context(|| format!("Cannot merge receipt {}", i))?;
+        receipt
+            .validate()
+            .with_context(|| format!("Cannot merge receipt {}", i))?;
+
+        // Merging the same envelope twice would count its amount twice
+        let hash = envelope.hash()?;
+        if parents.contains(&hash) {
+            return Err(anyhow::anyhow!("Receipt {} appears more than once", i));
+        }
+        parents.push(hash);
+
+        total = total
+            .checked_add(receipt.amount)
+            .ok_or_else(|| anyhow::anyhow!("Merged amounts overflow u64"))?;
+        expires_at = match (expires_at, receipt.expires_at) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if i == 0 {
+            encoding = found;
+        }
+    }
+
+    let mut merged = OwnershipReceipt::new_assignment(
+        owner_key,
+        new_beneficiary_key,
+        total,
+        parents[0],
+        description,
+    );
+    merged.expires_at = expires_at;
+    merged.merge_parents = parents;
+
+    merged
+        .validate()
+        .context("Merged receipt validation failed")?;
+
+    let payload =
+        encode_receipt_payload(&merged, encoding).context("Failed to serialize merged receipt")?;
+
+    Envelope::seal(&payload, owner_key, new_beneficiary_key)
+        .context("Failed to seal merged receipt in envelope")
+}
+
+/// The envelopes produced by [`assign_receipt_with_fee`].
+#[derive(Debug, Clone)]
+pub struct FeeAssignment {
+    /// The receipt for the new beneficiary, carrying the amount after fees
+    pub beneficiary_envelope: Envelope,
+    /// The receipt for the fee collector, or `None` if the fee rounds down to zero
+    pub fee_envelope: Option<Envelope>,
+}
+
+/// Assigns an existing OwnershipReceipt to a new beneficiary while deducting a fee.
+///
+/// The parent amount is split into a beneficiary receipt and a fee receipt for the
+/// fee collector. The fee is `fee_bps` basis points of the parent amount, rounded
+/// down; both receipts are chained to the parent envelope.
+///
+/// # Arguments
+/// * `previous_envelope` - The envelope containing the previous receipt
+/// * `assigner_key` - The private key of the current beneficiary (who is assigning)
+/// * `new_beneficiary_key` - The public key of the new beneficiary
+/// * `fee_bps` - Fee in basis points (0..10000) of the parent amount; a 100% fee
+///   would leave the new beneficiary nothing and is rejected
+/// * `fee_collector_key` - The public key of the entity collecting the fee
+/// * `description` - Optional description applied to both receipts
+///
+/// # Returns
+/// The beneficiary and fee envelopes, or an error
+pub fn assign_receipt_with_fee(
+    previous_envelope: &Envelope,
+    assigner_key: &SigningKey,
+    new_beneficiary_key: &VerifyingKey,
+    fee_bps: u16,
+    fee_collector_key: &VerifyingKey,
+    description: Option<String>,
+) -> Result<FeeAssignment> {
+    if fee_bps >= 10_000 {
+        return Err(anyhow::anyhow!(
+            "Fee of {} bps must be below 10000 bps (100%): the beneficiary would receive nothing",
+            fee_bps
+        ));
+    }
+
+    let (previous_receipt, encoding) =
+        open_previous_receipt(previous_envelope, assigner_key, None)?;
+
+    // An expired claim cannot be transferred
+    previous_receipt
+        .validate()
+        .context("Cannot assign previous receipt")?;
+
+    let net_amount = previous_receipt.amount_after_fees(fee_bps)?;
+    let fee_amount = previous_receipt.amount - net_amount;
+
+    let mut splits = vec![(*new_beneficiary_key, net_amount)];
+    if fee_amount > 0 {
+        splits.push((*fee_collector_key, fee_amount));
+    }
+
+    let mut envelopes = seal_splits(
+        previous_envelope,
+        &previous_receipt,
+        encoding,
+        assigner_key,
+        &splits,
+        description,
+    )?
+    .into_iter();
+
+    let beneficiary_envelope = envelopes
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("Missing beneficiary envelope"))?;
+
+    Ok(FeeAssignment {
+        beneficiary_envelope,
+        fee_envelope: envelopes.next(),
+    })
 }
 
 /// Extract and verify a OwnershipReceipt from an Envelope
@@ -282,9 +906,9 @@ pub fn extract_receipt(
         .unseal(decryption_key)
         .context("Failed to unseal envelope")?;
 
-    // Deserialize the business logic
-    let receipt: OwnershipReceipt =
-        serde_json::from_slice(&payload).context("Failed to deserialize receipt from payload")?;
+    // Deserialize the business logic, whatever encoding it was written in
+    let (receipt, _) =
+        decode_receipt_payload(&payload).context("Failed to deserialize receipt from payload")?;
 
     // Validate the business rules
     receipt
@@ -294,7 +918,59 @@ pub fn extract_receipt(
     Ok(receipt)
 }
 
-/// Verify a chain of receipt assignments
+/// Extract and verify a OwnershipReceipt sealed to an X25519 encryption key
+///
+/// Counterpart of [`extract_receipt`] for envelopes from
+/// [`create_receipt_with_encryption_key`] and [`assign_receipt_with_encryption_key`].
+pub fn extract_receipt_with_encryption_key(
+    envelope: &Envelope,
+    decryption_key: &StaticSecret,
+) -> Result<OwnershipReceipt> {
+    let payload = envelope
+        .unseal_with_encryption_key(decryption_key)
+        .context("Failed to unseal envelope")?;
+
+    let (receipt, _) =
+        decode_receipt_payload(&payload).context("Failed to deserialize receipt from payload")?;
+
+    receipt
+        .validate()
+        .context("Extracted receipt validation failed")?;
+
+    Ok(receipt)
+}
+
+/// Extract and verify a OwnershipReceipt that must use a specific payload encoding
+///
+/// Like [`extract_receipt`], but fails if the payload is not tagged with `encoding`.
+pub fn extract_receipt_with_encoding(
+    envelope: &Envelope,
+    decryption_key: &SigningKey,
+    encoding: ReceiptEncoding,
+) -> Result<OwnershipReceipt> {
+    let payload = envelope
+        .unseal(decryption_key)
+        .context("Failed to unseal envelope")?;
+
+    let (receipt, found) =
+        decode_receipt_payload(&payload).context("Failed to deserialize receipt from payload")?;
+
+    if found != Some(encoding) {
+        return Err(anyhow::anyhow!(
+            "Receipt encoding mismatch: expected {:?}, found {}",
+            encoding,
+            found.map_or_else(|| "untagged JSON".to_string(), |e| format!("{e:?}"))
+        ));
+    }
+
+    receipt
+        .validate()
+        .context("Extracted receipt validation failed")?;
+
+    Ok(receipt)
+}
+
+/// Verify a chain of receipt assignments
 ///
 /// This function validates that a series of envelopes form a valid ownership chain.
 ///
@@ -304,44 +980,265 @@ pub fn extract_receipt(
 /// # Returns
 /// True if the chain is valid, false otherwise
 pub fn verify_receipt_chain(envelopes: &[Envelope]) -> bool {
+    verify_receipt_chain_with_rotations(envelopes, &[])
+}
+
+/// Verify a chain of receipt assignments across holder key rotations
+///
+/// Like [`verify_receipt_chain`], except a link also holds when its issuer is a
+/// key the previous beneficiary rotated to, directly or through several
+/// rotations. A rotation takes effect only for links listed after its
+/// [`KeyRotation::after`] envelope, so links the old key signed before rotating
+/// still verify; later links must use the new key. Rotations not signed by their
+/// old key are ignored, and a holder whose key was rotated to two different keys
+/// cannot issue at all.
+///
+/// Parent hashes are sealed inside the receipts, so without keys a link can only
+/// be checked against its neighbour: each envelope after the first must be issued
+/// by the beneficiary of the envelope before it, and no envelope may appear twice.
+/// A holder who assigns the same receipt twice therefore breaks the chain. Use
+/// [`verify_receipt_graph_with_rotations`] to follow each receipt's
+/// [`OwnershipReceipt::parent_hashes`] through splits and merges.
+///
+/// # Arguments
+/// * `envelopes` - The chain of envelopes, ordered from origin to final assignment
+/// * `rotations` - Key rotation records for holders in the chain
+///
+/// # Returns
+/// True if the chain is valid, false otherwise
+pub fn verify_receipt_chain_with_rotations(
+    envelopes: &[Envelope],
+    rotations: &[KeyRotation],
+) -> bool {
     if envelopes.is_empty() {
         return false;
     }
 
-    // Verify each envelope individually
+    // Verify each envelope individually, and list each only once
+    let mut hashes = Vec::with_capacity(envelopes.len());
     for envelope in envelopes {
         if !envelope.verify() {
             return false;
         }
+        match envelope.hash() {
+            Ok(hash) if !hashes.contains(&hash) => hashes.push(hash),
+            _ => return false,
+        }
     }
 
-    // Verify the chain links
-    for i in 1..envelopes.len() {
-        let prev_envelope = &envelopes[i - 1];
-        let current_envelope = &envelopes[i];
+    // The issuer of each envelope must hold the one before it
+    (1..envelopes.len())
+        .all(|i| issued_by_holder(&envelopes[i], &envelopes[i - 1], rotations, &hashes[..i]))
+}
 
-        // The issuer of the current envelope should be the beneficiary of the previous
-        let Ok(current_issuer) = current_envelope.issuer() else {
-            return false;
+/// Verify a receipt graph by following every receipt's parent hashes
+///
+/// Every envelope is verified and unsealed. Each receipt after the first must
+/// either be an origin or name, through [`OwnershipReceipt::parent_hashes`], only
+/// envelopes listed before it, and its issuer must be the beneficiary of every one
+/// of those parents (or a key that beneficiary rotated to).
+///
+/// # Arguments
+/// * `envelopes` - The envelopes, each listed after its parents
+/// * `keys` - The beneficiary's private key for each envelope, in the same order
+/// * `rotations` - Key rotation records for holders in the graph
+///
+/// # Returns
+/// `Ok(())`, or an error naming the offending receipt index
+pub fn verify_receipt_graph_with_rotations(
+    envelopes: &[Envelope],
+    keys: &[SigningKey],
+    rotations: &[KeyRotation],
+) -> Result<()> {
+    let graph = open_receipt_graph(envelopes, keys)?;
+    let hashes = envelopes
+        .iter()
+        .map(Envelope::hash)
+        .collect::<Result<Vec<_>>>()?;
+    for (i, envelope) in envelopes.iter().enumerate() {
+        if !envelope.verify() {
+            return Err(anyhow::anyhow!("Receipt {} failed verification", i));
+        }
+        for &parent in &graph[i].1 {
+            if !issued_by_holder(envelope, &envelopes[parent], rotations, &hashes[..i]) {
+                return Err(anyhow::anyhow!(
+                    "Receipt {} was not issued by the holder of receipt {}",
+                    i,
+                    parent
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether `current` was issued by the key speaking for the beneficiary of
+/// `parent` at `current`'s place in the chain: the beneficiary itself, or the end
+/// of the rotations that took effect in the envelopes hashing to `earlier`
+fn issued_by_holder(
+    current: &Envelope,
+    parent: &Envelope,
+    rotations: &[KeyRotation],
+    earlier: &[[u8; 32]],
+) -> bool {
+    let (Ok(issuer), Ok(holder)) = (current.issuer(), parent.beneficiary()) else {
+        return false;
+    };
+    !issuer.is_anonymous()
+        && KeyRotation::resolves_to(
+            rotations,
+            &holder.to_bytes(),
+            &issuer.verifying_key().to_bytes(),
+            earlier,
+        )
+}
+
+/// Unseal every envelope and resolve each receipt's parent hashes to the indices
+/// of earlier envelopes
+///
+/// The first receipt and any origin receipt are roots with no parents; every
+/// other receipt must link only to envelopes listed before it, and no envelope
+/// may be listed twice.
+fn open_receipt_graph(
+    envelopes: &[Envelope],
+    keys: &[SigningKey],
+) -> Result<Vec<(OwnershipReceipt, Vec<usize>)>> {
+    if envelopes.is_empty() {
+        return Err(anyhow::anyhow!("Receipt chain is empty"));
+    }
+    if envelopes.len() != keys.len() {
+        return Err(anyhow::anyhow!(
+            "Expected one key per envelope: {} envelopes, {} keys",
+            envelopes.len(),
+            keys.len()
+        ));
+    }
+
+    let mut hashes = Vec::with_capacity(envelopes.len());
+    let mut graph = Vec::with_capacity(envelopes.len());
+    for (i, (envelope, key)) in envelopes.iter().zip(keys).enumerate() {
+        let payload = envelope
+            .unseal(key)
+            .with_context(|| format!("Failed to unseal receipt {}", i))?;
+        let (receipt, _) = decode_receipt_payload(&payload)
+            .with_context(|| format!("Failed to deserialize receipt {}", i))?;
+
+        let parents = if i == 0 {
+            Vec::new()
+        } else {
+            receipt
+                .parent_hashes()
+                .iter()
+                .map(|parent| {
+                    hashes
+                        .iter()
+                        .position(|hash| hash == parent)
+                        .ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "Receipt {} does not link to an earlier receipt in the chain",
+                                i
+                            )
+                        })
+                })
+                .collect::<Result<Vec<_>>>()?
         };
-        let Ok(prev_beneficiary) = prev_envelope.beneficiary() else {
-            return false;
+
+        let hash = envelope.hash()?;
+        if hashes.contains(&hash) {
+            return Err(anyhow::anyhow!("Receipt {} appears more than once", i));
+        }
+        hashes.push(hash);
+        graph.push((receipt, parents));
+    }
+    Ok(graph)
+}
+
+/// Verify that no value was created or destroyed across a receipt chain
+///
+/// Every envelope is unsealed and each receipt's [`OwnershipReceipt::parent_hashes`]
+/// must name receipts listed earlier in `envelopes`. The receipts linking to a
+/// parent must add up to exactly its amount: one assignment carries the whole
+/// amount, and the branches of a split or fee assignment sum to it. A merge
+/// consumes every parent in full and must carry exactly their sum. The first
+/// receipt and any origin receipts are the inputs; receipts with no children in
+/// the list are the current holdings.
+///
+/// # Arguments
+/// * `envelopes` - The chain of envelopes, each listed after its parents
+/// * `keys` - The beneficiary's private key for each envelope, in the same order
+///
+/// # Returns
+/// The conserved amount (the sum of the input receipts' amounts), or an error
+/// naming the offending receipt index
+pub fn verify_chain_conservation(envelopes: &[Envelope], keys: &[SigningKey]) -> Result<u64> {
+    let graph = open_receipt_graph(envelopes, keys)?;
+    let amount = |i: usize| graph[i].0.amount;
+
+    // Running total of the children seen so far for each parent
+    let mut assigned = vec![0u64; graph.len()];
+    let mut has_children = vec![false; graph.len()];
+    let mut inputs = 0u64;
+    for (i, (receipt, parents)) in graph.iter().enumerate() {
+        // A merge takes all of each parent; any other link takes its own amount
+        let shares: Vec<(usize, u64)> = match parents.as_slice() {
+            [] => {
+                inputs = inputs
+                    .checked_add(receipt.amount)
+                    .ok_or_else(|| anyhow::anyhow!("Input amounts overflow u64"))?;
+                continue;
+            }
+            [parent] => vec![(*parent, receipt.amount)],
+            merged => {
+                let total = merged
+                    .iter()
+                    .try_fold(0u64, |total, &p| total.checked_add(amount(p)));
+                if total != Some(receipt.amount) {
+                    return Err(anyhow::anyhow!(
+                        "Merged receipt {} amount {} does not equal the sum of its parents",
+                        i,
+                        receipt.amount
+                    ));
+                }
+                merged.iter().map(|&p| (p, amount(p))).collect()
+            }
         };
-        if current_issuer != prev_beneficiary {
-            return false;
+
+        for (parent, share) in shares {
+            assigned[parent] = assigned[parent]
+                .checked_add(share)
+                .filter(|total| *total <= amount(parent))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Receipt {} amount {} exceeds what remains of receipt {} amount {}",
+                        i,
+                        receipt.amount,
+                        parent,
+                        amount(parent)
+                    )
+                })?;
+            has_children[parent] = true;
         }
+    }
 
-        // NOTE: Full chain verification (checking envelope hash references) requires
-        // decryption and is planned for post-P0. Current validation checks issuer/beneficiary
-        // chain continuity which provides ownership transfer guarantees.
+    for (i, (receipt, _)) in graph.iter().enumerate() {
+        if has_children[i] && assigned[i] != receipt.amount {
+            return Err(anyhow::anyhow!(
+                "Receipt {} amount {} is only partly assigned ({})",
+                i,
+                receipt.amount,
+                assigned[i]
+            ));
+        }
     }
 
-    true
+    Ok(inputs)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::MockClock;
+    use crate::EnvelopeError;
     use ed25519_dalek::SigningKey;
     use rand_core::OsRng;
 
@@ -447,23 +1344,520 @@ mod tests {
     }
 
     #[test]
-    fn test_receipt_chain_verification() {
+    fn test_receipt_chain_verification() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+
+        // Create a chain: Alice -> Bob -> Charlie
+        let envelope1 = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None)
+            .expect("Failed to create receipt");
+
+        let envelope2 = assign_receipt(&envelope1, &bob_key, &charlie_key.verifying_key(), None)
+            .expect("Failed to assign receipt");
+
+        let chain = vec![envelope1, envelope2];
+        assert!(verify_receipt_chain(&chain));
+
+        // Test empty chain
+        assert!(!verify_receipt_chain(&[]));
+    }
+
+    #[test]
+    fn test_ownership_checks_compare_keys_in_constant_time() {
+        use crate::envelope::CONSTANT_TIME_KEY_COMPARISONS;
+        let comparisons = || CONSTANT_TIME_KEY_COMPARISONS.with(|count| count.get());
+
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+        let envelope1 = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None)
+            .expect("Failed to create receipt");
+
+        let before = comparisons();
+        let envelope2 = assign_receipt(&envelope1, &bob_key, &charlie_key.verifying_key(), None)
+            .expect("Failed to assign receipt");
+        assert!(comparisons() > before);
+
+        let before = comparisons();
+        assert!(verify_receipt_chain(&[envelope1, envelope2]));
+        assert!(comparisons() > before);
+    }
+
+    /// Alice -> Bob, then Bob assigns to Charlie under `bob_assigning_key`
+    fn chain_across_rotation(
+        alice_key: &SigningKey,
+        bob_key: &SigningKey,
+        bob_assigning_key: &SigningKey,
+        charlie_key: &SigningKey,
+    ) -> Vec<Envelope> {
+        let envelope1 = create_receipt(alice_key, &bob_key.verifying_key(), 1000, None)
+            .expect("Failed to create receipt");
+
+        let receipt = OwnershipReceipt::new_assignment(
+            bob_assigning_key,
+            &charlie_key.verifying_key(),
+            1000,
+            envelope1.hash().expect("hash"),
+            None,
+        );
+        let payload = serde_json::to_vec(&receipt).expect("serialize");
+        let envelope2 = Envelope::seal(&payload, bob_assigning_key, &charlie_key.verifying_key())
+            .expect("Failed to seal assignment");
+
+        vec![envelope1, envelope2]
+    }
+
+    #[test]
+    fn test_receipt_chain_across_key_rotation() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_old_key = SigningKey::generate(&mut OsRng);
+        let bob_new_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+
+        let chain = chain_across_rotation(&alice_key, &bob_old_key, &bob_new_key, &charlie_key);
+        let rotation = KeyRotation::new(
+            &bob_old_key,
+            &bob_new_key.verifying_key(),
+            chain[0].hash().unwrap(),
+        );
+        assert!(rotation.verify().is_ok());
+
+        assert!(!verify_receipt_chain(&chain));
+        assert!(verify_receipt_chain_with_rotations(&chain, &[rotation]));
+    }
+
+    #[test]
+    fn test_old_key_links_before_rotation_still_verify() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_old_key = SigningKey::generate(&mut OsRng);
+        let bob_new_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+        let dave_key = SigningKey::generate(&mut OsRng);
+
+        // Bob's old key assigns to Charlie, who later hands the asset back to Bob
+        let origin = create_receipt(&alice_key, &bob_old_key.verifying_key(), 1000, None).unwrap();
+        let link1 =
+            assign_receipt(&origin, &bob_old_key, &charlie_key.verifying_key(), None).unwrap();
+        let back_to_bob =
+            assign_receipt(&link1, &charlie_key, &bob_old_key.verifying_key(), None).unwrap();
+
+        // Bob rotates while holding the returned receipt, then assigns under the new key
+        let rotation = KeyRotation::new(
+            &bob_old_key,
+            &bob_new_key.verifying_key(),
+            back_to_bob.hash().unwrap(),
+        );
+        let receipt = OwnershipReceipt::new_assignment(
+            &bob_new_key,
+            &dave_key.verifying_key(),
+            1000,
+            back_to_bob.hash().unwrap(),
+            None,
+        );
+        let payload = serde_json::to_vec(&receipt).unwrap();
+        let link2 = Envelope::seal(&payload, &bob_new_key, &dave_key.verifying_key()).unwrap();
+
+        let chain = [origin, link1, back_to_bob.clone(), link2];
+        assert!(verify_receipt_chain_with_rotations(
+            &chain,
+            std::slice::from_ref(&rotation)
+        ));
+
+        // After the rotation the old key no longer speaks for Bob
+        let by_old =
+            assign_receipt(&back_to_bob, &bob_old_key, &dave_key.verifying_key(), None).unwrap();
+        let mut stale = chain[..3].to_vec();
+        stale.push(by_old);
+        assert!(verify_receipt_chain(&stale));
+        assert!(!verify_receipt_chain_with_rotations(&stale, &[rotation]));
+    }
+
+    #[test]
+    fn test_unauthorized_key_rotation_is_rejected() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let mallory_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+
+        let chain = chain_across_rotation(&alice_key, &bob_key, &mallory_key, &charlie_key);
+
+        // Mallory claims Bob rotated to her key, but signs the claim herself
+        let mut forged = KeyRotation::new(
+            &mallory_key,
+            &mallory_key.verifying_key(),
+            chain[0].hash().unwrap(),
+        );
+        forged.old_key = bob_key.verifying_key().to_bytes();
+        assert!(forged.verify().is_err());
+
+        assert!(!verify_receipt_chain_with_rotations(&chain, &[forged]));
+    }
+
+    #[test]
+    fn test_forked_key_rotation_is_rejected() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_old_key = SigningKey::generate(&mut OsRng);
+        let bob_new_key = SigningKey::generate(&mut OsRng);
+        let mallory_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+
+        let chain = chain_across_rotation(&alice_key, &bob_old_key, &bob_new_key, &charlie_key);
+        let after = chain[0].hash().unwrap();
+        let rotation = KeyRotation::new(&bob_old_key, &bob_new_key.verifying_key(), after);
+        // Someone holding Bob's leaked old key rotates it to their own key as well
+        let fork = KeyRotation::new(&bob_old_key, &mallory_key.verifying_key(), after);
+
+        // Neither branch is trusted, whichever order the rotations come in
+        assert!(!verify_receipt_chain_with_rotations(
+            &chain,
+            &[rotation.clone(), fork.clone()]
+        ));
+        assert!(!verify_receipt_chain_with_rotations(
+            &chain,
+            &[fork.clone(), rotation.clone()]
+        ));
+        let forked = chain_across_rotation(&alice_key, &bob_old_key, &mallory_key, &charlie_key);
+        assert!(!verify_receipt_chain_with_rotations(
+            &forked,
+            &[rotation.clone(), fork]
+        ));
+
+        // Presenting the same rotation twice is not a fork
+        assert!(verify_receipt_chain_with_rotations(
+            &chain,
+            &[rotation.clone(), rotation]
+        ));
+    }
+
+    #[test]
+    fn test_old_key_cannot_issue_after_rotation() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_old_key = SigningKey::generate(&mut OsRng);
+        let bob_mid_key = SigningKey::generate(&mut OsRng);
+        let bob_new_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+
+        // Both rotations take effect once Bob holds the chain's first envelope
+        let rotations = |chain: &[Envelope]| {
+            let after = chain[0].hash().unwrap();
+            [
+                KeyRotation::new(&bob_mid_key, &bob_new_key.verifying_key(), after),
+                KeyRotation::new(&bob_old_key, &bob_mid_key.verifying_key(), after),
+            ]
+        };
+
+        let by_old = chain_across_rotation(&alice_key, &bob_old_key, &bob_old_key, &charlie_key);
+        assert!(verify_receipt_chain(&by_old));
+        assert!(!verify_receipt_chain_with_rotations(
+            &by_old,
+            &rotations(&by_old)
+        ));
+
+        let by_mid = chain_across_rotation(&alice_key, &bob_old_key, &bob_mid_key, &charlie_key);
+        assert!(!verify_receipt_chain_with_rotations(
+            &by_mid,
+            &rotations(&by_mid)
+        ));
+
+        let by_new = chain_across_rotation(&alice_key, &bob_old_key, &bob_new_key, &charlie_key);
+        assert!(verify_receipt_chain_with_rotations(
+            &by_new,
+            &rotations(&by_new)
+        ));
+
+        // A rotation path that loops back has no current key
+        let after = by_new[0].hash().unwrap();
+        let looped = [
+            KeyRotation::new(&bob_old_key, &bob_new_key.verifying_key(), after),
+            KeyRotation::new(&bob_new_key, &bob_old_key.verifying_key(), after),
+        ];
+        assert!(!verify_receipt_chain_with_rotations(&by_new, &looped));
+    }
+
+    #[test]
+    fn test_replayed_receipt_is_rejected() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let envelope = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None)
+            .expect("Failed to create receipt");
+
+        let registry = SeenReceiptRegistry::new();
+        assert!(!registry.has_seen(&envelope).unwrap());
+        registry.check_and_record(&envelope).unwrap();
+        assert!(registry.has_seen(&envelope).unwrap());
+
+        let replayed = envelope.clone();
+        assert!(matches!(
+            registry.check_and_record(&replayed),
+            Err(crate::ReplayError::Replayed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_distinct_receipts_are_both_accepted() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let first = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None)
+            .expect("Failed to create receipt");
+        // Same parties and amount, sealed again
+        let second = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None)
+            .expect("Failed to create receipt");
+
+        let registry = SeenReceiptRegistry::with_store(Box::new(MemoryReceiptStore::new()));
+        registry.check_and_record(&first).unwrap();
+        registry.check_and_record(&second).unwrap();
+    }
+
+    #[test]
+    fn test_chain_conservation_returns_amount() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+        let dave_key = SigningKey::generate(&mut OsRng);
+        let erin_key = SigningKey::generate(&mut OsRng);
+
+        let origin = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None).unwrap();
+        let assignment =
+            assign_receipt(&origin, &bob_key, &charlie_key.verifying_key(), None).unwrap();
+        let splits = split_receipt(
+            &assignment,
+            &charlie_key,
+            &[
+                (dave_key.verifying_key(), 600),
+                (erin_key.verifying_key(), 400),
+            ],
+            None,
+        )
+        .unwrap();
+
+        let envelopes = [origin, assignment, splits[0].clone(), splits[1].clone()];
+        let keys = [bob_key, charlie_key, dave_key, erin_key];
+        assert_eq!(verify_chain_conservation(&envelopes, &keys).unwrap(), 1000);
+    }
+
+    #[test]
+    fn test_chain_conservation_rejects_inflated_link() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+        let dave_key = SigningKey::generate(&mut OsRng);
+
+        let origin = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None).unwrap();
+        let assignment =
+            assign_receipt(&origin, &bob_key, &charlie_key.verifying_key(), None).unwrap();
+
+        // Charlie assigns more than they received
+        let inflated = OwnershipReceipt::new_assignment(
+            &charlie_key,
+            &dave_key.verifying_key(),
+            1500,
+            assignment.hash().unwrap(),
+            None,
+        );
+        let payload = serde_json::to_vec(&inflated).unwrap();
+        let inflated = Envelope::seal(&payload, &charlie_key, &dave_key.verifying_key()).unwrap();
+
+        let envelopes = [origin, assignment, inflated];
+        let keys = [bob_key, charlie_key, dave_key];
+        let err = verify_chain_conservation(&envelopes, &keys).unwrap_err();
+        assert!(err.to_string().contains("Receipt 2"), "{err}");
+    }
+
+    #[test]
+    fn test_merge_receipts() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let carol_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let dave_key = SigningKey::generate(&mut OsRng);
+
+        let first = create_receipt(&alice_key, &bob_key.verifying_key(), 500, None).unwrap();
+        let second = create_receipt(&carol_key, &bob_key.verifying_key(), 500, None).unwrap();
+
+        let merged = merge_receipts(
+            &[first.clone(), second.clone()],
+            &bob_key,
+            &dave_key.verifying_key(),
+            Some("Consolidated".to_string()),
+        )
+        .unwrap();
+        assert!(merged.verify());
+
+        let receipt = extract_receipt(&merged, &dave_key).unwrap();
+        assert_eq!(receipt.amount, 1000);
+        assert_eq!(receipt.issuer, bob_key.verifying_key().to_bytes());
+        assert_eq!(receipt.prev_envelope_hash, Some(first.hash().unwrap()));
+        assert_eq!(
+            receipt.parent_hashes(),
+            vec![first.hash().unwrap(), second.hash().unwrap()]
+        );
+
+        // The merged receipt is assignable like any other
+        let onward = assign_receipt(&merged, &dave_key, &bob_key.verifying_key(), None).unwrap();
+        assert_eq!(extract_receipt(&onward, &bob_key).unwrap().amount, 1000);
+
+        // The same envelope cannot be merged with itself
+        let err = merge_receipts(
+            &[first.clone(), first],
+            &bob_key,
+            &dave_key.verifying_key(),
+            None,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("more than once"));
+    }
+
+    #[test]
+    fn test_merged_chain_conserves_value() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let carol_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let dave_key = SigningKey::generate(&mut OsRng);
+        let erin_key = SigningKey::generate(&mut OsRng);
+
+        let first = create_receipt(&alice_key, &bob_key.verifying_key(), 500, None).unwrap();
+        let second = create_receipt(&carol_key, &bob_key.verifying_key(), 300, None).unwrap();
+        let merged = merge_receipts(
+            &[first.clone(), second.clone()],
+            &bob_key,
+            &dave_key.verifying_key(),
+            None,
+        )
+        .unwrap();
+        let onward = assign_receipt(&merged, &dave_key, &erin_key.verifying_key(), None).unwrap();
+
+        let envelopes = [first.clone(), second.clone(), merged, onward];
+        let keys = [
+            bob_key.clone(),
+            bob_key.clone(),
+            dave_key.clone(),
+            erin_key.clone(),
+        ];
+        assert_eq!(verify_chain_conservation(&envelopes, &keys).unwrap(), 800);
+        verify_receipt_graph_with_rotations(&envelopes, &keys, &[]).unwrap();
+
+        // A merge claiming more than its inputs hold is caught
+        let mut inflated = OwnershipReceipt::new_assignment(
+            &bob_key,
+            &dave_key.verifying_key(),
+            900,
+            first.hash().unwrap(),
+            None,
+        );
+        inflated.merge_parents = vec![first.hash().unwrap(), second.hash().unwrap()];
+        let payload = serde_json::to_vec(&inflated).unwrap();
+        let inflated = Envelope::seal(&payload, &bob_key, &dave_key.verifying_key()).unwrap();
+        let envelopes = [first.clone(), second.clone(), inflated];
+        let keys = [bob_key.clone(), bob_key.clone(), dave_key.clone()];
+        let err = verify_chain_conservation(&envelopes, &keys).unwrap_err();
+        assert!(err.to_string().contains("Merged receipt 2"), "{err}");
+
+        // A link to an envelope outside the list is rejected
+        let stray = OwnershipReceipt::new_assignment(
+            &dave_key,
+            &erin_key.verifying_key(),
+            800,
+            [9u8; 32],
+            None,
+        );
+        let stray = Envelope::seal(
+            &serde_json::to_vec(&stray).unwrap(),
+            &dave_key,
+            &erin_key.verifying_key(),
+        )
+        .unwrap();
+        let err = verify_chain_conservation(&[first, stray], &[bob_key, erin_key]).unwrap_err();
+        assert!(err.to_string().contains("Receipt 1 does not link"), "{err}");
+    }
+
+    #[test]
+    fn test_receipt_graph_rejects_issuer_who_did_not_hold_parent() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let mallory_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+
+        let origin = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None).unwrap();
+        let stolen = OwnershipReceipt::new_assignment(
+            &mallory_key,
+            &charlie_key.verifying_key(),
+            1000,
+            origin.hash().unwrap(),
+            None,
+        );
+        let stolen = Envelope::seal(
+            &serde_json::to_vec(&stolen).unwrap(),
+            &mallory_key,
+            &charlie_key.verifying_key(),
+        )
+        .unwrap();
+
+        let err =
+            verify_receipt_graph_with_rotations(&[origin, stolen], &[bob_key, charlie_key], &[])
+                .unwrap_err();
+        assert!(
+            err.to_string().contains("not issued by the holder"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_double_assignment_chain_is_rejected() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+        let dave_key = SigningKey::generate(&mut OsRng);
+
+        let origin = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None).unwrap();
+        let to_charlie =
+            assign_receipt(&origin, &bob_key, &charlie_key.verifying_key(), None).unwrap();
+        let to_dave = assign_receipt(&origin, &bob_key, &dave_key.verifying_key(), None).unwrap();
+
+        assert!(verify_receipt_chain(&[origin.clone(), to_charlie.clone()]));
+        // Bob no longer holds the asset once he has assigned it to Charlie
+        assert!(!verify_receipt_chain(&[origin, to_charlie, to_dave]));
+    }
+
+    #[test]
+    fn test_split_branches_verify_as_graph() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let dave_key = SigningKey::generate(&mut OsRng);
+        let erin_key = SigningKey::generate(&mut OsRng);
+
+        let origin = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None).unwrap();
+        let splits = split_receipt(
+            &origin,
+            &bob_key,
+            &[
+                (dave_key.verifying_key(), 600),
+                (erin_key.verifying_key(), 400),
+            ],
+            None,
+        )
+        .unwrap();
+
+        // The second branch links to the origin, not to the branch before it, so
+        // only the graph verifier accepts the branches listed one after another
+        let envelopes = [origin, splits[0].clone(), splits[1].clone()];
+        assert!(!verify_receipt_chain(&envelopes));
+        verify_receipt_graph_with_rotations(&envelopes, &[bob_key, dave_key, erin_key], &[])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_merge_rejects_receipt_not_owned() {
         let alice_key = SigningKey::generate(&mut OsRng);
         let bob_key = SigningKey::generate(&mut OsRng);
-        let charlie_key = SigningKey::generate(&mut OsRng);
-
-        // Create a chain: Alice -> Bob -> Charlie
-        let envelope1 = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None)
-            .expect("Failed to create receipt");
-
-        let envelope2 = assign_receipt(&envelope1, &bob_key, &charlie_key.verifying_key(), None)
-            .expect("Failed to assign receipt");
+        let eve_key = SigningKey::generate(&mut OsRng);
 
-        let chain = vec![envelope1, envelope2];
-        assert!(verify_receipt_chain(&chain));
+        let bobs = create_receipt(&alice_key, &bob_key.verifying_key(), 500, None).unwrap();
+        let eves = create_receipt(&alice_key, &eve_key.verifying_key(), 500, None).unwrap();
 
-        // Test empty chain
-        assert!(!verify_receipt_chain(&[]));
+        let err =
+            merge_receipts(&[bobs, eves], &bob_key, &bob_key.verifying_key(), None).unwrap_err();
+        assert!(format!("{:#}", err).contains("Cannot merge receipt 1"));
+        assert!(format!("{:#}", err).contains("does not match previous beneficiary"));
     }
 
     #[test]
@@ -491,6 +1885,40 @@ mod tests {
         assert!(future_receipt.validate().is_err());
     }
 
+    #[test]
+    fn test_receipt_future_timestamp_boundary() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let clock = MockClock::new(1_700_000_000);
+
+        let mut receipt = OwnershipReceipt::new_origin_with_clock(
+            &alice_key,
+            &bob_key.verifying_key(),
+            1000,
+            None,
+            &clock,
+        );
+        assert_eq!(receipt.created_at, 1_700_000_000);
+
+        receipt.created_at = clock.now_secs() + 300;
+        assert!(receipt.validate_with_clock(&clock).is_ok());
+
+        receipt.created_at = clock.now_secs() + 301;
+        assert!(receipt.validate_with_clock(&clock).is_err());
+
+        // Expiry is judged against the same clock
+        receipt.created_at = clock.now_secs();
+        let receipt = receipt.with_expiry(1_700_000_500);
+        clock.advance(499);
+        assert!(!receipt.is_expired_at(clock.now_secs()));
+        assert!(receipt.validate_with_clock(&clock).is_ok());
+        // Expired at the expiry second itself, as envelopes are
+        clock.advance(1);
+        assert_eq!(clock.now_secs(), 1_700_000_500);
+        assert!(receipt.is_expired_at(clock.now_secs()));
+        assert!(receipt.validate_with_clock(&clock).is_err());
+    }
+
     #[test]
     fn test_envelope_unseal_integration() {
         let alice_key = SigningKey::generate(&mut OsRng);
@@ -601,7 +2029,11 @@ mod tests {
 
             // Unsealing should also fail
             let result = tampered_envelope.unseal(&bob_key);
-            assert!(result.is_err(), "Unsealing tampered envelope should fail");
+            assert_eq!(
+                result,
+                Err(EnvelopeError::SignatureInvalid),
+                "Unsealing tampered envelope should fail"
+            );
         }
         // If deserialization fails, that's also acceptable (tampered data is invalid)
     }
@@ -620,10 +2052,16 @@ mod tests {
         assert!(envelope.unseal(&bob_key).is_ok());
 
         // Charlie cannot unseal (wrong key)
-        assert!(envelope.unseal(&charlie_key).is_err());
+        assert_eq!(
+            envelope.unseal(&charlie_key),
+            Err(EnvelopeError::WrongRecipient)
+        );
 
         // Alice cannot unseal (she's the issuer, not beneficiary)
-        assert!(envelope.unseal(&alice_key).is_err());
+        assert_eq!(
+            envelope.unseal(&alice_key),
+            Err(EnvelopeError::WrongRecipient)
+        );
     }
 
     #[test]
@@ -706,20 +2144,24 @@ mod tests {
         assert_eq!(receipt4.beneficiary, eve_key.verifying_key().to_bytes());
 
         // Verify intermediate parties cannot unseal final envelope
-        assert!(
-            envelope4.unseal(&alice_key).is_err(),
+        assert_eq!(
+            envelope4.unseal(&alice_key),
+            Err(EnvelopeError::WrongRecipient),
             "Alice should not be able to unseal final envelope"
         );
-        assert!(
-            envelope4.unseal(&bob_key).is_err(),
+        assert_eq!(
+            envelope4.unseal(&bob_key),
+            Err(EnvelopeError::WrongRecipient),
             "Bob should not be able to unseal final envelope"
         );
-        assert!(
-            envelope4.unseal(&charlie_key).is_err(),
+        assert_eq!(
+            envelope4.unseal(&charlie_key),
+            Err(EnvelopeError::WrongRecipient),
             "Charlie should not be able to unseal final envelope"
         );
-        assert!(
-            envelope4.unseal(&dave_key).is_err(),
+        assert_eq!(
+            envelope4.unseal(&dave_key),
+            Err(EnvelopeError::WrongRecipient),
             "Dave should not be able to unseal final envelope"
         );
 
@@ -938,8 +2380,9 @@ mod tests {
             .expect("Failed to create receipt");
 
         // Eve (attacker) cannot unseal Bob's envelope
-        assert!(
-            envelope.unseal(&eve_key).is_err(),
+        assert_eq!(
+            envelope.unseal(&eve_key),
+            Err(EnvelopeError::WrongRecipient),
             "Eve should not be able to unseal Bob's envelope"
         );
 
@@ -965,8 +2408,9 @@ mod tests {
         .expect("Legitimate assignment should succeed");
 
         // Eve still cannot unseal Charlie's envelope
-        assert!(
-            legitimate_assignment.unseal(&eve_key).is_err(),
+        assert_eq!(
+            legitimate_assignment.unseal(&eve_key),
+            Err(EnvelopeError::WrongRecipient),
             "Eve should not be able to unseal Charlie's envelope"
         );
 
@@ -1309,4 +2753,467 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_split_receipt_exact() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+
+        let original_envelope = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None)
+            .expect("Failed to create receipt");
+        let parent_hash = original_envelope.hash().expect("hash");
+
+        // Bob sends 600 to Charlie and keeps 400
+        let splits = split_receipt(
+            &original_envelope,
+            &bob_key,
+            &[
+                (charlie_key.verifying_key(), 600),
+                (bob_key.verifying_key(), 400),
+            ],
+            Some("Split payment".to_string()),
+        )
+        .expect("Failed to split receipt");
+
+        assert_eq!(splits.len(), 2);
+
+        let charlie_receipt = extract_receipt(&splits[0], &charlie_key).expect("extract");
+        assert_eq!(charlie_receipt.amount, 600);
+        assert_eq!(
+            charlie_receipt.beneficiary,
+            charlie_key.verifying_key().to_bytes()
+        );
+        assert_eq!(charlie_receipt.prev_envelope_hash, Some(parent_hash));
+
+        let bob_receipt = extract_receipt(&splits[1], &bob_key).expect("extract");
+        assert_eq!(bob_receipt.amount, 400);
+        assert_eq!(bob_receipt.prev_envelope_hash, Some(parent_hash));
+
+        // Each split forms a valid chain with the parent
+        for split in splits {
+            assert!(verify_receipt_chain(&[original_envelope.clone(), split]));
+        }
+    }
+
+    #[test]
+    fn test_split_receipt_over_allocated() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+
+        let original_envelope = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None)
+            .expect("Failed to create receipt");
+
+        let result = split_receipt(
+            &original_envelope,
+            &bob_key,
+            &[
+                (charlie_key.verifying_key(), 700),
+                (bob_key.verifying_key(), 400),
+            ],
+            None,
+        );
+
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("expected total 1000"), "got: {err}");
+        assert!(err.contains("provided 1100"), "got: {err}");
+    }
+
+    #[test]
+    fn test_split_receipt_zero_amount_rejected() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+
+        let original_envelope = create_receipt(&alice_key, &bob_key.verifying_key(), 1000, None)
+            .expect("Failed to create receipt");
+
+        // Totals match, but a zero-amount receipt fails business validation
+        let result = split_receipt(
+            &original_envelope,
+            &bob_key,
+            &[
+                (charlie_key.verifying_key(), 0),
+                (bob_key.verifying_key(), 1000),
+            ],
+            None,
+        );
+
+        let err = format!("{:#}", result.unwrap_err());
+        assert!(
+            err.contains("Split receipt validation failed"),
+            "got: {err}"
+        );
+        assert!(err.contains("amount cannot be zero"), "got: {err}");
+    }
+
+    #[test]
+    fn test_expired_origin_rejected() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+
+        let mut receipt =
+            OwnershipReceipt::new_origin(&alice_key, &bob_key.verifying_key(), 1000, None);
+        receipt.created_at -= 7200;
+        let expires_at = receipt.created_at + 3600;
+        let receipt = receipt.with_expiry(expires_at);
+
+        let err = receipt.validate().unwrap_err().to_string();
+        assert!(err.contains("expired"), "got: {err}");
+    }
+
+    #[test]
+    fn test_unexpired_receipt_valid() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+
+        let receipt =
+            OwnershipReceipt::new_origin(&alice_key, &bob_key.verifying_key(), 1000, None);
+        let expires_at = receipt.created_at + 3600;
+        let receipt = receipt.with_expiry(expires_at);
+
+        assert!(receipt.validate().is_ok());
+        assert!(!receipt.is_expired_at(receipt.created_at));
+        assert!(receipt.is_expired_at(receipt.created_at + 3600));
+
+        // Older receipts without the field still parse
+        let mut legacy = serde_json::to_value(&receipt).expect("serialize");
+        legacy.as_object_mut().expect("object").remove("expires_at");
+        let parsed: OwnershipReceipt = serde_json::from_value(legacy).expect("deserialize");
+        assert_eq!(parsed.expires_at, None);
+    }
+
+    #[test]
+    fn test_assignment_blocked_by_parent_expiry() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+
+        // Seal an already-expired receipt directly, bypassing create_receipt validation
+        let mut receipt =
+            OwnershipReceipt::new_origin(&alice_key, &bob_key.verifying_key(), 1000, None);
+        receipt.created_at -= 7200;
+        let expires_at = receipt.created_at + 3600;
+        let receipt = receipt.with_expiry(expires_at);
+        let payload = serde_json::to_vec(&receipt).expect("serialize");
+        let expired_envelope =
+            Envelope::seal(&payload, &alice_key, &bob_key.verifying_key()).expect("seal");
+
+        let result = assign_receipt(
+            &expired_envelope,
+            &bob_key,
+            &charlie_key.verifying_key(),
+            None,
+        );
+
+        let err = format!("{:#}", result.unwrap_err());
+        assert!(err.contains("Cannot assign previous receipt"), "got: {err}");
+        assert!(err.contains("expired"), "got: {err}");
+    }
+
+    #[test]
+    fn test_cbor_receipt_roundtrip_is_smaller() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let description = Some("Quarterly settlement for sensor fleet telemetry ".repeat(20));
+
+        let json_envelope = create_receipt_with_encoding(
+            &alice_key,
+            &bob_key.verifying_key(),
+            1000,
+            description.clone(),
+            ReceiptEncoding::Json,
+        )
+        .expect("Failed to create JSON receipt");
+        let cbor_envelope = create_receipt_with_encoding(
+            &alice_key,
+            &bob_key.verifying_key(),
+            1000,
+            description.clone(),
+            ReceiptEncoding::Cbor,
+        )
+        .expect("Failed to create CBOR receipt");
+
+        let json_payload = json_envelope.unseal(&bob_key).expect("unseal");
+        let cbor_payload = cbor_envelope.unseal(&bob_key).expect("unseal");
+        assert_eq!(json_payload[0], ReceiptEncoding::Json.tag());
+        assert_eq!(cbor_payload[0], ReceiptEncoding::Cbor.tag());
+        assert!(
+            cbor_payload.len() < json_payload.len(),
+            "CBOR payload ({}) should be smaller than JSON ({})",
+            cbor_payload.len(),
+            json_payload.len()
+        );
+
+        // Auto-detection and explicit extraction agree
+        let auto = extract_receipt(&cbor_envelope, &bob_key).expect("extract");
+        let explicit =
+            extract_receipt_with_encoding(&cbor_envelope, &bob_key, ReceiptEncoding::Cbor)
+                .expect("extract");
+        assert_eq!(auto, explicit);
+        assert_eq!(auto.amount, 1000);
+        assert_eq!(auto.description, description);
+
+        // Explicit extraction rejects a payload in another encoding
+        assert!(
+            extract_receipt_with_encoding(&json_envelope, &bob_key, ReceiptEncoding::Cbor).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cbor_receipt_assignment_keeps_encoding() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+
+        let origin = create_receipt_with_encoding(
+            &alice_key,
+            &bob_key.verifying_key(),
+            1000,
+            None,
+            ReceiptEncoding::Cbor,
+        )
+        .expect("Failed to create receipt");
+        let assignment = assign_receipt(&origin, &bob_key, &charlie_key.verifying_key(), None)
+            .expect("Failed to assign receipt");
+
+        let receipt =
+            extract_receipt_with_encoding(&assignment, &charlie_key, ReceiptEncoding::Cbor)
+                .expect("extract");
+        assert_eq!(receipt.amount, 1000);
+        assert_eq!(
+            receipt.prev_envelope_hash,
+            Some(origin.hash().expect("hash"))
+        );
+    }
+
+    #[test]
+    fn test_create_receipts_batch_fails_fast() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let entries: Vec<(VerifyingKey, u64, Option<String>)> = (0..10)
+            .map(|i| {
+                let amount = if i == 7 { 0 } else { 100 + i };
+                (
+                    SigningKey::generate(&mut OsRng).verifying_key(),
+                    amount,
+                    None,
+                )
+            })
+            .collect();
+
+        let result = create_receipts_batch(&alice_key, &entries);
+
+        let err = format!("{:#}", result.unwrap_err());
+        assert!(err.contains("Batch entry 7"), "got: {err}");
+        assert!(err.contains("amount cannot be zero"), "got: {err}");
+    }
+
+    #[test]
+    fn test_create_receipts_batch_large() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let beneficiaries: Vec<SigningKey> =
+            (0..500).map(|_| SigningKey::generate(&mut OsRng)).collect();
+        let entries: Vec<(VerifyingKey, u64, Option<String>)> = beneficiaries
+            .iter()
+            .enumerate()
+            .map(|(i, key)| (key.verifying_key(), i as u64 + 1, Some(format!("#{i}"))))
+            .collect();
+
+        let envelopes = create_receipts_batch(&alice_key, &entries).expect("batch");
+        assert_eq!(envelopes.len(), 500);
+
+        for (i, (envelope, key)) in envelopes.iter().zip(&beneficiaries).enumerate() {
+            assert!(envelope.verify(), "envelope {i} should verify");
+            assert_eq!(envelope.beneficiary().expect("test"), key.verifying_key());
+        }
+
+        // Spot-check that input order is preserved
+        let last = extract_receipt(&envelopes[499], &beneficiaries[499]).expect("extract");
+        assert_eq!(last.amount, 500);
+        assert_eq!(last.description.as_deref(), Some("#499"));
+    }
+
+    #[test]
+    fn test_assign_receipt_with_fee() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+        let market_key = SigningKey::generate(&mut OsRng);
+
+        let original_envelope = create_receipt(&alice_key, &bob_key.verifying_key(), 10000, None)
+            .expect("Failed to create receipt");
+        let parent_hash = original_envelope.hash().expect("hash");
+
+        let assignment = assign_receipt_with_fee(
+            &original_envelope,
+            &bob_key,
+            &charlie_key.verifying_key(),
+            250,
+            &market_key.verifying_key(),
+            Some("Marketplace sale".to_string()),
+        )
+        .expect("Failed to assign with fee");
+
+        let charlie_receipt =
+            extract_receipt(&assignment.beneficiary_envelope, &charlie_key).expect("extract");
+        assert_eq!(charlie_receipt.amount, 9750);
+        assert_eq!(charlie_receipt.prev_envelope_hash, Some(parent_hash));
+
+        let fee_envelope = assignment.fee_envelope.expect("fee envelope");
+        let fee_receipt = extract_receipt(&fee_envelope, &market_key).expect("extract");
+        assert_eq!(fee_receipt.amount, 250);
+        assert_eq!(fee_receipt.prev_envelope_hash, Some(parent_hash));
+
+        // Fees above 100% are rejected
+        let result = assign_receipt_with_fee(
+            &original_envelope,
+            &bob_key,
+            &charlie_key.verifying_key(),
+            10001,
+            &market_key.verifying_key(),
+            None,
+        );
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("must be below 10000 bps"));
+    }
+
+    #[test]
+    fn test_assign_receipt_with_fee_bounds() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+        let market_key = SigningKey::generate(&mut OsRng);
+        let original_envelope = create_receipt(&alice_key, &bob_key.verifying_key(), 10000, None)
+            .expect("Failed to create receipt");
+        let assign = |fee_bps| {
+            assign_receipt_with_fee(
+                &original_envelope,
+                &bob_key,
+                &charlie_key.verifying_key(),
+                fee_bps,
+                &market_key.verifying_key(),
+                None,
+            )
+        };
+
+        // No fee: the whole amount passes on and there is no fee receipt
+        let free = assign(0).expect("0 bps");
+        assert!(free.fee_envelope.is_none());
+        let charlie_receipt =
+            extract_receipt(&free.beneficiary_envelope, &charlie_key).expect("extract");
+        assert_eq!(charlie_receipt.amount, 10000);
+
+        // The largest accepted fee still leaves the beneficiary something
+        let steep = assign(9999).expect("9999 bps");
+        let charlie_receipt =
+            extract_receipt(&steep.beneficiary_envelope, &charlie_key).expect("extract");
+        assert_eq!(charlie_receipt.amount, 1);
+        let fee_receipt = extract_receipt(&steep.fee_envelope.expect("fee envelope"), &market_key)
+            .expect("extract");
+        assert_eq!(fee_receipt.amount, 9999);
+
+        // A 100% fee is refused before anything is sealed
+        let err = assign(10000).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Fee of 10000 bps must be below 10000 bps (100%): the beneficiary would receive nothing"
+        );
+    }
+
+    #[test]
+    fn test_amount_after_fees_rounding() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let receipt = OwnershipReceipt::new_origin(&alice_key, &bob_key.verifying_key(), 999, None);
+
+        // 2.5% of 999 = 24.975, fee rounds down to 24
+        assert_eq!(receipt.amount_after_fees(250).expect("fee"), 975);
+        assert_eq!(receipt.amount_after_fees(0).expect("fee"), 999);
+        assert_eq!(receipt.amount_after_fees(10000).expect("fee"), 0);
+
+        // No overflow at the top of the range
+        let mut large = receipt.clone();
+        large.amount = u64::MAX;
+        assert_eq!(
+            large.amount_after_fees(10000).expect("fee"),
+            0,
+            "100% fee on u64::MAX"
+        );
+
+        // A fee that rounds to zero produces no fee receipt
+        let charlie_key = SigningKey::generate(&mut OsRng);
+        let market_key = SigningKey::generate(&mut OsRng);
+        let small = create_receipt(&alice_key, &bob_key.verifying_key(), 39, None)
+            .expect("Failed to create receipt");
+        let assignment = assign_receipt_with_fee(
+            &small,
+            &bob_key,
+            &charlie_key.verifying_key(),
+            250,
+            &market_key.verifying_key(),
+            None,
+        )
+        .expect("Failed to assign with fee");
+        assert!(assignment.fee_envelope.is_none());
+        let charlie_receipt =
+            extract_receipt(&assignment.beneficiary_envelope, &charlie_key).expect("extract");
+        assert_eq!(charlie_receipt.amount, 39);
+    }
+
+    #[test]
+    fn test_receipt_sealed_to_x25519_encryption_key() {
+        let alice_key = SigningKey::generate(&mut OsRng);
+        let bob_key = SigningKey::generate(&mut OsRng);
+        let bob_encryption = StaticSecret::random_from_rng(OsRng);
+        let charlie_key = SigningKey::generate(&mut OsRng);
+        let charlie_encryption = StaticSecret::random_from_rng(OsRng);
+
+        let origin = create_receipt_with_encryption_key(
+            &alice_key,
+            &bob_key.verifying_key(),
+            &X25519PublicKey::from(&bob_encryption),
+            500,
+            None,
+        )
+        .expect("Failed to create receipt");
+
+        // The receipt names Bob's identity; only his X25519 secret opens it
+        let receipt = extract_receipt_with_encryption_key(&origin, &bob_encryption)
+            .expect("Failed to extract receipt");
+        assert_eq!(receipt.beneficiary, bob_key.verifying_key().to_bytes());
+        assert_eq!(receipt.amount, 500);
+        let wrong_recipient = Some(&EnvelopeError::WrongRecipient);
+        let err = extract_receipt(&origin, &bob_key).unwrap_err();
+        assert_eq!(err.downcast_ref::<EnvelopeError>(), wrong_recipient);
+        let err = extract_receipt_with_encryption_key(&origin, &charlie_encryption).unwrap_err();
+        assert_eq!(err.downcast_ref::<EnvelopeError>(), wrong_recipient);
+
+        let assignment = assign_receipt_with_encryption_key(
+            &origin,
+            &bob_key,
+            Some(&bob_encryption),
+            &charlie_key.verifying_key(),
+            &X25519PublicKey::from(&charlie_encryption),
+            None,
+        )
+        .expect("Failed to assign receipt");
+        let charlie_receipt = extract_receipt_with_encryption_key(&assignment, &charlie_encryption)
+            .expect("Failed to extract assignment");
+        assert_eq!(charlie_receipt.issuer, bob_key.verifying_key().to_bytes());
+        assert_eq!(
+            charlie_receipt.prev_envelope_hash,
+            Some(origin.hash().unwrap())
+        );
+
+        // Identity-based chain checks are unaffected by the separate encryption keys
+        assert!(verify_receipt_chain(&[origin.clone(), assignment]));
+
+        // Without Bob's X25519 secret the assignment cannot open the origin receipt
+        let err =
+            assign_receipt(&origin, &bob_key, &charlie_key.verifying_key(), None).unwrap_err();
+        assert_eq!(err.downcast_ref::<EnvelopeError>(), wrong_recipient);
+    }
 }
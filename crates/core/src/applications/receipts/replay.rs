@@ -0,0 +1,113 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Replay protection for receipt envelopes
+//!
+//! Every sealed envelope hashes differently, but an envelope that was accepted
+//! once still verifies if it is submitted again. A [`SeenReceiptRegistry`]
+//! remembers the hash of every envelope it accepts and refuses repeats. Where the
+//! hashes are kept is up to the [`SeenReceiptStore`]; the default keeps them in
+//! memory, so a registry that must survive restarts needs a persistent store.
+
+use crate::error::ReplayError;
+use crate::Envelope;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// Storage for the hashes of accepted envelopes
+pub trait SeenReceiptStore: Send + Sync {
+    /// Record `hash`, returning `false` if it was already recorded
+    ///
+    /// Check and insert must be atomic, or two concurrent submissions of the same
+    /// envelope could both be accepted.
+    fn insert(&self, hash: [u8; 32]) -> Result<bool, ReplayError>;
+
+    /// Whether `hash` has been recorded
+    fn contains(&self, hash: &[u8; 32]) -> Result<bool, ReplayError>;
+}
+
+/// [`SeenReceiptStore`] that lives for the life of the process
+#[derive(Default)]
+pub struct MemoryReceiptStore {
+    seen: Mutex<HashSet<[u8; 32]>>,
+}
+
+impl MemoryReceiptStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashSet<[u8; 32]>>, ReplayError> {
+        self.seen
+            .lock()
+            .map_err(|_| ReplayError::Storage("Receipt store lock poisoned".to_string()))
+    }
+}
+
+impl SeenReceiptStore for MemoryReceiptStore {
+    fn insert(&self, hash: [u8; 32]) -> Result<bool, ReplayError> {
+        Ok(self.lock()?.insert(hash))
+    }
+
+    fn contains(&self, hash: &[u8; 32]) -> Result<bool, ReplayError> {
+        Ok(self.lock()?.contains(hash))
+    }
+}
+
+/// Accepts each receipt envelope at most once
+pub struct SeenReceiptRegistry {
+    store: Box<dyn SeenReceiptStore>,
+}
+
+impl SeenReceiptRegistry {
+    /// Registry backed by a [`MemoryReceiptStore`]
+    pub fn new() -> Self {
+        Self::with_store(Box::new(MemoryReceiptStore::new()))
+    }
+
+    /// Registry backed by `store`
+    pub fn with_store(store: Box<dyn SeenReceiptStore>) -> Self {
+        Self { store }
+    }
+
+    /// Accept `envelope` if its signatures verify and it has not been seen before
+    ///
+    /// Accepted envelopes are recorded, so a second call with the same envelope
+    /// returns [`ReplayError::Replayed`]. Envelopes that fail verification are
+    /// rejected without being recorded.
+    pub fn check_and_record(&self, envelope: &Envelope) -> Result<(), ReplayError> {
+        if !envelope.verify() {
+            return Err(ReplayError::InvalidEnvelope(
+                "Envelope signature is invalid".to_string(),
+            ));
+        }
+        let hash = envelope
+            .hash()
+            .map_err(|e| ReplayError::InvalidEnvelope(e.to_string()))?;
+        if !self.store.insert(hash)? {
+            return Err(ReplayError::Replayed {
+                envelope_hash: hex::encode(hash),
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether `envelope` has already been accepted
+    pub fn has_seen(&self, envelope: &Envelope) -> Result<bool, ReplayError> {
+        let hash = envelope
+            .hash()
+            .map_err(|e| ReplayError::InvalidEnvelope(e.to_string()))?;
+        self.store.contains(&hash)
+    }
+}
+
+impl Default for SeenReceiptRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
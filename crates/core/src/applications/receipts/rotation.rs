@@ -0,0 +1,132 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Key rotation records for receipt chains
+//!
+//! A holder who rotates keys between receiving a receipt and assigning it on
+//! issues the next link under a key the previous beneficiary never named. A
+//! [`KeyRotation`] is the old key's signed statement that the new key speaks for
+//! it from a given point in the chain, which lets
+//! [`verify_receipt_chain_with_rotations`](super::verify_receipt_chain_with_rotations)
+//! accept the link. The rotation names the envelope after which it takes effect:
+//! links before that envelope are still judged against the old key, links after
+//! it only against the new one. A key rotated to two different successors is not
+//! trusted at all.
+
+use anyhow::{Context, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// Domain separator for the signed rotation statement
+const ROTATION_DOMAIN: &[u8] = b"SEALEDGE_KEY_ROTATION_V2";
+
+/// Statement, signed by `old_key`, that `new_key` replaces it after envelope `after`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct KeyRotation {
+    /// The key being rotated out
+    pub old_key: [u8; 32],
+    /// The key taking over
+    pub new_key: [u8; 32],
+    /// Hash of the envelope after which `new_key` takes over, typically the one
+    /// the holder held when rotating
+    pub after: [u8; 32],
+    /// Ed25519 signature by `old_key` over the rotation statement
+    pub signature: Vec<u8>,
+}
+
+impl KeyRotation {
+    /// Authorize `new_key` to act for `old_key` in links after the envelope hashing
+    /// to `after`
+    pub fn new(old_key: &SigningKey, new_key: &VerifyingKey, after: [u8; 32]) -> Self {
+        let old_bytes = old_key.verifying_key().to_bytes();
+        let new_bytes = new_key.to_bytes();
+        let signature = old_key.sign(&Self::statement(&old_bytes, &new_bytes, &after));
+        KeyRotation {
+            old_key: old_bytes,
+            new_key: new_bytes,
+            after,
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    /// Bytes covered by the signature
+    fn statement(old_key: &[u8; 32], new_key: &[u8; 32], after: &[u8; 32]) -> Vec<u8> {
+        [ROTATION_DOMAIN, old_key, new_key, after].concat()
+    }
+
+    /// Check that `old_key` signed this rotation
+    pub fn verify(&self) -> Result<()> {
+        let old_key =
+            VerifyingKey::from_bytes(&self.old_key).context("Invalid old key in rotation")?;
+        let signature =
+            Signature::from_slice(&self.signature).context("Invalid rotation signature")?;
+        old_key
+            .verify(
+                &Self::statement(&self.old_key, &self.new_key, &self.after),
+                &signature,
+            )
+            .map_err(|_| anyhow::anyhow!("Key rotation is not signed by the old key"))
+    }
+
+    /// The key speaking for `from` in a link listed after the envelopes hashing to
+    /// `earlier`, following verified rotations
+    ///
+    /// A rotation applies only once its `after` envelope is among `earlier`, so a
+    /// link the old key signed before rotating still resolves to the old key.
+    /// `None` when the rotations are ambiguous: a key on the path rotated to two
+    /// different keys (a fork, e.g. after the old key leaked) or the path loops
+    /// back on itself. The answer does not depend on the order of `rotations`.
+    pub(super) fn current_key(
+        rotations: &[KeyRotation],
+        from: &[u8; 32],
+        earlier: &[[u8; 32]],
+    ) -> Option<[u8; 32]> {
+        let valid: Vec<&KeyRotation> = rotations.iter().filter(|r| r.verify().is_ok()).collect();
+
+        let mut path = vec![*from];
+        let mut current = *from;
+        loop {
+            let successors: Vec<&KeyRotation> = valid
+                .iter()
+                .copied()
+                .filter(|r| crate::envelope::keys_equal(&r.old_key, &current))
+                .collect();
+            let Some(first) = successors.first() else {
+                return Some(current);
+            };
+            let next = first.new_key;
+            if successors
+                .iter()
+                .any(|other| !crate::envelope::keys_equal(&other.new_key, &next))
+                || path.iter().any(|k| crate::envelope::keys_equal(k, &next))
+            {
+                return None;
+            }
+            if !successors.iter().any(|r| earlier.contains(&r.after)) {
+                return Some(current);
+            }
+            path.push(next);
+            current = next;
+        }
+    }
+
+    /// Whether `key` speaks for `from` in a link listed after the envelopes
+    /// hashing to `earlier`
+    ///
+    /// Once a rotation of `from` has taken effect, only the end of its rotation
+    /// path qualifies: the old key and any intermediate key are rejected.
+    pub(super) fn resolves_to(
+        rotations: &[KeyRotation],
+        from: &[u8; 32],
+        key: &[u8; 32],
+        earlier: &[[u8; 32]],
+    ) -> bool {
+        Self::current_key(rotations, from, earlier)
+            .is_some_and(|current| crate::envelope::keys_equal(&current, key))
+    }
+}
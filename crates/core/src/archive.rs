@@ -6,7 +6,11 @@
 // Project: sealedge — Privacy and trust at the edge.
 //
 
-use crate::TrstManifest;
+use crate::{DeviceKeypair, TrstManifest};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use ed25519_dalek::{Signature, SigningKey};
+use sealedge_types::receipt::VerificationReceipt;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fs::{self, File};
 use std::io::{Read, Write};
@@ -14,6 +18,9 @@ use std::path::Path;
 
 pub use crate::error::{ArchiveError, ChainError, ManifestError};
 
+pub mod diff;
+pub mod merkle;
+
 /// Type alias for chunk data (index, bytes)
 type ChunkData = Vec<(usize, Vec<u8>)>;
 
@@ -35,10 +42,34 @@ pub fn write_archive<P: AsRef<Path>>(
         )));
     }
 
+    write_manifest_files(base_path, manifest, detached_sig)?;
+    fs::create_dir_all(base_path.join("chunks"))?;
+
+    // Write chunk files with zero-padded five-digit names
+    for (index, chunk_data) in chunk_ciphertexts.iter().enumerate() {
+        let chunk_filename = format!("{:05}.bin", index);
+        let chunk_path = base_path.join("chunks").join(chunk_filename);
+        let mut chunk_file = File::create(chunk_path)?;
+        chunk_file.write_all(chunk_data)?;
+    }
+
+    Ok(())
+}
+
+/// Read buffer size for streaming chunk files through BLAKE3
+const STREAM_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Write manifest.json and the detached signature, creating the archive directories
+///
+/// Used on its own when chunk files are already on disk.
+pub(crate) fn write_manifest_files(
+    base_path: &Path,
+    manifest: &TrstManifest,
+    detached_sig: &[u8],
+) -> Result<(), ArchiveError> {
     // Create directory structure
     fs::create_dir_all(base_path)?;
     fs::create_dir_all(base_path.join("signatures"))?;
-    fs::create_dir_all(base_path.join("chunks"))?;
 
     // Write manifest.json
     let manifest_json = serde_json::to_string_pretty(manifest)?;
@@ -49,14 +80,6 @@ pub fn write_archive<P: AsRef<Path>>(
     let mut sig_file = File::create(base_path.join("signatures/manifest.sig"))?;
     sig_file.write_all(detached_sig)?;
 
-    // Write chunk files with zero-padded five-digit names
-    for (index, chunk_data) in chunk_ciphertexts.iter().enumerate() {
-        let chunk_filename = format!("{:05}.bin", index);
-        let chunk_path = base_path.join("chunks").join(chunk_filename);
-        let mut chunk_file = File::create(chunk_path)?;
-        chunk_file.write_all(chunk_data)?;
-    }
-
     Ok(())
 }
 
@@ -65,7 +88,27 @@ pub fn read_archive<P: AsRef<Path>>(
     base_dir: P,
 ) -> Result<(TrstManifest, ChunkData), ArchiveError> {
     let base_path = base_dir.as_ref();
+    let manifest = read_checked_manifest(base_path)?;
+
+    // Read chunk files
+    let chunks_dir = base_path.join("chunks");
+    let mut chunk_data = Vec::new();
 
+    for (index, segment) in manifest.segments.iter().enumerate() {
+        let mut chunk_file = File::open(chunks_dir.join(&segment.chunk_file))?;
+        let mut chunk_bytes = Vec::new();
+        chunk_file.read_to_end(&mut chunk_bytes)?;
+
+        chunk_data.push((index, chunk_bytes));
+    }
+
+    Ok((manifest, chunk_data))
+}
+
+/// Read manifest.json and check it against the signature and chunk files on disk
+///
+/// Chunk files are only checked for presence and naming, never read.
+pub(crate) fn read_checked_manifest(base_path: &Path) -> Result<TrstManifest, ArchiveError> {
     // Read and parse manifest.json
     let manifest_path = base_path.join("manifest.json");
     let mut manifest_file = File::open(manifest_path)?;
@@ -87,16 +130,12 @@ pub fn read_archive<P: AsRef<Path>>(
         }
     }
 
-    // Read chunk files
     let chunks_dir = base_path.join("chunks");
-    let mut chunk_data = Vec::new();
-
     for (expected_index, segment) in manifest.segments.iter().enumerate() {
         let chunk_filename = format!("{:05}.bin", expected_index);
-        let chunk_path = chunks_dir.join(&chunk_filename);
 
         // Check if chunk file exists
-        if !chunk_path.exists() {
+        if !chunks_dir.join(&chunk_filename).exists() {
             return Err(ArchiveError::MissingChunk(chunk_filename));
         }
 
@@ -107,22 +146,71 @@ pub fn read_archive<P: AsRef<Path>>(
                 found: parse_chunk_index(&segment.chunk_file)?,
             });
         }
-
-        // Read chunk data
-        let mut chunk_file = File::open(chunk_path)?;
-        let mut chunk_bytes = Vec::new();
-        chunk_file.read_to_end(&mut chunk_bytes)?;
-
-        chunk_data.push((expected_index, chunk_bytes));
     }
 
-    Ok((manifest, chunk_data))
+    Ok(manifest)
 }
 
 /// Validate archive integrity including continuity chain
+///
+/// Chunks are streamed through BLAKE3 one at a time with a fixed-size buffer, so
+/// memory use does not grow with chunk size or segment count.
 pub fn validate_archive<P: AsRef<Path>>(base_dir: P) -> Result<(), ArchiveError> {
     let base_path = base_dir.as_ref();
-    let (manifest, chunk_data) = read_archive(base_path)?;
+    let manifest = read_validated_manifest(base_path)?;
+    check_chunk_files(base_path, &manifest)
+}
+
+/// Stream each chunk file `manifest` lists through BLAKE3 once and check the
+/// hashes and continuity chain against it
+fn check_chunk_files(base_path: &Path, manifest: &TrstManifest) -> Result<(), ArchiveError> {
+    let chunks_dir = base_path.join("chunks");
+    let mut buffer = vec![0u8; STREAM_BUFFER_SIZE];
+    let hashes = manifest
+        .segments
+        .iter()
+        .map(|segment| hash_chunk_file(&chunks_dir.join(&segment.chunk_file), &mut buffer));
+    check_segment_hashes(manifest, hashes)
+}
+
+/// Validate an archive, hashing chunk files on all cores
+///
+/// Same checks and the same error for any given archive as [`validate_archive`];
+/// only the chunk hashing is spread across the rayon thread pool. The continuity
+/// chain is still walked in segment order from the computed hashes.
+#[cfg(feature = "parallel")]
+pub fn validate_archive_parallel<P: AsRef<Path>>(base_dir: P) -> Result<(), ArchiveError> {
+    use rayon::prelude::*;
+
+    let base_path = base_dir.as_ref();
+    let manifest = read_validated_manifest(base_path)?;
+    let chunks_dir = base_path.join("chunks");
+
+    let hashes: Vec<_> = manifest
+        .segments
+        .par_iter()
+        .map_init(
+            || vec![0u8; STREAM_BUFFER_SIZE],
+            |buffer, segment| hash_chunk_file(&chunks_dir.join(&segment.chunk_file), buffer),
+        )
+        .collect();
+    check_segment_hashes(&manifest, hashes)
+}
+
+/// Read the manifest and run every check that does not need chunk contents
+fn read_validated_manifest(base_path: &Path) -> Result<TrstManifest, ArchiveError> {
+    let manifest = read_checked_manifest(base_path)?;
+    check_manifest_structure(base_path, &manifest)?;
+    Ok(manifest)
+}
+
+/// Checks on an already-read manifest that do not need chunk contents
+fn check_manifest_structure(base_path: &Path, manifest: &TrstManifest) -> Result<(), ArchiveError> {
+    // A capture with no segments has no chain to verify; report that on its own
+    // rather than as a generic manifest field error
+    if manifest.segments.is_empty() {
+        return Err(ChainError::Empty.into());
+    }
 
     // Check for unreferenced chunk files (SEC-02)
     let expected_chunks: HashSet<String> = manifest
@@ -146,13 +234,24 @@ pub fn validate_archive<P: AsRef<Path>>(base_dir: P) -> Result<(), ArchiveError>
     manifest.validate().map_err(|e| {
         ArchiveError::ValidationFailed(format!("Manifest validation failed: {}", e))
     })?;
+    merkle::check_segments_root(manifest)?;
 
-    // Validate chunk hashes and continuity chain
-    let mut chain_segments = Vec::new();
+    Ok(())
+}
 
-    for ((index, chunk_bytes), segment) in chunk_data.iter().zip(manifest.segments.iter()) {
-        // Compute BLAKE3 hash of chunk
-        let computed_hash = crate::chain::segment_hash(chunk_bytes);
+/// Check computed chunk hashes (in segment order) against the manifest
+fn check_segment_hashes(
+    manifest: &TrstManifest,
+    hashes: impl IntoIterator<Item = Result<[u8; 32], ArchiveError>>,
+) -> Result<(), ArchiveError> {
+    // Validate chunk hashes and continuity chain. Hash and format errors take
+    // precedence over chain errors, so the first chain break is held until every
+    // chunk has been hashed.
+    let mut expected_continuity = crate::chain::genesis();
+    let mut chain_error = None;
+
+    for ((index, segment), computed_hash) in manifest.segments.iter().enumerate().zip(hashes) {
+        let computed_hash = computed_hash?;
         let computed_hash_hex = hex::encode(computed_hash);
 
         // Check if stored hash matches computed hash
@@ -178,22 +277,976 @@ pub fn validate_archive<P: AsRef<Path>>(base_dir: P) -> Result<(), ArchiveError>
             )));
         }
 
-        let mut continuity_array = [0u8; 32];
-        continuity_array.copy_from_slice(&stored_continuity);
+        if chain_error.is_none() {
+            expected_continuity = crate::chain::chain_next(&expected_continuity, &computed_hash);
+            if expected_continuity[..] != stored_continuity[..] {
+                chain_error = Some(ChainError::OutOfOrder {
+                    expected: hex::encode(expected_continuity),
+                    found: hex::encode(&stored_continuity),
+                });
+            }
+        }
+    }
+
+    match chain_error {
+        Some(e) => Err(e.into()),
+        None => Ok(()),
+    }
+}
+
+/// Why [`check_archive`] rejected an archive it could read
+#[derive(Debug)]
+pub enum ArchiveCheckFailure {
+    /// The manifest carries no signature
+    MissingSignature,
+    /// The manifest could not be serialized canonically for the signature check
+    Canonicalization(String),
+    /// The signature does not verify under the device key
+    Signature(String),
+    /// Manifest structure, a chunk hash or the continuity chain failed
+    Continuity(ArchiveError),
+}
+
+/// Result of [`check_archive`]
+#[derive(Debug)]
+pub struct ArchiveCheck {
+    pub manifest: TrstManifest,
+    /// First check that failed; `None` when the archive verified
+    pub failure: Option<ArchiveCheckFailure>,
+}
+
+/// Verify an archive's signature against `device_pub`, then its continuity chain
+///
+/// This is the check behind `seal verify` and `sealedge verify`. The manifest is
+/// read once and each chunk file is streamed through BLAKE3 once, after the
+/// signature has passed. `device_pub` may carry an `ed25519:` or `ecdsa-p256:`
+/// prefix; bare keys are taken as Ed25519. Fails only if the archive cannot be
+/// read.
+pub fn check_archive<P: AsRef<Path>>(
+    base_dir: P,
+    device_pub: &str,
+) -> Result<ArchiveCheck, ArchiveError> {
+    let base_path = base_dir.as_ref();
+    let manifest = read_checked_manifest(base_path)?;
+
+    let device_pub = if device_pub.starts_with("ed25519:") || device_pub.starts_with("ecdsa-p256:")
+    {
+        device_pub.to_string()
+    } else {
+        format!("ed25519:{}", device_pub)
+    };
+    let signature_failure = match manifest.signature.as_deref() {
+        None => Some(ArchiveCheckFailure::MissingSignature),
+        Some(signature) => match manifest.to_canonical_bytes() {
+            Err(e) => Some(ArchiveCheckFailure::Canonicalization(e.to_string())),
+            Ok(canonical) => {
+                match crate::crypto::verify_manifest(&device_pub, &canonical, signature) {
+                    Ok(true) => None,
+                    Ok(false) => Some(ArchiveCheckFailure::Signature(
+                        "Signature verification failed".to_string(),
+                    )),
+                    Err(e) => Some(ArchiveCheckFailure::Signature(format!(
+                        "Signature verification error: {}",
+                        e
+                    ))),
+                }
+            }
+        },
+    };
+
+    let failure = signature_failure.or_else(|| {
+        check_manifest_structure(base_path, &manifest)
+            .and_then(|()| check_chunk_files(base_path, &manifest))
+            .err()
+            .map(ArchiveCheckFailure::Continuity)
+    });
 
-        chain_segments.push(crate::chain::ChainSegment {
-            index: *index,
-            stored_hash: computed_hash,
-            stored_continuity: continuity_array,
+    Ok(ArchiveCheck { manifest, failure })
+}
+
+/// Validate an archive that must be of a specific profile
+///
+/// The profile is checked before anything else, so e.g. a `cam.video` verifier
+/// reports a clear [`ArchiveError::ProfileMismatch`] for a `cam.audio` archive rather
+/// than a metadata error. Everything else is as [`validate_archive`].
+pub fn validate_archive_for_profile<P: AsRef<Path>>(
+    base_dir: P,
+    expected_profile: &str,
+) -> Result<(), ArchiveError> {
+    let base_path = base_dir.as_ref();
+    let manifest = read_checked_manifest(base_path)?;
+    if manifest.expect_profile(expected_profile).is_err() {
+        return Err(ArchiveError::ProfileMismatch {
+            expected: expected_profile.to_string(),
+            found: manifest.profile,
         });
     }
+    validate_archive(base_path)
+}
+
+/// Validate an archive sealed under a per-archive key and decrypt its chunks
+///
+/// The continuity chain is checked over the ciphertext as in [`validate_archive`]
+/// before anything is decrypted. Each chunk is opened with the nonce recorded in
+/// its segment and the AAD rebuilt from the manifest; plaintexts are returned in
+/// segment order.
+pub fn decrypt_archive<P: AsRef<Path>>(
+    base_dir: P,
+    archive_key: &[u8; 32],
+) -> Result<Vec<Vec<u8>>, ArchiveError> {
+    let base_path = base_dir.as_ref();
+    validate_archive(base_path)?;
+    let manifest = read_checked_manifest(base_path)?;
+
+    let key = chacha20poly1305::Key::from(*archive_key);
+    let aad = crate::crypto::generate_aad(
+        &manifest.trst_version,
+        &manifest.profile,
+        &manifest.device.id,
+        manifest.metadata.started_at(),
+    );
+    let chunks_dir = base_path.join("chunks");
+
+    manifest
+        .segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            let decryption = |reason: String| ArchiveError::Decryption { index, reason };
+            let nonce = segment
+                .nonce
+                .as_deref()
+                .ok_or_else(|| decryption("segment has no recorded nonce".to_string()))?;
+            let nonce = crate::crypto::parse_nonce(nonce).map_err(|e| decryption(e.to_string()))?;
+            let ciphertext = fs::read(chunks_dir.join(&segment.chunk_file))?;
+            crate::crypto::decrypt_segment(&key, &nonce, &ciphertext, &aad)
+                .map_err(|e| decryption(e.to_string()))
+        })
+        .collect()
+}
+
+/// Per-segment findings from [`inspect_archive`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentReport {
+    pub index: usize,
+    pub chunk_file: String,
+    pub start_time: String,
+    pub duration_seconds: f64,
+    /// BLAKE3 hash recorded in the manifest
+    pub expected_blake3: String,
+    /// BLAKE3 hash of the chunk file, `None` when it is missing or unreadable
+    pub computed_blake3: Option<String>,
+    pub hash_matches: bool,
+    /// Whether the recorded continuity hash follows from the previous segment's
+    /// recorded continuity hash and this chunk's computed hash
+    pub continuity_matches: bool,
+}
+
+impl SegmentReport {
+    pub fn is_valid(&self) -> bool {
+        self.hash_matches && self.continuity_matches
+    }
+}
+
+/// Diagnostic breakdown of a `.trst` archive from [`inspect_archive`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveReport {
+    pub trst_version: String,
+    pub profile: String,
+    pub device_id: String,
+    pub started_at: String,
+    pub claims: Vec<String>,
+    /// Why `claims` does not parse as a [`crate::ClaimSet`], if it does not;
+    /// informational only, it does not affect [`is_valid`](Self::is_valid)
+    pub claims_error: Option<String>,
+    pub prev_archive_hash: Option<String>,
+    /// Whether the manifest carries an embedded signature
+    pub signature_present: bool,
+    /// Whether `signatures/manifest.sig` exists and matches the embedded signature
+    pub signature_consistent: bool,
+    /// Structural manifest validation error, if any
+    pub manifest_error: Option<String>,
+    /// `.bin` files in `chunks/` that no segment references
+    pub unreferenced_chunks: Vec<String>,
+    pub segments: Vec<SegmentReport>,
+}
+
+impl ArchiveReport {
+    /// Whether every check in the report passed
+    pub fn is_valid(&self) -> bool {
+        self.signature_consistent
+            && self.manifest_error.is_none()
+            && self.unreferenced_chunks.is_empty()
+            && self.segments.iter().all(SegmentReport::is_valid)
+    }
+
+    /// Segments that failed a hash or continuity check
+    pub fn failed_segments(&self) -> impl Iterator<Item = &SegmentReport> {
+        self.segments.iter().filter(|s| !s.is_valid())
+    }
+}
 
-    // Validate continuity chain
-    crate::chain::validate_chain(&chain_segments)?;
+/// Inspect an archive without stopping at the first problem
+///
+/// Unlike [`validate_archive`], every segment is hashed and reported, so a failing
+/// archive shows exactly which segments are bad. Each continuity link is checked
+/// against the previous segment's recorded continuity hash, so a single corrupted
+/// chunk flags only its own segment. The manifest signature is checked for presence
+/// and consistency with the detached copy only; no device key is needed. Only an
+/// unreadable or unparseable `manifest.json` is an error.
+pub fn inspect_archive<P: AsRef<Path>>(base_dir: P) -> Result<ArchiveReport, ArchiveError> {
+    let base_path = base_dir.as_ref();
+    let manifest: TrstManifest =
+        serde_json::from_str(&fs::read_to_string(base_path.join("manifest.json"))?)?;
+
+    let detached_sig = fs::read(base_path.join("signatures/manifest.sig")).ok();
+    let signature_consistent = match (&manifest.signature, &detached_sig) {
+        (Some(embedded), Some(detached)) => embedded.as_bytes() == detached.as_slice(),
+        (None, Some(_)) => true,
+        (_, None) => false,
+    };
+
+    let chunks_dir = base_path.join("chunks");
+    let referenced: HashSet<&str> = manifest
+        .segments
+        .iter()
+        .map(|s| s.chunk_file.as_str())
+        .collect();
+    let mut unreferenced_chunks = Vec::new();
+    if let Ok(entries) = fs::read_dir(&chunks_dir) {
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if file_name.ends_with(".bin") && !referenced.contains(file_name.as_str()) {
+                unreferenced_chunks.push(file_name);
+            }
+        }
+    }
+    unreferenced_chunks.sort();
+
+    let mut buffer = vec![0u8; STREAM_BUFFER_SIZE];
+    let mut previous_continuity = Some(crate::chain::genesis());
+    let mut segments = Vec::with_capacity(manifest.segments.len());
+    for (index, segment) in manifest.segments.iter().enumerate() {
+        let computed = hash_chunk_file(&chunks_dir.join(&segment.chunk_file), &mut buffer).ok();
+        let computed_blake3 = computed.map(hex::encode);
+        let hash_matches = computed_blake3.as_deref() == Some(segment.blake3_hash.as_str());
+
+        let stored_continuity: Option<[u8; 32]> = hex::decode(&segment.continuity_hash)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok());
+        let continuity_matches = match (previous_continuity, computed, stored_continuity) {
+            (Some(previous), Some(hash), Some(stored)) => {
+                crate::chain::chain_next(&previous, &hash) == stored
+            }
+            _ => false,
+        };
+        previous_continuity = stored_continuity;
+
+        segments.push(SegmentReport {
+            index,
+            chunk_file: segment.chunk_file.clone(),
+            start_time: segment.start_time.clone(),
+            duration_seconds: segment.duration_seconds,
+            expected_blake3: segment.blake3_hash.clone(),
+            computed_blake3,
+            hash_matches,
+            continuity_matches,
+        });
+    }
+
+    Ok(ArchiveReport {
+        trst_version: manifest.trst_version.clone(),
+        profile: manifest.profile.clone(),
+        device_id: manifest.device.id.clone(),
+        started_at: manifest.metadata.started_at().to_string(),
+        claims: manifest.claims.clone(),
+        claims_error: crate::ClaimSet::parse(&manifest.claims)
+            .err()
+            .map(|e| e.to_string()),
+        prev_archive_hash: manifest.prev_archive_hash.clone(),
+        signature_present: manifest.signature.is_some(),
+        signature_consistent,
+        manifest_error: manifest.validate().err().map(|e| e.to_string()),
+        unreferenced_chunks,
+        segments,
+    })
+}
+
+/// BLAKE3 hash of a chunk file, read through `buffer`
+pub(crate) fn hash_chunk_file(path: &Path, buffer: &mut [u8]) -> Result<[u8; 32], ArchiveError> {
+    let mut file = File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    loop {
+        let read = file.read(buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize().into())
+}
+
+/// Outcome of verifying a sequence of archives linked by `prev_archive_hash`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainVerifyOutcome {
+    /// Every archive verified and each one links to its predecessor
+    Valid {
+        archive_count: usize,
+        /// [`archive_hash`] of the last archive, for linking the next one
+        head_hash: String,
+    },
+    /// The first archive (by position in the input) that breaks the chain
+    Broken { index: usize, reason: ChainBreak },
+}
+
+/// Why an archive breaks a cross-archive chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainBreak {
+    /// The archive failed structural, hash, or continuity validation
+    InvalidArchive(String),
+    /// The manifest signature did not verify against the device key
+    BadSignature(String),
+    /// `prev_archive_hash` does not name the previous archive
+    PrevHashMismatch {
+        expected: String,
+        found: Option<String>,
+    },
+    /// The archive is signed by a key that is neither trusted nor reached by an
+    /// authorized rotation
+    UnauthorizedKey(String),
+}
+
+/// Canonical hash of an archive, as referenced by the next archive's `prev_archive_hash`
+///
+/// BLAKE3 over the manifest's canonical bytes (hex encoded). The manifest commits to
+/// every chunk hash and the continuity chain, so this pins the whole archive.
+pub fn archive_hash(manifest: &TrstManifest) -> Result<String, ArchiveError> {
+    Ok(blake3::hash(&canonical_manifest_bytes(manifest)?)
+        .to_hex()
+        .to_string())
+}
+
+fn canonical_manifest_bytes(manifest: &TrstManifest) -> Result<Vec<u8>, ArchiveError> {
+    manifest.to_canonical_bytes().map_err(|e| {
+        ArchiveError::ValidationFailed(format!("Manifest canonicalization failed: {}", e))
+    })
+}
+
+/// Sign a manifest's canonical bytes without touching the manifest or archive
+///
+/// For manifests signed after the fact, e.g. on a separate machine from the one that
+/// wrote the chunks. The result is the same `ed25519:<base64>` string an inline-signed
+/// manifest carries; any `signature` already on `manifest` is ignored.
+pub fn sign_manifest_detached(
+    manifest: &TrstManifest,
+    signing_key: &DeviceKeypair,
+) -> Result<String, ArchiveError> {
+    crate::crypto::sign_manifest(signing_key, &canonical_manifest_bytes(manifest)?)
+        .map_err(|e| ArchiveError::ValidationFailed(format!("Manifest signing failed: {}", e)))
+}
+
+/// Verify a detached signature over a serialized manifest
+///
+/// `manifest_bytes` is `manifest.json` as shipped. It is parsed and re-canonicalized,
+/// so formatting and any embedded `signature` do not affect the result. Returns
+/// `Ok(false)` when the signature does not match `device_pub`.
+pub fn verify_manifest_detached(
+    manifest_bytes: &[u8],
+    signature: &str,
+    device_pub: &str,
+) -> Result<bool, ArchiveError> {
+    let manifest: TrstManifest = serde_json::from_slice(manifest_bytes)?;
+    crate::crypto::verify_manifest(device_pub, &canonical_manifest_bytes(&manifest)?, signature)
+        .map_err(|e| ArchiveError::ValidationFailed(format!("Signature check failed: {}", e)))
+}
+
+/// Status of one verification check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// The check was not run, so it says nothing either way
+    Skipped,
+}
+
+/// A check that manifest-only verification cannot run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkippedCheck {
+    /// Chunk files were not supplied, so their BLAKE3 hashes were not recomputed
+    ChunkHashes,
+    /// The continuity chain depends on the chunk hashes
+    Continuity,
+}
+
+impl SkippedCheck {
+    /// Why the check was skipped, for display
+    pub fn reason(&self) -> &'static str {
+        match self {
+            SkippedCheck::ChunkHashes => "chunk hashes not checked: no chunk files supplied",
+            SkippedCheck::Continuity => "continuity chain not checked: no chunk files supplied",
+        }
+    }
+}
+
+/// Result of [`verify_manifest_only`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestOnlyOutcome {
+    pub signature: CheckStatus,
+    /// Always [`CheckStatus::Skipped`]; continuity needs the chunks
+    pub continuity: CheckStatus,
+    pub segment_count: usize,
+    /// Checks that were not run, in the order a full verification would run them
+    pub skipped: Vec<SkippedCheck>,
+    /// Why the signature check failed, if it did
+    pub signature_error: Option<String>,
+}
+
+/// Verify a manifest's embedded signature without any chunk files
+///
+/// Only the signature over the canonical manifest bytes is checked. Chunk hashes
+/// and continuity are reported as skipped rather than passed, so a caller cannot
+/// mistake this for full archive verification; use [`validate_archive`] for that.
+/// Fails only if `manifest_bytes` is not a manifest.
+pub fn verify_manifest_only(
+    manifest_bytes: &[u8],
+    device_pub: &str,
+) -> Result<ManifestOnlyOutcome, ArchiveError> {
+    let manifest: TrstManifest = serde_json::from_slice(manifest_bytes)?;
+
+    let signature_error = match manifest.signature.as_deref() {
+        None => Some("Manifest has no signature".to_string()),
+        Some(signature) => match verify_manifest_detached(manifest_bytes, signature, device_pub) {
+            Ok(true) => None,
+            Ok(false) => Some("Signature does not match the device public key".to_string()),
+            Err(e) => Some(e.to_string()),
+        },
+    };
+
+    Ok(ManifestOnlyOutcome {
+        signature: if signature_error.is_none() {
+            CheckStatus::Pass
+        } else {
+            CheckStatus::Fail
+        },
+        continuity: CheckStatus::Skipped,
+        segment_count: manifest.segments.len(),
+        skipped: vec![SkippedCheck::ChunkHashes, SkippedCheck::Continuity],
+        signature_error,
+    })
+}
+
+/// Result of [`verify_archive_with_receipt`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyOutcome {
+    pub signature: CheckStatus,
+    /// [`CheckStatus::Skipped`] when the signature failed
+    pub continuity: CheckStatus,
+    /// Why the first failing check failed
+    pub error: Option<String>,
+    /// Continuity hash after the last chunk on disk, `b3:`-prefixed; genesis when
+    /// there are no segments. Computed from the chunk contents, so a later archive
+    /// can chain from it whether or not verification passed.
+    pub chain_tip: String,
+}
+
+impl VerifyOutcome {
+    pub fn is_valid(&self) -> bool {
+        self.signature == CheckStatus::Pass && self.continuity == CheckStatus::Pass
+    }
+}
+
+/// Verify an archive and issue a [`VerificationReceipt`] signed by `service_signing_key`
+///
+/// A receipt is issued whether or not verification passed, so a failure is as
+/// provable as a pass. `manifest_digest` is the [`archive_hash`] and `service_kid`
+/// is derived from the service public key, so the receipt can be checked offline
+/// with [`VerificationReceipt::verify`]. `chain_tip` is the recomputed
+/// [`VerifyOutcome::chain_tip`], never the manifest's claimed continuity hash.
+/// Fails only if the archive cannot be read.
+pub fn verify_archive_with_receipt<P: AsRef<Path>>(
+    base_dir: P,
+    device_pub: &str,
+    service_signing_key: &SigningKey,
+) -> Result<(VerifyOutcome, VerificationReceipt, Signature), ArchiveError> {
+    let base_dir = base_dir.as_ref();
+    let (manifest, chunks) = read_archive(base_dir)?;
+    let outcome = verify_outcome(base_dir, &manifest, &chunks, device_pub)?;
+
+    let mut verification_id = [0u8; 16];
+    rand::RngCore::fill_bytes(&mut rand::rng(), &mut verification_id);
+    let service_public_key = service_signing_key.verifying_key();
+    let receipt = VerificationReceipt {
+        verification_id: format!("v_{}", hex::encode(verification_id)),
+        profile: manifest.profile.clone(),
+        device_id: manifest.device.id.clone(),
+        manifest_digest: archive_hash(&manifest)?,
+        segments: manifest.segments.len() as u32,
+        duration_s: manifest
+            .segments
+            .iter()
+            .map(|s| s.duration_seconds as f32)
+            .sum(),
+        signature: receipt_status(outcome.signature).to_string(),
+        continuity: receipt_status(outcome.continuity).to_string(),
+        issued_at: chrono::Utc::now().to_rfc3339(),
+        service_kid: format!(
+            "key_{}",
+            &blake3::hash(service_public_key.as_bytes()).to_hex()[..32]
+        ),
+        chain_tip: outcome.chain_tip.clone(),
+    };
+    let signature = receipt
+        .sign(service_signing_key)
+        .map_err(|e| ArchiveError::ValidationFailed(format!("Receipt signing failed: {}", e)))?;
+
+    Ok((outcome, receipt, signature))
+}
+
+/// Check the signature against `device_pub`, then the chunk hashes and continuity
+///
+/// Works only on the `manifest` and `chunks` already read, so what is reported is
+/// exactly what was checked even if the archive changes on disk meanwhile.
+fn verify_outcome(
+    base_dir: &Path,
+    manifest: &TrstManifest,
+    chunks: &ChunkData,
+    device_pub: &str,
+) -> Result<VerifyOutcome, ArchiveError> {
+    let chain_tip = crate::chain::blake3_hex_or_b64(
+        &chunks
+            .iter()
+            .fold(crate::chain::genesis(), |prev, (_, data)| {
+                crate::chain::chain_next(&prev, &crate::chain::segment_hash(data))
+            }),
+    );
+
+    let signature_error = match manifest.signature.as_deref() {
+        None => Some("Manifest has no signature".to_string()),
+        Some(signature) => {
+            match crate::crypto::verify_manifest(
+                device_pub,
+                &canonical_manifest_bytes(manifest)?,
+                signature,
+            ) {
+                Ok(true) => None,
+                Ok(false) => Some("Signature does not match the device public key".to_string()),
+                Err(e) => Some(format!("Signature check failed: {}", e)),
+            }
+        }
+    };
+    let outcome = match signature_error {
+        Some(error) => VerifyOutcome {
+            signature: CheckStatus::Fail,
+            continuity: CheckStatus::Skipped,
+            error: Some(error),
+            chain_tip,
+        },
+        None => {
+            let continuity_error = check_manifest_structure(base_dir, manifest)
+                .and_then(|()| {
+                    let hashes = chunks
+                        .iter()
+                        .map(|(_, data)| Ok(crate::chain::segment_hash(data)));
+                    check_segment_hashes(manifest, hashes)
+                })
+                .err()
+                .map(|e| e.to_string());
+            VerifyOutcome {
+                signature: CheckStatus::Pass,
+                continuity: if continuity_error.is_none() {
+                    CheckStatus::Pass
+                } else {
+                    CheckStatus::Fail
+                },
+                error: continuity_error,
+                chain_tip,
+            }
+        }
+    };
+
+    Ok(outcome)
+}
+
+/// Result of [`verify_archive_trust_on_first_use`] and [`verify_archive_pinned`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedKeyOutcome {
+    pub outcome: VerifyOutcome,
+    /// `device.public_key` from the manifest; the signature was checked against it
+    pub device_public_key: String,
+    /// [`device_key_fingerprint`] of `device_public_key`
+    pub fingerprint: String,
+}
+
+/// Fingerprint of a device public key, for pinning
+///
+/// Hex BLAKE3 over the key's algorithm name and raw bytes, so keys of different
+/// algorithms never share a fingerprint.
+pub fn device_key_fingerprint(device_pub: &str) -> Result<String, ArchiveError> {
+    let invalid = |reason: &str| {
+        ArchiveError::ValidationFailed(format!("Invalid device public key: {}", reason))
+    };
+    let (algorithm, encoded) = device_pub
+        .split_once(':')
+        .ok_or_else(|| invalid("expected <algorithm>:<base64>"))?;
+    let key_bytes = BASE64
+        .decode(encoded)
+        .map_err(|e| invalid(&e.to_string()))?;
+
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(algorithm.as_bytes());
+    hasher.update(&[0]);
+    hasher.update(&key_bytes);
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// Verify an archive against the device key embedded in its own manifest
+///
+/// A passing signature only shows the archive is self-consistent: anyone can sign
+/// with a fresh key and embed it. Record the returned `fingerprint` the first time
+/// a device is seen, and check its later archives with [`verify_archive_pinned`].
+pub fn verify_archive_trust_on_first_use<P: AsRef<Path>>(
+    base_dir: P,
+) -> Result<EmbeddedKeyOutcome, ArchiveError> {
+    verify_embedded_key(base_dir.as_ref(), None)
+}
+
+/// [`verify_archive_trust_on_first_use`] for a device whose key is already known
+///
+/// Fails with [`ArchiveError::DeviceKeyNotPinned`] before checking anything else
+/// when the embedded key's fingerprint is not `expected_fingerprint`.
+pub fn verify_archive_pinned<P: AsRef<Path>>(
+    base_dir: P,
+    expected_fingerprint: &str,
+) -> Result<EmbeddedKeyOutcome, ArchiveError> {
+    verify_embedded_key(base_dir.as_ref(), Some(expected_fingerprint))
+}
+
+/// Read the archive once, check the embedded key against `expected_fingerprint`
+/// if given, and verify that same manifest and chunk data
+fn verify_embedded_key(
+    base_dir: &Path,
+    expected_fingerprint: Option<&str>,
+) -> Result<EmbeddedKeyOutcome, ArchiveError> {
+    let (manifest, chunks) = read_archive(base_dir)?;
+    let fingerprint = device_key_fingerprint(&manifest.device.public_key)?;
+    if let Some(expected) = expected_fingerprint {
+        if !fingerprint.eq_ignore_ascii_case(expected.trim()) {
+            return Err(ArchiveError::DeviceKeyNotPinned {
+                expected: expected.to_string(),
+                found: fingerprint,
+            });
+        }
+    }
+    let outcome = verify_outcome(base_dir, &manifest, &chunks, &manifest.device.public_key)?;
+    Ok(EmbeddedKeyOutcome {
+        outcome,
+        device_public_key: manifest.device.public_key,
+        fingerprint,
+    })
+}
+
+/// Check status as spelled in receipts and verify reports
+fn receipt_status(status: CheckStatus) -> &'static str {
+    match status {
+        CheckStatus::Pass => "pass",
+        CheckStatus::Fail => "fail",
+        CheckStatus::Skipped => "skip",
+    }
+}
+
+/// Domain separator for device attestation signatures
+const DEVICE_ATTESTATION_DOMAIN: &str = "sealedge-device-attestation-v1";
+
+/// A root key's statement that a device key lives in attested hardware
+///
+/// Carried base64-encoded (JSON inside) in [`TrstManifest::device_attestation`],
+/// where the manifest signature covers it. The root signs the device id and
+/// public key, so the attestation cannot be moved to another device.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceAttestation {
+    pub device_id: String,
+    /// Attested device key, `ed25519:<base64>`
+    pub device_public_key: String,
+    /// Root that issued the attestation, `ed25519:<base64>`
+    pub root_public_key: String,
+    /// Root signature, `ed25519:<base64>`
+    pub signature: String,
+}
+
+impl DeviceAttestation {
+    /// Attest `device_public_key` for `device_id` with a root key
+    pub fn issue(
+        root: &DeviceKeypair,
+        device_id: &str,
+        device_public_key: &str,
+    ) -> Result<Self, ArchiveError> {
+        let signature =
+            crate::crypto::sign_manifest(root, &Self::signed_bytes(device_id, device_public_key)?)
+                .map_err(|e| {
+                    ArchiveError::ValidationFailed(format!(
+                        "Device attestation signing failed: {}",
+                        e
+                    ))
+                })?;
+        Ok(Self {
+            device_id: device_id.to_string(),
+            device_public_key: device_public_key.to_string(),
+            root_public_key: root.public.clone(),
+            signature,
+        })
+    }
+
+    /// Base64 blob for [`TrstManifest::device_attestation`]
+    pub fn encode(&self) -> Result<String, ArchiveError> {
+        Ok(BASE64.encode(serde_json::to_vec(self)?))
+    }
+
+    /// Parse a blob produced by [`DeviceAttestation::encode`]
+    pub fn decode(blob: &str) -> Result<Self, ArchiveError> {
+        let json = BASE64.decode(blob.trim()).map_err(|e| {
+            ArchiveError::ValidationFailed(format!("Invalid device attestation encoding: {}", e))
+        })?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    fn signed_bytes(device_id: &str, device_public_key: &str) -> Result<Vec<u8>, ArchiveError> {
+        Ok(serde_json::to_vec(&[
+            DEVICE_ATTESTATION_DOMAIN,
+            device_id,
+            device_public_key,
+        ])?)
+    }
+}
+
+/// Check a manifest's device attestation against trusted root keys
+///
+/// Fails if the manifest carries no attestation, the attestation's root is not
+/// one of `trusted_roots` (`ed25519:<base64>`), its signature does not verify, or
+/// it attests a different device id or key than the manifest's `device`. This
+/// does not check the manifest signature itself.
+pub fn verify_device_attestation(
+    manifest: &TrstManifest,
+    trusted_roots: &[&str],
+) -> Result<(), ArchiveError> {
+    let blob = manifest.device_attestation.as_deref().ok_or_else(|| {
+        ArchiveError::ValidationFailed("Manifest has no device attestation".to_string())
+    })?;
+    let attestation = DeviceAttestation::decode(blob)?;
+
+    if !trusted_roots.contains(&attestation.root_public_key.as_str()) {
+        return Err(ArchiveError::ValidationFailed(format!(
+            "Device attestation root {} is not trusted",
+            attestation.root_public_key
+        )));
+    }
+
+    let signed_bytes =
+        DeviceAttestation::signed_bytes(&attestation.device_id, &attestation.device_public_key)?;
+    let valid = crate::crypto::verify_manifest(
+        &attestation.root_public_key,
+        &signed_bytes,
+        &attestation.signature,
+    )
+    .map_err(|e| {
+        ArchiveError::ValidationFailed(format!("Device attestation check failed: {}", e))
+    })?;
+    if !valid {
+        return Err(ArchiveError::ValidationFailed(
+            "Device attestation signature is invalid".to_string(),
+        ));
+    }
+
+    if attestation.device_public_key != manifest.device.public_key {
+        return Err(ArchiveError::ValidationFailed(format!(
+            "Device attestation is for key {}, manifest device key is {}",
+            attestation.device_public_key, manifest.device.public_key
+        )));
+    }
+    if attestation.device_id != manifest.device.id {
+        return Err(ArchiveError::ValidationFailed(format!(
+            "Device attestation is for device {}, manifest device is {}",
+            attestation.device_id, manifest.device.id
+        )));
+    }
 
     Ok(())
 }
 
+/// Domain separator for device key rotation signatures
+const DEVICE_KEY_ROTATION_DOMAIN: &str = "sealedge-device-key-rotation-v1";
+
+/// A device's statement, signed with its old key, that it now signs with a new key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceKeyRotation {
+    pub device_id: String,
+    /// Key being retired, `ed25519:<base64>`
+    pub old_public_key: String,
+    /// Key taking over, `ed25519:<base64>`
+    pub new_public_key: String,
+    /// Signature by the old key, `ed25519:<base64>`
+    pub signature: String,
+}
+
+impl DeviceKeyRotation {
+    /// Rotate `device_id` from `old` to `new_public_key`, signed by the old key
+    pub fn issue(
+        old: &DeviceKeypair,
+        device_id: &str,
+        new_public_key: &str,
+    ) -> Result<Self, ArchiveError> {
+        let signature = crate::crypto::sign_manifest(
+            old,
+            &Self::signed_bytes(device_id, &old.public, new_public_key)?,
+        )
+        .map_err(|e| {
+            ArchiveError::ValidationFailed(format!("Key rotation signing failed: {}", e))
+        })?;
+        Ok(Self {
+            device_id: device_id.to_string(),
+            old_public_key: old.public.clone(),
+            new_public_key: new_public_key.to_string(),
+            signature,
+        })
+    }
+
+    /// Whether the old key really signed this rotation
+    pub fn verify(&self) -> bool {
+        Self::signed_bytes(&self.device_id, &self.old_public_key, &self.new_public_key)
+            .ok()
+            .and_then(|bytes| {
+                crate::crypto::verify_manifest(&self.old_public_key, &bytes, &self.signature).ok()
+            })
+            .unwrap_or(false)
+    }
+
+    fn signed_bytes(
+        device_id: &str,
+        old_public_key: &str,
+        new_public_key: &str,
+    ) -> Result<Vec<u8>, ArchiveError> {
+        Ok(serde_json::to_vec(&[
+            DEVICE_KEY_ROTATION_DOMAIN,
+            device_id,
+            old_public_key,
+            new_public_key,
+        ])?)
+    }
+}
+
+/// Verify that archives form an unbroken chain, oldest first
+///
+/// Each archive's manifest is read once, validated as by [`validate_archive`] and its
+/// signature checked against `device_pub`; every archive after the first must carry
+/// the [`archive_hash`] of that same manifest of the one before it. The
+/// first archive's own `prev_archive_hash` is not checked, so a verified window can
+/// start mid-chain.
+pub fn verify_archive_chain<P: AsRef<Path>>(
+    archive_paths: &[P],
+    device_pub: &str,
+) -> Result<ChainVerifyOutcome, ArchiveError> {
+    verify_archive_chain_with_rotations(archive_paths, &[device_pub], &[])
+}
+
+/// Verify an archive chain whose device keys may rotate partway through
+///
+/// Like [`verify_archive_chain`], but each archive is checked against the key in
+/// its manifest, which must be one of `trusted_keys` or reached from one through
+/// `rotations`. A rotation counts only if its old key signed it and it names the
+/// archive's device; once applied, the old key is retired for the rest of the chain.
+pub fn verify_archive_chain_with_rotations<P: AsRef<Path>>(
+    archive_paths: &[P],
+    trusted_keys: &[&str],
+    rotations: &[DeviceKeyRotation],
+) -> Result<ChainVerifyOutcome, ArchiveError> {
+    if archive_paths.is_empty() {
+        return Err(ArchiveError::ValidationFailed(
+            "No archives to verify".to_string(),
+        ));
+    }
+
+    let mut active_keys: Vec<String> = trusted_keys.iter().map(|k| k.to_string()).collect();
+    let mut previous_hash: Option<String> = None;
+    for (index, path) in archive_paths.iter().enumerate() {
+        let path = path.as_ref();
+        let broken = |reason| Ok(ChainVerifyOutcome::Broken { index, reason });
+
+        // Link on the very manifest that was validated, read once
+        let manifest = match read_validated_manifest(path)
+            .and_then(|manifest| check_chunk_files(path, &manifest).map(|()| manifest))
+        {
+            Ok(manifest) => manifest,
+            Err(e) => return broken(ChainBreak::InvalidArchive(e.to_string())),
+        };
+        let signer = &manifest.device.public_key;
+        if !authorize_device_key(&mut active_keys, &manifest.device.id, signer, rotations) {
+            return broken(ChainBreak::UnauthorizedKey(signer.clone()));
+        }
+        if let Err(reason) = check_archive_signature(&manifest, signer) {
+            return broken(ChainBreak::BadSignature(reason));
+        }
+
+        if let Some(expected) = previous_hash {
+            if manifest.prev_archive_hash.as_deref() != Some(expected.as_str()) {
+                return broken(ChainBreak::PrevHashMismatch {
+                    expected,
+                    found: manifest.prev_archive_hash,
+                });
+            }
+        }
+
+        previous_hash = Some(archive_hash(&manifest)?);
+    }
+
+    Ok(ChainVerifyOutcome::Valid {
+        archive_count: archive_paths.len(),
+        head_hash: previous_hash.unwrap_or_default(),
+    })
+}
+
+/// Apply rotations one at a time until `key` is active or none applies
+///
+/// Each step replaces an active key with the key it was rotated to, so a chain of
+/// rotations A -> B -> C is followed only as far as the archive being checked needs.
+fn authorize_device_key(
+    active_keys: &mut [String],
+    device_id: &str,
+    key: &str,
+    rotations: &[DeviceKeyRotation],
+) -> bool {
+    while !active_keys.iter().any(|k| k == key) {
+        let Some((slot, rotation)) = rotations.iter().find_map(|rotation| {
+            let slot = active_keys
+                .iter()
+                .position(|k| *k == rotation.old_public_key)?;
+            let applies = rotation.device_id == device_id
+                && !active_keys.contains(&rotation.new_public_key)
+                && rotation.verify();
+            applies.then_some((slot, rotation))
+        }) else {
+            return false;
+        };
+        active_keys[slot] = rotation.new_public_key.clone();
+    }
+    true
+}
+
+/// Check the embedded manifest signature against the device key
+pub(crate) fn check_archive_signature(
+    manifest: &TrstManifest,
+    device_pub: &str,
+) -> Result<(), String> {
+    let signature = manifest
+        .signature
+        .as_deref()
+        .ok_or_else(|| "Manifest has no signature".to_string())?;
+    let canonical = manifest.to_canonical_bytes().map_err(|e| e.to_string())?;
+    match crate::crypto::verify_manifest(device_pub, &canonical, signature) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("Signature does not match device key".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
 /// Parse chunk index from filename (e.g., "00002.bin" -> 2)
 fn parse_chunk_index(filename: &str) -> Result<usize, ArchiveError> {
     if !filename.ends_with(".bin") || filename.len() != 9 {
@@ -246,6 +1299,7 @@ mod tests {
                 start_time: "2025-01-15T10:30:00Z".to_string(),
                 duration_seconds: 2.0,
                 continuity_hash: hex::encode(continuity0),
+                nonce: None,
             },
             SegmentInfo {
                 chunk_file: "00001.bin".to_string(),
@@ -253,6 +1307,7 @@ mod tests {
                 start_time: "2025-01-15T10:30:02Z".to_string(),
                 duration_seconds: 2.0,
                 continuity_hash: hex::encode(continuity1),
+                nonce: None,
             },
             SegmentInfo {
                 chunk_file: "00002.bin".to_string(),
@@ -260,6 +1315,7 @@ mod tests {
                 start_time: "2025-01-15T10:30:04Z".to_string(),
                 duration_seconds: 2.0,
                 continuity_hash: hex::encode(continuity2),
+                nonce: None,
             },
         ];
 
@@ -407,6 +1463,159 @@ mod tests {
         }
     }
 
+    /// Validation as it was before streaming: every chunk loaded up front
+    fn validate_archive_buffered(base_path: &Path) -> Result<(), ArchiveError> {
+        let (manifest, chunk_data) = read_archive(base_path)?;
+        manifest.validate().map_err(|e| {
+            ArchiveError::ValidationFailed(format!("Manifest validation failed: {}", e))
+        })?;
+
+        let mut chain_segments = Vec::new();
+        for ((index, chunk_bytes), segment) in chunk_data.iter().zip(manifest.segments.iter()) {
+            let computed_hash = crate::chain::segment_hash(chunk_bytes);
+            let computed_hash_hex = hex::encode(computed_hash);
+            if segment.blake3_hash != computed_hash_hex {
+                return Err(ArchiveError::ValidationFailed(format!(
+                    "Chunk {} hash mismatch: expected {}, computed {}",
+                    index, segment.blake3_hash, computed_hash_hex
+                )));
+            }
+            let stored_continuity = hex::decode(&segment.continuity_hash).map_err(|_| {
+                ArchiveError::ValidationFailed(format!(
+                    "Invalid continuity hash format: {}",
+                    segment.continuity_hash
+                ))
+            })?;
+            if stored_continuity.len() != 32 {
+                return Err(ArchiveError::ValidationFailed(format!(
+                    "Continuity hash must be 32 bytes, got {}",
+                    stored_continuity.len()
+                )));
+            }
+            chain_segments.push(crate::chain::ChainSegment {
+                index: *index,
+                stored_hash: computed_hash,
+                stored_continuity: stored_continuity.try_into().unwrap(),
+            });
+        }
+        crate::chain::validate_chain(&chain_segments)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_streaming_matches_buffered_validation() {
+        let chunk_data = vec![
+            b"test_chunk_0".to_vec(),
+            b"test_chunk_1".to_vec(),
+            b"test_chunk_2".to_vec(),
+        ];
+
+        type Mutation = Box<dyn Fn(&mut TrstManifest, &Path)>;
+        let mutations: Vec<(&str, Mutation)> = vec![
+            ("intact", Box::new(|_, _| {})),
+            (
+                "tampered_chunk",
+                Box::new(|_, path| fs::write(path.join("chunks/00001.bin"), b"evil").unwrap()),
+            ),
+            (
+                "swapped_continuity",
+                Box::new(|m, _| {
+                    let first = m.segments[0].continuity_hash.clone();
+                    m.segments[0].continuity_hash = m.segments[1].continuity_hash.clone();
+                    m.segments[1].continuity_hash = first;
+                }),
+            ),
+            (
+                "chain_break_then_hash_mismatch",
+                Box::new(|m, path| {
+                    m.segments[0].continuity_hash = "00".repeat(32);
+                    fs::write(path.join("chunks/00002.bin"), b"evil").unwrap();
+                }),
+            ),
+            (
+                "bad_continuity_hex",
+                Box::new(|m, _| m.segments[2].continuity_hash = "zz".to_string()),
+            ),
+        ];
+
+        for (name, mutate) in mutations {
+            let temp_dir = TempDir::new().unwrap();
+            let archive_path = temp_dir.path().join("test.seal");
+            let mut manifest = create_test_manifest();
+            write_archive(
+                &archive_path,
+                &manifest,
+                chunk_data.clone(),
+                b"ed25519:test_signature",
+            )
+            .unwrap();
+
+            mutate(&mut manifest, &archive_path);
+            fs::write(
+                archive_path.join("manifest.json"),
+                serde_json::to_string_pretty(&manifest).unwrap(),
+            )
+            .unwrap();
+
+            let streamed = format!("{:?}", validate_archive(&archive_path));
+            let buffered = format!("{:?}", validate_archive_buffered(&archive_path));
+            assert_eq!(streamed, buffered, "outcome differs for {}", name);
+
+            #[cfg(feature = "parallel")]
+            {
+                let parallel = format!("{:?}", validate_archive_parallel(&archive_path));
+                assert_eq!(parallel, streamed, "parallel outcome differs for {}", name);
+            }
+        }
+    }
+
+    /// An audio archive built from the same chunks as `create_test_manifest`
+    fn create_audio_manifest() -> TrstManifest {
+        let mut manifest = TrstManifest::new_audio();
+        manifest.device.id = "MIC001".to_string();
+        manifest.device.public_key = "ed25519:test_key".to_string();
+        if let ProfileMetadata::Audio(ref mut m) = manifest.metadata {
+            m.started_at = "2025-01-15T10:30:00Z".to_string();
+            m.ended_at = "2025-01-15T10:30:06Z".to_string();
+            m.sample_rate_hz = 48_000;
+            m.channels = 1;
+            m.codec = "opus".to_string();
+        }
+        manifest.segments = create_test_manifest().segments;
+        manifest.signature = Some("ed25519:test_signature".to_string());
+        manifest
+    }
+
+    #[test]
+    fn test_audio_archive_validation() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("audio.seal");
+        let chunk_data = vec![
+            b"test_chunk_0".to_vec(),
+            b"test_chunk_1".to_vec(),
+            b"test_chunk_2".to_vec(),
+        ];
+        write_archive(
+            &archive_path,
+            &create_audio_manifest(),
+            chunk_data,
+            b"ed25519:test_signature",
+        )
+        .unwrap();
+
+        validate_archive(&archive_path).unwrap();
+        validate_archive_for_profile(&archive_path, "cam.audio").unwrap();
+
+        // A cam.video verifier must refuse the audio archive up front
+        match validate_archive_for_profile(&archive_path, "cam.video") {
+            Err(ArchiveError::ProfileMismatch { expected, found }) => {
+                assert_eq!(expected, "cam.video");
+                assert_eq!(found, "cam.audio");
+            }
+            other => panic!("Expected ProfileMismatch, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_parse_chunk_index() {
         assert_eq!(parse_chunk_index("00000.bin").unwrap(), 0);
@@ -458,4 +1667,746 @@ mod tests {
             other => panic!("Expected UnreferencedChunk error, got {:?}", other),
         }
     }
+
+    /// Write a signed archive whose manifest links to `prev_archive_hash`
+    fn write_signed_archive(
+        dir: &Path,
+        name: &str,
+        keypair: &crate::DeviceKeypair,
+        prev_archive_hash: Option<String>,
+    ) -> (std::path::PathBuf, TrstManifest) {
+        let mut manifest = create_test_manifest();
+        manifest.prev_archive_hash = prev_archive_hash;
+        let chunk_data = vec![
+            b"test_chunk_0".to_vec(),
+            b"test_chunk_1".to_vec(),
+            b"test_chunk_2".to_vec(),
+        ];
+        sign_and_write(&dir.join(name), keypair, manifest, chunk_data)
+    }
+
+    /// Sign `manifest` with `keypair` and write it out with `chunk_data`
+    fn sign_and_write(
+        path: &Path,
+        keypair: &crate::DeviceKeypair,
+        mut manifest: TrstManifest,
+        chunk_data: Vec<Vec<u8>>,
+    ) -> (std::path::PathBuf, TrstManifest) {
+        manifest.device.public_key = keypair.public.clone();
+        manifest.signature = None;
+        let canonical = manifest.to_canonical_bytes().unwrap();
+        let signature = crate::sign_manifest(keypair, &canonical).unwrap();
+        manifest.signature = Some(signature.clone());
+
+        write_archive(path, &manifest, chunk_data, signature.as_bytes()).unwrap();
+        (path.to_path_buf(), manifest)
+    }
+
+    /// Three archives, each linking to the one before
+    fn write_chain(dir: &Path, keypair: &crate::DeviceKeypair) -> Vec<std::path::PathBuf> {
+        let mut paths = Vec::new();
+        let mut prev = None;
+        for name in ["a.seal", "b.seal", "c.seal"] {
+            let (path, manifest) = write_signed_archive(dir, name, keypair, prev);
+            prev = Some(archive_hash(&manifest).unwrap());
+            paths.push(path);
+        }
+        paths
+    }
+
+    #[test]
+    fn test_archive_chain_valid() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let paths = write_chain(temp_dir.path(), &keypair);
+
+        let outcome = verify_archive_chain(&paths, &keypair.public).unwrap();
+        let (last, _) = read_archive(&paths[2]).unwrap();
+        assert_eq!(
+            outcome,
+            ChainVerifyOutcome::Valid {
+                archive_count: 3,
+                head_hash: archive_hash(&last).unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_archive_chain_missing_middle() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let paths = write_chain(temp_dir.path(), &keypair);
+
+        let outcome = verify_archive_chain(&[&paths[0], &paths[2]], &keypair.public).unwrap();
+        match outcome {
+            ChainVerifyOutcome::Broken {
+                index: 1,
+                reason: ChainBreak::PrevHashMismatch { expected, found },
+            } => {
+                let (first, _) = read_archive(&paths[0]).unwrap();
+                assert_eq!(expected, archive_hash(&first).unwrap());
+                assert_ne!(found, Some(expected));
+            }
+            other => panic!("Expected PrevHashMismatch at index 1, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_zero_segment_archive_is_reported_as_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let mut manifest = create_test_manifest();
+        manifest.segments.clear();
+        let (path, _) = sign_and_write(
+            &temp_dir.path().join("empty.seal"),
+            &keypair,
+            manifest,
+            vec![],
+        );
+
+        assert!(matches!(
+            validate_archive(&path),
+            Err(ArchiveError::Chain(ChainError::Empty))
+        ));
+
+        let service_key = SigningKey::from_bytes(&[7u8; 32]);
+        let (outcome, receipt, _) =
+            verify_archive_with_receipt(&path, &keypair.public, &service_key).unwrap();
+        assert_eq!(outcome.signature, CheckStatus::Pass);
+        assert_eq!(outcome.continuity, CheckStatus::Fail);
+        assert!(outcome.error.unwrap().contains("no segments"));
+        assert_eq!(receipt.segments, 0);
+        assert_eq!(
+            receipt.chain_tip,
+            crate::chain::blake3_hex_or_b64(&crate::chain::genesis())
+        );
+        assert_eq!(receipt.chain_tip, outcome.chain_tip);
+    }
+
+    #[test]
+    fn test_single_segment_archive_chains_from_genesis() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let mut manifest = create_test_manifest();
+        manifest.segments.truncate(1);
+        let chunk_data = vec![b"test_chunk_0".to_vec()];
+        let (path, _) = sign_and_write(
+            &temp_dir.path().join("one.seal"),
+            &keypair,
+            manifest.clone(),
+            chunk_data.clone(),
+        );
+        validate_archive(&path).unwrap();
+
+        // The lone continuity hash must be chain_next(genesis, hash), not the hash itself
+        manifest.segments[0].continuity_hash = manifest.segments[0].blake3_hash.clone();
+        let (path, _) = sign_and_write(
+            &temp_dir.path().join("unchained.seal"),
+            &keypair,
+            manifest,
+            chunk_data,
+        );
+        assert!(matches!(
+            validate_archive(&path),
+            Err(ArchiveError::Chain(ChainError::OutOfOrder { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_archive_chain_link_without_segments() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let (first, first_manifest) =
+            write_signed_archive(temp_dir.path(), "a.seal", &keypair, None);
+
+        // Links correctly to its predecessor, but captured nothing
+        let mut manifest = create_test_manifest();
+        manifest.segments.clear();
+        manifest.prev_archive_hash = Some(archive_hash(&first_manifest).unwrap());
+        let (empty, _) =
+            sign_and_write(&temp_dir.path().join("b.seal"), &keypair, manifest, vec![]);
+
+        assert!(matches!(
+            validate_archive(&empty),
+            Err(ArchiveError::Chain(ChainError::Empty))
+        ));
+        match verify_archive_chain(&[first, empty], &keypair.public).unwrap() {
+            ChainVerifyOutcome::Broken {
+                index: 1,
+                reason: ChainBreak::InvalidArchive(reason),
+            } => assert!(reason.contains("no segments"), "reason: {}", reason),
+            other => panic!(
+                "Expected the empty archive to break the chain, got {:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_archive_chain_wrong_prev_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let mut paths = write_chain(temp_dir.path(), &keypair);
+
+        // Re-sign the last archive with a bogus link
+        let (forged, _) = write_signed_archive(
+            temp_dir.path(),
+            "forged.seal",
+            &keypair,
+            Some("00".repeat(32)),
+        );
+        paths[2] = forged;
+
+        let outcome = verify_archive_chain(&paths, &keypair.public).unwrap();
+        match outcome {
+            ChainVerifyOutcome::Broken {
+                index: 2,
+                reason: ChainBreak::PrevHashMismatch { found, .. },
+            } => assert_eq!(found, Some("00".repeat(32))),
+            other => panic!("Expected PrevHashMismatch at index 2, got {:?}", other),
+        }
+
+        // A link edited after signing breaks the signature instead
+        let tampered = temp_dir.path().join("b.seal/manifest.json");
+        let mut manifest: TrstManifest =
+            serde_json::from_str(&fs::read_to_string(&tampered).unwrap()).unwrap();
+        manifest.prev_archive_hash = Some("11".repeat(32));
+        fs::write(&tampered, serde_json::to_string_pretty(&manifest).unwrap()).unwrap();
+
+        let outcome = verify_archive_chain(&paths[..2], &keypair.public).unwrap();
+        assert!(matches!(
+            outcome,
+            ChainVerifyOutcome::Broken {
+                index: 1,
+                reason: ChainBreak::BadSignature(_)
+            }
+        ));
+    }
+
+    #[test]
+    fn test_archive_chain_spanning_key_rotation() {
+        let temp_dir = TempDir::new().unwrap();
+        let old_key = crate::DeviceKeypair::generate().unwrap();
+        let new_key = crate::DeviceKeypair::generate().unwrap();
+        let (first, first_manifest) =
+            write_signed_archive(temp_dir.path(), "a.seal", &old_key, None);
+        let (second, second_manifest) = write_signed_archive(
+            temp_dir.path(),
+            "b.seal",
+            &new_key,
+            Some(archive_hash(&first_manifest).unwrap()),
+        );
+        let paths = [first, second];
+
+        let rotation =
+            DeviceKeyRotation::issue(&old_key, &first_manifest.device.id, &new_key.public).unwrap();
+        assert!(rotation.verify());
+
+        let outcome = verify_archive_chain_with_rotations(
+            &paths,
+            &[old_key.public.as_str()],
+            std::slice::from_ref(&rotation),
+        )
+        .unwrap();
+        assert_eq!(
+            outcome,
+            ChainVerifyOutcome::Valid {
+                archive_count: 2,
+                head_hash: archive_hash(&second_manifest).unwrap(),
+            }
+        );
+
+        // The retired key cannot sign for the device after the rotation
+        let (stale, _) = write_signed_archive(
+            temp_dir.path(),
+            "c.seal",
+            &old_key,
+            Some(archive_hash(&second_manifest).unwrap()),
+        );
+        let outcome = verify_archive_chain_with_rotations(
+            &[&paths[0], &paths[1], &stale],
+            &[old_key.public.as_str()],
+            &[rotation],
+        )
+        .unwrap();
+        assert_eq!(
+            outcome,
+            ChainVerifyOutcome::Broken {
+                index: 2,
+                reason: ChainBreak::UnauthorizedKey(old_key.public.clone()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_archive_chain_rejects_unauthorized_key_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let old_key = crate::DeviceKeypair::generate().unwrap();
+        let rogue_key = crate::DeviceKeypair::generate().unwrap();
+        let (first, first_manifest) =
+            write_signed_archive(temp_dir.path(), "a.seal", &old_key, None);
+        let (second, _) = write_signed_archive(
+            temp_dir.path(),
+            "b.seal",
+            &rogue_key,
+            Some(archive_hash(&first_manifest).unwrap()),
+        );
+        let paths = [first, second];
+        let unauthorized = ChainVerifyOutcome::Broken {
+            index: 1,
+            reason: ChainBreak::UnauthorizedKey(rogue_key.public.clone()),
+        };
+
+        // No rotation at all
+        assert_eq!(
+            verify_archive_chain(&paths, &old_key.public).unwrap(),
+            unauthorized
+        );
+
+        // A rotation the new key signed for itself
+        let mut forged =
+            DeviceKeyRotation::issue(&rogue_key, &first_manifest.device.id, &rogue_key.public)
+                .unwrap();
+        forged.old_public_key = old_key.public.clone();
+        assert!(!forged.verify());
+
+        // A genuine rotation, but for a different device
+        let other_device =
+            DeviceKeyRotation::issue(&old_key, "OTHER-DEVICE", &rogue_key.public).unwrap();
+
+        let outcome = verify_archive_chain_with_rotations(
+            &paths,
+            &[old_key.public.as_str()],
+            &[forged, other_device],
+        )
+        .unwrap();
+        assert_eq!(outcome, unauthorized);
+    }
+
+    #[test]
+    fn test_inspect_valid_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("test.seal");
+        let manifest = create_test_manifest();
+        let chunk_data = vec![
+            b"test_chunk_0".to_vec(),
+            b"test_chunk_1".to_vec(),
+            b"test_chunk_2".to_vec(),
+        ];
+        write_archive(
+            &archive_path,
+            &manifest,
+            chunk_data,
+            b"ed25519:test_signature",
+        )
+        .unwrap();
+
+        let report = inspect_archive(&archive_path).unwrap();
+        assert!(report.is_valid(), "{:?}", report);
+        assert_eq!(report.profile, "cam.video");
+        assert_eq!(report.device_id, "TEST001");
+        assert_eq!(report.started_at, "2025-01-15T10:30:00Z");
+        assert!(report.signature_present && report.signature_consistent);
+        assert_eq!(report.segments.len(), 3);
+        for (segment, expected) in report.segments.iter().zip(&manifest.segments) {
+            assert_eq!(
+                segment.computed_blake3.as_deref(),
+                Some(expected.blake3_hash.as_str())
+            );
+            assert_eq!(segment.duration_seconds, 2.0);
+        }
+    }
+
+    #[test]
+    fn test_inspect_pinpoints_corrupted_chunk() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("test.seal");
+        let chunk_data = vec![
+            b"test_chunk_0".to_vec(),
+            b"test_chunk_1".to_vec(),
+            b"test_chunk_2".to_vec(),
+        ];
+        write_archive(
+            &archive_path,
+            &create_test_manifest(),
+            chunk_data,
+            b"ed25519:test_signature",
+        )
+        .unwrap();
+        fs::write(archive_path.join("chunks/00001.bin"), b"corrupted!!!").unwrap();
+
+        // validate_archive only reports the first failure
+        assert!(validate_archive(&archive_path).is_err());
+
+        let report = inspect_archive(&archive_path).unwrap();
+        assert!(!report.is_valid());
+        let failed: Vec<_> = report.failed_segments().collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].index, 1);
+        assert_eq!(failed[0].chunk_file, "00001.bin");
+        assert!(!failed[0].hash_matches);
+        assert!(!failed[0].continuity_matches);
+        assert_eq!(
+            failed[0].computed_blake3,
+            Some(hex::encode(crate::chain::segment_hash(b"corrupted!!!")))
+        );
+        // The rest of the archive still checks out
+        assert!(report.signature_consistent);
+        assert!(report.manifest_error.is_none());
+    }
+
+    #[test]
+    fn test_detached_manifest_signature_verifies() {
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let mut manifest = create_test_manifest();
+        manifest.device.public_key = keypair.public.clone();
+        manifest.signature = None;
+
+        let signature = sign_manifest_detached(&manifest, &keypair).unwrap();
+        let manifest_json = serde_json::to_vec_pretty(&manifest).unwrap();
+        assert!(verify_manifest_detached(&manifest_json, &signature, &keypair.public).unwrap());
+
+        // Signing does not touch the manifest
+        assert!(manifest.signature.is_none());
+
+        // Another device's key does not verify it
+        let other = crate::DeviceKeypair::generate().unwrap();
+        assert!(!verify_manifest_detached(&manifest_json, &signature, &other.public).unwrap());
+    }
+
+    #[test]
+    fn test_detached_manifest_signature_covers_every_field() {
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let manifest = create_test_manifest();
+        let signature = sign_manifest_detached(&manifest, &keypair).unwrap();
+
+        let edits: Vec<fn(&mut TrstManifest)> = vec![
+            |m| m.trst_version.push('1'),
+            |m| m.device.id = "TEST002".to_string(),
+            |m| m.device.firmware_version = "9.9.9".to_string(),
+            |m| {
+                if let ProfileMetadata::CamVideo(ref mut meta) = m.metadata {
+                    meta.fps = 60.0;
+                }
+            },
+            |m| m.chunk.size_bytes += 1,
+            |m| m.segments[1].blake3_hash = "00".repeat(32),
+            |m| m.segments[2].duration_seconds = 3.0,
+            |m| m.segments.truncate(2),
+            |m| m.claims.push("location:unknown".to_string()),
+            |m| m.prev_archive_hash = Some("ab".repeat(32)),
+            |m| m.device_attestation = Some("YXR0ZXN0YXRpb24=".to_string()),
+            |m| m.segments_root = Some("cd".repeat(32)),
+        ];
+        for (i, edit) in edits.into_iter().enumerate() {
+            let mut edited = manifest.clone();
+            edit(&mut edited);
+            let json = serde_json::to_vec(&edited).unwrap();
+            assert!(
+                !verify_manifest_detached(&json, &signature, &keypair.public).unwrap(),
+                "edit {} did not invalidate the signature",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn test_manifest_only_never_claims_continuity() {
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let mut manifest = create_test_manifest();
+        manifest.device.public_key = keypair.public.clone();
+        manifest.signature = Some(sign_manifest_detached(&manifest, &keypair).unwrap());
+        let json = serde_json::to_vec_pretty(&manifest).unwrap();
+
+        let outcome = verify_manifest_only(&json, &keypair.public).unwrap();
+        assert_eq!(outcome.signature, CheckStatus::Pass);
+        assert_eq!(outcome.continuity, CheckStatus::Skipped);
+        assert_eq!(outcome.segment_count, manifest.segments.len());
+        assert_eq!(outcome.signature_error, None);
+        assert_eq!(
+            outcome.skipped,
+            vec![SkippedCheck::ChunkHashes, SkippedCheck::Continuity]
+        );
+        assert!(outcome
+            .skipped
+            .iter()
+            .all(|check| check.reason().contains("no chunk files")));
+
+        // A failed signature does not turn continuity into a failure either
+        let other = crate::DeviceKeypair::generate().unwrap();
+        let outcome = verify_manifest_only(&json, &other.public).unwrap();
+        assert_eq!(outcome.signature, CheckStatus::Fail);
+        assert_eq!(outcome.continuity, CheckStatus::Skipped);
+        assert!(outcome.signature_error.is_some());
+    }
+
+    #[test]
+    fn test_manifest_only_reports_missing_signature() {
+        let mut manifest = create_test_manifest();
+        manifest.signature = None;
+        let json = serde_json::to_vec(&manifest).unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+
+        let outcome = verify_manifest_only(&json, &keypair.public).unwrap();
+        assert_eq!(outcome.signature, CheckStatus::Fail);
+        assert_eq!(outcome.continuity, CheckStatus::Skipped);
+        assert_eq!(
+            outcome.signature_error.as_deref(),
+            Some("Manifest has no signature")
+        );
+        assert!(verify_manifest_only(b"not json", &keypair.public).is_err());
+    }
+
+    #[test]
+    fn test_verify_archive_with_receipt_signs_manifest_digest() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let (path, manifest) = write_signed_archive(temp_dir.path(), "clip.seal", &keypair, None);
+        let service_key = SigningKey::from_bytes(&[7u8; 32]);
+
+        let (outcome, receipt, signature) =
+            verify_archive_with_receipt(&path, &keypair.public, &service_key).unwrap();
+        assert!(outcome.is_valid());
+        assert_eq!(
+            receipt.manifest_digest,
+            blake3::hash(&manifest.to_canonical_bytes().unwrap())
+                .to_hex()
+                .to_string()
+        );
+        assert_eq!(receipt.segments, 3);
+        assert_eq!(receipt.duration_s, 6.0);
+        assert_eq!(receipt.chain_tip, outcome.chain_tip);
+        assert_eq!(
+            receipt.chain_tip,
+            crate::chain::blake3_hex_or_b64(
+                &hex::decode(&manifest.segments[2].continuity_hash).unwrap()
+            )
+        );
+        assert_eq!(
+            (receipt.signature.as_str(), receipt.continuity.as_str()),
+            ("pass", "pass")
+        );
+
+        receipt
+            .verify(&service_key.verifying_key(), &signature)
+            .unwrap();
+        let mut edited = receipt.clone();
+        edited.continuity = "fail".to_string();
+        assert!(edited
+            .verify(&service_key.verifying_key(), &signature)
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_outcome_reports_chain_tip() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let (path, _) = write_signed_archive(temp_dir.path(), "clip.seal", &keypair, None);
+        let service_key = SigningKey::from_bytes(&[7u8; 32]);
+
+        let mut expected = crate::chain::genesis();
+        for chunk in [b"test_chunk_0", b"test_chunk_1", b"test_chunk_2"] {
+            expected = crate::chain::chain_next(&expected, &crate::chain::segment_hash(chunk));
+        }
+
+        let (first, _, _) =
+            verify_archive_with_receipt(&path, &keypair.public, &service_key).unwrap();
+        let (second, _, _) =
+            verify_archive_with_receipt(&path, &keypair.public, &service_key).unwrap();
+        assert!(first.chain_tip.starts_with("b3:"));
+        assert_eq!(first.chain_tip, crate::chain::blake3_hex_or_b64(&expected));
+        assert_eq!(first.chain_tip, second.chain_tip);
+    }
+
+    #[test]
+    fn test_verify_archive_with_receipt_records_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let (path, _) = write_signed_archive(temp_dir.path(), "clip.seal", &keypair, None);
+        let other = crate::DeviceKeypair::generate().unwrap();
+        let service_key = SigningKey::from_bytes(&[7u8; 32]);
+
+        let (outcome, receipt, signature) =
+            verify_archive_with_receipt(&path, &other.public, &service_key).unwrap();
+        assert_eq!(outcome.signature, CheckStatus::Fail);
+        assert_eq!(outcome.continuity, CheckStatus::Skipped);
+        assert_eq!(
+            (receipt.signature.as_str(), receipt.continuity.as_str()),
+            ("fail", "skip")
+        );
+        receipt
+            .verify(&service_key.verifying_key(), &signature)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_check_archive_reports_first_failing_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let (path, _) = write_signed_archive(temp_dir.path(), "clip.seal", &keypair, None);
+
+        let check = check_archive(&path, &keypair.public).unwrap();
+        assert!(check.failure.is_none());
+        assert_eq!(check.manifest.segments.len(), 3);
+
+        // Bare keys are taken as Ed25519
+        let bare = keypair.public.strip_prefix("ed25519:").unwrap();
+        assert!(check_archive(&path, bare).unwrap().failure.is_none());
+
+        let other = crate::DeviceKeypair::generate().unwrap();
+        assert!(matches!(
+            check_archive(&path, &other.public).unwrap().failure,
+            Some(ArchiveCheckFailure::Signature(_))
+        ));
+
+        fs::write(path.join("chunks/00001.bin"), b"replaced").unwrap();
+        assert!(matches!(
+            check_archive(&path, &keypair.public).unwrap().failure,
+            Some(ArchiveCheckFailure::Continuity(
+                ArchiveError::ValidationFailed(_)
+            ))
+        ));
+
+        fs::remove_file(path.join("chunks/00002.bin")).unwrap();
+        assert!(matches!(
+            check_archive(&path, &keypair.public),
+            Err(ArchiveError::MissingChunk(_))
+        ));
+    }
+
+    #[test]
+    fn test_trust_on_first_use_reports_embedded_key_fingerprint() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let (path, _) = write_signed_archive(temp_dir.path(), "clip.seal", &keypair, None);
+
+        let tofu = verify_archive_trust_on_first_use(&path).unwrap();
+        assert!(tofu.outcome.is_valid());
+        assert_eq!(tofu.device_public_key, keypair.public);
+        assert_eq!(
+            tofu.fingerprint,
+            device_key_fingerprint(&keypair.public).unwrap()
+        );
+        assert_eq!(tofu.fingerprint.len(), 64);
+
+        // The pinned fingerprint accepts later archives from the same device
+        let (later, _) = write_signed_archive(temp_dir.path(), "later.seal", &keypair, None);
+        let pinned = verify_archive_pinned(&later, &tofu.fingerprint).unwrap();
+        assert!(pinned.outcome.is_valid());
+        assert_eq!(pinned.fingerprint, tofu.fingerprint);
+    }
+
+    #[test]
+    fn test_pinned_verification_rejects_unexpected_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let pinned_key = crate::DeviceKeypair::generate().unwrap();
+        let pinned = device_key_fingerprint(&pinned_key.public).unwrap();
+
+        // A correctly signed archive from some other key is still rejected
+        let impostor = crate::DeviceKeypair::generate().unwrap();
+        let (path, _) = write_signed_archive(temp_dir.path(), "clip.seal", &impostor, None);
+        assert!(verify_archive_trust_on_first_use(&path)
+            .unwrap()
+            .outcome
+            .is_valid());
+        match verify_archive_pinned(&path, &pinned) {
+            Err(ArchiveError::DeviceKeyNotPinned { expected, found }) => {
+                assert_eq!(expected, pinned);
+                assert_eq!(found, device_key_fingerprint(&impostor.public).unwrap());
+            }
+            other => panic!("expected DeviceKeyNotPinned, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_verify_outcome_checks_only_what_was_read() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let (path, _) = write_signed_archive(temp_dir.path(), "clip.seal", &keypair, None);
+        let (manifest, chunks) = read_archive(&path).unwrap();
+
+        // The archive changes on disk after it was read
+        std::fs::write(path.join("chunks/00000.bin"), b"swapped").unwrap();
+
+        let outcome = verify_outcome(&path, &manifest, &chunks, &keypair.public).unwrap();
+        assert!(outcome.is_valid(), "{:?}", outcome.error);
+        assert!(!verify_archive_trust_on_first_use(&path)
+            .unwrap()
+            .outcome
+            .is_valid());
+    }
+
+    fn attested_manifest(root: &DeviceKeypair, device_public_key: &str) -> TrstManifest {
+        let mut manifest = create_test_manifest();
+        let attestation =
+            DeviceAttestation::issue(root, &manifest.device.id, device_public_key).unwrap();
+        manifest.device_attestation = Some(attestation.encode().unwrap());
+        manifest
+    }
+
+    #[test]
+    fn test_device_attestation_verifies_against_trusted_root() {
+        let root = crate::DeviceKeypair::generate().unwrap();
+        let manifest = create_test_manifest();
+        let manifest = attested_manifest(&root, &manifest.device.public_key);
+
+        verify_device_attestation(&manifest, &[root.public.as_str()]).unwrap();
+
+        // An untrusted root is rejected even with a valid signature
+        let other_root = crate::DeviceKeypair::generate().unwrap();
+        let err = verify_device_attestation(&manifest, &[other_root.public.as_str()]).unwrap_err();
+        assert!(err.to_string().contains("not trusted"));
+
+        // A manifest without an attestation does not pass
+        let err = verify_device_attestation(&create_test_manifest(), &[root.public.as_str()])
+            .unwrap_err();
+        assert!(err.to_string().contains("no device attestation"));
+    }
+
+    #[test]
+    fn test_device_attestation_rejects_mismatched_device_key() {
+        let root = crate::DeviceKeypair::generate().unwrap();
+        let other_device = crate::DeviceKeypair::generate().unwrap();
+        let manifest = attested_manifest(&root, &other_device.public);
+
+        let err = verify_device_attestation(&manifest, &[root.public.as_str()]).unwrap_err();
+        assert!(err.to_string().contains("manifest device key"));
+    }
+
+    #[test]
+    fn test_device_attestation_is_covered_by_manifest_signature() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = crate::DeviceKeypair::generate().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let mut manifest = attested_manifest(&root, &keypair.public);
+        manifest.device.public_key = keypair.public.clone();
+        let signature = sign_manifest_detached(&manifest, &keypair).unwrap();
+
+        // Swapping in an attestation from another root breaks the signature
+        let other_root = crate::DeviceKeypair::generate().unwrap();
+        let mut swapped = manifest.clone();
+        swapped.device_attestation =
+            attested_manifest(&other_root, &keypair.public).device_attestation;
+        let json = serde_json::to_vec(&swapped).unwrap();
+        assert!(!verify_manifest_detached(&json, &signature, &keypair.public).unwrap());
+
+        // The attestation survives a write/read round trip
+        let json = serde_json::to_vec(&manifest).unwrap();
+        assert!(verify_manifest_detached(&json, &signature, &keypair.public).unwrap());
+        let path = temp_dir.path().join("manifest.json");
+        std::fs::write(&path, &json).unwrap();
+        let reread: TrstManifest = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(reread.device_attestation, manifest.device_attestation);
+        verify_device_attestation(&reread, &[root.public.as_str()]).unwrap();
+    }
+
+    #[test]
+    fn test_detached_signature_matches_inline_signature() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = crate::DeviceKeypair::generate().unwrap();
+        let (_, inline_manifest) = write_signed_archive(temp_dir.path(), "a.seal", &keypair, None);
+
+        let detached = sign_manifest_detached(&inline_manifest, &keypair).unwrap();
+        assert_eq!(Some(detached), inline_manifest.signature);
+    }
 }
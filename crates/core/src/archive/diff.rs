@@ -0,0 +1,304 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Compare two archives that are supposed to hold the same clip
+//!
+//! [`diff_archives`] is read-only and needs no keys. Segments are compared by the
+//! BLAKE3 hash of the chunk files actually on disk, so a chunk swapped after
+//! signing shows up even when both manifests are identical.
+
+use super::{check_archive_signature, hash_chunk_file, ArchiveError, STREAM_BUFFER_SIZE};
+use crate::TrstManifest;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// A manifest field whose value differs between the two archives
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// Dotted path into `manifest.json`, e.g. `device.model`
+    pub field: String,
+    /// JSON value in the first archive, `None` when the field is absent
+    pub a: Option<String>,
+    /// JSON value in the second archive, `None` when the field is absent
+    pub b: Option<String>,
+}
+
+/// A segment whose chunk differs between the two archives
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentDiff {
+    pub index: usize,
+    /// BLAKE3 hash of the chunk in the first archive, `None` when the segment or
+    /// its chunk file is missing
+    pub hash_a: Option<String>,
+    /// BLAKE3 hash of the chunk in the second archive, `None` when the segment or
+    /// its chunk file is missing
+    pub hash_b: Option<String>,
+}
+
+/// Whether an archive's embedded signature verifies under its own device key
+///
+/// This shows the manifest was not edited after signing; it does not say the
+/// device key is trusted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureStatus {
+    Valid,
+    Invalid(String),
+    Missing,
+}
+
+/// Where two archives diverge, from [`diff_archives`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveDiff {
+    /// Manifest fields that differ, excluding `segments` and `signature`
+    pub manifest_fields: Vec<FieldDiff>,
+    pub segments: Vec<SegmentDiff>,
+    pub signature_a: SignatureStatus,
+    pub signature_b: SignatureStatus,
+}
+
+impl ArchiveDiff {
+    /// Whether the archives hold the same manifest fields and chunks
+    ///
+    /// Signature status is not part of the comparison.
+    pub fn is_empty(&self) -> bool {
+        self.manifest_fields.is_empty() && self.segments.is_empty()
+    }
+}
+
+/// Report where two archives diverge
+///
+/// Only an unreadable or unparseable `manifest.json` is an error; missing chunk
+/// files show up as segment differences.
+pub fn diff_archives<P: AsRef<Path>, Q: AsRef<Path>>(
+    a: P,
+    b: Q,
+) -> Result<ArchiveDiff, ArchiveError> {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    let manifest_a = read_manifest(a)?;
+    let manifest_b = read_manifest(b)?;
+
+    let mut manifest_fields = Vec::new();
+    diff_values(
+        "",
+        &manifest_fields_value(&manifest_a)?,
+        &manifest_fields_value(&manifest_b)?,
+        &mut manifest_fields,
+    );
+
+    let hashes_a = chunk_hashes(a, &manifest_a);
+    let hashes_b = chunk_hashes(b, &manifest_b);
+    let segments = (0..hashes_a.len().max(hashes_b.len()))
+        .filter_map(|index| {
+            let hash_a = hashes_a.get(index).cloned().flatten();
+            let hash_b = hashes_b.get(index).cloned().flatten();
+            let same = hash_a.is_some() && hash_a == hash_b;
+            (!same).then_some(SegmentDiff {
+                index,
+                hash_a,
+                hash_b,
+            })
+        })
+        .collect();
+
+    Ok(ArchiveDiff {
+        manifest_fields,
+        segments,
+        signature_a: signature_status(&manifest_a),
+        signature_b: signature_status(&manifest_b),
+    })
+}
+
+fn read_manifest(base_path: &Path) -> Result<TrstManifest, ArchiveError> {
+    Ok(serde_json::from_str(&fs::read_to_string(
+        base_path.join("manifest.json"),
+    )?)?)
+}
+
+/// The manifest as JSON, minus the fields compared separately
+fn manifest_fields_value(manifest: &TrstManifest) -> Result<Value, ArchiveError> {
+    let mut value = serde_json::to_value(manifest)?;
+    if let Value::Object(ref mut fields) = value {
+        fields.remove("segments");
+        fields.remove("signature");
+    }
+    Ok(value)
+}
+
+/// Collect differing leaves; objects are walked, arrays compared whole
+fn diff_values(path: &str, a: &Value, b: &Value, out: &mut Vec<FieldDiff>) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            let mut keys: Vec<&String> = a.keys().chain(b.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                match (a.get(key), b.get(key)) {
+                    (Some(a), Some(b)) => diff_values(&child, a, b, out),
+                    (a, b) => out.push(FieldDiff {
+                        field: child,
+                        a: a.map(Value::to_string),
+                        b: b.map(Value::to_string),
+                    }),
+                }
+            }
+        }
+        (a, b) if a != b => out.push(FieldDiff {
+            field: path.to_string(),
+            a: Some(a.to_string()),
+            b: Some(b.to_string()),
+        }),
+        _ => {}
+    }
+}
+
+/// Hex BLAKE3 of each segment's chunk file, `None` where it cannot be read
+fn chunk_hashes(base_path: &Path, manifest: &TrstManifest) -> Vec<Option<String>> {
+    let chunks_dir = base_path.join("chunks");
+    let mut buffer = vec![0u8; STREAM_BUFFER_SIZE];
+    manifest
+        .segments
+        .iter()
+        .map(|segment| {
+            hash_chunk_file(&chunks_dir.join(&segment.chunk_file), &mut buffer)
+                .ok()
+                .map(hex::encode)
+        })
+        .collect()
+}
+
+fn signature_status(manifest: &TrstManifest) -> SignatureStatus {
+    if manifest.signature.is_none() {
+        return SignatureStatus::Missing;
+    }
+    match check_archive_signature(manifest, &manifest.device.public_key) {
+        Ok(()) => SignatureStatus::Valid,
+        Err(reason) => SignatureStatus::Invalid(reason),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::write_archive;
+    use crate::DeviceKeypair;
+    use tempfile::TempDir;
+
+    const CHUNKS: [&[u8]; 3] = [b"chunk zero", b"chunk one", b"chunk two"];
+
+    /// Wrap `chunks` into a signed archive at `dir/name`
+    fn write_clip(
+        dir: &Path,
+        name: &str,
+        keypair: &DeviceKeypair,
+        model: &str,
+        chunks: &[&[u8]],
+    ) -> std::path::PathBuf {
+        let mut manifest = TrstManifest::new_cam_video();
+        manifest.device.id = "CAM001".to_string();
+        manifest.device.model = model.to_string();
+        manifest.device.public_key = keypair.public.clone();
+
+        let mut continuity = crate::chain::genesis();
+        for (i, chunk) in chunks.iter().enumerate() {
+            let hash = crate::chain::segment_hash(chunk);
+            continuity = crate::chain::chain_next(&continuity, &hash);
+            manifest.segments.push(crate::SegmentInfo {
+                chunk_file: format!("{:05}.bin", i),
+                blake3_hash: hex::encode(hash),
+                start_time: format!("2025-01-15T10:30:{:02}Z", i * 2),
+                duration_seconds: 2.0,
+                continuity_hash: hex::encode(continuity),
+                nonce: None,
+            });
+        }
+
+        let signature =
+            crate::sign_manifest(keypair, &manifest.to_canonical_bytes().unwrap()).unwrap();
+        manifest.signature = Some(signature.clone());
+        let path = dir.join(name);
+        let chunk_data = chunks.iter().map(|c| c.to_vec()).collect();
+        write_archive(&path, &manifest, chunk_data, signature.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_identical_archives_have_empty_diff() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = DeviceKeypair::generate().unwrap();
+        let a = write_clip(temp_dir.path(), "a.seal", &keypair, "RefCam", &CHUNKS);
+        let b = write_clip(temp_dir.path(), "b.seal", &keypair, "RefCam", &CHUNKS);
+
+        let diff = diff_archives(&a, &b).unwrap();
+        assert!(diff.is_empty(), "unexpected diff: {:?}", diff);
+        assert_eq!(diff.signature_a, SignatureStatus::Valid);
+        assert_eq!(diff.signature_b, SignatureStatus::Valid);
+    }
+
+    #[test]
+    fn test_diff_reports_differing_chunk() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = DeviceKeypair::generate().unwrap();
+        let a = write_clip(temp_dir.path(), "a.seal", &keypair, "RefCam", &CHUNKS);
+        let b = write_clip(temp_dir.path(), "b.seal", &keypair, "RefCam", &CHUNKS);
+
+        // Swap a chunk after signing; the manifests stay identical
+        fs::write(b.join("chunks/00001.bin"), b"replaced").unwrap();
+
+        let diff = diff_archives(&a, &b).unwrap();
+        assert!(diff.manifest_fields.is_empty());
+        assert_eq!(diff.segments.len(), 1);
+        let segment = &diff.segments[0];
+        assert_eq!(segment.index, 1);
+        assert_eq!(
+            segment.hash_a,
+            Some(hex::encode(crate::chain::segment_hash(CHUNKS[1])))
+        );
+        assert_eq!(
+            segment.hash_b,
+            Some(hex::encode(crate::chain::segment_hash(b"replaced")))
+        );
+        assert_eq!(diff.signature_b, SignatureStatus::Valid);
+    }
+
+    #[test]
+    fn test_diff_reports_device_metadata_and_signatures() {
+        let temp_dir = TempDir::new().unwrap();
+        let keypair = DeviceKeypair::generate().unwrap();
+        let a = write_clip(temp_dir.path(), "a.seal", &keypair, "RefCam", &CHUNKS);
+        let b = write_clip(temp_dir.path(), "b.seal", &keypair, "RefCam v2", &CHUNKS);
+
+        let diff = diff_archives(&a, &b).unwrap();
+        assert!(diff.segments.is_empty());
+        assert_eq!(
+            diff.manifest_fields,
+            vec![FieldDiff {
+                field: "device.model".to_string(),
+                a: Some("\"RefCam\"".to_string()),
+                b: Some("\"RefCam v2\"".to_string()),
+            }]
+        );
+
+        // Editing a manifest after signing shows up in the signature status
+        let manifest_path = b.join("manifest.json");
+        let edited = fs::read_to_string(&manifest_path)
+            .unwrap()
+            .replace("CAM001", "CAM002");
+        fs::write(&manifest_path, edited).unwrap();
+
+        let diff = diff_archives(&a, &b).unwrap();
+        assert_eq!(diff.signature_a, SignatureStatus::Valid);
+        assert!(matches!(diff.signature_b, SignatureStatus::Invalid(_)));
+        assert!(diff.manifest_fields.iter().any(|f| f.field == "device.id"));
+    }
+}
@@ -0,0 +1,300 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Merkle inclusion proofs for archive segments
+//!
+//! A signed manifest records `segments_root`, the root of a BLAKE3 Merkle tree over
+//! its segments' hashes. A holder can hand a third party one chunk plus a
+//! [`SegmentInclusionProof`], and [`verify_inclusion`] shows the chunk belongs
+//! under that root without the rest of the segment list.
+//!
+//! Leaves and interior nodes are hashed with distinct prefixes so a node can never
+//! pass for a leaf. When a level has an odd number of nodes the last one is carried
+//! up unchanged rather than paired with itself.
+
+use super::{read_checked_manifest, ArchiveError};
+use crate::SegmentInfo;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Sibling hashes linking one segment to the archive's `segments_root`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentInclusionProof {
+    /// Position of the segment in the manifest
+    pub index: usize,
+    /// Number of segments in the archive
+    pub segment_count: usize,
+    /// Sibling hashes from the leaf up; levels where the node has no sibling are skipped
+    pub siblings: Vec<[u8; 32]>,
+}
+
+fn leaf_hash(segment_hash: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[LEAF_PREFIX]);
+    hasher.update(segment_hash);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Hash each adjacent pair, carrying an odd last node up unchanged
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash(left, right),
+            [single] => *single,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Decode each segment's hex `blake3_hash` into a leaf
+fn segment_leaves(segments: &[SegmentInfo]) -> Result<Vec<[u8; 32]>, ArchiveError> {
+    segments
+        .iter()
+        .map(|segment| {
+            let hash: [u8; 32] = hex::decode(&segment.blake3_hash)
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or_else(|| {
+                    ArchiveError::ValidationFailed(format!(
+                        "Invalid segment hash format: {}",
+                        segment.blake3_hash
+                    ))
+                })?;
+            Ok(leaf_hash(&hash))
+        })
+        .collect()
+}
+
+/// Compute the Merkle root over `segments`, in manifest order
+///
+/// Returns `None` for an archive with no segments.
+pub fn segments_root(segments: &[SegmentInfo]) -> Result<Option<[u8; 32]>, ArchiveError> {
+    let mut level = segment_leaves(segments)?;
+    if level.is_empty() {
+        return Ok(None);
+    }
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    Ok(Some(level[0]))
+}
+
+/// Check that a manifest's recorded `segments_root`, if any, matches its segments
+pub(crate) fn check_segments_root(manifest: &crate::TrstManifest) -> Result<(), ArchiveError> {
+    let Some(ref recorded) = manifest.segments_root else {
+        return Ok(());
+    };
+    let computed = segments_root(&manifest.segments)?.map(hex::encode);
+    if computed.as_ref() != Some(recorded) {
+        return Err(ArchiveError::ValidationFailed(format!(
+            "Segments root mismatch: manifest records {}, segments give {}",
+            recorded,
+            computed.as_deref().unwrap_or("none")
+        )));
+    }
+    Ok(())
+}
+
+/// Build the inclusion proof for segment `index` of the archive at `archive`
+///
+/// The archive must record a `segments_root` that matches its segment list; the
+/// proof is only as trustworthy as the signature over that root, which the
+/// recipient checks separately.
+pub fn segment_inclusion_proof<P: AsRef<Path>>(
+    archive: P,
+    index: usize,
+) -> Result<SegmentInclusionProof, ArchiveError> {
+    let manifest = read_checked_manifest(archive.as_ref())?;
+    if manifest.segments_root.is_none() {
+        return Err(ArchiveError::ValidationFailed(
+            "Archive manifest has no segments_root".to_string(),
+        ));
+    }
+    check_segments_root(&manifest)?;
+
+    let segment_count = manifest.segments.len();
+    if index >= segment_count {
+        return Err(ArchiveError::ValidationFailed(format!(
+            "Segment {} out of range for {} segments",
+            index, segment_count
+        )));
+    }
+
+    let mut level = segment_leaves(&manifest.segments)?;
+    let mut position = index;
+    let mut siblings = Vec::new();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(position ^ 1) {
+            siblings.push(*sibling);
+        }
+        level = next_level(&level);
+        position /= 2;
+    }
+
+    Ok(SegmentInclusionProof {
+        index,
+        segment_count,
+        siblings,
+    })
+}
+
+/// Check that `segment_hash` sits at `proof.index` under `root`
+pub fn verify_inclusion(
+    root: &[u8; 32],
+    proof: &SegmentInclusionProof,
+    segment_hash: &[u8; 32],
+) -> bool {
+    if proof.index >= proof.segment_count {
+        return false;
+    }
+
+    let mut siblings = proof.siblings.iter();
+    let mut node = leaf_hash(segment_hash);
+    let mut position = proof.index;
+    let mut width = proof.segment_count;
+    while width > 1 {
+        let has_sibling = position % 2 == 1 || position + 1 < width;
+        if has_sibling {
+            let Some(sibling) = siblings.next() else {
+                return false;
+            };
+            node = if position % 2 == 1 {
+                node_hash(sibling, &node)
+            } else {
+                node_hash(&node, sibling)
+            };
+        }
+        position /= 2;
+        width = width.div_ceil(2);
+    }
+
+    siblings.next().is_none() && node == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::check_archive_signature;
+    use crate::{wrap_file, CamVideoMetadata, DeviceKeypair, ProfileMetadata, WrapConfig};
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Wrap `segments` 16-byte chunks into a signed archive
+    fn wrap_segments(dir: &Path, segments: usize) -> (std::path::PathBuf, crate::TrstManifest) {
+        let input = dir.join("input.bin");
+        fs::write(&input, vec![7u8; segments * 16]).unwrap();
+        let mut config = WrapConfig::new(ProfileMetadata::CamVideo(CamVideoMetadata {
+            started_at: "2025-01-15T10:30:00Z".to_string(),
+            ended_at: "2025-01-15T10:31:00Z".to_string(),
+            timezone: "UTC".to_string(),
+            fps: 30.0,
+            resolution: "1920x1080".to_string(),
+            codec: "raw".to_string(),
+        }));
+        config.chunk_size = 16;
+        config.seed = Some(7);
+
+        let keypair = DeviceKeypair::generate().unwrap();
+        let output = dir.join(format!("clip{}.seal", segments));
+        let manifest = wrap_file(&input, &output, &keypair, &config).unwrap();
+        (output, manifest)
+    }
+
+    fn root_of(manifest: &crate::TrstManifest) -> [u8; 32] {
+        hex::decode(manifest.segments_root.as_ref().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    fn hash_of(manifest: &crate::TrstManifest, index: usize) -> [u8; 32] {
+        hex::decode(&manifest.segments[index].blake3_hash)
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_inclusion_proof_verifies_every_segment() {
+        let temp_dir = TempDir::new().unwrap();
+        for segments in [1, 2, 5, 8] {
+            let (archive, manifest) = wrap_segments(temp_dir.path(), segments);
+            let root = root_of(&manifest);
+            for index in 0..segments {
+                let proof = segment_inclusion_proof(&archive, index).unwrap();
+                assert!(
+                    verify_inclusion(&root, &proof, &hash_of(&manifest, index)),
+                    "segment {} of {} did not verify",
+                    index,
+                    segments
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_inclusion_proof_for_wrong_index_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let (archive, manifest) = wrap_segments(temp_dir.path(), 5);
+        let root = root_of(&manifest);
+        let proof = segment_inclusion_proof(&archive, 1).unwrap();
+
+        // Another segment's hash does not fit segment 1's path
+        assert!(!verify_inclusion(&root, &proof, &hash_of(&manifest, 2)));
+
+        // Nor does segment 1 verify at a different position
+        let moved = SegmentInclusionProof {
+            index: 0,
+            ..proof.clone()
+        };
+        assert!(!verify_inclusion(&root, &moved, &hash_of(&manifest, 1)));
+
+        assert!(matches!(
+            segment_inclusion_proof(&archive, 5),
+            Err(ArchiveError::ValidationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_segments_root_is_signed() {
+        let temp_dir = TempDir::new().unwrap();
+        let (archive, manifest) = wrap_segments(temp_dir.path(), 3);
+        let canonical = String::from_utf8(manifest.to_canonical_bytes().unwrap()).unwrap();
+        assert!(canonical.contains(manifest.segments_root.as_deref().unwrap()));
+        check_archive_signature(&manifest, &manifest.device.public_key).unwrap();
+
+        // A replaced root no longer matches the device's signature
+        let mut forged = manifest.clone();
+        forged.segments_root = Some(hex::encode([0u8; 32]));
+        assert!(check_archive_signature(&forged, &manifest.device.public_key).is_err());
+
+        // A root that disagrees with the segment list fails validation
+        let manifest_path = archive.join("manifest.json");
+        fs::write(
+            &manifest_path,
+            serde_json::to_string_pretty(&forged).unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(
+            crate::validate_archive(&archive),
+            Err(ArchiveError::ValidationFailed(reason)) if reason.contains("Segments root")
+        ));
+    }
+}
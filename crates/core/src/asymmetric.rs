@@ -149,6 +149,7 @@ impl KeyPair {
             AsymmetricAlgorithm::EcdsaP256 => Self::generate_ecdsa_p256(),
             AsymmetricAlgorithm::Rsa2048 => Self::generate_rsa(2048),
             AsymmetricAlgorithm::Rsa4096 => Self::generate_rsa(4096),
+            AsymmetricAlgorithm::X25519 => Self::generate_x25519(),
         }
     }
 
@@ -173,6 +174,20 @@ impl KeyPair {
         Ok(Self::new(public, private))
     }
 
+    /// Generate an X25519 key agreement key pair
+    fn generate_x25519() -> Result<Self> {
+        use rand_core::OsRng;
+        use x25519_dalek::{PublicKey as X25519PublicKey, StaticSecret};
+
+        let secret = StaticSecret::random_from_rng(OsRng);
+        let public_key = X25519PublicKey::from(&secret);
+
+        let private = PrivateKey::new(AsymmetricAlgorithm::X25519, secret.to_bytes().to_vec());
+        let public = PublicKey::new(AsymmetricAlgorithm::X25519, public_key.to_bytes().to_vec());
+
+        Ok(Self::new(public, private))
+    }
+
     /// Generate an ECDSA P-256 key pair
     fn generate_ecdsa_p256() -> Result<Self> {
         use p256::elliptic_curve::sec1::ToEncodedPoint;
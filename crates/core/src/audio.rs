@@ -15,6 +15,7 @@
 //!
 //! Note: This module requires the "audio" feature to be enabled.
 
+use crate::format::AudioFormat;
 use anyhow::{anyhow, Result};
 use std::time::Instant;
 
@@ -29,6 +30,30 @@ use std::sync::mpsc::{self, Receiver, Sender};
 #[cfg(feature = "audio")]
 use std::sync::{Arc, Mutex};
 
+#[cfg(feature = "opus")]
+#[cfg_attr(docsrs, doc(cfg(feature = "opus")))]
+pub mod opus;
+
+/// How captured samples are encoded into chunk bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AudioEncoding {
+    /// Raw little-endian f32 PCM
+    #[default]
+    PcmF32,
+    /// Opus packets (requires the "opus" feature and a 8/12/16/24/48 kHz sample rate)
+    Opus,
+}
+
+impl AudioEncoding {
+    /// Sample format recorded in the manifest's `DataType::Audio`
+    pub fn audio_format(self) -> AudioFormat {
+        match self {
+            AudioEncoding::PcmF32 => AudioFormat::F32Le,
+            AudioEncoding::Opus => AudioFormat::Opus,
+        }
+    }
+}
+
 /// Audio capture configuration
 #[derive(Debug, Clone)]
 pub struct AudioConfig {
@@ -42,6 +67,8 @@ pub struct AudioConfig {
     pub chunk_duration_ms: u64,
     /// Buffer size for audio chunks
     pub buffer_size: usize,
+    /// Encoding applied to each chunk before it becomes chunk bytes
+    pub encoding: AudioEncoding,
 }
 
 impl Default for AudioConfig {
@@ -52,6 +79,7 @@ impl Default for AudioConfig {
             channels: 1,             // Mono by default
             chunk_duration_ms: 1000, // 1 second chunks
             buffer_size: 8192,
+            encoding: AudioEncoding::PcmF32,
         }
     }
 }
@@ -83,6 +111,19 @@ impl AudioChunk {
         bytes
     }
 
+    /// Encode the samples for encryption with the configured encoding
+    pub fn encode(&self, encoding: AudioEncoding) -> Result<Vec<u8>> {
+        match encoding {
+            AudioEncoding::PcmF32 => Ok(self.to_bytes()),
+            #[cfg(feature = "opus")]
+            AudioEncoding::Opus => opus::encode_chunk(&self.data, self.sample_rate, self.channels),
+            #[cfg(not(feature = "opus"))]
+            AudioEncoding::Opus => Err(anyhow!(
+                "Opus encoding not available - rebuild with the 'opus' feature"
+            )),
+        }
+    }
+
     /// Convert from bytes (for decryption)
     pub fn from_bytes(
         bytes: &[u8],
@@ -454,6 +495,7 @@ mod tests {
         assert_eq!(config.sample_rate, 44100);
         assert_eq!(config.channels, 1);
         assert_eq!(config.chunk_duration_ms, 1000);
+        assert_eq!(config.encoding, AudioEncoding::PcmF32);
     }
 
     #[test]
@@ -503,4 +545,55 @@ mod tests {
             Ok(_) => panic!("Expected error when audio feature disabled"),
         }
     }
+
+    #[test]
+    #[cfg(feature = "opus")]
+    fn test_opus_chunk_smaller_and_codec_roundtrips() {
+        use crate::format::DataType;
+
+        let data: Vec<f32> = (0..48000)
+            .map(|i| (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 48000.0).sin() * 0.5)
+            .collect();
+        let chunk = AudioChunk {
+            data,
+            timestamp: Instant::now(),
+            sample_rate: 48000,
+            channels: 1,
+            sequence: 1,
+        };
+        let pcm = chunk.encode(AudioEncoding::PcmF32).unwrap();
+        let encoded = chunk.encode(AudioEncoding::Opus).unwrap();
+        assert!(encoded.len() < pcm.len());
+        let decoded = opus::decode_chunk(&encoded, 48000, 1).unwrap();
+        assert_eq!(decoded.len(), chunk.data.len());
+
+        // The codec recorded in the manifest survives serialization
+        let data_type = DataType::Audio {
+            sample_rate: 48000,
+            channels: 1,
+            format: AudioEncoding::Opus.audio_format(),
+        };
+        let restored: DataType =
+            bincode::deserialize(&bincode::serialize(&data_type).unwrap()).unwrap();
+        assert!(matches!(
+            restored,
+            DataType::Audio {
+                format: AudioFormat::Opus,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    #[cfg(not(feature = "opus"))]
+    fn test_opus_encoding_requires_feature() {
+        let chunk = AudioChunk {
+            data: vec![0.0; 960],
+            timestamp: Instant::now(),
+            sample_rate: 48000,
+            channels: 1,
+            sequence: 1,
+        };
+        assert!(chunk.encode(AudioEncoding::Opus).is_err());
+    }
 }
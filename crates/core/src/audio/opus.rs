@@ -0,0 +1,153 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+// GitHub: https://github.com/TrustEdge-Labs/sealedge
+//
+
+//! Opus encoding for captured audio chunks
+//!
+//! Each chunk is encoded with a fresh encoder so it decodes on its own, which keeps
+//! encrypted chunks independent. Chunk layout:
+//!
+//! ```text
+//! sample_count: u32 LE   (interleaved f32 samples in the original chunk)
+//! packets:      [len: u16 LE][Opus packet] ...   (one per 20 ms frame)
+//! ```
+//!
+//! The final frame is padded with silence; `sample_count` trims it on decode.
+
+use anyhow::{anyhow, Result};
+
+/// Opus frame length in milliseconds
+const FRAME_MS: u32 = 20;
+/// Largest packet libopus recommends allocating for
+const MAX_PACKET_LEN: usize = 4000;
+
+fn opus_channels(channels: u16) -> Result<opus::Channels> {
+    match channels {
+        1 => Ok(opus::Channels::Mono),
+        2 => Ok(opus::Channels::Stereo),
+        _ => Err(anyhow!("Opus supports 1 or 2 channels, not {}", channels)),
+    }
+}
+
+/// Interleaved samples in one 20 ms frame
+fn frame_samples(sample_rate: u32, channels: u16) -> Result<usize> {
+    match sample_rate {
+        8000 | 12000 | 16000 | 24000 | 48000 => {
+            Ok((sample_rate / (1000 / FRAME_MS)) as usize * channels as usize)
+        }
+        _ => Err(anyhow!(
+            "Opus requires a sample rate of 8, 12, 16, 24 or 48 kHz, not {} Hz",
+            sample_rate
+        )),
+    }
+}
+
+/// Encode one chunk of interleaved f32 samples
+pub fn encode_chunk(samples: &[f32], sample_rate: u32, channels: u16) -> Result<Vec<u8>> {
+    let frame_len = frame_samples(sample_rate, channels)?;
+    let mut encoder = opus::Encoder::new(
+        sample_rate,
+        opus_channels(channels)?,
+        opus::Application::Audio,
+    )
+    .map_err(|e| anyhow!("Failed to create Opus encoder: {}", e))?;
+
+    let sample_count =
+        u32::try_from(samples.len()).map_err(|_| anyhow!("Audio chunk too large for Opus"))?;
+    let mut out = sample_count.to_le_bytes().to_vec();
+    let mut frame = vec![0f32; frame_len];
+    let mut packet = [0u8; MAX_PACKET_LEN];
+    for input in samples.chunks(frame_len) {
+        frame[..input.len()].copy_from_slice(input);
+        frame[input.len()..].fill(0.0);
+        let len = encoder
+            .encode_float(&frame, &mut packet)
+            .map_err(|e| anyhow!("Opus encode failed: {}", e))?;
+        out.extend_from_slice(&(len as u16).to_le_bytes());
+        out.extend_from_slice(&packet[..len]);
+    }
+    Ok(out)
+}
+
+/// Decode a chunk produced by [`encode_chunk`] back to interleaved f32 samples
+pub fn decode_chunk(bytes: &[u8], sample_rate: u32, channels: u16) -> Result<Vec<f32>> {
+    let frame_len = frame_samples(sample_rate, channels)?;
+    let mut decoder = opus::Decoder::new(sample_rate, opus_channels(channels)?)
+        .map_err(|e| anyhow!("Failed to create Opus decoder: {}", e))?;
+
+    let (count, mut rest) = bytes
+        .split_first_chunk::<4>()
+        .ok_or_else(|| anyhow!("Opus chunk missing sample count"))?;
+    let sample_count = u32::from_le_bytes(*count) as usize;
+
+    let mut samples = Vec::new();
+    let mut frame = vec![0f32; frame_len];
+    while !rest.is_empty() {
+        let (len, tail) = rest
+            .split_first_chunk::<2>()
+            .ok_or_else(|| anyhow!("Truncated Opus packet length"))?;
+        let len = u16::from_le_bytes(*len) as usize;
+        if tail.len() < len {
+            return Err(anyhow!("Truncated Opus packet"));
+        }
+        let (packet, tail) = tail.split_at(len);
+        let decoded = decoder
+            .decode_float(packet, &mut frame, false)
+            .map_err(|e| anyhow!("Opus decode failed: {}", e))?;
+        samples.extend_from_slice(&frame[..decoded * channels as usize]);
+        // Padding never exceeds one frame
+        if samples.len() > sample_count + frame_len {
+            return Err(anyhow!("Opus chunk decodes past its sample count"));
+        }
+        rest = tail;
+    }
+
+    if samples.len() < sample_count {
+        return Err(anyhow!(
+            "Opus chunk decoded {} samples, expected {}",
+            samples.len(),
+            sample_count
+        ));
+    }
+    samples.truncate(sample_count);
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(sample_rate: u32, seconds: f32) -> Vec<f32> {
+        let n = (sample_rate as f32 * seconds) as usize;
+        (0..n)
+            .map(|i| {
+                (2.0 * std::f32::consts::PI * 440.0 * i as f32 / sample_rate as f32).sin() * 0.5
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_partial_final_frame_is_trimmed() {
+        // 1.01 s at 48 kHz stereo, so the last 20 ms frame is padded
+        let samples = sine(48000, 2.02);
+        let encoded = encode_chunk(&samples, 48000, 2).unwrap();
+        let decoded = decode_chunk(&encoded, 48000, 2).unwrap();
+        assert_eq!(decoded.len(), samples.len());
+    }
+
+    #[test]
+    fn test_unsupported_sample_rate_is_rejected() {
+        assert!(encode_chunk(&sine(44100, 0.1), 44100, 1).is_err());
+    }
+
+    #[test]
+    fn test_truncated_chunk_is_rejected() {
+        let encoded = encode_chunk(&sine(16000, 0.2), 16000, 1).unwrap();
+        assert!(decode_chunk(&encoded[..encoded.len() - 1], 16000, 1).is_err());
+    }
+}
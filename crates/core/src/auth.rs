@@ -201,7 +201,7 @@ impl ServerCertificate {
     /// Man-in-the-Middle attack. The pinned key is typically obtained by loading
     /// the server's exported certificate file via [`load_server_cert`].
     pub fn verify_pinned(&self, expected_pubkey: &[u8; 32]) -> Result<()> {
-        if self.public_key != *expected_pubkey {
+        if !crate::envelope::keys_equal(&self.public_key, expected_pubkey) {
             return Err(anyhow!(
                 "Server public key mismatch: certificate key does not match pinned key. \
                  Possible Man-in-the-Middle attack."
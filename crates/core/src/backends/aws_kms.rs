@@ -0,0 +1,495 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! AWS KMS backend for Universal Backend system
+//!
+//! Signing and envelope key wrapping with keys managed by AWS KMS. Key material
+//! never leaves KMS: every private-key operation is a KMS API call against the
+//! configured key ARN, and there are NO software fallbacks.
+//!
+//! ## Supported Operations
+//! - Key derivation (`KeyBackend`): KMS `GenerateMac` (HMAC-SHA256) over the key ID
+//!   and derivation context, so `key_arn` must name an `HMAC_256` key for this use
+//! - Signing: ECDSA P-256 (DER signatures), RSA PKCS#1 v1.5 and RSA-PSS, all over
+//!   SHA-256 digests computed locally
+//! - Public key export (DER SubjectPublicKeyInfo)
+//! - Key wrapping: `Encrypt`/`Decrypt` with a symmetric KMS key (AES-256-GCM,
+//!   `SYMMETRIC_DEFAULT`), for data keys up to 4 KiB
+//! - SHA-256/384/512 hashing (software, not security-critical)
+//!
+//! ## Configuration
+//! - `key_arn`: KMS key ARN, key ID or `alias/...` (required)
+//! - `region`: AWS region (default: from the environment)
+//! - `endpoint_url`: KMS endpoint override, e.g. `http://localhost:4566` for LocalStack
+//!
+//! Credentials come from the standard AWS provider chain. The SDK is async, so the
+//! backend owns a single-threaded Tokio runtime and must not be called from within
+//! another Tokio runtime.
+
+use crate::backends::traits::{BackendInfo, KeyBackend, KeyContext, KeyMetadata};
+use crate::backends::universal::{
+    AsymmetricAlgorithm, BackendCapabilities, CryptoOperation, CryptoResult, HashAlgorithm,
+    SignatureAlgorithm, SymmetricAlgorithm, UniversalBackend,
+};
+use crate::error::BackendError;
+use aws_sdk_kms::config::Region;
+use aws_sdk_kms::error::{DisplayErrorContext, ProvideErrorMetadata, SdkError};
+use aws_sdk_kms::primitives::Blob;
+use aws_sdk_kms::types::{
+    EncryptionAlgorithmSpec, MacAlgorithmSpec, MessageType, SigningAlgorithmSpec,
+};
+use aws_sdk_kms::Client;
+use sha2::{Digest as _, Sha256, Sha384, Sha512};
+use tokio::runtime::Runtime;
+
+/// Largest plaintext KMS `Encrypt` accepts
+pub const KMS_MAX_PLAINTEXT: usize = 4096;
+
+/// Domain separation for derived keys, bumped if the derivation ever changes
+const DERIVE_DOMAIN: &[u8] = b"sealedge-aws-kms-derive-v1";
+
+/// Configuration for the AWS KMS backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AwsKmsConfig {
+    /// Default KMS key (ARN, key ID or alias)
+    pub key_arn: String,
+    /// AWS region; falls back to the environment when unset
+    pub region: Option<String>,
+    /// Endpoint override for KMS-compatible emulators
+    pub endpoint_url: Option<String>,
+}
+
+impl AwsKmsConfig {
+    pub fn new(key_arn: impl Into<String>) -> Self {
+        Self {
+            key_arn: key_arn.into(),
+            region: None,
+            endpoint_url: None,
+        }
+    }
+
+    /// Build a config from `--backend-config key=value` entries
+    pub fn from_backend_config(entries: &[String]) -> Result<Self, BackendError> {
+        let mut key_arn = None;
+        let mut region = None;
+        let mut endpoint_url = None;
+        for entry in entries {
+            let (key, value) = entry.split_once('=').ok_or_else(|| {
+                BackendError::InitializationFailed(format!(
+                    "Invalid AWS KMS backend config '{}' (expected key=value)",
+                    entry
+                ))
+            })?;
+            let value = value.trim().to_string();
+            match key.trim() {
+                "key_arn" => key_arn = Some(value),
+                "region" => region = Some(value),
+                "endpoint_url" => endpoint_url = Some(value),
+                other => {
+                    return Err(BackendError::InitializationFailed(format!(
+                        "Unknown AWS KMS backend config key '{}' (supported: key_arn, region, endpoint_url)",
+                        other
+                    )))
+                }
+            }
+        }
+
+        let key_arn = key_arn.filter(|arn| !arn.is_empty()).ok_or_else(|| {
+            BackendError::InitializationFailed(
+                "AWS KMS backend requires --backend-config key_arn=<key ARN>".to_string(),
+            )
+        })?;
+        Ok(Self {
+            key_arn,
+            region,
+            endpoint_url,
+        })
+    }
+}
+
+/// AWS KMS backend
+pub struct AwsKmsBackend {
+    client: Client,
+    runtime: Runtime,
+    config: AwsKmsConfig,
+}
+
+impl AwsKmsBackend {
+    /// Load AWS configuration and create a KMS client for `config`
+    pub fn new(config: AwsKmsConfig) -> Result<Self, BackendError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                BackendError::InitializationFailed(format!("Failed to start async runtime: {}", e))
+            })?;
+
+        let mut loader = aws_config::from_env();
+        if let Some(region) = &config.region {
+            loader = loader.region(Region::new(region.clone()));
+        }
+        if let Some(endpoint_url) = &config.endpoint_url {
+            loader = loader.endpoint_url(endpoint_url);
+        }
+        let sdk_config = runtime.block_on(loader.load());
+        if sdk_config.region().is_none() {
+            return Err(BackendError::InitializationFailed(
+                "No AWS region configured; set AWS_REGION or --backend-config region=<region>"
+                    .to_string(),
+            ));
+        }
+
+        Ok(Self {
+            client: Client::new(&sdk_config),
+            runtime,
+            config,
+        })
+    }
+
+    /// Build the backend from `--backend-config key=value` entries
+    pub fn from_backend_config(entries: &[String]) -> Result<Self, BackendError> {
+        Self::new(AwsKmsConfig::from_backend_config(entries)?)
+    }
+
+    pub fn config(&self) -> &AwsKmsConfig {
+        &self.config
+    }
+
+    /// KMS key to use: `key_id` when it names a KMS key, otherwise the configured key
+    fn resolve_key<'a>(&'a self, key_id: &'a str) -> &'a str {
+        if key_id.starts_with("arn:") || key_id.starts_with("alias/") {
+            key_id
+        } else {
+            &self.config.key_arn
+        }
+    }
+
+    fn kms_sign(
+        &self,
+        key_id: &str,
+        data: &[u8],
+        algorithm: &SignatureAlgorithm,
+    ) -> Result<Vec<u8>, BackendError> {
+        let spec = match algorithm {
+            SignatureAlgorithm::EcdsaP256 => SigningAlgorithmSpec::EcdsaSha256,
+            SignatureAlgorithm::RsaPkcs1v15 => SigningAlgorithmSpec::RsassaPkcs1V15Sha256,
+            SignatureAlgorithm::RsaPss => SigningAlgorithmSpec::RsassaPssSha256,
+            SignatureAlgorithm::Ed25519 => {
+                return Err(BackendError::UnsupportedOperation(
+                    "Ed25519 signing is not supported by AWS KMS backend".to_string(),
+                ))
+            }
+        };
+        // Sign a locally computed digest so messages are not limited to 4 KiB
+        let digest = Sha256::digest(data);
+        let output = self
+            .runtime
+            .block_on(
+                self.client
+                    .sign()
+                    .key_id(key_id)
+                    .message(Blob::new(digest.to_vec()))
+                    .message_type(MessageType::Digest)
+                    .signing_algorithm(spec)
+                    .send(),
+            )
+            .map_err(|e| kms_error("Sign", e))?;
+        blob_bytes("Sign", output.signature())
+    }
+
+    fn kms_public_key(&self, key_id: &str) -> Result<Vec<u8>, BackendError> {
+        let output = self
+            .runtime
+            .block_on(self.client.get_public_key().key_id(key_id).send())
+            .map_err(|e| kms_error("GetPublicKey", e))?;
+        blob_bytes("GetPublicKey", output.public_key())
+    }
+
+    fn kms_encrypt(&self, key_id: &str, plaintext: Vec<u8>) -> Result<Vec<u8>, BackendError> {
+        if plaintext.len() > KMS_MAX_PLAINTEXT {
+            return Err(BackendError::OperationFailed(format!(
+                "KMS can wrap at most {} bytes (got {}); encrypt data with a data key instead",
+                KMS_MAX_PLAINTEXT,
+                plaintext.len()
+            )));
+        }
+        let output = self
+            .runtime
+            .block_on(
+                self.client
+                    .encrypt()
+                    .key_id(key_id)
+                    .plaintext(Blob::new(plaintext))
+                    .encryption_algorithm(EncryptionAlgorithmSpec::SymmetricDefault)
+                    .send(),
+            )
+            .map_err(|e| kms_error("Encrypt", e))?;
+        blob_bytes("Encrypt", output.ciphertext_blob())
+    }
+
+    fn kms_decrypt(&self, key_id: &str, ciphertext: Vec<u8>) -> Result<Vec<u8>, BackendError> {
+        let output = self
+            .runtime
+            .block_on(
+                self.client
+                    .decrypt()
+                    .key_id(key_id)
+                    .ciphertext_blob(Blob::new(ciphertext))
+                    .encryption_algorithm(EncryptionAlgorithmSpec::SymmetricDefault)
+                    .send(),
+            )
+            .map_err(|e| kms_error("Decrypt", e))?;
+        blob_bytes("Decrypt", output.plaintext())
+    }
+
+    /// HMAC-SHA256 of `message` under the configured KMS HMAC key
+    fn kms_mac(&self, message: &[u8]) -> Result<[u8; 32], BackendError> {
+        let output = self
+            .runtime
+            .block_on(
+                self.client
+                    .generate_mac()
+                    .key_id(&self.config.key_arn)
+                    .message(Blob::new(message.to_vec()))
+                    .mac_algorithm(MacAlgorithmSpec::HmacSha256)
+                    .send(),
+            )
+            .map_err(|e| kms_error("GenerateMac", e))?;
+        blob_bytes("GenerateMac", output.mac())?
+            .try_into()
+            .map_err(|_| {
+                BackendError::OperationFailed(
+                    "KMS GenerateMac returned a MAC of unexpected size".to_string(),
+                )
+            })
+    }
+}
+
+/// Length-prefixed derivation input so key ID, salt and context cannot be confused
+fn derivation_message(key_id: &[u8], salt: &[u8], additional_data: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(
+        DERIVE_DOMAIN.len() + key_id.len() + 8 + salt.len() + additional_data.len(),
+    );
+    message.extend_from_slice(DERIVE_DOMAIN);
+    message.extend_from_slice(key_id);
+    message.extend_from_slice(&(salt.len() as u32).to_be_bytes());
+    message.extend_from_slice(salt);
+    message.extend_from_slice(&(additional_data.len() as u32).to_be_bytes());
+    message.extend_from_slice(additional_data);
+    message
+}
+
+fn blob_bytes(operation: &str, blob: Option<&Blob>) -> Result<Vec<u8>, BackendError> {
+    blob.map(|b| b.as_ref().to_vec()).ok_or_else(|| {
+        BackendError::OperationFailed(format!("KMS {} returned an empty response", operation))
+    })
+}
+
+/// Map a KMS SDK error onto the backend error kinds
+fn kms_error<E, R>(operation: &str, err: SdkError<E, R>) -> BackendError
+where
+    E: ProvideErrorMetadata + std::error::Error + 'static,
+    R: std::fmt::Debug,
+{
+    let detail = format!("KMS {} failed: {}", operation, DisplayErrorContext(&err));
+    match err.code() {
+        Some("NotFoundException") => BackendError::KeyNotFound(detail),
+        Some("UnsupportedOperationException") => BackendError::UnsupportedOperation(detail),
+        // Service-side availability problems, or no response at all
+        Some("KeyUnavailableException" | "DependencyTimeoutException" | "KMSInternalException")
+        | None => BackendError::HardwareError(detail),
+        // Key disabled or in the wrong state, wrong key usage, bad ciphertext, access denied
+        Some(_) => BackendError::OperationFailed(detail),
+    }
+}
+
+impl UniversalBackend for AwsKmsBackend {
+    fn perform_operation(
+        &self,
+        key_id: &str,
+        operation: CryptoOperation,
+    ) -> Result<CryptoResult, BackendError> {
+        let kms_key = self.resolve_key(key_id);
+        match operation {
+            CryptoOperation::Sign { data, algorithm } => Ok(CryptoResult::Signed(
+                self.kms_sign(kms_key, &data, &algorithm)?,
+            )),
+
+            CryptoOperation::GetPublicKey => {
+                Ok(CryptoResult::PublicKey(self.kms_public_key(kms_key)?))
+            }
+
+            CryptoOperation::Encrypt {
+                plaintext,
+                algorithm: SymmetricAlgorithm::Aes256Gcm,
+            } => Ok(CryptoResult::Encrypted(
+                self.kms_encrypt(kms_key, plaintext)?,
+            )),
+
+            CryptoOperation::Decrypt {
+                ciphertext,
+                algorithm: SymmetricAlgorithm::Aes256Gcm,
+            } => Ok(CryptoResult::Decrypted(
+                self.kms_decrypt(kms_key, ciphertext)?,
+            )),
+
+            CryptoOperation::Hash { data, algorithm } => {
+                // Hash operations can use software (not security-critical)
+                let hash = match algorithm {
+                    HashAlgorithm::Sha256 => Sha256::digest(&data).to_vec(),
+                    HashAlgorithm::Sha384 => Sha384::digest(&data).to_vec(),
+                    HashAlgorithm::Sha512 => Sha512::digest(&data).to_vec(),
+                };
+                Ok(CryptoResult::Hash(hash))
+            }
+
+            _ => Err(BackendError::UnsupportedOperation(format!(
+                "Operation {:?} not supported by AWS KMS backend",
+                operation
+            ))),
+        }
+    }
+
+    fn supports_operation(&self, operation: &CryptoOperation) -> bool {
+        match operation {
+            CryptoOperation::GetPublicKey | CryptoOperation::Hash { .. } => true,
+            CryptoOperation::Sign { algorithm, .. } => {
+                !matches!(algorithm, SignatureAlgorithm::Ed25519)
+            }
+            CryptoOperation::Encrypt { algorithm, .. }
+            | CryptoOperation::Decrypt { algorithm, .. } => {
+                matches!(algorithm, SymmetricAlgorithm::Aes256Gcm)
+            }
+            _ => false,
+        }
+    }
+
+    fn get_capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            symmetric_algorithms: vec![SymmetricAlgorithm::Aes256Gcm],
+            asymmetric_algorithms: vec![
+                AsymmetricAlgorithm::EcdsaP256,
+                AsymmetricAlgorithm::Rsa2048,
+                AsymmetricAlgorithm::Rsa4096,
+            ],
+            signature_algorithms: vec![
+                SignatureAlgorithm::EcdsaP256,
+                SignatureAlgorithm::RsaPkcs1v15,
+                SignatureAlgorithm::RsaPss,
+            ],
+            hash_algorithms: vec![
+                HashAlgorithm::Sha256,
+                HashAlgorithm::Sha384,
+                HashAlgorithm::Sha512,
+            ],
+            // AWS KMS keys live in FIPS-validated HSMs; an endpoint override
+            // points at an emulator whose keys are plain software
+            hardware_backed: self.config.endpoint_url.is_none(),
+            supports_key_derivation: false,
+            supports_key_generation: false,
+            supports_attestation: false,
+            max_key_size: Some(4096),
+        }
+    }
+
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo::aws_kms()
+    }
+}
+
+impl KeyBackend for AwsKmsBackend {
+    fn derive_key(
+        &self,
+        key_id: &[u8; 16],
+        context: &KeyContext,
+    ) -> Result<[u8; 32], BackendError> {
+        let message = derivation_message(key_id, &context.salt, &context.additional_data);
+        if message.len() > KMS_MAX_PLAINTEXT {
+            return Err(BackendError::OperationFailed(format!(
+                "KMS GenerateMac accepts at most {} bytes of derivation context",
+                KMS_MAX_PLAINTEXT
+            )));
+        }
+        self.kms_mac(&message)
+    }
+
+    fn store_key(&self, _key_id: &[u8; 16], _key_data: &[u8; 32]) -> Result<(), BackendError> {
+        Err(BackendError::UnsupportedOperation(
+            "AWS KMS backend does not import key material".to_string(),
+        ))
+    }
+
+    fn rotate_key(&self, _old_id: &[u8; 16], _new_id: &[u8; 16]) -> Result<(), BackendError> {
+        Err(BackendError::UnsupportedOperation(
+            "Rotate AWS KMS keys in KMS itself; derive with a new key ID instead".to_string(),
+        ))
+    }
+
+    fn list_keys(&self) -> Result<Vec<KeyMetadata>, BackendError> {
+        // Keys are re-derived on demand, nothing is persisted to enumerate
+        Ok(vec![])
+    }
+
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo::aws_kms()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_from_backend_config() {
+        let config = AwsKmsConfig::from_backend_config(&[
+            "key_arn=arn:aws:kms:us-east-1:000000000000:key/abcd".to_string(),
+            "region=us-east-1".to_string(),
+            "endpoint_url=http://localhost:4566".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(
+            config.key_arn,
+            "arn:aws:kms:us-east-1:000000000000:key/abcd"
+        );
+        assert_eq!(config.region.as_deref(), Some("us-east-1"));
+        assert_eq!(
+            config.endpoint_url.as_deref(),
+            Some("http://localhost:4566")
+        );
+    }
+
+    #[test]
+    fn test_config_requires_key_arn() {
+        assert!(AwsKmsConfig::from_backend_config(&[]).is_err());
+        assert!(AwsKmsConfig::from_backend_config(&["key_arn=".to_string()]).is_err());
+        assert!(AwsKmsConfig::from_backend_config(&["region=us-east-1".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_config_rejects_unknown_and_malformed_entries() {
+        assert!(AwsKmsConfig::from_backend_config(&[
+            "key_arn=alias/sealedge".to_string(),
+            "profile=default".to_string()
+        ])
+        .is_err());
+        assert!(AwsKmsConfig::from_backend_config(&["key_arn".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_derivation_message_is_unambiguous() {
+        let key_id = [7u8; 16];
+        assert_ne!(
+            derivation_message(&key_id, b"ab", b"c"),
+            derivation_message(&key_id, b"a", b"bc")
+        );
+        assert_ne!(
+            derivation_message(&[1u8; 16], b"salt", b""),
+            derivation_message(&[2u8; 16], b"salt", b"")
+        );
+    }
+}
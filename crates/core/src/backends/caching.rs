@@ -0,0 +1,289 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Derived key caching
+//!
+//! Key derivation is deliberately slow (600k PBKDF2 iterations for the keyring
+//! backend), so repeated encrypt/decrypt calls with the same key ID and salt pay
+//! that cost every time. [`CachingBackend`] wraps any [`KeyBackend`] with a small
+//! LRU cache of derived keys. Caching keeps key material in memory for longer, so
+//! it is opt-in: enable it with [`BackendRegistry::with_key_cache`] or wrap a
+//! backend directly.
+//!
+//! Cached keys are zeroized when evicted, cleared, or dropped.
+//!
+//! [`BackendRegistry::with_key_cache`]: crate::backends::BackendRegistry::with_key_cache
+
+use crate::backends::traits::{
+    BackendInfo, KeyBackend, KeyContext, KeyDerivationParams, KeyMetadata,
+};
+use crate::error::BackendError;
+use crate::secret::Secret;
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+use zeroize::Zeroize;
+
+/// Default number of derived keys kept by [`CachingBackend`]
+pub const DEFAULT_KEY_CACHE_CAPACITY: usize = 16;
+
+/// Everything that determines a derived key
+#[derive(Clone, PartialEq, Eq)]
+struct CacheKey {
+    key_id: [u8; 16],
+    salt: Vec<u8>,
+    additional_data: Vec<u8>,
+    params: KeyDerivationParams,
+}
+
+impl CacheKey {
+    fn new(key_id: &[u8; 16], context: &KeyContext) -> Self {
+        Self {
+            key_id: *key_id,
+            salt: context.salt.clone(),
+            additional_data: context.additional_data.clone(),
+            params: context.params,
+        }
+    }
+}
+
+/// Bounded LRU of derived keys; least recently used entries sit at the front
+struct KeyCache {
+    capacity: usize,
+    entries: VecDeque<(CacheKey, Secret<[u8; 32]>)>,
+}
+
+impl KeyCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    fn get(&mut self, key: &CacheKey) -> Option<[u8; 32]> {
+        let index = self.entries.iter().position(|(k, _)| k == key)?;
+        let entry = self.entries.remove(index)?;
+        let value = *entry.1.expose_secret();
+        self.entries.push_back(entry);
+        Some(value)
+    }
+
+    fn insert(&mut self, key: CacheKey, value: [u8; 32]) {
+        if let Some(index) = self.entries.iter().position(|(k, _)| *k == key) {
+            self.entries.remove(index);
+        }
+        while self.entries.len() >= self.capacity {
+            self.evict_lru();
+        }
+        self.entries.push_back((key, Secret::new(value)));
+    }
+
+    /// Remove the least recently used entry, returning its (zeroized) key
+    fn evict_lru(&mut self) -> Option<Secret<[u8; 32]>> {
+        let (_, mut value) = self.entries.pop_front()?;
+        value.zeroize();
+        Some(value)
+    }
+
+    fn clear(&mut self) {
+        while self.evict_lru().is_some() {}
+    }
+}
+
+/// [`KeyBackend`] wrapper that memoizes derived keys
+pub struct CachingBackend {
+    inner: Box<dyn KeyBackend>,
+    cache: Mutex<KeyCache>,
+}
+
+impl CachingBackend {
+    /// Wrap `inner`, keeping at most `capacity` derived keys (minimum 1)
+    pub fn new(inner: Box<dyn KeyBackend>, capacity: usize) -> Self {
+        Self {
+            inner,
+            cache: Mutex::new(KeyCache::new(capacity.max(1))),
+        }
+    }
+
+    /// Zeroize and drop every cached key
+    pub fn clear_cache(&self) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.clear();
+        }
+    }
+
+    /// Number of keys currently cached
+    pub fn cached_keys(&self) -> usize {
+        self.cache.lock().map(|c| c.entries.len()).unwrap_or(0)
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, KeyCache>, BackendError> {
+        self.cache
+            .lock()
+            .map_err(|_| BackendError::OperationFailed("Key cache lock poisoned".to_string()))
+    }
+}
+
+impl Drop for CachingBackend {
+    fn drop(&mut self) {
+        self.clear_cache();
+    }
+}
+
+impl KeyBackend for CachingBackend {
+    fn derive_key(
+        &self,
+        key_id: &[u8; 16],
+        context: &KeyContext,
+    ) -> Result<[u8; 32], BackendError> {
+        let cache_key = CacheKey::new(key_id, context);
+        if let Some(key) = self.lock()?.get(&cache_key) {
+            return Ok(key);
+        }
+
+        // Derive without holding the lock; derivation can take a second
+        let key = self.inner.derive_key(key_id, context)?;
+        self.lock()?.insert(cache_key, key);
+        Ok(key)
+    }
+
+    fn store_key(&self, key_id: &[u8; 16], key_data: &[u8; 32]) -> Result<(), BackendError> {
+        self.clear_cache();
+        self.inner.store_key(key_id, key_data)
+    }
+
+    fn rotate_key(&self, old_id: &[u8; 16], new_id: &[u8; 16]) -> Result<(), BackendError> {
+        self.clear_cache();
+        self.inner.rotate_key(old_id, new_id)
+    }
+
+    fn list_keys(&self) -> Result<Vec<KeyMetadata>, BackendError> {
+        self.inner.list_keys()
+    }
+
+    fn backend_info(&self) -> BackendInfo {
+        self.inner.backend_info()
+    }
+
+    fn supports_storage(&self) -> bool {
+        self.inner.supports_storage()
+    }
+
+    fn supports_rotation(&self) -> bool {
+        self.inner.supports_rotation()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Backend whose derived key encodes the key ID and how many derivations ran
+    struct CountingBackend {
+        derivations: Arc<AtomicUsize>,
+    }
+
+    impl KeyBackend for CountingBackend {
+        fn derive_key(
+            &self,
+            key_id: &[u8; 16],
+            context: &KeyContext,
+        ) -> Result<[u8; 32], BackendError> {
+            let count = self.derivations.fetch_add(1, Ordering::SeqCst) + 1;
+            let mut key = [0u8; 32];
+            key[..16].copy_from_slice(key_id);
+            key[16] = context.salt.first().copied().unwrap_or(0);
+            key[31] = count as u8;
+            Ok(key)
+        }
+
+        fn store_key(&self, _: &[u8; 16], _: &[u8; 32]) -> Result<(), BackendError> {
+            Ok(())
+        }
+
+        fn rotate_key(&self, _: &[u8; 16], _: &[u8; 16]) -> Result<(), BackendError> {
+            Ok(())
+        }
+
+        fn list_keys(&self) -> Result<Vec<KeyMetadata>, BackendError> {
+            Ok(vec![])
+        }
+
+        fn backend_info(&self) -> BackendInfo {
+            BackendInfo::keyring()
+        }
+    }
+
+    fn caching_backend(capacity: usize) -> (CachingBackend, Arc<AtomicUsize>) {
+        let derivations = Arc::new(AtomicUsize::new(0));
+        let inner = CountingBackend {
+            derivations: derivations.clone(),
+        };
+        (CachingBackend::new(Box::new(inner), capacity), derivations)
+    }
+
+    #[test]
+    fn test_identical_inputs_return_cached_key() {
+        let (backend, derivations) = caching_backend(4);
+        let context = KeyContext::new(vec![1u8; 32]);
+
+        let first = backend.derive_key(&[7u8; 16], &context).unwrap();
+        let second = backend.derive_key(&[7u8; 16], &context).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(derivations.load(Ordering::SeqCst), 1);
+
+        // A different salt is a different cache entry
+        let other = backend
+            .derive_key(&[7u8; 16], &KeyContext::new(vec![2u8; 32]))
+            .unwrap();
+        assert_ne!(first, other);
+        assert_eq!(derivations.load(Ordering::SeqCst), 2);
+
+        backend.clear_cache();
+        assert_eq!(backend.cached_keys(), 0);
+        backend.derive_key(&[7u8; 16], &context).unwrap();
+        assert_eq!(derivations.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_least_recently_used_key_is_evicted() {
+        let (backend, derivations) = caching_backend(2);
+        let context = KeyContext::new(vec![1u8; 32]);
+
+        backend.derive_key(&[1u8; 16], &context).unwrap();
+        backend.derive_key(&[2u8; 16], &context).unwrap();
+        // Touch key 1 so key 2 becomes least recently used
+        backend.derive_key(&[1u8; 16], &context).unwrap();
+        backend.derive_key(&[3u8; 16], &context).unwrap();
+        assert_eq!(backend.cached_keys(), 2);
+        assert_eq!(derivations.load(Ordering::SeqCst), 3);
+
+        backend.derive_key(&[1u8; 16], &context).unwrap();
+        assert_eq!(derivations.load(Ordering::SeqCst), 3);
+        backend.derive_key(&[2u8; 16], &context).unwrap();
+        assert_eq!(derivations.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_eviction_zeroizes_key() {
+        let mut cache = KeyCache::new(2);
+        let context = KeyContext::new(vec![1u8; 32]);
+        cache.insert(CacheKey::new(&[1u8; 16], &context), [0xAA; 32]);
+        cache.insert(CacheKey::new(&[2u8; 16], &context), [0xBB; 32]);
+
+        let evicted = cache.evict_lru().unwrap();
+        assert_eq!(evicted.expose_secret(), &[0u8; 32]);
+        assert!(cache.get(&CacheKey::new(&[1u8; 16], &context)).is_none());
+        assert_eq!(
+            cache.get(&CacheKey::new(&[2u8; 16], &context)),
+            Some([0xBB; 32])
+        );
+    }
+}
@@ -6,4 +6,493 @@
 // Project: sealedge — Privacy and trust at the edge.
 //
 
+//! File-based key backend
+//!
+//! Loads master key material from a file on disk, for headless deployments where
+//! no OS keyring or hardware token is available. The file is read once when the
+//! backend is created and the material is zeroized when the backend is dropped.
+//!
+//! ## File Format
+//! Either a bare 64-character hex key (as written by `--key-out`), or `key=value`
+//! lines with `#` comments:
+//!
+//! ```text
+//! # raw 256-bit master key
+//! key=<64 hex chars>
+//!
+//! # ...or a passphrase, with an optional default salt
+//! passphrase=correct horse battery staple
+//! salt=<hex>
+//! ```
+//!
+//! ## Derivation
+//! - Raw key: HKDF-SHA256 with the context salt, bound to the key ID and additional data
+//! - Passphrase: the context's [`KeyDerivationParams`] (PBKDF2 or Argon2id) like the
+//!   keyring backend; the context salt is used when non-empty, otherwise the salt from
+//!   the file
+//!
+//! ## Permissions
+//! On Unix the file must not be accessible by its group or other users (`chmod 600`).
+//!
+//! ## Configuration
+//! - `path`: key file location (required)
 
+use crate::backends::traits::{
+    BackendInfo, KeyBackend, KeyContext, KeyDerivationParams, KeyMetadata,
+};
+use crate::backends::universal::{
+    BackendCapabilities, CryptoOperation, CryptoResult, HashAlgorithm, UniversalBackend,
+};
+use crate::error::BackendError;
+use crate::secret::Secret;
+use hkdf::Hkdf;
+use sha2::{Digest as _, Sha256, Sha384, Sha512};
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+/// Domain separation for keys derived from a raw master key
+const DERIVE_DOMAIN: &[u8] = b"sealedge-file-derive-v1";
+
+/// Minimum salt length for passphrase derivation
+const MIN_SALT_LEN: usize = 16;
+
+/// Key material loaded from a key file
+enum KeyMaterial {
+    Key(Secret<[u8; 32]>),
+    Passphrase {
+        passphrase: Secret<String>,
+        salt: Option<Vec<u8>>,
+    },
+}
+
+/// Key backend that reads its master key material from a file
+pub struct FileKeyBackend {
+    path: PathBuf,
+    material: KeyMaterial,
+}
+
+impl FileKeyBackend {
+    /// Load key material from `path`, refusing files its group or other users can access
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, BackendError> {
+        let path = path.as_ref();
+        check_permissions(path)?;
+
+        let contents = Zeroizing::new(std::fs::read_to_string(path).map_err(|e| {
+            BackendError::InitializationFailed(format!(
+                "Failed to read key file '{}': {}",
+                path.display(),
+                e
+            ))
+        })?);
+        let material = parse_key_file(&contents).map_err(|e| {
+            BackendError::InitializationFailed(format!(
+                "Invalid key file '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            material,
+        })
+    }
+
+    /// Build the backend from `--backend-config key=value` entries
+    pub fn from_backend_config(entries: &[String]) -> Result<Self, BackendError> {
+        let mut path = None;
+        for entry in entries {
+            let (key, value) = entry.split_once('=').ok_or_else(|| {
+                BackendError::InitializationFailed(format!(
+                    "Invalid file backend config '{}' (expected key=value)",
+                    entry
+                ))
+            })?;
+            match key.trim() {
+                "path" => path = Some(PathBuf::from(value.trim())),
+                other => {
+                    return Err(BackendError::InitializationFailed(format!(
+                        "Unknown file backend config key '{}' (supported: path)",
+                        other
+                    )))
+                }
+            }
+        }
+        let path = path.ok_or_else(|| {
+            BackendError::InitializationFailed(
+                "File backend requires --backend-config path=<key file>".to_string(),
+            )
+        })?;
+        Self::open(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Derive a 256-bit key bound to `key_id` from the loaded material
+    fn derive(
+        &self,
+        key_id: &[u8],
+        salt: &[u8],
+        additional_data: &[u8],
+        params: &KeyDerivationParams,
+    ) -> Result<[u8; 32], BackendError> {
+        match &self.material {
+            KeyMaterial::Key(master) => {
+                let mut info = Zeroizing::new(Vec::with_capacity(
+                    DERIVE_DOMAIN.len() + key_id.len() + additional_data.len(),
+                ));
+                info.extend_from_slice(DERIVE_DOMAIN);
+                info.extend_from_slice(key_id);
+                info.extend_from_slice(additional_data);
+                let mut key = [0u8; 32];
+                Hkdf::<Sha256>::new(Some(salt), master.expose_secret())
+                    .expand(&info, &mut key)
+                    .map_err(|e| {
+                        BackendError::OperationFailed(format!("HKDF expand failed: {}", e))
+                    })?;
+                Ok(key)
+            }
+            KeyMaterial::Passphrase {
+                passphrase,
+                salt: file_salt,
+            } => {
+                let salt = match (salt, file_salt) {
+                    (salt, _) if !salt.is_empty() => salt,
+                    (_, Some(file_salt)) => file_salt.as_slice(),
+                    _ => return Err(BackendError::OperationFailed(
+                        "Passphrase key file has no salt; provide one in the derivation context"
+                            .to_string(),
+                    )),
+                };
+                if salt.len() < MIN_SALT_LEN {
+                    return Err(BackendError::OperationFailed(format!(
+                        "Salt must be at least {} bytes for file backend",
+                        MIN_SALT_LEN
+                    )));
+                }
+
+                // Include key_id in the derivation for key isolation
+                let mut input = Zeroizing::new(passphrase.expose_secret().as_bytes().to_vec());
+                input.extend_from_slice(key_id);
+                input.extend_from_slice(additional_data);
+                params.derive(&input, salt)
+            }
+        }
+    }
+}
+
+/// Refuse key files that the group or other users can access
+#[cfg(unix)]
+fn check_permissions(path: &Path) -> Result<(), BackendError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = std::fs::metadata(path).map_err(|e| {
+        BackendError::InitializationFailed(format!(
+            "Failed to stat key file '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let mode = metadata.permissions().mode() & 0o777;
+    if mode & 0o077 != 0 {
+        return Err(BackendError::InitializationFailed(format!(
+            "Key file '{}' has permissions {:o} and is accessible by other users or its group; run: chmod 600 {}",
+            path.display(),
+            mode,
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_permissions(_path: &Path) -> Result<(), BackendError> {
+    Ok(())
+}
+
+fn parse_key_file(contents: &str) -> Result<KeyMaterial, String> {
+    let trimmed = contents.trim();
+    if trimmed.len() == 64 && trimmed.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return parse_hex_key(trimmed).map(KeyMaterial::Key);
+    }
+
+    let mut key = None;
+    let mut passphrase = None;
+    let mut salt = None;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, value) = line
+            .split_once('=')
+            .ok_or_else(|| "expected a hex key or key=value lines".to_string())?;
+        match name.trim() {
+            "key" => key = Some(parse_hex_key(value.trim())?),
+            "passphrase" => passphrase = Some(Secret::new(value.trim().to_string())),
+            "salt" => {
+                salt = Some(hex::decode(value.trim()).map_err(|e| format!("invalid salt: {}", e))?)
+            }
+            other => return Err(format!("unknown entry '{}'", other)),
+        }
+    }
+
+    match (key, passphrase) {
+        (Some(_), Some(_)) => Err("key and passphrase are mutually exclusive".to_string()),
+        (Some(_), None) if salt.is_some() => {
+            Err("salt only applies to passphrase key files".to_string())
+        }
+        (Some(key), None) => Ok(KeyMaterial::Key(key)),
+        (None, Some(passphrase)) if passphrase.expose_secret().is_empty() => {
+            Err("passphrase is empty".to_string())
+        }
+        (None, Some(passphrase)) => Ok(KeyMaterial::Passphrase { passphrase, salt }),
+        (None, None) => Err("no key or passphrase found".to_string()),
+    }
+}
+
+fn parse_hex_key(value: &str) -> Result<Secret<[u8; 32]>, String> {
+    let mut key = [0u8; 32];
+    hex::decode_to_slice(value, &mut key)
+        .map_err(|_| "key must be 64 hex characters".to_string())?;
+    let secret = Secret::new(key);
+    zeroize::Zeroize::zeroize(&mut key);
+    Ok(secret)
+}
+
+impl KeyBackend for FileKeyBackend {
+    fn derive_key(
+        &self,
+        key_id: &[u8; 16],
+        context: &KeyContext,
+    ) -> Result<[u8; 32], BackendError> {
+        self.derive(
+            key_id,
+            &context.salt,
+            &context.additional_data,
+            &context.params,
+        )
+    }
+
+    fn store_key(&self, _key_id: &[u8; 16], _key_data: &[u8; 32]) -> Result<(), BackendError> {
+        Err(BackendError::UnsupportedOperation(
+            "File backend is read-only".to_string(),
+        ))
+    }
+
+    fn rotate_key(&self, _old_id: &[u8; 16], _new_id: &[u8; 16]) -> Result<(), BackendError> {
+        Err(BackendError::UnsupportedOperation(
+            "File backend does not support key rotation; replace the key file instead".to_string(),
+        ))
+    }
+
+    fn list_keys(&self) -> Result<Vec<KeyMetadata>, BackendError> {
+        Ok(vec![])
+    }
+
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo::file()
+    }
+}
+
+impl UniversalBackend for FileKeyBackend {
+    fn perform_operation(
+        &self,
+        key_id: &str,
+        operation: CryptoOperation,
+    ) -> Result<CryptoResult, BackendError> {
+        match operation {
+            CryptoOperation::DeriveKey { context } => Ok(CryptoResult::DerivedKey(self.derive(
+                key_id.as_bytes(),
+                &context.salt,
+                &context.additional_data,
+                &context.derivation_params(),
+            )?)),
+
+            CryptoOperation::Hash { data, algorithm } => {
+                let hash = match algorithm {
+                    HashAlgorithm::Sha256 => Sha256::digest(&data).to_vec(),
+                    HashAlgorithm::Sha384 => Sha384::digest(&data).to_vec(),
+                    HashAlgorithm::Sha512 => Sha512::digest(&data).to_vec(),
+                };
+                Ok(CryptoResult::Hash(hash))
+            }
+
+            _ => Err(BackendError::UnsupportedOperation(format!(
+                "Operation {:?} not supported by file backend",
+                operation
+            ))),
+        }
+    }
+
+    fn supports_operation(&self, operation: &CryptoOperation) -> bool {
+        matches!(
+            operation,
+            CryptoOperation::DeriveKey { .. } | CryptoOperation::Hash { .. }
+        )
+    }
+
+    fn get_capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            symmetric_algorithms: vec![],
+            asymmetric_algorithms: vec![],
+            signature_algorithms: vec![],
+            hash_algorithms: vec![
+                HashAlgorithm::Sha256,
+                HashAlgorithm::Sha384,
+                HashAlgorithm::Sha512,
+            ],
+            hardware_backed: false,
+            supports_key_derivation: true,
+            supports_key_generation: false,
+            supports_attestation: false,
+            max_key_size: Some(256),
+        }
+    }
+
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo::file()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    const KEY_HEX: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    fn key_file(contents: &str, mode: u32) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(file.path(), std::fs::Permissions::from_mode(mode)).unwrap();
+        }
+        #[cfg(not(unix))]
+        let _ = mode;
+        file
+    }
+
+    #[test]
+    fn test_valid_key_file_derives_isolated_keys() {
+        let file = key_file(&format!("{}\n", KEY_HEX), 0o600);
+        let backend =
+            FileKeyBackend::from_backend_config(&[format!("path={}", file.path().display())])
+                .unwrap();
+        let context = KeyContext::new(vec![7u8; 16]);
+
+        let first = backend.derive_key(&[1u8; 16], &context).unwrap();
+        assert_eq!(first, backend.derive_key(&[1u8; 16], &context).unwrap());
+        assert_ne!(first, backend.derive_key(&[2u8; 16], &context).unwrap());
+
+        // key=value form of the same key derives the same keys
+        let file = key_file(&format!("# master\nkey={}\n", KEY_HEX), 0o600);
+        let same = FileKeyBackend::open(file.path()).unwrap();
+        assert_eq!(first, same.derive_key(&[1u8; 16], &context).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_world_readable_key_file_rejected() {
+        let file = key_file(KEY_HEX, 0o644);
+        let err = FileKeyBackend::open(file.path()).err().unwrap();
+        assert!(err.to_string().contains("chmod 600"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_group_accessible_key_file_rejected() {
+        for mode in [0o640, 0o620, 0o610] {
+            let file = key_file(KEY_HEX, mode);
+            let err = FileKeyBackend::open(file.path()).err().unwrap();
+            assert!(err.to_string().contains("chmod 600"), "mode {:o}", mode);
+        }
+        // Owner-only modes other than 600 are fine
+        let file = key_file(KEY_HEX, 0o400);
+        assert!(FileKeyBackend::open(file.path()).is_ok());
+    }
+
+    #[test]
+    fn test_passphrase_derivation_with_salt() {
+        let file = key_file(
+            "passphrase=correct horse battery staple\nsalt=00112233445566778899aabbccddeeff\n",
+            0o600,
+        );
+        let backend = FileKeyBackend::open(file.path()).unwrap();
+        let key_id = [3u8; 16];
+
+        // Empty context salt falls back to the salt stored in the file
+        let file_salt = backend
+            .derive_key(&key_id, &KeyContext::new(vec![]))
+            .unwrap();
+        let explicit = KeyContext::new(hex::decode("00112233445566778899aabbccddeeff").unwrap());
+        assert_eq!(file_salt, backend.derive_key(&key_id, &explicit).unwrap());
+
+        let other_salt = backend
+            .derive_key(&key_id, &KeyContext::new(vec![9u8; 16]))
+            .unwrap();
+        assert_ne!(file_salt, other_salt);
+
+        assert!(backend
+            .derive_key(&key_id, &KeyContext::new(vec![9u8; 8]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_passphrase_derivation_follows_context_params() {
+        let file = key_file(
+            "passphrase=correct horse battery staple
+salt=00112233445566778899aabbccddeeff
+",
+            0o600,
+        );
+        let backend = FileKeyBackend::open(file.path()).unwrap();
+        let key_id = [3u8; 16];
+        let salt = vec![9u8; 16];
+
+        let pbkdf2 = backend
+            .derive_key(&key_id, &KeyContext::new(salt.clone()))
+            .unwrap();
+        let argon2 = backend
+            .derive_key(
+                &key_id,
+                &KeyContext::new(salt.clone()).with_params(KeyDerivationParams::argon2id(
+                    crate::backends::traits::ARGON2_DEFAULT_MEMORY_KIB,
+                    2,
+                    1,
+                )),
+            )
+            .unwrap();
+        assert_ne!(pbkdf2, argon2);
+
+        // Below-minimum parameters are refused by the shared validation
+        assert!(backend
+            .derive_key(
+                &key_id,
+                &KeyContext::new(salt).with_params(KeyDerivationParams::pbkdf2(1_000))
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_malformed_key_files_rejected() {
+        for contents in [
+            "",
+            "key=abcd",
+            "passphrase=",
+            "token=1234",
+            &format!("key={}\nsalt=00", KEY_HEX),
+            &format!("key={}\npassphrase=x", KEY_HEX),
+        ] {
+            let file = key_file(contents, 0o600);
+            assert!(FileKeyBackend::open(file.path()).is_err(), "{:?}", contents);
+        }
+        assert!(FileKeyBackend::from_backend_config(&[]).is_err());
+        assert!(FileKeyBackend::from_backend_config(&["device=/x".to_string()]).is_err());
+    }
+}
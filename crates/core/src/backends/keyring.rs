@@ -9,15 +9,15 @@
 
 //! Keyring backend implementation
 //!
-//! This backend uses the OS keyring for passphrase storage and PBKDF2 for key derivation.
+//! This backend uses the OS keyring for passphrase storage and the context's
+//! [`KeyDerivationParams`](crate::backends::traits::KeyDerivationParams) for key derivation.
 
 use crate::backends::traits::{BackendInfo, KeyBackend, KeyContext, KeyMetadata};
 use crate::error::BackendError;
 use anyhow::Result;
 use keyring::Entry;
-use pbkdf2::pbkdf2_hmac;
-use sha2::Sha256;
 use std::time::{SystemTime, UNIX_EPOCH};
+use subtle::ConstantTimeEq;
 
 /// Keyring-based key backend using PBKDF2 key derivation
 pub struct KeyringBackend {
@@ -55,6 +55,59 @@ impl KeyringBackend {
         let passphrase = entry.get_password()?;
         Ok(passphrase)
     }
+
+    /// Replace the stored passphrase, provided `old` matches it
+    ///
+    /// The swap is a single keyring write, so the entry holds either the old
+    /// or the new passphrase, never neither. Keys derived from the old
+    /// passphrase do not change; anything encrypted under them must be
+    /// decrypted with the old passphrase and re-encrypted.
+    pub fn rotate_passphrase(&self, old: &str, new: &str) -> Result<()> {
+        anyhow::ensure!(!new.is_empty(), "New passphrase must not be empty");
+        anyhow::ensure!(
+            new != old,
+            "New passphrase must differ from the old passphrase"
+        );
+
+        let entry = Entry::new(&self.service_name, &self.username)?;
+        let stored = entry
+            .get_password()
+            .map_err(|e| anyhow::anyhow!("No passphrase stored in keyring: {}", e))?;
+        // Constant-time, so the check does not leak how much of a guess was right
+        anyhow::ensure!(
+            bool::from(stored.as_bytes().ct_eq(old.as_bytes())),
+            "Old passphrase does not match the stored passphrase"
+        );
+
+        entry.set_password(new)?;
+        Ok(())
+    }
+
+    /// Store the encoded private key of a generated keypair in the OS keyring
+    pub fn store_keypair_secret(&self, key_id: &str, secret: &str) -> Result<()> {
+        let entry = Entry::new(&self.service_name, &self.keypair_username(key_id))?;
+        entry.set_password(secret)?;
+        Ok(())
+    }
+
+    /// Get the encoded private key of a stored keypair from the OS keyring
+    pub fn get_keypair_secret(&self, key_id: &str) -> Result<String> {
+        let entry = Entry::new(&self.service_name, &self.keypair_username(key_id))?;
+        let secret = entry.get_password()?;
+        Ok(secret)
+    }
+
+    /// Remove a stored keypair from the OS keyring
+    pub fn delete_keypair_secret(&self, key_id: &str) -> Result<()> {
+        let entry = Entry::new(&self.service_name, &self.keypair_username(key_id))?;
+        entry.delete_password()?;
+        Ok(())
+    }
+
+    /// Keypairs live beside the passphrase, one keyring entry per key id
+    fn keypair_username(&self, key_id: &str) -> String {
+        format!("{}:keypair:{}", self.username, key_id)
+    }
 }
 
 impl KeyBackend for KeyringBackend {
@@ -75,29 +128,7 @@ impl KeyBackend for KeyringBackend {
             BackendError::OperationFailed(format!("Failed to get passphrase from keyring: {}", e))
         })?;
 
-        // Convert salt to array
-        let mut salt_array = [0u8; 32];
-        salt_array.copy_from_slice(&context.salt);
-
-        // Use PBKDF2 with SHA256 (OWASP 2023 recommended iterations)
-        let iterations = context.iterations.unwrap_or(600_000);
-        if iterations < crate::backends::universal::PBKDF2_MIN_ITERATIONS {
-            return Err(BackendError::OperationFailed(format!(
-                "PBKDF2 iterations {} is below minimum of {}",
-                iterations,
-                crate::backends::universal::PBKDF2_MIN_ITERATIONS,
-            )));
-        }
-        let mut key = [0u8; 32];
-
-        // Include key_id in the derivation for key isolation
-        let mut input = passphrase.as_bytes().to_vec();
-        input.extend_from_slice(key_id);
-        input.extend_from_slice(&context.additional_data);
-
-        pbkdf2_hmac::<Sha256>(&input, &salt_array, iterations, &mut key);
-
-        Ok(key)
+        derive_with_passphrase(&passphrase, key_id, context)
     }
 
     fn store_key(&self, _key_id: &[u8; 16], _key_data: &[u8; 32]) -> Result<(), BackendError> {
@@ -150,6 +181,25 @@ impl KeyBackend for KeyringBackend {
     }
 }
 
+/// Derive a key from `passphrase`; the caller has already checked the salt length
+fn derive_with_passphrase(
+    passphrase: &str,
+    key_id: &[u8; 16],
+    context: &KeyContext,
+) -> Result<[u8; 32], BackendError> {
+    // Convert salt to array
+    let mut salt_array = [0u8; 32];
+    salt_array.copy_from_slice(&context.salt);
+
+    // Include key_id in the derivation for key isolation
+    let mut input = passphrase.as_bytes().to_vec();
+    input.extend_from_slice(key_id);
+    input.extend_from_slice(&context.additional_data);
+
+    // Cost parameters come from the context so decryption can match them
+    context.params.derive(&input, &salt_array)
+}
+
 impl Default for KeyringBackend {
     fn default() -> Self {
         Self::new().expect("Failed to create default keyring backend")
@@ -188,4 +238,90 @@ mod tests {
         let error_msg = result.unwrap_err().to_string();
         assert!(error_msg.contains("Salt must be exactly 32 bytes"));
     }
+
+    fn memory_backend() -> KeyringBackend {
+        crate::backends::keyring_test_store::install();
+        KeyringBackend::new_with_service(
+            "sealedge-test".to_string(),
+            crate::backends::keyring_test_store::unique_username("rotate"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rotate_passphrase_requires_old_passphrase() {
+        let backend = memory_backend();
+        backend.store_passphrase("correct horse").unwrap();
+
+        let err = backend
+            .rotate_passphrase("wrong horse", "battery staple")
+            .unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+        assert_eq!(backend.get_passphrase().unwrap(), "correct horse");
+
+        assert!(backend
+            .rotate_passphrase("correct horse", "correct horse")
+            .is_err());
+        assert!(backend.rotate_passphrase("correct horse", "").is_err());
+
+        backend
+            .rotate_passphrase("correct horse", "battery staple")
+            .unwrap();
+        assert_eq!(backend.get_passphrase().unwrap(), "battery staple");
+
+        // The old passphrase no longer unlocks a second rotation
+        assert!(backend
+            .rotate_passphrase("correct horse", "another one")
+            .is_err());
+    }
+
+    #[test]
+    fn test_rotated_passphrase_derives_a_different_key() {
+        let backend = memory_backend();
+        let key_id = [3u8; 16];
+        let context = KeyContext::new(vec![9u8; 32]);
+
+        backend.store_passphrase("old passphrase").unwrap();
+        let old_key = backend.derive_key(&key_id, &context).unwrap();
+        assert_eq!(
+            old_key,
+            derive_with_passphrase("old passphrase", &key_id, &context).unwrap()
+        );
+
+        backend
+            .rotate_passphrase("old passphrase", "new passphrase")
+            .unwrap();
+        let new_key = backend.derive_key(&key_id, &context).unwrap();
+        assert_ne!(old_key, new_key);
+        assert_eq!(
+            new_key,
+            derive_with_passphrase("new passphrase", &key_id, &context).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_derive_key_with_argon2id_params() {
+        use crate::backends::traits::{
+            KeyDerivationParams, ARGON2_DEFAULT_ITERATIONS, ARGON2_DEFAULT_MEMORY_KIB,
+            ARGON2_DEFAULT_PARALLELISM,
+        };
+
+        let backend = memory_backend();
+        backend.store_passphrase("correct horse").unwrap();
+        let key_id = [4u8; 16];
+        let argon2 = KeyContext::new(vec![9u8; 32]).with_params(KeyDerivationParams::argon2id(
+            ARGON2_DEFAULT_MEMORY_KIB,
+            ARGON2_DEFAULT_ITERATIONS,
+            ARGON2_DEFAULT_PARALLELISM,
+        ));
+
+        let key = backend.derive_key(&key_id, &argon2).unwrap();
+        assert_eq!(backend.derive_key(&key_id, &argon2).unwrap(), key);
+        assert_ne!(
+            backend
+                .derive_key(&key_id, &KeyContext::new(vec![9u8; 32]))
+                .unwrap(),
+            key
+        );
+    }
 }
@@ -0,0 +1,86 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+// GitHub: https://github.com/TrustEdge-Labs/sealedge
+//
+
+//! In-memory keyring credential store for tests
+//!
+//! The keyring crate's default credential builder is process-global, so every
+//! keyring test in the binary must agree on it. [`install`] sets this store exactly
+//! once; tests then keep apart by using a unique username each.
+
+use keyring::credential::{Credential, CredentialApi, CredentialBuilderApi};
+use std::collections::HashMap;
+use std::sync::{Mutex, Once, OnceLock};
+
+/// Route all keyring entries in this test binary to the in-memory store
+pub(crate) fn install() {
+    static INSTALL: Once = Once::new();
+    INSTALL
+        .call_once(|| keyring::set_default_credential_builder(Box::new(MemoryCredentialBuilder)));
+}
+
+/// A username no other test uses, so tests sharing the store never collide
+pub(crate) fn unique_username(prefix: &str) -> String {
+    format!("{}-{}", prefix, hex::encode(rand::random::<[u8; 8]>()))
+}
+
+fn entries() -> &'static Mutex<HashMap<String, String>> {
+    static ENTRIES: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    ENTRIES.get_or_init(Default::default)
+}
+
+struct MemoryCredential(String);
+
+impl CredentialApi for MemoryCredential {
+    fn set_password(&self, password: &str) -> keyring::Result<()> {
+        entries()
+            .lock()
+            .unwrap()
+            .insert(self.0.clone(), password.to_string());
+        Ok(())
+    }
+
+    fn get_password(&self) -> keyring::Result<String> {
+        entries()
+            .lock()
+            .unwrap()
+            .get(&self.0)
+            .cloned()
+            .ok_or(keyring::Error::NoEntry)
+    }
+
+    fn delete_password(&self) -> keyring::Result<()> {
+        entries()
+            .lock()
+            .unwrap()
+            .remove(&self.0)
+            .map(|_| ())
+            .ok_or(keyring::Error::NoEntry)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+struct MemoryCredentialBuilder;
+
+impl CredentialBuilderApi for MemoryCredentialBuilder {
+    fn build(
+        &self,
+        _target: Option<&str>,
+        service: &str,
+        user: &str,
+    ) -> keyring::Result<Box<Credential>> {
+        Ok(Box::new(MemoryCredential(format!("{}/{}", service, user))))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
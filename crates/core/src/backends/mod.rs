@@ -12,17 +12,28 @@
 //! This module provides a pluggable backend system for key management operations.
 //! Currently supports:
 //! - Keyring backend (PBKDF2 with OS keyring)
+//! - File backend (key or passphrase file with permission checks)
 //! - Software HSM backend (file-based key storage)
 //! - Universal backend registry system
+//! - Opt-in derived key caching (`CachingBackend`)
+//! - TPM 2.0 backend (feature-gated: tpm)
+//! - AWS KMS backend (feature-gated: aws-kms)
 //!
 //! Planned backends:
 //! - YubiKey backend (v1.1 rewrite in progress)
-//! - TPM 2.0 backend
 //! - Hardware HSM backend (additional PKCS#11 devices)
 
+#[cfg(feature = "aws-kms")]
+pub mod aws_kms;
+pub mod caching;
+pub mod file;
 #[cfg(feature = "keyring")]
 pub mod keyring;
+#[cfg(all(test, feature = "keyring"))]
+mod keyring_test_store;
 pub mod software_hsm;
+#[cfg(feature = "tpm")]
+pub mod tpm;
 pub mod traits;
 pub mod universal;
 #[cfg(feature = "keyring")]
@@ -31,9 +42,15 @@ pub mod universal_registry;
 #[cfg(feature = "yubikey")]
 pub mod yubikey;
 
+#[cfg(feature = "aws-kms")]
+pub use aws_kms::{AwsKmsBackend, AwsKmsConfig};
+pub use caching::{CachingBackend, DEFAULT_KEY_CACHE_CAPACITY};
+pub use file::FileKeyBackend;
 #[cfg(feature = "keyring")]
 pub use keyring::KeyringBackend;
 pub use software_hsm::SoftwareHsmBackend;
+#[cfg(feature = "tpm")]
+pub use tpm::{TpmBackend, TpmConfig};
 pub use traits::*;
 pub use universal::*;
 #[cfg(feature = "keyring")]
@@ -46,7 +63,8 @@ use anyhow::Result;
 
 /// Backend registry for selecting and instantiating key backends
 pub struct BackendRegistry {
-    // Future: registry of available backends
+    /// Derived key cache size for created backends; `None` disables caching
+    key_cache_capacity: Option<usize>,
 }
 
 impl Default for BackendRegistry {
@@ -57,11 +75,42 @@ impl Default for BackendRegistry {
 
 impl BackendRegistry {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            key_cache_capacity: None,
+        }
+    }
+
+    /// Wrap created backends in a [`CachingBackend`] holding up to `capacity` derived keys
+    ///
+    /// Off by default: cached keys stay in memory until evicted or the backend is dropped.
+    pub fn with_key_cache(mut self, capacity: usize) -> Self {
+        self.key_cache_capacity = Some(capacity);
+        self
     }
 
     /// Create a backend based on CLI arguments or configuration
     pub fn create_backend(&self, backend_type: &str) -> Result<Box<dyn KeyBackend>> {
+        self.create_backend_with_config(backend_type, &[])
+    }
+
+    /// Create a backend with `key=value` settings (e.g. from `--backend-config`)
+    pub fn create_backend_with_config(
+        &self,
+        backend_type: &str,
+        config: &[String],
+    ) -> Result<Box<dyn KeyBackend>> {
+        let backend = self.create_uncached(backend_type, config)?;
+        Ok(match self.key_cache_capacity {
+            Some(capacity) => Box::new(CachingBackend::new(backend, capacity)),
+            None => backend,
+        })
+    }
+
+    fn create_uncached(
+        &self,
+        backend_type: &str,
+        config: &[String],
+    ) -> Result<Box<dyn KeyBackend>> {
         match backend_type {
             #[cfg(feature = "keyring")]
             "keyring" => Ok(Box::new(KeyringBackend::new()?)),
@@ -87,8 +136,22 @@ impl BackendRegistry {
                 ● Get help:\n\
                   sealedge-pubky --help"
             )),
+            "file" => Ok(Box::new(FileKeyBackend::from_backend_config(config)?)),
+            #[cfg(feature = "tpm")]
+            "tpm" => Ok(Box::new(TpmBackend::new(TpmConfig::from_backend_config(
+                config,
+            )?)?)),
+            #[cfg(not(feature = "tpm"))]
+            "tpm" => Err(anyhow::anyhow!(
+                "TPM backend requires the 'tpm' feature. Build with: --features tpm"
+            )),
+            #[cfg(feature = "aws-kms")]
+            "aws-kms" => Ok(Box::new(AwsKmsBackend::from_backend_config(config)?)),
+            #[cfg(not(feature = "aws-kms"))]
+            "aws-kms" => Err(anyhow::anyhow!(
+                "AWS KMS backend requires the 'aws-kms' feature. Build with: --features aws-kms"
+            )),
             // Future backends:
-            // "tpm" => Ok(Box::new(TpmBackend::new(device_path)?)),
             // "hsm" => Ok(Box::new(HsmBackend::new(pkcs11_lib, slot_id)?)),
             // "matter" => Ok(Box::new(MatterBackend::new(fabric_id, cert_path)?)),
             _ => Err(anyhow::anyhow!("Unknown backend type: {}", backend_type)),
@@ -97,18 +160,20 @@ impl BackendRegistry {
 
     /// List available backends on this system
     pub fn list_available_backends(&self) -> Vec<&'static str> {
-        #[cfg(feature = "keyring")]
-        {
-            vec!["keyring"]
-        }
-        #[cfg(not(feature = "keyring"))]
-        {
-            vec![]
+        #[allow(unused_mut)]
+        let mut backends: Vec<&'static str> = if cfg!(feature = "keyring") {
+            vec!["keyring", "file"]
+        } else {
+            vec!["file"]
+        };
+        #[cfg(feature = "tpm")]
+        if TpmBackend::is_available() {
+            backends.push("tpm");
         }
 
         // Note: pubky backend is available via separate sealedge-pubky binary
-        // Future: detect TPM, HSM availability
-        // if tpm_available() { backends.push("tpm"); }
+        // Future: detect HSM availability
         // if hsm_available() { backends.push("hsm"); }
+        backends
     }
 }
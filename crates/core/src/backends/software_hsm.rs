@@ -20,7 +20,7 @@
 //! - Key enumeration and metadata management
 //! - Validates UniversalBackend architecture for hardware integration
 
-use crate::backends::traits::{BackendInfo, KeyMetadata};
+use crate::backends::traits::{BackendInfo, KeyDerivationParams, KeyMetadata};
 use crate::backends::universal::{
     AsymmetricAlgorithm, BackendCapabilities, CryptoOperation, CryptoResult, HashAlgorithm,
     SignatureAlgorithm, UniversalBackend,
@@ -38,7 +38,6 @@ use p256::{
     elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint},
     PublicKey as P256PublicKey, SecretKey as P256SecretKey,
 };
-use pbkdf2::pbkdf2_hmac;
 use rand_core::{OsRng, RngCore};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256, Sha384, Sha512};
@@ -225,13 +224,8 @@ impl SoftwareHsmBackend {
         let mut salt = [0u8; SALT_LEN];
         OsRng.fill_bytes(&mut salt);
 
-        let mut derived_key = [0u8; 32];
-        pbkdf2_hmac::<Sha256>(
-            self.config.default_passphrase().as_bytes(),
-            &salt,
-            PBKDF2_ITERATIONS,
-            &mut derived_key,
-        );
+        let mut derived_key = KeyDerivationParams::pbkdf2(PBKDF2_ITERATIONS)
+            .derive(self.config.default_passphrase().as_bytes(), &salt)?;
 
         let cipher = Aes256Gcm::new_from_slice(&derived_key)
             .map_err(|e| anyhow::anyhow!("Failed to create AES cipher: {}", e))?;
@@ -275,13 +269,8 @@ impl SoftwareHsmBackend {
         let nonce_bytes = &data[SALT_LEN..SALT_LEN + AES_NONCE_LEN];
         let ciphertext = &data[SALT_LEN + AES_NONCE_LEN..];
 
-        let mut derived_key = [0u8; 32];
-        pbkdf2_hmac::<Sha256>(
-            self.config.default_passphrase().as_bytes(),
-            salt,
-            PBKDF2_ITERATIONS,
-            &mut derived_key,
-        );
+        let mut derived_key = KeyDerivationParams::pbkdf2(PBKDF2_ITERATIONS)
+            .derive(self.config.default_passphrase().as_bytes(), salt)?;
 
         let cipher = Aes256Gcm::new_from_slice(&derived_key)
             .map_err(|e| anyhow::anyhow!("Failed to create AES cipher: {}", e))?;
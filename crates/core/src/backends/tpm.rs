@@ -0,0 +1,507 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! TPM 2.0 backend for Universal Backend system
+//!
+//! Hardware-backed key derivation and signing through a TPM 2.0 device (or the
+//! `swtpm`/`mssim` simulators) via the `tss-esapi` crate. Fail-closed like the
+//! YubiKey backend: every security-relevant operation runs inside the TPM and
+//! there are NO software fallbacks.
+//!
+//! ## Keys
+//! Keys are never imported or stored. Each key ID selects a primary key under the
+//! owner hierarchy whose template carries a hash of the ID in its `unique` field,
+//! so the TPM re-derives the same key from its seed on every call and different
+//! IDs give independent keys.
+//!
+//! ## Supported Operations
+//! - Key derivation: HMAC-SHA256 with a TPM-resident keyed-hash key
+//! - ECDSA P-256 signing (SHA-256 digest), DER-encoded signatures
+//! - Public key export (DER SubjectPublicKeyInfo)
+//! - SHA-256/384/512 hashing (software, not security-critical)
+//!
+//! ## Configuration
+//! - `device_path`: TPM character device (default `/dev/tpmrm0`)
+//! - `tcti`: full TCTI string, overrides `device_path`
+//!   (e.g. `mssim:host=localhost,port=2321` for a simulator)
+
+use crate::backends::traits::{BackendInfo, KeyBackend, KeyContext, KeyMetadata};
+use crate::backends::universal::{
+    AsymmetricAlgorithm, BackendCapabilities, CryptoOperation, CryptoResult, HashAlgorithm,
+    SignatureAlgorithm, UniversalBackend,
+};
+use crate::error::BackendError;
+use p256::pkcs8::EncodePublicKey;
+use sha2::{Digest as _, Sha256, Sha384, Sha512};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
+use tss_esapi::attributes::ObjectAttributesBuilder;
+use tss_esapi::constants::tss::{TPM2_RH_NULL, TPM2_ST_HASHCHECK};
+use tss_esapi::handles::KeyHandle;
+use tss_esapi::interface_types::algorithm::{HashingAlgorithm, PublicAlgorithm};
+use tss_esapi::interface_types::ecc::EccCurve;
+use tss_esapi::interface_types::resource_handles::Hierarchy;
+use tss_esapi::structures::{
+    Digest, EccParameter, EccPoint, EccScheme, HashScheme, HashcheckTicket,
+    KeyDerivationFunctionScheme, KeyedHashScheme, MaxBuffer, Public, PublicBuilder,
+    PublicEccParametersBuilder, PublicKeyedHashParameters, Signature, SignatureScheme,
+};
+use tss_esapi::tss2_esys::TPMT_TK_HASHCHECK;
+use tss_esapi::{Context, TctiNameConf};
+
+/// Default TPM resource manager device
+pub const DEFAULT_TPM_DEVICE: &str = "/dev/tpmrm0";
+
+/// Domain separation for derived keys, bumped if the derivation ever changes
+const DERIVE_DOMAIN: &[u8] = b"sealedge-tpm-derive-v1";
+
+/// Configuration for the TPM 2.0 backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TpmConfig {
+    /// TPM character device
+    pub device_path: String,
+    /// Full TCTI configuration string; overrides `device_path` when set
+    pub tcti: Option<String>,
+}
+
+impl Default for TpmConfig {
+    fn default() -> Self {
+        Self {
+            device_path: DEFAULT_TPM_DEVICE.to_string(),
+            tcti: None,
+        }
+    }
+}
+
+impl TpmConfig {
+    /// Build a config from `--backend-config key=value` entries
+    pub fn from_backend_config(entries: &[String]) -> Result<Self, BackendError> {
+        let mut config = Self::default();
+        for entry in entries {
+            let (key, value) = entry.split_once('=').ok_or_else(|| {
+                BackendError::InitializationFailed(format!(
+                    "Invalid TPM backend config '{}' (expected key=value)",
+                    entry
+                ))
+            })?;
+            match key.trim() {
+                "device_path" => config.device_path = value.trim().to_string(),
+                "tcti" => config.tcti = Some(value.trim().to_string()),
+                other => {
+                    return Err(BackendError::InitializationFailed(format!(
+                        "Unknown TPM backend config key '{}' (supported: device_path, tcti)",
+                        other
+                    )))
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    /// TCTI string passed to the TSS
+    pub fn tcti_string(&self) -> String {
+        match &self.tcti {
+            Some(tcti) => tcti.clone(),
+            None => format!("device:{}", self.device_path),
+        }
+    }
+}
+
+/// TPM 2.0 backend
+///
+/// The ESAPI context is not safe for concurrent use, so every operation holds the
+/// mutex for its whole TPM command sequence.
+pub struct TpmBackend {
+    context: Mutex<Context>,
+    config: TpmConfig,
+}
+
+impl TpmBackend {
+    /// Connect to the TPM described by `config`
+    pub fn new(config: TpmConfig) -> Result<Self, BackendError> {
+        let tcti = TctiNameConf::from_str(&config.tcti_string()).map_err(|e| {
+            BackendError::InitializationFailed(format!(
+                "Invalid TPM TCTI '{}': {}",
+                config.tcti_string(),
+                e
+            ))
+        })?;
+        let context = Context::new(tcti).map_err(|e| {
+            BackendError::HardwareError(format!(
+                "Failed to open TPM at '{}': {}",
+                config.tcti_string(),
+                e
+            ))
+        })?;
+        Ok(Self {
+            context: Mutex::new(context),
+            config,
+        })
+    }
+
+    /// Whether a TPM device node exists on this system
+    pub fn is_available() -> bool {
+        Path::new(DEFAULT_TPM_DEVICE).exists() || Path::new("/dev/tpm0").exists()
+    }
+
+    pub fn config(&self) -> &TpmConfig {
+        &self.config
+    }
+
+    /// HMAC-SHA256 of `message` under the TPM-resident key for `key_id`
+    fn tpm_hmac(&self, key_id: &[u8], message: &[u8]) -> Result<[u8; 32], BackendError> {
+        let template = hmac_key_template(key_id)?;
+        // MaxBuffer is limited to 1024 bytes, so the TPM HMACs a fixed-size digest
+        let digest = Sha256::digest(message);
+        let buffer = MaxBuffer::try_from(digest.to_vec()).map_err(tpm_error)?;
+
+        let mut context = self.lock()?;
+        let output = context.execute_with_nullauth_session(|ctx| {
+            let key = ctx
+                .create_primary(Hierarchy::Owner, template, None, None, None, None)?
+                .key_handle;
+            let result = ctx.hmac(key.into(), buffer, HashingAlgorithm::Sha256);
+            ctx.flush_context(key.into())?;
+            result
+        });
+        let output = output.map_err(tpm_error)?;
+
+        output.value().try_into().map_err(|_| {
+            BackendError::HardwareError("TPM returned an HMAC of unexpected size".to_string())
+        })
+    }
+
+    /// Create the ECDSA P-256 primary key for `key_id` and run `f` with it
+    fn with_signing_key<T>(
+        &self,
+        key_id: &str,
+        f: impl FnOnce(&mut Context, KeyHandle, &Public) -> tss_esapi::Result<T>,
+    ) -> Result<T, BackendError> {
+        let template = ecc_signing_key_template(key_id.as_bytes())?;
+        let mut context = self.lock()?;
+        context
+            .execute_with_nullauth_session(|ctx| {
+                let primary =
+                    ctx.create_primary(Hierarchy::Owner, template, None, None, None, None)?;
+                let result = f(ctx, primary.key_handle, &primary.out_public);
+                ctx.flush_context(primary.key_handle.into())?;
+                result
+            })
+            .map_err(tpm_error)
+    }
+
+    fn tpm_sign(&self, key_id: &str, data: &[u8]) -> Result<Vec<u8>, BackendError> {
+        let digest = Digest::try_from(Sha256::digest(data).to_vec()).map_err(tpm_error)?;
+        let signature = self.with_signing_key(key_id, |ctx, key, _| {
+            ctx.sign(key, digest, SignatureScheme::Null, null_hashcheck_ticket()?)
+        })?;
+
+        let Signature::EcDsa(ecc) = signature else {
+            return Err(BackendError::HardwareError(
+                "TPM returned a non-ECDSA signature".to_string(),
+            ));
+        };
+        let signature = p256::ecdsa::Signature::from_scalars(
+            scalar_bytes(ecc.signature_r().value())?,
+            scalar_bytes(ecc.signature_s().value())?,
+        )
+        .map_err(|e| BackendError::HardwareError(format!("Invalid TPM signature: {}", e)))?;
+        Ok(signature.to_der().as_bytes().to_vec())
+    }
+
+    fn tpm_public_key(&self, key_id: &str) -> Result<Vec<u8>, BackendError> {
+        let public = self.with_signing_key(key_id, |_, _, public| Ok(public.clone()))?;
+        let Public::Ecc { unique, .. } = public else {
+            return Err(BackendError::HardwareError(
+                "TPM returned a non-ECC public key".to_string(),
+            ));
+        };
+
+        let x = scalar_bytes(unique.x().value())?;
+        let y = scalar_bytes(unique.y().value())?;
+        let point = p256::EncodedPoint::from_affine_coordinates(&x.into(), &y.into(), false);
+        let key = p256::PublicKey::from_sec1_bytes(point.as_bytes())
+            .map_err(|e| BackendError::HardwareError(format!("Invalid TPM public key: {}", e)))?;
+        let der = key
+            .to_public_key_der()
+            .map_err(|e| BackendError::OperationFailed(format!("SPKI encoding failed: {}", e)))?;
+        Ok(der.as_bytes().to_vec())
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Context>, BackendError> {
+        self.context
+            .lock()
+            .map_err(|_| BackendError::HardwareError("TPM context lock poisoned".to_string()))
+    }
+}
+
+/// Keyed-hash HMAC key template whose `unique` field is bound to `key_id`
+fn hmac_key_template(key_id: &[u8]) -> Result<Public, BackendError> {
+    let attributes = ObjectAttributesBuilder::new()
+        .with_fixed_tpm(true)
+        .with_fixed_parent(true)
+        .with_sensitive_data_origin(true)
+        .with_user_with_auth(true)
+        .with_sign_encrypt(true)
+        .build()
+        .map_err(tpm_error)?;
+
+    PublicBuilder::new()
+        .with_public_algorithm(PublicAlgorithm::KeyedHash)
+        .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+        .with_object_attributes(attributes)
+        .with_keyed_hash_parameters(PublicKeyedHashParameters::new(
+            KeyedHashScheme::HMAC_SHA_256,
+        ))
+        .with_keyed_hash_unique_identifier(unique_digest(b"hmac", key_id)?)
+        .build()
+        .map_err(tpm_error)
+}
+
+/// Unrestricted ECDSA P-256 signing key template whose `unique` field is bound to `key_id`
+fn ecc_signing_key_template(key_id: &[u8]) -> Result<Public, BackendError> {
+    let attributes = ObjectAttributesBuilder::new()
+        .with_fixed_tpm(true)
+        .with_fixed_parent(true)
+        .with_sensitive_data_origin(true)
+        .with_user_with_auth(true)
+        .with_sign_encrypt(true)
+        .build()
+        .map_err(tpm_error)?;
+    let parameters = PublicEccParametersBuilder::new()
+        .with_ecc_scheme(EccScheme::EcDsa(HashScheme::new(HashingAlgorithm::Sha256)))
+        .with_curve(EccCurve::NistP256)
+        .with_is_signing_key(true)
+        .with_is_decryption_key(false)
+        .with_restricted(false)
+        .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+        .build()
+        .map_err(tpm_error)?;
+    let unique_x =
+        EccParameter::try_from(unique_digest(b"ecdsa-p256", key_id)?.value()).map_err(tpm_error)?;
+
+    PublicBuilder::new()
+        .with_public_algorithm(PublicAlgorithm::Ecc)
+        .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+        .with_object_attributes(attributes)
+        .with_ecc_parameters(parameters)
+        .with_ecc_unique_identifier(EccPoint::new(unique_x, EccParameter::default()))
+        .build()
+        .map_err(tpm_error)
+}
+
+fn unique_digest(kind: &[u8], key_id: &[u8]) -> Result<Digest, BackendError> {
+    let mut hasher = Sha256::new();
+    hasher.update(b"sealedge-tpm-key:");
+    hasher.update(kind);
+    hasher.update(b":");
+    hasher.update(key_id);
+    Digest::try_from(hasher.finalize().to_vec()).map_err(tpm_error)
+}
+
+/// Hashcheck ticket for signing an externally computed digest with an unrestricted key
+fn null_hashcheck_ticket() -> tss_esapi::Result<HashcheckTicket> {
+    HashcheckTicket::try_from(TPMT_TK_HASHCHECK {
+        tag: TPM2_ST_HASHCHECK,
+        hierarchy: TPM2_RH_NULL,
+        digest: Default::default(),
+    })
+}
+
+/// Left-pad a big-endian TPM integer to a P-256 field element
+fn scalar_bytes(value: &[u8]) -> Result<[u8; 32], BackendError> {
+    if value.len() > 32 {
+        return Err(BackendError::HardwareError(format!(
+            "TPM returned a {}-byte P-256 value",
+            value.len()
+        )));
+    }
+    let mut out = [0u8; 32];
+    out[32 - value.len()..].copy_from_slice(value);
+    Ok(out)
+}
+
+/// Input to the TPM HMAC for a derivation context
+fn derivation_message(salt: &[u8], additional_data: &[u8]) -> Vec<u8> {
+    let mut message =
+        Vec::with_capacity(DERIVE_DOMAIN.len() + 8 + salt.len() + additional_data.len());
+    message.extend_from_slice(DERIVE_DOMAIN);
+    message.extend_from_slice(&(salt.len() as u32).to_be_bytes());
+    message.extend_from_slice(salt);
+    message.extend_from_slice(&(additional_data.len() as u32).to_be_bytes());
+    message.extend_from_slice(additional_data);
+    message
+}
+
+fn tpm_error(e: tss_esapi::Error) -> BackendError {
+    BackendError::HardwareError(format!("TPM operation failed: {}", e))
+}
+
+impl UniversalBackend for TpmBackend {
+    fn perform_operation(
+        &self,
+        key_id: &str,
+        operation: CryptoOperation,
+    ) -> Result<CryptoResult, BackendError> {
+        match operation {
+            CryptoOperation::DeriveKey { context } => {
+                let message = derivation_message(&context.salt, &context.additional_data);
+                Ok(CryptoResult::DerivedKey(
+                    self.tpm_hmac(key_id.as_bytes(), &message)?,
+                ))
+            }
+
+            CryptoOperation::Sign {
+                data,
+                algorithm: SignatureAlgorithm::EcdsaP256,
+            } => Ok(CryptoResult::Signed(self.tpm_sign(key_id, &data)?)),
+
+            CryptoOperation::GetPublicKey => {
+                Ok(CryptoResult::PublicKey(self.tpm_public_key(key_id)?))
+            }
+
+            CryptoOperation::Hash { data, algorithm } => {
+                // Hash operations can use software (not security-critical)
+                let hash = match algorithm {
+                    HashAlgorithm::Sha256 => Sha256::digest(&data).to_vec(),
+                    HashAlgorithm::Sha384 => Sha384::digest(&data).to_vec(),
+                    HashAlgorithm::Sha512 => Sha512::digest(&data).to_vec(),
+                };
+                Ok(CryptoResult::Hash(hash))
+            }
+
+            _ => Err(BackendError::UnsupportedOperation(format!(
+                "Operation {:?} not supported by TPM backend",
+                operation
+            ))),
+        }
+    }
+
+    fn supports_operation(&self, operation: &CryptoOperation) -> bool {
+        match operation {
+            CryptoOperation::DeriveKey { .. } | CryptoOperation::GetPublicKey => true,
+            CryptoOperation::Sign { algorithm, .. } => {
+                matches!(algorithm, SignatureAlgorithm::EcdsaP256)
+            }
+            CryptoOperation::Hash { .. } => true,
+            _ => false,
+        }
+    }
+
+    fn get_capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            symmetric_algorithms: vec![],
+            asymmetric_algorithms: vec![AsymmetricAlgorithm::EcdsaP256],
+            signature_algorithms: vec![SignatureAlgorithm::EcdsaP256],
+            hash_algorithms: vec![
+                HashAlgorithm::Sha256,
+                HashAlgorithm::Sha384,
+                HashAlgorithm::Sha512,
+            ],
+            hardware_backed: true,
+            supports_key_derivation: true,
+            // Keys are re-derived from the TPM seed per key ID, not generated and stored
+            supports_key_generation: false,
+            supports_attestation: false,
+            max_key_size: Some(256),
+        }
+    }
+
+    fn backend_info(&self) -> BackendInfo {
+        tpm_backend_info()
+    }
+}
+
+impl KeyBackend for TpmBackend {
+    fn derive_key(
+        &self,
+        key_id: &[u8; 16],
+        context: &KeyContext,
+    ) -> Result<[u8; 32], BackendError> {
+        let message = derivation_message(&context.salt, &context.additional_data);
+        self.tpm_hmac(key_id, &message)
+    }
+
+    fn store_key(&self, _key_id: &[u8; 16], _key_data: &[u8; 32]) -> Result<(), BackendError> {
+        Err(BackendError::UnsupportedOperation(
+            "TPM backend derives keys in hardware and does not import key material".to_string(),
+        ))
+    }
+
+    fn rotate_key(&self, _old_id: &[u8; 16], _new_id: &[u8; 16]) -> Result<(), BackendError> {
+        Err(BackendError::UnsupportedOperation(
+            "TPM keys are bound to their key ID; derive with a new ID instead".to_string(),
+        ))
+    }
+
+    fn list_keys(&self) -> Result<Vec<KeyMetadata>, BackendError> {
+        // Keys are re-derived on demand, nothing is persisted to enumerate
+        Ok(vec![])
+    }
+
+    fn backend_info(&self) -> BackendInfo {
+        tpm_backend_info()
+    }
+}
+
+fn tpm_backend_info() -> BackendInfo {
+    BackendInfo {
+        available: true,
+        config_requirements: vec!["device_path (optional)", "tcti (optional)"],
+        ..BackendInfo::tpm()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_defaults_to_resource_manager() {
+        let config = TpmConfig::from_backend_config(&[]).unwrap();
+        assert_eq!(config.device_path, DEFAULT_TPM_DEVICE);
+        assert_eq!(config.tcti_string(), "device:/dev/tpmrm0");
+    }
+
+    #[test]
+    fn test_config_device_path_and_tcti() {
+        let config =
+            TpmConfig::from_backend_config(&["device_path=/dev/tpm0".to_string()]).unwrap();
+        assert_eq!(config.tcti_string(), "device:/dev/tpm0");
+
+        let config = TpmConfig::from_backend_config(&[
+            "device_path=/dev/tpm0".to_string(),
+            "tcti=mssim:host=localhost,port=2321".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(config.tcti_string(), "mssim:host=localhost,port=2321");
+    }
+
+    #[test]
+    fn test_config_rejects_unknown_and_malformed_entries() {
+        assert!(TpmConfig::from_backend_config(&["key_handle=0x81000001".to_string()]).is_err());
+        assert!(TpmConfig::from_backend_config(&["device_path".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_derivation_message_is_unambiguous() {
+        assert_ne!(
+            derivation_message(b"ab", b"c"),
+            derivation_message(b"a", b"bc")
+        );
+    }
+
+    #[test]
+    fn test_scalar_bytes_left_pads() {
+        let padded = scalar_bytes(&[1, 2, 3]).unwrap();
+        assert_eq!(&padded[29..], &[1, 2, 3]);
+        assert!(padded[..29].iter().all(|b| *b == 0));
+        assert!(scalar_bytes(&[0u8; 33]).is_err());
+    }
+}
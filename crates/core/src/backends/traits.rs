@@ -12,7 +12,9 @@
 //! This module defines the core traits that all key management backends must implement.
 
 use crate::error::BackendError;
+use pbkdf2::pbkdf2_hmac;
 use serde::{Deserialize, Serialize};
+use sha2::{Sha256, Sha384, Sha512};
 
 /// Core trait that all key management backends must implement
 pub trait KeyBackend: Send + Sync {
@@ -43,15 +45,169 @@ pub trait KeyBackend: Send + Sync {
     }
 }
 
+/// Password hashing algorithm for passphrase-based backends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyDerivationAlgorithm {
+    Pbkdf2Sha256,
+    Pbkdf2Sha384,
+    Pbkdf2Sha512,
+    Argon2id,
+}
+
+/// OWASP 2023 recommended PBKDF2-SHA256 iterations
+pub const PBKDF2_DEFAULT_ITERATIONS: u32 = 600_000;
+/// OWASP 2023 recommended Argon2id memory cost (19 MiB)
+pub const ARGON2_DEFAULT_MEMORY_KIB: u32 = 19 * 1024;
+/// OWASP 2023 recommended Argon2id time cost
+pub const ARGON2_DEFAULT_ITERATIONS: u32 = 2;
+/// OWASP 2023 recommended Argon2id parallelism
+pub const ARGON2_DEFAULT_PARALLELISM: u32 = 1;
+
+/// Cost parameters for turning a passphrase into a key
+///
+/// Store these next to anything encrypted with the derived key (the type is
+/// serializable, as is [`KeyContext`]): decryption must derive with the same
+/// values, and the defaults may be raised in later releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyDerivationParams {
+    pub algorithm: KeyDerivationAlgorithm,
+    /// PBKDF2 iteration count, or Argon2 time cost
+    pub iterations: u32,
+    /// Argon2 memory cost in KiB; unused by PBKDF2
+    pub memory_kib: u32,
+    /// Argon2 lanes; unused by PBKDF2
+    pub parallelism: u32,
+}
+
+impl KeyDerivationParams {
+    /// PBKDF2-SHA256 with the given iteration count
+    pub fn pbkdf2(iterations: u32) -> Self {
+        Self {
+            algorithm: KeyDerivationAlgorithm::Pbkdf2Sha256,
+            iterations,
+            memory_kib: 0,
+            parallelism: 1,
+        }
+    }
+
+    /// Argon2id with the given memory cost, time cost and lanes
+    pub fn argon2id(memory_kib: u32, iterations: u32, parallelism: u32) -> Self {
+        Self {
+            algorithm: KeyDerivationAlgorithm::Argon2id,
+            iterations,
+            memory_kib,
+            parallelism,
+        }
+    }
+
+    /// Reject parameters below the OWASP minimums
+    pub fn validate(&self) -> Result<(), BackendError> {
+        let invalid = |message: String| Err(BackendError::OperationFailed(message));
+        match self.algorithm {
+            KeyDerivationAlgorithm::Pbkdf2Sha256
+            | KeyDerivationAlgorithm::Pbkdf2Sha384
+            | KeyDerivationAlgorithm::Pbkdf2Sha512 => {
+                if self.iterations < crate::backends::universal::PBKDF2_MIN_ITERATIONS {
+                    return invalid(format!(
+                        "PBKDF2 iterations {} is below minimum of {}",
+                        self.iterations,
+                        crate::backends::universal::PBKDF2_MIN_ITERATIONS,
+                    ));
+                }
+            }
+            KeyDerivationAlgorithm::Argon2id => {
+                if self.memory_kib < ARGON2_DEFAULT_MEMORY_KIB {
+                    return invalid(format!(
+                        "Argon2 memory {} KiB is below minimum of {} KiB",
+                        self.memory_kib, ARGON2_DEFAULT_MEMORY_KIB,
+                    ));
+                }
+                if self.iterations == 0 || self.parallelism == 0 {
+                    return invalid(
+                        "Argon2 iterations and parallelism must be at least 1".to_string(),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Derive a 32-byte key from `secret` and `salt` with these parameters
+    ///
+    /// Every passphrase-based backend derives through here, so the recorded
+    /// parameters alone decide the key.
+    pub fn derive(&self, secret: &[u8], salt: &[u8]) -> Result<[u8; 32], BackendError> {
+        self.validate()?;
+        let mut key = [0u8; 32];
+        match self.algorithm {
+            KeyDerivationAlgorithm::Pbkdf2Sha256 => {
+                pbkdf2_hmac::<Sha256>(secret, salt, self.iterations, &mut key);
+            }
+            KeyDerivationAlgorithm::Pbkdf2Sha384 => {
+                pbkdf2_hmac::<Sha384>(secret, salt, self.iterations, &mut key);
+            }
+            KeyDerivationAlgorithm::Pbkdf2Sha512 => {
+                pbkdf2_hmac::<Sha512>(secret, salt, self.iterations, &mut key);
+            }
+            KeyDerivationAlgorithm::Argon2id => {
+                let failed = |e: argon2::Error| {
+                    BackendError::OperationFailed(format!("Argon2id key derivation failed: {}", e))
+                };
+                let params =
+                    argon2::Params::new(self.memory_kib, self.iterations, self.parallelism, None)
+                        .map_err(failed)?;
+                argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+                    .hash_password_into(secret, salt, &mut key)
+                    .map_err(failed)?;
+            }
+        }
+        Ok(key)
+    }
+}
+
+impl Default for KeyDerivationParams {
+    fn default() -> Self {
+        Self::pbkdf2(PBKDF2_DEFAULT_ITERATIONS)
+    }
+}
+
 /// Context information for key derivation
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "KeyContextRecord")]
 pub struct KeyContext {
     /// Salt for key derivation (backend-specific interpretation)
     pub salt: Vec<u8>,
     /// Additional context data (e.g., device ID, session info)
     pub additional_data: Vec<u8>,
-    /// Iteration count for PBKDF2-like backends
-    pub iterations: Option<u32>,
+    /// Cost parameters for passphrase-based backends
+    pub params: KeyDerivationParams,
+}
+
+/// Serialized [`KeyContext`], including the `iterations` field written before
+/// [`KeyDerivationParams`] existed
+#[derive(Deserialize)]
+struct KeyContextRecord {
+    salt: Vec<u8>,
+    additional_data: Vec<u8>,
+    #[serde(default)]
+    params: Option<KeyDerivationParams>,
+    #[serde(default)]
+    iterations: Option<u32>,
+}
+
+impl From<KeyContextRecord> for KeyContext {
+    fn from(record: KeyContextRecord) -> Self {
+        let params = match (record.params, record.iterations) {
+            (Some(params), _) => params,
+            (None, Some(iterations)) => KeyDerivationParams::pbkdf2(iterations),
+            (None, None) => KeyDerivationParams::default(),
+        };
+        Self {
+            salt: record.salt,
+            additional_data: record.additional_data,
+            params,
+        }
+    }
 }
 
 impl KeyContext {
@@ -59,7 +215,7 @@ impl KeyContext {
         Self {
             salt,
             additional_data: Vec::new(),
-            iterations: Some(600_000), // OWASP 2023 recommended PBKDF2 iterations
+            params: KeyDerivationParams::default(),
         }
     }
 
@@ -68,6 +224,7 @@ impl KeyContext {
         self
     }
 
+    /// Use PBKDF2-SHA256 with `iterations`; [`KeyContext::with_params`] selects any algorithm
     pub fn with_iterations(mut self, iterations: u32) -> Self {
         assert!(
             iterations >= crate::backends::universal::PBKDF2_MIN_ITERATIONS,
@@ -75,9 +232,20 @@ impl KeyContext {
             crate::backends::universal::PBKDF2_MIN_ITERATIONS,
             iterations,
         );
-        self.iterations = Some(iterations);
+        self.params = KeyDerivationParams::pbkdf2(iterations);
+        self
+    }
+
+    pub fn with_params(mut self, params: KeyDerivationParams) -> Self {
+        self.params = params;
         self
     }
+
+    /// PBKDF2 iteration count (or Argon2 time cost), formerly the `iterations` field
+    #[deprecated(note = "read `params.iterations`, which sits next to the algorithm it applies to")]
+    pub fn iterations(&self) -> u32 {
+        self.params.iterations
+    }
 }
 
 /// Metadata about a stored key
@@ -121,6 +289,16 @@ impl BackendInfo {
         }
     }
 
+    pub fn file() -> Self {
+        Self {
+            name: "file",
+            description: "Key file on disk with HKDF, PBKDF2 or Argon2id key derivation",
+            version: "1.0.0",
+            available: true,
+            config_requirements: vec!["path"],
+        }
+    }
+
     pub fn tpm() -> Self {
         Self {
             name: "tpm",
@@ -131,6 +309,16 @@ impl BackendInfo {
         }
     }
 
+    pub fn aws_kms() -> Self {
+        Self {
+            name: "aws-kms",
+            description: "AWS KMS key derivation, signing and key wrapping",
+            version: "1.0.0",
+            available: true,
+            config_requirements: vec!["key_arn", "region (optional)", "endpoint_url (optional)"],
+        }
+    }
+
     pub fn hsm() -> Self {
         Self {
             name: "hsm",
@@ -141,3 +329,148 @@ impl BackendInfo {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_different_params_produce_different_keys() {
+        let secret = b"correct horse battery staple";
+        let salt = [7u8; 32];
+
+        let default_key = KeyDerivationParams::default()
+            .derive(secret, &salt)
+            .unwrap();
+        let stronger_key = KeyDerivationParams::pbkdf2(PBKDF2_DEFAULT_ITERATIONS + 1)
+            .derive(secret, &salt)
+            .unwrap();
+        assert_ne!(default_key, stronger_key);
+
+        // Same params, same key
+        assert_eq!(
+            KeyDerivationParams::default()
+                .derive(secret, &salt)
+                .unwrap(),
+            default_key
+        );
+    }
+
+    #[test]
+    fn test_recorded_params_round_trip() {
+        use aes_gcm::aead::{Aead, KeyInit};
+        use aes_gcm::{Aes256Gcm, Key, Nonce};
+
+        let secret = b"correct horse battery staple";
+        let context = KeyContext::new(vec![3u8; 32]).with_params(KeyDerivationParams::pbkdf2(
+            PBKDF2_DEFAULT_ITERATIONS + 1000,
+        ));
+        let nonce = Nonce::from_slice(&[0u8; 12]);
+
+        let key = context.params.derive(secret, &context.salt).unwrap();
+        let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+            .encrypt(nonce, b"payload".as_ref())
+            .unwrap();
+
+        // Decrypt from the recorded context, not the defaults
+        let recorded = serde_json::to_string(&context).unwrap();
+        let restored: KeyContext = serde_json::from_str(&recorded).unwrap();
+        assert_eq!(restored.params, context.params);
+        let key = restored.params.derive(secret, &restored.salt).unwrap();
+        let plaintext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+            .decrypt(nonce, ciphertext.as_ref())
+            .unwrap();
+        assert_eq!(plaintext, b"payload");
+
+        // Deriving with the defaults instead would not decrypt
+        let default_key = KeyDerivationParams::default()
+            .derive(secret, &restored.salt)
+            .unwrap();
+        assert!(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&default_key))
+            .decrypt(nonce, ciphertext.as_ref())
+            .is_err());
+    }
+
+    #[test]
+    fn test_weak_or_unsupported_params_are_rejected() {
+        let salt = [1u8; 32];
+        assert!(KeyDerivationParams::pbkdf2(1000)
+            .derive(b"secret", &salt)
+            .is_err());
+        assert!(KeyDerivationParams::argon2id(1024, 2, 1)
+            .validate()
+            .is_err());
+
+        let argon2 = KeyDerivationParams::argon2id(
+            ARGON2_DEFAULT_MEMORY_KIB,
+            ARGON2_DEFAULT_ITERATIONS,
+            ARGON2_DEFAULT_PARALLELISM,
+        );
+        assert!(argon2.validate().is_ok());
+        assert!(argon2.derive(b"secret", &[1u8; 4]).is_err());
+    }
+
+    #[test]
+    fn test_argon2id_derivation() {
+        let secret = b"correct horse battery staple";
+        let salt = [5u8; 32];
+        let params = KeyDerivationParams::argon2id(
+            ARGON2_DEFAULT_MEMORY_KIB,
+            ARGON2_DEFAULT_ITERATIONS,
+            ARGON2_DEFAULT_PARALLELISM,
+        );
+
+        let key = params.derive(secret, &salt).unwrap();
+        assert_eq!(params.derive(secret, &salt).unwrap(), key);
+
+        // Matches the reference implementation with the same cost parameters
+        let mut expected = [0u8; 32];
+        argon2::Argon2::new(
+            argon2::Algorithm::Argon2id,
+            argon2::Version::V0x13,
+            argon2::Params::new(
+                ARGON2_DEFAULT_MEMORY_KIB,
+                ARGON2_DEFAULT_ITERATIONS,
+                ARGON2_DEFAULT_PARALLELISM,
+                None,
+            )
+            .unwrap(),
+        )
+        .hash_password_into(secret, &salt, &mut expected)
+        .unwrap();
+        assert_eq!(key, expected);
+
+        // Each cost parameter and the algorithm itself change the key
+        let more_memory = KeyDerivationParams::argon2id(
+            ARGON2_DEFAULT_MEMORY_KIB * 2,
+            ARGON2_DEFAULT_ITERATIONS,
+            ARGON2_DEFAULT_PARALLELISM,
+        );
+        assert_ne!(more_memory.derive(secret, &salt).unwrap(), key);
+        assert_ne!(
+            KeyDerivationParams::default()
+                .derive(secret, &salt)
+                .unwrap(),
+            key
+        );
+    }
+
+    #[test]
+    fn test_legacy_iterations_field_is_honored() {
+        let legacy = r#"{"salt":[1,2],"additional_data":[],"iterations":700000}"#;
+        let context: KeyContext = serde_json::from_str(legacy).unwrap();
+        assert_eq!(context.params, KeyDerivationParams::pbkdf2(700_000));
+
+        let unset = r#"{"salt":[1,2],"additional_data":[],"iterations":null}"#;
+        let context: KeyContext = serde_json::from_str(unset).unwrap();
+        assert_eq!(context.params, KeyDerivationParams::default());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_iterations_accessor() {
+        let context = KeyContext::new(vec![0u8; 32]).with_iterations(700_000);
+        assert_eq!(context.iterations(), 700_000);
+        assert_eq!(context.params, KeyDerivationParams::pbkdf2(700_000));
+    }
+}
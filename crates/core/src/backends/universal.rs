@@ -11,7 +11,12 @@
 //! This module defines a unified, capability-based approach to cryptographic backends.
 //! Instead of a monolithic trait with many methods, backends implement a single
 //! `perform_operation` method and advertise their capabilities through `supports_operation`.
+//!
+//! Backends that talk to hardware or the network also implement
+//! [`AsyncUniversalBackend`], so async servers can use them without nesting runtimes.
+//! Purely synchronous backends are adapted with [`SyncBackendAdapter`].
 
+use crate::backends::traits::{KeyDerivationAlgorithm, KeyDerivationParams};
 use crate::error::BackendError;
 use serde::{Deserialize, Serialize};
 
@@ -31,6 +36,8 @@ pub enum AsymmetricAlgorithm {
     EcdsaP256,
     Rsa2048,
     Rsa4096,
+    /// Key agreement only; cannot sign
+    X25519,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -59,6 +66,9 @@ pub struct KeyDerivationContext {
     pub iterations: Option<u32>,
     /// Hash algorithm to use for derivation
     pub hash_algorithm: Option<HashAlgorithm>,
+    /// Full cost parameters; when set they replace `iterations` and `hash_algorithm`
+    #[serde(default)]
+    pub params: Option<KeyDerivationParams>,
 }
 
 impl KeyDerivationContext {
@@ -68,6 +78,7 @@ impl KeyDerivationContext {
             additional_data: Vec::new(),
             iterations: Some(600_000), // OWASP 2023 recommended PBKDF2 iterations
             hash_algorithm: Some(HashAlgorithm::Sha256),
+            params: None,
         }
     }
 
@@ -91,6 +102,34 @@ impl KeyDerivationContext {
         self.hash_algorithm = Some(algorithm);
         self
     }
+
+    /// Derive with `params`, e.g. [`KeyDerivationParams::argon2id`]
+    pub fn with_params(mut self, params: KeyDerivationParams) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    /// The parameters passphrase backends derive with
+    ///
+    /// Explicit `params` win; otherwise PBKDF2 with `hash_algorithm` (default
+    /// SHA-256) and `iterations` (default 600,000).
+    pub fn derivation_params(&self) -> KeyDerivationParams {
+        if let Some(params) = self.params {
+            return params;
+        }
+        let algorithm = match self.hash_algorithm {
+            None | Some(HashAlgorithm::Sha256) => KeyDerivationAlgorithm::Pbkdf2Sha256,
+            Some(HashAlgorithm::Sha384) => KeyDerivationAlgorithm::Pbkdf2Sha384,
+            Some(HashAlgorithm::Sha512) => KeyDerivationAlgorithm::Pbkdf2Sha512,
+        };
+        KeyDerivationParams {
+            algorithm,
+            ..KeyDerivationParams::pbkdf2(
+                self.iterations
+                    .unwrap_or(crate::backends::traits::PBKDF2_DEFAULT_ITERATIONS),
+            )
+        }
+    }
 }
 
 /// Operations that can be performed by cryptographic backends
@@ -257,6 +296,67 @@ pub trait UniversalBackend: Send + Sync {
     }
 }
 
+/// Async counterpart of [`UniversalBackend`] for callers already running inside Tokio
+///
+/// Network and hardware backends cannot service [`UniversalBackend::perform_operation`]
+/// from within a runtime without nesting `block_on`; they implement this trait so async
+/// callers can await the operation instead. Capability and metadata queries stay on the
+/// synchronous supertrait.
+#[async_trait::async_trait]
+pub trait AsyncUniversalBackend: UniversalBackend {
+    /// Perform a cryptographic operation without blocking the current runtime
+    async fn perform_operation_async(
+        &self,
+        key_id: &str,
+        operation: CryptoOperation,
+    ) -> Result<CryptoResult, BackendError>;
+}
+
+/// Adapts any synchronous [`UniversalBackend`] to [`AsyncUniversalBackend`]
+///
+/// The blanket impl lives on this wrapper rather than on every `UniversalBackend`, so
+/// backends with a native async path (which implement both traits) keep their own impl.
+/// Operations run inline on the calling task, which suits in-memory and local-file
+/// backends.
+pub struct SyncBackendAdapter<B: ?Sized>(pub Box<B>);
+
+impl<B: UniversalBackend + ?Sized> UniversalBackend for SyncBackendAdapter<B> {
+    fn perform_operation(
+        &self,
+        key_id: &str,
+        operation: CryptoOperation,
+    ) -> Result<CryptoResult, BackendError> {
+        self.0.perform_operation(key_id, operation)
+    }
+
+    fn supports_operation(&self, operation: &CryptoOperation) -> bool {
+        self.0.supports_operation(operation)
+    }
+
+    fn get_capabilities(&self) -> BackendCapabilities {
+        self.0.get_capabilities()
+    }
+
+    fn backend_info(&self) -> crate::backends::traits::BackendInfo {
+        self.0.backend_info()
+    }
+
+    fn list_keys(&self) -> Result<Vec<crate::backends::traits::KeyMetadata>, BackendError> {
+        self.0.list_keys()
+    }
+}
+
+#[async_trait::async_trait]
+impl<B: UniversalBackend + ?Sized> AsyncUniversalBackend for SyncBackendAdapter<B> {
+    async fn perform_operation_async(
+        &self,
+        key_id: &str,
+        operation: CryptoOperation,
+    ) -> Result<CryptoResult, BackendError> {
+        self.0.perform_operation(key_id, operation)
+    }
+}
+
 /// Helper function to check if an operation type is supported by backend capabilities
 ///
 /// This function provides a quick way to check if a backend with the given capabilities
@@ -310,6 +410,21 @@ mod tests {
         assert_eq!(context.hash_algorithm, Some(HashAlgorithm::Sha512));
     }
 
+    #[test]
+    fn test_key_derivation_context_params() {
+        let context = KeyDerivationContext::new(vec![1; 32])
+            .with_iterations(700_000)
+            .with_hash_algorithm(HashAlgorithm::Sha512);
+        let params = context.derivation_params();
+        assert_eq!(params.algorithm, KeyDerivationAlgorithm::Pbkdf2Sha512);
+        assert_eq!(params.iterations, 700_000);
+
+        // Explicit params replace the PBKDF2 fields
+        let argon2 =
+            KeyDerivationParams::argon2id(crate::backends::traits::ARGON2_DEFAULT_MEMORY_KIB, 2, 1);
+        assert_eq!(context.with_params(argon2).derivation_params(), argon2);
+    }
+
     #[test]
     #[should_panic(expected = "PBKDF2 iterations must be at least 300000")]
     fn test_key_derivation_context_rejects_low_iterations() {
@@ -9,21 +9,24 @@
 //! Universal keyring backend implementation
 //!
 //! This module implements the UniversalBackend trait for the OS keyring,
-//! supporting key derivation and hash operations.
+//! supporting key derivation, hash operations, and Ed25519/X25519 keypairs.
+//!
+//! Generated keypairs are stored as one keyring entry per key id, holding
+//! `<algorithm>:<hex private key>`; public keys are recomputed on demand.
 
 use crate::backends::keyring::KeyringBackend;
 use crate::backends::traits::{BackendInfo, KeyMetadata};
 use crate::backends::universal::*;
 use crate::error::BackendError;
 use anyhow::{anyhow, Result};
-use pbkdf2::pbkdf2_hmac;
-use sha2::Sha256;
+use zeroize::Zeroizing;
 
 /// Universal backend wrapper for KeyringBackend
 ///
 /// UniversalKeyringBackend implements the UniversalBackend trait for OS keyring
 /// operations, providing key derivation and hash operations using the system
-/// keyring for secure passphrase storage.
+/// keyring for secure passphrase storage, plus Ed25519 and X25519 keypairs
+/// whose private keys are kept in the keyring.
 pub struct UniversalKeyringBackend {
     inner: KeyringBackend,
 }
@@ -53,6 +56,99 @@ impl UniversalKeyringBackend {
         self.inner.get_passphrase()
     }
 
+    /// Replace the stored passphrase, provided `old` matches it
+    pub fn rotate_passphrase(&self, old: &str, new: &str) -> Result<()> {
+        self.inner.rotate_passphrase(old, new)
+    }
+
+    /// Generate an Ed25519 or X25519 keypair and store it under `key_id`
+    ///
+    /// Returns the public key bytes. An existing keypair under the same id is replaced.
+    pub fn generate_keypair(
+        &self,
+        key_id: &str,
+        algorithm: AsymmetricAlgorithm,
+    ) -> Result<Vec<u8>, BackendError> {
+        let tag = keypair_tag(algorithm)?;
+        let key_pair = crate::asymmetric::KeyPair::generate(algorithm)
+            .map_err(|e| BackendError::OperationFailed(format!("Key generation failed: {}", e)))?;
+
+        let secret = Zeroizing::new(format!(
+            "{}:{}",
+            tag,
+            hex::encode(key_pair.private.as_bytes())
+        ));
+        self.inner
+            .store_keypair_secret(key_id, &secret)
+            .map_err(|e| {
+                BackendError::OperationFailed(format!("Failed to store keypair in keyring: {}", e))
+            })?;
+
+        Ok(key_pair.public.key_bytes)
+    }
+
+    /// Remove the keypair stored under `key_id`
+    pub fn delete_keypair(&self, key_id: &str) -> Result<(), BackendError> {
+        self.inner
+            .delete_keypair_secret(key_id)
+            .map_err(|_| BackendError::KeyNotFound(key_id.to_string()))
+    }
+
+    /// Load the keypair stored under `key_id` as its algorithm and private key bytes
+    fn load_keypair(
+        &self,
+        key_id: &str,
+    ) -> Result<(AsymmetricAlgorithm, Zeroizing<[u8; 32]>), BackendError> {
+        let secret = Zeroizing::new(
+            self.inner
+                .get_keypair_secret(key_id)
+                .map_err(|_| BackendError::KeyNotFound(key_id.to_string()))?,
+        );
+        let invalid =
+            || BackendError::OperationFailed(format!("Stored keypair {} is malformed", key_id));
+
+        let (tag, encoded) = secret.split_once(':').ok_or_else(invalid)?;
+        let algorithm = match tag {
+            "ed25519" => AsymmetricAlgorithm::Ed25519,
+            "x25519" => AsymmetricAlgorithm::X25519,
+            _ => return Err(invalid()),
+        };
+        let mut private_key = Zeroizing::new([0u8; 32]);
+        hex::decode_to_slice(encoded, private_key.as_mut()).map_err(|_| invalid())?;
+
+        Ok((algorithm, private_key))
+    }
+
+    /// Public key of the keypair stored under `key_id`
+    fn public_key(&self, key_id: &str) -> Result<Vec<u8>, BackendError> {
+        let (algorithm, private_key) = self.load_keypair(key_id)?;
+        Ok(match algorithm {
+            AsymmetricAlgorithm::X25519 => {
+                let secret = x25519_dalek::StaticSecret::from(*private_key);
+                x25519_dalek::PublicKey::from(&secret).to_bytes().to_vec()
+            }
+            _ => ed25519_dalek::SigningKey::from_bytes(&private_key)
+                .verifying_key()
+                .to_bytes()
+                .to_vec(),
+        })
+    }
+
+    /// Sign `data` with the Ed25519 keypair stored under `key_id`
+    fn sign_ed25519(&self, key_id: &str, data: &[u8]) -> Result<Vec<u8>, BackendError> {
+        use ed25519_dalek::Signer;
+
+        let (algorithm, private_key) = self.load_keypair(key_id)?;
+        if algorithm != AsymmetricAlgorithm::Ed25519 {
+            return Err(BackendError::UnsupportedOperation(format!(
+                "Key {} is an {:?} key and cannot sign",
+                key_id, algorithm
+            )));
+        }
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&private_key);
+        Ok(signing_key.sign(data).to_bytes().to_vec())
+    }
+
     /// Derive a key using the specified context
     fn derive_key_internal(
         &self,
@@ -73,40 +169,28 @@ impl UniversalKeyringBackend {
         let mut salt_array = [0u8; 32];
         salt_array.copy_from_slice(&context.salt);
 
-        // Use PBKDF2 with the specified hash algorithm (OWASP 2023 recommended iterations)
-        let iterations = context.iterations.unwrap_or(600_000);
-        if iterations < crate::backends::universal::PBKDF2_MIN_ITERATIONS {
-            return Err(anyhow!(
-                "PBKDF2 iterations {} is below minimum of {}",
-                iterations,
-                crate::backends::universal::PBKDF2_MIN_ITERATIONS,
-            ));
-        }
-        let mut key = [0u8; 32];
-
         // Include key_id in the derivation for key isolation
-        let mut input = passphrase.as_bytes().to_vec();
+        let mut input = Zeroizing::new(passphrase.as_bytes().to_vec());
         input.extend_from_slice(key_id.as_bytes());
         input.extend_from_slice(&context.additional_data);
 
-        // Use the hash algorithm from context or default to SHA-256
-        match context
-            .hash_algorithm
-            .as_ref()
-            .unwrap_or(&HashAlgorithm::Sha256)
-        {
-            HashAlgorithm::Sha256 => {
-                pbkdf2_hmac::<Sha256>(&input, &salt_array, iterations, &mut key);
-            }
-            HashAlgorithm::Sha384 => {
-                pbkdf2_hmac::<sha2::Sha384>(&input, &salt_array, iterations, &mut key);
-            }
-            HashAlgorithm::Sha512 => {
-                pbkdf2_hmac::<sha2::Sha512>(&input, &salt_array, iterations, &mut key);
-            }
-        }
+        // PBKDF2 (with the context's hash and iterations) or Argon2id
+        context
+            .derivation_params()
+            .derive(&input, &salt_array)
+            .map_err(|e| anyhow!("{}", e))
+    }
+}
 
-        Ok(key)
+/// Algorithm tag stored in front of a keypair's private key
+fn keypair_tag(algorithm: AsymmetricAlgorithm) -> Result<&'static str, BackendError> {
+    match algorithm {
+        AsymmetricAlgorithm::Ed25519 => Ok("ed25519"),
+        AsymmetricAlgorithm::X25519 => Ok("x25519"),
+        _ => Err(BackendError::UnsupportedOperation(format!(
+            "Keyring backend generates Ed25519 or X25519 keypairs, not {:?}",
+            algorithm
+        ))),
     }
 }
 
@@ -158,6 +242,21 @@ impl UniversalBackend for UniversalKeyringBackend {
                 ))),
             },
 
+            CryptoOperation::GenerateKeyPair { algorithm } => {
+                let public_key = self.generate_keypair(key_id, algorithm)?;
+                Ok(CryptoResult::KeyPair {
+                    public_key,
+                    private_key_id: key_id.to_string(),
+                })
+            }
+
+            CryptoOperation::GetPublicKey => Ok(CryptoResult::PublicKey(self.public_key(key_id)?)),
+
+            CryptoOperation::Sign {
+                data,
+                algorithm: SignatureAlgorithm::Ed25519,
+            } => Ok(CryptoResult::Signed(self.sign_ed25519(key_id, &data)?)),
+
             CryptoOperation::Hash { data, algorithm } => match algorithm {
                 HashAlgorithm::Sha256 => {
                     use sha2::{Digest, Sha256};
@@ -187,6 +286,14 @@ impl UniversalBackend for UniversalKeyringBackend {
         matches!(
             operation,
             CryptoOperation::DeriveKey { .. }
+                | CryptoOperation::GenerateKeyPair {
+                    algorithm: AsymmetricAlgorithm::Ed25519 | AsymmetricAlgorithm::X25519
+                }
+                | CryptoOperation::GetPublicKey
+                | CryptoOperation::Sign {
+                    algorithm: SignatureAlgorithm::Ed25519,
+                    ..
+                }
                 | CryptoOperation::Hash {
                     algorithm: HashAlgorithm::Sha256
                         | HashAlgorithm::Sha384
@@ -199,8 +306,8 @@ impl UniversalBackend for UniversalKeyringBackend {
     fn get_capabilities(&self) -> BackendCapabilities {
         BackendCapabilities {
             symmetric_algorithms: vec![], // We support AES-256-GCM but need better key management
-            asymmetric_algorithms: vec![],
-            signature_algorithms: vec![],
+            asymmetric_algorithms: vec![AsymmetricAlgorithm::Ed25519, AsymmetricAlgorithm::X25519],
+            signature_algorithms: vec![SignatureAlgorithm::Ed25519],
             hash_algorithms: vec![
                 HashAlgorithm::Sha256,
                 HashAlgorithm::Sha384,
@@ -208,7 +315,7 @@ impl UniversalBackend for UniversalKeyringBackend {
             ],
             hardware_backed: false,
             supports_key_derivation: true,
-            supports_key_generation: false,
+            supports_key_generation: true,
             supports_attestation: false,
             max_key_size: None,
         }
@@ -247,7 +354,7 @@ mod tests {
         let capabilities = backend.get_capabilities();
 
         assert!(capabilities.supports_key_derivation);
-        assert!(!capabilities.supports_key_generation);
+        assert!(capabilities.supports_key_generation);
         assert!(!capabilities.supports_attestation);
         assert!(!capabilities.hardware_backed);
         assert!(capabilities
@@ -272,12 +379,17 @@ mod tests {
         };
         assert!(backend.supports_operation(&hash_op));
 
-        // Should not support signing
+        // Should support Ed25519 signing with generated keys, but not ECDSA
         let sign_op = CryptoOperation::Sign {
             data: vec![1, 2, 3],
             algorithm: SignatureAlgorithm::Ed25519,
         };
-        assert!(!backend.supports_operation(&sign_op));
+        assert!(backend.supports_operation(&sign_op));
+        let ecdsa_op = CryptoOperation::Sign {
+            data: vec![1, 2, 3],
+            algorithm: SignatureAlgorithm::EcdsaP256,
+        };
+        assert!(!backend.supports_operation(&ecdsa_op));
 
         // Should not support attestation
         let attest_op = CryptoOperation::Attest {
@@ -337,4 +449,98 @@ mod tests {
     fn test_pbkdf2_minimum_iterations_rejected() {
         let _context = KeyDerivationContext::new(vec![1; 32]).with_iterations(1000);
     }
+
+    fn keypair_backend() -> UniversalKeyringBackend {
+        crate::backends::keyring_test_store::install();
+        UniversalKeyringBackend::new_with_service(
+            "sealedge-test".to_string(),
+            crate::backends::keyring_test_store::unique_username("keygen"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_generate_ed25519_keypair_sign_and_verify() {
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+        let backend = keypair_backend();
+        let result = backend
+            .perform_operation(
+                "signing_key",
+                CryptoOperation::GenerateKeyPair {
+                    algorithm: AsymmetricAlgorithm::Ed25519,
+                },
+            )
+            .unwrap();
+        let public_key = match result {
+            CryptoResult::KeyPair {
+                public_key,
+                private_key_id,
+            } => {
+                assert_eq!(private_key_id, "signing_key");
+                public_key
+            }
+            other => panic!("Expected CryptoResult::KeyPair, got {:?}", other),
+        };
+
+        let message = b"firmware manifest";
+        let signature = match backend
+            .perform_operation(
+                "signing_key",
+                CryptoOperation::Sign {
+                    data: message.to_vec(),
+                    algorithm: SignatureAlgorithm::Ed25519,
+                },
+            )
+            .unwrap()
+        {
+            CryptoResult::Signed(signature) => signature,
+            other => panic!("Expected CryptoResult::Signed, got {:?}", other),
+        };
+
+        let verifying_key = VerifyingKey::from_bytes(&public_key.try_into().unwrap()).unwrap();
+        let signature = Signature::from_slice(&signature).unwrap();
+        assert!(verifying_key.verify(message, &signature).is_ok());
+        assert!(verifying_key.verify(b"tampered", &signature).is_err());
+
+        backend.delete_keypair("signing_key").unwrap();
+        assert!(matches!(
+            backend.perform_operation("signing_key", CryptoOperation::GetPublicKey),
+            Err(BackendError::KeyNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_generate_x25519_keypair() {
+        let backend = keypair_backend();
+        let public_key = backend
+            .generate_keypair("agreement_key", AsymmetricAlgorithm::X25519)
+            .unwrap();
+        assert_eq!(public_key.len(), 32);
+
+        match backend
+            .perform_operation("agreement_key", CryptoOperation::GetPublicKey)
+            .unwrap()
+        {
+            CryptoResult::PublicKey(stored) => assert_eq!(stored, public_key),
+            other => panic!("Expected CryptoResult::PublicKey, got {:?}", other),
+        }
+
+        // X25519 keys are for key agreement only
+        let sign = backend.perform_operation(
+            "agreement_key",
+            CryptoOperation::Sign {
+                data: vec![1, 2, 3],
+                algorithm: SignatureAlgorithm::Ed25519,
+            },
+        );
+        assert!(matches!(sign, Err(BackendError::UnsupportedOperation(_))));
+
+        // Only Ed25519 and X25519 can be generated
+        assert!(backend
+            .generate_keypair("rsa_key", AsymmetricAlgorithm::Rsa2048)
+            .is_err());
+
+        backend.delete_keypair("agreement_key").unwrap();
+    }
 }
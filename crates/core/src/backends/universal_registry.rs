@@ -25,8 +25,12 @@ use std::collections::HashMap;
 /// The UniversalBackendRegistry provides centralized management of cryptographic
 /// backends, allowing applications to discover capabilities and select appropriate
 /// backends for specific operations at runtime.
+///
+/// Synchronous backends are stored behind a [`SyncBackendAdapter`], so every registered
+/// backend can be looked up either as a [`UniversalBackend`] or as an
+/// [`AsyncUniversalBackend`].
 pub struct UniversalBackendRegistry {
-    backends: HashMap<String, Box<dyn UniversalBackend>>,
+    backends: HashMap<String, Box<dyn AsyncUniversalBackend>>,
 }
 
 impl UniversalBackendRegistry {
@@ -74,11 +78,27 @@ impl UniversalBackendRegistry {
 
     /// Register a backend with the registry
     pub fn register_backend(&mut self, name: String, backend: Box<dyn UniversalBackend>) {
+        self.backends
+            .insert(name, Box::new(SyncBackendAdapter(backend)));
+    }
+
+    /// Register a backend with a native async path
+    pub fn register_async_backend(
+        &mut self,
+        name: String,
+        backend: Box<dyn AsyncUniversalBackend>,
+    ) {
         self.backends.insert(name, backend);
     }
 
     /// Get a backend by name
     pub fn get_backend(&self, name: &str) -> Option<&dyn UniversalBackend> {
+        self.get_async_backend(name)
+            .map(|b| b as &dyn UniversalBackend)
+    }
+
+    /// Get a backend by name for use from async code
+    pub fn get_async_backend(&self, name: &str) -> Option<&dyn AsyncUniversalBackend> {
         self.backends.get(name).map(|b| b.as_ref())
     }
 
@@ -90,7 +110,7 @@ impl UniversalBackendRegistry {
         self.backends
             .values()
             .find(|backend| backend.supports_operation(operation))
-            .map(|b| b.as_ref())
+            .map(|b| b.as_ref() as &dyn UniversalBackend)
     }
 
     /// Find all backends that support a specific operation
@@ -101,7 +121,7 @@ impl UniversalBackendRegistry {
         self.backends
             .iter()
             .filter(|(_, backend)| backend.supports_operation(operation))
-            .map(|(name, backend)| (name.as_str(), backend.as_ref()))
+            .map(|(name, backend)| (name.as_str(), backend.as_ref() as &dyn UniversalBackend))
             .collect()
     }
 
@@ -124,6 +144,16 @@ impl UniversalBackendRegistry {
         operation: &CryptoOperation,
         preferences: &BackendPreferences,
     ) -> Option<&dyn UniversalBackend> {
+        self.find_preferred_async_backend(operation, preferences)
+            .map(|b| b as &dyn UniversalBackend)
+    }
+
+    /// Find the best backend for an operation based on preferences, for use from async code
+    pub fn find_preferred_async_backend(
+        &self,
+        operation: &CryptoOperation,
+        preferences: &BackendPreferences,
+    ) -> Option<&dyn AsyncUniversalBackend> {
         let mut candidates: Vec<_> = self
             .backends
             .iter()
@@ -163,7 +193,7 @@ impl UniversalBackendRegistry {
         if let Some((_, backend)) = candidates.first() {
             // Get the backend name to look it up again
             let backend_name = backend.backend_info().name;
-            self.get_backend(backend_name)
+            self.get_async_backend(backend_name)
         } else {
             None
         }
@@ -190,6 +220,31 @@ impl UniversalBackendRegistry {
             ))),
         }
     }
+
+    /// Perform an operation using the best available backend, awaiting async backends
+    pub async fn perform_operation_async(
+        &self,
+        key_id: &str,
+        operation: CryptoOperation,
+        preferences: Option<&BackendPreferences>,
+    ) -> Result<CryptoResult, BackendError> {
+        let backend = match preferences {
+            Some(prefs) => self.find_preferred_async_backend(&operation, prefs),
+            None => self
+                .backends
+                .values()
+                .find(|backend| backend.supports_operation(&operation))
+                .map(|b| b.as_ref()),
+        };
+
+        match backend {
+            Some(backend) => backend.perform_operation_async(key_id, operation).await,
+            None => Err(BackendError::UnsupportedOperation(format!(
+                "No backend supports the requested operation: {:?}",
+                operation
+            ))),
+        }
+    }
 }
 
 impl Default for UniversalBackendRegistry {
@@ -341,4 +396,103 @@ mod tests {
             panic!("Expected hash result");
         }
     }
+
+    /// Backend whose keys live behind an async lock, like a network directory
+    struct MockAsyncBackend {
+        keys: tokio::sync::RwLock<HashMap<String, Vec<u8>>>,
+    }
+
+    impl UniversalBackend for MockAsyncBackend {
+        fn perform_operation(
+            &self,
+            _key_id: &str,
+            _operation: CryptoOperation,
+        ) -> Result<CryptoResult, BackendError> {
+            Err(BackendError::UnsupportedOperation(
+                "MockAsyncBackend is async-only".to_string(),
+            ))
+        }
+
+        fn supports_operation(&self, operation: &CryptoOperation) -> bool {
+            matches!(operation, CryptoOperation::GetPublicKey)
+        }
+
+        fn get_capabilities(&self) -> BackendCapabilities {
+            BackendCapabilities::software_only()
+        }
+
+        fn backend_info(&self) -> crate::backends::traits::BackendInfo {
+            crate::backends::traits::BackendInfo {
+                name: "mock_async",
+                description: "Mock async backend",
+                version: "test",
+                available: true,
+                config_requirements: vec![],
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl AsyncUniversalBackend for MockAsyncBackend {
+        async fn perform_operation_async(
+            &self,
+            key_id: &str,
+            operation: CryptoOperation,
+        ) -> Result<CryptoResult, BackendError> {
+            match operation {
+                CryptoOperation::GetPublicKey => {
+                    tokio::task::yield_now().await;
+                    self.keys
+                        .read()
+                        .await
+                        .get(key_id)
+                        .cloned()
+                        .map(CryptoResult::PublicKey)
+                        .ok_or_else(|| BackendError::KeyNotFound(key_id.to_string()))
+                }
+                _ => Err(BackendError::UnsupportedOperation(format!(
+                    "{:?}",
+                    operation
+                ))),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_backend_resolves_key_inside_runtime() {
+        let mut registry = UniversalBackendRegistry::with_defaults().unwrap();
+        registry.register_async_backend(
+            "mock_async".to_string(),
+            Box::new(MockAsyncBackend {
+                keys: tokio::sync::RwLock::new(HashMap::from([(
+                    "alice".to_string(),
+                    vec![7u8; 32],
+                )])),
+            }),
+        );
+
+        // Native async path, awaited on the test runtime without a nested block_on
+        let backend = registry.get_async_backend("mock_async").unwrap();
+        match backend
+            .perform_operation_async("alice", CryptoOperation::GetPublicKey)
+            .await
+        {
+            Ok(CryptoResult::PublicKey(key)) => assert_eq!(key, vec![7u8; 32]),
+            other => panic!("Expected public key, got {:?}", other),
+        }
+
+        // Sync backends are reachable through the same async interface
+        let hash_op = CryptoOperation::Hash {
+            data: b"test data".to_vec(),
+            algorithm: HashAlgorithm::Sha256,
+        };
+        let result = registry
+            .perform_operation_async("test_key", hash_op, None)
+            .await;
+        assert!(matches!(result, Ok(CryptoResult::Hash(hash)) if hash.len() == 32));
+
+        // Both kinds are also visible through the sync view
+        assert!(registry.get_backend("mock_async").is_some());
+        assert!(registry.get_backend("software_hsm").is_some());
+    }
 }
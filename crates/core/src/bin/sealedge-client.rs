@@ -21,8 +21,8 @@ use tokio_util::codec::{Framed, LengthDelimitedCodec};
 use sealedge_core::KeyringBackend;
 use sealedge_core::{
     auth::{client_authenticate, load_server_cert, save_client_cert, ClientCertificate},
-    build_aad, FileHeader, KeyBackend, KeyContext, Manifest, NetworkChunk, SignedManifest,
-    NONCE_LEN, VERSION,
+    build_aad, ChunkCompression, FileHeader, KeyBackend, KeyContext, Manifest, NetworkChunk,
+    SignedManifest, NONCE_LEN, VERSION,
 };
 
 // --- Cryptograph ---
@@ -72,6 +72,10 @@ struct Args {
     #[arg(long, default_value_t = 4096)]
     chunk_size: usize,
 
+    /// Zstd-compress file chunks before encryption (announced per chunk in the manifest)
+    #[arg(long)]
+    compress: bool,
+
     /// AES-256 key as hex (64 chars) - if not provided, generate or use keyring
     #[arg(long)]
     key_hex: Option<String>,
@@ -333,6 +337,7 @@ async fn main() -> Result<()> {
                 file_path,
                 &key_bytes,
                 args.chunk_size,
+                args.compress,
                 args.verbose,
                 args.expect_secure_acks,
             )
@@ -353,6 +358,7 @@ async fn main() -> Result<()> {
                 file_path,
                 &key_bytes,
                 args.chunk_size,
+                args.compress,
                 args.verbose,
             )
             .await?;
@@ -372,6 +378,7 @@ async fn send_encrypted_file(
     file_path: &PathBuf,
     key_bytes: &[u8; 32],
     chunk_size: usize,
+    compress: bool,
     verbose: bool,
 ) -> Result<()> {
     println!("Encrypting and sending file: {:?}", file_path);
@@ -401,10 +408,11 @@ async fn send_encrypted_file(
         let nonce_bytes = make_nonce(nonce_prefix, sequence);
         let nonce = (&nonce_bytes).into();
 
-        // Build manifest
+        // Build manifest; chunk_len and pt_hash describe the uncompressed chunk
         let pt_hash = blake3::hash(&buffer[..bytes_read]);
+        let (compression, body) = chunk_compression(compress).compress(&buffer[..bytes_read])?;
         let manifest = Manifest {
-            v: 1,
+            v: compression.manifest_version(),
             ts_ms: now_ms(),
             seq: sequence,
             header_hash,
@@ -439,7 +447,7 @@ async fn send_encrypted_file(
             .encrypt(
                 nonce,
                 Payload {
-                    msg: &buffer[..bytes_read],
+                    msg: &body,
                     aad: &aad,
                 },
             )
@@ -590,6 +598,14 @@ fn now_ms() -> u64 {
         .as_millis() as u64
 }
 
+fn chunk_compression(compress: bool) -> ChunkCompression {
+    if compress {
+        ChunkCompression::Zstd
+    } else {
+        ChunkCompression::None
+    }
+}
+
 fn make_nonce(prefix: [u8; 4], seq: u64) -> [u8; NONCE_LEN] {
     let mut nonce_bytes = [0u8; NONCE_LEN];
     nonce_bytes[..4].copy_from_slice(&prefix);
@@ -791,6 +807,7 @@ async fn send_encrypted_file_hardened(
     file_path: &std::path::Path,
     key_bytes: &[u8; 32],
     chunk_size: usize,
+    compress: bool,
     verbose: bool,
     expect_secure_acks: bool,
 ) -> Result<()> {
@@ -829,8 +846,9 @@ async fn send_encrypted_file_hardened(
         let nonce = (&nonce_bytes).into();
 
         let pt_hash = blake3::hash(pt);
+        let (compression, body) = chunk_compression(compress).compress(pt)?;
         let manifest = Manifest {
-            v: 1,
+            v: compression.manifest_version(),
             ts_ms: now_ms(),
             seq,
             header_hash,
@@ -860,7 +878,13 @@ async fn send_encrypted_file_hardened(
             manifest.chunk_len,
         );
         let ciphertext = cipher
-            .encrypt(nonce, Payload { msg: pt, aad: &aad })
+            .encrypt(
+                nonce,
+                Payload {
+                    msg: &body,
+                    aad: &aad,
+                },
+            )
             .map_err(|_| anyhow::anyhow!("AES-GCM encrypt failed"))?;
 
         let chunk = NetworkChunk::new(seq, ciphertext, bincode::serialize(&sm)?, nonce_bytes);
@@ -6,10 +6,8 @@
 // Project: sealedge — Privacy and trust at the edge.
 //
 
-/// Genesis seed for the continuity chain
-const GENESIS_SEED: &[u8] = b"sealedge:genesis";
-
 pub use crate::error::ChainError;
+pub use sealedge_primitives::chain::{chain_next, genesis, segment_hash};
 
 use base64::engine::general_purpose::STANDARD as BASE64;
 use base64::Engine as _;
@@ -19,24 +17,6 @@ pub fn blake3_hex_or_b64(bytes: &[u8]) -> String {
     format!("b3:{}", BASE64.encode(bytes))
 }
 
-/// Compute BLAKE3 hash of segment ciphertext
-pub fn segment_hash(ciphertext: &[u8]) -> [u8; 32] {
-    blake3::hash(ciphertext).into()
-}
-
-/// Compute next continuity chain hash: BLAKE3(prev||curr)
-pub fn chain_next(prev: &[u8; 32], curr: &[u8; 32]) -> [u8; 32] {
-    let mut hasher = blake3::Hasher::new();
-    hasher.update(prev);
-    hasher.update(curr);
-    hasher.finalize().into()
-}
-
-/// Compute genesis continuity hash: BLAKE3(GENESIS_SEED)
-pub fn genesis() -> [u8; 32] {
-    blake3::hash(GENESIS_SEED).into()
-}
-
 /// Segment with stored hash and continuity values
 #[derive(Debug, Clone)]
 pub struct ChainSegment {
@@ -46,9 +26,12 @@ pub struct ChainSegment {
 }
 
 /// Validate continuity chain for ordered segments
+///
+/// The first segment's continuity hash must chain from [`genesis`]. An empty
+/// chain is reported as [`ChainError::Empty`] rather than passing vacuously.
 pub fn validate_chain(segments: &[ChainSegment]) -> Result<(), ChainError> {
     if segments.is_empty() {
-        return Ok(());
+        return Err(ChainError::Empty);
     }
 
     // Check for gaps in segment indices
@@ -84,7 +67,7 @@ mod tests {
     #[test]
     fn test_genesis_computation() {
         let computed_genesis = genesis();
-        let expected = blake3::hash(GENESIS_SEED);
+        let expected = blake3::hash(b"sealedge:genesis");
         assert_eq!(computed_genesis, *expected.as_bytes());
     }
 
@@ -271,8 +254,29 @@ mod tests {
 
     #[test]
     fn test_empty_segments() {
-        // Empty segments should validate successfully
-        assert!(validate_chain(&[]).is_ok());
+        // An empty chain is its own state, not a vacuous pass
+        assert!(matches!(validate_chain(&[]), Err(ChainError::Empty)));
+    }
+
+    #[test]
+    fn test_single_segment_chains_from_genesis() {
+        let hash = segment_hash(b"only_segment");
+        let segment = ChainSegment {
+            index: 0,
+            stored_hash: hash,
+            stored_continuity: chain_next(&genesis(), &hash),
+        };
+        assert!(validate_chain(std::slice::from_ref(&segment)).is_ok());
+
+        // The one continuity hash must be derived from genesis, not stand alone
+        let unchained = ChainSegment {
+            stored_continuity: hash,
+            ..segment
+        };
+        assert!(matches!(
+            validate_chain(&[unchained]),
+            Err(ChainError::OutOfOrder { .. })
+        ));
     }
 
     #[test]
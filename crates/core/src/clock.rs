@@ -0,0 +1,86 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Clock sources for timestamp creation and validation.
+//!
+//! Code that stamps or checks times takes a [`Clock`] through its
+//! `_with_clock` variant; the plain variant uses [`SystemClock`]. Tests pass a
+//! [`MockClock`] to pin the current time and hit boundaries exactly.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub use sealedge_primitives::Clock;
+
+/// The system wall clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_secs(&self) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+}
+
+/// A clock that only moves when told to
+#[derive(Debug, Default)]
+pub struct MockClock {
+    now: AtomicU64,
+}
+
+impl MockClock {
+    pub fn new(now_secs: u64) -> Self {
+        Self {
+            now: AtomicU64::new(now_secs),
+        }
+    }
+
+    pub fn set(&self, now_secs: u64) {
+        self.now.store(now_secs, Ordering::SeqCst);
+    }
+
+    pub fn advance(&self, secs: u64) {
+        self.now.fetch_add(secs, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now_secs(&self) -> u64 {
+        self.now.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NetworkChunk;
+
+    #[test]
+    fn test_mock_clock_set_and_advance() {
+        let clock = MockClock::new(1_000);
+        clock.advance(5);
+        assert_eq!(clock.now_secs(), 1_005);
+        clock.set(42);
+        assert_eq!(clock.now_secs(), 42);
+    }
+
+    #[test]
+    fn test_network_chunk_future_timestamp_boundary() {
+        let clock = MockClock::new(1_700_000_000);
+        let mut chunk = NetworkChunk::new_with_clock(0, vec![1], vec![2], [0u8; 12], &clock);
+        assert_eq!(chunk.timestamp, 1_700_000_000);
+
+        chunk.timestamp = clock.now_secs() + 300;
+        assert!(chunk.validate_with_clock(&clock).is_ok());
+
+        chunk.timestamp = clock.now_secs() + 301;
+        assert!(chunk.validate_with_clock(&clock).is_err());
+    }
+}
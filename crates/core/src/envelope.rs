@@ -7,31 +7,60 @@
 //! This module provides a clean, simple interface over the complex NetworkChunk/Record system.
 //! Think of it as the driver interface that hides the engine complexity.
 
+use crate::clock::{Clock, SystemClock};
+use crate::error::EnvelopeError;
 use crate::format::{build_aad, AeadAlgorithm, HashAlgorithm, SignatureAlgorithm, SignedManifest};
 use crate::{NetworkChunk, NONCE_LEN};
 use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use blake3;
-use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
-use hkdf::Hkdf;
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+use sealedge_primitives::envelope::{
+    chunk_nonce, derive_shared_encryption_key, derive_x25519_encryption_key, sign_manifest,
+    verify_manifest, ChunkAead, ChunkCipher,
+};
 use serde::{Deserialize, Serialize};
-use sha2::Sha256;
+use std::borrow::Cow;
+use std::io::Read;
+use subtle::ConstantTimeEq;
 use zeroize::Zeroize;
 
+pub mod stream;
+
 /// The chunk size to use when breaking up large payloads
 const DEFAULT_CHUNK_SIZE: usize = 64 * 1024; // 64KB chunks
 
-/// Maximum chunk index that fits in the 3-byte nonce field (2^24 - 1).
-const MAX_CHUNK_INDEX: u64 = 16_777_215;
+/// BLAKE3 key-derivation context for the HKDF salt of [`Envelope::seal_deterministic`]
+const DETERMINISTIC_SALT_CONTEXT: &str = "SEALEDGE_DETERMINISTIC_SALT_V1";
+
+/// Armor label for envelopes exchanged over text-only channels
+const PEM_LABEL: &str = "SEALEDGE ENVELOPE";
+
+/// Base64 characters per line inside the PEM armor
+const PEM_LINE_WIDTH: usize = 64;
+
+/// Layout written before envelopes carried extended metadata and recipient keys
+const LEGACY_ENVELOPE_VERSION: u8 = 2;
+
+/// Layout written by every `seal_*` constructor
+const ENVELOPE_VERSION: u8 = 3;
 
 /// A high-level envelope that wraps and secures arbitrary payloads
 ///
 /// This is the "steering wheel" - a simple interface that hides the complexity
 /// of NetworkChunks, Records, manifests, and cryptographic operations.
 ///
-/// Format: v2 — single HKDF derivation per envelope with deterministic counter nonces
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Format: single HKDF derivation per envelope with deterministic counter nonces.
+///
+/// The serialized layout is selected by `version`:
+/// - 3: the full [`EnvelopeMetadata`] followed by the recipient list.
+/// - 2: the legacy layout, whose metadata stops at `hash_algorithm` and which has
+///   no recipient list. Legacy envelopes decode with the newer metadata fields at
+///   their defaults and keep version 2, so they re-encode to the same bytes and
+///   their [`Envelope::hash`] and chunk signatures are unchanged.
+#[derive(Debug, Clone)]
 pub struct Envelope {
-    /// Envelope format version (always 2).
+    /// Envelope layout version (3, or 2 for legacy envelopes)
     version: u8,
     /// Per-envelope random HKDF salt (32 bytes).
     hkdf_salt: [u8; 32],
@@ -43,6 +72,180 @@ pub struct Envelope {
     beneficiary_key_bytes: [u8; 32],
     /// Optional metadata about the envelope
     metadata: EnvelopeMetadata,
+    /// Per-recipient wrapped content keys; empty for single-beneficiary envelopes
+    recipients: Vec<WrappedContentKey>,
+}
+
+impl Serialize for Envelope {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{Error as _, SerializeStruct};
+
+        let legacy = self.version == LEGACY_ENVELOPE_VERSION;
+        let mut state = serializer.serialize_struct("Envelope", if legacy { 6 } else { 7 })?;
+        state.serialize_field("version", &self.version)?;
+        state.serialize_field("hkdf_salt", &self.hkdf_salt)?;
+        state.serialize_field("chunks", &self.chunks)?;
+        state.serialize_field("verifying_key_bytes", &self.verifying_key_bytes)?;
+        state.serialize_field("beneficiary_key_bytes", &self.beneficiary_key_bytes)?;
+        if legacy {
+            if !self.recipients.is_empty() {
+                return Err(S::Error::custom(
+                    "legacy envelopes cannot carry a recipient list",
+                ));
+            }
+            let metadata =
+                LegacyEnvelopeMetadata::try_from(&self.metadata).map_err(S::Error::custom)?;
+            state.serialize_field("metadata", &metadata)?;
+        } else {
+            state.serialize_field("metadata", &self.metadata)?;
+            state.serialize_field("recipients", &self.recipients)?;
+        }
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Envelope {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error as _;
+
+        let layout = if deserializer.is_human_readable() {
+            // Self-describing formats name their fields, so the layouts differ by shape
+            EnvelopeLayout::deserialize(deserializer)?
+        } else {
+            // Positional formats such as bincode: the leading version byte decides
+            deserializer.deserialize_tuple(7, BinaryLayoutVisitor)?
+        };
+        Envelope::try_from(layout).map_err(D::Error::custom)
+    }
+}
+
+/// Serialized envelope in one of the supported layouts
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EnvelopeLayout {
+    Current(CurrentLayout),
+    Legacy(LegacyLayout),
+}
+
+/// Version 3 layout
+#[derive(Deserialize)]
+struct CurrentLayout {
+    version: u8,
+    hkdf_salt: [u8; 32],
+    chunks: Vec<NetworkChunk>,
+    verifying_key_bytes: [u8; 32],
+    beneficiary_key_bytes: [u8; 32],
+    metadata: EnvelopeMetadata,
+    recipients: Vec<WrappedContentKey>,
+}
+
+/// Version 2 layout
+#[derive(Deserialize)]
+struct LegacyLayout {
+    version: u8,
+    hkdf_salt: [u8; 32],
+    chunks: Vec<NetworkChunk>,
+    verifying_key_bytes: [u8; 32],
+    beneficiary_key_bytes: [u8; 32],
+    metadata: LegacyEnvelopeMetadata,
+}
+
+impl TryFrom<EnvelopeLayout> for Envelope {
+    type Error = String;
+
+    fn try_from(layout: EnvelopeLayout) -> Result<Self, String> {
+        match layout {
+            EnvelopeLayout::Current(current) if current.version == ENVELOPE_VERSION => Ok(Self {
+                version: current.version,
+                hkdf_salt: current.hkdf_salt,
+                chunks: current.chunks,
+                verifying_key_bytes: current.verifying_key_bytes,
+                beneficiary_key_bytes: current.beneficiary_key_bytes,
+                metadata: current.metadata,
+                recipients: current.recipients,
+            }),
+            EnvelopeLayout::Legacy(legacy) if legacy.version == LEGACY_ENVELOPE_VERSION => {
+                Ok(Self {
+                    version: legacy.version,
+                    hkdf_salt: legacy.hkdf_salt,
+                    chunks: legacy.chunks,
+                    verifying_key_bytes: legacy.verifying_key_bytes,
+                    beneficiary_key_bytes: legacy.beneficiary_key_bytes,
+                    metadata: legacy.metadata.into(),
+                    recipients: Vec::new(),
+                })
+            }
+            EnvelopeLayout::Current(CurrentLayout { version, .. })
+            | EnvelopeLayout::Legacy(LegacyLayout { version, .. }) => Err(format!(
+                "envelope version {version} does not match its layout"
+            )),
+        }
+    }
+}
+
+/// Reads the fields of a positionally encoded envelope in the order its version implies
+struct BinaryLayoutVisitor;
+
+impl<'de> serde::de::Visitor<'de> for BinaryLayoutVisitor {
+    type Value = EnvelopeLayout;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a sealedge envelope")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<EnvelopeLayout, A::Error> {
+        fn next<'de, A: serde::de::SeqAccess<'de>, T: Deserialize<'de>>(
+            seq: &mut A,
+            index: usize,
+        ) -> Result<T, A::Error> {
+            seq.next_element()?
+                .ok_or_else(|| serde::de::Error::invalid_length(index, &BinaryLayoutVisitor))
+        }
+
+        let version: u8 = next(&mut seq, 0)?;
+        if version != ENVELOPE_VERSION && version != LEGACY_ENVELOPE_VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "unsupported envelope version {version}"
+            )));
+        }
+        let hkdf_salt = next(&mut seq, 1)?;
+        let chunks = next(&mut seq, 2)?;
+        let verifying_key_bytes = next(&mut seq, 3)?;
+        let beneficiary_key_bytes = next(&mut seq, 4)?;
+
+        if version == LEGACY_ENVELOPE_VERSION {
+            return Ok(EnvelopeLayout::Legacy(LegacyLayout {
+                version,
+                hkdf_salt,
+                chunks,
+                verifying_key_bytes,
+                beneficiary_key_bytes,
+                metadata: next(&mut seq, 5)?,
+            }));
+        }
+        Ok(EnvelopeLayout::Current(CurrentLayout {
+            version,
+            hkdf_salt,
+            chunks,
+            verifying_key_bytes,
+            beneficiary_key_bytes,
+            metadata: next(&mut seq, 5)?,
+            recipients: next(&mut seq, 6)?,
+        }))
+    }
+}
+
+/// The envelope content key wrapped for one recipient of a multi-recipient envelope
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct WrappedContentKey {
+    /// The recipient's verifying key - stored as bytes
+    recipient_key_bytes: [u8; 32],
+    /// AES-256-GCM encryption of `content_key || nonce_prefix` under the sender/recipient
+    /// ECDH-derived key
+    wrapped_key: Vec<u8>,
 }
 
 /// Metadata associated with an envelope
@@ -54,66 +257,247 @@ pub struct EnvelopeMetadata {
     pub payload_size: u64,
     /// Number of chunks in this envelope
     pub chunk_count: u32,
-    /// Algorithm used for encryption
+    /// Chunk AEAD as an [`AeadAlgorithm`] discriminant; `unseal` dispatches on it
     pub aead_algorithm: u8, // Using u8 for serialization simplicity
     /// Algorithm used for signatures
     pub signature_algorithm: u8,
     /// Algorithm used for hashing
     pub hash_algorithm: u8,
+    /// Compression applied to the payload before encryption
+    pub compression: CompressionAlgorithm,
+    /// Unix time from which `unseal` refuses to decrypt, covered by the chunk signatures
+    pub expires_at: Option<u64>,
+    /// Sealed with a discarded ephemeral key by [`Envelope::seal_anonymous`], covered by
+    /// the chunk signatures
+    pub anonymous: bool,
+    /// MIME type of the payload, set by [`Envelope::seal_with_content_type`] and
+    /// covered by the chunk signatures
+    pub content_type: Option<String>,
+    /// X25519 key the payload is encrypted to when it is separate from the
+    /// beneficiary's Ed25519 identity, set by [`Envelope::seal_for_encryption_key`]
+    /// and covered by the chunk signatures
+    pub encryption_key: Option<[u8; 32]>,
 }
 
-/// Derive shared encryption key material via X25519 ECDH key agreement and HKDF-SHA256.
-///
-/// Converts Ed25519 keys to X25519 using the standard conversion path
-/// documented by `ed25519-dalek`: `SigningKey::to_scalar_bytes()` →
-/// `x25519_dalek::StaticSecret`, and `VerifyingKey::to_montgomery()` →
-/// `x25519_dalek::PublicKey`. The raw ECDH shared secret is fed as IKM into
-/// HKDF-Extract (RFC 5869), then HKDF-Expand derives 40 bytes of output key material:
-///   - bytes 0..32 → AES-256-GCM encryption key
-///   - bytes 32..40 → 8-byte nonce prefix for deterministic per-chunk nonce construction
+/// Metadata of a version 2 envelope, which ends at `hash_algorithm`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct LegacyEnvelopeMetadata {
+    created_at: u64,
+    payload_size: u64,
+    chunk_count: u32,
+    aead_algorithm: u8,
+    signature_algorithm: u8,
+    hash_algorithm: u8,
+}
+
+impl From<LegacyEnvelopeMetadata> for EnvelopeMetadata {
+    fn from(legacy: LegacyEnvelopeMetadata) -> Self {
+        Self {
+            created_at: legacy.created_at,
+            payload_size: legacy.payload_size,
+            chunk_count: legacy.chunk_count,
+            aead_algorithm: legacy.aead_algorithm,
+            signature_algorithm: legacy.signature_algorithm,
+            hash_algorithm: legacy.hash_algorithm,
+            compression: CompressionAlgorithm::None,
+            expires_at: None,
+            anonymous: false,
+            content_type: None,
+            encryption_key: None,
+        }
+    }
+}
+
+impl TryFrom<&EnvelopeMetadata> for LegacyEnvelopeMetadata {
+    type Error = &'static str;
+
+    fn try_from(metadata: &EnvelopeMetadata) -> Result<Self, Self::Error> {
+        if metadata.compression != CompressionAlgorithm::None
+            || metadata.expires_at.is_some()
+            || metadata.anonymous
+            || metadata.content_type.is_some()
+            || metadata.encryption_key.is_some()
+        {
+            return Err("metadata does not fit the legacy envelope layout");
+        }
+        Ok(Self {
+            created_at: metadata.created_at,
+            payload_size: metadata.payload_size,
+            chunk_count: metadata.chunk_count,
+            aead_algorithm: metadata.aead_algorithm,
+            signature_algorithm: metadata.signature_algorithm,
+            hash_algorithm: metadata.hash_algorithm,
+        })
+    }
+}
+
+/// Who sealed an envelope, as reported by [`Envelope::issuer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeIssuer {
+    /// A long-term sender identity
+    Identified(VerifyingKey),
+    /// A one-off key generated by [`Envelope::seal_anonymous`] and discarded after sealing
+    Anonymous(VerifyingKey),
+}
+
+impl EnvelopeIssuer {
+    /// Key the chunk signatures verify under
+    pub fn verifying_key(&self) -> VerifyingKey {
+        match self {
+            EnvelopeIssuer::Identified(key) | EnvelopeIssuer::Anonymous(key) => *key,
+        }
+    }
+
+    /// Whether the sender chose not to be identified
+    pub fn is_anonymous(&self) -> bool {
+        matches!(self, EnvelopeIssuer::Anonymous(_))
+    }
+}
+
+/// An anonymous issuer never equals a long-term identity
+impl PartialEq<VerifyingKey> for EnvelopeIssuer {
+    fn eq(&self, other: &VerifyingKey) -> bool {
+        matches!(self, EnvelopeIssuer::Identified(key) if keys_equal(key.as_bytes(), other.as_bytes()))
+    }
+}
+
+/// Compare two 32-byte keys without short-circuiting on the first differing byte
 ///
-/// DH commutativity guarantees both sides derive the same key:
-///   sender_secret.diffie_hellman(recipient_pub) == recipient_secret.diffie_hellman(sender_pub)
+/// Use this wherever the outcome of a comparison gates access or ownership: who
+/// may unwrap a content key, whether a receipt's issuer owns what it assigns, and
+/// whether a rotation links two keys. `==` on bytes is fine for structural checks
+/// whose inputs are public and whose outcome reveals nothing new, such as chunk
+/// sequence numbers, magic bytes, or matching envelope hashes in a chain.
+pub(crate) fn keys_equal(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    #[cfg(test)]
+    CONSTANT_TIME_KEY_COMPARISONS.with(|count| count.set(count.get() + 1));
+    a.ct_eq(b).into()
+}
+
+#[cfg(test)]
+thread_local! {
+    /// How many times [`keys_equal`] ran on this thread, so tests can check it is used
+    pub(crate) static CONSTANT_TIME_KEY_COMPARISONS: std::cell::Cell<usize> =
+        const { std::cell::Cell::new(0) };
+}
+
+/// Compression applied to an envelope payload before it is encrypted
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompressionAlgorithm {
+    /// Payload is stored uncompressed
+    #[default]
+    None,
+    /// Zstandard compression
+    Zstd,
+}
+
+impl CompressionAlgorithm {
+    /// Zstandard level used when sealing
+    const ZSTD_LEVEL: i32 = 3;
+
+    /// Compress a payload ahead of encryption
+    fn compress<'a>(&self, payload: &'a [u8]) -> Result<Cow<'a, [u8]>> {
+        match self {
+            CompressionAlgorithm::None => Ok(Cow::Borrowed(payload)),
+            CompressionAlgorithm::Zstd => zstd::encode_all(payload, Self::ZSTD_LEVEL)
+                .map(Cow::Owned)
+                .context("Failed to compress envelope payload"),
+        }
+    }
+
+    /// Decompress a decrypted payload, refusing to expand past `expected_size` bytes
+    fn decompress(&self, body: Vec<u8>, expected_size: u64) -> Result<Vec<u8>> {
+        match self {
+            CompressionAlgorithm::None => Ok(body),
+            CompressionAlgorithm::Zstd => {
+                let decoder = zstd::stream::read::Decoder::new(body.as_slice())
+                    .context("Failed to initialize decompressor")?;
+                let mut payload = Vec::new();
+                decoder
+                    .take(expected_size.saturating_add(1))
+                    .read_to_end(&mut payload)
+                    .context("Failed to decompress envelope payload")?;
+                Ok(payload)
+            }
+        }
+    }
+}
+
+/// Optional settings for [`Envelope::seal_with_options`]
 ///
-/// Returns `(encryption_key, nonce_prefix)`.
-fn derive_shared_encryption_key(
-    my_private_key: &SigningKey,
-    their_public_key: &VerifyingKey,
-    salt: &[u8; 32],
-) -> Result<([u8; 32], [u8; 8])> {
-    // Convert Ed25519 keys to X25519 using the standard conversion path
-    let x25519_secret = x25519_dalek::StaticSecret::from(my_private_key.to_scalar_bytes());
-    let x25519_public = x25519_dalek::PublicKey::from(their_public_key.to_montgomery().to_bytes());
-
-    // Standard X25519 Diffie-Hellman key agreement
-    let shared_secret = x25519_secret.diffie_hellman(&x25519_public);
-
-    // Reject low-order points (all-zero shared secret = contributory behavior failure)
-    if shared_secret.as_bytes().iter().all(|&b| b == 0) {
-        return Err(anyhow::anyhow!("ECDH produced zero shared secret"));
-    }
-
-    // HKDF-Extract: extract pseudorandom key from ECDH shared secret
-    // Salt provides randomness; IKM is the raw ECDH output (NOT concatenated with other data)
-    let hkdf = Hkdf::<Sha256>::new(Some(salt), shared_secret.as_bytes());
-
-    // HKDF-Expand: derive 40 bytes of output key material with domain separation.
-    // The info parameter binds the derived key to the sealedge envelope v2 context.
-    // Layout: bytes 0..32 = AES-256-GCM encryption key, bytes 32..40 = 8-byte nonce prefix.
-    let info = b"SEALEDGE_ENVELOPE_V1";
-    let mut okm = [0u8; 40];
-    hkdf.expand(info, &mut okm)
-        .map_err(|_| anyhow::anyhow!("HKDF expand failed"))?;
-
-    let mut encryption_key = [0u8; 32];
-    let mut nonce_prefix = [0u8; 8];
-    encryption_key.copy_from_slice(&okm[0..32]);
-    nonce_prefix.copy_from_slice(&okm[32..40]);
-
-    // Zeroize the full OKM buffer before returning split copies
-    okm.zeroize();
-
-    Ok((encryption_key, nonce_prefix))
+/// The defaults — AES-256-GCM, no compression, expiry or content type, and a
+/// random HKDF salt — are what [`Envelope::seal`] uses.
+#[derive(Clone)]
+pub struct SealOptions {
+    aead: AeadAlgorithm,
+    compression: CompressionAlgorithm,
+    expires_at: Option<u64>,
+    content_type: Option<String>,
+    encryption_key: Option<x25519_dalek::PublicKey>,
+    deterministic_seed: Option<[u8; 32]>,
+    // Only set by `seal_anonymous`, which owns the ephemeral signing key
+    anonymous: bool,
+}
+
+impl Default for SealOptions {
+    fn default() -> Self {
+        Self {
+            aead: AeadAlgorithm::Aes256Gcm,
+            compression: CompressionAlgorithm::None,
+            expires_at: None,
+            content_type: None,
+            encryption_key: None,
+            deterministic_seed: None,
+            anonymous: false,
+        }
+    }
+}
+
+impl SealOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Chunk AEAD; see [`Envelope::seal_with_alg`]
+    pub fn with_aead(mut self, aead: AeadAlgorithm) -> Self {
+        self.aead = aead;
+        self
+    }
+
+    /// Compression applied before encryption; see [`Envelope::seal_with_compression`]
+    pub fn with_compression(mut self, compression: CompressionAlgorithm) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Unix time from which `unseal` refuses to decrypt; see [`Envelope::seal_with_expiry`]
+    pub fn with_expiry(mut self, expires_at: u64) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// MIME type of the payload; see [`Envelope::seal_with_content_type`]
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Dedicated X25519 encryption key; see [`Envelope::seal_for_encryption_key`]
+    pub fn with_encryption_key(mut self, encryption_key: x25519_dalek::PublicKey) -> Self {
+        self.encryption_key = Some(encryption_key);
+        self
+    }
+
+    /// Derive the HKDF salt from `nonce_seed`; see [`Envelope::seal_deterministic`]
+    pub fn with_deterministic_seed(mut self, nonce_seed: [u8; 32]) -> Self {
+        self.deterministic_seed = Some(nonce_seed);
+        self
+    }
+}
+
+/// Wrap an internal decryption failure, keeping its context chain in the message
+fn decrypt_error(e: impl Into<anyhow::Error>) -> EnvelopeError {
+    EnvelopeError::Decrypt(format!("{:#}", e.into()))
 }
 
 impl Envelope {
@@ -133,62 +517,310 @@ impl Envelope {
         signing_key: &SigningKey,
         beneficiary_key: &VerifyingKey,
     ) -> Result<Self> {
-        use rand::RngCore;
+        Self::seal_with_compression(
+            payload,
+            signing_key,
+            beneficiary_key,
+            CompressionAlgorithm::None,
+        )
+    }
 
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .context("Failed to get current timestamp")?
-            .as_secs();
+    /// Seal a payload, compressing it before encryption
+    ///
+    /// The algorithm is recorded in [`EnvelopeMetadata::compression`] and `unseal`
+    /// decompresses transparently. `payload_size` still reports the original size.
+    pub fn seal_with_compression(
+        payload: &[u8],
+        signing_key: &SigningKey,
+        beneficiary_key: &VerifyingKey,
+        compression: CompressionAlgorithm,
+    ) -> Result<Self> {
+        Self::seal_with_options(
+            payload,
+            signing_key,
+            beneficiary_key,
+            &SealOptions::new().with_compression(compression),
+        )
+    }
 
-        // Calculate how many chunks we'll need
-        let chunk_count = payload.len().div_ceil(DEFAULT_CHUNK_SIZE) as u32;
+    /// Seal a payload with a chosen AEAD
+    ///
+    /// ChaCha20-Poly1305 is faster than AES-256-GCM on platforms without AES
+    /// hardware acceleration. The choice is recorded in
+    /// [`EnvelopeMetadata::aead_algorithm`] and `unseal` dispatches on it.
+    /// AES-256-SIV is not supported for envelopes.
+    pub fn seal_with_alg(
+        payload: &[u8],
+        signing_key: &SigningKey,
+        beneficiary_key: &VerifyingKey,
+        algorithm: AeadAlgorithm,
+    ) -> Result<Self> {
+        Self::seal_with_options(
+            payload,
+            signing_key,
+            beneficiary_key,
+            &SealOptions::new().with_aead(algorithm),
+        )
+    }
 
-        let metadata = EnvelopeMetadata {
-            created_at: timestamp,
-            payload_size: payload.len() as u64,
-            chunk_count,
-            aead_algorithm: AeadAlgorithm::Aes256Gcm as u8,
-            signature_algorithm: SignatureAlgorithm::Ed25519 as u8,
-            hash_algorithm: HashAlgorithm::Blake3 as u8,
+    /// Seal a payload that `unseal` will refuse to decrypt from `expires_at` on
+    ///
+    /// The expiry is bound into every chunk signature, so stripping or editing it
+    /// makes [`Envelope::verify`] fail. From the `expires_at` second itself,
+    /// `unseal` returns [`EnvelopeError::Expired`], the same boundary as
+    /// [`OwnershipReceipt::is_expired_at`](crate::OwnershipReceipt::is_expired_at).
+    pub fn seal_with_expiry(
+        payload: &[u8],
+        signing_key: &SigningKey,
+        beneficiary_key: &VerifyingKey,
+        expires_at: u64,
+    ) -> Result<Self> {
+        Self::seal_with_options(
+            payload,
+            signing_key,
+            beneficiary_key,
+            &SealOptions::new().with_expiry(expires_at),
+        )
+    }
+
+    /// Seal a payload without revealing the sender
+    ///
+    /// A fresh signing key is generated for this envelope alone and dropped once
+    /// sealing completes, so the envelope carries no long-term sender identity.
+    /// [`Envelope::issuer`] reports it as [`EnvelopeIssuer::Anonymous`];
+    /// [`Envelope::verify`] still checks every chunk against the ephemeral key and
+    /// the recipient unseals as usual.
+    pub fn seal_anonymous(payload: &[u8], beneficiary_key: &VerifyingKey) -> Result<Self> {
+        let ephemeral_key = SigningKey::generate(&mut rand_core::OsRng);
+        let options = SealOptions {
+            anonymous: true,
+            ..SealOptions::default()
         };
+        Self::seal_with_options(payload, &ephemeral_key, beneficiary_key, &options)
+    }
 
-        // Generate per-envelope random HKDF salt once
-        let mut hkdf_salt = [0u8; 32];
-        rand::rng().fill_bytes(&mut hkdf_salt);
+    /// Seal a payload labelled with its MIME type
+    ///
+    /// The type is readable through [`Envelope::content_type`] without the recipient
+    /// key, so payloads can be routed before they are unsealed. It is bound into every
+    /// chunk signature, so editing or stripping it makes [`Envelope::verify`] fail.
+    pub fn seal_with_content_type(
+        payload: &[u8],
+        signing_key: &SigningKey,
+        beneficiary_key: &VerifyingKey,
+        content_type: &str,
+    ) -> Result<Self> {
+        Self::seal_with_options(
+            payload,
+            signing_key,
+            beneficiary_key,
+            &SealOptions::new().with_content_type(content_type),
+        )
+    }
 
-        // Derive key material once for the entire envelope (v2 path)
-        let (mut encryption_key, nonce_prefix) =
-            derive_shared_encryption_key(signing_key, beneficiary_key, &hkdf_salt)?;
+    /// Seal a payload to a dedicated X25519 encryption key
+    ///
+    /// `beneficiary_key` stays the recipient's Ed25519 identity and is what
+    /// [`Envelope::beneficiary`] reports, so ownership checks are unchanged. The
+    /// payload key is agreed with `encryption_key` instead, and only its secret can
+    /// [`Envelope::unseal_with_encryption_key`]. The encryption key is bound into
+    /// every chunk signature.
+    pub fn seal_for_encryption_key(
+        payload: &[u8],
+        signing_key: &SigningKey,
+        beneficiary_key: &VerifyingKey,
+        encryption_key: &x25519_dalek::PublicKey,
+    ) -> Result<Self> {
+        Self::seal_with_options(
+            payload,
+            signing_key,
+            beneficiary_key,
+            &SealOptions::new().with_encryption_key(*encryption_key),
+        )
+    }
 
-        // Chunk count for last-chunk detection
-        let total_chunks = payload.chunks(DEFAULT_CHUNK_SIZE).count();
+    /// Seal a payload so that identical inputs produce byte-identical envelopes
+    ///
+    /// Intended for reproducible-build pipelines that compare sealed artifacts byte
+    /// for byte. The HKDF salt, and with it the encryption key and nonce prefix, is
+    /// derived from `nonce_seed`, the payload and the envelope metadata instead of
+    /// drawn at random, and
+    /// `created_at` is recorded as 0. Ed25519 signatures are already deterministic.
+    ///
+    /// This trades away nonce unpredictability: anyone who knows the seed and guesses
+    /// the payload can confirm the guess by resealing it. Never reuse a seed across
+    /// unrelated payloads that must stay confidential, and prefer [`Envelope::seal`]
+    /// outside reproducibility contexts.
+    pub fn seal_deterministic(
+        payload: &[u8],
+        signing_key: &SigningKey,
+        beneficiary_key: &VerifyingKey,
+        nonce_seed: &[u8; 32],
+    ) -> Result<Self> {
+        Self::seal_with_options(
+            payload,
+            signing_key,
+            beneficiary_key,
+            &SealOptions::new().with_deterministic_seed(*nonce_seed),
+        )
+    }
 
-        // Break the payload into chunks and encrypt each one
-        let mut chunks = Vec::new();
-        for (i, chunk_data) in payload.chunks(DEFAULT_CHUNK_SIZE).enumerate() {
-            let is_last = i == total_chunks - 1;
-            let chunk = Self::create_encrypted_chunk(
-                i as u64,
-                chunk_data,
-                signing_key,
-                &encryption_key,
-                &nonce_prefix,
-                is_last,
-                &metadata,
-            )?;
-            chunks.push(chunk);
+    /// Seal a payload for a single beneficiary with any combination of [`SealOptions`]
+    ///
+    /// The other single-beneficiary `seal_*` constructors are shorthands for this one.
+    pub fn seal_with_options(
+        payload: &[u8],
+        signing_key: &SigningKey,
+        beneficiary_key: &VerifyingKey,
+        options: &SealOptions,
+    ) -> Result<Self> {
+        use rand::RngCore;
+
+        if options.aead == AeadAlgorithm::Aes256Siv {
+            return Err(anyhow::anyhow!(
+                "AEAD algorithm {:?} is not supported for envelopes",
+                options.aead
+            ));
+        }
+        if let Some(content_type) = &options.content_type {
+            validate_content_type(content_type)?;
+        }
+
+        let body = options.compression.compress(payload)?;
+        let mut metadata = Self::build_metadata(&body)?;
+        metadata.aead_algorithm = options.aead as u8;
+        metadata.payload_size = payload.len() as u64;
+        metadata.compression = options.compression;
+        metadata.expires_at = options.expires_at;
+        metadata.anonymous = options.anonymous;
+        metadata.content_type = options.content_type.clone();
+        metadata.encryption_key = options.encryption_key.map(|key| key.to_bytes());
+
+        // Generate per-envelope HKDF salt once: random, or derived from the seed, the
+        // encrypted body and every sealed option for reproducible output. Binding the
+        // options keeps two option sets from sharing a key and nonce prefix.
+        let mut hkdf_salt = [0u8; 32];
+        match &options.deterministic_seed {
+            Some(seed) => {
+                metadata.created_at = 0;
+                let metadata_bytes = bincode::serialize(&metadata)
+                    .context("Failed to serialize envelope metadata")?;
+                hkdf_salt = blake3::Hasher::new_derive_key(DETERMINISTIC_SALT_CONTEXT)
+                    .update(seed)
+                    .update(&(body.len() as u64).to_le_bytes())
+                    .update(&body)
+                    .update(&metadata_bytes)
+                    .finalize()
+                    .into();
+            }
+            None => rand::rng().fill_bytes(&mut hkdf_salt),
         }
 
+        // Derive key material once for the entire envelope (v2 path), agreed with the
+        // dedicated encryption key when there is one
+        let (mut encryption_key, nonce_prefix) = match &options.encryption_key {
+            Some(recipient) => derive_x25519_encryption_key(
+                &x25519_dalek::StaticSecret::from(signing_key.to_scalar_bytes()),
+                recipient,
+                &hkdf_salt,
+            )?,
+            None => derive_shared_encryption_key(signing_key, beneficiary_key, &hkdf_salt)?,
+        };
+
+        let binding = signature_binding(&metadata, &[])?;
+        let chunks = Self::encrypt_chunks(
+            &body,
+            signing_key,
+            &encryption_key,
+            &nonce_prefix,
+            &metadata,
+            &binding,
+        );
+
         // Zeroize the envelope-level encryption key after all chunks are sealed
         encryption_key.zeroize();
 
         Ok(Envelope {
-            version: 2,
+            version: ENVELOPE_VERSION,
             hkdf_salt,
-            chunks,
+            chunks: chunks?,
             verifying_key_bytes: signing_key.verifying_key().to_bytes(),
             beneficiary_key_bytes: beneficiary_key.to_bytes(),
             metadata,
+            recipients: Vec::new(),
+        })
+    }
+
+    /// Seal a payload once for several recipients
+    ///
+    /// The payload is encrypted a single time under a random content key. That key is
+    /// then wrapped separately for each recipient using the sender/recipient ECDH key,
+    /// so any listed recipient can [`Envelope::unseal`] it without the ciphertext being
+    /// duplicated. [`Envelope::beneficiary`] reports the first recipient.
+    pub fn seal_for_many(
+        payload: &[u8],
+        signing_key: &SigningKey,
+        recipients: &[VerifyingKey],
+    ) -> Result<Self> {
+        use rand::RngCore;
+
+        let first_recipient = recipients
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("At least one recipient is required"))?;
+        for (i, recipient) in recipients.iter().enumerate() {
+            if recipients[..i].contains(recipient) {
+                return Err(anyhow::anyhow!("Duplicate recipient in envelope"));
+            }
+        }
+
+        let metadata = Self::build_metadata(payload)?;
+
+        let mut hkdf_salt = [0u8; 32];
+        rand::rng().fill_bytes(&mut hkdf_salt);
+
+        // Random content key and nonce prefix shared by every recipient
+        let mut content_key = [0u8; 32];
+        let mut nonce_prefix = [0u8; 8];
+        rand::rng().fill_bytes(&mut content_key);
+        rand::rng().fill_bytes(&mut nonce_prefix);
+
+        // Wrap first so the chunk signatures can cover the recipient list
+        let sealed = recipients
+            .iter()
+            .map(|recipient| {
+                Self::wrap_content_key(
+                    signing_key,
+                    recipient,
+                    &hkdf_salt,
+                    &content_key,
+                    &nonce_prefix,
+                )
+            })
+            .collect::<Result<Vec<WrappedContentKey>>>()
+            .and_then(|wrapped| {
+                let binding = signature_binding(&metadata, &wrapped)?;
+                let chunks = Self::encrypt_chunks(
+                    payload,
+                    signing_key,
+                    &content_key,
+                    &nonce_prefix,
+                    &metadata,
+                    &binding,
+                )?;
+                Ok((wrapped, chunks))
+            });
+
+        content_key.zeroize();
+        let (wrapped, chunks) = sealed?;
+
+        Ok(Envelope {
+            version: ENVELOPE_VERSION,
+            hkdf_salt,
+            chunks,
+            verifying_key_bytes: signing_key.verifying_key().to_bytes(),
+            beneficiary_key_bytes: first_recipient.to_bytes(),
+            metadata,
+            recipients: wrapped,
         })
     }
 
@@ -196,9 +828,20 @@ impl Envelope {
     ///
     /// This validates all signatures and ensures the envelope hasn't been tampered with.
     pub fn verify(&self) -> bool {
+        // Version 3 signatures also cover the metadata and recipient list; legacy ones
+        // cover the manifest alone
+        let binding = if self.version == LEGACY_ENVELOPE_VERSION {
+            None
+        } else {
+            match signature_binding(&self.metadata, &self.recipients) {
+                Ok(binding) => Some(binding),
+                Err(_) => return false,
+            }
+        };
+
         // Verify each chunk's signature
         for chunk in &self.chunks {
-            if !self.verify_chunk_signature(chunk) {
+            if !Self::verify_chunk_signature(chunk, binding.as_ref(), &self.verifying_key_bytes) {
                 return false;
             }
         }
@@ -214,47 +857,176 @@ impl Envelope {
     /// Unseal the envelope to recover the original payload (the "unlock")
     ///
     /// Decrypts using v2 path: single HKDF key derivation + deterministic nonce reconstruction.
-    pub fn unseal(&self, decryption_key: &SigningKey) -> Result<Vec<u8>> {
-        if !self.verify() {
-            return Err(anyhow::anyhow!("Envelope verification failed"));
+    /// Envelopes from [`Envelope::seal_for_encryption_key`] need
+    /// [`Envelope::unseal_with_encryption_key`] instead; passing them here is
+    /// [`EnvelopeError::WrongRecipient`].
+    pub fn unseal(&self, decryption_key: &SigningKey) -> Result<Vec<u8>, EnvelopeError> {
+        self.unseal_with_clock(decryption_key, &SystemClock)
+    }
+
+    /// Like [`Envelope::unseal`], but judges expiry against `clock`
+    pub fn unseal_with_clock(
+        &self,
+        decryption_key: &SigningKey,
+        clock: &dyn Clock,
+    ) -> Result<Vec<u8>, EnvelopeError> {
+        if self.metadata.encryption_key.is_some() {
+            return Err(EnvelopeError::WrongRecipient);
         }
 
-        // Sort chunks by sequence number to ensure correct order
-        let mut sorted_chunks = self.chunks.clone();
-        sorted_chunks.sort_by_key(|chunk| chunk.sequence);
+        self.open(clock, |sender_public_key| {
+            // Derive key material once for the entire envelope (v2 path), or unwrap the
+            // shared content key when the envelope was sealed for several recipients
+            if self.recipients.is_empty() {
+                let recipient_key_bytes = decryption_key.verifying_key().to_bytes();
+                if !keys_equal(&self.beneficiary_key_bytes, &recipient_key_bytes) {
+                    return Err(EnvelopeError::WrongRecipient);
+                }
+                derive_shared_encryption_key(decryption_key, sender_public_key, &self.hkdf_salt)
+                    .map_err(decrypt_error)
+            } else {
+                self.unwrap_content_key(decryption_key, sender_public_key)
+            }
+        })
+    }
 
-        let total_chunks = sorted_chunks.len();
+    /// Unseal an envelope from [`Envelope::seal_for_encryption_key`] with the X25519 secret
+    pub fn unseal_with_encryption_key(
+        &self,
+        decryption_key: &x25519_dalek::StaticSecret,
+    ) -> Result<Vec<u8>, EnvelopeError> {
+        let expected = self
+            .metadata
+            .encryption_key
+            .ok_or(EnvelopeError::WrongRecipient)?;
+        if !keys_equal(
+            &expected,
+            x25519_dalek::PublicKey::from(decryption_key).as_bytes(),
+        ) {
+            return Err(EnvelopeError::WrongRecipient);
+        }
 
-        // Get the sender's public key from the envelope
-        let sender_public_key = VerifyingKey::from_bytes(&self.verifying_key_bytes)
-            .context("Invalid sender public key in envelope")?;
+        self.open(&SystemClock, |sender_public_key| {
+            let sender =
+                x25519_dalek::PublicKey::from(sender_public_key.to_montgomery().to_bytes());
+            derive_x25519_encryption_key(decryption_key, &sender, &self.hkdf_salt)
+                .map_err(decrypt_error)
+        })
+    }
 
-        // Derive key material once for the entire envelope (v2 path)
-        let (mut encryption_key, nonce_prefix) =
-            derive_shared_encryption_key(decryption_key, &sender_public_key, &self.hkdf_salt)?;
+    /// Re-encrypt the payload to `new_recipient` on behalf of the current holder
+    ///
+    /// Only someone who can [`Envelope::unseal`] with `holder_key` can reseal, so the
+    /// plaintext is never exposed to anyone else. The new envelope is signed by
+    /// `holder_key` and keeps the AEAD, compression, expiry, and content type of this
+    /// one; `created_at` is the time of resealing.
+    ///
+    /// Envelopes from [`Envelope::seal_anonymous`] and
+    /// [`Envelope::seal_for_encryption_key`] are refused with
+    /// [`EnvelopeError::NotResealable`]: a reseal signed by `holder_key` cannot stay
+    /// anonymous, and the original X25519 encryption key belongs to the old holder,
+    /// not `new_recipient`.
+    pub fn reseal(&self, holder_key: &SigningKey, new_recipient: &VerifyingKey) -> Result<Self> {
+        if self.metadata.anonymous {
+            return Err(EnvelopeError::NotResealable(
+                "anonymous envelopes cannot be resealed under the holder's identity",
+            )
+            .into());
+        }
+        if self.metadata.encryption_key.is_some() {
+            return Err(EnvelopeError::NotResealable(
+                "envelopes sealed to a dedicated encryption key cannot be resealed",
+            )
+            .into());
+        }
+
+        let mut payload = self
+            .unseal(holder_key)
+            .context("Failed to unseal envelope for resealing")?;
+
+        let mut options = SealOptions::new()
+            .with_aead(AeadAlgorithm::try_from(self.metadata.aead_algorithm)?)
+            .with_compression(self.metadata.compression);
+        options.expires_at = self.metadata.expires_at;
+        options.content_type = self.metadata.content_type.clone();
+
+        let resealed = Self::seal_with_options(&payload, holder_key, new_recipient, &options);
+        payload.zeroize();
+        resealed
+    }
+
+    /// Verify the envelope and decrypt it under the key material from `derive_key`
+    ///
+    /// `derive_key` is given the sender's public key.
+    fn open(
+        &self,
+        clock: &dyn Clock,
+        derive_key: impl FnOnce(&VerifyingKey) -> Result<([u8; 32], [u8; 8]), EnvelopeError>,
+    ) -> Result<Vec<u8>, EnvelopeError> {
+        if !self.verify() {
+            return Err(EnvelopeError::SignatureInvalid);
+        }
+
+        if let Some(expires_at) = self.metadata.expires_at {
+            let now = clock.now_secs();
+            if self.is_expired_at(now) {
+                return Err(EnvelopeError::Expired { expires_at, now });
+            }
+        }
+
+        // Sort chunks by sequence number to ensure correct order
+        let mut sorted_chunks = self.chunks.clone();
+        sorted_chunks.sort_by_key(|chunk| chunk.sequence);
+
+        let total_chunks = sorted_chunks.len();
+
+        // Get the sender's public key from the envelope
+        let sender_public_key = VerifyingKey::from_bytes(&self.verifying_key_bytes)
+            .map_err(|_| EnvelopeError::SignatureInvalid)?;
+
+        let (mut encryption_key, nonce_prefix) = derive_key(&sender_public_key)?;
 
         let mut payload = Vec::new();
         for (i, chunk) in sorted_chunks.iter().enumerate() {
             let is_last = i == total_chunks - 1;
-            let decrypted =
-                self.decrypt_chunk_v2(chunk, &encryption_key, &nonce_prefix, is_last)?;
+            let decrypted = Self::decrypt_chunk_v2(
+                chunk,
+                &self.metadata,
+                &encryption_key,
+                &nonce_prefix,
+                is_last,
+            )
+            .map_err(decrypt_error)?;
             payload.extend_from_slice(&decrypted);
         }
 
         // Zeroize the encryption key after all chunks are decrypted
         encryption_key.zeroize();
 
+        let payload = self
+            .metadata
+            .compression
+            .decompress(payload, self.metadata.payload_size)
+            .map_err(decrypt_error)?;
+
         if payload.len() != self.metadata.payload_size as usize {
-            return Err(anyhow::anyhow!(
+            return Err(EnvelopeError::Decrypt(format!(
                 "Payload size mismatch: expected {}, got {}",
                 self.metadata.payload_size,
                 payload.len()
-            ));
+            )));
         }
 
         Ok(payload)
     }
 
+    /// Check whether this envelope has expired at `now` (seconds since UNIX epoch)
+    pub fn is_expired_at(&self, now: u64) -> bool {
+        self.metadata
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+
     /// Get the hash of this envelope for chaining purposes
     pub fn hash(&self) -> Result<[u8; 32]> {
         let envelope_bytes = bincode::serialize(self)
@@ -268,19 +1040,287 @@ impl Envelope {
             .map_err(|e| anyhow::anyhow!("Invalid beneficiary key bytes: {e}"))
     }
 
+    /// Get the verifying keys of every recipient of a multi-recipient envelope
+    ///
+    /// Returns an empty list for envelopes sealed with [`Envelope::seal`].
+    pub fn recipients(&self) -> Result<Vec<VerifyingKey>> {
+        self.recipients
+            .iter()
+            .map(|wrapped| {
+                VerifyingKey::from_bytes(&wrapped.recipient_key_bytes)
+                    .map_err(|e| anyhow::anyhow!("Invalid recipient key bytes: {e}"))
+            })
+            .collect()
+    }
+
+    /// Layout version this envelope was decoded from or sealed with
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
     /// Get the envelope metadata
     pub fn metadata(&self) -> &EnvelopeMetadata {
         &self.metadata
     }
 
-    /// Get the issuer's verifying key
-    pub fn issuer(&self) -> Result<VerifyingKey> {
-        VerifyingKey::from_bytes(&self.verifying_key_bytes)
-            .map_err(|e| anyhow::anyhow!("Invalid issuer key bytes: {e}"))
+    /// MIME type of the payload, if the sender recorded one
+    ///
+    /// Readable without the recipient key. Only trust it once [`Envelope::verify`]
+    /// has passed, since that is what checks it against the chunk signatures.
+    pub fn content_type(&self) -> Option<&str> {
+        self.metadata.content_type.as_deref()
+    }
+
+    /// X25519 key the payload is encrypted to, for envelopes from
+    /// [`Envelope::seal_for_encryption_key`]
+    pub fn encryption_key(&self) -> Option<x25519_dalek::PublicKey> {
+        self.metadata
+            .encryption_key
+            .map(x25519_dalek::PublicKey::from)
+    }
+
+    /// Get the issuer, marked anonymous for envelopes from [`Envelope::seal_anonymous`]
+    pub fn issuer(&self) -> Result<EnvelopeIssuer> {
+        let key = VerifyingKey::from_bytes(&self.verifying_key_bytes)
+            .map_err(|e| anyhow::anyhow!("Invalid issuer key bytes: {e}"))?;
+        Ok(if self.metadata.anonymous {
+            EnvelopeIssuer::Anonymous(key)
+        } else {
+            EnvelopeIssuer::Identified(key)
+        })
+    }
+
+    /// Encode the envelope's bincode bytes as standard base64
+    pub fn to_base64(&self) -> Result<String> {
+        let envelope_bytes = bincode::serialize(self)
+            .map_err(|e| anyhow::anyhow!("Failed to serialize envelope: {e}"))?;
+        Ok(BASE64.encode(envelope_bytes))
+    }
+
+    /// Decode an envelope produced by [`Envelope::to_base64`]
+    pub fn from_base64(encoded: &str) -> Result<Self, EnvelopeError> {
+        use bincode::Options;
+
+        let envelope_bytes = BASE64
+            .decode(encoded.trim())
+            .map_err(|e| EnvelopeError::Deserialize(format!("Invalid base64 encoding: {e}")))?;
+        // Same encoding as `bincode::serialize`, but a relabelled version byte cannot
+        // leave the rest of a longer layout unread
+        bincode::options()
+            .with_fixint_encoding()
+            .reject_trailing_bytes()
+            .deserialize(&envelope_bytes)
+            .map_err(|e| EnvelopeError::Deserialize(e.to_string()))
+    }
+
+    /// Encode the envelope as PEM-style ASCII armor
+    ///
+    /// The body is the base64 of the bincode bytes wrapped at 64 columns between
+    /// `-----BEGIN SEALEDGE ENVELOPE-----` and `-----END SEALEDGE ENVELOPE-----`.
+    pub fn to_pem(&self) -> Result<String> {
+        let encoded = self.to_base64()?;
+
+        let mut pem = format!("-----BEGIN {PEM_LABEL}-----\n");
+        for line in encoded.as_bytes().chunks(PEM_LINE_WIDTH) {
+            // Base64 output is ASCII, so every chunk boundary is a char boundary
+            pem.push_str(std::str::from_utf8(line).context("Invalid base64 output")?);
+            pem.push('\n');
+        }
+        pem.push_str(&format!("-----END {PEM_LABEL}-----\n"));
+        Ok(pem)
+    }
+
+    /// Decode an envelope from the armor produced by [`Envelope::to_pem`]
+    pub fn from_pem(pem: &str) -> Result<Self, EnvelopeError> {
+        let mut lines = pem.trim().lines().map(str::trim);
+
+        let begin = format!("-----BEGIN {PEM_LABEL}-----");
+        let end = format!("-----END {PEM_LABEL}-----");
+        if lines.next() != Some(begin.as_str()) {
+            return Err(EnvelopeError::Deserialize(format!(
+                "Missing {begin} header"
+            )));
+        }
+
+        let mut body = String::new();
+        let mut terminated = false;
+        for line in lines {
+            if terminated {
+                return Err(EnvelopeError::Deserialize(format!(
+                    "Unexpected data after {end}"
+                )));
+            }
+            if line == end {
+                terminated = true;
+            } else {
+                body.push_str(line);
+            }
+        }
+        if !terminated {
+            return Err(EnvelopeError::Deserialize(format!("Missing {end} footer")));
+        }
+
+        Self::from_base64(&body)
     }
 
     // Private helper methods for the complex crypto operations
 
+    /// Build the metadata for a payload about to be sealed
+    fn build_metadata(payload: &[u8]) -> Result<EnvelopeMetadata> {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .context("Failed to get current timestamp")?
+            .as_secs();
+
+        // Calculate how many chunks we'll need
+        let chunk_count = payload.len().div_ceil(DEFAULT_CHUNK_SIZE) as u32;
+
+        Ok(EnvelopeMetadata {
+            created_at: timestamp,
+            payload_size: payload.len() as u64,
+            chunk_count,
+            aead_algorithm: AeadAlgorithm::Aes256Gcm as u8,
+            signature_algorithm: SignatureAlgorithm::Ed25519 as u8,
+            hash_algorithm: HashAlgorithm::Blake3 as u8,
+            compression: CompressionAlgorithm::None,
+            expires_at: None,
+            anonymous: false,
+            content_type: None,
+            encryption_key: None,
+        })
+    }
+
+    /// Break the payload into chunks and encrypt each one under the envelope key
+    fn encrypt_chunks(
+        payload: &[u8],
+        signing_key: &SigningKey,
+        encryption_key: &[u8; 32],
+        nonce_prefix: &[u8; 8],
+        metadata: &EnvelopeMetadata,
+        binding: &[u8; 32],
+    ) -> Result<Vec<NetworkChunk>> {
+        // Chunk count for last-chunk detection
+        let total_chunks = payload.chunks(DEFAULT_CHUNK_SIZE).count();
+
+        let mut chunks = Vec::new();
+        for (i, chunk_data) in payload.chunks(DEFAULT_CHUNK_SIZE).enumerate() {
+            let is_last = i == total_chunks - 1;
+            let chunk = Self::create_encrypted_chunk(
+                i as u64,
+                chunk_data,
+                signing_key,
+                encryption_key,
+                nonce_prefix,
+                is_last,
+                metadata,
+                Some(binding),
+            )?;
+            chunks.push(chunk);
+        }
+        Ok(chunks)
+    }
+
+    /// Wrap the content key and nonce prefix for one recipient
+    ///
+    /// The wrapping key comes from the same ECDH + HKDF derivation as single-recipient
+    /// envelopes and is used exactly once, so its derived nonce prefix with a zero
+    /// counter is a safe nonce. The recipient key is bound in as AAD.
+    fn wrap_content_key(
+        signing_key: &SigningKey,
+        recipient: &VerifyingKey,
+        hkdf_salt: &[u8; 32],
+        content_key: &[u8; 32],
+        nonce_prefix: &[u8; 8],
+    ) -> Result<WrappedContentKey> {
+        use aes_gcm::aead::{Aead, Payload};
+        use aes_gcm::{Aes256Gcm, KeyInit};
+
+        let (mut wrapping_key, wrapping_nonce_prefix) =
+            derive_shared_encryption_key(signing_key, recipient, hkdf_salt)?;
+        let cipher = Aes256Gcm::new_from_slice(&wrapping_key)
+            .context("Failed to create key wrapping cipher");
+        wrapping_key.zeroize();
+        let cipher = cipher?;
+
+        let mut key_material = [0u8; 40];
+        key_material[0..32].copy_from_slice(content_key);
+        key_material[32..40].copy_from_slice(nonce_prefix);
+
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce[0..8].copy_from_slice(&wrapping_nonce_prefix);
+        let recipient_key_bytes = recipient.to_bytes();
+
+        let wrapped_key = cipher.encrypt(
+            (&nonce).into(),
+            Payload {
+                msg: &key_material,
+                aad: &recipient_key_bytes,
+            },
+        );
+        key_material.zeroize();
+
+        Ok(WrappedContentKey {
+            recipient_key_bytes,
+            wrapped_key: wrapped_key
+                .map_err(|e| anyhow::anyhow!("Content key wrapping failed: {:?}", e))?,
+        })
+    }
+
+    /// Locate and unwrap the content key addressed to `decryption_key`
+    fn unwrap_content_key(
+        &self,
+        decryption_key: &SigningKey,
+        sender_public_key: &VerifyingKey,
+    ) -> Result<([u8; 32], [u8; 8]), EnvelopeError> {
+        use aes_gcm::aead::{Aead, Payload};
+        use aes_gcm::{Aes256Gcm, KeyInit};
+
+        let recipient_key_bytes = decryption_key.verifying_key().to_bytes();
+        let wrapped = self
+            .recipients
+            .iter()
+            .find(|wrapped| keys_equal(&wrapped.recipient_key_bytes, &recipient_key_bytes))
+            .ok_or(EnvelopeError::WrongRecipient)?;
+
+        let (mut wrapping_key, wrapping_nonce_prefix) =
+            derive_shared_encryption_key(decryption_key, sender_public_key, &self.hkdf_salt)
+                .map_err(decrypt_error)?;
+        let cipher = Aes256Gcm::new_from_slice(&wrapping_key).map_err(|e| {
+            EnvelopeError::Decrypt(format!("Failed to create key wrapping cipher: {e}"))
+        });
+        wrapping_key.zeroize();
+        let cipher = cipher?;
+
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce[0..8].copy_from_slice(&wrapping_nonce_prefix);
+
+        let mut key_material = cipher
+            .decrypt(
+                (&nonce).into(),
+                Payload {
+                    msg: &wrapped.wrapped_key,
+                    aad: &recipient_key_bytes,
+                },
+            )
+            .map_err(|e| {
+                EnvelopeError::Decrypt(format!("Content key unwrapping failed: {:?}", e))
+            })?;
+        if key_material.len() != 40 {
+            key_material.zeroize();
+            return Err(EnvelopeError::Decrypt(
+                "Unwrapped content key has invalid length".to_string(),
+            ));
+        }
+
+        let mut content_key = [0u8; 32];
+        let mut nonce_prefix = [0u8; 8];
+        content_key.copy_from_slice(&key_material[0..32]);
+        nonce_prefix.copy_from_slice(&key_material[32..40]);
+        key_material.zeroize();
+
+        Ok((content_key, nonce_prefix))
+    }
+
     /// Create an encrypted chunk from raw data (v2 path — deterministic nonce)
     ///
     /// The encryption key and nonce prefix are derived once at seal level and passed in.
@@ -297,26 +1337,10 @@ impl Envelope {
         nonce_prefix: &[u8; 8],
         is_last_chunk: bool,
         metadata: &EnvelopeMetadata,
+        binding: Option<&[u8; 32]>,
     ) -> Result<NetworkChunk> {
-        use aes_gcm::{AeadInPlace, Aes256Gcm, KeyInit};
-
-        // Guard: only 3 bytes of the u32 are used in the nonce (bytes 8..11),
-        // so chunk index must fit in 24 bits.
-        if sequence > MAX_CHUNK_INDEX {
-            return Err(anyhow::anyhow!(
-                "Chunk index exceeds maximum (16,777,215) — envelope too large for current nonce format"
-            ));
-        }
-
-        // Construct deterministic 12-byte nonce:
-        //   bytes 0..8  = nonce_prefix (8 bytes from HKDF output)
-        //   bytes 8..11 = low 3 bytes of chunk index as BE u32
-        //   byte 11     = last-chunk flag (0xFF if last, 0x00 otherwise)
-        let mut nonce = [0u8; NONCE_LEN];
-        nonce[0..8].copy_from_slice(nonce_prefix);
-        let idx_be = (sequence as u32).to_be_bytes();
-        nonce[8..11].copy_from_slice(&idx_be[1..4]); // low 3 bytes of u32 BE
-        nonce[11] = if is_last_chunk { 0xFF } else { 0x00 };
+        // Deterministic 12-byte counter nonce; the chunk index must fit in 24 bits
+        let nonce = chunk_nonce(nonce_prefix, sequence, is_last_chunk)?;
 
         // Create the v2 manifest — key_derivation_salt and pbkdf2_iterations zeroed.
         // Fields are kept for serde compat with ChunkManifest; not used by v2 decrypt path.
@@ -334,7 +1358,7 @@ impl Envelope {
 
         // Create signed manifest
         let manifest_hash = blake3::hash(&manifest_bytes);
-        let manifest_signature = signing_key.sign(manifest_hash.as_bytes());
+        let manifest_signature = sign_manifest(signing_key, &manifest_bytes, binding);
 
         let signed_manifest = SignedManifest {
             manifest: manifest_bytes,
@@ -353,18 +1377,13 @@ impl Envelope {
         );
 
         // Encrypt the chunk data using the envelope-level key
-        let cipher =
-            Aes256Gcm::new_from_slice(encryption_key).context("Failed to create cipher")?;
+        let cipher = chunk_cipher(
+            AeadAlgorithm::try_from(metadata.aead_algorithm)?,
+            encryption_key,
+        )?;
 
         let mut ciphertext = chunk_data.to_vec();
-        let nonce_array: &[u8; 12] = nonce
-            .as_slice()
-            .try_into()
-            .context("Nonce conversion failed")?;
-
-        cipher
-            .encrypt_in_place(nonce_array.into(), &aad, &mut ciphertext)
-            .map_err(|e| anyhow::anyhow!("Encryption failed: {:?}", e))?;
+        cipher.encrypt_in_place(&nonce, &aad, &mut ciphertext)?;
 
         // Create the network chunk
         let signed_manifest_bytes =
@@ -378,17 +1397,18 @@ impl Envelope {
         ))
     }
 
-    /// Verify a chunk's cryptographic signature
-    fn verify_chunk_signature(&self, chunk: &NetworkChunk) -> bool {
+    /// Verify a chunk's manifest signature against the sender's key
+    fn verify_chunk_signature(
+        chunk: &NetworkChunk,
+        binding: Option<&[u8; 32]>,
+        verifying_key_bytes: &[u8; 32],
+    ) -> bool {
         // Deserialize the signed manifest
         let signed_manifest: SignedManifest = match bincode::deserialize(&chunk.manifest) {
             Ok(sm) => sm,
             Err(_) => return false,
         };
 
-        // Verify the manifest signature
-        let manifest_hash = blake3::hash(&signed_manifest.manifest);
-
         // Convert signature bytes to Signature
         let signature_bytes: [u8; 64] = match signed_manifest.sig.try_into() {
             Ok(bytes) => bytes,
@@ -401,14 +1421,17 @@ impl Envelope {
         };
 
         // Get the verifying key from the envelope (not from manifest for consistency)
-        let verifying_key = match VerifyingKey::from_bytes(&self.verifying_key_bytes) {
+        let verifying_key = match VerifyingKey::from_bytes(verifying_key_bytes) {
             Ok(key) => key,
             Err(_) => return false,
         };
 
-        verifying_key
-            .verify(manifest_hash.as_bytes(), &signature)
-            .is_ok()
+        verify_manifest(
+            &verifying_key,
+            &signed_manifest.manifest,
+            binding,
+            &signature,
+        )
     }
 
     /// Verify that all chunks are present and in sequence
@@ -436,14 +1459,12 @@ impl Envelope {
     /// The deterministic nonce is reconstructed as:
     ///   `nonce_prefix[0..8] || chunk_index[1..4] (BE u32 low 3 bytes) || last_flag`
     fn decrypt_chunk_v2(
-        &self,
         chunk: &NetworkChunk,
+        metadata: &EnvelopeMetadata,
         encryption_key: &[u8; 32],
         nonce_prefix: &[u8; 8],
         is_last_chunk: bool,
     ) -> Result<Vec<u8>> {
-        use aes_gcm::{AeadInPlace, Aes256Gcm, KeyInit};
-
         // Deserialize the signed manifest to get chunk metadata
         let signed_manifest: SignedManifest = bincode::deserialize(&chunk.manifest)
             .context("Failed to deserialize signed manifest")?;
@@ -451,20 +1472,8 @@ impl Envelope {
         let manifest: ChunkManifest = bincode::deserialize(&signed_manifest.manifest)
             .context("Failed to deserialize chunk manifest")?;
 
-        // Guard: only 3 bytes of the u32 are used in the nonce (bytes 8..11),
-        // so chunk index must fit in 24 bits.
-        if manifest.sequence > MAX_CHUNK_INDEX {
-            return Err(anyhow::anyhow!(
-                "Chunk index exceeds maximum (16,777,215) — envelope too large for current nonce format"
-            ));
-        }
-
         // Reconstruct the deterministic 12-byte nonce (must match create_encrypted_chunk)
-        let mut nonce = [0u8; NONCE_LEN];
-        nonce[0..8].copy_from_slice(nonce_prefix);
-        let idx_be = (manifest.sequence as u32).to_be_bytes();
-        nonce[8..11].copy_from_slice(&idx_be[1..4]); // low 3 bytes of BE u32
-        nonce[11] = if is_last_chunk { 0xFF } else { 0x00 };
+        let nonce = chunk_nonce(nonce_prefix, manifest.sequence, is_last_chunk)?;
 
         // Recreate the AAD used during encryption
         let header_hash = blake3::hash(b"ENVELOPE_V1");
@@ -477,24 +1486,83 @@ impl Envelope {
             manifest.chunk_size,
         );
 
-        // Create the cipher and decrypt
-        let cipher = Aes256Gcm::new_from_slice(encryption_key)
-            .context("Failed to create cipher for v2 decryption")?;
-
-        let nonce_array: &[u8; 12] = nonce
-            .as_slice()
-            .try_into()
-            .context("Nonce conversion failed")?;
+        // Create the cipher for the envelope's AEAD and decrypt
+        let cipher = chunk_cipher(
+            AeadAlgorithm::try_from(metadata.aead_algorithm)?,
+            encryption_key,
+        )
+        .context("Failed to create cipher for v2 decryption")?;
 
         let mut plaintext = chunk.data.clone();
         cipher
-            .decrypt_in_place(nonce_array.into(), &aad, &mut plaintext)
-            .map_err(|e| anyhow::anyhow!("V2 decryption failed: {:?}", e))?;
+            .decrypt_in_place(&nonce, &aad, &mut plaintext)
+            .context("V2 decryption failed")?;
 
         Ok(plaintext)
     }
 }
 
+/// Chunk AEAD selected by [`EnvelopeMetadata::aead_algorithm`]
+fn chunk_cipher(algorithm: AeadAlgorithm, key: &[u8; 32]) -> Result<ChunkCipher> {
+    let aead = match algorithm {
+        AeadAlgorithm::Aes256Gcm => ChunkAead::Aes256Gcm,
+        AeadAlgorithm::ChaCha20Poly1305 => ChunkAead::ChaCha20Poly1305,
+        AeadAlgorithm::Aes256Siv => {
+            return Err(anyhow::anyhow!(
+                "AEAD algorithm {:?} is not supported for envelopes",
+                algorithm
+            ))
+        }
+    };
+    Ok(ChunkCipher::new(aead, key))
+}
+
+/// Longest accepted `content_type`, the RFC 6838 limit of 127 characters each for
+/// type and subtype plus the slash
+const MAX_CONTENT_TYPE_LEN: usize = 255;
+
+/// Check that `content_type` looks like a MIME type (`type/subtype[; params]`)
+fn validate_content_type(content_type: &str) -> Result<()> {
+    let well_formed = content_type.len() <= MAX_CONTENT_TYPE_LEN
+        && content_type.is_ascii()
+        && !content_type.chars().any(|c| c.is_ascii_control())
+        && content_type
+            .split(';')
+            .next()
+            .and_then(|essence| essence.trim().split_once('/'))
+            .is_some_and(|(kind, subtype)| !kind.is_empty() && !subtype.is_empty());
+    if !well_formed {
+        return Err(anyhow::anyhow!("Invalid content type: {:?}", content_type));
+    }
+    Ok(())
+}
+
+/// BLAKE3 key-derivation context for [`signature_binding`]
+const SIGNATURE_BINDING_CONTEXT: &str = "SEALEDGE_ENVELOPE_V3_METADATA";
+
+/// Hash of the envelope fields that version 3 chunk signatures cover besides the
+/// chunk manifest
+///
+/// This is the complete bincode encoding of the metadata, so every field — AEAD,
+/// compression, sizes, expiry, content type and any added later — is signed
+/// without having to be listed here, followed by the ordered recipient list so
+/// recipients cannot be added, dropped or reordered.
+fn signature_binding(
+    metadata: &EnvelopeMetadata,
+    recipients: &[WrappedContentKey],
+) -> Result<[u8; 32]> {
+    let metadata_bytes =
+        bincode::serialize(metadata).context("Failed to serialize envelope metadata")?;
+    let recipient_bytes =
+        bincode::serialize(recipients).context("Failed to serialize envelope recipients")?;
+    Ok(blake3::Hasher::new_derive_key(SIGNATURE_BINDING_CONTEXT)
+        .update(&(metadata_bytes.len() as u64).to_le_bytes())
+        .update(&metadata_bytes)
+        .update(&recipient_bytes)
+        .finalize()
+        .into())
+}
+
 /// Manifest for a single chunk within an envelope
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct ChunkManifest {
@@ -518,6 +1586,25 @@ mod tests {
     use ed25519_dalek::SigningKey;
     use rand_core::OsRng;
 
+    #[test]
+    fn test_constant_time_key_comparison_semantics() {
+        let key = SigningKey::generate(&mut OsRng).verifying_key();
+        let other = SigningKey::generate(&mut OsRng).verifying_key();
+        let mut one_bit_off = key.to_bytes();
+        one_bit_off[31] ^= 1;
+
+        assert!(keys_equal(key.as_bytes(), &key.to_bytes()));
+        assert!(!keys_equal(key.as_bytes(), other.as_bytes()));
+        assert!(!keys_equal(key.as_bytes(), &one_bit_off));
+
+        // EnvelopeIssuer equality is unchanged and goes through keys_equal
+        CONSTANT_TIME_KEY_COMPARISONS.with(|count| count.set(0));
+        assert!(EnvelopeIssuer::Identified(key) == key);
+        assert!(EnvelopeIssuer::Identified(key) != other);
+        assert!(EnvelopeIssuer::Anonymous(key) != key);
+        assert_eq!(CONSTANT_TIME_KEY_COMPARISONS.with(|count| count.get()), 2);
+    }
+
     #[test]
     fn test_envelope_creation() {
         let signing_key = SigningKey::generate(&mut OsRng);
@@ -701,6 +1788,150 @@ mod tests {
         assert_eq!(hash1, hash2);
     }
 
+    #[test]
+    fn test_seal_deterministic_is_reproducible() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+        let seed = [7u8; 32];
+        let payload = b"Reproducible build attestation";
+
+        let first = Envelope::seal_deterministic(
+            payload,
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            &seed,
+        )
+        .unwrap();
+        let second = Envelope::seal_deterministic(
+            payload,
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            &seed,
+        )
+        .unwrap();
+
+        assert_eq!(
+            bincode::serialize(&first).unwrap(),
+            bincode::serialize(&second).unwrap()
+        );
+        assert!(first.verify());
+        assert_eq!(first.unseal(&beneficiary_key).unwrap(), payload);
+
+        // A different seed yields a different envelope
+        let reseeded = Envelope::seal_deterministic(
+            payload,
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            &[8u8; 32],
+        )
+        .unwrap();
+        assert_ne!(first.hash().unwrap(), reseeded.hash().unwrap());
+    }
+
+    #[test]
+    fn test_seal_deterministic_binds_options_into_salt() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+        let seed = [7u8; 32];
+        let payload = b"Reproducible build attestation";
+        let base = SealOptions::new().with_deterministic_seed(seed);
+
+        let seal = |options: &SealOptions| {
+            Envelope::seal_with_options(
+                payload,
+                &signing_key,
+                &beneficiary_key.verifying_key(),
+                options,
+            )
+            .unwrap()
+        };
+        let nonce_prefix = |envelope: &Envelope| envelope.chunks[0].nonce[..8].to_vec();
+
+        let plain = seal(&base);
+        let variants = [
+            base.clone().with_compression(CompressionAlgorithm::Zstd),
+            base.clone().with_aead(AeadAlgorithm::ChaCha20Poly1305),
+            base.clone().with_expiry(u64::MAX),
+            base.clone().with_content_type("text/plain"),
+        ];
+        for options in &variants {
+            let other = seal(options);
+            assert_ne!(plain.hkdf_salt, other.hkdf_salt);
+            assert_ne!(nonce_prefix(&plain), nonce_prefix(&other));
+            assert_eq!(other.unseal(&beneficiary_key).unwrap(), payload);
+        }
+    }
+
+    #[test]
+    fn test_reseal_to_new_recipient() {
+        let alice = SigningKey::generate(&mut OsRng);
+        let bob = SigningKey::generate(&mut OsRng);
+        let carol = SigningKey::generate(&mut OsRng);
+        let payload = b"Forwarded to the next holder";
+
+        let original =
+            Envelope::seal_with_content_type(payload, &alice, &bob.verifying_key(), "text/plain")
+                .unwrap();
+        let resealed = original.reseal(&bob, &carol.verifying_key()).unwrap();
+
+        assert!(resealed.verify());
+        assert_eq!(resealed.unseal(&carol).unwrap(), payload);
+        assert_eq!(resealed.beneficiary().unwrap(), carol.verifying_key());
+        assert_eq!(resealed.issuer().unwrap(), bob.verifying_key());
+        assert_eq!(resealed.content_type(), Some("text/plain"));
+        assert_eq!(
+            resealed.metadata().aead_algorithm,
+            original.metadata().aead_algorithm
+        );
+
+        // The previous holder can no longer open it
+        assert!(resealed.unseal(&bob).is_err());
+    }
+
+    #[test]
+    fn test_reseal_with_wrong_holder_fails() {
+        let alice = SigningKey::generate(&mut OsRng);
+        let bob = SigningKey::generate(&mut OsRng);
+        let mallory = SigningKey::generate(&mut OsRng);
+
+        let envelope = Envelope::seal(b"not yours", &alice, &bob.verifying_key()).unwrap();
+        assert!(envelope.reseal(&mallory, &mallory.verifying_key()).is_err());
+    }
+
+    #[test]
+    fn test_reseal_rejects_anonymous_envelope() {
+        let bob = SigningKey::generate(&mut OsRng);
+        let carol = SigningKey::generate(&mut OsRng);
+
+        let envelope = Envelope::seal_anonymous(b"no sender", &bob.verifying_key()).unwrap();
+        let err = envelope.reseal(&bob, &carol.verifying_key()).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<EnvelopeError>(),
+            Some(EnvelopeError::NotResealable(_))
+        ));
+    }
+
+    #[test]
+    fn test_reseal_rejects_encryption_key_envelope() {
+        let alice = SigningKey::generate(&mut OsRng);
+        let bob = SigningKey::generate(&mut OsRng);
+        let carol = SigningKey::generate(&mut OsRng);
+        let bob_encryption = x25519_dalek::StaticSecret::random_from_rng(OsRng);
+
+        let envelope = Envelope::seal_for_encryption_key(
+            b"for bob's device key",
+            &alice,
+            &bob.verifying_key(),
+            &x25519_dalek::PublicKey::from(&bob_encryption),
+        )
+        .unwrap();
+        let err = envelope.reseal(&bob, &carol.verifying_key()).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<EnvelopeError>(),
+            Some(EnvelopeError::NotResealable(_))
+        ));
+    }
+
     #[test]
     fn test_v2_multi_chunk_roundtrip() {
         let signing_key = SigningKey::generate(&mut OsRng);
@@ -715,7 +1946,10 @@ mod tests {
             .expect("Failed to seal multi-chunk v2 envelope");
 
         // Assert v2 format fields
-        assert_eq!(envelope.version, 2, "Sealed envelope must be version 2");
+        assert_eq!(
+            envelope.version, ENVELOPE_VERSION,
+            "Sealed envelope must use the current layout"
+        );
         assert_ne!(
             envelope.hkdf_salt, [0u8; 32],
             "hkdf_salt must be non-zero (randomly generated)"
@@ -748,7 +1982,7 @@ mod tests {
         let envelope = Envelope::seal(payload, &signing_key, &beneficiary_key.verifying_key())
             .expect("Failed to seal single-chunk v2 envelope");
 
-        assert_eq!(envelope.version, 2);
+        assert_eq!(envelope.version, ENVELOPE_VERSION);
         assert_eq!(envelope.metadata.chunk_count, 1);
 
         assert!(envelope.verify());
@@ -769,14 +2003,14 @@ mod tests {
         let envelope = Envelope::seal(payload, &signing_key, &beneficiary_key.verifying_key())
             .expect("Failed to seal envelope");
 
-        // Assert version field is 2
-        assert_eq!(envelope.version, 2);
+        // New envelopes use the current layout
+        assert_eq!(envelope.version, ENVELOPE_VERSION);
 
         // Serialize to JSON and check the field is present
         let json = serde_json::to_string(&envelope).expect("Failed to serialize envelope to JSON");
         assert!(
-            json.contains("\"version\":2"),
-            "JSON must contain \"version\":2, got: {}",
+            json.contains("\"version\":3"),
+            "JSON must contain \"version\":3, got: {}",
             &json[..json.len().min(200)]
         );
 
@@ -784,7 +2018,7 @@ mod tests {
         let deserialized: Envelope =
             serde_json::from_str(&json).expect("Failed to deserialize envelope from JSON");
         assert_eq!(
-            deserialized.version, 2,
+            deserialized.version, ENVELOPE_VERSION,
             "Version must be preserved through serialization roundtrip"
         );
     }
@@ -841,6 +2075,735 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_seal_for_many_all_recipients_unseal() {
+        let sender = SigningKey::generate(&mut OsRng);
+        let recipients: Vec<SigningKey> =
+            (0..3).map(|_| SigningKey::generate(&mut OsRng)).collect();
+        let recipient_keys: Vec<VerifyingKey> =
+            recipients.iter().map(|k| k.verifying_key()).collect();
+
+        let payload: Vec<u8> = (0..(DEFAULT_CHUNK_SIZE + 300))
+            .map(|i| (i % 241) as u8)
+            .collect();
+        let envelope = Envelope::seal_for_many(&payload, &sender, &recipient_keys)
+            .expect("Failed to seal for many");
+
+        assert!(envelope.verify());
+        assert_eq!(envelope.recipients().unwrap(), recipient_keys);
+        assert_eq!(envelope.beneficiary().unwrap(), recipient_keys[0]);
+
+        for recipient in &recipients {
+            let recovered = envelope.unseal(recipient).expect("Recipient should unseal");
+            assert_eq!(recovered, payload);
+        }
+    }
+
+    #[test]
+    fn test_seal_for_many_rejects_unlisted_key() {
+        let sender = SigningKey::generate(&mut OsRng);
+        let recipients: Vec<VerifyingKey> = (0..3)
+            .map(|_| SigningKey::generate(&mut OsRng).verifying_key())
+            .collect();
+        let outsider = SigningKey::generate(&mut OsRng);
+
+        let envelope = Envelope::seal_for_many(b"broadcast", &sender, &recipients)
+            .expect("Failed to seal for many");
+
+        assert!(envelope.unseal(&outsider).is_err());
+        assert!(envelope.unseal(&sender).is_err());
+
+        assert!(Envelope::seal_for_many(b"broadcast", &sender, &[]).is_err());
+        assert!(
+            Envelope::seal_for_many(b"broadcast", &sender, &[recipients[0], recipients[0]])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_recipient_list_is_signed() {
+        let sender = SigningKey::generate(&mut OsRng);
+        let keys: Vec<SigningKey> = (0..3).map(|_| SigningKey::generate(&mut OsRng)).collect();
+        let recipients: Vec<VerifyingKey> = keys.iter().map(|k| k.verifying_key()).collect();
+        let intruder = SigningKey::generate(&mut OsRng);
+
+        let envelope = Envelope::seal_for_many(b"broadcast", &sender, &recipients)
+            .expect("Failed to seal for many");
+        assert!(envelope.verify());
+
+        let mut dropped = envelope.clone();
+        dropped.recipients.remove(1);
+        let mut reordered = envelope.clone();
+        reordered.recipients.swap(0, 2);
+        let mut added = envelope.clone();
+        let other = Envelope::seal_for_many(b"other", &sender, &[intruder.verifying_key()])
+            .expect("Failed to seal for many");
+        added.recipients.push(other.recipients[0].clone());
+
+        for tampered in [dropped, reordered, added] {
+            assert!(!tampered.verify());
+            assert_eq!(
+                tampered.unseal(&keys[0]),
+                Err(EnvelopeError::SignatureInvalid)
+            );
+        }
+    }
+
+    #[test]
+    fn test_seal_for_many_stores_ciphertext_once() {
+        let sender = SigningKey::generate(&mut OsRng);
+        let recipients: Vec<VerifyingKey> = (0..3)
+            .map(|_| SigningKey::generate(&mut OsRng).verifying_key())
+            .collect();
+
+        let payload = vec![0x5Au8; 4096];
+        let envelope = Envelope::seal_for_many(&payload, &sender, &recipients)
+            .expect("Failed to seal for many");
+
+        let serialized = bincode::serialize(&envelope).expect("Failed to serialize envelope");
+        let ciphertext = &envelope.chunks[0].data;
+        let occurrences = serialized
+            .windows(ciphertext.len())
+            .filter(|window| *window == ciphertext.as_slice())
+            .count();
+        assert_eq!(occurrences, 1);
+    }
+
+    #[test]
+    fn test_compressed_envelope_roundtrip() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+
+        // 1 MB of repetitive JSON-like content compresses very well
+        let record = br#"{"chunk":"receipt","amount":1000,"status":"ok"},"#;
+        let payload: Vec<u8> = record.iter().copied().cycle().take(1024 * 1024).collect();
+
+        let plain = Envelope::seal(&payload, &signing_key, &beneficiary_key.verifying_key())
+            .expect("Failed to seal uncompressed envelope");
+        let compressed = Envelope::seal_with_compression(
+            &payload,
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            CompressionAlgorithm::Zstd,
+        )
+        .expect("Failed to seal compressed envelope");
+
+        let ciphertext_len =
+            |envelope: &Envelope| envelope.chunks.iter().map(|c| c.data.len()).sum::<usize>();
+        assert!(ciphertext_len(&compressed) * 10 < ciphertext_len(&plain));
+
+        assert_eq!(
+            compressed.metadata().compression,
+            CompressionAlgorithm::Zstd
+        );
+        assert_eq!(plain.metadata().compression, CompressionAlgorithm::None);
+        assert_eq!(compressed.metadata().payload_size, payload.len() as u64);
+
+        assert!(compressed.verify());
+        let recovered = compressed
+            .unseal(&beneficiary_key)
+            .expect("Failed to unseal compressed envelope");
+        assert_eq!(recovered, payload);
+    }
+
+    #[test]
+    fn test_seal_with_alg_roundtrip_each_algorithm() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+        // Spans several chunks so every nonce position is exercised
+        let payload: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+
+        for algorithm in [AeadAlgorithm::Aes256Gcm, AeadAlgorithm::ChaCha20Poly1305] {
+            let envelope = Envelope::seal_with_alg(
+                &payload,
+                &signing_key,
+                &beneficiary_key.verifying_key(),
+                algorithm,
+            )
+            .expect("Failed to seal envelope");
+            assert_eq!(envelope.metadata().aead_algorithm, algorithm as u8);
+            assert!(envelope.verify());
+            assert_eq!(envelope.unseal(&beneficiary_key).unwrap(), payload);
+        }
+
+        assert!(Envelope::seal_with_alg(
+            &payload,
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            AeadAlgorithm::Aes256Siv,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_chacha_envelope_fails_when_decrypted_as_aes() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+        let payload = b"sealed with chacha".to_vec();
+
+        let mut envelope = Envelope::seal_with_alg(
+            &payload,
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            AeadAlgorithm::ChaCha20Poly1305,
+        )
+        .unwrap();
+        envelope.metadata.aead_algorithm = AeadAlgorithm::Aes256Gcm as u8;
+
+        assert!(envelope.unseal(&beneficiary_key).is_err());
+    }
+
+    /// Version 2 envelope sealed by the pre-extension code, from sender key
+    /// `[1; 32]` to recipient key `[2; 32]`
+    const LEGACY_FIXTURE_B64: &str = include_str!("../tests/fixtures/envelope.v2.b64");
+    const LEGACY_FIXTURE_JSON: &str = include_str!("../tests/fixtures/envelope.v2.json");
+    const LEGACY_FIXTURE_HASH: &str =
+        "2e106ffbd7175134e0d6ed4b06bac1f30d880773c2a974cdaffe85592fe864d2";
+
+    #[test]
+    fn test_legacy_envelope_fixture_decodes_and_keeps_its_hash() {
+        let recipient = SigningKey::from_bytes(&[2u8; 32]);
+
+        let legacy = Envelope::from_base64(LEGACY_FIXTURE_B64).expect("legacy envelope decodes");
+        assert_eq!(legacy.version(), LEGACY_ENVELOPE_VERSION);
+        assert_eq!(hex::encode(legacy.hash().unwrap()), LEGACY_FIXTURE_HASH);
+        assert!(legacy.verify());
+        assert_eq!(legacy.metadata().compression, CompressionAlgorithm::None);
+        assert_eq!(legacy.metadata().expires_at, None);
+        assert!(legacy.recipients().unwrap().is_empty());
+        assert_eq!(
+            legacy.unseal(&recipient).unwrap(),
+            b"legacy envelope fixture"
+        );
+
+        // Re-encoding keeps the legacy layout byte for byte
+        assert_eq!(legacy.to_base64().unwrap(), LEGACY_FIXTURE_B64.trim());
+        let from_pem = Envelope::from_pem(&legacy.to_pem().unwrap()).unwrap();
+        assert_eq!(hex::encode(from_pem.hash().unwrap()), LEGACY_FIXTURE_HASH);
+
+        // The same envelope stored as JSON hashes identically
+        let from_json: Envelope = serde_json::from_str(LEGACY_FIXTURE_JSON).unwrap();
+        assert_eq!(from_json.version(), LEGACY_ENVELOPE_VERSION);
+        assert_eq!(hex::encode(from_json.hash().unwrap()), LEGACY_FIXTURE_HASH);
+        assert_eq!(
+            serde_json::to_string(&from_json).unwrap(),
+            LEGACY_FIXTURE_JSON.trim()
+        );
+    }
+
+    #[test]
+    fn test_extended_metadata_survives_binary_encoding() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+        let encryption_secret = x25519_dalek::StaticSecret::random_from_rng(OsRng);
+
+        let envelopes = [
+            Envelope::seal_anonymous(b"anonymous", &beneficiary_key.verifying_key()).unwrap(),
+            Envelope::seal_with_content_type(
+                b"typed",
+                &signing_key,
+                &beneficiary_key.verifying_key(),
+                "text/plain",
+            )
+            .unwrap(),
+            Envelope::seal_for_encryption_key(
+                b"separate key",
+                &signing_key,
+                &beneficiary_key.verifying_key(),
+                &x25519_dalek::PublicKey::from(&encryption_secret),
+            )
+            .unwrap(),
+        ];
+
+        for envelope in envelopes {
+            let decoded = Envelope::from_base64(&envelope.to_base64().unwrap()).unwrap();
+            assert_eq!(decoded.version(), ENVELOPE_VERSION);
+            assert_eq!(decoded.hash().unwrap(), envelope.hash().unwrap());
+            assert_eq!(decoded.metadata().anonymous, envelope.metadata().anonymous);
+            assert_eq!(decoded.content_type(), envelope.content_type());
+            assert_eq!(decoded.encryption_key(), envelope.encryption_key());
+            assert!(decoded.verify());
+        }
+    }
+
+    #[test]
+    fn test_envelope_layout_must_match_version() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+        let envelope = Envelope::seal_with_expiry(
+            b"layout",
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            now_secs() + 60,
+        )
+        .unwrap();
+
+        let decoded = Envelope::from_base64(&envelope.to_base64().unwrap()).unwrap();
+        assert_eq!(decoded.version(), ENVELOPE_VERSION);
+        assert_eq!(decoded.hash().unwrap(), envelope.hash().unwrap());
+
+        // Relabelling a current envelope as legacy, or the reverse, is rejected
+        let mut bytes = bincode::serialize(&envelope).unwrap();
+        bytes[0] = LEGACY_ENVELOPE_VERSION;
+        assert!(Envelope::from_base64(&BASE64.encode(&bytes)).is_err());
+        bytes[0] = 9;
+        assert!(Envelope::from_base64(&BASE64.encode(&bytes))
+            .unwrap_err()
+            .to_string()
+            .contains("unsupported envelope version 9"));
+
+        let mut json = serde_json::to_value(&envelope).unwrap();
+        json["version"] = LEGACY_ENVELOPE_VERSION.into();
+        assert!(serde_json::from_value::<Envelope>(json).is_err());
+        let mut legacy_json: serde_json::Value = serde_json::from_str(LEGACY_FIXTURE_JSON).unwrap();
+        legacy_json["version"] = ENVELOPE_VERSION.into();
+        assert!(serde_json::from_value::<Envelope>(legacy_json).is_err());
+    }
+
+    #[test]
+    fn test_pem_roundtrip_preserves_envelope() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+        let payload: Vec<u8> = (0..(DEFAULT_CHUNK_SIZE + 10))
+            .map(|i| (i % 199) as u8)
+            .collect();
+
+        let envelope = Envelope::seal(&payload, &signing_key, &beneficiary_key.verifying_key())
+            .expect("Failed to seal envelope");
+
+        let pem = envelope.to_pem().expect("Failed to encode PEM");
+        assert!(pem.starts_with("-----BEGIN SEALEDGE ENVELOPE-----\n"));
+        assert!(pem.ends_with("-----END SEALEDGE ENVELOPE-----\n"));
+        assert!(pem
+            .lines()
+            .all(|line| line.len() <= 64 || line.starts_with("-----")));
+        assert!(pem.is_ascii());
+
+        let decoded = Envelope::from_pem(&pem).expect("Failed to decode PEM");
+        assert_eq!(decoded.hash().unwrap(), envelope.hash().unwrap());
+        assert_eq!(decoded.issuer().unwrap(), envelope.issuer().unwrap());
+        assert_eq!(
+            decoded.beneficiary().unwrap(),
+            envelope.beneficiary().unwrap()
+        );
+        assert!(decoded.verify());
+        assert_eq!(decoded.unseal(&beneficiary_key).unwrap(), payload);
+
+        let from_b64 =
+            Envelope::from_base64(&envelope.to_base64().unwrap()).expect("Failed to decode base64");
+        assert_eq!(from_b64.hash().unwrap(), envelope.hash().unwrap());
+    }
+
+    #[test]
+    fn test_pem_rejects_corrupted_armor() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+        let envelope = Envelope::seal(b"armored", &signing_key, &beneficiary_key.verifying_key())
+            .expect("Failed to seal envelope");
+        let pem = envelope.to_pem().unwrap();
+
+        // Wrong label
+        let relabeled = pem.replace("SEALEDGE ENVELOPE", "SEALEDGE RECEIPT");
+        assert!(Envelope::from_pem(&relabeled).is_err());
+
+        // Missing footer
+        let truncated: String = pem.lines().take(2).collect::<Vec<_>>().join("\n");
+        assert!(Envelope::from_pem(&truncated).is_err());
+
+        // Invalid base64 character in the body
+        let mut lines: Vec<String> = pem.lines().map(str::to_string).collect();
+        lines[1].replace_range(0..1, "*");
+        assert!(Envelope::from_pem(&lines.join("\n")).is_err());
+
+        // Body cut short decodes as base64 but not as an envelope
+        let mut lines: Vec<String> = pem.lines().map(str::to_string).collect();
+        lines[1].truncate(8);
+        lines.truncate(2);
+        lines.push("-----END SEALEDGE ENVELOPE-----".to_string());
+        assert!(Envelope::from_pem(&lines.join("\n")).is_err());
+    }
+
+    fn now_secs() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[test]
+    fn test_unexpired_envelope_unseals() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+
+        let envelope = Envelope::seal_with_expiry(
+            b"time sensitive",
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            now_secs() + 3600,
+        )
+        .expect("Failed to seal envelope");
+
+        assert!(envelope.verify());
+        assert_eq!(
+            envelope.unseal(&beneficiary_key).unwrap(),
+            b"time sensitive"
+        );
+    }
+
+    #[test]
+    fn test_expired_envelope_fails_with_expired_error() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+        let expires_at = now_secs() - 60;
+
+        let envelope = Envelope::seal_with_expiry(
+            b"too late",
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            expires_at,
+        )
+        .expect("Failed to seal envelope");
+
+        assert!(envelope.verify());
+        match envelope.unseal(&beneficiary_key).unwrap_err() {
+            EnvelopeError::Expired {
+                expires_at: reported,
+                ..
+            } => assert_eq!(reported, expires_at),
+            other => panic!("Expected EnvelopeError::Expired, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_envelope_expires_at_the_expiry_second() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+        let expires_at = 1_700_000_500;
+
+        let envelope = Envelope::seal_with_expiry(
+            b"boundary",
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            expires_at,
+        )
+        .expect("Failed to seal envelope");
+
+        let clock = crate::clock::MockClock::new(expires_at - 1);
+        assert!(!envelope.is_expired_at(clock.now_secs()));
+        assert_eq!(
+            envelope
+                .unseal_with_clock(&beneficiary_key, &clock)
+                .unwrap(),
+            b"boundary"
+        );
+
+        clock.advance(1);
+        assert!(envelope.is_expired_at(clock.now_secs()));
+        assert_eq!(
+            envelope.unseal_with_clock(&beneficiary_key, &clock),
+            Err(EnvelopeError::Expired {
+                expires_at,
+                now: expires_at
+            })
+        );
+    }
+
+    #[test]
+    fn test_unseal_failures_map_to_error_variants() {
+        let sender = SigningKey::generate(&mut OsRng);
+        let recipient = SigningKey::generate(&mut OsRng);
+        let outsider = SigningKey::generate(&mut OsRng);
+        let envelope = Envelope::seal_with_content_type(
+            b"payload",
+            &sender,
+            &recipient.verifying_key(),
+            "text/plain",
+        )
+        .expect("Failed to seal envelope");
+
+        let mut relabelled = envelope.clone();
+        relabelled.metadata.content_type = Some("text/html".to_string());
+        assert_eq!(
+            relabelled.unseal(&recipient),
+            Err(EnvelopeError::SignatureInvalid)
+        );
+
+        assert_eq!(
+            envelope.unseal(&outsider),
+            Err(EnvelopeError::WrongRecipient)
+        );
+        let many = Envelope::seal_for_many(b"payload", &sender, &[recipient.verifying_key()])
+            .expect("Failed to seal envelope");
+        assert_eq!(many.unseal(&outsider), Err(EnvelopeError::WrongRecipient));
+        let encryption = x25519_dalek::StaticSecret::random_from_rng(OsRng);
+        assert_eq!(
+            envelope.unseal_with_encryption_key(&encryption),
+            Err(EnvelopeError::WrongRecipient)
+        );
+
+        // The salt is not signed, so a flipped salt only shows up as a failed decrypt
+        let mut resalted = envelope.clone();
+        resalted.hkdf_salt[0] ^= 0x01;
+        assert!(matches!(
+            resalted.unseal(&recipient),
+            Err(EnvelopeError::Decrypt(_))
+        ));
+
+        assert!(matches!(
+            Envelope::from_base64("not base64!"),
+            Err(EnvelopeError::Deserialize(_))
+        ));
+        assert!(matches!(
+            Envelope::from_base64(&BASE64.encode(b"not an envelope")),
+            Err(EnvelopeError::Deserialize(_))
+        ));
+        assert!(matches!(
+            Envelope::from_pem("-----BEGIN SOMETHING ELSE-----"),
+            Err(EnvelopeError::Deserialize(_))
+        ));
+
+        // Context added by callers does not hide the variant
+        let err = envelope
+            .unseal(&outsider)
+            .context("Failed to unseal envelope")
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<EnvelopeError>(),
+            Some(&EnvelopeError::WrongRecipient)
+        );
+    }
+
+    #[test]
+    fn test_expiry_tampering_breaks_verification() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+
+        let envelope = Envelope::seal_with_expiry(
+            b"expiring",
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            now_secs() - 60,
+        )
+        .expect("Failed to seal envelope");
+
+        // Pushing the deadline out is detected
+        let mut extended = envelope.clone();
+        extended.metadata.expires_at = Some(now_secs() + 3600);
+        assert!(!extended.verify());
+        assert_eq!(
+            extended.unseal(&beneficiary_key),
+            Err(EnvelopeError::SignatureInvalid)
+        );
+
+        // Removing the deadline entirely is detected
+        let mut stripped = envelope;
+        stripped.metadata.expires_at = None;
+        assert!(!stripped.verify());
+        assert!(stripped.unseal(&beneficiary_key).is_err());
+    }
+
+    #[test]
+    fn test_every_metadata_field_is_signed() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+
+        let envelope = Envelope::seal_with_options(
+            &[3u8; 10_000],
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            &SealOptions::new()
+                .with_aead(AeadAlgorithm::ChaCha20Poly1305)
+                .with_compression(CompressionAlgorithm::Zstd),
+        )
+        .expect("Failed to seal envelope");
+        assert!(envelope.verify());
+
+        let tamperings: [fn(&mut EnvelopeMetadata); 5] = [
+            |m| m.aead_algorithm = AeadAlgorithm::Aes256Gcm as u8,
+            |m| m.compression = CompressionAlgorithm::None,
+            |m| m.payload_size += 1,
+            |m| m.created_at += 1,
+            |m| m.hash_algorithm ^= 0xFF,
+        ];
+        for tamper in tamperings {
+            let mut tampered = envelope.clone();
+            tamper(&mut tampered.metadata);
+            assert!(!tampered.verify());
+            assert_eq!(
+                tampered.unseal(&beneficiary_key),
+                Err(EnvelopeError::SignatureInvalid)
+            );
+        }
+    }
+
+    #[test]
+    fn test_anonymous_envelope_unseals_for_recipient() {
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+
+        let envelope = Envelope::seal_anonymous(b"drop", &beneficiary_key.verifying_key())
+            .expect("Failed to seal anonymous envelope");
+
+        assert!(envelope.metadata().anonymous);
+        assert!(envelope.issuer().unwrap().is_anonymous());
+        assert_eq!(envelope.unseal(&beneficiary_key).unwrap(), b"drop");
+    }
+
+    #[test]
+    fn test_anonymous_envelopes_have_distinct_issuers() {
+        let beneficiary_key = SigningKey::generate(&mut OsRng).verifying_key();
+
+        let first = Envelope::seal_anonymous(b"one", &beneficiary_key).unwrap();
+        let second = Envelope::seal_anonymous(b"two", &beneficiary_key).unwrap();
+
+        let first_issuer = first.issuer().unwrap();
+        let second_issuer = second.issuer().unwrap();
+        assert_ne!(first_issuer.verifying_key(), second_issuer.verifying_key());
+        // An ephemeral key never stands in for a long-term identity
+        assert_ne!(first_issuer, first_issuer.verifying_key());
+    }
+
+    #[test]
+    fn test_content_type_round_trips_without_recipient_key() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+
+        let envelope = Envelope::seal_with_content_type(
+            b"{\"reading\": 21.5}",
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            "application/json",
+        )
+        .expect("Failed to seal envelope");
+
+        // A relay holding only the wire bytes can route on the content type
+        let relayed = Envelope::from_base64(&envelope.to_base64().unwrap()).unwrap();
+        assert!(relayed.verify());
+        assert_eq!(relayed.content_type(), Some("application/json"));
+        assert_eq!(
+            relayed.unseal(&beneficiary_key).unwrap(),
+            b"{\"reading\": 21.5}"
+        );
+
+        let plain = Envelope::seal(b"untyped", &signing_key, &beneficiary_key.verifying_key())
+            .expect("Failed to seal envelope");
+        assert_eq!(plain.content_type(), None);
+    }
+
+    #[test]
+    fn test_seal_options_combine() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+        let payload = vec![7u8; 100_000];
+
+        let options = SealOptions::new()
+            .with_aead(AeadAlgorithm::ChaCha20Poly1305)
+            .with_compression(CompressionAlgorithm::Zstd)
+            .with_expiry(now_secs() + 3600)
+            .with_content_type("application/octet-stream");
+        let envelope = Envelope::seal_with_options(
+            &payload,
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            &options,
+        )
+        .expect("Failed to seal envelope");
+
+        let metadata = envelope.metadata();
+        assert_eq!(
+            metadata.aead_algorithm,
+            AeadAlgorithm::ChaCha20Poly1305 as u8
+        );
+        assert_eq!(metadata.compression, CompressionAlgorithm::Zstd);
+        assert!(metadata.expires_at.is_some());
+        assert_eq!(envelope.content_type(), Some("application/octet-stream"));
+        assert_eq!(envelope.unseal(&beneficiary_key).unwrap(), payload);
+
+        // Content types are validated however they are supplied
+        assert!(Envelope::seal_with_options(
+            &payload,
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            &SealOptions::new().with_content_type("not a mime type"),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_encryption_key_is_bound_to_signatures() {
+        let sender = SigningKey::generate(&mut OsRng);
+        let recipient = SigningKey::generate(&mut OsRng);
+        let encryption = x25519_dalek::StaticSecret::random_from_rng(OsRng);
+        let other = x25519_dalek::StaticSecret::random_from_rng(OsRng);
+
+        let envelope = Envelope::seal_for_encryption_key(
+            b"for the encryption key",
+            &sender,
+            &recipient.verifying_key(),
+            &x25519_dalek::PublicKey::from(&encryption),
+        )
+        .unwrap();
+        assert_eq!(envelope.beneficiary().unwrap(), recipient.verifying_key());
+        assert_eq!(
+            envelope.encryption_key(),
+            Some(x25519_dalek::PublicKey::from(&encryption))
+        );
+        assert_eq!(
+            envelope.unseal_with_encryption_key(&encryption).unwrap(),
+            b"for the encryption key"
+        );
+        assert!(envelope.unseal(&recipient).is_err());
+        assert!(envelope.unseal_with_encryption_key(&other).is_err());
+
+        let mut redirected = envelope.clone();
+        redirected.metadata.encryption_key = Some(x25519_dalek::PublicKey::from(&other).to_bytes());
+        assert!(!redirected.verify());
+    }
+
+    #[test]
+    fn test_content_type_tampering_breaks_verification() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+
+        let envelope = Envelope::seal_with_content_type(
+            b"%PDF-1.7",
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            "application/pdf",
+        )
+        .expect("Failed to seal envelope");
+
+        let mut relabelled = envelope.clone();
+        relabelled.metadata.content_type = Some("text/html".to_string());
+        assert!(!relabelled.verify());
+        assert!(relabelled.unseal(&beneficiary_key).is_err());
+
+        let mut stripped = envelope;
+        stripped.metadata.content_type = None;
+        assert!(!stripped.verify());
+
+        assert!(Envelope::seal_with_content_type(
+            b"data",
+            &signing_key,
+            &beneficiary_key.verifying_key(),
+            "not a mime type",
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_anonymous_envelope_verifies_ephemeral_signature() {
+        let beneficiary_key = SigningKey::generate(&mut OsRng);
+
+        let envelope =
+            Envelope::seal_anonymous(b"signed", &beneficiary_key.verifying_key()).unwrap();
+        assert!(envelope.verify());
+
+        // Clearing the anonymous flag breaks the chunk signatures
+        let mut unmarked = envelope;
+        unmarked.metadata.anonymous = false;
+        assert!(!unmarked.verify());
+    }
+
     /// D-02 clean-break rejection tests: prove that the legacy HKDF info literal
     /// `b"TRUSTEDGE_ENVELOPE_V1"` and the new `b"SEALEDGE_ENVELOPE_V1"` produce
     /// distinct key material, and that a real seal/unseal round-trip using the
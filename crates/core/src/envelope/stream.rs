@@ -0,0 +1,408 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+// GitHub: https://github.com/TrustEdge-Labs/sealedge
+//
+
+//! Streaming envelopes for payloads larger than memory
+//!
+//! [`EnvelopeWriter`] seals chunks as they are written and [`EnvelopeReader`]
+//! verifies and decrypts them one at a time, so neither side holds more than one
+//! chunk. Chunks use the same signed manifests, ECDH + HKDF key and counter
+//! nonces as [`Envelope`](super::Envelope). Stream layout:
+//!
+//! ```text
+//! magic:   b"SESTRM01"
+//! header:  bincode StreamHeader   (salt, sender, recipient, created_at, AEAD)
+//! records: [len: u32 LE][bincode NetworkChunk] ...
+//! ```
+//!
+//! Data chunks are never flagged last. [`EnvelopeWriter::finalize`] appends an
+//! empty chunk carrying the last-chunk nonce flag, so a reader that hits end of
+//! input without seeing it knows the stream was truncated.
+
+use super::{
+    derive_shared_encryption_key, ChunkManifest, Envelope, EnvelopeMetadata, DEFAULT_CHUNK_SIZE,
+};
+use crate::format::{AeadAlgorithm, SignedManifest};
+use crate::NetworkChunk;
+use anyhow::{Context, Result};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use zeroize::Zeroizing;
+
+/// Leading bytes of a streaming envelope
+const STREAM_MAGIC: &[u8; 8] = b"SESTRM01";
+
+/// Largest encoded chunk record a reader will allocate for
+const MAX_RECORD_LEN: u32 = (DEFAULT_CHUNK_SIZE as u32) + 4096;
+
+/// Stream header written once after the magic
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct StreamHeader {
+    /// Per-stream random HKDF salt
+    hkdf_salt: [u8; 32],
+    /// Sender verifying key; chunk manifests are signed by it
+    sender_key: [u8; 32],
+    /// Recipient verifying key
+    recipient_key: [u8; 32],
+    /// Timestamp when the stream was opened
+    created_at: u64,
+    /// Chunk AEAD as an [`AeadAlgorithm`] discriminant
+    aead_algorithm: u8,
+}
+
+impl StreamHeader {
+    /// Envelope metadata that chunk signing and decryption key off
+    fn metadata(&self) -> Result<EnvelopeMetadata> {
+        let mut metadata = Envelope::build_metadata(&[])?;
+        metadata.created_at = self.created_at;
+        metadata.aead_algorithm = self.aead_algorithm;
+        Ok(metadata)
+    }
+}
+
+/// Seals a payload chunk by chunk into `output`
+pub struct EnvelopeWriter<W: Write> {
+    output: W,
+    signing_key: SigningKey,
+    encryption_key: Zeroizing<[u8; 32]>,
+    nonce_prefix: [u8; 8],
+    metadata: EnvelopeMetadata,
+    sequence: u64,
+}
+
+impl<W: Write> EnvelopeWriter<W> {
+    /// Start a stream from `sender` to `recipient`, writing the header to `output`
+    pub fn new(mut output: W, sender: &SigningKey, recipient: &VerifyingKey) -> Result<Self> {
+        use rand::RngCore;
+
+        let mut hkdf_salt = [0u8; 32];
+        rand::rng().fill_bytes(&mut hkdf_salt);
+
+        let metadata = Envelope::build_metadata(&[])?;
+        let header = StreamHeader {
+            hkdf_salt,
+            sender_key: sender.verifying_key().to_bytes(),
+            recipient_key: recipient.to_bytes(),
+            created_at: metadata.created_at,
+            aead_algorithm: AeadAlgorithm::Aes256Gcm as u8,
+        };
+        output
+            .write_all(STREAM_MAGIC)
+            .context("Failed to write stream magic")?;
+        bincode::serialize_into(&mut output, &header).context("Failed to write stream header")?;
+
+        let (encryption_key, nonce_prefix) =
+            derive_shared_encryption_key(sender, recipient, &hkdf_salt)?;
+
+        Ok(Self {
+            output,
+            signing_key: sender.clone(),
+            encryption_key: Zeroizing::new(encryption_key),
+            nonce_prefix,
+            metadata,
+            sequence: 0,
+        })
+    }
+
+    /// Seal and write `data`, split into chunks of at most 64 KB
+    ///
+    /// Empty input writes nothing; the empty chunk is reserved for the end marker.
+    pub fn write_chunk(&mut self, data: &[u8]) -> Result<()> {
+        for piece in data.chunks(DEFAULT_CHUNK_SIZE) {
+            self.write_record(piece, false)?;
+        }
+        Ok(())
+    }
+
+    /// Write the end marker and return the output
+    pub fn finalize(mut self) -> Result<W> {
+        self.write_record(&[], true)?;
+        self.output.flush().context("Failed to flush stream")?;
+        Ok(self.output)
+    }
+
+    fn write_record(&mut self, data: &[u8], is_last: bool) -> Result<()> {
+        let chunk = Envelope::create_encrypted_chunk(
+            self.sequence,
+            data,
+            &self.signing_key,
+            &self.encryption_key,
+            &self.nonce_prefix,
+            is_last,
+            &self.metadata,
+            None,
+        )?;
+        let record = bincode::serialize(&chunk).context("Failed to serialize chunk")?;
+        let len = u32::try_from(record.len()).context("Chunk record too large")?;
+        self.output
+            .write_all(&len.to_le_bytes())
+            .and_then(|_| self.output.write_all(&record))
+            .with_context(|| format!("Failed to write chunk {}", self.sequence))?;
+        self.sequence += 1;
+        Ok(())
+    }
+}
+
+/// Verifies and decrypts a stream written by [`EnvelopeWriter`], chunk by chunk
+pub struct EnvelopeReader<R: Read> {
+    input: R,
+    sender_key: [u8; 32],
+    encryption_key: Zeroizing<[u8; 32]>,
+    nonce_prefix: [u8; 8],
+    metadata: EnvelopeMetadata,
+    sequence: u64,
+    finished: bool,
+}
+
+impl<R: Read> EnvelopeReader<R> {
+    /// Read the stream header and derive the key for `recipient`
+    pub fn new(mut input: R, recipient: &SigningKey) -> Result<Self> {
+        let mut magic = [0u8; 8];
+        input
+            .read_exact(&mut magic)
+            .context("Failed to read stream magic")?;
+        if &magic != STREAM_MAGIC {
+            return Err(anyhow::anyhow!("Not a sealedge envelope stream"));
+        }
+        let header: StreamHeader =
+            bincode::deserialize_from(&mut input).context("Failed to read stream header")?;
+
+        if !super::keys_equal(&header.recipient_key, recipient.verifying_key().as_bytes()) {
+            return Err(anyhow::anyhow!(
+                "Decryption key is not the recipient of this stream"
+            ));
+        }
+        let sender = VerifyingKey::from_bytes(&header.sender_key)
+            .context("Invalid sender public key in stream header")?;
+        let (encryption_key, nonce_prefix) =
+            derive_shared_encryption_key(recipient, &sender, &header.hkdf_salt)?;
+
+        Ok(Self {
+            input,
+            sender_key: header.sender_key,
+            encryption_key: Zeroizing::new(encryption_key),
+            nonce_prefix,
+            metadata: header.metadata()?,
+            sequence: 0,
+            finished: false,
+        })
+    }
+
+    /// Sender's verifying key from the stream header
+    pub fn sender(&self) -> Result<VerifyingKey> {
+        VerifyingKey::from_bytes(&self.sender_key)
+            .map_err(|e| anyhow::anyhow!("Invalid sender key bytes: {e}"))
+    }
+
+    /// Verify and decrypt the next chunk; `None` once the end marker is read
+    ///
+    /// Errors name the failing chunk. A stream that ends without the end marker
+    /// is reported as truncated.
+    pub fn read_chunk(&mut self) -> Result<Option<Vec<u8>>> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let sequence = self.sequence;
+        let chunk = self
+            .read_record()?
+            .ok_or_else(|| anyhow::anyhow!("Stream truncated before chunk {}", sequence))?;
+
+        if !Envelope::verify_chunk_signature(&chunk, None, &self.sender_key) {
+            return Err(anyhow::anyhow!(
+                "Chunk {} signature verification failed",
+                sequence
+            ));
+        }
+        let signed_manifest: SignedManifest = bincode::deserialize(&chunk.manifest)
+            .with_context(|| format!("Chunk {} has a malformed manifest", sequence))?;
+        let manifest: ChunkManifest = bincode::deserialize(&signed_manifest.manifest)
+            .with_context(|| format!("Chunk {} has a malformed manifest", sequence))?;
+        if manifest.sequence != sequence || chunk.sequence != sequence {
+            return Err(anyhow::anyhow!(
+                "Chunk {} is out of sequence (found {})",
+                sequence,
+                manifest.sequence
+            ));
+        }
+
+        // The signed empty chunk is the end marker; its nonce carries the last flag
+        let is_last = manifest.chunk_size == 0;
+        let plaintext = Envelope::decrypt_chunk_v2(
+            &chunk,
+            &self.metadata,
+            &self.encryption_key,
+            &self.nonce_prefix,
+            is_last,
+        )
+        .with_context(|| format!("Chunk {} failed to decrypt", sequence))?;
+        self.sequence += 1;
+
+        if is_last {
+            self.finished = true;
+            if self.read_record()?.is_some() {
+                return Err(anyhow::anyhow!("Unexpected data after end of stream"));
+            }
+            return Ok(None);
+        }
+        Ok(Some(plaintext))
+    }
+
+    /// Decrypt every remaining chunk into `output`, returning the bytes written
+    pub fn copy_to<W: Write>(&mut self, output: &mut W) -> Result<u64> {
+        let mut written = 0u64;
+        while let Some(plaintext) = self.read_chunk()? {
+            output
+                .write_all(&plaintext)
+                .context("Failed to write decrypted chunk")?;
+            written += plaintext.len() as u64;
+        }
+        Ok(written)
+    }
+
+    /// Read one length-prefixed chunk record; `None` at a clean end of input
+    fn read_record(&mut self) -> Result<Option<NetworkChunk>> {
+        let mut len = [0u8; 4];
+        match self.input.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e).context("Failed to read chunk length"),
+        }
+        let len = u32::from_le_bytes(len);
+        if len > MAX_RECORD_LEN {
+            return Err(anyhow::anyhow!(
+                "Chunk {} record length {} exceeds maximum {}",
+                self.sequence,
+                len,
+                MAX_RECORD_LEN
+            ));
+        }
+
+        let mut record = vec![0u8; len as usize];
+        self.input
+            .read_exact(&mut record)
+            .with_context(|| format!("Stream truncated inside chunk {}", self.sequence))?;
+        bincode::deserialize(&record)
+            .map(Some)
+            .with_context(|| format!("Chunk {} record is malformed", self.sequence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+    use std::io::{BufReader, BufWriter, Cursor, Seek, SeekFrom};
+
+    /// Offset of chunk `index`'s ciphertext within a stream
+    fn chunk_data_offset(stream: &[u8], index: usize) -> usize {
+        let header_len = bincode::serialized_size(&StreamHeader {
+            hkdf_salt: [0; 32],
+            sender_key: [0; 32],
+            recipient_key: [0; 32],
+            created_at: 0,
+            aead_algorithm: 0,
+        })
+        .unwrap() as usize;
+
+        let mut offset = STREAM_MAGIC.len() + header_len;
+        for _ in 0..index {
+            let len = u32::from_le_bytes(stream[offset..offset + 4].try_into().unwrap());
+            offset += 4 + len as usize;
+        }
+        // Record length, then NetworkChunk's sequence and data length prefix
+        offset + 4 + 8 + 8
+    }
+
+    #[test]
+    fn test_large_file_round_trip() {
+        let sender = SigningKey::generate(&mut OsRng);
+        let recipient = SigningKey::generate(&mut OsRng);
+
+        let dir = tempfile::tempdir().unwrap();
+        let plain_path = dir.path().join("payload.bin");
+        let sealed_path = dir.path().join("payload.sealed");
+        let opened_path = dir.path().join("payload.out");
+
+        // 3 MB plus a partial chunk, written in uneven pieces
+        let payload: Vec<u8> = (0..3 * 1024 * 1024 + 12_345)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        std::fs::write(&plain_path, &payload).unwrap();
+
+        let mut input = std::fs::File::open(&plain_path).unwrap();
+        let output = BufWriter::new(std::fs::File::create(&sealed_path).unwrap());
+        let mut writer = EnvelopeWriter::new(output, &sender, &recipient.verifying_key()).unwrap();
+        let mut buf = vec![0u8; 100_000];
+        loop {
+            let n = input.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            writer.write_chunk(&buf[..n]).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let sealed = BufReader::new(std::fs::File::open(&sealed_path).unwrap());
+        let mut reader = EnvelopeReader::new(sealed, &recipient).unwrap();
+        assert_eq!(reader.sender().unwrap(), sender.verifying_key());
+        let mut opened = std::fs::File::create(&opened_path).unwrap();
+        let written = reader.copy_to(&mut opened).unwrap();
+
+        assert_eq!(written, payload.len() as u64);
+        assert_eq!(std::fs::read(&opened_path).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_tampered_chunk_fails_at_that_chunk() {
+        let sender = SigningKey::generate(&mut OsRng);
+        let recipient = SigningKey::generate(&mut OsRng);
+
+        let mut writer =
+            EnvelopeWriter::new(Cursor::new(Vec::new()), &sender, &recipient.verifying_key())
+                .unwrap();
+        for i in 0..4u8 {
+            writer.write_chunk(&[i; 1000]).unwrap();
+        }
+        let mut stream = writer.finalize().unwrap();
+
+        let offset = chunk_data_offset(stream.get_ref(), 2);
+        stream.get_mut()[offset + 10] ^= 0x01;
+        stream.seek(SeekFrom::Start(0)).unwrap();
+
+        let mut reader = EnvelopeReader::new(stream, &recipient).unwrap();
+        assert_eq!(reader.read_chunk().unwrap().unwrap(), vec![0u8; 1000]);
+        assert_eq!(reader.read_chunk().unwrap().unwrap(), vec![1u8; 1000]);
+        let err = reader.read_chunk().unwrap_err();
+        assert!(err.to_string().contains("Chunk 2"), "{err}");
+    }
+
+    #[test]
+    fn test_truncated_stream_is_rejected() {
+        let sender = SigningKey::generate(&mut OsRng);
+        let recipient = SigningKey::generate(&mut OsRng);
+
+        let mut writer =
+            EnvelopeWriter::new(Vec::new(), &sender, &recipient.verifying_key()).unwrap();
+        writer.write_chunk(b"first").unwrap();
+        writer.write_chunk(b"second").unwrap();
+        let stream = writer.finalize().unwrap();
+
+        // Drop the end marker
+        let cut = chunk_data_offset(&stream, 2) - 8 - 8 - 4;
+        let mut reader = EnvelopeReader::new(&stream[..cut], &recipient).unwrap();
+        assert_eq!(reader.read_chunk().unwrap().unwrap(), b"first");
+        assert_eq!(reader.read_chunk().unwrap().unwrap(), b"second");
+        assert!(reader
+            .read_chunk()
+            .unwrap_err()
+            .to_string()
+            .contains("truncated"));
+    }
+}
@@ -39,6 +39,9 @@ pub enum TrustEdgeError {
     #[error("Asymmetric crypto error")]
     Asymmetric(#[from] AsymmetricError),
 
+    #[error("Envelope error")]
+    Envelope(#[from] EnvelopeError),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -82,6 +85,54 @@ pub enum ChainError {
 
     #[error("End of chain truncated")]
     EndOfChainTruncated,
+
+    /// Nothing to chain from genesis: the capture has no segments
+    #[error("Chain has no segments")]
+    Empty,
+}
+
+/// Errors from decoding and unsealing an [`Envelope`](crate::Envelope)
+///
+/// Converts into `anyhow::Error` with `?`, and can be recovered from one with
+/// `downcast_ref` through any added context.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum EnvelopeError {
+    /// A chunk signature or the chunk sequence does not verify
+    #[error("Envelope verification failed")]
+    SignatureInvalid,
+
+    /// The key is not one the envelope was sealed to
+    #[error("Decryption key is not a recipient of this envelope")]
+    WrongRecipient,
+
+    /// Key agreement, decryption, or decompression of a verified envelope failed
+    #[error("Envelope decryption failed: {0}")]
+    Decrypt(String),
+
+    /// The encoded envelope could not be parsed
+    #[error("Failed to deserialize envelope: {0}")]
+    Deserialize(String),
+
+    #[error("Envelope expired at {expires_at} (now {now})")]
+    Expired { expires_at: u64, now: u64 },
+
+    /// [`Envelope::reseal`](crate::Envelope::reseal) cannot keep what this envelope
+    /// promises its recipient
+    #[error("Envelope cannot be resealed: {0}")]
+    NotResealable(&'static str),
+}
+
+/// Errors from [`SeenReceiptRegistry`](crate::SeenReceiptRegistry)
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    #[error("Receipt envelope {envelope_hash} was already accepted")]
+    Replayed { envelope_hash: String },
+
+    #[error("Invalid receipt envelope: {0}")]
+    InvalidEnvelope(String),
+
+    #[error("Receipt store error: {0}")]
+    Storage(String),
 }
 
 /// Errors related to asymmetric cryptography operations
@@ -135,6 +186,38 @@ pub enum ArchiveError {
 
     #[error("Archive validation failed: {0}")]
     ValidationFailed(String),
+
+    #[error("Profile mismatch: expected '{expected}', archive is '{found}'")]
+    ProfileMismatch { expected: String, found: String },
+
+    #[error("Chunk {index} decryption failed: {reason}")]
+    Decryption { index: usize, reason: String },
+
+    #[error("Device key fingerprint {found} does not match pinned {expected}")]
+    DeviceKeyNotPinned { expected: String, found: String },
+}
+
+impl ArchiveError {
+    /// Short human-readable summary, the first line `seal verify` and
+    /// `sealedge verify` print for an archive they could not read
+    pub fn summary(&self) -> &'static str {
+        match self {
+            ArchiveError::MissingChunk(_) | ArchiveError::InvalidChunkIndex { .. } => {
+                "Missing chunk file"
+            }
+            ArchiveError::UnreferencedChunk(_) => "Unreferenced chunk file",
+            ArchiveError::Json(_) => "Invalid manifest format",
+            ArchiveError::SignatureMismatch => "Signature verification failed",
+            ArchiveError::Io(_) => "Archive read error",
+            ArchiveError::SchemaMismatch(_) => "Schema error",
+            ArchiveError::Manifest(_) => "Manifest error",
+            ArchiveError::Chain(_) => "Continuity chain error",
+            ArchiveError::ValidationFailed(_) => "Validation error",
+            ArchiveError::ProfileMismatch { .. } => "Profile mismatch",
+            ArchiveError::Decryption { .. } => "Decryption error",
+            ArchiveError::DeviceKeyNotPinned { .. } => "Device key not pinned",
+        }
+    }
 }
 
 /// Errors related to backend operations
@@ -177,3 +260,16 @@ pub enum TransportError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
+
+/// Errors reported by [`ChunkStreamValidator`](crate::transport::ChunkStreamValidator)
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum StreamError {
+    #[error("Sequence gap: expected {expected}, got {got}")]
+    Gap { expected: u64, got: u64 },
+
+    #[error("Chunk {got} arrived out of order (expected {expected})")]
+    OutOfOrder { expected: u64, got: u64 },
+
+    #[error("Duplicate chunk {sequence}")]
+    Duplicate { sequence: u64 },
+}
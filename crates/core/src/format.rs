@@ -8,7 +8,7 @@ use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
 
 pub const NONCE_LEN: usize = 12;
-pub const AAD_LEN: usize = 32 + 8 + NONCE_LEN + 32 + 4; // Added 4 bytes for chunk_len
+pub use sealedge_primitives::envelope::{build_aad, AAD_LEN};
 pub const HEADER_LEN: usize = 66; // Updated from 58 for algorithm agility
 
 pub const MAGIC: &[u8; 4] = b"SEAL";
@@ -257,6 +257,8 @@ pub enum AudioFormat {
     I24Le,
     /// Other/custom format
     Other(String),
+    /// Opus packets framed per chunk (see `audio::opus`); decode before playback
+    Opus,
 }
 
 /// Manifest structure
@@ -274,6 +276,99 @@ pub struct Manifest {
     pub chunk_len: u32,      // NEW: Expected plaintext length of this chunk (bound via AAD)
 }
 
+/// Manifest version for chunks whose plaintext is encrypted as-is
+pub const MANIFEST_V1: u8 = 1;
+/// Manifest version for chunks whose plaintext was zstd-compressed before encryption.
+/// `chunk_len` and `pt_hash` still describe the original plaintext.
+pub const MANIFEST_V2_ZSTD: u8 = 2;
+
+/// Per-chunk compression, negotiated through the signed manifest's `v`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkCompression {
+    None,
+    Zstd,
+}
+
+impl ChunkCompression {
+    /// Zstandard level used for chunks
+    const ZSTD_LEVEL: i32 = 3;
+
+    /// Compression implied by a manifest version
+    pub fn from_manifest_version(v: u8) -> Result<Self> {
+        match v {
+            MANIFEST_V1 => Ok(ChunkCompression::None),
+            MANIFEST_V2_ZSTD => Ok(ChunkCompression::Zstd),
+            _ => Err(anyhow::anyhow!("Unsupported manifest version: {}", v)),
+        }
+    }
+
+    /// Manifest version announcing this compression
+    pub fn manifest_version(self) -> u8 {
+        match self {
+            ChunkCompression::None => MANIFEST_V1,
+            ChunkCompression::Zstd => MANIFEST_V2_ZSTD,
+        }
+    }
+
+    /// Compress a chunk ahead of encryption.
+    ///
+    /// Returns the compression actually applied: chunks that would not shrink are
+    /// left as-is and reported as [`ChunkCompression::None`].
+    pub fn compress(self, plaintext: &[u8]) -> Result<(ChunkCompression, Vec<u8>)> {
+        match self {
+            ChunkCompression::None => Ok((ChunkCompression::None, plaintext.to_vec())),
+            ChunkCompression::Zstd => {
+                let body = zstd::encode_all(plaintext, Self::ZSTD_LEVEL)
+                    .context("Failed to compress chunk")?;
+                if body.len() < plaintext.len() {
+                    Ok((ChunkCompression::Zstd, body))
+                } else {
+                    Ok((ChunkCompression::None, plaintext.to_vec()))
+                }
+            }
+        }
+    }
+
+    /// Decompress a decrypted chunk and check it against the manifest's `chunk_len`.
+    ///
+    /// Expansion stops at `max_chunk_len` (the stream's chunk bound), so a
+    /// decompression bomb is rejected without being inflated in memory.
+    pub fn decompress(self, body: Vec<u8>, chunk_len: u32, max_chunk_len: u32) -> Result<Vec<u8>> {
+        anyhow::ensure!(
+            chunk_len <= max_chunk_len,
+            "chunk_len {} exceeds chunk bound {}",
+            chunk_len,
+            max_chunk_len
+        );
+        let plaintext = match self {
+            ChunkCompression::None => body,
+            ChunkCompression::Zstd => {
+                use std::io::Read;
+                let decoder = zstd::stream::read::Decoder::new(body.as_slice())
+                    .context("Failed to initialize chunk decompressor")?;
+                let mut plaintext = Vec::with_capacity(chunk_len as usize);
+                decoder
+                    .take(u64::from(max_chunk_len) + 1)
+                    .read_to_end(&mut plaintext)
+                    .context("Failed to decompress chunk")?;
+                anyhow::ensure!(
+                    plaintext.len() <= max_chunk_len as usize,
+                    "decompressed chunk exceeds chunk bound {}",
+                    max_chunk_len
+                );
+                plaintext
+            }
+        };
+        anyhow::ensure!(
+            plaintext.len() == chunk_len as usize,
+            "plaintext length {} != manifest chunk_len {}",
+            plaintext.len(),
+            chunk_len
+        );
+        Ok(plaintext)
+    }
+}
+
 /// SignedManifest structure
 #[derive(Serialize, Deserialize)]
 pub struct SignedManifest {
@@ -299,29 +394,6 @@ pub struct Record {
     pub ct: Vec<u8>,
 }
 
-/// Build Additional Authenticated Data (AAD) for encryption
-/// AAD = header_hash(32) || seq_be(8) || nonce(12) || manifest_hash(32) || chunk_len_be(4)
-pub fn build_aad(
-    header_hash: &[u8; 32],
-    seq: u64,
-    nonce: &[u8; NONCE_LEN],
-    manifest_hash: &[u8; 32],
-    chunk_len: u32,
-) -> [u8; AAD_LEN] {
-    let mut aad = [0u8; AAD_LEN];
-    let mut off = 0;
-    aad[off..off + 32].copy_from_slice(header_hash);
-    off += 32;
-    aad[off..off + 8].copy_from_slice(&seq.to_be_bytes());
-    off += 8;
-    aad[off..off + NONCE_LEN].copy_from_slice(nonce);
-    off += NONCE_LEN;
-    aad[off..off + 32].copy_from_slice(manifest_hash);
-    off += 32;
-    aad[off..off + 4].copy_from_slice(&chunk_len.to_be_bytes());
-    aad
-}
-
 /// Write the stream header to the output
 pub fn write_stream_header<W: std::io::Write>(w: &mut W, sh: &StreamHeader) -> Result<()> {
     w.write_all(MAGIC).context("write magic")?;
@@ -660,4 +732,53 @@ mod tests {
         assert_eq!(v2_header.nonce_prefix, [0x11, 0x22, 0x33, 0x44]);
         assert_eq!(v2_header.chunk_size, 4096);
     }
+
+    #[test]
+    fn test_chunk_compression_roundtrip() {
+        // Repetitive audio-like samples shrink and are announced as v2
+        let compressible: Vec<u8> = (0..16 * 1024u32).map(|i| (i % 16) as u8).collect();
+        let (used, body) = ChunkCompression::Zstd.compress(&compressible).unwrap();
+        assert_eq!(used, ChunkCompression::Zstd);
+        assert_eq!(used.manifest_version(), MANIFEST_V2_ZSTD);
+        assert!(body.len() < compressible.len() / 4);
+
+        let negotiated = ChunkCompression::from_manifest_version(used.manifest_version()).unwrap();
+        let restored = negotiated
+            .decompress(body, compressible.len() as u32, 16 * 1024)
+            .unwrap();
+        assert_eq!(restored, compressible);
+    }
+
+    #[test]
+    fn test_incompressible_chunk_is_sent_as_is() {
+        use rand_core::{OsRng, RngCore};
+        let mut incompressible = vec![0u8; 4096];
+        OsRng.fill_bytes(&mut incompressible);
+
+        let (used, body) = ChunkCompression::Zstd.compress(&incompressible).unwrap();
+        assert_eq!(used, ChunkCompression::None);
+        assert_eq!(used.manifest_version(), MANIFEST_V1);
+        assert_eq!(body, incompressible);
+        assert_eq!(used.decompress(body, 4096, 4096).unwrap(), incompressible);
+    }
+
+    #[test]
+    fn test_oversized_decompression_is_rejected() {
+        // 1 MiB of zeros compresses to a few dozen bytes
+        let bomb = zstd::encode_all(&vec![0u8; 1024 * 1024][..], 3).unwrap();
+        let err = ChunkCompression::Zstd
+            .decompress(bomb.clone(), 4096, 4096)
+            .unwrap_err();
+        assert!(err.to_string().contains("exceeds chunk bound"));
+
+        // A manifest claiming more than the bound is refused before decompressing
+        assert!(ChunkCompression::Zstd
+            .decompress(bomb, 1024 * 1024, 4096)
+            .is_err());
+
+        // A chunk that expands to a different length than the manifest says
+        let body = zstd::encode_all(&[7u8; 100][..], 3).unwrap();
+        assert!(ChunkCompression::Zstd.decompress(body, 99, 4096).is_err());
+        assert!(ChunkCompression::from_manifest_version(9).is_err());
+    }
 }
@@ -79,6 +79,13 @@
 //! - **`audio`** — Live audio capture from microphones via cpal.
 //!   Requires audio libraries (`libasound2-dev` on Linux, CoreAudio on macOS, WASAPI on Windows).
 //!
+//! ### Embedded Use
+//!
+//! The envelope key schedule, chunk AEAD, manifest signing digests and chain
+//! hashing live in the `#![no_std]` + `alloc` crate `sealedge-primitives`, which
+//! this crate re-exports as [`primitives`]. Devices that cannot link `std` depend
+//! on it directly.
+//!
 //! ### Usage
 //!
 //! ```toml
@@ -97,6 +104,7 @@ pub mod audio;
 pub mod auth;
 pub mod backends;
 pub mod chain;
+pub mod clock;
 pub mod crypto;
 pub mod envelope;
 pub mod error;
@@ -106,6 +114,7 @@ pub mod point_attestation;
 pub mod secret;
 pub mod transport;
 pub mod vectors;
+pub mod wrap;
 
 // Layer hierarchy (Phase 1 scaffolding -- populated in later phases)
 pub mod applications;
@@ -113,7 +122,23 @@ pub mod io;
 pub mod primitives;
 pub mod protocols;
 
-pub use archive::{archive_dir_name, read_archive, validate_archive, write_archive, ArchiveError};
+pub use archive::diff::{diff_archives, ArchiveDiff, FieldDiff, SegmentDiff, SignatureStatus};
+pub use archive::merkle::{
+    segment_inclusion_proof, segments_root, verify_inclusion, SegmentInclusionProof,
+};
+#[cfg(feature = "parallel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+pub use archive::validate_archive_parallel;
+pub use archive::{
+    archive_dir_name, archive_hash, check_archive, decrypt_archive, device_key_fingerprint,
+    inspect_archive, read_archive, validate_archive, validate_archive_for_profile,
+    verify_archive_chain, verify_archive_chain_with_rotations, verify_archive_pinned,
+    verify_archive_trust_on_first_use, verify_archive_with_receipt, verify_device_attestation,
+    verify_manifest_only, write_archive, ArchiveCheck, ArchiveCheckFailure, ArchiveError,
+    ArchiveReport, ChainBreak, ChainVerifyOutcome, CheckStatus, DeviceAttestation,
+    DeviceKeyRotation, EmbeddedKeyOutcome, ManifestOnlyOutcome, SegmentReport, SkippedCheck,
+    VerifyOutcome,
+};
 pub use asymmetric::{
     decrypt_key_asymmetric, encrypt_key_asymmetric, key_exchange, AsymmetricError, KeyPair,
     PrivateKey, PublicKey,
@@ -121,7 +146,7 @@ pub use asymmetric::{
 #[cfg(feature = "audio")]
 #[cfg_attr(docsrs, doc(cfg(feature = "audio")))]
 pub use audio::AudioCapture;
-pub use audio::{AudioChunk, AudioConfig};
+pub use audio::{AudioChunk, AudioConfig, AudioEncoding};
 pub use auth::{
     client_authenticate, server_authenticate, AuthChallenge, AuthMessage, AuthMessageType,
     ClientAuthResponse, ClientAuthResult, ServerAuthConfirm, ServerCertificate, SessionInfo,
@@ -138,7 +163,9 @@ pub use backends::{
     HashAlgorithm,
     KeyBackend,
     KeyContext,
+    KeyDerivationAlgorithm,
     KeyDerivationContext,
+    KeyDerivationParams,
     KeyMetadata,
     SignatureAlgorithm,
     SymmetricAlgorithm,
@@ -152,6 +179,7 @@ pub use backends::{KeyringBackend, UniversalKeyringBackend};
 pub use chain::{
     blake3_hex_or_b64, chain_next, genesis, segment_hash, validate_chain, ChainError, ChainSegment,
 };
+pub use clock::{Clock, MockClock, SystemClock};
 pub use crypto::{
     decrypt_segment, derive_chunk_key, encrypt_segment, format_nonce, generate_aad,
     generate_nonce24, is_encrypted_key_file, parse_nonce, sign_manifest, verify_manifest,
@@ -159,19 +187,29 @@ pub use crypto::{
 };
 pub use secret::Secret;
 // Ed25519 types re-exported for downstream crates (JWKS, key management)
-pub use ed25519_dalek::{SigningKey, VerifyingKey};
-pub use envelope::{Envelope, EnvelopeMetadata};
+pub use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+pub use envelope::stream::{EnvelopeReader, EnvelopeWriter};
+pub use envelope::{CompressionAlgorithm, Envelope, EnvelopeIssuer, EnvelopeMetadata, SealOptions};
 pub use error::ManifestError; // ManifestError is re-exported from error.rs (which aliases ManifestFormatError)
-pub use error::{BackendError, TransportError, TrustEdgeError};
+pub use error::{
+    BackendError, EnvelopeError, ReplayError, StreamError, TransportError, TrustEdgeError,
+};
 pub use format::*;
 pub use hybrid::{open_envelope, seal_for_recipient, HybridEncryptionError, SymmetricKey};
 pub use point_attestation::{ArtifactRef, PointAttestation, PointAttestationError};
+pub use sealedge_seal_protocols::archive::claims::{Claim, ClaimError, ClaimSet};
 pub use sealedge_seal_protocols::archive::manifest::{
     AudioMetadata, CamVideoManifest, CamVideoMetadata, CaptureInfo, ChunkInfo, DeviceInfo,
     GenericMetadata, LogMetadata, ManifestFormatError, ProfileMetadata, SegmentInfo,
     SensorMetadata, TrstManifest,
 };
-pub use transport::{Transport, TransportConfig, TransportFactory};
+pub use transport::{ChunkStreamValidator, Transport, TransportConfig, TransportFactory};
+#[cfg(feature = "parallel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+pub use wrap::wrap_file_parallel;
+pub use wrap::{
+    append_segments, wrap_file, wrap_file_resumable, wrap_file_with_signer, WrapConfig,
+};
 
 // Shared wire types — re-exported from sealedge-types for downstream access
 // Downstream crates can use `sealedge_core::sealedge_types::*` or the
@@ -181,15 +219,28 @@ pub use sealedge_types::{DateTime, Utc, Uuid};
 
 // Receipt system re-exports (Layer 4 applications)
 pub use applications::receipts::{
-    assign_receipt, create_receipt, extract_receipt, verify_receipt_chain, OwnershipReceipt,
+    assign_receipt, assign_receipt_with_encryption_key, assign_receipt_with_fee, create_receipt,
+    create_receipt_with_encoding, create_receipt_with_encryption_key, create_receipts_batch,
+    extract_receipt, extract_receipt_with_encoding, extract_receipt_with_encryption_key,
+    merge_receipts, split_receipt, verify_chain_conservation, verify_receipt_chain,
+    verify_receipt_chain_with_rotations, verify_receipt_graph_with_rotations, FeeAssignment,
+    KeyRotation, MemoryReceiptStore, OwnershipReceipt, ReceiptEncoding, SeenReceiptRegistry,
+    SeenReceiptStore,
 };
 
 // Attestation system re-exports (Layer 4 applications)
 pub use applications::attestation::{
-    create_signed_attestation, verify_attestation, Attestation, AttestationConfig,
-    AttestationResult, KeySource, OutputFormat, VerificationConfig, VerificationDetails,
-    VerificationInfo, VerificationResult,
+    attestation_hash, canonical_attestation_bytes, create_attestation_data_for_dir,
+    create_signed_attestation, to_in_toto_link, verify_attestation,
+    verify_attestation_with_revocation, verify_directory_attestation, Attestation,
+    AttestationConfig, AttestationResult, AttestationStatus, CommitStatus, DetachedSignature,
+    DirectoryAttestation, DirectoryEntry, DirectoryVerification, HashChoice, KeySource,
+    OutputFormat, RevocationEntry, RevocationList, RevokedTarget, SignedRevocationList,
+    VerificationConfig, VerificationDetails, VerificationInfo, VerificationResult,
 };
+#[cfg(feature = "git-attestation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "git-attestation")))]
+pub use applications::attestation::{verify_attestation_with_commit, verify_commit};
 
 /// Represents a chunk of data sent over the network, including encrypted data,
 /// a signed manifest, the nonce used for encryption, and a timestamp.
@@ -217,16 +268,29 @@ impl NetworkChunk {
         manifest_bytes: Vec<u8>,
         nonce: [u8; NONCE_LEN],
     ) -> Self {
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        Self::new_with_clock(
+            seq,
+            encrypted_data,
+            manifest_bytes,
+            nonce,
+            &clock::SystemClock,
+        )
+    }
+
+    /// Like [`NetworkChunk::new`], but stamps the chunk with the time from `clock`.
+    pub fn new_with_clock(
+        seq: u64,
+        encrypted_data: Vec<u8>,
+        manifest_bytes: Vec<u8>,
+        nonce: [u8; NONCE_LEN],
+        clock: &dyn clock::Clock,
+    ) -> Self {
         Self {
             sequence: seq,
             data: encrypted_data,
             manifest: manifest_bytes,
             nonce,
-            timestamp,
+            timestamp: clock.now_secs(),
         }
     }
 
@@ -235,6 +299,11 @@ impl NetworkChunk {
     /// Checks that the data and manifest are not empty, and that the timestamp is not more than 5 minutes in the future.
     /// Returns `Ok(())` if valid, or an error otherwise.
     pub fn validate(&self) -> Result<(), anyhow::Error> {
+        self.validate_with_clock(&clock::SystemClock)
+    }
+
+    /// Like [`NetworkChunk::validate`], but takes the current time from `clock`.
+    pub fn validate_with_clock(&self, clock: &dyn clock::Clock) -> Result<(), anyhow::Error> {
         if self.data.is_empty() {
             return Err(anyhow::anyhow!("Chunk data is empty"));
         }
@@ -242,12 +311,7 @@ impl NetworkChunk {
             return Err(anyhow::anyhow!("Manifest is empty"));
         }
 
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|e| anyhow::anyhow!("Time error: {}", e))?
-            .as_secs();
-
-        if self.timestamp > now + 300 {
+        if self.timestamp > clock.now_secs() + 300 {
             // Not more than 5 minutes in future
             return Err(anyhow::anyhow!("Chunk timestamp is too far in the future"));
         }
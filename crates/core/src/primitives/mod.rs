@@ -77,7 +77,14 @@
 //!
 //! ## Status
 //!
-//! **Phase 1 scaffolding - no code yet**
+//! **Phase 1 scaffolding**
 //!
 //! This module structure was created in Phase 1 to establish the layer hierarchy.
 //! Actual code migration happens in Phase 2-8 of the consolidation roadmap.
+//!
+//! The exception is the `sealedge-primitives` crate, re-exported here: the
+//! `#![no_std]` + `alloc` envelope key schedule, chunk AEAD, manifest signing
+//! digests, chain hashing and [`Clock`] that [`crate::envelope`] and
+//! [`crate::chain`] are built on.
+
+pub use sealedge_primitives::{chain, envelope, Clock, PrimitiveError};
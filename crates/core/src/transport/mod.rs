@@ -13,8 +13,11 @@ use anyhow::Result;
 use std::net::SocketAddr;
 
 pub mod quic;
+pub mod sequence;
 pub mod tcp;
 
+pub use sequence::ChunkStreamValidator;
+
 /// Generic transport trait for network communication.
 ///
 /// This trait abstracts over different transport protocols (TCP, QUIC, etc.)
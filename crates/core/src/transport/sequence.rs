@@ -0,0 +1,220 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Sequence tracking for received [`NetworkChunk`] streams.
+//!
+//! TCP and QUIC streams deliver chunks in order, so a strict validator (reorder
+//! window 0) flags any drop or duplicate. UDP-style transports may reorder
+//! chunks; a reorder window lets chunks up to `window` sequences ahead of the
+//! next expected one be accepted and the missing ones fill in later.
+
+use crate::error::StreamError;
+use crate::NetworkChunk;
+use std::collections::{BTreeSet, VecDeque};
+use std::ops::Range;
+
+/// Number of lost sequence ranges remembered to tell late chunks from duplicates
+const MAX_LOST_RANGES: usize = 64;
+
+/// Detects gaps, duplicates and reordering in a received chunk stream
+#[derive(Debug, Clone)]
+pub struct ChunkStreamValidator {
+    expected: u64,
+    window: u64,
+    /// Accepted sequences above `expected`, all within the reorder window
+    pending: BTreeSet<u64>,
+    /// Sequences given up on after a gap, most recent last
+    lost: VecDeque<Range<u64>>,
+}
+
+impl ChunkStreamValidator {
+    /// Strict validator whose first chunk must carry `first_sequence`
+    pub fn new(first_sequence: u64) -> Self {
+        Self {
+            expected: first_sequence,
+            window: 0,
+            pending: BTreeSet::new(),
+            lost: VecDeque::new(),
+        }
+    }
+
+    /// Accept chunks up to `window` sequences ahead of the next expected one
+    pub fn with_reorder_window(mut self, window: u64) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Lowest sequence number not yet received
+    pub fn next_expected(&self) -> u64 {
+        self.expected
+    }
+
+    /// Check `chunk` against the stream so far and record it.
+    ///
+    /// A [`StreamError::Gap`] means the chunk landed beyond the reorder window: the
+    /// missing sequences are written off as lost and the validator resynchronises
+    /// on `chunk`, so the caller may log the gap and keep reading. A chunk from a
+    /// lost range that turns up afterwards is [`StreamError::OutOfOrder`].
+    pub fn accept(&mut self, chunk: &NetworkChunk) -> Result<(), StreamError> {
+        let seq = chunk.sequence;
+
+        if seq < self.expected {
+            return Err(if self.lost.iter().any(|r| r.contains(&seq)) {
+                StreamError::OutOfOrder {
+                    expected: self.expected,
+                    got: seq,
+                }
+            } else {
+                StreamError::Duplicate { sequence: seq }
+            });
+        }
+
+        if seq - self.expected <= self.window {
+            if !self.pending.insert(seq) {
+                return Err(StreamError::Duplicate { sequence: seq });
+            }
+            self.advance();
+            return Ok(());
+        }
+
+        let gap = StreamError::Gap {
+            expected: self.expected,
+            got: seq,
+        };
+        // Slide the window so `seq` sits at its far edge; anything before that is lost
+        let new_expected = seq - self.window;
+        let mut start = self.expected;
+        while let Some(&received) = self.pending.first() {
+            if received >= new_expected {
+                break;
+            }
+            self.pending.pop_first();
+            self.mark_lost(start..received);
+            start = received + 1;
+        }
+        self.mark_lost(start..new_expected);
+        self.expected = new_expected;
+        self.pending.insert(seq);
+        self.advance();
+        Err(gap)
+    }
+
+    fn advance(&mut self) {
+        while self.pending.remove(&self.expected) {
+            self.expected = self.expected.saturating_add(1);
+        }
+    }
+
+    fn mark_lost(&mut self, range: Range<u64>) {
+        if range.is_empty() {
+            return;
+        }
+        if self.lost.len() == MAX_LOST_RANGES {
+            self.lost.pop_front();
+        }
+        self.lost.push_back(range);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NONCE_LEN;
+
+    fn chunk(sequence: u64) -> NetworkChunk {
+        NetworkChunk::new(sequence, vec![1], vec![1], [0u8; NONCE_LEN])
+    }
+
+    #[test]
+    fn test_in_order_stream_is_accepted() {
+        let mut validator = ChunkStreamValidator::new(1);
+        for seq in 1..=5 {
+            validator.accept(&chunk(seq)).unwrap();
+        }
+        assert_eq!(validator.next_expected(), 6);
+    }
+
+    #[test]
+    fn test_dropped_sequence_is_a_gap() {
+        let mut validator = ChunkStreamValidator::new(0);
+        validator.accept(&chunk(0)).unwrap();
+        assert_eq!(
+            validator.accept(&chunk(2)),
+            Err(StreamError::Gap {
+                expected: 1,
+                got: 2
+            })
+        );
+        // Resynchronised on the chunk after the gap
+        validator.accept(&chunk(3)).unwrap();
+        assert_eq!(
+            validator.accept(&chunk(1)),
+            Err(StreamError::OutOfOrder {
+                expected: 4,
+                got: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_duplicate_is_rejected() {
+        let mut validator = ChunkStreamValidator::new(0);
+        validator.accept(&chunk(0)).unwrap();
+        validator.accept(&chunk(1)).unwrap();
+        assert_eq!(
+            validator.accept(&chunk(1)),
+            Err(StreamError::Duplicate { sequence: 1 })
+        );
+
+        // Also while still held inside the reorder window
+        let mut validator = ChunkStreamValidator::new(0).with_reorder_window(4);
+        validator.accept(&chunk(2)).unwrap();
+        assert_eq!(
+            validator.accept(&chunk(2)),
+            Err(StreamError::Duplicate { sequence: 2 })
+        );
+    }
+
+    #[test]
+    fn test_reordering_within_window_is_tolerated() {
+        let mut validator = ChunkStreamValidator::new(0).with_reorder_window(2);
+        for seq in [0, 2, 1, 4, 3, 5] {
+            validator.accept(&chunk(seq)).unwrap();
+        }
+        assert_eq!(validator.next_expected(), 6);
+    }
+
+    #[test]
+    fn test_reordering_outside_window_is_reported() {
+        let mut validator = ChunkStreamValidator::new(0).with_reorder_window(2);
+        validator.accept(&chunk(0)).unwrap();
+        validator.accept(&chunk(2)).unwrap();
+        assert_eq!(
+            validator.accept(&chunk(5)),
+            Err(StreamError::Gap {
+                expected: 1,
+                got: 5
+            })
+        );
+        // 1 was written off, 2 was received, 3 and 4 are still inside the window
+        assert_eq!(
+            validator.accept(&chunk(1)),
+            Err(StreamError::OutOfOrder {
+                expected: 3,
+                got: 1
+            })
+        );
+        assert_eq!(
+            validator.accept(&chunk(2)),
+            Err(StreamError::Duplicate { sequence: 2 })
+        );
+        validator.accept(&chunk(4)).unwrap();
+        validator.accept(&chunk(3)).unwrap();
+        assert_eq!(validator.next_expected(), 6);
+    }
+}
@@ -5,219 +5,482 @@
 //
 // Project: sealedge — Privacy and trust at the edge.
 //
-/// Deterministic test vectors for the sealedge envelope format.
+//! Deterministic test vectors for the sealedge envelope format.
+//!
+//! Fix *all* randomness (AES key, signing key, nonce prefix, header fields,
+//! timestamp) so `.seal` bytes are **identical** on every run under test.
+//!
+//! First run: the test will print a BLAKE3 digest of the generated .seal buffer.
+//! Copy that digest into `GOLDEN_TRST_BLAKE3` below and commit. When the format/crypto
+//! changes intentionally, re-run, copy the new digest (rebase the golden), and commit.
+//!
+//! [`export`] publishes the same fixed-seed envelope, plus a receipt payload and a
+//! signed archive manifest, as JSON files for third-party implementations to check
+//! themselves against. [`verify_against`] re-checks a published directory.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm,
+};
+use bincode::serialize_into;
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    build_aad,
+    format::{FileHeader, Manifest, Record, SignedManifest, StreamHeader},
+    write_stream_header, HEADER_LEN, MAGIC, NONCE_LEN, VERSION,
+};
+
+// ----------------------------
+// Fixed, test-only constants
+// ----------------------------
+
+/// 32-byte AES-256 key (hex = 000102...1f). TEST USE ONLY.
+const TEST_AES_KEY: [u8; 32] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+    0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E, 0x1F,
+];
+
+/// 32-byte Ed25519 signing seed (NOT a production key). TEST USE ONLY.
+const TEST_SIGNING_SEED: [u8; 32] = [
+    0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42,
+    0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24,
+];
+
+/// 32-byte Ed25519 seed for the receipt beneficiary. TEST USE ONLY.
+const TEST_BENEFICIARY_SEED: [u8; 32] = [0x5A; 32];
+
+/// 4-byte deterministic nonce prefix for tests.
+const TEST_NONCE_PREFIX: [u8; 4] = [0xAA, 0xBB, 0xCC, 0xDD];
+
+/// 16-byte key id embedded in header + manifest for tests.
+const TEST_KEY_ID: [u8; 16] = *b"TEST_KEY_ID_16B!";
+
+/// Deterministic device id hash (we derive at runtime from fixed inputs).
+const TEST_DEVICE_ID: &[u8] = b"sealedge-test-device";
+const TEST_SALT: &[u8] = b"sealedge-test-salt";
+
+/// Deterministic timestamp used in manifests for tests.
+const TEST_TS_MS: u64 = 1_700_000_000_000;
+
+/// Envelope vector input: three full chunks and one partial chunk.
+const VECTOR_INPUT_LEN: usize = 14_000;
+const VECTOR_CHUNK_SIZE: usize = 4096;
+
+/// Chunk contents of the archive manifest vector.
+const VECTOR_ARCHIVE_CHUNKS: [&[u8]; 3] = [b"chunk zero", b"chunk one", b"chunk two"];
+
+/// File names written by [`export`].
+pub const ENVELOPE_FILE: &str = "envelope.json";
+pub const RECEIPT_FILE: &str = "receipt.json";
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Error, Debug)]
+pub enum VectorError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error in {file}: {source}")]
+    Json {
+        file: String,
+        source: serde_json::Error,
+    },
+    #[error("Failed to build vector: {0}")]
+    Build(String),
+    #[error("{file}: field '{field}' does not match the expected vector")]
+    Mismatch { file: String, field: String },
+}
+
+/// Fixed-seed `.seal` stream envelope.
 ///
-/// Fix *all* randomness (AES key, signing key, nonce prefix, header fields,
-/// timestamp) so `.seal` bytes are **identical** on every run under test.
+/// Byte strings are lowercase hex.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvelopeVector {
+    pub aes_key: String,
+    pub signing_seed: String,
+    pub signing_public_key: String,
+    pub nonce_prefix: String,
+    pub key_id: String,
+    pub ts_ms: u64,
+    pub chunk_size: usize,
+    pub input: String,
+    /// BLAKE3 of the serialized file header
+    pub header_hash: String,
+    /// Domain-separated Ed25519 signature of each record's manifest, in sequence order
+    pub record_signatures: Vec<String>,
+    pub envelope: String,
+    pub envelope_blake3: String,
+}
+
+/// Fixed origin receipt and its payload encodings.
 ///
-/// First run: the test will print a BLAKE3 digest of the generated .seal buffer.
-/// Copy that digest into `GOLDEN_TRST_BLAKE3` below and commit. When the format/crypto
-/// changes intentionally, re-run, copy the new digest (rebase the golden), and commit.
+/// Byte strings are lowercase hex.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptVector {
+    pub issuer_public_key: String,
+    pub beneficiary_public_key: String,
+    pub amount: u64,
+    pub description: String,
+    pub created_at: u64,
+    /// Untagged JSON payload, as written by `create_receipt`
+    pub payload_json: String,
+    pub payload_json_blake3: String,
+    /// Tagged CBOR payload, as written by `create_receipt_with_encoding`
+    pub payload_cbor: String,
+    pub payload_cbor_blake3: String,
+}
+
+/// Fixed cam.video archive manifest and its device signature.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestVector {
+    /// Hex chunk contents, in segment order
+    pub chunks: Vec<String>,
+    pub device_public_key: String,
+    /// Canonical manifest JSON that the signature covers
+    pub canonical_manifest: String,
+    pub canonical_manifest_blake3: String,
+    pub signature: String,
+}
+
+/// Write the envelope, receipt and manifest vectors into `dir` as JSON.
+pub fn export(dir: &Path) -> Result<(), VectorError> {
+    fs::create_dir_all(dir)?;
+    for (file, vector) in expected_vectors()? {
+        let json = serde_json::to_string_pretty(&vector).map_err(|source| VectorError::Json {
+            file: file.to_string(),
+            source,
+        })?;
+        fs::write(dir.join(file), json + "\n")?;
+    }
+    Ok(())
+}
+
+/// Check every vector file in `dir` against the vectors this build produces.
 ///
-/// Test vectors for the sealedge envelope format.
-#[cfg(test)]
-mod tests {
-    use std::io::Write;
+/// Fails on the first missing, unreadable or differing field.
+pub fn verify_against(dir: &Path) -> Result<(), VectorError> {
+    for (file, expected) in expected_vectors()? {
+        let bytes = fs::read(dir.join(file))?;
+        let actual: serde_json::Value =
+            serde_json::from_slice(&bytes).map_err(|source| VectorError::Json {
+                file: file.to_string(),
+                source,
+            })?;
+        let mismatch = |field: &str| VectorError::Mismatch {
+            file: file.to_string(),
+            field: field.to_string(),
+        };
 
-    use crate::{
-        build_aad,
-        format::{FileHeader, Manifest, Record, SignedManifest, StreamHeader},
-        write_stream_header, HEADER_LEN, MAGIC, NONCE_LEN, VERSION,
+        let (Some(expected), Some(actual)) = (expected.as_object(), actual.as_object()) else {
+            return Err(mismatch("<root>"));
+        };
+        for (field, value) in expected {
+            if actual.get(field) != Some(value) {
+                return Err(mismatch(field));
+            }
+        }
+        if let Some(extra) = actual.keys().find(|k| !expected.contains_key(*k)) {
+            return Err(mismatch(extra));
+        }
+    }
+    Ok(())
+}
+
+/// Every published vector, keyed by file name
+fn expected_vectors() -> Result<Vec<(&'static str, serde_json::Value)>, VectorError> {
+    let to_value = |file: &'static str, value: Result<serde_json::Value, serde_json::Error>| {
+        value
+            .map(|v| (file, v))
+            .map_err(|source| VectorError::Json {
+                file: file.to_string(),
+                source,
+            })
     };
+    Ok(vec![
+        to_value(ENVELOPE_FILE, serde_json::to_value(envelope_vector()?))?,
+        to_value(RECEIPT_FILE, serde_json::to_value(receipt_vector()?))?,
+        to_value(MANIFEST_FILE, serde_json::to_value(manifest_vector()?))?,
+    ])
+}
 
-    use bincode::serialize_into;
-    use blake3;
-    use hex;
+fn envelope_vector() -> Result<EnvelopeVector, VectorError> {
+    let input = make_golden_input(VECTOR_INPUT_LEN);
+    let (_, _, header_hash) = make_file_header(VECTOR_CHUNK_SIZE);
+    let (envelope, signatures) = deterministic_trst(&input, VECTOR_CHUNK_SIZE)?;
+    let signing = SigningKey::from_bytes(&TEST_SIGNING_SEED);
+
+    Ok(EnvelopeVector {
+        aes_key: hex::encode(TEST_AES_KEY),
+        signing_seed: hex::encode(TEST_SIGNING_SEED),
+        signing_public_key: hex::encode(signing.verifying_key().to_bytes()),
+        nonce_prefix: hex::encode(TEST_NONCE_PREFIX),
+        key_id: hex::encode(TEST_KEY_ID),
+        ts_ms: TEST_TS_MS,
+        chunk_size: VECTOR_CHUNK_SIZE,
+        input: hex::encode(&input),
+        header_hash: hex::encode(header_hash),
+        record_signatures: signatures
+            .iter()
+            .map(|sig| hex::encode(sig.to_bytes()))
+            .collect(),
+        envelope_blake3: hex::encode(blake3::hash(&envelope).as_bytes()),
+        envelope: hex::encode(envelope),
+    })
+}
 
-    /// crypto (test-only)
-    use aes_gcm::{
-        aead::{Aead, KeyInit, Payload},
-        Aes256Gcm,
+fn receipt_vector() -> Result<ReceiptVector, VectorError> {
+    use crate::applications::receipts::{
+        encode_receipt_payload, OwnershipReceipt, ReceiptEncoding,
     };
-    use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
 
-    // ----------------------------
-    // Fixed, test-only constants
-    // ----------------------------
+    let issuer = SigningKey::from_bytes(&TEST_SIGNING_SEED).verifying_key();
+    let beneficiary = SigningKey::from_bytes(&TEST_BENEFICIARY_SEED).verifying_key();
+    let receipt = OwnershipReceipt {
+        issuer: issuer.to_bytes(),
+        beneficiary: beneficiary.to_bytes(),
+        amount: 1000,
+        prev_envelope_hash: None,
+        description: Some("sealedge test vector".to_string()),
+        created_at: TEST_TS_MS / 1000,
+        expires_at: None,
+        merge_parents: Vec::new(),
+    };
 
-    /// 32-byte AES-256 key (hex = 000102...1f). TEST USE ONLY.
-    const TEST_AES_KEY: [u8; 32] = [
-        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
-        0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D,
-        0x1E, 0x1F,
-    ];
+    let build_err = |e: anyhow::Error| VectorError::Build(e.to_string());
+    let payload_json = encode_receipt_payload(&receipt, None).map_err(build_err)?;
+    let payload_cbor =
+        encode_receipt_payload(&receipt, Some(ReceiptEncoding::Cbor)).map_err(build_err)?;
+
+    Ok(ReceiptVector {
+        issuer_public_key: hex::encode(receipt.issuer),
+        beneficiary_public_key: hex::encode(receipt.beneficiary),
+        amount: receipt.amount,
+        description: receipt.description.unwrap_or_default(),
+        created_at: receipt.created_at,
+        payload_json_blake3: hex::encode(blake3::hash(&payload_json).as_bytes()),
+        payload_json: hex::encode(payload_json),
+        payload_cbor_blake3: hex::encode(blake3::hash(&payload_cbor).as_bytes()),
+        payload_cbor: hex::encode(payload_cbor),
+    })
+}
 
-    /// 32-byte Ed25519 signing seed (NOT a production key). TEST USE ONLY.
-    const TEST_SIGNING_SEED: [u8; 32] = [
-        0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42,
-        0x42, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x24,
-        0x24, 0x24,
-    ];
+fn manifest_vector() -> Result<ManifestVector, VectorError> {
+    use crate::{
+        chain_next, genesis, segment_hash, sign_manifest, DeviceKeypair, ProfileMetadata,
+        SegmentInfo, TrstManifest,
+    };
 
-    /// 4-byte deterministic nonce prefix for tests.
-    const TEST_NONCE_PREFIX: [u8; 4] = [0xAA, 0xBB, 0xCC, 0xDD];
+    let build_err = |e: &dyn std::fmt::Display| VectorError::Build(e.to_string());
+    let keypair =
+        DeviceKeypair::import_secret(&hex::encode(TEST_SIGNING_SEED)).map_err(|e| build_err(&e))?;
 
-    /// 16-byte key id embedded in header + manifest for tests.
-    const TEST_KEY_ID: [u8; 16] = *b"TEST_KEY_ID_16B!";
+    let mut manifest = TrstManifest::new_cam_video();
+    manifest.device.id = "sealedge-test-device".to_string();
+    manifest.device.public_key = keypair.public.clone();
+    if let ProfileMetadata::CamVideo(metadata) = &mut manifest.metadata {
+        metadata.started_at = "2023-11-14T22:13:20Z".to_string();
+        metadata.ended_at = "2023-11-14T22:13:26Z".to_string();
+    }
 
-    /// Deterministic device id hash (we derive at runtime from fixed inputs).
-    const TEST_DEVICE_ID: &[u8] = b"sealedge-test-device";
-    const TEST_SALT: &[u8] = b"sealedge-test-salt";
+    let mut continuity = genesis();
+    for (i, chunk) in VECTOR_ARCHIVE_CHUNKS.iter().enumerate() {
+        let hash = segment_hash(chunk);
+        continuity = chain_next(&continuity, &hash);
+        manifest.segments.push(SegmentInfo {
+            chunk_file: format!("{:05}.bin", i),
+            blake3_hash: hex::encode(hash),
+            start_time: format!("2023-11-14T22:13:{:02}Z", 20 + i * 2),
+            duration_seconds: 2.0,
+            continuity_hash: hex::encode(continuity),
+            nonce: None,
+        });
+    }
 
-    /// Deterministic timestamp used in manifests for tests.
-    const TEST_TS_MS: u64 = 1_700_000_000_000;
+    let canonical = manifest.to_canonical_bytes().map_err(|e| build_err(&e))?;
+    let signature = sign_manifest(&keypair, &canonical).map_err(|e| build_err(&e))?;
+    let canonical_manifest = String::from_utf8(canonical).map_err(|e| build_err(&e))?;
+
+    Ok(ManifestVector {
+        chunks: VECTOR_ARCHIVE_CHUNKS.iter().map(hex::encode).collect(),
+        device_public_key: keypair.public.clone(),
+        canonical_manifest_blake3: hex::encode(
+            blake3::hash(canonical_manifest.as_bytes()).as_bytes(),
+        ),
+        canonical_manifest,
+        signature,
+    })
+}
 
-    /// Replace this after first run (see test output).
-    /// const GOLDEN_TRST_BLAKE3: &str = "<fill-me-after-first-run>";
-    /// Updated for Phase 85 Plan 01 MAGIC rename: b"TRST" -> b"SEAL"
-    /// Post-v6.0 test vectors: b"sealedge-test-*" (v5.x-shaped b"trustedge-test-*" vectors rejected by clean-break checks).
-    const GOLDEN_TRST_BLAKE3: &str =
-        "f2ee31599f7b279363f0024ed1390e299cff7669c9036b425af91a2fb242c17b";
+// ----------------------------
+// Helpers
+// ----------------------------
+
+/// Create a file header for the given chunk size.
+fn make_file_header(chunk_size: usize) -> (FileHeader, [u8; HEADER_LEN], [u8; 32]) {
+    assert!(chunk_size as u64 <= u32::MAX as u64, "chunk too large");
+
+    // device_id_hash = BLAKE3(device_id || salt)
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(TEST_DEVICE_ID);
+    hasher.update(TEST_SALT);
+    let device_hash = *hasher.finalize().as_bytes();
+
+    let fh = FileHeader {
+        version: VERSION,
+        aead_alg: crate::format::AeadAlgorithm::Aes256Gcm as u8,
+        sig_alg: crate::format::SignatureAlgorithm::Ed25519 as u8,
+        hash_alg: crate::format::HashAlgorithm::Blake3 as u8,
+        kdf_alg: crate::format::KdfAlgorithm::Pbkdf2Sha256 as u8,
+        reserved: [0; 3],
+        key_id: TEST_KEY_ID,
+        device_id_hash: device_hash,
+        nonce_prefix: TEST_NONCE_PREFIX,
+        chunk_size: chunk_size as u32,
+    };
 
-    // ----------------------------
-    // Helpers
-    // ----------------------------
+    let header_bytes = fh.to_bytes();
+    let header_hash = *blake3::hash(&header_bytes).as_bytes();
+    (fh, header_bytes, header_hash)
+}
 
-    /// Create a file header for the given chunk size.
-    fn make_file_header(chunk_size: usize) -> (FileHeader, [u8; HEADER_LEN], [u8; 32]) {
-        assert!(chunk_size as u64 <= u32::MAX as u64, "chunk too large");
-
-        // device_id_hash = BLAKE3(device_id || salt)
-        let mut hasher = blake3::Hasher::new();
-        hasher.update(TEST_DEVICE_ID);
-        hasher.update(TEST_SALT);
-        let device_hash = *hasher.finalize().as_bytes();
-
-        let fh = FileHeader {
-            version: VERSION,
-            aead_alg: crate::format::AeadAlgorithm::Aes256Gcm as u8,
-            sig_alg: crate::format::SignatureAlgorithm::Ed25519 as u8,
-            hash_alg: crate::format::HashAlgorithm::Blake3 as u8,
-            kdf_alg: crate::format::KdfAlgorithm::Pbkdf2Sha256 as u8,
-            reserved: [0; 3],
-            key_id: TEST_KEY_ID,
-            device_id_hash: device_hash,
-            nonce_prefix: TEST_NONCE_PREFIX,
-            chunk_size: chunk_size as u32,
-        };
+/// Create a nonce for the given prefix and sequence number.
+fn make_nonce(prefix: [u8; 4], seq: u64) -> [u8; NONCE_LEN] {
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    nonce_bytes[..4].copy_from_slice(&prefix);
+    nonce_bytes[4..].copy_from_slice(&seq.to_be_bytes());
+    nonce_bytes
+}
 
-        let header_bytes = fh.to_bytes();
-        let header_hash = *blake3::hash(&header_bytes).as_bytes();
-        (fh, header_bytes, header_hash)
-    }
+/// Produce a deterministic `.trst` envelope for `input`, chunked with `chunk_size`,
+/// along with the manifest signature of each record.
+fn deterministic_trst(
+    input: &[u8],
+    chunk_size: usize,
+) -> Result<(Vec<u8>, Vec<Signature>), VectorError> {
+    let build_err = |e: &dyn std::fmt::Display| VectorError::Build(e.to_string());
+
+    // Keys / crypto (fixed for tests)
+    let cipher = Aes256Gcm::new((&TEST_AES_KEY).into());
+    let signing = SigningKey::from_bytes(&TEST_SIGNING_SEED);
+    let verify: VerifyingKey = signing.verifying_key();
+
+    // Header & hash
+    let (fh, header_bytes, header_hash) = make_file_header(chunk_size);
+    let nonce_prefix = fh.nonce_prefix;
+
+    // Build the .seal in memory
+    let mut out = Vec::<u8>::new();
+    let mut signatures = Vec::new();
+
+    // Preamble
+    out.write_all(MAGIC)?;
+    out.write_all(&[VERSION])?;
+
+    // Stream header
+    let sh = StreamHeader {
+        v: VERSION,
+        header: header_bytes.to_vec(),
+        header_hash,
+    };
+    write_stream_header(&mut out, &sh).map_err(|e| build_err(&e))?;
 
-    /// Create a nonce for the given prefix and sequence number.
-    fn make_nonce(prefix: [u8; 4], seq: u64) -> [u8; NONCE_LEN] {
-        let mut nonce_bytes = [0u8; NONCE_LEN];
-        nonce_bytes[..4].copy_from_slice(&prefix);
-        nonce_bytes[4..].copy_from_slice(&seq.to_be_bytes());
-        nonce_bytes
-    }
+    // Chunk & record loop
+    let mut seq: u64 = 0;
+    let mut offset: usize = 0;
 
-    /// Produce a deterministic `.trst` envelope for `input`, chunked with `chunk_size`.
-    fn deterministic_trst(input: &[u8], chunk_size: usize) -> Vec<u8> {
-        // Keys / crypto (fixed for tests)
-        let cipher = Aes256Gcm::new((&TEST_AES_KEY).into());
-        let signing = SigningKey::from_bytes(&TEST_SIGNING_SEED);
-        let verify: VerifyingKey = signing.verifying_key();
+    while offset < input.len() {
+        let end = usize::min(offset + chunk_size, input.len());
+        let pt = &input[offset..end];
 
-        // Header & hash
-        let (fh, header_bytes, header_hash) = make_file_header(chunk_size);
-        let nonce_prefix = fh.nonce_prefix;
+        seq = seq
+            .checked_add(1)
+            .ok_or_else(|| VectorError::Build("record sequence overflow".to_string()))?;
 
-        // Build the .seal in memory
-        let mut out = Vec::<u8>::new();
+        let nonce_bytes = make_nonce(nonce_prefix, seq);
+        let nonce = (&nonce_bytes).into();
 
-        // Preamble
-        out.write_all(MAGIC).unwrap();
-        out.write_all(&[VERSION]).unwrap();
+        // Plaintext hash
+        let pt_hash = blake3::hash(pt);
 
-        // Stream header
-        let sh = StreamHeader {
-            v: VERSION,
-            header: header_bytes.to_vec(),
+        // Manifest (use fixed timestamp + key_id)
+        let m = Manifest {
+            v: 1,
+            ts_ms: TEST_TS_MS,
+            seq,
             header_hash,
+            pt_hash: *pt_hash.as_bytes(),
+            ai_used: false,
+            model_ids: vec![],
+            key_id: TEST_KEY_ID,
+            data_type: crate::DataType::File { mime_type: None }, // Test data
+            chunk_len: pt.len() as u32,                           // Bind actual chunk length to AAD
+        };
+
+        let m_bytes = bincode::serialize(&m).map_err(|e| build_err(&e))?;
+        let sig: Signature = crate::format::sign_manifest_with_domain(&signing, &m_bytes);
+        signatures.push(sig);
+        let sm = SignedManifest {
+            manifest: m_bytes.clone(),
+            sig: sig.to_bytes().to_vec(),
+            pubkey: verify.to_bytes().to_vec(),
+        };
+
+        // AAD = [header_hash || seq || nonce || blake3(manifest) || chunk_len]
+        let mh = blake3::hash(&m_bytes);
+        let aad = build_aad(&header_hash, seq, &nonce_bytes, mh.as_bytes(), m.chunk_len);
+
+        // Encrypt
+        let ct = cipher
+            .encrypt(nonce, Payload { msg: pt, aad: &aad })
+            .map_err(|_| VectorError::Build("AES-GCM encrypt failed".to_string()))?;
+
+        // Record
+        let rec = Record {
+            seq,
+            nonce: nonce_bytes,
+            sm,
+            ct,
         };
-        write_stream_header(&mut out, &sh).unwrap();
-
-        // Chunk & record loop
-        let mut seq: u64 = 0;
-        let mut offset: usize = 0;
-
-        while offset < input.len() {
-            let end = usize::min(offset + chunk_size, input.len());
-            let pt = &input[offset..end];
-
-            seq = seq.checked_add(1).expect("seq overflow");
-
-            let nonce_bytes = make_nonce(nonce_prefix, seq);
-            let nonce = (&nonce_bytes).into();
-
-            // Plaintext hash
-            let pt_hash = blake3::hash(pt);
-
-            // Manifest (use fixed timestamp + key_id)
-            let m = Manifest {
-                v: 1,
-                ts_ms: TEST_TS_MS,
-                seq,
-                header_hash,
-                pt_hash: *pt_hash.as_bytes(),
-                ai_used: false,
-                model_ids: vec![],
-                key_id: TEST_KEY_ID,
-                data_type: crate::DataType::File { mime_type: None }, // Test data
-                chunk_len: pt.len() as u32, // Bind actual chunk length to AAD
-            };
-
-            let m_bytes = bincode::serialize(&m).expect("manifest serialize");
-            let sig: Signature = crate::format::sign_manifest_with_domain(&signing, &m_bytes);
-            let sm = SignedManifest {
-                manifest: m_bytes.clone(),
-                sig: sig.to_bytes().to_vec(),
-                pubkey: verify.to_bytes().to_vec(),
-            };
-
-            // AAD = [header_hash || seq || nonce || blake3(manifest) || chunk_len]
-            let mh = blake3::hash(&m_bytes);
-            let aad = build_aad(&header_hash, seq, &nonce_bytes, mh.as_bytes(), m.chunk_len);
-
-            // Encrypt
-            let ct = cipher
-                .encrypt(nonce, Payload { msg: pt, aad: &aad })
-                .expect("AES-GCM encrypt failed");
-
-            // Record
-            let rec = Record {
-                seq,
-                nonce: nonce_bytes,
-                sm,
-                ct,
-            };
-
-            serialize_into(&mut out, &rec).expect("write record");
-            offset = end;
-        }
 
-        out
+        serialize_into(&mut out, &rec).map_err(|e| build_err(&e))?;
+        offset = end;
     }
 
-    /// Deterministic generator for a stable pseudo-random test input buffer.
-    fn make_golden_input(len: usize) -> Vec<u8> {
-        // Simple LCG to create repeatable "random-looking" bytes.
-        let mut v = Vec::with_capacity(len);
-        let (mut x, a, c, m) = (
-            0x1234_5678u64,
-            6364136223846793005u64,
-            1442695040888963407u64,
-            1u64 << 63,
-        );
-        for _ in 0..len {
-            x = (a.wrapping_mul(x).wrapping_add(c)) % m;
-            v.push(((x >> 24) & 0xFF) as u8);
-        }
-        v
+    Ok((out, signatures))
+}
+
+/// Deterministic generator for a stable pseudo-random test input buffer.
+fn make_golden_input(len: usize) -> Vec<u8> {
+    // Simple LCG to create repeatable "random-looking" bytes.
+    let mut v = Vec::with_capacity(len);
+    let (mut x, a, c, m) = (
+        0x1234_5678u64,
+        6364136223846793005u64,
+        1442695040888963407u64,
+        1u64 << 63,
+    );
+    for _ in 0..len {
+        x = (a.wrapping_mul(x).wrapping_add(c)) % m;
+        v.push(((x >> 24) & 0xFF) as u8);
     }
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Replace this after first run (see test output).
+    /// const GOLDEN_TRST_BLAKE3: &str = "<fill-me-after-first-run>";
+    /// Updated for Phase 85 Plan 01 MAGIC rename: b"TRST" -> b"SEAL"
+    /// Post-v6.0 test vectors: b"sealedge-test-*" (v5.x-shaped b"trustedge-test-*" vectors rejected by clean-break checks).
+    const GOLDEN_TRST_BLAKE3: &str =
+        "f2ee31599f7b279363f0024ed1390e299cff7669c9036b425af91a2fb242c17b";
 
     // ----------------------------
     // The golden test
@@ -231,7 +494,7 @@ mod tests {
         let chunk_size = 4096;
 
         // Build deterministic .seal bytes
-        let trst = deterministic_trst(&input, chunk_size);
+        let (trst, _) = deterministic_trst(&input, chunk_size).unwrap();
 
         // Compute digest and print it for first-time copy
         let digest_hex = hex::encode(blake3::hash(&trst).as_bytes());
@@ -246,4 +509,44 @@ mod tests {
              If you intentionally changed format/crypto, update (rebase) the golden digest."
         );
     }
+
+    #[test]
+    fn export_then_verify_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        export(dir.path()).unwrap();
+        verify_against(dir.path()).unwrap();
+
+        // The published envelope matches the golden generator and its signatures verify
+        let envelope: EnvelopeVector =
+            serde_json::from_slice(&fs::read(dir.path().join(ENVELOPE_FILE)).unwrap()).unwrap();
+        let public: [u8; 32] = hex::decode(&envelope.signing_public_key)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(envelope.record_signatures.len(), 4);
+        assert_eq!(
+            VerifyingKey::from_bytes(&public).unwrap(),
+            SigningKey::from_bytes(&TEST_SIGNING_SEED).verifying_key()
+        );
+    }
+
+    #[test]
+    fn corrupted_vector_is_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        export(dir.path()).unwrap();
+
+        let path = dir.path().join(MANIFEST_FILE);
+        let mut manifest: ManifestVector =
+            serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        manifest.signature.replace_range(8..12, "AAAA");
+        fs::write(&path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+
+        match verify_against(dir.path()) {
+            Err(VectorError::Mismatch { file, field }) => {
+                assert_eq!(file, MANIFEST_FILE);
+                assert_eq!(field, "signature");
+            }
+            other => panic!("expected a mismatch, got {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,988 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Building signed .seal archives from input files
+//!
+//! Produces the same layout as `seal wrap` with the software backend: each chunk is
+//! encrypted with XChaCha20-Poly1305 under a key derived from the device key and
+//! stored as `[nonce:24][ciphertext]`, chunk hashes feed the continuity chain, and
+//! the manifest is signed with Ed25519.
+//!
+//! With [`WrapConfig::archive_key`] set, chunks are instead encrypted under that
+//! per-archive key, so the footage can be shared without the device key. Chunk
+//! files then hold only the ciphertext and each nonce is recorded in its segment's
+//! `nonce` field; see [`crate::archive::decrypt_archive`] for the read side.
+//!
+//! [`append_segments`] extends a signed archive with new chunks for live capture.
+//!
+//! [`wrap_file_resumable`] writes chunks one at a time and records progress in a
+//! sidecar state file, so an interrupted build continues where it stopped.
+
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use base64::Engine as _;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::archive::{
+    check_archive_signature, hash_chunk_file, merkle::segments_root, read_checked_manifest,
+    write_archive, write_manifest_files,
+};
+use crate::chain::{chain_next, genesis, segment_hash};
+use crate::crypto::{derive_chunk_key, encrypt_segment, format_nonce, generate_aad, sign_manifest};
+use crate::secret::Secret;
+use crate::{ChunkInfo, DeviceInfo, DeviceKeypair, ProfileMetadata, SegmentInfo, TrstManifest};
+
+const TRST_VERSION: &str = "0.1.0";
+
+/// Sidecar recording resumable wrap progress; removed once the archive is complete
+pub const WRAP_STATE_FILE: &str = "wrap-state.json";
+
+/// Archive settings for [`wrap_file`] and [`wrap_file_resumable`]
+#[derive(Debug, Clone)]
+pub struct WrapConfig {
+    /// Profile metadata; its variant selects the manifest `profile` and its
+    /// `started_at` is bound into every chunk's AAD
+    pub metadata: ProfileMetadata,
+    /// Plaintext bytes per chunk
+    pub chunk_size: usize,
+    /// Nominal chunk duration recorded in the manifest
+    pub chunk_seconds: f64,
+    /// `kind:value` claims, checked against [`crate::ClaimSet`] before wrapping
+    pub claims: Vec<String>,
+    /// [`crate::archive_hash`] of the previous archive in a cross-archive chain
+    pub prev_archive_hash: Option<String>,
+    /// Base64 device attestation blob, see [`crate::DeviceAttestation`]
+    pub device_attestation: Option<String>,
+    /// Seed the nonce RNG for reproducible output (testing/CI only, not secure)
+    pub seed: Option<u64>,
+    /// Per-archive XChaCha20-Poly1305 key; `None` derives the chunk key from the
+    /// device key
+    pub archive_key: Option<Secret<[u8; 32]>>,
+}
+
+impl WrapConfig {
+    /// Defaults matching `seal wrap`: 1 MiB chunks, 2 s for cam.video, 0 s otherwise
+    pub fn new(metadata: ProfileMetadata) -> Self {
+        let chunk_seconds = match metadata {
+            ProfileMetadata::CamVideo(_) => 2.0,
+            _ => 0.0,
+        };
+        Self {
+            metadata,
+            chunk_size: 1_048_576,
+            chunk_seconds,
+            claims: vec!["location:unknown".to_string()],
+            prev_archive_hash: None,
+            device_attestation: None,
+            seed: None,
+            archive_key: None,
+        }
+    }
+
+    fn profile(&self) -> &'static str {
+        self.metadata.profile()
+    }
+
+    fn check(&self) -> Result<()> {
+        if self.chunk_size == 0 {
+            anyhow::bail!("chunk_size must be greater than zero");
+        }
+        crate::ClaimSet::parse(&self.claims).context("Invalid manifest claims")?;
+        Ok(())
+    }
+}
+
+/// Wrap `input` into a signed archive at `output_dir`
+pub fn wrap_file<P: AsRef<Path>, Q: AsRef<Path>>(
+    input: P,
+    output_dir: Q,
+    signing_key: &DeviceKeypair,
+    config: &WrapConfig,
+) -> Result<TrstManifest> {
+    let data = read_input(input.as_ref(), config)?;
+
+    let mut sealer = ChunkSealer::new(signing_key, config)?;
+    let sealed = data
+        .chunks(config.chunk_size)
+        .map(|plaintext| sealer.seal(plaintext))
+        .collect::<Result<Vec<_>>>()?;
+
+    finish_wrap(
+        output_dir.as_ref(),
+        &signing_key.public,
+        config,
+        sealed,
+        software_signer(signing_key),
+    )
+}
+
+/// Wrap `input` like [`wrap_file`], with the manifest signed outside this process
+///
+/// Chunks are encrypted under a key derived from `chunk_key` (or the config's
+/// archive key) exactly as in [`wrap_file`], while `public_key` is recorded as the
+/// device key and `sign` turns the manifest's canonical bytes into its prefixed
+/// signature string. This is how `seal wrap --backend yubikey` signs in hardware.
+pub fn wrap_file_with_signer<P, Q, F>(
+    input: P,
+    output_dir: Q,
+    chunk_key: &DeviceKeypair,
+    public_key: &str,
+    config: &WrapConfig,
+    sign: F,
+) -> Result<TrstManifest>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+    F: FnOnce(&[u8]) -> Result<String>,
+{
+    let data = read_input(input.as_ref(), config)?;
+
+    let mut sealer = ChunkSealer::new(chunk_key, config)?;
+    let sealed = data
+        .chunks(config.chunk_size)
+        .map(|plaintext| sealer.seal(plaintext))
+        .collect::<Result<Vec<_>>>()?;
+
+    finish_wrap(output_dir.as_ref(), public_key, config, sealed, sign)
+}
+
+/// Wrap `input` like [`wrap_file`], encrypting and hashing chunks on all cores
+///
+/// Nonces are still drawn from one stream in chunk order and the continuity chain
+/// is built sequentially from the hashes, so with a `seed` the archive is
+/// byte-identical to [`wrap_file`].
+#[cfg(feature = "parallel")]
+pub fn wrap_file_parallel<P: AsRef<Path>, Q: AsRef<Path>>(
+    input: P,
+    output_dir: Q,
+    signing_key: &DeviceKeypair,
+    config: &WrapConfig,
+) -> Result<TrstManifest> {
+    use rayon::prelude::*;
+
+    let data = read_input(input.as_ref(), config)?;
+
+    let mut sealer = ChunkSealer::new(signing_key, config)?;
+    let nonces: Vec<[u8; 24]> = data
+        .chunks(config.chunk_size)
+        .map(|_| sealer.next_nonce())
+        .collect();
+    let cipher = &sealer.cipher;
+    let sealed = data
+        .par_chunks(config.chunk_size)
+        .zip(nonces.par_iter())
+        .map(|(plaintext, nonce)| cipher.seal(nonce, plaintext))
+        .collect::<Result<Vec<_>>>()?;
+
+    finish_wrap(
+        output_dir.as_ref(),
+        &signing_key.public,
+        config,
+        sealed,
+        software_signer(signing_key),
+    )
+}
+
+fn read_input(input: &Path, config: &WrapConfig) -> Result<Vec<u8>> {
+    config.check()?;
+    let data = fs::read(input)
+        .with_context(|| format!("Failed to read input file: {}", input.display()))?;
+    if data.is_empty() {
+        anyhow::bail!("Input file is empty");
+    }
+    Ok(data)
+}
+
+/// Chain, sign, and write sealed chunks (in order)
+fn finish_wrap(
+    output_dir: &Path,
+    public_key: &str,
+    config: &WrapConfig,
+    sealed: Vec<SealedChunk>,
+    sign: impl FnOnce(&[u8]) -> Result<String>,
+) -> Result<TrstManifest> {
+    let mut chain = SegmentChain::new();
+    let mut chunks = Vec::with_capacity(sealed.len());
+    for chunk in sealed {
+        chain.push(config, chunk.hash, chunk.nonce);
+        chunks.push(chunk.bytes);
+    }
+
+    let manifest = sign_archive_manifest(public_key, config, chain.segments, sign)?;
+    let signature = manifest.signature.clone().unwrap_or_default();
+    write_archive(output_dir, &manifest, chunks, signature.as_bytes())?;
+    Ok(manifest)
+}
+
+/// Wrap `input` into `output_dir`, continuing an earlier interrupted attempt
+///
+/// Chunk files recorded in the sidecar state are re-hashed and kept if intact; the
+/// build resumes at the first missing or damaged chunk. The manifest is signed only
+/// once every chunk exists, after which the sidecar is removed. With a `seed`, the
+/// result is byte-identical to an uninterrupted [`wrap_file`].
+pub fn wrap_file_resumable<P: AsRef<Path>, Q: AsRef<Path>>(
+    input: P,
+    output_dir: Q,
+    signing_key: &DeviceKeypair,
+    config: &WrapConfig,
+) -> Result<TrstManifest> {
+    let output_dir = output_dir.as_ref();
+    let chain =
+        write_chunks_resumable(input.as_ref(), output_dir, signing_key, config, usize::MAX)?;
+
+    let manifest = sign_archive_manifest(
+        &signing_key.public,
+        config,
+        chain.segments,
+        software_signer(signing_key),
+    )?;
+    let signature = manifest.signature.clone().unwrap_or_default();
+    write_manifest_files(output_dir, &manifest, signature.as_bytes())?;
+    fs::remove_file(output_dir.join(WRAP_STATE_FILE))?;
+    Ok(manifest)
+}
+
+/// Append plaintext chunks to an existing signed archive
+///
+/// The archive's signature must currently verify under `signing_key`, which must be
+/// the archive's device key. New chunks are sealed with the archive's own AAD and
+/// chunk duration, the continuity chain continues from the last segment's
+/// `continuity_hash`, and the updated manifest is re-signed. Existing chunk files
+/// are not re-read; use [`crate::validate_archive`] for a full integrity check.
+pub fn append_segments<P: AsRef<Path>>(
+    archive_path: P,
+    signing_key: &DeviceKeypair,
+    new_chunks: &[Vec<u8>],
+) -> Result<TrstManifest> {
+    let archive_path = archive_path.as_ref();
+    let mut manifest = read_checked_manifest(archive_path)?;
+    if manifest.device.public_key != signing_key.public {
+        anyhow::bail!("Signing key does not match the archive's device key");
+    }
+    check_archive_signature(&manifest, &signing_key.public)
+        .map_err(|e| anyhow::anyhow!("Refusing to append to unverified archive: {}", e))?;
+    if manifest.segments.iter().any(|s| s.nonce.is_some()) {
+        anyhow::bail!("Appending to an archive sealed under an archive key is not supported");
+    }
+
+    let config = WrapConfig {
+        metadata: manifest.metadata.clone(),
+        chunk_size: manifest.chunk.size_bytes as usize,
+        chunk_seconds: manifest.chunk.duration_seconds,
+        claims: manifest.claims.clone(),
+        prev_archive_hash: manifest.prev_archive_hash.clone(),
+        device_attestation: manifest.device_attestation.clone(),
+        seed: None,
+        archive_key: None,
+    };
+    if let Some(chunk) = new_chunks
+        .iter()
+        .find(|c| c.is_empty() || c.len() > config.chunk_size)
+    {
+        anyhow::bail!(
+            "Appended chunk of {} bytes does not fit chunk size {}",
+            chunk.len(),
+            config.chunk_size
+        );
+    }
+
+    let mut sealer = ChunkSealer::new(signing_key, &config)?;
+    let mut chain = SegmentChain::resume(std::mem::take(&mut manifest.segments))?;
+    let chunks_dir = archive_path.join("chunks");
+    for plaintext in new_chunks {
+        let sealed = sealer.seal(plaintext)?;
+        fs::write(chunk_path(&chunks_dir, chain.segments.len()), &sealed.bytes)?;
+        chain.push(&config, sealed.hash, sealed.nonce);
+    }
+
+    manifest.segments = chain.segments;
+    sign_in_place(&mut manifest, software_signer(signing_key))?;
+    let signature = manifest.signature.clone().unwrap_or_default();
+    write_manifest_files(archive_path, &manifest, signature.as_bytes())?;
+    Ok(manifest)
+}
+
+/// Progress of a resumable wrap, as persisted in [`WRAP_STATE_FILE`]
+#[derive(Serialize, Deserialize, Debug)]
+struct WrapState {
+    input_len: u64,
+    /// BLAKE3 of the whole input, so a changed input is never mixed with old chunks
+    input_hash: String,
+    chunk_size: usize,
+    profile: String,
+    started_at: String,
+    public_key: String,
+    /// BLAKE3 of each chunk file written so far, in order
+    chunk_hashes: Vec<String>,
+}
+
+impl WrapState {
+    fn same_build(&self, other: &WrapState) -> bool {
+        self.input_len == other.input_len
+            && self.input_hash == other.input_hash
+            && self.chunk_size == other.chunk_size
+            && self.profile == other.profile
+            && self.started_at == other.started_at
+            && self.public_key == other.public_key
+    }
+
+    fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read wrap state: {}", path.display()))?;
+        Ok(Some(serde_json::from_str(&json).with_context(|| {
+            format!("Corrupt wrap state: {}", path.display())
+        })?))
+    }
+
+    /// Write via a temporary file so a crash never leaves a torn state file
+    fn save(&self, path: &Path) -> Result<()> {
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Keep the longest prefix of recorded chunks whose files still hash as recorded
+    fn retain_intact_chunks(&mut self, chunks_dir: &Path) {
+        let mut buffer = vec![0u8; 64 * 1024];
+        let intact = self
+            .chunk_hashes
+            .iter()
+            .enumerate()
+            .take_while(|(index, recorded)| {
+                hash_chunk_file(&chunk_path(chunks_dir, *index), &mut buffer)
+                    .is_ok_and(|hash| hex::encode(hash) == **recorded)
+            })
+            .count();
+        self.chunk_hashes.truncate(intact);
+    }
+}
+
+/// Encrypt and write chunk files, stopping after `limit` chunks exist
+fn write_chunks_resumable(
+    input: &Path,
+    output_dir: &Path,
+    signing_key: &DeviceKeypair,
+    config: &WrapConfig,
+    limit: usize,
+) -> Result<SegmentChain> {
+    config.check()?;
+    if config.archive_key.is_some() {
+        // Replaying the nonce stream cannot recover unseeded nonces for chunks
+        // written before the interruption
+        anyhow::bail!("Resumable wrap does not support an archive key");
+    }
+    let mut file = File::open(input)
+        .with_context(|| format!("Failed to read input file: {}", input.display()))?;
+    let input_len = file.metadata()?.len();
+    if input_len == 0 {
+        anyhow::bail!("Input file is empty");
+    }
+    let mut hasher = blake3::Hasher::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    let chunk_count = input_len.div_ceil(config.chunk_size as u64) as usize;
+
+    let chunks_dir = output_dir.join("chunks");
+    fs::create_dir_all(&chunks_dir)?;
+    let state_path = output_dir.join(WRAP_STATE_FILE);
+
+    let mut state = WrapState {
+        input_len,
+        input_hash: hasher.finalize().to_hex().to_string(),
+        chunk_size: config.chunk_size,
+        profile: config.profile().to_string(),
+        started_at: config.metadata.started_at().to_string(),
+        public_key: signing_key.public.clone(),
+        chunk_hashes: Vec::new(),
+    };
+    if let Some(previous) = WrapState::load(&state_path)? {
+        if !previous.same_build(&state) {
+            anyhow::bail!(
+                "{} records a different wrap (input, key, or settings changed)",
+                state_path.display()
+            );
+        }
+        state.chunk_hashes = previous.chunk_hashes;
+        state.retain_intact_chunks(&chunks_dir);
+    }
+
+    // Replay the nonce stream and chain over chunks that are already done
+    let mut sealer = ChunkSealer::new(signing_key, config)?;
+    let mut chain = SegmentChain::new();
+    for recorded in &state.chunk_hashes {
+        let hash: [u8; 32] = hex::decode(recorded)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Corrupt chunk hash in wrap state"))?;
+        sealer.skip();
+        chain.push(config, hash, None);
+    }
+
+    let start = state.chunk_hashes.len();
+    file.seek(SeekFrom::Start((start * config.chunk_size) as u64))?;
+    let mut buffer = vec![0u8; config.chunk_size];
+    for index in start..chunk_count.min(limit) {
+        let len = read_up_to(&mut file, &mut buffer)?;
+        let sealed = sealer.seal(&buffer[..len])?;
+        fs::write(chunk_path(&chunks_dir, index), &sealed.bytes)?;
+
+        chain.push(config, sealed.hash, sealed.nonce);
+        state.chunk_hashes.push(hex::encode(sealed.hash));
+        state.save(&state_path)?;
+    }
+
+    Ok(chain)
+}
+
+/// Fill `buffer` from `reader`, returning fewer bytes only at end of input
+fn read_up_to(reader: &mut impl Read, buffer: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        let read = reader.read(&mut buffer[filled..])?;
+        if read == 0 {
+            break;
+        }
+        filled += read;
+    }
+    Ok(filled)
+}
+
+fn chunk_path(chunks_dir: &Path, index: usize) -> std::path::PathBuf {
+    chunks_dir.join(format!("{:05}.bin", index))
+}
+
+/// A chunk file's contents with its hash and, under an archive key, its nonce
+struct SealedChunk {
+    bytes: Vec<u8>,
+    hash: [u8; 32],
+    nonce: Option<String>,
+}
+
+/// Chunk key and AAD shared by every chunk of an archive
+struct ChunkCipher {
+    key: chacha20poly1305::Key,
+    aad: Vec<u8>,
+    /// Record nonces in the manifest rather than in the chunk files
+    detached_nonces: bool,
+}
+
+impl ChunkCipher {
+    /// Encrypt a chunk as `[nonce:24][ciphertext]`, or as bare ciphertext with
+    /// the nonce returned for the manifest
+    fn seal(&self, nonce: &[u8; 24], plaintext: &[u8]) -> Result<SealedChunk> {
+        let ciphertext = encrypt_segment(&self.key, nonce, plaintext, &self.aad)?;
+        let (bytes, nonce) = if self.detached_nonces {
+            (ciphertext, Some(format_nonce(nonce)))
+        } else {
+            let mut sealed = Vec::with_capacity(nonce.len() + ciphertext.len());
+            sealed.extend_from_slice(nonce);
+            sealed.extend_from_slice(&ciphertext);
+            (sealed, None)
+        };
+        Ok(SealedChunk {
+            hash: segment_hash(&bytes),
+            bytes,
+            nonce,
+        })
+    }
+}
+
+/// Encrypts chunks in order under one cipher and nonce stream
+struct ChunkSealer {
+    cipher: ChunkCipher,
+    nonces: Box<dyn RngCore>,
+}
+
+impl ChunkSealer {
+    fn new(signing_key: &DeviceKeypair, config: &WrapConfig) -> Result<Self> {
+        let device_id = device_id_for_key(&signing_key.public)?;
+        let nonces: Box<dyn RngCore> = match config.seed {
+            Some(seed) => Box::new(ChaCha20Rng::seed_from_u64(seed)),
+            None => Box::new(rand::rng()),
+        };
+        let key = match &config.archive_key {
+            Some(key) => chacha20poly1305::Key::from(*key.expose_secret()),
+            None => derive_chunk_key(signing_key.secret_bytes()),
+        };
+        let cipher = ChunkCipher {
+            key,
+            aad: generate_aad(
+                TRST_VERSION,
+                config.profile(),
+                &device_id,
+                config.metadata.started_at(),
+            ),
+            detached_nonces: config.archive_key.is_some(),
+        };
+        Ok(Self { cipher, nonces })
+    }
+
+    /// Encrypt the next chunk
+    fn seal(&mut self, plaintext: &[u8]) -> Result<SealedChunk> {
+        let nonce = self.next_nonce();
+        self.cipher.seal(&nonce, plaintext)
+    }
+
+    fn next_nonce(&mut self) -> [u8; 24] {
+        let mut nonce = [0u8; 24];
+        self.nonces.fill_bytes(&mut nonce);
+        nonce
+    }
+
+    /// Advance the nonce stream past a chunk that is already written
+    fn skip(&mut self) {
+        self.next_nonce();
+    }
+}
+
+/// Segment list with the running continuity hash
+struct SegmentChain {
+    state: [u8; 32],
+    segments: Vec<SegmentInfo>,
+}
+
+impl SegmentChain {
+    fn new() -> Self {
+        Self {
+            state: genesis(),
+            segments: Vec::new(),
+        }
+    }
+
+    /// Continue the chain after existing segments
+    fn resume(segments: Vec<SegmentInfo>) -> Result<Self> {
+        let state = match segments.last() {
+            Some(last) => hex::decode(&last.continuity_hash)?
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Continuity hash must be 32 bytes"))?,
+            None => genesis(),
+        };
+        Ok(Self { state, segments })
+    }
+
+    fn push(&mut self, config: &WrapConfig, hash: [u8; 32], nonce: Option<String>) {
+        let index = self.segments.len();
+        self.state = chain_next(&self.state, &hash);
+
+        // Time-based start for cam.video, index-based otherwise (as `seal wrap`)
+        let start_time = match config.metadata {
+            ProfileMetadata::CamVideo(_) => format!("{:.3}s", index as f64 * config.chunk_seconds),
+            _ => format!("segment-{}", index),
+        };
+        self.segments.push(SegmentInfo {
+            chunk_file: format!("{:05}.bin", index),
+            blake3_hash: hex::encode(hash),
+            start_time,
+            duration_seconds: config.chunk_seconds,
+            continuity_hash: hex::encode(self.state),
+            nonce,
+        });
+    }
+}
+
+/// Build the manifest for `segments` and sign its canonical bytes
+fn sign_archive_manifest(
+    public_key: &str,
+    config: &WrapConfig,
+    segments: Vec<SegmentInfo>,
+    sign: impl FnOnce(&[u8]) -> Result<String>,
+) -> Result<TrstManifest> {
+    let mut manifest = TrstManifest {
+        trst_version: TRST_VERSION.to_string(),
+        profile: config.profile().to_string(),
+        device: DeviceInfo {
+            id: device_id_for_key(public_key)?,
+            model: "TrustEdgeRefCam".to_string(),
+            firmware_version: "1.0.0".to_string(),
+            public_key: public_key.to_string(),
+        },
+        metadata: config.metadata.clone(),
+        chunk: ChunkInfo {
+            size_bytes: config.chunk_size as u64,
+            duration_seconds: config.chunk_seconds,
+        },
+        segments,
+        claims: config.claims.clone(),
+        prev_archive_hash: config.prev_archive_hash.clone(),
+        device_attestation: config.device_attestation.clone(),
+        segments_root: None,
+        signature: None,
+    };
+    sign_in_place(&mut manifest, sign)?;
+    Ok(manifest)
+}
+
+/// Sign canonical manifest bytes with a software device key
+fn software_signer(signing_key: &DeviceKeypair) -> impl FnOnce(&[u8]) -> Result<String> + '_ {
+    move |bytes| Ok(sign_manifest(signing_key, bytes)?)
+}
+
+/// Record the segments' Merkle root and sign the manifest's canonical bytes
+fn sign_in_place(
+    manifest: &mut TrstManifest,
+    sign: impl FnOnce(&[u8]) -> Result<String>,
+) -> Result<()> {
+    manifest.segments_root = segments_root(&manifest.segments)?.map(hex::encode);
+    let canonical_bytes = manifest.to_canonical_bytes()?;
+    manifest.signature = Some(sign(&canonical_bytes)?);
+    Ok(())
+}
+
+/// Derive a device id from a prefixed public key string
+///
+/// The first 6 bytes of the raw key (after the `ed25519:` or `ecdsa-p256:` prefix),
+/// formatted as `te:cam:<hex>`.
+pub fn device_id_for_key(pub_key_str: &str) -> Result<String> {
+    let raw_b64 = if let Some(rest) = pub_key_str.strip_prefix("ed25519:") {
+        rest
+    } else if let Some(rest) = pub_key_str.strip_prefix("ecdsa-p256:") {
+        rest
+    } else {
+        anyhow::bail!("Unrecognized public key prefix in: {}", pub_key_str);
+    };
+    let key_bytes = base64::engine::general_purpose::STANDARD
+        .decode(raw_b64)
+        .with_context(|| "Failed to decode public key bytes for device_id")?;
+    if key_bytes.len() < 6 {
+        anyhow::bail!(
+            "Public key bytes too short for device_id (got {} bytes)",
+            key_bytes.len()
+        );
+    }
+    Ok(format!("te:cam:{}", hex::encode(&key_bytes[..6])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{validate_archive, CamVideoMetadata};
+    use tempfile::TempDir;
+
+    const CHUNK_SIZE: usize = 4096;
+
+    fn test_config() -> WrapConfig {
+        let mut config = WrapConfig::new(ProfileMetadata::CamVideo(CamVideoMetadata {
+            started_at: "2025-01-01T00:00:00Z".to_string(),
+            ended_at: "2025-01-01T00:00:20Z".to_string(),
+            timezone: "UTC".to_string(),
+            fps: 30.0,
+            resolution: "1920x1080".to_string(),
+            codec: "raw".to_string(),
+        }));
+        config.chunk_size = CHUNK_SIZE;
+        config.seed = Some(42);
+        config
+    }
+
+    /// Ten chunks, the last one partial
+    fn write_input(dir: &Path, fill: u8) -> std::path::PathBuf {
+        let path = dir.join("input.bin");
+        let data: Vec<u8> = (0..CHUNK_SIZE * 9 + 100)
+            .map(|i| (i % 251) as u8 ^ fill)
+            .collect();
+        fs::write(&path, data).unwrap();
+        path
+    }
+
+    fn assert_archives_identical(a: &Path, b: &Path) {
+        for file in ["manifest.json", "signatures/manifest.sig"] {
+            assert_eq!(
+                fs::read(a.join(file)).unwrap(),
+                fs::read(b.join(file)).unwrap()
+            );
+        }
+        let count = fs::read_dir(a.join("chunks")).unwrap().count();
+        assert_eq!(count, fs::read_dir(b.join("chunks")).unwrap().count());
+        for index in 0..count {
+            let name = format!("chunks/{:05}.bin", index);
+            assert_eq!(
+                fs::read(a.join(&name)).unwrap(),
+                fs::read(b.join(&name)).unwrap(),
+                "{} differs",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_wrap_file_produces_valid_archive() {
+        let temp = TempDir::new().unwrap();
+        let input = write_input(temp.path(), 0);
+        let keypair = DeviceKeypair::generate().unwrap();
+        let output = temp.path().join("clip.seal");
+
+        let manifest = wrap_file(&input, &output, &keypair, &test_config()).unwrap();
+        assert_eq!(manifest.segments.len(), 10);
+        assert_eq!(manifest.profile, "cam.video");
+        validate_archive(&output).unwrap();
+
+        let canonical = manifest.to_canonical_bytes().unwrap();
+        let signature = manifest.signature.as_deref().unwrap();
+        assert!(crate::verify_manifest(&keypair.public, &canonical, signature).unwrap());
+    }
+
+    #[test]
+    fn test_wrap_with_archive_key_round_trips() {
+        let temp = TempDir::new().unwrap();
+        let input = write_input(temp.path(), 0);
+        let keypair = DeviceKeypair::generate().unwrap();
+        let output = temp.path().join("clip.seal");
+        let mut config = test_config();
+        config.archive_key = Some(Secret::new([9u8; 32]));
+
+        let manifest = wrap_file(&input, &output, &keypair, &config).unwrap();
+
+        // Chunk files hold bare ciphertext; the chain is over those bytes
+        let mut continuity = genesis();
+        for segment in &manifest.segments {
+            let chunk = fs::read(output.join("chunks").join(&segment.chunk_file)).unwrap();
+            assert!(chunk.len() <= CHUNK_SIZE + 16);
+            crate::parse_nonce(segment.nonce.as_deref().unwrap()).unwrap();
+            assert_eq!(segment.blake3_hash, hex::encode(segment_hash(&chunk)));
+            continuity = chain_next(&continuity, &segment_hash(&chunk));
+            assert_eq!(segment.continuity_hash, hex::encode(continuity));
+        }
+        validate_archive(&output).unwrap();
+
+        let plaintext = crate::decrypt_archive(&output, &[9u8; 32])
+            .unwrap()
+            .concat();
+        assert_eq!(plaintext, fs::read(&input).unwrap());
+
+        assert!(matches!(
+            crate::decrypt_archive(&output, &[8u8; 32]),
+            Err(crate::ArchiveError::Decryption { index: 0, .. })
+        ));
+        let err = wrap_file_resumable(&input, temp.path().join("resumed.seal"), &keypair, &config)
+            .unwrap_err();
+        assert!(err.to_string().contains("archive key"));
+    }
+
+    #[test]
+    fn test_decrypt_archive_requires_recorded_nonces() {
+        let temp = TempDir::new().unwrap();
+        let input = write_input(temp.path(), 0);
+        let keypair = DeviceKeypair::generate().unwrap();
+        let output = temp.path().join("clip.seal");
+        wrap_file(&input, &output, &keypair, &test_config()).unwrap();
+
+        let err = crate::decrypt_archive(&output, &[9u8; 32]).unwrap_err();
+        assert!(err.to_string().contains("no recorded nonce"), "{}", err);
+    }
+
+    #[test]
+    fn test_wrap_rejects_malformed_claims() {
+        let temp = TempDir::new().unwrap();
+        let input = write_input(temp.path(), 0);
+        let keypair = DeviceKeypair::generate().unwrap();
+        let output = temp.path().join("clip.seal");
+
+        let mut config = test_config();
+        config.claims = vec!["location:lab".to_string(), "operator_id:op 7".to_string()];
+        let err = wrap_file(&input, &output, &keypair, &config).unwrap_err();
+        assert!(
+            err.chain().any(|e| e
+                .to_string()
+                .contains("Invalid value for claim 'operator_id'")),
+            "{:#}",
+            err
+        );
+        assert!(!output.exists());
+
+        // Extension claims pass through
+        config.claims = vec!["location:lab".to_string(), "x-project:alpha".to_string()];
+        let manifest = wrap_file(&input, &output, &keypair, &config).unwrap();
+        assert_eq!(manifest.claims, config.claims);
+    }
+
+    #[test]
+    fn test_wrap_with_signer_records_external_key() {
+        let temp = TempDir::new().unwrap();
+        let input = write_input(temp.path(), 0);
+        let chunk_key = DeviceKeypair::generate().unwrap();
+        let signer = DeviceKeypair::generate().unwrap();
+        let output = temp.path().join("clip.seal");
+
+        let manifest = wrap_file_with_signer(
+            &input,
+            &output,
+            &chunk_key,
+            &signer.public,
+            &test_config(),
+            |bytes| Ok(sign_manifest(&signer, bytes)?),
+        )
+        .unwrap();
+        assert_eq!(manifest.device.public_key, signer.public);
+        assert_eq!(
+            manifest.device.id,
+            device_id_for_key(&signer.public).unwrap()
+        );
+        validate_archive(&output).unwrap();
+
+        let canonical = manifest.to_canonical_bytes().unwrap();
+        let signature = manifest.signature.as_deref().unwrap();
+        assert!(crate::verify_manifest(&signer.public, &canonical, signature).unwrap());
+
+        // Chunks are still sealed under the chunk key, exactly as wrap_file does
+        let fresh = temp.path().join("fresh.seal");
+        wrap_file(&input, &fresh, &chunk_key, &test_config()).unwrap();
+        assert_eq!(
+            fs::read(output.join("chunks/00000.bin")).unwrap(),
+            fs::read(fresh.join("chunks/00000.bin")).unwrap()
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_wrap_matches_sequential() {
+        let temp = TempDir::new().unwrap();
+        let input = write_input(temp.path(), 0);
+        let keypair = DeviceKeypair::generate().unwrap();
+
+        // One chunk, uneven split, exact split, and many tiny chunks
+        for chunk_size in [CHUNK_SIZE * 16, 5000, CHUNK_SIZE, 512] {
+            let mut config = test_config();
+            config.chunk_size = chunk_size;
+
+            let sequential = temp.path().join(format!("seq-{}.seal", chunk_size));
+            let parallel = temp.path().join(format!("par-{}.seal", chunk_size));
+            wrap_file(&input, &sequential, &keypair, &config).unwrap();
+            wrap_file_parallel(&input, &parallel, &keypair, &config).unwrap();
+
+            assert_archives_identical(&sequential, &parallel);
+            crate::validate_archive_parallel(&parallel).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_resume_after_interruption_matches_fresh_wrap() {
+        let temp = TempDir::new().unwrap();
+        let input = write_input(temp.path(), 0);
+        let keypair = DeviceKeypair::generate().unwrap();
+        let config = test_config();
+
+        let fresh = temp.path().join("fresh.seal");
+        wrap_file(&input, &fresh, &keypair, &config).unwrap();
+
+        // Interrupted after half the chunks: no manifest yet, progress recorded
+        let resumed = temp.path().join("resumed.seal");
+        write_chunks_resumable(&input, &resumed, &keypair, &config, 5).unwrap();
+        assert!(resumed.join(WRAP_STATE_FILE).exists());
+        assert!(!resumed.join("manifest.json").exists());
+        assert_eq!(fs::read_dir(resumed.join("chunks")).unwrap().count(), 5);
+
+        wrap_file_resumable(&input, &resumed, &keypair, &config).unwrap();
+        assert!(!resumed.join(WRAP_STATE_FILE).exists());
+        validate_archive(&resumed).unwrap();
+        assert_archives_identical(&fresh, &resumed);
+    }
+
+    #[test]
+    fn test_resume_rewrites_damaged_chunk() {
+        let temp = TempDir::new().unwrap();
+        let input = write_input(temp.path(), 0);
+        let keypair = DeviceKeypair::generate().unwrap();
+        let config = test_config();
+
+        let fresh = temp.path().join("fresh.seal");
+        wrap_file(&input, &fresh, &keypair, &config).unwrap();
+
+        let resumed = temp.path().join("resumed.seal");
+        write_chunks_resumable(&input, &resumed, &keypair, &config, 5).unwrap();
+        fs::write(resumed.join("chunks/00002.bin"), b"torn write").unwrap();
+
+        wrap_file_resumable(&input, &resumed, &keypair, &config).unwrap();
+        validate_archive(&resumed).unwrap();
+        assert_archives_identical(&fresh, &resumed);
+    }
+
+    #[test]
+    fn test_append_segments_extends_archive() {
+        let temp = TempDir::new().unwrap();
+        let keypair = DeviceKeypair::generate().unwrap();
+        let config = test_config();
+        let input = temp.path().join("input.bin");
+        fs::write(&input, vec![7u8; CHUNK_SIZE * 3]).unwrap();
+        let archive = temp.path().join("live.seal");
+        let original = wrap_file(&input, &archive, &keypair, &config).unwrap();
+        assert_eq!(original.segments.len(), 3);
+
+        let new_chunks = vec![vec![1u8; CHUNK_SIZE], vec![2u8; 100]];
+        let manifest = append_segments(&archive, &keypair, &new_chunks).unwrap();
+
+        assert_eq!(manifest.segments.len(), 5);
+        for (appended, before) in manifest.segments.iter().zip(&original.segments) {
+            assert_eq!(appended.continuity_hash, before.continuity_hash);
+        }
+        assert_eq!(manifest.segments[4].chunk_file, "00004.bin");
+        assert_eq!(manifest.segments[4].start_time, "8.000s");
+        validate_archive(&archive).unwrap();
+        let canonical = manifest.to_canonical_bytes().unwrap();
+        let signature = manifest.signature.as_deref().unwrap();
+        assert!(crate::verify_manifest(&keypair.public, &canonical, signature).unwrap());
+
+        // Appended chunks decrypt under the archive's own key and AAD
+        let key = derive_chunk_key(keypair.secret_bytes());
+        let aad = generate_aad(
+            TRST_VERSION,
+            "cam.video",
+            &manifest.device.id,
+            "2025-01-01T00:00:00Z",
+        );
+        let sealed = fs::read(archive.join("chunks/00004.bin")).unwrap();
+        let nonce: [u8; 24] = sealed[..24].try_into().unwrap();
+        let plaintext = crate::decrypt_segment(&key, &nonce, &sealed[24..], &aad).unwrap();
+        assert_eq!(plaintext, new_chunks[1]);
+    }
+
+    #[test]
+    fn test_append_segments_rejects_tampered_archive() {
+        let temp = TempDir::new().unwrap();
+        let keypair = DeviceKeypair::generate().unwrap();
+        let input = write_input(temp.path(), 0);
+        let archive = temp.path().join("live.seal");
+        let mut manifest = wrap_file(&input, &archive, &keypair, &test_config()).unwrap();
+
+        // Signature fields left intact, signed content changed
+        manifest.claims.push("location:elsewhere".to_string());
+        fs::write(
+            archive.join("manifest.json"),
+            serde_json::to_string_pretty(&manifest).unwrap(),
+        )
+        .unwrap();
+
+        let err = append_segments(&archive, &keypair, &[vec![1u8; 64]]).unwrap_err();
+        assert!(err.to_string().contains("unverified archive"));
+        assert!(!archive.join("chunks/00010.bin").exists());
+
+        let other = DeviceKeypair::generate().unwrap();
+        assert!(append_segments(&archive, &other, &[vec![1u8; 64]]).is_err());
+    }
+
+    #[test]
+    fn test_resume_rejects_changed_input() {
+        let temp = TempDir::new().unwrap();
+        let keypair = DeviceKeypair::generate().unwrap();
+        let config = test_config();
+        let output = temp.path().join("clip.seal");
+
+        let input = write_input(temp.path(), 0);
+        write_chunks_resumable(&input, &output, &keypair, &config, 3).unwrap();
+
+        let input = write_input(temp.path(), 0xff);
+        let err = wrap_file_resumable(&input, &output, &keypair, &config).unwrap_err();
+        assert!(err.to_string().contains("different wrap"));
+    }
+}
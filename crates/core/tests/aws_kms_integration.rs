@@ -0,0 +1,185 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! AWS KMS LocalStack Integration Tests
+//!
+//! These tests create throwaway keys in LocalStack and skip (pass without asserting)
+//! unless SEALEDGE_KMS_ENDPOINT points at a running instance:
+//!
+//! ```text
+//! docker run --rm -p 4566:4566 localstack/localstack
+//! SEALEDGE_KMS_ENDPOINT=http://localhost:4566 \
+//!     cargo test -p sealedge-core --features aws-kms --test aws_kms_integration
+//! ```
+
+#![cfg(feature = "aws-kms")]
+
+use aws_sdk_kms::types::{KeySpec, KeyUsageType};
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+use p256::pkcs8::DecodePublicKey;
+use sealedge_core::backends::aws_kms::{AwsKmsBackend, AwsKmsConfig};
+use sealedge_core::backends::universal::{
+    CryptoOperation, CryptoResult, SignatureAlgorithm, SymmetricAlgorithm, UniversalBackend,
+};
+use sealedge_core::BackendError;
+
+const REGION: &str = "us-east-1";
+
+/// LocalStack endpoint, or None (with a note) when the tests should skip
+fn localstack_endpoint() -> Option<String> {
+    match std::env::var("SEALEDGE_KMS_ENDPOINT") {
+        Ok(endpoint) => {
+            // LocalStack accepts any credentials
+            if std::env::var("AWS_ACCESS_KEY_ID").is_err() {
+                std::env::set_var("AWS_ACCESS_KEY_ID", "test");
+                std::env::set_var("AWS_SECRET_ACCESS_KEY", "test");
+            }
+            Some(endpoint)
+        }
+        Err(_) => {
+            eprintln!("Skipping AWS KMS test, SEALEDGE_KMS_ENDPOINT is not set");
+            None
+        }
+    }
+}
+
+/// Create a LocalStack key and return its ARN
+fn create_key(endpoint: &str, spec: KeySpec, usage: KeyUsageType) -> String {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let sdk_config = aws_config::from_env()
+            .region(aws_sdk_kms::config::Region::new(REGION))
+            .endpoint_url(endpoint)
+            .load()
+            .await;
+        let output = aws_sdk_kms::Client::new(&sdk_config)
+            .create_key()
+            .key_spec(spec)
+            .key_usage(usage)
+            .send()
+            .await
+            .expect("LocalStack CreateKey failed");
+        output.key_metadata().unwrap().arn().unwrap().to_string()
+    })
+}
+
+fn backend_for(endpoint: &str, key_arn: String) -> AwsKmsBackend {
+    let mut config = AwsKmsConfig::new(key_arn);
+    config.region = Some(REGION.to_string());
+    config.endpoint_url = Some(endpoint.to_string());
+    AwsKmsBackend::new(config).unwrap()
+}
+
+#[test]
+fn test_kms_signature_verifies_with_exported_public_key() {
+    let Some(endpoint) = localstack_endpoint() else {
+        return;
+    };
+    let key_arn = create_key(&endpoint, KeySpec::EccNistP256, KeyUsageType::SignVerify);
+    let backend = backend_for(&endpoint, key_arn);
+    let data = b"sealedge manifest bytes".to_vec();
+
+    let CryptoResult::PublicKey(spki) = backend
+        .perform_operation("signing-key", CryptoOperation::GetPublicKey)
+        .unwrap()
+    else {
+        panic!("expected a public key");
+    };
+    let CryptoResult::Signed(der) = backend
+        .perform_operation(
+            "signing-key",
+            CryptoOperation::Sign {
+                data: data.clone(),
+                algorithm: SignatureAlgorithm::EcdsaP256,
+            },
+        )
+        .unwrap()
+    else {
+        panic!("expected a signature");
+    };
+
+    let verifying_key = VerifyingKey::from_public_key_der(&spki).unwrap();
+    let signature = Signature::from_der(&der).unwrap();
+    assert!(verifying_key.verify(&data, &signature).is_ok());
+    assert!(verifying_key.verify(b"tampered", &signature).is_err());
+}
+
+#[test]
+fn test_kms_wraps_and_unwraps_data_key() {
+    let Some(endpoint) = localstack_endpoint() else {
+        return;
+    };
+    let key_arn = create_key(
+        &endpoint,
+        KeySpec::SymmetricDefault,
+        KeyUsageType::EncryptDecrypt,
+    );
+    let backend = backend_for(&endpoint, key_arn);
+    let data_key = vec![0x42u8; 32];
+
+    let CryptoResult::Encrypted(wrapped) = backend
+        .perform_operation(
+            "",
+            CryptoOperation::Encrypt {
+                plaintext: data_key.clone(),
+                algorithm: SymmetricAlgorithm::Aes256Gcm,
+            },
+        )
+        .unwrap()
+    else {
+        panic!("expected a wrapped key");
+    };
+    assert_ne!(wrapped, data_key);
+
+    let CryptoResult::Decrypted(unwrapped) = backend
+        .perform_operation(
+            "",
+            CryptoOperation::Decrypt {
+                ciphertext: wrapped,
+                algorithm: SymmetricAlgorithm::Aes256Gcm,
+            },
+        )
+        .unwrap()
+    else {
+        panic!("expected an unwrapped key");
+    };
+    assert_eq!(unwrapped, data_key);
+}
+
+#[test]
+fn test_kms_errors_map_to_backend_errors() {
+    let Some(endpoint) = localstack_endpoint() else {
+        return;
+    };
+    let backend = backend_for(
+        &endpoint,
+        format!(
+            "arn:aws:kms:{}:000000000000:key/00000000-0000-0000-0000-000000000000",
+            REGION
+        ),
+    );
+
+    let missing = backend.perform_operation("", CryptoOperation::GetPublicKey);
+    assert!(matches!(missing, Err(BackendError::KeyNotFound(_))));
+
+    let unsupported = backend.perform_operation(
+        "",
+        CryptoOperation::Sign {
+            data: vec![1, 2, 3],
+            algorithm: SignatureAlgorithm::Ed25519,
+        },
+    );
+    assert!(matches!(
+        unsupported,
+        Err(BackendError::UnsupportedOperation(_))
+    ));
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Memory bound for streaming archive validation
+//!
+//! Kept in its own test binary because it installs a counting global allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::Result;
+use sealedge_core::chain::{chain_next, genesis, segment_hash};
+use sealedge_core::{validate_archive, write_archive, ProfileMetadata, SegmentInfo, TrstManifest};
+use tempfile::TempDir;
+
+struct CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let now = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(now, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const CHUNK_COUNT: usize = 32;
+const CHUNK_SIZE: usize = 2 * 1024 * 1024;
+
+/// Write a synthetic archive of `CHUNK_COUNT` chunks of `CHUNK_SIZE` bytes each
+fn write_large_archive(dir: &TempDir) -> Result<std::path::PathBuf> {
+    let mut manifest = TrstManifest::new_cam_video();
+    manifest.device.id = "STREAM001".to_string();
+    manifest.device.public_key = "ed25519:test_key".to_string();
+    if let ProfileMetadata::CamVideo(ref mut m) = manifest.metadata {
+        m.started_at = "2025-01-15T10:30:00Z".to_string();
+        m.ended_at = "2025-01-15T10:31:04Z".to_string();
+    }
+
+    let mut continuity = genesis();
+    let mut chunks = Vec::with_capacity(CHUNK_COUNT);
+    for i in 0..CHUNK_COUNT {
+        let chunk: Vec<u8> = (0..CHUNK_SIZE).map(|b| (b ^ i) as u8).collect();
+        let hash = segment_hash(&chunk);
+        continuity = chain_next(&continuity, &hash);
+        manifest.segments.push(SegmentInfo {
+            chunk_file: format!("{:05}.bin", i),
+            blake3_hash: hex::encode(hash),
+            start_time: format!("{}.000s", i * 2),
+            duration_seconds: 2.0,
+            continuity_hash: hex::encode(continuity),
+            nonce: None,
+        });
+        chunks.push(chunk);
+    }
+    manifest.signature = Some("ed25519:test_signature".to_string());
+
+    let path = dir.path().join("large.seal");
+    write_archive(&path, &manifest, chunks, b"ed25519:test_signature")?;
+    Ok(path)
+}
+
+#[test]
+fn test_validation_memory_stays_bounded() -> Result<()> {
+    let dir = TempDir::new()?;
+    let archive_path = write_large_archive(&dir)?;
+
+    let baseline = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+
+    validate_archive(&archive_path)?;
+
+    // Loading even a single chunk would exceed this; the whole archive is 64 MiB
+    let peak_growth = PEAK.load(Ordering::SeqCst).saturating_sub(baseline);
+    assert!(
+        peak_growth < CHUNK_SIZE / 2,
+        "validation allocated {} bytes at peak for {} chunks of {} bytes",
+        peak_growth,
+        CHUNK_COUNT,
+        CHUNK_SIZE
+    );
+    Ok(())
+}
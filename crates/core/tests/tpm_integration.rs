@@ -0,0 +1,140 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! TPM 2.0 Simulator Integration Tests
+//!
+//! These tests run against a TPM simulator and skip (pass without asserting) when
+//! none is reachable. Start one with e.g.:
+//!
+//! ```text
+//! swtpm socket --tpm2 --server type=tcp,port=2321 --ctrl type=tcp,port=2322 \
+//!     --tpmstate dir=/tmp/swtpm --flags startup-clear
+//! ```
+//!
+//! The TCTI defaults to `mssim:host=localhost,port=2321`; override with SEALEDGE_TPM_TCTI.
+//!
+//! Run with: cargo test -p sealedge-core --features tpm --test tpm_integration
+
+#![cfg(feature = "tpm")]
+
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+use p256::pkcs8::DecodePublicKey;
+use sealedge_core::backends::tpm::{TpmBackend, TpmConfig};
+use sealedge_core::backends::traits::{KeyBackend, KeyContext};
+use sealedge_core::backends::universal::{
+    CryptoOperation, CryptoResult, KeyDerivationContext, SignatureAlgorithm, UniversalBackend,
+};
+
+/// Connect to the simulator, or None (with a note) when it is not running
+fn simulator_backend() -> Option<TpmBackend> {
+    let tcti = std::env::var("SEALEDGE_TPM_TCTI")
+        .unwrap_or_else(|_| "mssim:host=localhost,port=2321".to_string());
+    let config = TpmConfig::from_backend_config(&[format!("tcti={}", tcti)]).unwrap();
+    match TpmBackend::new(config) {
+        Ok(backend) => Some(backend),
+        Err(e) => {
+            eprintln!(
+                "Skipping TPM test, simulator unavailable at {}: {}",
+                tcti, e
+            );
+            None
+        }
+    }
+}
+
+#[test]
+fn test_tpm_capabilities_are_hardware_backed() {
+    let Some(backend) = simulator_backend() else {
+        return;
+    };
+    let caps = backend.get_capabilities();
+    assert!(caps.hardware_backed);
+    assert!(caps.supports_key_derivation);
+    assert!(caps
+        .signature_algorithms
+        .contains(&SignatureAlgorithm::EcdsaP256));
+    assert!(UniversalBackend::backend_info(&backend).available);
+}
+
+#[test]
+fn test_tpm_derive_key_is_deterministic_per_key_id() {
+    let Some(backend) = simulator_backend() else {
+        return;
+    };
+    let context = KeyContext::new(b"salt-0123456789a".to_vec());
+
+    let first = backend.derive_key(&[1u8; 16], &context).unwrap();
+    let again = backend.derive_key(&[1u8; 16], &context).unwrap();
+    let other_id = backend.derive_key(&[2u8; 16], &context).unwrap();
+    let other_salt = backend
+        .derive_key(&[1u8; 16], &KeyContext::new(b"salt-fedcba98765".to_vec()))
+        .unwrap();
+
+    assert_eq!(first, again);
+    assert_ne!(first, other_id);
+    assert_ne!(first, other_salt);
+
+    let universal = backend
+        .perform_operation(
+            "derive-key",
+            CryptoOperation::DeriveKey {
+                context: KeyDerivationContext::new(b"salt-0123456789a".to_vec()),
+            },
+        )
+        .unwrap();
+    assert!(matches!(universal, CryptoResult::DerivedKey(_)));
+}
+
+#[test]
+fn test_tpm_signature_verifies_with_exported_public_key() {
+    let Some(backend) = simulator_backend() else {
+        return;
+    };
+    let data = b"sealedge manifest bytes".to_vec();
+
+    let CryptoResult::PublicKey(spki) = backend
+        .perform_operation("signing-key", CryptoOperation::GetPublicKey)
+        .unwrap()
+    else {
+        panic!("expected a public key");
+    };
+    let CryptoResult::Signed(der) = backend
+        .perform_operation(
+            "signing-key",
+            CryptoOperation::Sign {
+                data: data.clone(),
+                algorithm: SignatureAlgorithm::EcdsaP256,
+            },
+        )
+        .unwrap()
+    else {
+        panic!("expected a signature");
+    };
+
+    let verifying_key = VerifyingKey::from_public_key_der(&spki).unwrap();
+    let signature = Signature::from_der(&der).unwrap();
+    assert!(verifying_key.verify(&data, &signature).is_ok());
+    assert!(verifying_key.verify(b"tampered", &signature).is_err());
+}
+
+#[test]
+fn test_tpm_rejects_unsupported_operations() {
+    let Some(backend) = simulator_backend() else {
+        return;
+    };
+    let result = backend.perform_operation(
+        "signing-key",
+        CryptoOperation::Sign {
+            data: vec![1, 2, 3],
+            algorithm: SignatureAlgorithm::Ed25519,
+        },
+    );
+    assert!(result.is_err());
+    assert!(backend.store_key(&[0u8; 16], &[0u8; 32]).is_err());
+}
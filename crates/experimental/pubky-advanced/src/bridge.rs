@@ -0,0 +1,248 @@
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// MPL-2.0: https://mozilla.org/MPL/2.0/
+// Project: sealedge — Privacy and trust at the edge.
+
+//! Bridge between core V1 envelopes and V2 hybrid envelopes
+//!
+//! Stores holding both formats can be read through [`UnifiedEnvelope`] without
+//! knowing up front which format each blob uses. Both formats are bincode
+//! encoded; V2 blobs start with the `TRS2` header magic, anything else is tried
+//! as a core [`Envelope`].
+//!
+//! A [`DualKeyPair`] opens either format: V1 envelopes are unsealed with its
+//! Ed25519 key, V2 envelopes with its X25519 key.
+
+use crate::envelope::EnvelopeV2;
+use crate::keys::DualKeyPair;
+use anyhow::{Context, Result};
+use sealedge_core::Envelope;
+
+/// Leading bytes of a bincode-encoded [`EnvelopeV2`]
+const ENVELOPE_V2_MAGIC: &[u8; 4] = b"TRS2";
+
+/// Envelope format of a serialized blob
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeFormat {
+    /// Core `sealedge_core::Envelope`
+    V1,
+    /// Hybrid-encryption [`EnvelopeV2`]
+    V2,
+    /// Neither format
+    Unknown,
+}
+
+/// Classify a serialized envelope
+pub fn detect_envelope_format(bytes: &[u8]) -> EnvelopeFormat {
+    if bytes.starts_with(ENVELOPE_V2_MAGIC) {
+        if EnvelopeV2::from_bytes(bytes).is_ok() {
+            return EnvelopeFormat::V2;
+        }
+        return EnvelopeFormat::Unknown;
+    }
+    if bincode::deserialize::<Envelope>(bytes).is_ok() {
+        return EnvelopeFormat::V1;
+    }
+    EnvelopeFormat::Unknown
+}
+
+/// An envelope of either format
+#[derive(Debug, Clone)]
+pub enum UnifiedEnvelope {
+    V1(Envelope),
+    V2(EnvelopeV2),
+}
+
+impl UnifiedEnvelope {
+    /// Wrap a core V1 envelope
+    pub fn from_v1(envelope: Envelope) -> Self {
+        Self::V1(envelope)
+    }
+
+    /// Wrap a V2 envelope
+    pub fn from_v2(envelope: EnvelopeV2) -> Self {
+        Self::V2(envelope)
+    }
+
+    /// Parse a serialized envelope of either format
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match detect_envelope_format(bytes) {
+            EnvelopeFormat::V1 => Ok(Self::V1(
+                bincode::deserialize(bytes).context("Failed to deserialize V1 envelope")?,
+            )),
+            EnvelopeFormat::V2 => Ok(Self::V2(EnvelopeV2::from_bytes(bytes)?)),
+            EnvelopeFormat::Unknown => Err(anyhow::anyhow!("Unrecognized envelope format")),
+        }
+    }
+
+    /// Serialize in the envelope's own format
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        match self {
+            Self::V1(envelope) => {
+                bincode::serialize(envelope).context("Failed to serialize V1 envelope")
+            }
+            Self::V2(envelope) => envelope.to_bytes(),
+        }
+    }
+
+    /// Format of the wrapped envelope
+    pub fn format(&self) -> EnvelopeFormat {
+        match self {
+            Self::V1(_) => EnvelopeFormat::V1,
+            Self::V2(_) => EnvelopeFormat::V2,
+        }
+    }
+
+    /// Verify the wrapped envelope's signatures
+    pub fn verify(&self) -> bool {
+        match self {
+            Self::V1(envelope) => envelope.verify(),
+            Self::V2(envelope) => envelope.verify(),
+        }
+    }
+
+    /// Decrypt the payload with the recipient's keys
+    ///
+    /// V1 envelopes must have been sealed to `keys.ed25519_public()`, V2
+    /// envelopes to `keys.x25519_public()`.
+    pub fn open(&self, keys: &DualKeyPair) -> Result<Vec<u8>> {
+        match self {
+            Self::V1(envelope) => envelope.unseal(&keys.ed25519_key),
+            Self::V2(envelope) => envelope.unseal(keys),
+        }
+    }
+
+    /// Re-seal the payload as a V2 envelope addressed to `keys`
+    ///
+    /// The recipient opens the envelope and seals it again to its own X25519
+    /// key, so the result is signed by `keys` rather than the original sender.
+    /// Verify the original with [`UnifiedEnvelope::verify`] first if the sender
+    /// matters.
+    pub fn to_v2(&self, keys: &DualKeyPair) -> Result<EnvelopeV2> {
+        match self {
+            Self::V2(envelope) => Ok(envelope.clone()),
+            Self::V1(_) => {
+                let payload = self.open(keys)?;
+                EnvelopeV2::seal(
+                    &payload,
+                    keys,
+                    &keys.x25519_public(),
+                    &keys.pubky_identity(),
+                )
+            }
+        }
+    }
+
+    /// Re-seal the payload as a V1 envelope addressed to `keys`
+    ///
+    /// As with [`UnifiedEnvelope::to_v2`], the result is signed by `keys`.
+    pub fn to_v1(&self, keys: &DualKeyPair) -> Result<Envelope> {
+        match self {
+            Self::V1(envelope) => Ok(envelope.clone()),
+            Self::V2(_) => {
+                let payload = self.open(keys)?;
+                Envelope::seal(&payload, &keys.ed25519_key, &keys.ed25519_public())
+            }
+        }
+    }
+}
+
+impl From<Envelope> for UnifiedEnvelope {
+    fn from(envelope: Envelope) -> Self {
+        Self::from_v1(envelope)
+    }
+}
+
+impl From<EnvelopeV2> for UnifiedEnvelope {
+    fn from(envelope: EnvelopeV2) -> Self {
+        Self::from_v2(envelope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seal_v1(payload: &[u8], sender: &DualKeyPair, recipient: &DualKeyPair) -> Envelope {
+        Envelope::seal(payload, &sender.ed25519_key, &recipient.ed25519_public())
+            .expect("Failed to seal V1 envelope")
+    }
+
+    fn seal_v2(payload: &[u8], sender: &DualKeyPair, recipient: &DualKeyPair) -> EnvelopeV2 {
+        EnvelopeV2::seal(
+            payload,
+            sender,
+            &recipient.x25519_public(),
+            &recipient.pubky_identity(),
+        )
+        .expect("Failed to seal V2 envelope")
+    }
+
+    #[test]
+    fn test_open_both_formats() {
+        let sender = DualKeyPair::generate();
+        let recipient = DualKeyPair::generate();
+
+        let v1 = UnifiedEnvelope::from_v1(seal_v1(b"sealed with v1", &sender, &recipient));
+        let v2 = UnifiedEnvelope::from_v2(seal_v2(b"sealed with v2", &sender, &recipient));
+
+        assert_eq!(v1.open(&recipient).unwrap(), b"sealed with v1");
+        assert_eq!(v2.open(&recipient).unwrap(), b"sealed with v2");
+
+        let stranger = DualKeyPair::generate();
+        assert!(v1.open(&stranger).is_err());
+        assert!(v2.open(&stranger).is_err());
+    }
+
+    #[test]
+    fn test_open_from_serialized_bytes() {
+        let sender = DualKeyPair::generate();
+        let recipient = DualKeyPair::generate();
+
+        for (envelope, payload) in [
+            (
+                UnifiedEnvelope::from_v1(seal_v1(b"v1 bytes", &sender, &recipient)),
+                b"v1 bytes",
+            ),
+            (
+                UnifiedEnvelope::from_v2(seal_v2(b"v2 bytes", &sender, &recipient)),
+                b"v2 bytes",
+            ),
+        ] {
+            let bytes = envelope.to_bytes().unwrap();
+            let parsed = UnifiedEnvelope::from_bytes(&bytes).unwrap();
+            assert_eq!(parsed.format(), envelope.format());
+            assert!(parsed.verify());
+            assert_eq!(parsed.open(&recipient).unwrap(), payload);
+        }
+    }
+
+    #[test]
+    fn test_detect_envelope_format() {
+        let sender = DualKeyPair::generate();
+        let recipient = DualKeyPair::generate();
+
+        let v1_bytes = bincode::serialize(&seal_v1(b"payload", &sender, &recipient)).unwrap();
+        let v2_bytes = seal_v2(b"payload", &sender, &recipient).to_bytes().unwrap();
+
+        assert_eq!(detect_envelope_format(&v1_bytes), EnvelopeFormat::V1);
+        assert_eq!(detect_envelope_format(&v2_bytes), EnvelopeFormat::V2);
+        assert_eq!(detect_envelope_format(b""), EnvelopeFormat::Unknown);
+        assert_eq!(
+            detect_envelope_format(b"TRS2 but not an envelope"),
+            EnvelopeFormat::Unknown
+        );
+    }
+
+    #[test]
+    fn test_convert_between_formats() {
+        let sender = DualKeyPair::generate();
+        let recipient = DualKeyPair::generate();
+
+        let v1 = UnifiedEnvelope::from_v1(seal_v1(b"convert me", &sender, &recipient));
+        let upgraded = UnifiedEnvelope::from_v2(v1.to_v2(&recipient).unwrap());
+        assert_eq!(upgraded.open(&recipient).unwrap(), b"convert me");
+
+        let downgraded = UnifiedEnvelope::from_v1(upgraded.to_v1(&recipient).unwrap());
+        assert_eq!(downgraded.open(&recipient).unwrap(), b"convert me");
+    }
+}
@@ -12,7 +12,7 @@ use anyhow::{Context, Result};
 use ed25519_dalek::{SigningKey, VerifyingKey};
 use serde::{Deserialize, Serialize};
 use x25519_dalek::{PublicKey as X25519PublicKey, StaticSecret};
-use zeroize::Zeroize;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
 /// A dual key pair for Sealedge Pubky integration
 ///
@@ -130,13 +130,13 @@ impl DualKeyPair {
         // Create deterministic X25519 key from Ed25519 key
         let mut hasher = Hasher::new();
         hasher.update(b"SEALEDGE_X25519_DERIVATION");
-        hasher.update(&ed25519_key.to_bytes());
+        hasher.update(Zeroizing::new(ed25519_key.to_bytes()).as_slice());
 
         let hash = hasher.finalize();
-        let mut x25519_bytes = [0u8; 32];
+        let mut x25519_bytes = Zeroizing::new([0u8; 32]);
         x25519_bytes.copy_from_slice(&hash.as_bytes()[..32]);
 
-        StaticSecret::from(x25519_bytes)
+        StaticSecret::from(*x25519_bytes)
     }
 
     /// Create a DualKeyPair with derived X25519 key
@@ -150,8 +150,8 @@ impl DualKeyPair {
     }
 }
 
-/// Serializable byte representation of dual keys
-#[derive(Serialize, Deserialize, Zeroize)]
+/// Serializable byte representation of dual keys, wiped on drop
+#[derive(Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 pub struct DualKeyBytes {
     pub ed25519_key: [u8; 32],
     pub x25519_key: [u8; 32],
@@ -221,9 +221,9 @@ impl std::fmt::Debug for DualKeyPair {
 
 impl Drop for DualKeyPair {
     fn drop(&mut self) {
-        // Note: ed25519_dalek::SigningKey doesn't implement Zeroize directly
-        // The underlying key material is zeroized when the SigningKey is dropped
-        // x25519_dalek::StaticSecret implements Zeroize automatically
+        // SigningKey wipes its own secret on drop; StaticSecret is cleared here
+        // explicitly rather than relying on its feature-gated drop glue
+        self.x25519_key.zeroize();
     }
 }
 
@@ -345,4 +345,18 @@ mod tests {
             restored_keys.x25519_public().to_bytes()
         );
     }
+
+    #[test]
+    fn test_key_bytes_zeroize() {
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<DualKeyBytes>();
+
+        let mut key_bytes = DualKeyPair::generate().to_bytes();
+        assert_ne!(key_bytes.ed25519_key, [0u8; 32]);
+
+        // The same wipe runs when DualKeyBytes is dropped
+        key_bytes.zeroize();
+        assert_eq!(key_bytes.ed25519_key, [0u8; 32]);
+        assert_eq!(key_bytes.x25519_key, [0u8; 32]);
+    }
 }
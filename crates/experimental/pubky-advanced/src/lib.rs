@@ -11,11 +11,14 @@
 //! - Hybrid encryption (X25519 ECDH + AES-256-GCM)
 //! - Pubky integration for censorship-resistant key discovery
 //! - V2 envelope format with improved security and usability
+//! - Unified reading of V1 and V2 envelopes
 
+pub mod bridge;
 pub mod envelope;
 pub mod keys;
 pub mod pubky_client;
 
+pub use bridge::{detect_envelope_format, EnvelopeFormat, UnifiedEnvelope};
 pub use envelope::{EnvelopeHeaderV2, EnvelopeV2, KeyExchangeAlgorithm};
 pub use keys::{DualKeyPair, PubkyIdentity};
 pub use pubky_client::{PubkyClient, PubkyError};
@@ -41,8 +41,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Step 3: Publish keys to mock network
     println!("● Step 3: Publishing keys to mock network...");
-    let alice_pubky_id = alice_backend.publish_public_key(&alice_keypair.public)?;
-    let bob_pubky_id = bob_backend.publish_public_key(&bob_keypair.public)?;
+    let alice_pubky_id = alice_backend.publish_public_key(&alice_keypair.public, None)?;
+    let bob_pubky_id = bob_backend.publish_public_key(&bob_keypair.public, None)?;
 
     println!("✔ Alice published: {}", alice_pubky_id);
     println!("✔ Bob published: {}", bob_pubky_id);
@@ -123,7 +123,7 @@ mod tests {
             KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate Bob's key");
 
         let bob_pubky_id = bob_adapter
-            .publish_public_key(&bob_keypair.public)
+            .publish_public_key(&bob_keypair.public, None)
             .expect("Failed to publish Bob's key");
 
         let message = b"Test workflow";
@@ -55,7 +55,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _alice_keypair = KeyPair::generate(AsymmetricAlgorithm::Rsa2048)?;
     let bob_keypair = KeyPair::generate(AsymmetricAlgorithm::Rsa2048)?;
 
-    let bob_pubky_id = bob_adapter.publish_public_key(&bob_keypair.public)?;
+    let bob_pubky_id = bob_adapter.publish_public_key(&bob_keypair.public, None)?;
 
     println!("✅ Bob's Pubky ID: {}", bob_pubky_id);
     println!("✅ Bob's Sealedge key: {}", bob_keypair.public.id());
@@ -159,7 +159,7 @@ mod tests {
         let bob_keypair =
             KeyPair::generate(AsymmetricAlgorithm::Rsa2048).expect("Failed to generate Bob's key");
         let bob_pubky_id = bob_adapter
-            .publish_public_key(&bob_keypair.public)
+            .publish_public_key(&bob_keypair.public, None)
             .expect("Failed to publish Bob's key");
 
         // Test your exact API
@@ -183,7 +183,7 @@ mod tests {
         let ed25519_keypair = KeyPair::generate(AsymmetricAlgorithm::Ed25519)
             .expect("Failed to generate Ed25519 key");
         let ed25519_pubky_id = ed25519_adapter
-            .publish_public_key(&ed25519_keypair.public)
+            .publish_public_key(&ed25519_keypair.public, None)
             .expect("Failed to publish Ed25519 key");
 
         // Note: Ed25519 keys can't be used for RSA encryption in our current implementation
@@ -12,10 +12,11 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use sealedge_pubky::{
-    create_pubky_backend_from_seed, create_pubky_backend_random, extract_private_key_seed,
-    receive_trusted_data, send_trusted_data,
+    create_pubky_backend_from_seed, create_pubky_backend_random, receive_trusted_data,
+    send_trusted_data,
 };
 use std::path::PathBuf;
+use zeroize::Zeroizing;
 
 #[derive(Parser)]
 #[command(name = "sealedge-pubky")]
@@ -255,10 +256,12 @@ fn generate_keypair(output: Option<PathBuf>, seed: Option<String>, id_only: bool
     println!("  Pubky ID: {}", pubky_id);
 
     if let Some(output_path) = output {
-        let private_key_seed = extract_private_key_seed(&backend);
-        let private_key_hex = hex::encode(private_key_seed);
+        // The key file is the raw seed by design; it is the user's only copy
+        #[allow(deprecated)]
+        let private_key_seed = sealedge_pubky::extract_private_key_seed(&backend);
+        let private_key_hex = Zeroizing::new(hex::encode(private_key_seed.as_slice()));
 
-        std::fs::write(&output_path, &private_key_hex).with_context(|| {
+        std::fs::write(&output_path, private_key_hex.as_bytes()).with_context(|| {
             format!(
                 "❌ Failed to write private key to: {}\n\
                 Check that:\n\
@@ -11,16 +11,20 @@
 pub mod mock;
 
 use anyhow::Result;
+use async_trait::async_trait;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use pubky::{Client, ClientBuilder, Keypair};
 use sealedge_core::backends::{
-    AsymmetricAlgorithm, BackendCapabilities, BackendInfo, CryptoOperation, CryptoResult,
-    KeyMetadata, UniversalBackend,
+    AsymmetricAlgorithm, AsyncUniversalBackend, BackendCapabilities, BackendInfo, CryptoOperation,
+    CryptoResult, KeyMetadata, UniversalBackend,
 };
 use sealedge_core::error::BackendError;
 use sealedge_core::{PrivateKey, PublicKey};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
 use tokio::runtime::Runtime;
+use zeroize::{Zeroize, Zeroizing};
 
 /// Errors that can occur during Pubky operations
 #[derive(Debug, thiserror::Error)]
@@ -39,6 +43,102 @@ pub enum PubkyAdapterError {
 
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+
+    #[error("Invalid key name: {0}")]
+    InvalidKeyName(String),
+
+    #[error("Key record signature verification failed: {0}")]
+    SignatureVerificationFailed(String),
+
+    #[error("Stale key record: {0}")]
+    StaleKeyRecord(String),
+
+    #[error("Blocking call from inside an async runtime: {0}")]
+    BlockingInAsyncContext(String),
+
+    #[error("Wrapped key is malformed: {0}")]
+    InvalidWrappedKey(String),
+}
+
+impl PubkyAdapterError {
+    /// Whether the failure may clear up on a later attempt
+    ///
+    /// Network errors and missing records are transient: relays drop requests and
+    /// records take time to propagate. Everything else, such as a malformed ID or
+    /// a record that fails its signature check, fails the same way every time.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            PubkyAdapterError::Network(_) | PubkyAdapterError::KeyResolutionFailed(_)
+        )
+    }
+}
+
+/// Bounded exponential backoff with jitter for resolution retries
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts including the first; values below 1 are treated as 1
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_delay: Duration,
+    /// Upper bound on any single delay
+    pub max_delay: Duration,
+    /// Fraction of each delay that is randomized away, `0.0..=1.0`
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+            jitter: 0.5,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry` (0 for the first retry)
+    ///
+    /// Doubles from `initial_delay` up to `max_delay`, then subtracts a random
+    /// share of up to `jitter` so that clients do not retry in lockstep.
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let base = self
+            .initial_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay);
+        let jitter = self.jitter.clamp(0.0, 1.0) * rand::random::<f64>();
+        base.mul_f64(1.0 - jitter)
+    }
+
+    /// Whether another attempt is allowed after `attempts` have failed with `error`
+    pub(crate) fn should_retry(&self, attempts: u32, error: &PubkyAdapterError) -> bool {
+        error.is_retryable() && attempts < self.max_attempts.max(1)
+    }
+}
+
+/// Path of the default key record for an identity
+pub const DEFAULT_KEY_PATH: &str = "/sealedge/public_key";
+
+/// Directory holding named key records, one per slot
+pub const NAMED_KEYS_DIR: &str = "/sealedge/keys/";
+
+/// Build the path of a named key slot, e.g. `/sealedge/keys/signing`
+///
+/// Names are restricted to ASCII letters, digits, `-`, `_` and `.` so that they map
+/// to exactly one path segment.
+pub fn named_key_path(name: &str) -> Result<String, PubkyAdapterError> {
+    let valid = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(PubkyAdapterError::InvalidKeyName(name.to_string()));
+    }
+    Ok(format!("{}{}", NAMED_KEYS_DIR, name))
 }
 
 /// A Sealedge public key record stored in the Pubky network
@@ -48,8 +148,164 @@ pub struct SealedgeKeyRecord {
     pub public_key: PublicKeyData,
     /// When this record was created
     pub created_at: u64,
+    /// When this record stops being valid, if it was published with a TTL
+    #[serde(default)]
+    pub expires_at: Option<u64>,
     /// Optional metadata
     pub metadata: Option<HashMap<String, String>>,
+    /// Ed25519 signature by the publishing identity over the canonical record bytes (hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// Signed view of a [`SealedgeKeyRecord`]: the path it is published at plus every
+/// field except the signature, with metadata in sorted order so the bytes do not
+/// depend on `HashMap` iteration
+#[derive(Serialize)]
+struct CanonicalKeyRecord<'a> {
+    path: &'a str,
+    public_key: &'a PublicKeyData,
+    created_at: u64,
+    expires_at: Option<u64>,
+    metadata: Option<BTreeMap<&'a String, &'a String>>,
+}
+
+impl SealedgeKeyRecord {
+    /// Create a record for `public_key` timestamped now
+    ///
+    /// # Arguments
+    /// * `public_key` - The key to advertise
+    /// * `ttl_secs` - Optional lifetime; sets `expires_at` to `created_at + ttl_secs`
+    pub fn new(public_key: &PublicKey, ttl_secs: Option<u64>) -> Self {
+        let created_at = unix_now();
+        Self {
+            public_key: PublicKeyData {
+                algorithm: format!("{:?}", public_key.algorithm),
+                key_bytes: hex::encode(&public_key.key_bytes),
+                key_id: public_key.key_id.clone(),
+            },
+            created_at,
+            expires_at: ttl_secs.map(|ttl| created_at.saturating_add(ttl)),
+            metadata: None,
+            signature: None,
+        }
+    }
+
+    /// Canonical bytes covered by the record signature when published at `path`
+    ///
+    /// Binding the path means a record signed for one slot (say `signing`) fails
+    /// verification if a relay serves it from another (say `encryption`).
+    pub fn canonical_bytes(&self, path: &str) -> Result<Vec<u8>, PubkyAdapterError> {
+        let canonical = CanonicalKeyRecord {
+            path,
+            public_key: &self.public_key,
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+            metadata: self
+                .metadata
+                .as_ref()
+                .map(|metadata| metadata.iter().collect()),
+        };
+        Ok(serde_json::to_vec(&canonical)?)
+    }
+
+    /// Sign the record for `path` with the publishing identity's Ed25519 key
+    pub fn sign(&mut self, signing_key: &SigningKey, path: &str) -> Result<(), PubkyAdapterError> {
+        let signature = signing_key.sign(&self.canonical_bytes(path)?);
+        self.signature = Some(hex::encode(signature.to_bytes()));
+        Ok(())
+    }
+
+    /// Verify the record signature against a Pubky ID (the hex-encoded Ed25519 public
+    /// key) for a record read from `path`
+    pub fn verify_signature(&self, pubky_id: &str, path: &str) -> Result<(), PubkyAdapterError> {
+        let id_bytes: [u8; 32] = hex::decode(pubky_id)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| PubkyAdapterError::InvalidPubkyId(pubky_id.to_string()))?;
+        let verifying_key = VerifyingKey::from_bytes(&id_bytes)
+            .map_err(|_| PubkyAdapterError::InvalidPubkyId(pubky_id.to_string()))?;
+
+        let signature_hex = self.signature.as_deref().ok_or_else(|| {
+            PubkyAdapterError::SignatureVerificationFailed("record is not signed".to_string())
+        })?;
+        let signature_bytes: [u8; 64] = hex::decode(signature_hex)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                PubkyAdapterError::SignatureVerificationFailed(
+                    "malformed signature encoding".to_string(),
+                )
+            })?;
+
+        verifying_key
+            .verify(
+                &self.canonical_bytes(path)?,
+                &Signature::from_bytes(&signature_bytes),
+            )
+            .map_err(|_| {
+                PubkyAdapterError::SignatureVerificationFailed(format!(
+                    "record at {} does not match signature of {}",
+                    path, pubky_id
+                ))
+            })
+    }
+
+    /// Reject the record if it is older than `max_age_secs` or past its `expires_at`
+    pub fn check_freshness(&self, max_age_secs: u64, now: u64) -> Result<(), PubkyAdapterError> {
+        if let Some(expires_at) = self.expires_at {
+            if now >= expires_at {
+                return Err(PubkyAdapterError::StaleKeyRecord(format!(
+                    "record expired at {} (now {})",
+                    expires_at, now
+                )));
+            }
+        }
+
+        let age = now.saturating_sub(self.created_at);
+        if age > max_age_secs {
+            return Err(PubkyAdapterError::StaleKeyRecord(format!(
+                "record is {}s old, maximum allowed age is {}s",
+                age, max_age_secs
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Convert the record back into a Sealedge public key
+    pub fn to_public_key(&self) -> Result<PublicKey, PubkyAdapterError> {
+        let algorithm = match self.public_key.algorithm.as_str() {
+            "Ed25519" => AsymmetricAlgorithm::Ed25519,
+            "EcdsaP256" => AsymmetricAlgorithm::EcdsaP256,
+            "Rsa2048" => AsymmetricAlgorithm::Rsa2048,
+            "Rsa4096" => AsymmetricAlgorithm::Rsa4096,
+            _ => {
+                return Err(PubkyAdapterError::InvalidPubkyId(format!(
+                    "Unsupported algorithm: {}",
+                    self.public_key.algorithm
+                )))
+            }
+        };
+
+        let key_bytes = hex::decode(&self.public_key.key_bytes)
+            .map_err(|e| PubkyAdapterError::InvalidPubkyId(format!("Invalid hex: {:?}", e)))?;
+
+        let public_key = match &self.public_key.key_id {
+            Some(key_id) => PublicKey::with_id(algorithm, key_bytes, key_id.clone()),
+            None => PublicKey::new(algorithm, key_bytes),
+        };
+
+        Ok(public_key)
+    }
+}
+
+/// Current Unix time in seconds
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
 }
 
 /// Serializable public key data
@@ -67,27 +323,27 @@ pub struct PublicKeyData {
 pub struct PubkyBackend {
     /// The Pubky client
     client: Client,
-    /// Our Pubky keypair
+    /// Our Pubky keypair; its secret half is an `ed25519_dalek::SigningKey`, which
+    /// zeroizes itself on drop
     keypair: Keypair,
-    /// Async runtime for network operations
-    runtime: Runtime,
+    /// Runtime for the blocking API, only present when created with `new_sync`
+    runtime: Option<Runtime>,
 }
 
 impl PubkyBackend {
     /// Create a new Pubky backend
+    ///
+    /// The backend runs on the caller's runtime and owns none of its own, so it can be
+    /// created and dropped freely inside async code.
     pub async fn new(keypair: Keypair) -> Result<Self, PubkyAdapterError> {
         let client = ClientBuilder::default().build().map_err(|e| {
             PubkyAdapterError::Network(anyhow::anyhow!("Failed to build Pubky client: {:?}", e))
         })?;
 
-        let runtime = Runtime::new().map_err(|e| {
-            PubkyAdapterError::Network(anyhow::anyhow!("Failed to create async runtime: {:?}", e))
-        })?;
-
         Ok(Self {
             client,
             keypair,
-            runtime,
+            runtime: None,
         })
     }
 
@@ -106,30 +362,125 @@ impl PubkyBackend {
         Ok(Self {
             client,
             keypair,
-            runtime,
+            runtime: Some(runtime),
         })
     }
 
     /// Publish a Sealedge public key to the Pubky network
+    ///
+    /// When `ttl_secs` is set the record carries an `expires_at`, after which
+    /// [`PubkyBackend::resolve_public_key_fresh`] refuses it.
     pub async fn publish_public_key(
         &self,
         public_key: &PublicKey,
+        ttl_secs: Option<u64>,
     ) -> Result<String, PubkyAdapterError> {
-        let record = SealedgeKeyRecord {
-            public_key: PublicKeyData {
-                algorithm: format!("{:?}", public_key.algorithm),
-                key_bytes: hex::encode(&public_key.key_bytes),
-                key_id: public_key.key_id.clone(),
-            },
-            created_at: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
-            metadata: None,
-        };
+        let record = SealedgeKeyRecord::new(public_key, ttl_secs);
+        self.store_key_record(DEFAULT_KEY_PATH, &record).await
+    }
+
+    /// Publish a Sealedge public key under a named slot (`/sealedge/keys/<name>`)
+    ///
+    /// Separate slots let one identity advertise, for example, distinct signing and
+    /// encryption keys, or a new key alongside the one it is rotating out.
+    pub async fn publish_public_key_named(
+        &self,
+        name: &str,
+        public_key: &PublicKey,
+        ttl_secs: Option<u64>,
+    ) -> Result<String, PubkyAdapterError> {
+        let path = named_key_path(name)?;
+        let record = SealedgeKeyRecord::new(public_key, ttl_secs);
+        self.store_key_record(&path, &record).await
+    }
+
+    /// List the named key slots published by this identity
+    pub async fn list_published_keys(&self) -> Result<Vec<String>, PubkyAdapterError> {
+        let url = format!("pubky://{}{}", self.our_pubky_id(), NAMED_KEYS_DIR);
+
+        let entries = self
+            .client
+            .list(&url)
+            .map_err(|e| {
+                PubkyAdapterError::Network(anyhow::anyhow!("Failed to list keys: {:?}", e))
+            })?
+            .send()
+            .await
+            .map_err(|e| {
+                PubkyAdapterError::Network(anyhow::anyhow!("Failed to list keys: {:?}", e))
+            })?;
+
+        let mut names: Vec<String> = entries
+            .iter()
+            .filter_map(|entry| entry.rsplit('/').next())
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Resolve a Pubky ID to get the Sealedge public key (async)
+    pub async fn resolve_public_key(&self, pubky_id: &str) -> Result<PublicKey, PubkyAdapterError> {
+        self.fetch_key_record(pubky_id, DEFAULT_KEY_PATH)
+            .await?
+            .to_public_key()
+    }
+
+    /// Resolve a Pubky ID, retrying transient failures according to `policy`
+    ///
+    /// Only errors for which [`PubkyAdapterError::is_retryable`] holds are retried;
+    /// the last error is returned once `policy.max_attempts` is exhausted.
+    pub async fn resolve_public_key_with_retry(
+        &self,
+        pubky_id: &str,
+        policy: &RetryPolicy,
+    ) -> Result<PublicKey, PubkyAdapterError> {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            match self.resolve_public_key(pubky_id).await {
+                Err(e) if policy.should_retry(attempts, &e) => {
+                    tokio::time::sleep(policy.delay_for(attempts - 1)).await;
+                }
+                result => return result,
+            }
+        }
+    }
 
+    /// Resolve the key published under a named slot of a Pubky ID
+    pub async fn resolve_public_key_named(
+        &self,
+        pubky_id: &str,
+        name: &str,
+    ) -> Result<PublicKey, PubkyAdapterError> {
+        let path = named_key_path(name)?;
+        self.fetch_key_record(pubky_id, &path)
+            .await?
+            .to_public_key()
+    }
+
+    /// Resolve a Pubky ID, rejecting records older than `max_age_secs` or past `expires_at`
+    pub async fn resolve_public_key_fresh(
+        &self,
+        pubky_id: &str,
+        max_age_secs: u64,
+    ) -> Result<PublicKey, PubkyAdapterError> {
+        let record = self.fetch_key_record(pubky_id, DEFAULT_KEY_PATH).await?;
+        record.check_freshness(max_age_secs, unix_now())?;
+        record.to_public_key()
+    }
+
+    /// Store a key record at `path` under our identity and return our Pubky ID
+    async fn store_key_record(
+        &self,
+        path: &str,
+        record: &SealedgeKeyRecord,
+    ) -> Result<String, PubkyAdapterError> {
+        let mut record = record.clone();
+        let seed = Zeroizing::new(self.keypair.secret_key());
+        record.sign(&SigningKey::from_bytes(&seed), path)?;
         let record_json = serde_json::to_string(&record)?;
-        let path = "/sealedge/public_key";
 
         // Store the record in Pubky network
         self.client
@@ -142,12 +493,15 @@ impl PubkyBackend {
             })?;
 
         // Return the Pubky ID
-        Ok(hex::encode(self.keypair.public_key().to_bytes()))
+        Ok(self.our_pubky_id())
     }
 
-    /// Resolve a Pubky ID to get the Sealedge public key (async)
-    pub async fn resolve_public_key(&self, pubky_id: &str) -> Result<PublicKey, PubkyAdapterError> {
-        let path = "/sealedge/public_key";
+    /// Fetch the raw key record published at `path` under a Pubky ID
+    async fn fetch_key_record(
+        &self,
+        pubky_id: &str,
+        path: &str,
+    ) -> Result<SealedgeKeyRecord, PubkyAdapterError> {
         let url = format!("pubky://{}{}", pubky_id, path);
 
         // Retrieve the record from Pubky network
@@ -155,6 +509,13 @@ impl PubkyBackend {
             PubkyAdapterError::Network(anyhow::anyhow!("Failed to resolve key: {:?}", e))
         })?;
 
+        if response.status().as_u16() == 404 {
+            return Err(PubkyAdapterError::KeyResolutionFailed(format!(
+                "{}{}",
+                pubky_id, path
+            )));
+        }
+
         let record_bytes = response.bytes().await.map_err(|e| {
             PubkyAdapterError::Network(anyhow::anyhow!("Failed to read response: {:?}", e))
         })?;
@@ -162,43 +523,75 @@ impl PubkyBackend {
         let record_str = String::from_utf8(record_bytes.to_vec())
             .map_err(|e| PubkyAdapterError::InvalidPubkyId(format!("Invalid UTF-8: {:?}", e)))?;
 
+        // A relay could serve anything, so only trust records signed by the identity
         let record: SealedgeKeyRecord = serde_json::from_str(&record_str)?;
-
-        // Convert back to Sealedge PublicKey
-        let algorithm = match record.public_key.algorithm.as_str() {
-            "Ed25519" => AsymmetricAlgorithm::Ed25519,
-            "EcdsaP256" => AsymmetricAlgorithm::EcdsaP256,
-            "Rsa2048" => AsymmetricAlgorithm::Rsa2048,
-            "Rsa4096" => AsymmetricAlgorithm::Rsa4096,
-            _ => {
-                return Err(PubkyAdapterError::InvalidPubkyId(format!(
-                    "Unsupported algorithm: {}",
-                    record.public_key.algorithm
-                )))
-            }
-        };
-
-        let key_bytes = hex::decode(&record.public_key.key_bytes)
-            .map_err(|e| PubkyAdapterError::InvalidPubkyId(format!("Invalid hex: {:?}", e)))?;
-
-        let public_key = if let Some(key_id) = record.public_key.key_id {
-            PublicKey::with_id(algorithm, key_bytes, key_id)
-        } else {
-            PublicKey::new(algorithm, key_bytes)
-        };
-
-        Ok(public_key)
+        record.verify_signature(pubky_id, path)?;
+        Ok(record)
     }
 
     /// Resolve a Pubky ID to get the Sealedge public key (sync)
+    ///
+    /// Returns [`PubkyAdapterError::BlockingInAsyncContext`] when called from inside a
+    /// Tokio runtime; use [`PubkyBackend::resolve_public_key`] there instead.
     pub fn resolve_public_key_sync(&self, pubky_id: &str) -> Result<PublicKey, PubkyAdapterError> {
-        self.runtime.block_on(self.resolve_public_key(pubky_id))
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(PubkyAdapterError::BlockingInAsyncContext(
+                "resolve_public_key_sync cannot block inside a Tokio runtime, \
+                 use resolve_public_key or AsyncUniversalBackend instead"
+                    .to_string(),
+            ));
+        }
+
+        match &self.runtime {
+            Some(runtime) => runtime.block_on(self.resolve_public_key(pubky_id)),
+            None => tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| {
+                    PubkyAdapterError::Network(anyhow::anyhow!(
+                        "Failed to create async runtime: {:?}",
+                        e
+                    ))
+                })?
+                .block_on(self.resolve_public_key(pubky_id)),
+        }
     }
 
     /// Get our Pubky ID
     pub fn our_pubky_id(&self) -> String {
         hex::encode(self.keypair.public_key().to_bytes())
     }
+
+    /// Export our private seed encrypted to `wrapping_pub`
+    ///
+    /// The blob is a hybrid envelope from [`sealedge_core::seal_for_recipient`], so only
+    /// the holder of the matching private key can recover the identity. Use this for
+    /// backups and migration instead of [`extract_private_key_seed`].
+    pub fn export_wrapped(&self, wrapping_pub: &PublicKey) -> Result<Vec<u8>, PubkyAdapterError> {
+        let seed = Zeroizing::new(self.keypair.secret_key());
+        Ok(sealedge_core::seal_for_recipient(
+            seed.as_slice(),
+            wrapping_pub,
+        )?)
+    }
+
+    /// Recreate a backend from a blob produced by [`export_wrapped`](Self::export_wrapped)
+    ///
+    /// Fails when `unwrapping_priv` is not the key the blob was wrapped to.
+    pub fn import_wrapped(
+        blob: &[u8],
+        unwrapping_priv: &PrivateKey,
+    ) -> Result<Self, PubkyAdapterError> {
+        let mut plaintext = sealedge_core::open_envelope(blob, unwrapping_priv)?;
+        let seed: Result<[u8; 32], _> = plaintext.as_slice().try_into();
+        plaintext.zeroize();
+        let mut seed = seed.map_err(|_| {
+            PubkyAdapterError::InvalidWrappedKey("expected a 32-byte private seed".to_string())
+        })?;
+        let keypair = Keypair::from_secret_key(&seed);
+        seed.zeroize();
+        Self::new_sync(keypair)
+    }
 }
 
 impl UniversalBackend for PubkyBackend {
@@ -264,6 +657,32 @@ impl UniversalBackend for PubkyBackend {
     }
 }
 
+#[async_trait]
+impl AsyncUniversalBackend for PubkyBackend {
+    async fn perform_operation_async(
+        &self,
+        key_id: &str,
+        operation: CryptoOperation,
+    ) -> Result<CryptoResult, BackendError> {
+        match operation {
+            CryptoOperation::GetPublicKey => {
+                // key_id is the Pubky ID
+                let public_key = self.resolve_public_key(key_id).await.map_err(|e| {
+                    BackendError::KeyNotFound(format!(
+                        "Failed to resolve Pubky ID {}: {}",
+                        key_id, e
+                    ))
+                })?;
+                Ok(CryptoResult::PublicKey(public_key.key_bytes))
+            }
+            _ => Err(BackendError::UnsupportedOperation(format!(
+                "Operation not supported by PubkyBackend: {:?}",
+                operation
+            ))),
+        }
+    }
+}
+
 /// Send trusted data to a recipient via Pubky network resolution
 ///
 /// This is the main high-level function that:
@@ -304,12 +723,13 @@ pub fn create_pubky_backend_from_seed(seed: &[u8; 32]) -> Result<PubkyBackend, P
     PubkyBackend::new_sync(keypair)
 }
 
-/// Extract the private key seed from a PubkyBackend
-/// This is needed for key export functionality
-pub fn extract_private_key_seed(backend: &PubkyBackend) -> [u8; 32] {
-    // This is a temporary implementation - in a real system,
-    // private keys should be handled more securely
-    backend.keypair.secret_key()
+/// Extract the raw private key seed from a PubkyBackend
+///
+/// The seed is wiped when the returned wrapper is dropped; copy it out only into
+/// storage that is itself zeroized.
+#[deprecated(note = "exposes raw secret bytes; use PubkyBackend::export_wrapped instead")]
+pub fn extract_private_key_seed(backend: &PubkyBackend) -> Zeroizing<[u8; 32]> {
+    Zeroizing::new(backend.keypair.secret_key())
 }
 
 /// Convenience function to create a Pubky backend with a random keypair
@@ -323,6 +743,27 @@ mod tests {
     use super::*;
     use sealedge_core::{AsymmetricAlgorithm, KeyPair};
 
+    #[test]
+    fn test_retry_policy_backoff_is_bounded() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(300),
+            jitter: 0.0,
+        };
+        assert_eq!(policy.delay_for(0), Duration::from_millis(100));
+        assert_eq!(policy.delay_for(1), Duration::from_millis(200));
+        assert_eq!(policy.delay_for(2), Duration::from_millis(300));
+        assert_eq!(policy.delay_for(40), Duration::from_millis(300));
+
+        let jittered = RetryPolicy {
+            jitter: 0.5,
+            ..policy
+        };
+        let delay = jittered.delay_for(1);
+        assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+    }
+
     #[test]
     fn test_public_key_serialization() {
         let keypair =
@@ -341,7 +782,9 @@ mod tests {
                 key_id: keypair.public.key_id.clone(),
             },
             created_at: current_timestamp,
+            expires_at: None,
             metadata: None,
+            signature: None,
         };
 
         let json = serde_json::to_string(&record).expect("Failed to serialize record");
@@ -486,7 +929,7 @@ mod tests {
 
         // Publish Bob's key
         let bob_pubky_id = bob_backend
-            .publish_public_key(&bob_keypair.public)
+            .publish_public_key(&bob_keypair.public, None)
             .expect("Failed to publish Bob's key");
 
         // Test the clean API with mock
@@ -501,6 +944,44 @@ mod tests {
         assert_eq!(message, decrypted.as_slice());
     }
 
+    #[tokio::test]
+    async fn test_async_resolution_inside_runtime() {
+        use crate::mock::MockPubkyBackend;
+
+        let backend = MockPubkyBackend::new();
+        let keypair =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key pair");
+        let pubky_id = backend
+            .publish_public_key(&keypair.public, None)
+            .expect("Failed to publish key");
+
+        let result = backend
+            .perform_operation_async(&pubky_id, CryptoOperation::GetPublicKey)
+            .await
+            .expect("Async resolution failed");
+        match result {
+            CryptoResult::PublicKey(key_bytes) => assert_eq!(key_bytes, keypair.public.key_bytes),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_resolution_inside_runtime_errors() {
+        let backend = PubkyBackend::new(Keypair::random())
+            .await
+            .expect("Failed to create backend");
+        let pubky_id = backend.our_pubky_id();
+
+        let result = backend.resolve_public_key_sync(&pubky_id);
+        assert!(matches!(
+            result,
+            Err(PubkyAdapterError::BlockingInAsyncContext(_))
+        ));
+
+        let result = backend.perform_operation(&pubky_id, CryptoOperation::GetPublicKey);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_deterministic_key_generation() {
         let seed = [0x42; 32]; // Fixed seed for deterministic testing
@@ -532,6 +1013,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_extract_private_key_seed() {
         let original_seed = [0x13; 32];
         let backend =
@@ -539,7 +1021,7 @@ mod tests {
 
         let extracted_seed = extract_private_key_seed(&backend);
         assert_eq!(
-            original_seed, extracted_seed,
+            original_seed, *extracted_seed,
             "Extracted seed should match original"
         );
 
@@ -553,4 +1035,53 @@ mod tests {
             "Recreated backend should have same Pubky ID"
         );
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_extracted_seed_is_zeroizing() {
+        let backend =
+            create_pubky_backend_from_seed(&[0x13; 32]).expect("Failed to create backend");
+
+        // The annotation pins the public API: no bare [u8; 32] secret
+        let mut seed: Zeroizing<[u8; 32]> = extract_private_key_seed(&backend);
+        assert_eq!(*seed, [0x13; 32]);
+
+        // Zeroizing's Drop runs exactly this before releasing the memory
+        seed.zeroize();
+        assert_eq!(*seed, [0u8; 32]);
+    }
+
+    #[test]
+    fn test_wrapped_export_round_trips() {
+        let backend =
+            create_pubky_backend_from_seed(&[0x42; 32]).expect("Failed to create backend");
+        let wrapping = KeyPair::generate(AsymmetricAlgorithm::Rsa2048)
+            .expect("Failed to generate wrapping key pair");
+
+        let blob = backend
+            .export_wrapped(&wrapping.public)
+            .expect("Failed to export wrapped key");
+        assert!(
+            !blob.windows(32).any(|w| w == [0x42; 32]),
+            "Wrapped blob must not contain the raw seed"
+        );
+
+        let imported = PubkyBackend::import_wrapped(&blob, &wrapping.private)
+            .expect("Failed to import wrapped key");
+        assert_eq!(backend.our_pubky_id(), imported.our_pubky_id());
+    }
+
+    #[test]
+    fn test_wrapped_import_rejects_wrong_key() {
+        let backend = create_pubky_backend_random().expect("Failed to create backend");
+        let wrapping = KeyPair::generate(AsymmetricAlgorithm::Rsa2048)
+            .expect("Failed to generate wrapping key pair");
+        let other = KeyPair::generate(AsymmetricAlgorithm::Rsa2048)
+            .expect("Failed to generate other key pair");
+
+        let blob = backend
+            .export_wrapped(&wrapping.public)
+            .expect("Failed to export wrapped key");
+        assert!(PubkyBackend::import_wrapped(&blob, &other.private).is_err());
+    }
 }
@@ -5,27 +5,113 @@
 //! Mock Pubky adapter for testing
 //!
 //! This module provides a mock implementation that doesn't require actual
-//! network connectivity, useful for testing and development.
+//! network connectivity, useful for testing and development. A [`MockTransport`]
+//! injects the latency, failures, and evictions a real homeserver exhibits, so
+//! adapters can exercise their retry and caching logic.
 
-use crate::{PubkyAdapterError, PublicKeyData, SealedgeKeyRecord};
+use crate::{
+    named_key_path, unix_now, PubkyAdapterError, RetryPolicy, SealedgeKeyRecord, DEFAULT_KEY_PATH,
+    NAMED_KEYS_DIR,
+};
 use anyhow::Result;
+use async_trait::async_trait;
+use ed25519_dalek::SigningKey;
 use sealedge_core::backends::{
-    BackendCapabilities, BackendInfo, CryptoOperation, CryptoResult, KeyMetadata, UniversalBackend,
+    AsyncUniversalBackend, BackendCapabilities, BackendInfo, CryptoOperation, CryptoResult,
+    KeyMetadata, UniversalBackend,
 };
 use sealedge_core::error::BackendError;
 use sealedge_core::{backends::AsymmetricAlgorithm, PublicKey};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Mock storage for testing
 type MockStorage = Arc<Mutex<HashMap<String, String>>>;
 
+/// Network behaviour a [`MockPubkyBackend`] simulates around every storage call
+///
+/// Each hook receives the storage slot being read or written. The defaults model
+/// a perfect network, so implementations override only what they need.
+pub trait MockTransport: Send + Sync {
+    /// Delay before the call completes
+    fn latency(&self, _slot: &str) -> Duration {
+        Duration::ZERO
+    }
+
+    /// Whether the call fails as if the homeserver were unreachable
+    fn fail(&self, _slot: &str) -> bool {
+        false
+    }
+
+    /// Whether a read reports the record missing even though it is stored
+    fn not_found(&self, _slot: &str) -> bool {
+        false
+    }
+}
+
+/// Transport with no latency, failures, or evictions
+#[derive(Debug, Default, Clone, Copy)]
+pub struct InstantTransport;
+
+impl MockTransport for InstantTransport {}
+
+/// Transport with fixed latency, random failures, and evicted slots
+#[derive(Debug, Default)]
+pub struct SimulatedTransport {
+    latency: Duration,
+    failure_rate: f64,
+    evicted: Mutex<HashSet<String>>,
+}
+
+impl SimulatedTransport {
+    /// Create a transport that behaves like [`InstantTransport`] until configured
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delay every call by `latency`
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Fail each call with probability `rate`, clamped to `0.0..=1.0`
+    pub fn with_failure_rate(mut self, rate: f64) -> Self {
+        self.failure_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Report `slot` as missing on every later read, as if the homeserver evicted it
+    pub fn evict(&self, slot: &str) {
+        self.evicted.lock().unwrap().insert(slot.to_string());
+    }
+}
+
+impl MockTransport for SimulatedTransport {
+    fn latency(&self, _slot: &str) -> Duration {
+        self.latency
+    }
+
+    fn fail(&self, _slot: &str) -> bool {
+        self.failure_rate > 0.0 && rand::random::<f64>() < self.failure_rate
+    }
+
+    fn not_found(&self, slot: &str) -> bool {
+        self.evicted.lock().unwrap().contains(slot)
+    }
+}
+
 /// Mock Pubky backend that stores data in memory instead of the network
 pub struct MockPubkyBackend {
-    /// Our mock Pubky ID
+    /// Our mock Pubky ID (hex-encoded Ed25519 public key)
     pubky_id: String,
+    /// Our mock identity key, used to sign published records
+    signing_key: SigningKey,
     /// Shared storage for all mock backends
     storage: MockStorage,
+    /// Simulated network between this backend and the storage
+    transport: Arc<dyn MockTransport>,
 }
 
 impl Default for MockPubkyBackend {
@@ -37,77 +123,163 @@ impl Default for MockPubkyBackend {
 impl MockPubkyBackend {
     /// Create a new mock backend
     pub fn new() -> Self {
-        Self {
-            pubky_id: hex::encode(rand::random::<[u8; 32]>()),
-            storage: Arc::new(Mutex::new(HashMap::new())),
-        }
+        Self::with_shared_storage(Arc::new(Mutex::new(HashMap::new())))
     }
 
     /// Create a mock backend with shared storage
     pub fn with_shared_storage(storage: MockStorage) -> Self {
+        Self::with_transport(storage, Arc::new(InstantTransport))
+    }
+
+    /// Create a mock backend with shared storage reached through `transport`
+    pub fn with_transport(storage: MockStorage, transport: Arc<dyn MockTransport>) -> Self {
+        let signing_key = SigningKey::from_bytes(&rand::random::<[u8; 32]>());
         Self {
-            pubky_id: hex::encode(rand::random::<[u8; 32]>()),
+            pubky_id: hex::encode(signing_key.verifying_key().to_bytes()),
+            signing_key,
             storage,
+            transport,
         }
     }
 
+    /// Apply the transport's latency and failure hooks to a call on `slot`
+    fn simulate_call(&self, slot: &str) -> bool {
+        let latency = self.transport.latency(slot);
+        if !latency.is_zero() {
+            std::thread::sleep(latency);
+        }
+        !self.transport.fail(slot)
+    }
+
     /// Publish a public key (stores in mock storage)
-    pub fn publish_public_key(&self, public_key: &PublicKey) -> Result<String, PubkyAdapterError> {
-        let record = SealedgeKeyRecord {
-            public_key: PublicKeyData {
-                algorithm: format!("{:?}", public_key.algorithm),
-                key_bytes: hex::encode(&public_key.key_bytes),
-                key_id: public_key.key_id.clone(),
-            },
-            created_at: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
-            metadata: None,
-        };
+    pub fn publish_public_key(
+        &self,
+        public_key: &PublicKey,
+        ttl_secs: Option<u64>,
+    ) -> Result<String, PubkyAdapterError> {
+        let record = SealedgeKeyRecord::new(public_key, ttl_secs);
+        self.store_key_record(self.pubky_id.clone(), DEFAULT_KEY_PATH, &record)
+    }
+
+    /// Publish a public key under a named slot (stores in mock storage)
+    pub fn publish_public_key_named(
+        &self,
+        name: &str,
+        public_key: &PublicKey,
+        ttl_secs: Option<u64>,
+    ) -> Result<String, PubkyAdapterError> {
+        let path = named_key_path(name)?;
+        let slot = format!("{}{}", self.pubky_id, path);
+        let record = SealedgeKeyRecord::new(public_key, ttl_secs);
+        self.store_key_record(slot, &path, &record)
+    }
+
+    /// List the named key slots published by this identity
+    pub fn list_published_keys(&self) -> Result<Vec<String>, PubkyAdapterError> {
+        let prefix = format!("{}{}", self.pubky_id, NAMED_KEYS_DIR);
+        let storage = self.storage.lock().unwrap();
+
+        let mut names: Vec<String> = storage
+            .keys()
+            .filter_map(|slot| slot.strip_prefix(&prefix))
+            .map(str::to_string)
+            .collect();
+        names.sort();
+        Ok(names)
+    }
 
+    /// Resolve a public key (retrieves from mock storage)
+    pub fn resolve_public_key(&self, pubky_id: &str) -> Result<PublicKey, PubkyAdapterError> {
+        self.fetch_key_record(pubky_id, pubky_id, DEFAULT_KEY_PATH)?
+            .to_public_key()
+    }
+
+    /// Resolve a public key, retrying transient failures according to `policy`
+    pub fn resolve_public_key_with_retry(
+        &self,
+        pubky_id: &str,
+        policy: &RetryPolicy,
+    ) -> Result<PublicKey, PubkyAdapterError> {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            match self.resolve_public_key(pubky_id) {
+                Err(e) if policy.should_retry(attempts, &e) => {
+                    std::thread::sleep(policy.delay_for(attempts - 1));
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Resolve the key stored under a named slot of a Pubky ID
+    pub fn resolve_public_key_named(
+        &self,
+        pubky_id: &str,
+        name: &str,
+    ) -> Result<PublicKey, PubkyAdapterError> {
+        let path = named_key_path(name)?;
+        let slot = format!("{}{}", pubky_id, path);
+        self.fetch_key_record(pubky_id, &slot, &path)?
+            .to_public_key()
+    }
+
+    /// Resolve a public key, rejecting records older than `max_age_secs` or past `expires_at`
+    pub fn resolve_public_key_fresh(
+        &self,
+        pubky_id: &str,
+        max_age_secs: u64,
+    ) -> Result<PublicKey, PubkyAdapterError> {
+        let record = self.fetch_key_record(pubky_id, pubky_id, DEFAULT_KEY_PATH)?;
+        record.check_freshness(max_age_secs, unix_now())?;
+        record.to_public_key()
+    }
+
+    /// Store a key record signed for `path` under `slot` and return our Pubky ID
+    fn store_key_record(
+        &self,
+        slot: String,
+        path: &str,
+        record: &SealedgeKeyRecord,
+    ) -> Result<String, PubkyAdapterError> {
+        let mut record = record.clone();
+        record.sign(&self.signing_key, path)?;
         let record_json = serde_json::to_string(&record)?;
 
+        if !self.simulate_call(&slot) {
+            return Err(PubkyAdapterError::Network(anyhow::anyhow!(
+                "Injected transport failure publishing {}",
+                slot
+            )));
+        }
+
         // Store in mock storage
         let mut storage = self.storage.lock().unwrap();
-        storage.insert(self.pubky_id.clone(), record_json);
+        storage.insert(slot, record_json);
 
         Ok(self.pubky_id.clone())
     }
 
-    /// Resolve a public key (retrieves from mock storage)
-    pub fn resolve_public_key(&self, pubky_id: &str) -> Result<PublicKey, PubkyAdapterError> {
+    /// Fetch the key record stored under a slot (a bare Pubky ID for the default key)
+    /// and verify it was signed by `pubky_id` for `path`
+    fn fetch_key_record(
+        &self,
+        pubky_id: &str,
+        slot: &str,
+        path: &str,
+    ) -> Result<SealedgeKeyRecord, PubkyAdapterError> {
+        if !self.simulate_call(slot) || self.transport.not_found(slot) {
+            return Err(PubkyAdapterError::KeyResolutionFailed(slot.to_string()));
+        }
+
         let storage = self.storage.lock().unwrap();
         let record_json = storage
-            .get(pubky_id)
-            .ok_or_else(|| PubkyAdapterError::KeyResolutionFailed(pubky_id.to_string()))?;
+            .get(slot)
+            .ok_or_else(|| PubkyAdapterError::KeyResolutionFailed(slot.to_string()))?;
 
         let record: SealedgeKeyRecord = serde_json::from_str(record_json)?;
-
-        // Convert back to Sealedge PublicKey
-        let algorithm = match record.public_key.algorithm.as_str() {
-            "Ed25519" => sealedge_core::backends::AsymmetricAlgorithm::Ed25519,
-            "EcdsaP256" => sealedge_core::backends::AsymmetricAlgorithm::EcdsaP256,
-            "Rsa2048" => sealedge_core::backends::AsymmetricAlgorithm::Rsa2048,
-            "Rsa4096" => sealedge_core::backends::AsymmetricAlgorithm::Rsa4096,
-            _ => {
-                return Err(PubkyAdapterError::InvalidPubkyId(format!(
-                    "Unsupported algorithm: {}",
-                    record.public_key.algorithm
-                )))
-            }
-        };
-
-        let key_bytes = hex::decode(&record.public_key.key_bytes)
-            .map_err(|e| PubkyAdapterError::InvalidPubkyId(format!("Invalid hex: {:?}", e)))?;
-
-        let public_key = if let Some(key_id) = record.public_key.key_id {
-            PublicKey::with_id(algorithm, key_bytes, key_id)
-        } else {
-            PublicKey::new(algorithm, key_bytes)
-        };
-
-        Ok(public_key)
+        record.verify_signature(pubky_id, path)?;
+        Ok(record)
     }
 
     /// Get our mock Pubky ID
@@ -179,6 +351,18 @@ impl UniversalBackend for MockPubkyBackend {
     }
 }
 
+#[async_trait]
+impl AsyncUniversalBackend for MockPubkyBackend {
+    async fn perform_operation_async(
+        &self,
+        key_id: &str,
+        operation: CryptoOperation,
+    ) -> Result<CryptoResult, BackendError> {
+        // Mock storage is in memory, so the blocking path never touches a runtime
+        self.perform_operation(key_id, operation)
+    }
+}
+
 /// Mock version of send_trusted_data for testing
 pub fn mock_send_trusted_data(
     data: &[u8],
@@ -216,10 +400,10 @@ mod tests {
 
         // Publish keys
         let alice_pubky_id = alice_backend
-            .publish_public_key(&alice_keypair.public)
+            .publish_public_key(&alice_keypair.public, None)
             .expect("Failed to publish Alice's key");
         let bob_pubky_id = bob_backend
-            .publish_public_key(&bob_keypair.public)
+            .publish_public_key(&bob_keypair.public, None)
             .expect("Failed to publish Bob's key");
 
         // Test key resolution
@@ -240,4 +424,338 @@ mod tests {
 
         assert_eq!(message, decrypted.as_slice());
     }
+
+    #[test]
+    fn test_stale_record_rejected() {
+        let backend = MockPubkyBackend::new();
+        let keypair =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key");
+
+        let pubky_id = backend
+            .publish_public_key(&keypair.public, Some(1))
+            .expect("Failed to publish key");
+
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+
+        let result = backend.resolve_public_key_fresh(&pubky_id, 3600);
+        assert!(matches!(result, Err(PubkyAdapterError::StaleKeyRecord(_))));
+
+        // Plain resolution does not apply freshness rules
+        assert!(backend.resolve_public_key(&pubky_id).is_ok());
+    }
+
+    #[test]
+    fn test_fresh_record_resolves() {
+        let backend = MockPubkyBackend::new();
+        let keypair =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key");
+
+        let pubky_id = backend
+            .publish_public_key(&keypair.public, Some(3600))
+            .expect("Failed to publish key");
+
+        let resolved = backend
+            .resolve_public_key_fresh(&pubky_id, 60)
+            .expect("Fresh record should resolve");
+        assert_eq!(resolved.key_bytes, keypair.public.key_bytes);
+    }
+
+    #[test]
+    fn test_max_age_enforced() {
+        let keypair =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key");
+        let record = SealedgeKeyRecord::new(&keypair.public, None);
+
+        assert!(record.check_freshness(60, record.created_at + 60).is_ok());
+        assert!(matches!(
+            record.check_freshness(60, record.created_at + 61),
+            Err(PubkyAdapterError::StaleKeyRecord(_))
+        ));
+    }
+
+    #[test]
+    fn test_named_key_slots() {
+        let storage = Arc::new(Mutex::new(HashMap::new()));
+        let alice_backend = MockPubkyBackend::with_shared_storage(storage.clone());
+        let bob_backend = MockPubkyBackend::with_shared_storage(storage);
+
+        let signing =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key");
+        let encryption =
+            KeyPair::generate(AsymmetricAlgorithm::EcdsaP256).expect("Failed to generate key");
+
+        let alice_id = alice_backend
+            .publish_public_key_named("signing", &signing.public, None)
+            .expect("Failed to publish signing key");
+        alice_backend
+            .publish_public_key_named("encryption", &encryption.public, None)
+            .expect("Failed to publish encryption key");
+
+        assert_eq!(
+            alice_backend.list_published_keys().unwrap(),
+            vec!["encryption", "signing"]
+        );
+        assert!(bob_backend.list_published_keys().unwrap().is_empty());
+
+        let resolved_signing = bob_backend
+            .resolve_public_key_named(&alice_id, "signing")
+            .expect("Failed to resolve signing key");
+        let resolved_encryption = bob_backend
+            .resolve_public_key_named(&alice_id, "encryption")
+            .expect("Failed to resolve encryption key");
+        assert_eq!(resolved_signing.key_bytes, signing.public.key_bytes);
+        assert_eq!(resolved_encryption.key_bytes, encryption.public.key_bytes);
+
+        // Named slots are independent of the default record
+        assert!(matches!(
+            bob_backend.resolve_public_key(&alice_id),
+            Err(PubkyAdapterError::KeyResolutionFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_named_key() {
+        let backend = MockPubkyBackend::new();
+        let keypair =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key");
+        let pubky_id = backend
+            .publish_public_key_named("signing", &keypair.public, None)
+            .expect("Failed to publish key");
+
+        assert!(matches!(
+            backend.resolve_public_key_named(&pubky_id, "rotated"),
+            Err(PubkyAdapterError::KeyResolutionFailed(_))
+        ));
+        assert!(matches!(
+            backend.publish_public_key_named("../escape", &keypair.public, None),
+            Err(PubkyAdapterError::InvalidKeyName(_))
+        ));
+    }
+
+    #[test]
+    fn test_injected_failure_surfaces_as_resolution_failure() {
+        let storage = Arc::new(Mutex::new(HashMap::new()));
+        let publisher = MockPubkyBackend::with_shared_storage(storage.clone());
+        let keypair =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key");
+        let pubky_id = publisher
+            .publish_public_key(&keypair.public, None)
+            .expect("Failed to publish key");
+
+        let flaky = MockPubkyBackend::with_transport(
+            storage,
+            Arc::new(SimulatedTransport::new().with_failure_rate(1.0)),
+        );
+        assert!(matches!(
+            flaky.resolve_public_key(&pubky_id),
+            Err(PubkyAdapterError::KeyResolutionFailed(_))
+        ));
+        assert!(publisher.resolve_public_key(&pubky_id).is_ok());
+    }
+
+    #[test]
+    fn test_injected_latency_is_observable() {
+        let latency = Duration::from_millis(50);
+        let backend = MockPubkyBackend::with_transport(
+            Arc::new(Mutex::new(HashMap::new())),
+            Arc::new(SimulatedTransport::new().with_latency(latency)),
+        );
+        let keypair =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key");
+        let pubky_id = backend
+            .publish_public_key(&keypair.public, None)
+            .expect("Failed to publish key");
+
+        let started = std::time::Instant::now();
+        backend
+            .resolve_public_key(&pubky_id)
+            .expect("Failed to resolve key");
+        assert!(started.elapsed() >= latency);
+    }
+
+    #[test]
+    fn test_evicted_record_not_found() {
+        let transport = Arc::new(SimulatedTransport::new());
+        let backend = MockPubkyBackend::with_transport(
+            Arc::new(Mutex::new(HashMap::new())),
+            transport.clone(),
+        );
+        let keypair =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key");
+        let pubky_id = backend
+            .publish_public_key(&keypair.public, None)
+            .expect("Failed to publish key");
+        assert!(backend.resolve_public_key(&pubky_id).is_ok());
+
+        transport.evict(&pubky_id);
+        assert!(matches!(
+            backend.resolve_public_key(&pubky_id),
+            Err(PubkyAdapterError::KeyResolutionFailed(_))
+        ));
+    }
+
+    /// Fails the first `failures` calls, then behaves like a perfect network
+    struct FlakyTransport {
+        failures: u32,
+        calls: std::sync::atomic::AtomicU32,
+    }
+
+    impl FlakyTransport {
+        fn new(failures: u32) -> Self {
+            Self {
+                failures,
+                calls: std::sync::atomic::AtomicU32::new(0),
+            }
+        }
+
+        fn calls(&self) -> u32 {
+            self.calls.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    impl MockTransport for FlakyTransport {
+        fn fail(&self, _slot: &str) -> bool {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) < self.failures
+        }
+    }
+
+    fn fast_retries() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 4,
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            jitter: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_retry_succeeds_on_third_attempt() {
+        let storage = Arc::new(Mutex::new(HashMap::new()));
+        let publisher = MockPubkyBackend::with_shared_storage(storage.clone());
+        let keypair =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key");
+        let pubky_id = publisher
+            .publish_public_key(&keypair.public, None)
+            .expect("Failed to publish key");
+
+        let transport = Arc::new(FlakyTransport::new(2));
+        let resolver = MockPubkyBackend::with_transport(storage, transport.clone());
+
+        let resolved = resolver
+            .resolve_public_key_with_retry(&pubky_id, &fast_retries())
+            .expect("Third attempt should succeed");
+        assert_eq!(resolved.key_bytes, keypair.public.key_bytes);
+        assert_eq!(transport.calls(), 3);
+
+        // Running out of attempts returns the last transient error
+        let transport = Arc::new(FlakyTransport::new(10));
+        let resolver = MockPubkyBackend::with_transport(
+            Arc::new(Mutex::new(HashMap::new())),
+            transport.clone(),
+        );
+        assert!(matches!(
+            resolver.resolve_public_key_with_retry(&pubky_id, &fast_retries()),
+            Err(PubkyAdapterError::KeyResolutionFailed(_))
+        ));
+        assert_eq!(transport.calls(), 4);
+    }
+
+    #[test]
+    fn test_invalid_pubky_id_not_retried() {
+        let storage = Arc::new(Mutex::new(HashMap::new()));
+        let publisher = MockPubkyBackend::with_shared_storage(storage.clone());
+        let keypair =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key");
+        let pubky_id = publisher
+            .publish_public_key(&keypair.public, None)
+            .expect("Failed to publish key");
+
+        // A record served under an ID that is not a hex Ed25519 key
+        let bad_id = "not-a-pubky-id";
+        {
+            let mut storage = storage.lock().unwrap();
+            let record = storage.get(&pubky_id).unwrap().clone();
+            storage.insert(bad_id.to_string(), record);
+        }
+
+        let transport = Arc::new(FlakyTransport::new(0));
+        let resolver = MockPubkyBackend::with_transport(storage, transport.clone());
+        assert!(matches!(
+            resolver.resolve_public_key_with_retry(bad_id, &fast_retries()),
+            Err(PubkyAdapterError::InvalidPubkyId(_))
+        ));
+        assert_eq!(transport.calls(), 1);
+    }
+
+    #[test]
+    fn test_tampered_record_rejected() {
+        let storage = Arc::new(Mutex::new(HashMap::new()));
+        let backend = MockPubkyBackend::with_shared_storage(storage.clone());
+        let keypair =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key");
+        let attacker_keypair =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key");
+
+        let pubky_id = backend
+            .publish_public_key(&keypair.public, None)
+            .expect("Failed to publish key");
+        assert!(backend.resolve_public_key(&pubky_id).is_ok());
+
+        // A malicious relay swaps in its own key bytes
+        {
+            let mut storage = storage.lock().unwrap();
+            let stored = storage.get_mut(&pubky_id).unwrap();
+            let mut record: SealedgeKeyRecord = serde_json::from_str(stored).unwrap();
+            record.public_key.key_bytes = hex::encode(&attacker_keypair.public.key_bytes);
+            *stored = serde_json::to_string(&record).unwrap();
+        }
+
+        assert!(matches!(
+            backend.resolve_public_key(&pubky_id),
+            Err(PubkyAdapterError::SignatureVerificationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_record_moved_between_slots_rejected() {
+        let storage = Arc::new(Mutex::new(HashMap::new()));
+        let backend = MockPubkyBackend::with_shared_storage(storage.clone());
+        let signing =
+            KeyPair::generate(AsymmetricAlgorithm::Ed25519).expect("Failed to generate key");
+        let encryption =
+            KeyPair::generate(AsymmetricAlgorithm::EcdsaP256).expect("Failed to generate key");
+
+        let pubky_id = backend
+            .publish_public_key_named("signing", &signing.public, None)
+            .expect("Failed to publish signing key");
+        backend
+            .publish_public_key_named("encryption", &encryption.public, None)
+            .expect("Failed to publish encryption key");
+
+        // A relay serves the correctly signed `signing` record from other slots
+        {
+            let mut storage = storage.lock().unwrap();
+            let signing_slot = format!("{}{}", pubky_id, named_key_path("signing").unwrap());
+            let encryption_slot = format!("{}{}", pubky_id, named_key_path("encryption").unwrap());
+            let record = storage.get(&signing_slot).unwrap().clone();
+            storage.insert(encryption_slot, record.clone());
+            storage.insert(pubky_id.clone(), record);
+        }
+
+        assert!(matches!(
+            backend.resolve_public_key_named(&pubky_id, "encryption"),
+            Err(PubkyAdapterError::SignatureVerificationFailed(_))
+        ));
+        assert!(matches!(
+            backend.resolve_public_key(&pubky_id),
+            Err(PubkyAdapterError::SignatureVerificationFailed(_))
+        ));
+        assert_eq!(
+            backend
+                .resolve_public_key_named(&pubky_id, "signing")
+                .unwrap()
+                .key_bytes,
+            signing.public.key_bytes
+        );
+    }
 }
@@ -15,6 +15,7 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use sealedge_platform::http::{create_router, AppState, Config};
+use sealedge_platform::verify::audit::{AuditLog, DEFAULT_AUDIT_CAPACITY};
 use sealedge_platform::verify::jwks::KeyManager;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -87,6 +88,23 @@ async fn serve() -> Result<()> {
 
     let keys = Arc::new(RwLock::new(KeyManager::new()?));
 
+    // Verification audit log: bounded in memory, persisted when AUDIT_LOG_PATH is set.
+    let audit_capacity = std::env::var("AUDIT_LOG_CAPACITY")
+        .ok()
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_AUDIT_CAPACITY);
+    let audit = match std::env::var("AUDIT_LOG_PATH") {
+        Ok(path) => {
+            tracing::info!("Audit log: {}", path);
+            AuditLog::open(&path, audit_capacity)?
+        }
+        Err(_) => {
+            tracing::warn!("AUDIT_LOG_PATH not set — audit log is kept in memory only");
+            AuditLog::with_capacity(audit_capacity)
+        }
+    };
+    let audit = Arc::new(std::sync::Mutex::new(audit));
+
     #[cfg(feature = "postgres")]
     let state = {
         let db_pool = create_connection_pool(&config.database_url).await?;
@@ -94,6 +112,8 @@ async fn serve() -> Result<()> {
             keys,
             db_pool,
             receipt_ttl_secs: config.receipt_ttl_secs,
+            audit,
+            webhooks: Default::default(),
         }
     };
 
@@ -101,6 +121,8 @@ async fn serve() -> Result<()> {
     let state = AppState {
         keys,
         receipt_ttl_secs: config.receipt_ttl_secs,
+        audit,
+        webhooks: Default::default(),
     };
 
     let router = create_router(state);
@@ -113,7 +135,16 @@ async fn serve() -> Result<()> {
         .ok()
         .and_then(|s| s.parse::<u32>().ok())
         .unwrap_or(10);
-    tracing::info!("Rate limit: {} req/sec per IP on /v1/verify", rps);
+    let burst = std::env::var("RATE_LIMIT_BURST")
+        .ok()
+        .and_then(|s| s.parse::<u32>().ok())
+        .filter(|&b| b > 0)
+        .unwrap_or(rps);
+    tracing::info!(
+        "Rate limit: {} req/sec per client (burst {}) on /v1/verify",
+        rps,
+        burst
+    );
 
     axum::serve(
         listener,
@@ -118,6 +118,8 @@ async fn test_appstate_construction_and_router_health() {
     let state = AppState {
         keys: Arc::new(RwLock::new(key_manager)),
         receipt_ttl_secs: 3600,
+        audit: Default::default(),
+        webhooks: Default::default(),
     };
 
     let app = create_router(state);
@@ -170,6 +172,8 @@ async fn test_router_verify_rejects_empty_body() {
     let state = AppState {
         keys: Arc::new(RwLock::new(key_manager)),
         receipt_ttl_secs: 3600,
+        audit: Default::default(),
+        webhooks: Default::default(),
     };
 
     let app = create_router(state);
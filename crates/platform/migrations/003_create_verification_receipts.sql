@@ -0,0 +1,27 @@
+-- Typed storage for issued VerificationReceipts
+-- Copyright (c) 2025 TRUSTEDGE LABS LLC
+
+-- One row per receipt; columns mirror sealedge_types::VerificationReceipt.
+-- issued_at keeps the receipt's RFC 3339 string verbatim so stored receipts
+-- round-trip exactly; stored_at orders device listings.
+CREATE TABLE verification_receipts (
+    verification_id TEXT PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    profile TEXT NOT NULL,
+    device_id TEXT NOT NULL,
+    manifest_digest TEXT NOT NULL,
+    segments BIGINT NOT NULL CHECK (segments >= 0),
+    duration_s REAL NOT NULL,
+    signature TEXT NOT NULL,
+    continuity TEXT NOT NULL,
+    issued_at TEXT NOT NULL,
+    service_kid TEXT NOT NULL,
+    chain_tip TEXT NOT NULL,
+    stored_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Device-scoped listing, newest first
+CREATE INDEX idx_verification_receipts_device
+    ON verification_receipts(org_id, device_id, stored_at DESC, verification_id DESC);
+CREATE INDEX idx_verification_receipts_manifest_digest
+    ON verification_receipts(manifest_digest);
@@ -53,6 +53,15 @@ pub enum CAError {
 
     #[error("Certificate generation error: {0}")]
     CertificateGeneration(String),
+
+    #[error("CA key not found: {0}")]
+    KeyNotFound(String),
+
+    #[error("CA key revoked: {0}")]
+    KeyRevoked(String),
+
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
 }
 
 impl CAError {
@@ -60,8 +69,12 @@ impl CAError {
         match self {
             CAError::Authentication(_) => 401,
             CAError::Authorization(_) => 403,
-            CAError::TenantNotFound(_) | CAError::CertificateNotFound(_) => 404,
-            CAError::InvalidRequest(_) => 400,
+            CAError::TenantNotFound(_)
+            | CAError::CertificateNotFound(_)
+            | CAError::KeyNotFound(_) => 404,
+            CAError::InvalidRequest(_) | CAError::KeyRevoked(_) | CAError::InvalidSignature(_) => {
+                400
+            }
             _ => 500,
         }
     }
@@ -0,0 +1,441 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Versioned CA receipt-signing keys.
+//!
+//! Status: Library-only. The key ring holds one `Active` Ed25519 key that signs
+//! new receipts and any number of older versions:
+//!
+//! - `Retired` keys no longer sign but still verify what they signed, so
+//!   rotation does not invalidate previously issued receipts.
+//! - `Revoked` keys are distrusted; receipts naming them are rejected.
+//!
+//! Each version is identified by a `service_kid`, which issued receipts carry
+//! so verifiers know which key to check against. Certificate signing still
+//! goes through the backend key configured on the service.
+//!
+//! A ring created with [`CaKeyRing::open`] is stored as JSON at the given path
+//! (mode 0600, like the JWKS signing key) and rewritten on every rotation and
+//! revocation, so key history survives restarts. Only the active version's
+//! private key is stored.
+
+use super::error::{CAError, CAResult};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Utc};
+use sealedge_core::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Lifecycle state of a CA key version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaKeyStatus {
+    /// Signs new receipts
+    Active,
+    /// Replaced by a newer key; still trusted for verification
+    Retired,
+    /// No longer trusted for anything
+    Revoked,
+}
+
+/// One version of the CA signing key
+#[derive(Debug, Clone)]
+pub struct CaKeyVersion {
+    pub kid: String,
+    pub version: u32,
+    pub status: CaKeyStatus,
+    pub created_at: DateTime<Utc>,
+    pub retired_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    verifying_key: VerifyingKey,
+    /// Dropped once the key is retired so it can no longer sign
+    signing_key: Option<SigningKey>,
+}
+
+impl CaKeyVersion {
+    fn generate(version: u32) -> Self {
+        let signing_key = SigningKey::generate(&mut rand_core::OsRng);
+        Self {
+            kid: format!("ca_key_v{}_{}", version, uuid::Uuid::new_v4().simple()),
+            version,
+            status: CaKeyStatus::Active,
+            created_at: Utc::now(),
+            retired_at: None,
+            revoked_at: None,
+            verifying_key: signing_key.verifying_key(),
+            signing_key: Some(signing_key),
+        }
+    }
+
+    pub fn verifying_key(&self) -> &VerifyingKey {
+        &self.verifying_key
+    }
+}
+
+/// On-disk form of a [`CaKeyVersion`]
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredKeyVersion {
+    kid: String,
+    version: u32,
+    status: CaKeyStatus,
+    created_at: DateTime<Utc>,
+    retired_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+    public_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    private_key: Option<String>,
+}
+
+impl From<&CaKeyVersion> for StoredKeyVersion {
+    fn from(key: &CaKeyVersion) -> Self {
+        Self {
+            kid: key.kid.clone(),
+            version: key.version,
+            status: key.status,
+            created_at: key.created_at,
+            retired_at: key.retired_at,
+            revoked_at: key.revoked_at,
+            public_key: BASE64.encode(key.verifying_key.to_bytes()),
+            private_key: key
+                .signing_key
+                .as_ref()
+                .map(|signing_key| BASE64.encode(signing_key.to_bytes())),
+        }
+    }
+}
+
+impl TryFrom<StoredKeyVersion> for CaKeyVersion {
+    type Error = CAError;
+
+    fn try_from(stored: StoredKeyVersion) -> CAResult<Self> {
+        let invalid =
+            |what: &str| CAError::Configuration(format!("CA key {}: invalid {}", stored.kid, what));
+        let public_key: [u8; 32] = BASE64
+            .decode(&stored.public_key)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| invalid("public key"))?;
+        let verifying_key =
+            VerifyingKey::from_bytes(&public_key).map_err(|_| invalid("public key"))?;
+        let signing_key = match &stored.private_key {
+            Some(encoded) => {
+                let secret: [u8; 32] = BASE64
+                    .decode(encoded)
+                    .ok()
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or_else(|| invalid("private key"))?;
+                let signing_key = SigningKey::from_bytes(&secret);
+                if signing_key.verifying_key() != verifying_key {
+                    return Err(invalid("private key (does not match public key)"));
+                }
+                Some(signing_key)
+            }
+            None => None,
+        };
+        Ok(Self {
+            kid: stored.kid,
+            version: stored.version,
+            status: stored.status,
+            created_at: stored.created_at,
+            retired_at: stored.retired_at,
+            revoked_at: stored.revoked_at,
+            verifying_key,
+            signing_key,
+        })
+    }
+}
+
+/// All CA key versions, oldest first; the last one is always active
+#[derive(Debug, Clone)]
+pub struct CaKeyRing {
+    versions: Vec<CaKeyVersion>,
+    /// File the ring is persisted to, if it was opened from one
+    path: Option<PathBuf>,
+}
+
+impl CaKeyRing {
+    /// Create an in-memory key ring with a freshly generated version-1 key
+    pub fn generate() -> Self {
+        Self {
+            versions: vec![CaKeyVersion::generate(1)],
+            path: None,
+        }
+    }
+
+    /// Load the key ring stored at `path`, or generate one and store it there
+    pub fn open(path: impl AsRef<Path>) -> CAResult<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            let ring = Self {
+                path: Some(path.to_path_buf()),
+                ..Self::generate()
+            };
+            ring.save(&ring.versions)?;
+            return Ok(ring);
+        }
+
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CAError::Configuration(format!(
+                "Failed to read CA keys from {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let stored: Vec<StoredKeyVersion> = serde_json::from_str(&content).map_err(|e| {
+            CAError::Configuration(format!(
+                "Failed to parse CA keys from {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let versions = stored
+            .into_iter()
+            .map(CaKeyVersion::try_from)
+            .collect::<CAResult<Vec<_>>>()?;
+
+        // Exactly the last version is active and able to sign
+        let well_formed = versions.last().is_some_and(|active| {
+            active.status == CaKeyStatus::Active && active.signing_key.is_some()
+        }) && versions[..versions.len() - 1]
+            .iter()
+            .all(|k| k.status != CaKeyStatus::Active && k.signing_key.is_none());
+        if !well_formed {
+            return Err(CAError::Configuration(format!(
+                "CA keys in {} must end with exactly one active key",
+                path.display()
+            )));
+        }
+
+        Ok(Self {
+            versions,
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Write `versions` to the ring's file, if it has one
+    fn save(&self, versions: &[CaKeyVersion]) -> CAResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let write_error = |e: &dyn std::fmt::Display| {
+            CAError::Internal(format!(
+                "Failed to write CA keys to {}: {}",
+                path.display(),
+                e
+            ))
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| write_error(&e))?;
+        }
+        let stored: Vec<StoredKeyVersion> = versions.iter().map(StoredKeyVersion::from).collect();
+        let content = serde_json::to_string_pretty(&stored).map_err(|e| write_error(&e))?;
+        std::fs::write(path, content).map_err(|e| write_error(&e))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+                .map_err(|e| write_error(&e))?;
+        }
+
+        Ok(())
+    }
+
+    fn active(&self) -> &CaKeyVersion {
+        self.versions
+            .last()
+            .expect("key ring always holds an active key")
+    }
+
+    /// `service_kid` of the key that signs new receipts
+    pub fn active_kid(&self) -> &str {
+        &self.active().kid
+    }
+
+    /// All key versions, oldest first
+    pub fn versions(&self) -> &[CaKeyVersion] {
+        &self.versions
+    }
+
+    /// Generate a new active key and retire the current one.
+    ///
+    /// The retired key keeps verifying receipts it signed unless revoked.
+    /// The ring is unchanged if it cannot be persisted. Returns the new
+    /// `service_kid`.
+    pub fn rotate_key(&mut self) -> CAResult<&str> {
+        let mut versions = self.versions.clone();
+        let next_version = self.active().version + 1;
+        if let Some(current) = versions.last_mut() {
+            current.status = CaKeyStatus::Retired;
+            current.retired_at = Some(Utc::now());
+            current.signing_key = None;
+        }
+        versions.push(CaKeyVersion::generate(next_version));
+
+        self.save(&versions)?;
+        self.versions = versions;
+        Ok(self.active_kid())
+    }
+
+    /// Revoke a retired key so receipts it signed are rejected.
+    ///
+    /// The active key cannot be revoked directly; rotate first.
+    pub fn revoke_key(&mut self, kid: &str) -> CAResult<()> {
+        let mut versions = self.versions.clone();
+        let key = versions
+            .iter_mut()
+            .find(|k| k.kid == kid)
+            .ok_or_else(|| CAError::KeyNotFound(kid.to_string()))?;
+        match key.status {
+            CaKeyStatus::Active => Err(CAError::InvalidRequest(
+                "Cannot revoke the active CA key; rotate first".to_string(),
+            )),
+            CaKeyStatus::Revoked => Ok(()),
+            CaKeyStatus::Retired => {
+                key.status = CaKeyStatus::Revoked;
+                key.revoked_at = Some(Utc::now());
+                self.save(&versions)?;
+                self.versions = versions;
+                Ok(())
+            }
+        }
+    }
+
+    /// Sign `data` with the active key; returns `(service_kid, signature)`
+    pub fn sign(&self, data: &[u8]) -> (String, Vec<u8>) {
+        let active = self.active();
+        let signing_key = active
+            .signing_key
+            .as_ref()
+            .expect("active key always holds its signing key");
+        (
+            active.kid.clone(),
+            signing_key.sign(data).to_bytes().to_vec(),
+        )
+    }
+
+    /// Verify `signature` over `data` against key `kid`.
+    ///
+    /// Accepts any non-revoked version, active or retired.
+    pub fn verify(&self, kid: &str, data: &[u8], signature: &[u8]) -> CAResult<()> {
+        let key = self
+            .versions
+            .iter()
+            .find(|k| k.kid == kid)
+            .ok_or_else(|| CAError::KeyNotFound(kid.to_string()))?;
+        if key.status == CaKeyStatus::Revoked {
+            return Err(CAError::KeyRevoked(kid.to_string()));
+        }
+        let signature = Signature::from_slice(signature)
+            .map_err(|_| CAError::InvalidSignature("malformed signature".to_string()))?;
+        key.verifying_key
+            .verify_strict(data, &signature)
+            .map_err(|_| {
+                CAError::InvalidSignature(format!("signature does not verify under {}", kid))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_retires_previous_key() {
+        let mut ring = CaKeyRing::generate();
+        let (old_kid, signature) = ring.sign(b"payload");
+
+        let new_kid = ring.rotate_key().unwrap().to_string();
+        assert_ne!(old_kid, new_kid);
+        assert_eq!(ring.versions()[0].status, CaKeyStatus::Retired);
+        assert_eq!(ring.versions()[1].version, 2);
+
+        ring.verify(&old_kid, b"payload", &signature).unwrap();
+        assert_eq!(ring.sign(b"payload").0, new_kid);
+    }
+
+    #[test]
+    fn test_active_key_cannot_be_revoked() {
+        let mut ring = CaKeyRing::generate();
+        let kid = ring.active_kid().to_string();
+        assert!(matches!(
+            ring.revoke_key(&kid),
+            Err(CAError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            ring.revoke_key("unknown"),
+            Err(CAError::KeyNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_tampered_data_is_rejected() {
+        let ring = CaKeyRing::generate();
+        let (kid, signature) = ring.sign(b"payload");
+        assert!(matches!(
+            ring.verify(&kid, b"tampered", &signature),
+            Err(CAError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_opened_ring_is_restored_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ca_keys.json");
+
+        let (retired_kid, retired_sig, revoked_kid, revoked_sig, active_kid) = {
+            let mut ring = CaKeyRing::open(&path).unwrap();
+            let (revoked_kid, revoked_sig) = ring.sign(b"payload");
+            ring.rotate_key().unwrap();
+            let (retired_kid, retired_sig) = ring.sign(b"payload");
+            let active_kid = ring.rotate_key().unwrap().to_string();
+            ring.revoke_key(&revoked_kid).unwrap();
+            (
+                retired_kid,
+                retired_sig,
+                revoked_kid,
+                revoked_sig,
+                active_kid,
+            )
+        };
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let stored = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(stored.matches("private_key").count(), 1);
+
+        let ring = CaKeyRing::open(&path).unwrap();
+        assert_eq!(ring.versions().len(), 3);
+        assert_eq!(ring.active_kid(), active_kid);
+        assert_eq!(ring.sign(b"payload").0, active_kid);
+        ring.verify(&retired_kid, b"payload", &retired_sig).unwrap();
+        assert!(matches!(
+            ring.verify(&revoked_kid, b"payload", &revoked_sig),
+            Err(CAError::KeyRevoked(_))
+        ));
+    }
+
+    #[test]
+    fn test_ring_without_active_key_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ca_keys.json");
+        CaKeyRing::open(&path).unwrap();
+
+        let stored = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, stored.replace("\"active\"", "\"retired\"")).unwrap();
+        assert!(matches!(
+            CaKeyRing::open(&path),
+            Err(CAError::Configuration(_))
+        ));
+    }
+}
@@ -21,6 +21,7 @@
 pub mod auth;
 pub mod database;
 pub mod error;
+pub mod keys;
 pub mod models;
 pub mod service;
 
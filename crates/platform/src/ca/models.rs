@@ -12,6 +12,7 @@
 
 use chrono::{DateTime, Utc};
 use sealedge_core::Secret;
+use sealedge_types::receipt::VerificationReceipt;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use uuid::Uuid;
@@ -112,6 +113,14 @@ pub struct Certificate {
     pub revocation_reason: Option<String>,
 }
 
+/// A `VerificationReceipt` signed by the CA key named in its `service_kid`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedReceipt {
+    pub receipt: VerificationReceipt,
+    /// Base64 Ed25519 signature over the receipt's JSON serialization
+    pub signature: String,
+}
+
 /// Certificate signing request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CertificateRequest {
@@ -9,12 +9,15 @@
 //! CA service — core certificate authority operations.
 //!
 //! Status: Library-only. Provides certificate issuance, revocation,
-//! and listing via the Universal Backend system. Consumed directly by library callers.
+//! and listing via the Universal Backend system, plus receipt signing with
+//! rotatable CA keys (see [`super::keys`]). Consumed directly by library callers.
 
-use super::{error::*, models::*};
+use super::{error::*, keys::CaKeyRing, models::*};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use chrono::{DateTime, Duration, Utc};
 use sealedge_core::{CryptoOperation, CryptoResult, SignatureAlgorithm, UniversalBackend};
-use std::sync::Arc;
+use sealedge_types::receipt::VerificationReceipt;
+use std::sync::{Arc, RwLock};
 use uuid::Uuid;
 
 /// Core Certificate Authority service
@@ -24,6 +27,7 @@ pub struct CertificateAuthorityService {
     ca_certificate: String, // PEM-encoded CA certificate
     ca_subject: CertificateSubject,
     default_validity_days: u32,
+    receipt_keys: RwLock<CaKeyRing>,
 }
 
 impl CertificateAuthorityService {
@@ -41,9 +45,64 @@ impl CertificateAuthorityService {
             ca_certificate,
             ca_subject,
             default_validity_days,
+            receipt_keys: RwLock::new(CaKeyRing::generate()),
         }
     }
 
+    /// Replace the generated, in-memory receipt key ring, e.g. with one loaded by
+    /// [`CaKeyRing::open`] so rotations and revocations persist across restarts
+    pub fn with_receipt_keys(mut self, keys: CaKeyRing) -> Self {
+        self.receipt_keys = RwLock::new(keys);
+        self
+    }
+
+    /// `service_kid` of the key currently signing receipts
+    pub fn service_kid(&self) -> String {
+        self.read_keys().active_kid().to_string()
+    }
+
+    /// Rotate the receipt-signing key; returns the new `service_kid`.
+    ///
+    /// Receipts signed by the previous key keep verifying until it is revoked.
+    pub fn rotate_key(&self) -> CAResult<String> {
+        let mut keys = self.write_keys();
+        let kid = keys.rotate_key()?.to_string();
+        tracing::info!("CA receipt key rotated, new service_kid={}", kid);
+        Ok(kid)
+    }
+
+    /// Revoke a retired receipt-signing key so its receipts are rejected
+    pub fn revoke_key(&self, kid: &str) -> CAResult<()> {
+        self.write_keys().revoke_key(kid)?;
+        tracing::info!("CA receipt key revoked: {}", kid);
+        Ok(())
+    }
+
+    /// Sign `receipt` with the active key, setting its `service_kid` to the signer
+    pub fn sign_receipt(&self, mut receipt: VerificationReceipt) -> CAResult<SignedReceipt> {
+        let keys = self.read_keys();
+        receipt.service_kid = keys.active_kid().to_string();
+        let (_, signature) = keys.sign(&receipt_bytes(&receipt)?);
+        Ok(SignedReceipt {
+            receipt,
+            signature: BASE64.encode(signature),
+        })
+    }
+
+    /// Verify a signed receipt against the key named by its `service_kid`.
+    ///
+    /// Receipts from any non-revoked key version are accepted.
+    pub fn verify_receipt(&self, signed: &SignedReceipt) -> CAResult<()> {
+        let signature = BASE64
+            .decode(&signed.signature)
+            .map_err(|_| CAError::InvalidSignature("signature is not base64".to_string()))?;
+        self.read_keys().verify(
+            &signed.receipt.service_kid,
+            &receipt_bytes(&signed.receipt)?,
+            &signature,
+        )
+    }
+
     /// Issue a new certificate
     pub async fn issue_certificate(
         &self,
@@ -218,6 +277,18 @@ impl CertificateAuthorityService {
 
     // Private helper methods
 
+    fn read_keys(&self) -> std::sync::RwLockReadGuard<'_, CaKeyRing> {
+        self.receipt_keys
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write_keys(&self) -> std::sync::RwLockWriteGuard<'_, CaKeyRing> {
+        self.receipt_keys
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
     fn generate_serial_number(&self) -> CAResult<String> {
         let uuid = Uuid::new_v4();
         Ok(uuid.to_string().replace('-', ""))
@@ -375,6 +446,14 @@ impl CertificateAuthorityService {
     }
 }
 
+/// Bytes a receipt signature covers: the receipt's canonical JSON, so third
+/// parties can check it with `VerificationReceipt::verify`
+fn receipt_bytes(receipt: &VerificationReceipt) -> CAResult<Vec<u8>> {
+    receipt
+        .canonical_bytes()
+        .map_err(|e| CAError::Internal(format!("Failed to serialize receipt: {}", e)))
+}
+
 /// Helper function to create a CA service with YubiKey backend.
 /// Requires the `yubikey` feature.
 #[cfg(feature = "yubikey")]
@@ -428,3 +507,95 @@ pub async fn create_yubikey_ca_service(
 
     Ok(ca_service)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sealedge_core::backends::{software_hsm::SoftwareHsmConfig, SoftwareHsmBackend};
+
+    fn test_service(dir: &std::path::Path) -> CertificateAuthorityService {
+        let config = SoftwareHsmConfig::builder()
+            .key_store_path(dir.to_path_buf())
+            .build();
+        CertificateAuthorityService::new(
+            Arc::new(SoftwareHsmBackend::with_config(config).unwrap()),
+            "ca-key".to_string(),
+            String::new(),
+            CertificateSubject {
+                common_name: "Test CA".to_string(),
+                organization: None,
+                organizational_unit: None,
+                country: None,
+                state: None,
+                locality: None,
+                email: None,
+            },
+            365,
+        )
+    }
+
+    fn test_receipt() -> VerificationReceipt {
+        VerificationReceipt {
+            verification_id: "v_test".to_string(),
+            profile: "cam.video".to_string(),
+            device_id: "device-1".to_string(),
+            manifest_digest: "b3:00".to_string(),
+            segments: 2,
+            duration_s: 4.0,
+            signature: "pass".to_string(),
+            continuity: "pass".to_string(),
+            issued_at: "2025-01-01T00:00:00Z".to_string(),
+            service_kid: String::new(),
+            chain_tip: "b3:11".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_receipt_signed_before_rotation_verifies_after() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = test_service(dir.path());
+
+        let old_kid = service.service_kid();
+        let signed = service.sign_receipt(test_receipt()).unwrap();
+        assert_eq!(signed.receipt.service_kid, old_kid);
+
+        let new_kid = service.rotate_key().unwrap();
+        assert_ne!(new_kid, old_kid);
+        service.verify_receipt(&signed).unwrap();
+
+        let resigned = service.sign_receipt(test_receipt()).unwrap();
+        assert_eq!(resigned.receipt.service_kid, new_kid);
+        service.verify_receipt(&resigned).unwrap();
+    }
+
+    #[test]
+    fn test_revoked_key_receipts_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = test_service(dir.path());
+
+        let signed = service.sign_receipt(test_receipt()).unwrap();
+        service.rotate_key().unwrap();
+        service.revoke_key(&signed.receipt.service_kid).unwrap();
+
+        assert!(matches!(
+            service.verify_receipt(&signed),
+            Err(CAError::KeyRevoked(_))
+        ));
+        // Receipts from the replacement key are unaffected
+        let fresh = service.sign_receipt(test_receipt()).unwrap();
+        service.verify_receipt(&fresh).unwrap();
+    }
+
+    #[test]
+    fn test_tampered_receipt_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = test_service(dir.path());
+
+        let mut signed = service.sign_receipt(test_receipt()).unwrap();
+        signed.receipt.segments = 3;
+        assert!(matches!(
+            service.verify_receipt(&signed),
+            Err(CAError::InvalidSignature(_))
+        ));
+    }
+}
@@ -8,8 +8,9 @@
 
 //! PostgreSQL CRUD operations for organizations, devices, verifications, and receipts.
 
-use anyhow::Result;
-use sqlx::{PgPool, Row};
+use anyhow::{Context, Result};
+use sealedge_types::receipt::VerificationReceipt;
+use sqlx::{postgres::PgRow, PgPool, Row};
 use uuid::Uuid;
 
 pub async fn create_connection_pool(database_url: &str) -> Result<PgPool> {
@@ -131,3 +132,101 @@ pub async fn get_receipt(
     .await?;
     Ok(row.map(|r| (r.get("jws"), r.get("kid"))))
 }
+
+/// Store an issued receipt under `org_id`. Fails if `verification_id` already exists.
+pub async fn store_receipt(
+    pool: &PgPool,
+    org_id: Uuid,
+    receipt: &VerificationReceipt,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO verification_receipts (
+            verification_id, org_id, profile, device_id, manifest_digest, segments,
+            duration_s, signature, continuity, issued_at, service_kid, chain_tip
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        "#,
+    )
+    .bind(&receipt.verification_id)
+    .bind(org_id)
+    .bind(&receipt.profile)
+    .bind(&receipt.device_id)
+    .bind(&receipt.manifest_digest)
+    .bind(i64::from(receipt.segments))
+    .bind(receipt.duration_s)
+    .bind(&receipt.signature)
+    .bind(&receipt.continuity)
+    .bind(&receipt.issued_at)
+    .bind(&receipt.service_kid)
+    .bind(&receipt.chain_tip)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Look up a stored receipt by its verification ID within `org_id`.
+pub async fn get_receipt_by_id(
+    pool: &PgPool,
+    org_id: Uuid,
+    verification_id: &str,
+) -> Result<Option<VerificationReceipt>> {
+    let row = sqlx::query(
+        r#"
+        SELECT verification_id, profile, device_id, manifest_digest, segments, duration_s,
+               signature, continuity, issued_at, service_kid, chain_tip
+        FROM verification_receipts
+        WHERE verification_id = $1 AND org_id = $2
+        "#,
+    )
+    .bind(verification_id)
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await?;
+    row.map(|r| receipt_from_row(&r)).transpose()
+}
+
+/// List a device's receipts within `org_id`, newest first.
+///
+/// Returns at most `limit` receipts after skipping the first `offset`.
+pub async fn list_receipts_by_device(
+    pool: &PgPool,
+    org_id: Uuid,
+    device_id: &str,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<VerificationReceipt>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT verification_id, profile, device_id, manifest_digest, segments, duration_s,
+               signature, continuity, issued_at, service_kid, chain_tip
+        FROM verification_receipts
+        WHERE org_id = $1 AND device_id = $2
+        ORDER BY stored_at DESC, verification_id DESC
+        LIMIT $3 OFFSET $4
+        "#,
+    )
+    .bind(org_id)
+    .bind(device_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await?;
+    rows.iter().map(receipt_from_row).collect()
+}
+
+fn receipt_from_row(row: &PgRow) -> Result<VerificationReceipt> {
+    let segments: i64 = row.get("segments");
+    Ok(VerificationReceipt {
+        verification_id: row.get("verification_id"),
+        profile: row.get("profile"),
+        device_id: row.get("device_id"),
+        manifest_digest: row.get("manifest_digest"),
+        segments: u32::try_from(segments).context("stored segment count out of range")?,
+        duration_s: row.get("duration_s"),
+        signature: row.get("signature"),
+        continuity: row.get("continuity"),
+        issued_at: row.get("issued_at"),
+        service_kid: row.get("service_kid"),
+        chain_tip: row.get("chain_tip"),
+    })
+}
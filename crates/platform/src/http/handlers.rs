@@ -12,15 +12,22 @@
 //! `crate::verify::engine::verify_to_report()` directly instead of forwarding
 //! to a separate verify-core service via HTTP.
 
-use axum::{extract::State, http::StatusCode, response::Json};
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use chrono::Utc;
 use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
 use tracing::{info, warn};
 
 use crate::verify::{
-    engine::verify_to_report,
-    types::{HealthResponse, VerifyRequest, VerifyResponse},
+    audit::{request_digest, result_label, AuditCheckpoint, AuditEntry},
+    engine::{verify_to_report, VerifyReport},
+    types::{BatchVerifyItem, HealthResponse, VerifyRequest, VerifyResponse},
     validation::{validate_verify_request_full, ValidationError},
 };
 
@@ -39,7 +46,9 @@ use crate::verify::signing::sign_receipt_jws;
 
 use sealedge_core::{point_attestation::FORMAT_V1, PointAttestation};
 
+use super::rate_limit::RateLimitCharge;
 use super::state::AppState;
+use super::webhook::spawn_delivery;
 
 // ---------------------------------------------------------------------------
 // Always-available handlers (no postgres required)
@@ -61,10 +70,111 @@ pub async fn health_handler() -> Json<HealthResponse> {
     })
 }
 
+/// GET /metrics — verification counters in the Prometheus text format.
+pub async fn metrics_handler() -> impl axum::response::IntoResponse {
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            crate::metrics::METRICS_CONTENT_TYPE,
+        )],
+        crate::metrics::metrics().render(),
+    )
+}
+
+/// Most entries returned by one GET /v1/audit page.
+pub const MAX_AUDIT_PAGE: usize = 500;
+
+/// Query parameters for GET /v1/audit.
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct AuditQuery {
+    /// First sequence number to return (default: the oldest retained entry)
+    pub from: Option<u64>,
+    /// Page size, capped at `MAX_AUDIT_PAGE`
+    pub limit: Option<usize>,
+    /// `next_sequence` from an earlier response; with `head`, the log must still
+    /// reach that checkpoint, which detects entries dropped from the end
+    pub next_sequence: Option<u64>,
+    /// `head` from the same earlier response as `next_sequence`
+    pub head: Option<String>,
+}
+
+/// Response from GET /v1/audit.
+#[derive(Debug, serde::Serialize)]
+pub struct AuditResponse {
+    /// Hash of the latest entry; pass it back with `next_sequence` to detect
+    /// truncation
+    pub head: String,
+    /// Sequence number of the oldest entry still retained
+    pub first_sequence: u64,
+    /// Sequence number the next entry will get
+    pub next_sequence: u64,
+    /// Whether the retained chain verified
+    pub intact: bool,
+    /// Why the chain did not verify
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub entries: Vec<AuditEntry>,
+}
+
+/// GET /v1/audit — page through the audit log and check its hash chain.
+///
+/// Rehashing the retained chain is CPU-bound, so it runs on the blocking pool.
+pub async fn audit_handler(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<AuditQuery>,
+) -> Result<Json<AuditResponse>, (StatusCode, Json<ValidationError>)> {
+    let limit = query.limit.unwrap_or(MAX_AUDIT_PAGE).min(MAX_AUDIT_PAGE);
+    let checkpoint = match (query.next_sequence, query.head) {
+        (Some(next_sequence), Some(head)) => Some(AuditCheckpoint {
+            next_sequence,
+            head,
+        }),
+        (None, None) => None,
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ValidationError::new(
+                    "invalid_checkpoint",
+                    "next_sequence and head must be given together",
+                )),
+            ))
+        }
+    };
+    let audit = state.audit.clone();
+    tokio::task::spawn_blocking(move || {
+        let log = audit
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let integrity = log.verify_log_integrity(checkpoint.as_ref());
+        AuditResponse {
+            head: log.head(),
+            first_sequence: log.first_sequence(),
+            next_sequence: log.next_sequence(),
+            intact: integrity.is_ok(),
+            error: integrity.err().map(|e| e.to_string()),
+            entries: log.page(query.from.unwrap_or(0), limit),
+        }
+    })
+    .await
+    .map(Json)
+    .map_err(|e| {
+        warn!("Audit log task failed: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ValidationError::new(
+                "internal_error",
+                "Audit log task failed",
+            )),
+        )
+    })
+}
+
 /// POST /v1/verify — inline verification (stateless, no DB storage).
 ///
 /// Validates the request, calls `verify_to_report()` directly, and optionally
-/// signs a JWS receipt. This handler does not require the `postgres` feature.
+/// signs a JWS receipt. When `callback_url` is set, the response is also
+/// delivered to it as a signed webhook (see `super::webhook`). This handler does
+/// not require the `postgres` feature.
 ///
 /// When the `postgres` feature is enabled, use `verify_handler` instead for
 /// full multi-tenant operation with DB audit trail.
@@ -73,14 +183,31 @@ pub async fn verify_handler(
     State(state): State<AppState>,
     Json(request): Json<VerifyRequest>,
 ) -> Result<Json<VerifyResponse>, (StatusCode, Json<ValidationError>)> {
+    verify_request(&state, request).await.map(Json)
+}
+
+/// Verify one request; shared by `verify_handler` and `verify_batch_handler`.
+#[cfg(not(feature = "postgres"))]
+async fn verify_request(
+    state: &AppState,
+    request: VerifyRequest,
+) -> Result<VerifyResponse, (StatusCode, Json<ValidationError>)> {
     info!(
         "Processing verification request for device: {}",
         request.device_pub
     );
 
     validate_verify_request_full(&request).map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))?;
+    if let Some(ref url) = request.callback_url {
+        state
+            .webhooks
+            .validate(url)
+            .map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))?;
+    }
 
-    let report = match verify_to_report(&request.manifest, &request.segments, &request.device_pub) {
+    let report = verify_to_report(&request.manifest, &request.segments, &request.device_pub);
+    record_audit(state, &request, report.as_ref().ok()).await?;
+    let report = match report {
         Ok(report) => report,
         Err(e) => {
             warn!("Verification failed: {}", e);
@@ -107,11 +234,16 @@ pub async fn verify_handler(
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(e)))?;
 
-    Ok(Json(VerifyResponse {
+    let response = VerifyResponse {
         verification_id,
         result: report,
         receipt,
-    }))
+    };
+    if let Some(url) = request.callback_url {
+        spawn_delivery(state, url, &response).await;
+    }
+
+    Ok(response)
 }
 
 // ---------------------------------------------------------------------------
@@ -274,6 +406,108 @@ pub async fn verify_attestation_handler(
     }
 }
 
+// ---------------------------------------------------------------------------
+// Batch verification (no feature gate — delegates to the active verify_request)
+// ---------------------------------------------------------------------------
+
+/// Maximum number of requests accepted in one batch.
+pub const MAX_BATCH_SIZE: usize = 32;
+
+/// Maximum number of batch items verified at the same time.
+const BATCH_WORKERS: usize = 4;
+
+/// POST /v1/verify/batch — verify several archives in one request.
+///
+/// Each item is processed exactly as POST /v1/verify would process it, on up to
+/// `BATCH_WORKERS` concurrent tasks. The response lists one `BatchVerifyItem` per
+/// request, in request order; a failing item carries its own status and error
+/// instead of failing the batch. Behind the rate limiter, every item costs a token.
+pub async fn verify_batch_handler(
+    State(state): State<AppState>,
+    #[cfg(feature = "postgres")] org_ctx: Option<
+        axum::extract::Extension<crate::http::auth::OrgContext>,
+    >,
+    charge: Option<axum::extract::Extension<RateLimitCharge>>,
+    Json(requests): Json<Vec<VerifyRequest>>,
+) -> Result<Json<Vec<BatchVerifyItem>>, Response> {
+    if requests.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ValidationError::new(
+                "invalid_batch",
+                "batch cannot be empty",
+            )),
+        )
+            .into_response());
+    }
+    if requests.len() > MAX_BATCH_SIZE {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ValidationError::new(
+                "batch_too_large",
+                &format!("batch cannot exceed {} requests", MAX_BATCH_SIZE),
+            )),
+        )
+            .into_response());
+    }
+    // The rate limiter charged one token for the request; each further item
+    // costs a verification of its own
+    if let Some(axum::extract::Extension(charge)) = &charge {
+        charge
+            .charge(requests.len() as u32 - 1)
+            .map_err(IntoResponse::into_response)?;
+    }
+
+    info!(
+        "Processing verification batch of {} requests",
+        requests.len()
+    );
+
+    let workers = Arc::new(Semaphore::new(BATCH_WORKERS));
+    let tasks: Vec<_> = requests
+        .into_iter()
+        .map(|request| {
+            let state = state.clone();
+            let workers = workers.clone();
+            #[cfg(feature = "postgres")]
+            let org_ctx = org_ctx.as_ref().map(|e| e.0.clone());
+            tokio::spawn(async move {
+                let Ok(_permit) = workers.acquire_owned().await else {
+                    return Err((
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ValidationError::new(
+                            "internal_error",
+                            "Verification task failed",
+                        )),
+                    ));
+                };
+                #[cfg(feature = "postgres")]
+                let result = verify_request(&state, org_ctx, request).await;
+                #[cfg(not(feature = "postgres"))]
+                let result = verify_request(&state, request).await;
+                result
+            })
+        })
+        .collect();
+
+    let mut items = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        items.push(match task.await {
+            Ok(Ok(response)) => BatchVerifyItem::verified(response),
+            Ok(Err((status, Json(error)))) => BatchVerifyItem::failed(status.as_u16(), error),
+            Err(e) => {
+                warn!("Batch verification task failed: {}", e);
+                BatchVerifyItem::failed(
+                    StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+                    ValidationError::new("internal_error", "Verification task failed"),
+                )
+            }
+        });
+    }
+
+    Ok(Json(items))
+}
+
 // ---------------------------------------------------------------------------
 // Postgres-gated handlers (full multi-tenant with DB audit trail)
 // ---------------------------------------------------------------------------
@@ -288,6 +522,18 @@ pub async fn verify_handler(
     org_ctx: Option<axum::extract::Extension<crate::http::auth::OrgContext>>,
     Json(request): Json<VerifyRequest>,
 ) -> Result<Json<VerifyResponse>, (StatusCode, Json<ValidationError>)> {
+    verify_request(&state, org_ctx.map(|e| e.0), request)
+        .await
+        .map(Json)
+}
+
+/// Verify one request; shared by `verify_handler` and `verify_batch_handler`.
+#[cfg(feature = "postgres")]
+async fn verify_request(
+    state: &AppState,
+    org_ctx: Option<crate::http::auth::OrgContext>,
+    request: VerifyRequest,
+) -> Result<VerifyResponse, (StatusCode, Json<ValidationError>)> {
     info!(
         "Processing verification request for device: {}",
         request.device_pub
@@ -300,6 +546,12 @@ pub async fn verify_handler(
     }
 
     validate_verify_request_full(&request).map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))?;
+    if let Some(ref url) = request.callback_url {
+        state
+            .webhooks
+            .validate(url)
+            .map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))?;
+    }
 
     // Look up device record if device_id option was provided
     let device_id = if let Some(ref options) = request.options {
@@ -327,7 +579,9 @@ pub async fn verify_handler(
     };
 
     // Inline verification — direct call, no HTTP forwarding
-    let report = match verify_to_report(&request.manifest, &request.segments, &request.device_pub) {
+    let report = verify_to_report(&request.manifest, &request.segments, &request.device_pub);
+    record_audit(state, &request, report.as_ref().ok()).await?;
+    let report = match report {
         Ok(report) => report,
         Err(e) => {
             warn!("Verification failed: {}", e);
@@ -453,11 +707,16 @@ pub async fn verify_handler(
         .map(|rid| format!("{}/{}", verification_id, rid))
         .unwrap_or(verification_id);
 
-    Ok(Json(VerifyResponse {
+    let response = VerifyResponse {
         verification_id: response_id,
         result: report,
         receipt,
-    }))
+    };
+    if let Some(url) = request.callback_url {
+        spawn_delivery(state, url, &response).await;
+    }
+
+    Ok(response)
 }
 
 /// POST /v1/devices — register a device for an organization.
@@ -553,6 +812,8 @@ pub fn create_test_app(pool: sqlx::PgPool) -> axum::Router {
         db_pool: pool,
         keys,
         receipt_ttl_secs: 3600,
+        audit: Default::default(),
+        webhooks: Default::default(),
     };
 
     // Delegate to create_router so middleware stack is identical to production
@@ -564,6 +825,53 @@ pub fn create_test_app(pool: sqlx::PgPool) -> axum::Router {
 // Private helpers
 // ---------------------------------------------------------------------------
 
+/// Append the outcome of one `verify_to_report` call to the audit log.
+///
+/// The append writes to the log file, so it runs on the blocking pool rather
+/// than holding the lock on an async worker. A verification whose audit entry
+/// could not be persisted is not answered.
+async fn record_audit(
+    state: &AppState,
+    request: &VerifyRequest,
+    report: Option<&VerifyReport>,
+) -> Result<(), (StatusCode, Json<ValidationError>)> {
+    // Hash the request before taking the lock so it is held only for the append
+    let digest = request_digest(&request.manifest, &request.segments, &request.device_pub);
+    let result = result_label(report);
+    let audit = state.audit.clone();
+    let appended = tokio::task::spawn_blocking(move || {
+        audit
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .append(&digest, result, &Utc::now().to_rfc3339())
+            .map(|_| ())
+    })
+    .await;
+    match appended {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(e)) => {
+            tracing::error!("Failed to persist audit entry: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ValidationError::new(
+                    "audit_error",
+                    "Failed to record verification in the audit log",
+                )),
+            ))
+        }
+        Err(e) => {
+            warn!("Audit log task failed: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ValidationError::new(
+                    "internal_error",
+                    "Audit log task failed",
+                )),
+            ))
+        }
+    }
+}
+
 /// Compute BLAKE3 manifest digest (for receipt construction).
 pub(crate) fn compute_manifest_digest_blake3(manifest: &Value) -> String {
     let canonical = serde_json::to_string(manifest).unwrap_or_default();
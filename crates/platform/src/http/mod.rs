@@ -13,6 +13,7 @@
 //! - Auth middleware for Bearer token validation
 //! - Handlers: verify, register_device, get_receipt, jwks, health
 //! - AppState and Config for service wiring
+//! - Signed webhook delivery of verification results
 
 pub mod auth;
 pub mod config;
@@ -21,6 +22,7 @@ pub mod rate_limit;
 pub mod router;
 pub mod state;
 pub mod static_files;
+pub mod webhook;
 
 pub use config::Config;
 pub use router::{build_base_router, create_router};
@@ -23,28 +23,61 @@
 //!
 //! When `TRUSTED_PROXIES` is unset or empty, the peer IP is always used
 //! (preserves prior behavior).
+//!
+//! ## Token buckets
+//!
+//! Each client gets a token bucket refilled at `RATE_LIMIT_RPS` tokens per
+//! second and holding at most `RATE_LIMIT_BURST` tokens (default: the RPS).
+//!
+//! ## API key buckets
+//!
+//! When `RATE_LIMIT_KEY_HEADER` names a header (e.g. `x-api-key`), requests
+//! carrying it are bucketed by the header value instead of the client IP;
+//! requests without it fall back to the IP bucket. The value is not
+//! authenticated here, so only enable this behind a gateway that rejects
+//! unknown keys — otherwise a client can rotate values to get fresh buckets.
+//!
+//! ## Per-item charges
+//!
+//! The middleware charges one token per request. Handlers whose cost depends on
+//! the body (`/v1/verify/batch`) take the [`RateLimitCharge`] it leaves in the
+//! request extensions and charge the rest once the body is parsed.
 
 use axum::{
     body::Body,
     extract::{ConnectInfo, State},
-    http::{HeaderMap, Request},
+    http::{HeaderMap, HeaderName, Request},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
+    Json,
 };
-use governor::{DefaultKeyedRateLimiter, Quota, RateLimiter};
+use governor::{clock::Clock, DefaultKeyedRateLimiter, Quota, RateLimiter};
 use std::{
     net::{IpAddr, SocketAddr},
     num::NonZeroU32,
     sync::Arc,
 };
 
-/// Shared rate-limiter state keyed by client IP address.
+/// Identity a token bucket is kept for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    /// Effective client IP (see [`extract_client_ip`])
+    Ip(IpAddr),
+    /// Value of the configured API key header
+    ApiKey(String),
+}
+
+/// Shared rate-limiter state keyed by client IP address or API key.
 #[derive(Clone)]
 pub struct RateLimitState {
-    pub limiter: Arc<DefaultKeyedRateLimiter<IpAddr>>,
+    pub limiter: Arc<DefaultKeyedRateLimiter<RateLimitKey>>,
     /// CIDR blocks for trusted reverse proxies.
     /// When non-empty, X-Forwarded-For is trusted when the peer is in this list.
     pub trusted_proxies: Vec<ipnet::IpNet>,
+    /// Header whose value selects the bucket instead of the client IP.
+    pub key_header: Option<HeaderName>,
+    /// Most tokens a bucket holds.
+    pub burst: u32,
 }
 
 impl RateLimitState {
@@ -56,15 +89,112 @@ impl RateLimitState {
     /// `trusted_proxies` is the list of CIDR blocks for trusted reverse proxies.
     /// Pass an empty `Vec` to preserve the prior behavior (peer IP only).
     pub fn new(rps: u32, trusted_proxies: Vec<ipnet::IpNet>) -> Self {
-        let quota = Quota::per_second(NonZeroU32::new(rps).expect("rps must be greater than zero"));
+        Self::with_burst(rps, rps, trusted_proxies)
+    }
+
+    /// Create a rate limiter refilling `rps` tokens per second into buckets
+    /// holding at most `burst` tokens.
+    pub fn with_burst(rps: u32, burst: u32, trusted_proxies: Vec<ipnet::IpNet>) -> Self {
+        let quota = Quota::per_second(NonZeroU32::new(rps).expect("rps must be greater than zero"))
+            .allow_burst(NonZeroU32::new(burst).expect("burst must be greater than zero"));
         let limiter = Arc::new(RateLimiter::keyed(quota));
         Self {
             limiter,
             trusted_proxies,
+            key_header: None,
+            burst,
+        }
+    }
+
+    /// Bucket requests carrying `header` by its value rather than by client IP.
+    pub fn with_key_header(mut self, header: HeaderName) -> Self {
+        self.key_header = Some(header);
+        self
+    }
+
+    /// Bucket a request: the API key header value if configured and present,
+    /// otherwise the effective client IP.
+    fn key_for(&self, peer_ip: IpAddr, headers: &HeaderMap) -> RateLimitKey {
+        self.key_header
+            .as_ref()
+            .and_then(|name| headers.get(name))
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| !value.is_empty())
+            .map(|value| RateLimitKey::ApiKey(value.to_string()))
+            .unwrap_or_else(|| {
+                RateLimitKey::Ip(extract_client_ip(peer_ip, headers, &self.trusted_proxies))
+            })
+    }
+}
+
+/// The bucket a request was charged to, left in the request extensions by
+/// [`rate_limit_middleware`] for handlers that cost more than one token.
+#[derive(Clone)]
+pub struct RateLimitCharge {
+    state: RateLimitState,
+    key: RateLimitKey,
+}
+
+impl RateLimitCharge {
+    /// Charge `extra` more tokens to the request's bucket, all or nothing.
+    ///
+    /// Together with the token the middleware already took, the request must fit
+    /// in one full bucket; otherwise it could never be admitted.
+    pub fn charge(&self, extra: u32) -> Result<(), ChargeError> {
+        let Some(n) = NonZeroU32::new(extra) else {
+            return Ok(());
+        };
+        if extra >= self.state.burst {
+            return Err(ChargeError::ExceedsBurst(extra));
+        }
+        match self.state.limiter.check_key_n(&self.key, n) {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(not_until)) => Err(ChargeError::Limited(
+                not_until.wait_time_from(self.state.limiter.clock().now()),
+            )),
+            Err(_) => Err(ChargeError::ExceedsBurst(extra)),
+        }
+    }
+}
+
+/// Why a [`RateLimitCharge`] was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChargeError {
+    /// The bucket is short; retry after this long (`429` with `Retry-After`)
+    Limited(std::time::Duration),
+    /// More tokens than the bucket can ever hold (`400`)
+    ExceedsBurst(u32),
+}
+
+impl IntoResponse for ChargeError {
+    fn into_response(self) -> Response {
+        match self {
+            ChargeError::Limited(wait) => too_many_requests(wait),
+            ChargeError::ExceedsBurst(extra) => (
+                axum::http::StatusCode::BAD_REQUEST,
+                Json(crate::verify::validation::ValidationError::new(
+                    "rate_limit_exceeded",
+                    &format!(
+                        "request costs {} more tokens than the rate limit burst allows",
+                        extra
+                    ),
+                )),
+            )
+                .into_response(),
         }
     }
 }
 
+/// `429 Too Many Requests` with a `Retry-After` of whole seconds, at least 1
+fn too_many_requests(wait: std::time::Duration) -> Response {
+    let retry_after = (wait.as_secs() + u64::from(wait.subsec_nanos() > 0)).max(1);
+    Response::builder()
+        .status(429)
+        .header("retry-after", retry_after)
+        .body(Body::empty())
+        .expect("429 response must always be buildable")
+}
+
 /// Determine the effective client IP, respecting trusted proxy headers.
 ///
 /// Rules (in priority order):
@@ -113,33 +243,34 @@ pub fn extract_client_ip(
     peer_ip
 }
 
-/// Axum middleware that enforces per-IP rate limiting.
+/// Axum middleware that enforces per-client rate limiting.
 ///
 /// If `ConnectInfo` is not available (e.g., in test environments using
 /// `tower::ServiceExt::oneshot`), the middleware falls back to `127.0.0.1`
 /// so tests can exercise rate limiting without a real TCP connection.
 ///
 /// When the rate limit is exceeded, returns `429 Too Many Requests` with a
-/// `Retry-After: 1` header (RFC 6585 §4).
+/// `Retry-After` header (RFC 6585 §4) giving the whole seconds until the next
+/// token is available, at least 1. Admitted requests carry a [`RateLimitCharge`]
+/// extension for any further charges.
 pub async fn rate_limit_middleware(
     connect_info: Option<ConnectInfo<SocketAddr>>,
     State(state): State<RateLimitState>,
-    req: Request<Body>,
+    mut req: Request<Body>,
     next: Next,
 ) -> Response {
     let peer_ip = connect_info
         .map(|ci| ci.0.ip())
         .unwrap_or(IpAddr::V4(std::net::Ipv4Addr::LOCALHOST));
 
-    let effective_ip = extract_client_ip(peer_ip, req.headers(), &state.trusted_proxies);
+    let key = state.key_for(peer_ip, req.headers());
 
-    match state.limiter.check_key(&effective_ip) {
-        Ok(_) => next.run(req).await,
-        Err(_) => Response::builder()
-            .status(429)
-            .header("retry-after", "1")
-            .body(Body::empty())
-            .expect("static 429 response must always be buildable"),
+    match state.limiter.check_key(&key) {
+        Ok(_) => {
+            req.extensions_mut().insert(RateLimitCharge { state, key });
+            next.run(req).await
+        }
+        Err(not_until) => too_many_requests(not_until.wait_time_from(state.limiter.clock().now())),
     }
 }
 
@@ -227,4 +358,109 @@ mod tests {
         assert_eq!(state.trusted_proxies.len(), 1);
         assert_eq!(state.trusted_proxies[0], proxies[0]);
     }
+
+    // -------------------------------------------------------------------
+    // Token bucket behavior through the middleware
+    // -------------------------------------------------------------------
+
+    fn limited_app(state: RateLimitState) -> axum::Router {
+        axum::Router::new()
+            .route("/", axum::routing::get(|| async { "ok" }))
+            .route_layer(axum::middleware::from_fn_with_state(
+                state,
+                rate_limit_middleware,
+            ))
+    }
+
+    async fn send(app: &axum::Router, api_key: Option<&str>) -> Response {
+        use tower::ServiceExt;
+
+        let mut req = Request::builder().uri("/");
+        if let Some(key) = api_key {
+            req = req.header("x-api-key", key);
+        }
+        app.clone()
+            .oneshot(req.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    /// A burst up to the bucket size succeeds; the next request gets 429.
+    #[tokio::test]
+    async fn test_burst_within_limit_succeeds_then_429() {
+        let app = limited_app(RateLimitState::with_burst(1, 3, vec![]));
+        for _ in 0..3 {
+            assert_eq!(send(&app, None).await.status(), 200);
+        }
+        let over = send(&app, None).await;
+        assert_eq!(over.status(), 429);
+        assert_eq!(over.headers()["retry-after"], "1");
+    }
+
+    /// Tokens come back at the configured rate after the bucket is drained.
+    #[tokio::test]
+    async fn test_bucket_refills_over_time() {
+        // One token every 50 ms
+        let app = limited_app(RateLimitState::with_burst(20, 2, vec![]));
+        assert_eq!(send(&app, None).await.status(), 200);
+        assert_eq!(send(&app, None).await.status(), 200);
+        assert_eq!(send(&app, None).await.status(), 429);
+
+        tokio::time::sleep(std::time::Duration::from_millis(120)).await;
+        assert_eq!(send(&app, None).await.status(), 200);
+    }
+
+    /// With a key header configured, each key value gets its own bucket and
+    /// requests without the header share the IP bucket.
+    #[tokio::test]
+    async fn test_api_key_header_selects_bucket() {
+        let state = RateLimitState::with_burst(1, 1, vec![])
+            .with_key_header(HeaderName::from_static("x-api-key"));
+        let app = limited_app(state);
+
+        assert_eq!(send(&app, Some("key-a")).await.status(), 200);
+        assert_eq!(send(&app, Some("key-a")).await.status(), 429);
+        assert_eq!(send(&app, Some("key-b")).await.status(), 200);
+        assert_eq!(send(&app, None).await.status(), 200);
+        assert_eq!(send(&app, None).await.status(), 429);
+    }
+
+    /// Extra charges draw on the same bucket as the middleware's own token.
+    #[tokio::test]
+    async fn test_charge_draws_on_request_bucket() {
+        let app = axum::Router::new()
+            .route(
+                "/",
+                axum::routing::get(
+                    |axum::Extension(charge): axum::Extension<RateLimitCharge>| async move {
+                        match charge.charge(2) {
+                            Ok(()) => "ok".into_response(),
+                            Err(error) => error.into_response(),
+                        }
+                    },
+                ),
+            )
+            .route_layer(axum::middleware::from_fn_with_state(
+                RateLimitState::with_burst(1, 4, vec![]),
+                rate_limit_middleware,
+            ));
+
+        // 1 + 2 tokens fit in a bucket of 4; the next request gets its own
+        // token but not the 2 extra
+        assert_eq!(send(&app, None).await.status(), 200);
+        let over = send(&app, None).await;
+        assert_eq!(over.status(), 429);
+        assert!(over.headers().contains_key("retry-after"));
+
+        // A charge no bucket can ever hold is a client error, not a retry
+        let state = RateLimitState::with_burst(1, 4, vec![]);
+        let charge = RateLimitCharge {
+            state,
+            key: RateLimitKey::Ip(peer("10.0.0.1")),
+        };
+        assert_eq!(charge.charge(4), Err(ChargeError::ExceedsBurst(4)));
+        assert_eq!(charge.charge(4).unwrap_err().into_response().status(), 400);
+        assert!(charge.charge(3).is_ok());
+        assert!(charge.charge(0).is_ok());
+    }
 }
@@ -10,11 +10,15 @@
 //!
 //! Routes:
 //!   POST  /v1/verify              — verify archive (always available)
+//!   POST  /v1/verify/batch        — verify up to 32 archives concurrently (always available)
 //!   POST  /v1/verify-attestation  — verify point attestation (always available)
+//!   GET   /v1/audit               — page through and check the verification audit log
+//!                                     (auth-protected with postgres)
 //!   POST  /v1/devices             — register device (postgres only)
 //!   GET   /v1/receipts/:id        — get receipt (postgres only)
 //!   GET   /.well-known/jwks.json  — local JWKS (no proxy)
 //!   GET   /healthz                — health check
+//!   GET   /metrics                — Prometheus verification metrics
 //!   GET   /verify                 — self-contained attestation verifier HTML page
 
 use axum::{
@@ -24,7 +28,10 @@ use axum::{
 use tower_http::{cors::CorsLayer, limit::RequestBodyLimitLayer, trace::TraceLayer};
 
 use super::{
-    handlers::{health_handler, jwks_handler, verify_attestation_handler, verify_handler},
+    handlers::{
+        audit_handler, health_handler, jwks_handler, metrics_handler, verify_attestation_handler,
+        verify_batch_handler, verify_handler,
+    },
     rate_limit::{rate_limit_middleware, RateLimitState},
     state::AppState,
     static_files::verify_page_handler,
@@ -33,7 +40,7 @@ use super::{
 /// Build the base router with routes shared across all feature configurations.
 ///
 /// The `/v1/verify` route is NOT included here — it is added in `create_router`
-/// with rate limiting applied. All other routes (healthz, jwks) are public and
+/// with rate limiting applied. All other routes (healthz, metrics, jwks) are public and
 /// unthrottled.
 ///
 /// Both `create_router` and `create_test_app` ultimately call this function,
@@ -42,6 +49,7 @@ pub fn build_base_router() -> Router<AppState> {
     Router::new()
         .route("/.well-known/jwks.json", get(jwks_handler))
         .route("/healthz", get(health_handler))
+        .route("/metrics", get(metrics_handler))
         .route("/verify", get(verify_page_handler))
 }
 
@@ -49,11 +57,14 @@ pub fn build_base_router() -> Router<AppState> {
 ///
 /// Applies:
 /// - `RequestBodyLimitLayer` (2 MB) on all routes to prevent body-flood DoS.
-/// - Per-IP rate limiting on `/v1/verify` only (configurable via `RATE_LIMIT_RPS`,
-///   default 10 req/sec) to protect the CPU-intensive verify endpoint.
+/// - Per-IP token-bucket rate limiting on the verify routes (configurable via
+///   `RATE_LIMIT_RPS`, default 10 req/sec, and `RATE_LIMIT_BURST`, default the
+///   RPS; `RATE_LIMIT_KEY_HEADER` buckets by an API key header instead) to protect
+///   the CPU-intensive verify endpoints.
 ///
 /// When the `postgres` feature is enabled, the router includes device and
-/// receipt endpoints protected by the Bearer token auth middleware.
+/// receipt endpoints, and puts the audit log, behind the Bearer token auth
+/// middleware.
 pub fn create_router(state: AppState) -> Router {
     // Read rate limit RPS from environment, default to 10.
     let rps = std::env::var("RATE_LIMIT_RPS")
@@ -67,12 +78,35 @@ pub fn create_router(state: AppState) -> Router {
         .filter(|s| !s.is_empty())
         .filter_map(|s| s.parse::<ipnet::IpNet>().ok())
         .collect();
-    let rl_state = RateLimitState::new(rps, trusted_proxies);
+    // Bucket capacity, defaulting to one second's worth of requests.
+    let burst = std::env::var("RATE_LIMIT_BURST")
+        .ok()
+        .and_then(|s| s.parse::<u32>().ok())
+        .filter(|&b| b > 0)
+        .unwrap_or(rps);
+    let mut rl_state = RateLimitState::with_burst(rps, burst, trusted_proxies);
+    if let Some(header) = std::env::var("RATE_LIMIT_KEY_HEADER")
+        .ok()
+        .and_then(|s| s.trim().parse::<axum::http::HeaderName>().ok())
+    {
+        rl_state = rl_state.with_key_header(header);
+    }
 
-    // Rate-limited verify sub-router — /v1/verify and /v1/verify-attestation are throttled.
+    // Rate-limited verify sub-router — /v1/verify, /v1/verify/batch and
+    // /v1/verify-attestation are throttled.
     let verify_router = Router::new()
         .route("/v1/verify", post(verify_handler))
+        .route("/v1/verify/batch", post(verify_batch_handler))
         .route("/v1/verify-attestation", post(verify_attestation_handler))
+        .route_layer(axum::middleware::from_fn_with_state(
+            rl_state.clone(),
+            rate_limit_middleware,
+        ));
+
+    // /v1/audit rehashes the retained audit chain on every call, so it is
+    // throttled too. With `postgres` it sits behind auth like the other read APIs.
+    let audit_router = Router::new()
+        .route("/v1/audit", get(audit_handler))
         .route_layer(axum::middleware::from_fn_with_state(
             rl_state,
             rate_limit_middleware,
@@ -115,10 +149,11 @@ pub fn create_router(state: AppState) -> Router {
                 axum::http::header::ACCEPT,
             ]);
 
-        // Auth-protected routes (devices, receipts)
+        // Auth-protected routes (devices, receipts, audit log)
         let protected = Router::new()
             .route("/v1/devices", post(register_device_handler))
             .route("/v1/receipts/:id", get(get_receipt_handler))
+            .merge(audit_router)
             .layer(middleware::from_fn_with_state(
                 state.db_pool.clone(),
                 auth_middleware,
@@ -134,6 +169,7 @@ pub fn create_router(state: AppState) -> Router {
 
     #[cfg(not(feature = "postgres"))]
     let base = base
+        .merge(audit_router)
         .with_state(state)
         .layer(RequestBodyLimitLayer::new(2 * 1024 * 1024))
         // Same-origin only — no cross-origin requests allowed for verify-only builds
@@ -11,8 +11,9 @@
 //! The `verify_core_url` field from the original platform-api AppState has been
 //! removed: verification is now performed inline via direct function calls.
 
-use crate::verify::jwks::KeyManager;
-use std::sync::Arc;
+use super::webhook::WebhookDispatcher;
+use crate::verify::{audit::AuditLog, jwks::KeyManager};
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
 
 /// Shared application state for the HTTP layer.
@@ -26,4 +27,9 @@ pub struct AppState {
     pub db_pool: sqlx::PgPool,
     pub keys: Arc<RwLock<KeyManager>>,
     pub receipt_ttl_secs: u64,
+    /// Bounded audit log every verification is recorded in. Locked only on the
+    /// blocking pool, for the append or a page read, never while a verification runs.
+    pub audit: Arc<Mutex<AuditLog>>,
+    /// Concurrency limit and target policy for `callback_url` deliveries
+    pub webhooks: WebhookDispatcher,
 }
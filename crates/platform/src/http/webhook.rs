@@ -0,0 +1,396 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Webhook delivery of verification results.
+//!
+//! When a `VerifyRequest` carries a `callback_url`, the finished `VerifyResponse`
+//! is POSTed there as JSON from a background task, so the HTTP response is never
+//! held up by the receiver. Failed deliveries (transport errors or non-2xx
+//! statuses) are retried with exponential backoff.
+//!
+//! ## Authenticating deliveries
+//!
+//! Each delivery carries two headers:
+//! - `X-Sealedge-Signature`: base64 Ed25519 signature over the exact request body
+//! - `X-Sealedge-Key-Id`: `kid` of the signing key, published at
+//!   `/.well-known/jwks.json`
+//!
+//! Receivers look the key up in the JWKS and verify the signature before
+//! trusting the body.
+//!
+//! ## Restricting targets
+//!
+//! Callback URLs are supplied by whoever calls the API, so deliveries must not
+//! become a way to reach the service's own network. Unless the
+//! [`WebhookDispatcher`] was built with `allow_private_targets`, a callback is
+//! refused when its host is, or resolves to, a loopback, private (RFC 1918),
+//! shared (RFC 6598), link-local (including the 169.254.169.254 metadata
+//! endpoint), unique-local, multicast or unspecified address. The host is
+//! resolved once per delivery and the client is pinned to the checked
+//! addresses, and redirects are never followed, so neither DNS rebinding nor a
+//! `3xx` can send the request elsewhere. At most `max_in_flight` deliveries run
+//! at a time; further callbacks are dropped with a warning.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use sealedge_core::Signer;
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+
+use super::state::AppState;
+use crate::verify::{jwks::KeyManager, types::VerifyResponse, validation::ValidationError};
+
+/// Header carrying the base64 Ed25519 signature over the body
+pub const SIGNATURE_HEADER: &str = "x-sealedge-signature";
+/// Header carrying the `kid` of the key that signed the body
+pub const KEY_ID_HEADER: &str = "x-sealedge-key-id";
+
+/// Delivery attempts before a webhook is dropped
+const MAX_ATTEMPTS: u32 = 5;
+/// Wait before the first retry; doubled after every failed attempt
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+/// Per-attempt request timeout
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Deliveries allowed to run at once by a default dispatcher
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 64;
+
+/// Limits and target policy shared by every webhook delivery
+#[derive(Clone, Debug)]
+pub struct WebhookDispatcher {
+    slots: Arc<Semaphore>,
+    allow_private_targets: bool,
+}
+
+impl Default for WebhookDispatcher {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_IN_FLIGHT)
+    }
+}
+
+impl WebhookDispatcher {
+    /// Dispatcher running at most `max_in_flight` deliveries at once
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            slots: Arc::new(Semaphore::new(max_in_flight)),
+            allow_private_targets: false,
+        }
+    }
+
+    /// Also deliver to loopback and private addresses (local development and tests)
+    pub fn allow_private_targets(mut self) -> Self {
+        self.allow_private_targets = true;
+        self
+    }
+
+    /// Reject callback URLs that are malformed or name a forbidden address.
+    ///
+    /// Only IP literals and `localhost` can be judged here; hostnames are
+    /// checked again after resolution, when the webhook is delivered.
+    pub fn validate(&self, url: &str) -> Result<(), ValidationError> {
+        validate_callback_url(url)?;
+        if self.allow_private_targets {
+            return Ok(());
+        }
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_ascii_lowercase))
+            .unwrap_or_default();
+        let forbidden = match ip_literal(&host) {
+            Some(ip) => is_forbidden_address(ip),
+            None => {
+                let name = host.trim_end_matches('.');
+                name.is_empty() || name == "localhost" || name.ends_with(".localhost")
+            }
+        };
+        if forbidden {
+            return Err(ValidationError::new(
+                "invalid_callback_url",
+                "callback_url must not point at a loopback, private or link-local address",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The address in a URL host that is an IP literal (IPv6 in brackets)
+fn ip_literal(host: &str) -> Option<IpAddr> {
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .ok()
+}
+
+/// Whether a webhook may not be delivered to `ip`
+pub fn is_forbidden_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                // 0.0.0.0/8 "this network" and 100.64.0.0/10 shared address space
+                || a == 0
+                || (a == 100 && (b & 0xc0) == 64)
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_forbidden_address(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                // fc00::/7 unique local, fe80::/10 link local
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80
+        }
+    }
+}
+
+/// Reject callback URLs that are not absolute `http`/`https` URLs.
+pub fn validate_callback_url(url: &str) -> Result<(), ValidationError> {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") && parsed.has_host() => Ok(()),
+        _ => Err(ValidationError::new(
+            "invalid_callback_url",
+            "callback_url must be an absolute http or https URL",
+        )),
+    }
+}
+
+/// Sign `body` with the current service key; returns `(kid, base64 signature)`.
+pub fn sign_payload(keys: &KeyManager, body: &[u8]) -> (String, String) {
+    let signature = keys.current_signing_key().sign(body);
+    (keys.current_kid(), BASE64.encode(signature.to_bytes()))
+}
+
+/// Sign `response` and deliver it to `callback_url` in the background.
+pub async fn spawn_delivery(state: &AppState, callback_url: String, response: &VerifyResponse) {
+    let body = match serde_json::to_vec(response) {
+        Ok(body) => body,
+        Err(e) => {
+            warn!("Failed to serialize webhook payload: {}", e);
+            return;
+        }
+    };
+    let permit = match state.webhooks.slots.clone().try_acquire_owned() {
+        Ok(permit) => permit,
+        Err(_) => {
+            warn!(
+                "Too many webhook deliveries in flight; dropping callback to {}",
+                callback_url
+            );
+            return;
+        }
+    };
+    let (kid, signature) = sign_payload(&*state.keys.read().await, &body);
+    let allow_private_targets = state.webhooks.allow_private_targets;
+
+    tokio::spawn(async move {
+        let _permit = permit;
+        match webhook_client(&callback_url, allow_private_targets).await {
+            Ok(client) => deliver(client, callback_url, body, kid, signature).await,
+            Err(reason) => warn!("Not delivering webhook to {}: {}", callback_url, reason),
+        }
+    });
+}
+
+/// Resolve the callback host, refuse forbidden addresses, and build a client
+/// pinned to the checked addresses that does not follow redirects.
+async fn webhook_client(
+    callback_url: &str,
+    allow_private_targets: bool,
+) -> Result<reqwest::Client, String> {
+    let url = reqwest::Url::parse(callback_url).map_err(|e| e.to_string())?;
+    let host = url
+        .host_str()
+        .ok_or("callback URL has no host")?
+        .to_string();
+    let port = url
+        .port_or_known_default()
+        .ok_or("callback URL has no port")?;
+
+    let addrs: Vec<SocketAddr> = match ip_literal(&host) {
+        Some(ip) => vec![SocketAddr::new(ip, port)],
+        None => tokio::net::lookup_host((host.as_str(), port))
+            .await
+            .map_err(|e| format!("failed to resolve {}: {}", host, e))?
+            .collect(),
+    };
+    if addrs.is_empty() {
+        return Err(format!("{} did not resolve to any address", host));
+    }
+    if !allow_private_targets {
+        if let Some(addr) = addrs.iter().find(|a| is_forbidden_address(a.ip())) {
+            return Err(format!(
+                "{} resolves to forbidden address {}",
+                host,
+                addr.ip()
+            ));
+        }
+    }
+
+    let mut builder = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none());
+    if ip_literal(&host).is_none() {
+        builder = builder.resolve_to_addrs(&host, &addrs);
+    }
+    builder
+        .build()
+        .map_err(|e| format!("failed to build webhook client: {}", e))
+}
+
+async fn deliver(
+    client: reqwest::Client,
+    callback_url: String,
+    body: Vec<u8>,
+    kid: String,
+    signature: String,
+) {
+    let mut backoff = INITIAL_BACKOFF;
+    for attempt in 1..=MAX_ATTEMPTS {
+        let result = client
+            .post(&callback_url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, &signature)
+            .header(KEY_ID_HEADER, &kid)
+            .body(body.clone())
+            .send()
+            .await;
+
+        match result {
+            Ok(resp) if resp.status().is_success() => {
+                info!(
+                    "Webhook delivered to {} (attempt {})",
+                    callback_url, attempt
+                );
+                return;
+            }
+            Ok(resp) => warn!(
+                "Webhook to {} returned {} (attempt {}/{})",
+                callback_url,
+                resp.status(),
+                attempt,
+                MAX_ATTEMPTS
+            ),
+            Err(e) => warn!(
+                "Webhook to {} failed: {} (attempt {}/{})",
+                callback_url, e, attempt, MAX_ATTEMPTS
+            ),
+        }
+
+        if attempt < MAX_ATTEMPTS {
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+
+    warn!(
+        "Giving up on webhook to {} after {} attempts",
+        callback_url, MAX_ATTEMPTS
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_callback_url_must_be_http() {
+        assert!(validate_callback_url("https://example.com/hook").is_ok());
+        assert!(validate_callback_url("http://127.0.0.1:8080/hook").is_ok());
+        assert!(validate_callback_url("file:///etc/passwd").is_err());
+        assert!(validate_callback_url("not a url").is_err());
+    }
+
+    #[test]
+    fn test_forbidden_address_ranges() {
+        for ip in [
+            "127.0.0.1",
+            "127.8.9.10",
+            "10.0.0.1",
+            "172.16.0.1",
+            "172.31.255.254",
+            "192.168.1.1",
+            "169.254.169.254",
+            "169.254.0.1",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "224.0.0.1",
+            "::1",
+            "::",
+            "fc00::1",
+            "fd12:3456::1",
+            "fe80::1",
+            "ff02::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+        ] {
+            assert!(
+                is_forbidden_address(ip.parse().unwrap()),
+                "{ip} must be forbidden"
+            );
+        }
+        for ip in [
+            "93.184.216.34",
+            "172.32.0.1",
+            "100.128.0.1",
+            "2606:4700::1111",
+        ] {
+            assert!(
+                !is_forbidden_address(ip.parse().unwrap()),
+                "{ip} must be allowed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_dispatcher_rejects_private_callback_urls() {
+        let dispatcher = WebhookDispatcher::default();
+        assert!(dispatcher.validate("https://example.com/hook").is_ok());
+        for url in [
+            "http://127.0.0.1:8080/hook",
+            "http://10.1.2.3/hook",
+            "http://172.20.0.5/hook",
+            "http://192.168.0.10/hook",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://localhost/hook",
+            "http://api.localhost./hook",
+        ] {
+            assert!(dispatcher.validate(url).is_err(), "{url} must be rejected");
+        }
+
+        let permissive = WebhookDispatcher::default().allow_private_targets();
+        assert!(permissive.validate("http://127.0.0.1:8080/hook").is_ok());
+        assert!(permissive.validate("file:///etc/passwd").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolved_address_is_checked() {
+        // A hostname passes the literal check; its resolved address must not
+        let err = webhook_client("http://localhost:9/hook", false)
+            .await
+            .unwrap_err();
+        assert!(err.contains("forbidden"), "{err}");
+        assert!(webhook_client("http://localhost:9/hook", true)
+            .await
+            .is_ok());
+        assert!(webhook_client("http://[::1]:9/hook", false).await.is_err());
+    }
+}
@@ -12,6 +12,7 @@
 //! - `verify` module: core verification logic (signature verify, continuity check, receipt construction)
 //! - `ca` module (feature `ca`): Certificate Authority service using UniversalBackend
 //! - `http` module (feature `http`): HTTP layer — Plan 02 creates this
+//! - `metrics` module (feature `http`): Prometheus counters for the verify engine
 
 pub mod verify;
 
@@ -24,3 +25,6 @@ pub mod database;
 
 #[cfg(feature = "http")]
 pub mod http;
+
+#[cfg(feature = "http")]
+pub mod metrics;
@@ -0,0 +1,112 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Prometheus metrics for the verification service.
+//!
+//! A single process-wide registry is shared by the verify engine, which records
+//! every `verify_to_report()` call, and the `/metrics` route, which renders it in
+//! the Prometheus text exposition format.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use prometheus::{Encoder, Histogram, HistogramOpts, IntCounter, Registry, TextEncoder};
+
+use crate::verify::engine::VerifyReport;
+
+/// Content type of the Prometheus text exposition format
+pub const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Verification counters and latency histogram
+pub struct Metrics {
+    registry: Registry,
+    verifications: IntCounter,
+    signature_failures: IntCounter,
+    continuity_failures: IntCounter,
+    verify_time_ms: Histogram,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+        let verifications = IntCounter::new(
+            "sealedge_verifications_total",
+            "Verification requests processed by the verify engine",
+        )
+        .expect("valid metric");
+        let signature_failures = IntCounter::new(
+            "sealedge_signature_failures_total",
+            "Verifications whose manifest signature did not verify",
+        )
+        .expect("valid metric");
+        let continuity_failures = IntCounter::new(
+            "sealedge_continuity_failures_total",
+            "Verifications whose segment chain was not continuous",
+        )
+        .expect("valid metric");
+        let verify_time_ms = Histogram::with_opts(
+            HistogramOpts::new(
+                "sealedge_verify_time_ms",
+                "Time spent in the verify engine per request, in milliseconds",
+            )
+            .buckets(vec![
+                0.5, 1.0, 2.5, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 1000.0,
+            ]),
+        )
+        .expect("valid metric");
+
+        for collector in [
+            Box::new(verifications.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(signature_failures.clone()),
+            Box::new(continuity_failures.clone()),
+            Box::new(verify_time_ms.clone()),
+        ] {
+            registry.register(collector).expect("unique metric names");
+        }
+
+        Self {
+            registry,
+            verifications,
+            signature_failures,
+            continuity_failures,
+            verify_time_ms,
+        }
+    }
+
+    /// Record one verification.
+    ///
+    /// `report` is `None` when the input was rejected before a report could be
+    /// built; that counts toward the total and the timing only.
+    pub fn record_verification(&self, report: Option<&VerifyReport>, elapsed: Duration) {
+        self.verifications.inc();
+        if let Some(report) = report {
+            if !report.signature_verification.passed {
+                self.signature_failures.inc();
+            }
+            if !report.continuity_verification.passed {
+                self.continuity_failures.inc();
+            }
+        }
+        self.verify_time_ms.observe(elapsed.as_secs_f64() * 1000.0);
+    }
+
+    /// Render all metrics in the Prometheus text format
+    pub fn render(&self) -> String {
+        let mut buf = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buf)
+            .expect("text encoding into a Vec cannot fail");
+        String::from_utf8(buf).expect("Prometheus text format is UTF-8")
+    }
+}
+
+/// The process-wide metrics registry
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::new)
+}
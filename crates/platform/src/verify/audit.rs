@@ -0,0 +1,627 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Append-only audit log of verification operations.
+//!
+//! Each entry is linked to the one before it the same way archive segments are:
+//! `entry_hash = chain_next(prev_hash, BLAKE3(entry fields))`, starting from the
+//! continuity chain's genesis value. Editing, inserting or deleting an entry
+//! breaks every link after it, which [`AuditLog::verify_log_integrity`] detects.
+//!
+//! The log keeps at most `capacity` entries in memory. Once full, the oldest
+//! entry is evicted and its hash becomes the anchor the retained entries are
+//! checked against. [`AuditLog::open`] additionally appends every entry to a
+//! JSON-lines file and replays that file on start-up, so the complete chain
+//! survives restarts.
+//!
+//! Dropping entries from the end leaves a shorter chain that still links up, so
+//! truncation is detected against an [`AuditCheckpoint`]: the head and length
+//! the log was seen to reach, passed back to [`AuditLog::verify_log_integrity`].
+
+use super::engine::{decode_b3_lenient, format_b3, VerifyReport};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+/// Entries kept in memory when no capacity is given
+pub const DEFAULT_AUDIT_CAPACITY: usize = 10_000;
+
+/// One recorded verification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, from 0
+    pub sequence: u64,
+    /// When the verification ran (RFC 3339)
+    pub timestamp: String,
+    /// `b3:` digest of the verify request
+    pub request_digest: String,
+    /// "pass", "fail", or "error" when no report could be built
+    pub result: String,
+    /// `entry_hash` of the previous entry, or the genesis hash for the first
+    pub prev_hash: String,
+    /// Chain value over this entry and everything before it
+    pub entry_hash: String,
+}
+
+/// Where [`AuditLog::verify_log_integrity`] found the chain broken
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AuditLogError {
+    #[error("audit entry {found} found where entry {expected} was expected")]
+    SequenceGap { expected: u64, found: u64 },
+    #[error("audit entry {sequence} does not link to the entry before it")]
+    BrokenLink { sequence: u64 },
+    #[error("audit entry {sequence} does not match its recorded hash")]
+    HashMismatch { sequence: u64 },
+    #[error("audit log ends before entry {expected}; it was truncated")]
+    Truncated { expected: u64 },
+    #[error("audit entry {sequence} does not match the checkpoint")]
+    CheckpointMismatch { sequence: u64 },
+}
+
+/// Head and length the log was seen to reach, kept outside the log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditCheckpoint {
+    /// [`AuditLog::next_sequence`] when the checkpoint was taken
+    pub next_sequence: u64,
+    /// [`AuditLog::head`] when the checkpoint was taken
+    pub head: String,
+}
+
+/// Hash-chained, append-only record of verifications
+#[derive(Debug)]
+pub struct AuditLog {
+    entries: VecDeque<AuditEntry>,
+    capacity: usize,
+    /// Sequence number of the oldest retained entry
+    first_sequence: u64,
+    /// `prev_hash` the oldest retained entry must link to
+    anchor: String,
+    /// Append-only JSON-lines file every entry is written to
+    file: Option<File>,
+    /// Length of the file up to the last complete entry
+    persisted_len: u64,
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_AUDIT_CAPACITY)
+    }
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// In-memory log that retains at most `capacity` entries (minimum 1)
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity: capacity.max(1),
+            first_sequence: 0,
+            anchor: format_b3(&sealedge_core::chain::genesis()),
+            file: None,
+            persisted_len: 0,
+        }
+    }
+
+    /// Open the log persisted at `path`, creating the file if it does not exist.
+    ///
+    /// Every stored entry is replayed and checked from genesis; a broken chain is
+    /// reported as [`std::io::ErrorKind::InvalidData`] rather than silently
+    /// continued. A final line without its newline is what a crash mid-append
+    /// leaves behind; it was never part of the chain and is truncated away. New
+    /// entries are appended to the same file.
+    pub fn open(path: impl AsRef<Path>, capacity: usize) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let mut log = Self::with_capacity(capacity);
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)?;
+
+        let mut reader = BufReader::new(&file);
+        let mut line = Vec::new();
+        let mut complete_len = 0u64;
+        loop {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 {
+                break;
+            }
+            if line.last() != Some(&b'\n') {
+                tracing::warn!(
+                    "{}: dropping incomplete trailing audit entry ({} bytes)",
+                    path.display(),
+                    read
+                );
+                file.set_len(complete_len)?;
+                break;
+            }
+            complete_len += read as u64;
+            if line.trim_ascii().is_empty() {
+                continue;
+            }
+            let entry: AuditEntry = serde_json::from_slice(&line)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            log.check_next(&entry).map_err(|e| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), e),
+                )
+            })?;
+            log.push(entry);
+        }
+        log.persisted_len = complete_len;
+        log.file = Some(file);
+        Ok(log)
+    }
+
+    /// Append an entry for one verification and return it.
+    ///
+    /// For a persisted log the entry is written before it joins the chain: when
+    /// the write fails the error is returned, any partial line is cut off again,
+    /// and the head stays where it was.
+    pub fn append(
+        &mut self,
+        request_digest: &str,
+        result: &str,
+        timestamp: &str,
+    ) -> std::io::Result<&AuditEntry> {
+        let sequence = self.next_sequence();
+        let prev_hash = self.head();
+        let entry_hash = entry_hash(&prev_hash, sequence, timestamp, request_digest, result);
+        let entry = AuditEntry {
+            sequence,
+            timestamp: timestamp.to_string(),
+            request_digest: request_digest.to_string(),
+            result: result.to_string(),
+            prev_hash,
+            entry_hash,
+        };
+        if let Some(file) = self.file.as_mut() {
+            let mut line = serde_json::to_vec(&entry)?;
+            line.push(b'\n');
+            if let Err(e) = file.write_all(&line) {
+                if let Err(truncate) = file.set_len(self.persisted_len) {
+                    tracing::error!(
+                        "Failed to cut off partial audit entry {}: {}",
+                        sequence,
+                        truncate
+                    );
+                }
+                return Err(e);
+            }
+            self.persisted_len += line.len() as u64;
+        }
+        self.push(entry);
+        Ok(self.entries.back().expect("entry was just pushed"))
+    }
+
+    /// Append an entry for the outcome of a `verify_to_report` call, timestamped now
+    pub fn record(
+        &mut self,
+        request_digest: &str,
+        report: Option<&VerifyReport>,
+    ) -> std::io::Result<&AuditEntry> {
+        self.append(
+            request_digest,
+            result_label(report),
+            &chrono::Utc::now().to_rfc3339(),
+        )
+    }
+
+    /// Retained entries, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &AuditEntry> {
+        self.entries.iter()
+    }
+
+    /// Up to `limit` retained entries with a sequence number of at least `from`
+    pub fn page(&self, from: u64, limit: usize) -> Vec<AuditEntry> {
+        let skip = from.saturating_sub(self.first_sequence) as usize;
+        self.entries
+            .iter()
+            .skip(skip)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Sequence number of the oldest retained entry
+    pub fn first_sequence(&self) -> u64 {
+        self.first_sequence
+    }
+
+    /// Sequence number the next appended entry will get
+    pub fn next_sequence(&self) -> u64 {
+        self.first_sequence + self.entries.len() as u64
+    }
+
+    /// Hash of the latest entry, or the anchor (genesis) for an empty log
+    pub fn head(&self) -> String {
+        self.entries
+            .back()
+            .map_or_else(|| self.anchor.clone(), |entry| entry.entry_hash.clone())
+    }
+
+    /// Head and length of the log now, to record outside it
+    pub fn checkpoint(&self) -> AuditCheckpoint {
+        AuditCheckpoint {
+            next_sequence: self.next_sequence(),
+            head: self.head(),
+        }
+    }
+
+    /// Recompute the chain from the anchor and check every retained entry against it
+    ///
+    /// With a `checkpoint`, the log must also still reach it: it must not end
+    /// before the checkpoint's length, and the entry the checkpoint ended on must
+    /// still carry its head. A checkpoint older than the retained window has been
+    /// folded into the anchor and is not checked further.
+    pub fn verify_log_integrity(
+        &self,
+        checkpoint: Option<&AuditCheckpoint>,
+    ) -> Result<(), AuditLogError> {
+        let mut expected_prev = self.anchor.clone();
+        for (expected, entry) in (self.first_sequence..).zip(&self.entries) {
+            expected_prev = check_entry(entry, expected, &expected_prev)?;
+        }
+
+        let Some(checkpoint) = checkpoint else {
+            return Ok(());
+        };
+        if checkpoint.next_sequence > self.next_sequence() {
+            return Err(AuditLogError::Truncated {
+                expected: checkpoint.next_sequence - 1,
+            });
+        }
+        let head = match checkpoint.next_sequence.checked_sub(self.first_sequence) {
+            Some(0) => &self.anchor,
+            Some(retained) => &self.entries[retained as usize - 1].entry_hash,
+            None => return Ok(()),
+        };
+        if *head != checkpoint.head {
+            return Err(AuditLogError::CheckpointMismatch {
+                sequence: checkpoint.next_sequence.saturating_sub(1),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check that `entry` is the valid successor of the current head
+    fn check_next(&self, entry: &AuditEntry) -> Result<(), AuditLogError> {
+        check_entry(entry, self.next_sequence(), &self.head()).map(|_| ())
+    }
+
+    /// Add `entry` at the end, evicting the oldest entry once over capacity
+    fn push(&mut self, entry: AuditEntry) {
+        if self.entries.is_empty() {
+            self.first_sequence = entry.sequence;
+            self.anchor = entry.prev_hash.clone();
+        }
+        self.entries.push_back(entry);
+        if self.entries.len() > self.capacity {
+            if let Some(evicted) = self.entries.pop_front() {
+                self.first_sequence = evicted.sequence + 1;
+                self.anchor = evicted.entry_hash;
+            }
+        }
+    }
+}
+
+/// Audit result for the outcome of a `verify_to_report` call
+pub fn result_label(report: Option<&VerifyReport>) -> &'static str {
+    match report {
+        Some(r) if r.signature_verification.passed && r.continuity_verification.passed => "pass",
+        Some(_) => "fail",
+        None => "error",
+    }
+}
+
+/// Check one entry against its expected position and predecessor; returns its hash
+fn check_entry(
+    entry: &AuditEntry,
+    expected: u64,
+    expected_prev: &str,
+) -> Result<String, AuditLogError> {
+    if entry.sequence != expected {
+        return Err(AuditLogError::SequenceGap {
+            expected,
+            found: entry.sequence,
+        });
+    }
+    if entry.prev_hash != expected_prev {
+        return Err(AuditLogError::BrokenLink {
+            sequence: entry.sequence,
+        });
+    }
+    let recomputed = entry_hash(
+        &entry.prev_hash,
+        entry.sequence,
+        &entry.timestamp,
+        &entry.request_digest,
+        &entry.result,
+    );
+    if entry.entry_hash != recomputed {
+        return Err(AuditLogError::HashMismatch {
+            sequence: entry.sequence,
+        });
+    }
+    Ok(recomputed)
+}
+
+fn entry_hash(
+    prev_hash: &str,
+    sequence: u64,
+    timestamp: &str,
+    request_digest: &str,
+    result: &str,
+) -> String {
+    // A JSON array keeps field boundaries unambiguous
+    let fields = serde_json::json!([sequence, timestamp, request_digest, result]).to_string();
+    format_b3(&sealedge_core::chain::chain_next(
+        &decode_b3_lenient(prev_hash),
+        &sealedge_core::chain::segment_hash(fields.as_bytes()),
+    ))
+}
+
+/// `b3:` digest identifying a verify request by its manifest, segments and key
+pub fn request_digest(
+    manifest: &serde_json::Value,
+    segments: &[super::engine::SegmentDigest],
+    device_pub: &str,
+) -> String {
+    let request = serde_json::json!({
+        "device_pub": device_pub,
+        "manifest": manifest,
+        "segments": segments,
+    });
+    format_b3(&sealedge_core::chain::segment_hash(
+        request.to_string().as_bytes(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_log() -> AuditLog {
+        let mut log = AuditLog::new();
+        for (i, result) in ["pass", "fail", "pass", "error"].into_iter().enumerate() {
+            log.append(
+                &format_b3(&sealedge_core::chain::segment_hash(&[i as u8])),
+                result,
+                &format!("2025-01-15T10:3{}:00Z", i),
+            )
+            .unwrap();
+        }
+        log
+    }
+
+    #[test]
+    fn test_well_formed_log_verifies() {
+        let log = sample_log();
+        assert_eq!(log.len(), 4);
+        assert_eq!(log.verify_log_integrity(None), Ok(()));
+        assert_eq!(log.head(), log.entries[3].entry_hash);
+        assert_eq!(log.entries[1].prev_hash, log.entries[0].entry_hash);
+        assert_eq!(AuditLog::new().verify_log_integrity(None), Ok(()));
+    }
+
+    #[test]
+    fn test_deleted_entry_is_detected() {
+        let mut log = sample_log();
+        log.entries.remove(1);
+        assert_eq!(
+            log.verify_log_integrity(None),
+            Err(AuditLogError::SequenceGap {
+                expected: 1,
+                found: 2
+            })
+        );
+
+        // Renumbering to hide the gap still leaves the chain broken
+        log.entries[1].sequence = 1;
+        log.entries[2].sequence = 2;
+        assert_eq!(
+            log.verify_log_integrity(None),
+            Err(AuditLogError::BrokenLink { sequence: 1 })
+        );
+
+        // Dropping the first entry is caught too
+        let mut log = sample_log();
+        log.entries.remove(0);
+        assert!(log.verify_log_integrity(None).is_err());
+    }
+
+    #[test]
+    fn test_deleted_last_entry_is_detected() {
+        let mut log = sample_log();
+        let checkpoint = log.checkpoint();
+        assert_eq!(log.verify_log_integrity(Some(&checkpoint)), Ok(()));
+
+        // The shorter chain still links up on its own
+        log.entries.pop_back();
+        assert_eq!(log.verify_log_integrity(None), Ok(()));
+        assert_eq!(
+            log.verify_log_integrity(Some(&checkpoint)),
+            Err(AuditLogError::Truncated { expected: 3 })
+        );
+
+        // Appending a replacement restores the length but not the head
+        log.append(&format_b3(&[9u8; 32]), "pass", "2025-01-15T10:39:00Z")
+            .unwrap();
+        assert_eq!(
+            log.verify_log_integrity(Some(&checkpoint)),
+            Err(AuditLogError::CheckpointMismatch { sequence: 3 })
+        );
+
+        // An older checkpoint still holds once the log has grown past it
+        let mut log = sample_log();
+        let checkpoint = log.checkpoint();
+        log.append(&format_b3(&[9u8; 32]), "pass", "2025-01-15T10:39:00Z")
+            .unwrap();
+        assert_eq!(log.verify_log_integrity(Some(&checkpoint)), Ok(()));
+        let empty = AuditLog::new().checkpoint();
+        assert_eq!(log.verify_log_integrity(Some(&empty)), Ok(()));
+    }
+
+    #[test]
+    fn test_edited_entry_is_detected() {
+        let mut log = sample_log();
+        log.entries[1].result = "pass".to_string();
+        assert_eq!(
+            log.verify_log_integrity(None),
+            Err(AuditLogError::HashMismatch { sequence: 1 })
+        );
+
+        // Re-hashing the edited entry moves the break to the next link
+        let mut log = sample_log();
+        log.entries[1].result = "pass".to_string();
+        log.entries[1].entry_hash = entry_hash(
+            &log.entries[1].prev_hash,
+            1,
+            &log.entries[1].timestamp,
+            &log.entries[1].request_digest,
+            "pass",
+        );
+        assert_eq!(
+            log.verify_log_integrity(None),
+            Err(AuditLogError::BrokenLink { sequence: 2 })
+        );
+    }
+
+    #[test]
+    fn test_inserted_entry_is_detected() {
+        let mut log = sample_log();
+        let mut forged = log.entries[1].clone();
+        forged.request_digest = format_b3(&[7u8; 32]);
+        log.entries.insert(2, forged);
+        assert!(log.verify_log_integrity(None).is_err());
+    }
+
+    #[test]
+    fn test_full_log_evicts_oldest_and_stays_verifiable() {
+        let mut log = AuditLog::with_capacity(3);
+        for i in 0..5u8 {
+            log.append(&format_b3(&[i; 32]), "pass", "2025-01-15T10:30:00Z")
+                .unwrap();
+        }
+        assert_eq!(log.len(), 3);
+        assert_eq!(log.first_sequence(), 2);
+        assert_eq!(log.next_sequence(), 5);
+        assert_eq!(log.verify_log_integrity(None), Ok(()));
+
+        let page = log.page(3, 10);
+        assert_eq!(
+            page.iter().map(|e| e.sequence).collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        assert_eq!(log.page(0, 1)[0].sequence, 2);
+
+        // The evicted entry's hash still anchors the retained window
+        log.entries.pop_front();
+        assert_eq!(
+            log.verify_log_integrity(None),
+            Err(AuditLogError::SequenceGap {
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_persisted_log_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+
+        let head = {
+            let mut log = AuditLog::open(&path, 2).unwrap();
+            for i in 0..3u8 {
+                log.append(&format_b3(&[i; 32]), "pass", "2025-01-15T10:30:00Z")
+                    .unwrap();
+            }
+            log.head()
+        };
+
+        let mut log = AuditLog::open(&path, 2).unwrap();
+        assert_eq!(log.head(), head);
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.next_sequence(), 3);
+        assert_eq!(log.append("b3:next", "fail", "t").unwrap().sequence, 3);
+        assert_eq!(log.verify_log_integrity(None), Ok(()));
+        drop(log);
+        assert_eq!(AuditLog::open(&path, 10).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_tampered_log_file_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        {
+            let mut log = AuditLog::open(&path, 10).unwrap();
+            log.append("b3:a", "fail", "2025-01-15T10:30:00Z").unwrap();
+            log.append("b3:b", "pass", "2025-01-15T10:31:00Z").unwrap();
+        }
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, contents.replacen("\"fail\"", "\"pass\"", 1)).unwrap();
+        let err = AuditLog::open(&path, 10).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_incomplete_trailing_line_is_truncated() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let head = {
+            let mut log = AuditLog::open(&path, 10).unwrap();
+            log.append("b3:a", "pass", "2025-01-15T10:30:00Z").unwrap();
+            log.head()
+        };
+
+        // A crash mid-append leaves half a line with no newline
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"sequence":1,"timesta"#).unwrap();
+        drop(file);
+
+        let mut log = AuditLog::open(&path, 10).unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log.head(), head);
+        log.append("b3:b", "fail", "2025-01-15T10:31:00Z").unwrap();
+        drop(log);
+
+        let log = AuditLog::open(&path, 10).unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.verify_log_integrity(None), Ok(()));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_failed_write_does_not_advance_chain() {
+        // Writes to /dev/full always fail with ENOSPC
+        let mut log = AuditLog::new();
+        log.file = Some(OpenOptions::new().write(true).open("/dev/full").unwrap());
+        let head = log.head();
+        assert!(log.append("b3:a", "pass", "2025-01-15T10:30:00Z").is_err());
+        assert_eq!(log.head(), head);
+        assert_eq!(log.next_sequence(), 0);
+        assert!(log.is_empty());
+    }
+}
@@ -58,10 +58,61 @@ pub struct ReceiptClaims {
     pub result: VerifyReport,
 }
 
+/// Tuning for [`verify_to_report_with_config`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyConfig {
+    /// Worker threads that decode and check segment hashes; 1 runs on the calling
+    /// thread. The continuity chain is always folded in index order, so the report
+    /// does not depend on this value.
+    pub parallelism: usize,
+}
+
+impl Default for VerifyConfig {
+    fn default() -> Self {
+        Self { parallelism: 1 }
+    }
+}
+
+impl VerifyConfig {
+    /// One worker per available core
+    pub fn parallel() -> Self {
+        Self {
+            parallelism: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+}
+
 pub fn verify_to_report(
     manifest: &serde_json::Value,
     segments: &[SegmentDigest],
     device_pub: &str,
+) -> Result<VerifyReport> {
+    verify_to_report_with_config(manifest, segments, device_pub, &VerifyConfig::default())
+}
+
+/// [`verify_to_report`] with segment hashes decoded on `config.parallelism` workers
+pub fn verify_to_report_with_config(
+    manifest: &serde_json::Value,
+    segments: &[SegmentDigest],
+    device_pub: &str,
+    config: &VerifyConfig,
+) -> Result<VerifyReport> {
+    #[cfg(feature = "http")]
+    let started = std::time::Instant::now();
+
+    let report = build_report(manifest, segments, device_pub, config);
+
+    #[cfg(feature = "http")]
+    crate::metrics::metrics().record_verification(report.as_ref().ok(), started.elapsed());
+
+    report
+}
+
+fn build_report(
+    manifest: &serde_json::Value,
+    segments: &[SegmentDigest],
+    device_pub: &str,
+    config: &VerifyConfig,
 ) -> Result<VerifyReport> {
     let signature_result = verify_signature(manifest, device_pub)?;
     let continuity_result = verify_continuity(segments)?;
@@ -69,6 +120,8 @@ pub fn verify_to_report(
     let genesis_hash = compute_genesis_hash();
     let chain_tip = if segments.is_empty() {
         genesis_hash.clone()
+    } else if config.parallelism > 1 {
+        compute_chain_tip_parallel(segments, config.parallelism)
     } else {
         compute_chain_tip(segments)?
     };
@@ -104,7 +157,10 @@ pub fn receipt_from_report(
     }
 }
 
-fn verify_signature(manifest: &serde_json::Value, device_pub: &str) -> Result<VerificationResult> {
+pub(super) fn verify_signature(
+    manifest: &serde_json::Value,
+    device_pub: &str,
+) -> Result<VerificationResult> {
     // device_pub must have "ed25519:" prefix — core's verify_manifest expects it present
     if !device_pub.starts_with("ed25519:") {
         return Err(anyhow!("Device public key must have ed25519: prefix"));
@@ -161,15 +217,7 @@ fn verify_continuity(segments: &[SegmentDigest]) -> Result<VerificationResult> {
         }
     }
 
-    let genesis = compute_genesis_hash();
-    let mut chain_value = genesis;
-
-    for segment in &sorted_segments {
-        let hash = segment.hash.strip_prefix("b3:").unwrap_or(&segment.hash);
-        let next_chain = compute_chain_link(&chain_value, hash);
-        chain_value = next_chain;
-    }
-
+    // The chain itself is folded once, in build_report, to produce the tip
     Ok(VerificationResult {
         passed: true,
         error: None,
@@ -198,22 +246,10 @@ fn compute_genesis_hash() -> String {
 
 /// Compute a chain link using sealedge_core's chain module.
 fn compute_chain_link(prev: &str, hash: &str) -> String {
-    let prev_clean = prev.strip_prefix("b3:").unwrap_or(prev);
-    let hash_clean = hash.strip_prefix("b3:").unwrap_or(hash);
-
-    let prev_bytes = BASE64.decode(prev_clean).unwrap_or_default();
-    let hash_bytes = BASE64.decode(hash_clean).unwrap_or_default();
-
-    let mut prev_arr = [0u8; 32];
-    let mut hash_arr = [0u8; 32];
-    if prev_bytes.len() == 32 {
-        prev_arr.copy_from_slice(&prev_bytes);
-    }
-    if hash_bytes.len() == 32 {
-        hash_arr.copy_from_slice(&hash_bytes);
-    }
-
-    format_b3(&sealedge_core::chain::chain_next(&prev_arr, &hash_arr))
+    format_b3(&sealedge_core::chain::chain_next(
+        &decode_b3_lenient(prev),
+        &decode_b3_lenient(hash),
+    ))
 }
 
 fn compute_chain_tip(segments: &[SegmentDigest]) -> Result<String> {
@@ -231,11 +267,56 @@ fn compute_chain_tip(segments: &[SegmentDigest]) -> Result<String> {
     Ok(chain_value)
 }
 
+/// [`compute_chain_tip`] with segment hashes decoded on `parallelism` workers
+///
+/// Decoding is independent per segment; only the fold over the decoded hashes is
+/// sequential, in index order, so the tip matches the serial computation.
+fn compute_chain_tip_parallel(segments: &[SegmentDigest], parallelism: usize) -> String {
+    let mut sorted_segments = segments.to_vec();
+    sorted_segments.sort_by_key(|s| s.index);
+
+    let per_worker = sorted_segments.len().div_ceil(parallelism.max(1)).max(1);
+    let hashes: Vec<[u8; 32]> = std::thread::scope(|scope| {
+        let workers: Vec<_> = sorted_segments
+            .chunks(per_worker)
+            .map(|batch| {
+                scope.spawn(move || {
+                    batch
+                        .iter()
+                        .map(|s| decode_b3_lenient(&s.hash))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("segment hash worker panicked"))
+            .collect()
+    });
+
+    let tip = hashes
+        .iter()
+        .fold(sealedge_core::chain::genesis(), |prev, hash| {
+            sealedge_core::chain::chain_next(&prev, hash)
+        });
+    format_b3(&tip)
+}
+
+/// Decode a `b3:BASE64` hash; anything that is not 32 bytes becomes all zeroes
+pub(super) fn decode_b3_lenient(hash: &str) -> [u8; 32] {
+    let clean = hash.strip_prefix("b3:").unwrap_or(hash);
+    BASE64
+        .decode(clean)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .unwrap_or([0u8; 32])
+}
+
 /// Format a 32-byte hash as "b3:BASE64" using the standard base64 alphabet.
 ///
 /// Uses the `base64` crate's STANDARD encoder (RFC 4648 with padding) to ensure
 /// consistent output with callers that decode using the same encoder.
-fn format_b3(bytes: &[u8; 32]) -> String {
+pub(super) fn format_b3(bytes: &[u8; 32]) -> String {
     format!("b3:{}", BASE64.encode(bytes))
 }
 
@@ -306,6 +387,55 @@ mod tests {
         assert!(result.error.is_some());
     }
 
+    fn segments_with_indices(indices: impl IntoIterator<Item = u32>) -> Vec<SegmentDigest> {
+        indices
+            .into_iter()
+            .map(|index| SegmentDigest {
+                index,
+                hash: format_b3(&sealedge_core::chain::segment_hash(&index.to_le_bytes())),
+            })
+            .collect()
+    }
+
+    fn report_json(segments: &[SegmentDigest], parallelism: usize) -> serde_json::Value {
+        let manifest = json!({ "version": "1.0", "signature": "ed25519:invalid" });
+        let config = VerifyConfig { parallelism };
+        let report =
+            verify_to_report_with_config(&manifest, segments, "ed25519:invalid", &config).unwrap();
+        serde_json::to_value(report).unwrap()
+    }
+
+    #[test]
+    fn test_parallel_report_matches_serial() {
+        // Shuffled input, so workers see segments out of index order
+        let mut segments = segments_with_indices(0..37);
+        segments.reverse();
+        segments.swap(3, 20);
+
+        let serial = report_json(&segments, 1);
+        for parallelism in [2, 4, 64] {
+            assert_eq!(report_json(&segments, parallelism), serial);
+        }
+        assert_eq!(
+            compute_chain_tip_parallel(&segments, 4),
+            compute_chain_tip(&segments).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parallel_report_matches_serial_on_gap() {
+        let segments = segments_with_indices((0..20).filter(|&i| i != 7 && i != 12));
+
+        let serial = report_json(&segments, 1);
+        assert_eq!(
+            serial["continuity_verification"]["error"],
+            "Missing segment at index 7"
+        );
+        for parallelism in [2, 4, 64] {
+            assert_eq!(report_json(&segments, parallelism), serial);
+        }
+    }
+
     #[test]
     fn test_manifest_canonicalization() {
         let manifest = json!({
@@ -8,8 +8,10 @@
 
 //! Verification module — core logic for segment continuity and manifest signature verification.
 
+pub mod audit;
 pub mod engine;
 pub mod jwks;
 pub mod signing;
+pub mod streaming;
 pub mod types;
 pub mod validation;
@@ -0,0 +1,408 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Streaming verification — fold segments into the continuity chain as they arrive.
+//!
+//! [`StreamingVerifier`] checks the manifest signature once up front, then takes
+//! segments one at a time from a live ingest. Segments that arrive ahead of a
+//! missing index are held back until the hole is filled, so a complete stream
+//! yields the same chain tip as the batch engine regardless of arrival order.
+//! At most [`MAX_PENDING`] indices past the hole are held; a segment further
+//! ahead is dropped, and the hole is reported as a gap unless the dropped
+//! segment is sent again once the window has caught up.
+
+use super::engine::{decode_b3_lenient, format_b3, verify_signature, VerificationResult};
+use anyhow::Result;
+use sealedge_types::verification::SegmentRef;
+use sealedge_types::verify_report::{OutOfOrder, VerifyReport};
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+/// How far past the lowest missing index a segment may arrive and still be held
+pub const MAX_PENDING: u32 = 1024;
+
+/// Progress after a [`StreamingVerifier::push_segment`] call
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamStatus {
+    /// Segments folded into the chain, contiguous from index 0
+    pub verified_segments: u32,
+    /// Segments received ahead of a missing index, waiting for it
+    pub pending_segments: u32,
+    /// Lowest index not yet received
+    pub next_index: u32,
+    /// Chain value over the verified segments
+    pub chain_tip: String,
+    /// Whether every segment so far arrived in order, exactly once
+    pub in_order: bool,
+}
+
+/// Incremental counterpart to [`super::engine::verify_to_report`]
+pub struct StreamingVerifier {
+    signature: VerificationResult,
+    profile: String,
+    device_id: String,
+    chunk_duration_s: f32,
+    chain: [u8; 32],
+    next_index: u32,
+    pending: BTreeMap<u32, [u8; 32]>,
+    // The first segment seen ahead of a hole; promoted to `out_of_order` once the
+    // hole is filled, otherwise the hole is reported as a gap
+    early: Option<OutOfOrder>,
+    out_of_order: Option<OutOfOrder>,
+    duplicate_index: Option<u32>,
+    // Highest segment dropped for arriving beyond the pending window. Cleared once
+    // the chain passes it (the segment was resent); otherwise the stream ends in a gap
+    dropped_index: Option<u32>,
+    started: Instant,
+}
+
+impl StreamingVerifier {
+    /// Check `manifest`'s signature against `device_pub` and start an empty chain
+    ///
+    /// Fails only when the manifest has no signature or the key lacks the
+    /// `ed25519:` prefix; a signature that does not verify is reported by
+    /// [`finalize`](Self::finalize).
+    pub fn new(manifest: &serde_json::Value, device_pub: &str) -> Result<Self> {
+        let started = Instant::now();
+        let signature = verify_signature(manifest, device_pub)?;
+
+        let text = |pointer: &str| {
+            manifest
+                .pointer(pointer)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        Ok(Self {
+            signature,
+            profile: text("/profile"),
+            device_id: text("/device/id"),
+            chunk_duration_s: manifest
+                .pointer("/chunk/approx_duration_s")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0) as f32,
+            chain: sealedge_core::chain::genesis(),
+            next_index: 0,
+            pending: BTreeMap::new(),
+            early: None,
+            out_of_order: None,
+            duplicate_index: None,
+            dropped_index: None,
+            started,
+        })
+    }
+
+    /// Accept the next segment from the stream
+    pub fn push_segment(&mut self, segment: SegmentRef) -> StreamStatus {
+        let hash = decode_b3_lenient(&segment.hash);
+
+        if segment.index < self.next_index || self.pending.contains_key(&segment.index) {
+            self.duplicate_index.get_or_insert(segment.index);
+        } else if segment.index - self.next_index >= MAX_PENDING {
+            self.note_early(segment.index);
+            self.dropped_index = Some(
+                self.dropped_index
+                    .map_or(segment.index, |dropped| dropped.max(segment.index)),
+            );
+        } else if segment.index > self.next_index {
+            self.note_early(segment.index);
+            self.pending.insert(segment.index, hash);
+        } else {
+            if self
+                .early
+                .as_ref()
+                .is_some_and(|early| early.expected == segment.index)
+            {
+                self.out_of_order = self.early.take();
+            }
+            self.advance(&hash);
+            while let Some(hash) = self.pending.remove(&self.next_index) {
+                self.advance(&hash);
+            }
+        }
+
+        self.status()
+    }
+
+    /// Remember the first segment seen ahead of a hole
+    fn note_early(&mut self, index: u32) {
+        if self.early.is_none() && self.out_of_order.is_none() {
+            self.early = Some(OutOfOrder {
+                expected: self.next_index,
+                found: index,
+            });
+        }
+    }
+
+    fn advance(&mut self, hash: &[u8; 32]) {
+        self.chain = sealedge_core::chain::chain_next(&self.chain, hash);
+        self.next_index += 1;
+        // Every dropped segment has since been resent and folded in
+        if self
+            .dropped_index
+            .is_some_and(|dropped| dropped < self.next_index)
+        {
+            self.dropped_index = None;
+        }
+    }
+
+    /// Current progress, as returned by the last [`push_segment`](Self::push_segment)
+    pub fn status(&self) -> StreamStatus {
+        StreamStatus {
+            verified_segments: self.next_index,
+            pending_segments: self.pending.len() as u32,
+            next_index: self.next_index,
+            chain_tip: format_b3(&self.chain),
+            in_order: self.pending.is_empty()
+                && self.dropped_index.is_none()
+                && self.early.is_none()
+                && self.out_of_order.is_none()
+                && self.duplicate_index.is_none(),
+        }
+    }
+
+    /// Close the stream and produce the full report
+    ///
+    /// Segments still waiting on a missing index are left out of the chain tip,
+    /// and the missing index is reported as `first_gap_index`. So is the index the
+    /// chain stopped at when a segment was dropped beyond the pending window.
+    pub fn finalize(self) -> VerifyReport {
+        let first_gap_index =
+            (!self.pending.is_empty() || self.dropped_index.is_some()).then_some(self.next_index);
+        let segments = self.next_index + self.pending.len() as u32;
+
+        let continuity_error = if let Some(index) = first_gap_index {
+            Some(match self.dropped_index {
+                Some(dropped) => format!(
+                    "Missing segment at index {} (segment {} arrived more than {} ahead and was dropped)",
+                    index, dropped, MAX_PENDING
+                ),
+                None => format!("Missing segment at index {}", index),
+            })
+        } else if let Some(ref ooo) = self.out_of_order {
+            Some(format!(
+                "Segment {} arrived out of order (expected {})",
+                ooo.found, ooo.expected
+            ))
+        } else {
+            self.duplicate_index
+                .map(|index| format!("Duplicate segment at index {}", index))
+        };
+
+        VerifyReport {
+            signature: verdict(self.signature.passed),
+            continuity: verdict(continuity_error.is_none()),
+            segments,
+            duration_s: segments as f32 * self.chunk_duration_s,
+            profile: self.profile,
+            device_id: self.device_id,
+            first_gap_index,
+            out_of_order: self.out_of_order,
+            error: self.signature.error.or(continuity_error),
+            verify_time_ms: self.started.elapsed().as_millis() as u64,
+            chain_tip: Some(format_b3(&self.chain)),
+            ..VerifyReport::default()
+        }
+    }
+}
+
+fn verdict(passed: bool) -> String {
+    if passed { "pass" } else { "fail" }.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::engine::{verify_to_report, SegmentDigest};
+    use sealedge_core::DeviceKeypair;
+    use serde_json::json;
+
+    fn signed_manifest() -> (serde_json::Value, String) {
+        let keypair = DeviceKeypair::generate().unwrap();
+        let mut manifest = json!({
+            "trst_version": "0.1.0",
+            "profile": "cam.video",
+            "device": { "id": "cam-01", "public_key": keypair.public },
+            "chunk": { "bytes": 1048576, "approx_duration_s": 2.0 },
+        });
+        let canonical = serde_json::to_string(&manifest).unwrap();
+        let signature =
+            sealedge_core::crypto::sign_manifest(&keypair, canonical.as_bytes()).unwrap();
+        manifest["signature"] = json!(signature.strip_prefix("ed25519:").unwrap());
+        (manifest, keypair.public.clone())
+    }
+
+    fn segment(index: u32) -> SegmentRef {
+        SegmentRef {
+            index,
+            hash: format_b3(&sealedge_core::chain::segment_hash(&index.to_le_bytes())),
+        }
+    }
+
+    fn batch_tip(manifest: &serde_json::Value, device_pub: &str, count: u32) -> String {
+        let segments: Vec<SegmentDigest> = (0..count)
+            .map(|index| {
+                let SegmentRef { index, hash } = segment(index);
+                SegmentDigest { index, hash }
+            })
+            .collect();
+        verify_to_report(manifest, &segments, device_pub)
+            .unwrap()
+            .metadata
+            .chain_tip
+    }
+
+    #[test]
+    fn test_streaming_in_order_passes() {
+        let (manifest, device_pub) = signed_manifest();
+        let mut verifier = StreamingVerifier::new(&manifest, &device_pub).unwrap();
+
+        for index in 0..5 {
+            let status = verifier.push_segment(segment(index));
+            assert_eq!(status.verified_segments, index + 1);
+            assert_eq!(status.pending_segments, 0);
+            assert!(status.in_order);
+        }
+
+        let report = verifier.finalize();
+        assert_eq!(report.signature, "pass");
+        assert_eq!(report.continuity, "pass");
+        assert_eq!(report.segments, 5);
+        assert_eq!(report.duration_s, 10.0);
+        assert_eq!(report.profile, "cam.video");
+        assert_eq!(report.device_id, "cam-01");
+        assert_eq!(report.first_gap_index, None);
+        assert_eq!(report.out_of_order, None);
+        assert_eq!(report.error, None);
+        assert_eq!(report.chain_tip, Some(batch_tip(&manifest, &device_pub, 5)));
+    }
+
+    #[test]
+    fn test_streaming_out_of_order_is_reported() {
+        let (manifest, device_pub) = signed_manifest();
+        let mut verifier = StreamingVerifier::new(&manifest, &device_pub).unwrap();
+
+        verifier.push_segment(segment(0));
+        let status = verifier.push_segment(segment(2));
+        assert_eq!(status.verified_segments, 1);
+        assert_eq!(status.pending_segments, 1);
+        assert!(!status.in_order);
+
+        // The late segment fills the hole and releases the one held back
+        let status = verifier.push_segment(segment(1));
+        assert_eq!(status.verified_segments, 3);
+        assert_eq!(status.pending_segments, 0);
+        verifier.push_segment(segment(3));
+
+        let report = verifier.finalize();
+        assert_eq!(report.continuity, "fail");
+        assert_eq!(
+            report.out_of_order,
+            Some(OutOfOrder {
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(report.first_gap_index, None);
+        assert_eq!(report.segments, 4);
+        assert_eq!(report.chain_tip, Some(batch_tip(&manifest, &device_pub, 4)));
+    }
+
+    #[test]
+    fn test_streaming_gap_sets_first_gap_index() {
+        let (manifest, device_pub) = signed_manifest();
+        let mut verifier = StreamingVerifier::new(&manifest, &device_pub).unwrap();
+
+        for index in [0, 1, 3, 4, 6] {
+            verifier.push_segment(segment(index));
+        }
+        let status = verifier.status();
+        assert_eq!(status.next_index, 2);
+        assert_eq!(status.pending_segments, 3);
+
+        let report = verifier.finalize();
+        assert_eq!(report.signature, "pass");
+        assert_eq!(report.continuity, "fail");
+        assert_eq!(report.first_gap_index, Some(2));
+        assert_eq!(report.out_of_order, None);
+        assert_eq!(report.error.as_deref(), Some("Missing segment at index 2"));
+        assert_eq!(report.segments, 5);
+        // Only the contiguous prefix is chained
+        assert_eq!(report.chain_tip, Some(batch_tip(&manifest, &device_pub, 2)));
+    }
+
+    #[test]
+    fn test_streaming_pending_window_is_bounded() {
+        let (manifest, device_pub) = signed_manifest();
+        let mut verifier = StreamingVerifier::new(&manifest, &device_pub).unwrap();
+
+        verifier.push_segment(segment(0));
+        // The last index inside the window is held back
+        let status = verifier.push_segment(segment(MAX_PENDING));
+        assert_eq!(status.pending_segments, 1);
+
+        // One past the window is dropped instead of buffered
+        let status = verifier.push_segment(segment(MAX_PENDING + 1));
+        assert_eq!(status.pending_segments, 1);
+        assert!(!status.in_order);
+
+        // Filling the hole releases the held segment, but the dropped one is gone
+        for index in 1..MAX_PENDING {
+            verifier.push_segment(segment(index));
+        }
+        let status = verifier.status();
+        assert_eq!(status.next_index, MAX_PENDING + 1);
+        assert_eq!(status.pending_segments, 0);
+
+        let report = verifier.finalize();
+        assert_eq!(report.continuity, "fail");
+        assert_eq!(report.first_gap_index, Some(MAX_PENDING + 1));
+        assert_eq!(report.segments, MAX_PENDING + 1);
+        assert!(report
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("Missing segment at index 1025"));
+        assert_eq!(
+            report.chain_tip,
+            Some(batch_tip(&manifest, &device_pub, MAX_PENDING + 1))
+        );
+    }
+
+    #[test]
+    fn test_streaming_resent_dropped_segment_closes_gap() {
+        let (manifest, device_pub) = signed_manifest();
+        let mut verifier = StreamingVerifier::new(&manifest, &device_pub).unwrap();
+
+        verifier.push_segment(segment(0));
+        verifier.push_segment(segment(MAX_PENDING + 1));
+        for index in 1..=MAX_PENDING {
+            verifier.push_segment(segment(index));
+        }
+        // The sender retransmits the dropped segment once the window has caught up
+        let status = verifier.push_segment(segment(MAX_PENDING + 1));
+        assert_eq!(status.next_index, MAX_PENDING + 2);
+        assert!(!status.in_order);
+
+        let report = verifier.finalize();
+        assert_eq!(report.first_gap_index, None);
+        assert_eq!(report.segments, MAX_PENDING + 2);
+        assert_eq!(
+            report.out_of_order,
+            Some(OutOfOrder {
+                expected: 1,
+                found: MAX_PENDING + 1
+            })
+        );
+        assert_eq!(
+            report.chain_tip,
+            Some(batch_tip(&manifest, &device_pub, MAX_PENDING + 2))
+        );
+    }
+}
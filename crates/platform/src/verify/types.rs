@@ -12,6 +12,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use super::engine::{SegmentDigest, VerifyReport};
+use super::validation::ValidationError;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -21,6 +22,10 @@ pub struct VerifyRequest {
     pub manifest: Value,
     pub segments: Vec<SegmentDigest>,
     pub options: Option<VerifyOptions>,
+    /// URL the `VerifyResponse` is POSTed to once verification completes; must
+    /// not point at a loopback, private or link-local address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -38,6 +43,38 @@ pub struct VerifyResponse {
     pub receipt: Option<String>,
 }
 
+/// One entry of a batch verification response, in request order.
+///
+/// `status` is the HTTP status the request would have received on its own;
+/// exactly one of `response` and `error` is present.
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BatchVerifyItem {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<VerifyResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ValidationError>,
+}
+
+impl BatchVerifyItem {
+    pub fn verified(response: VerifyResponse) -> Self {
+        Self {
+            status: 200,
+            response: Some(response),
+            error: None,
+        }
+    }
+
+    pub fn failed(status: u16, error: ValidationError) -> Self {
+        Self {
+            status,
+            response: None,
+            error: Some(error),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HealthResponse {
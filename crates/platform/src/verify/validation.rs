@@ -193,6 +193,7 @@ mod tests {
             manifest: serde_json::json!({}),
             segments: vec![],
             options: None,
+            callback_url: None,
         };
 
         let result = validate_verify_request(&request);
@@ -210,6 +211,7 @@ mod tests {
             manifest: serde_json::json!({}),
             segments,
             options: None,
+            callback_url: None,
         };
 
         let result = validate_verify_request(&request);
@@ -227,6 +229,7 @@ mod tests {
             manifest: serde_json::json!({}),
             segments,
             options: None,
+            callback_url: None,
         };
 
         let result = validate_verify_request(&request);
@@ -256,6 +259,7 @@ mod tests {
             manifest: serde_json::json!({}),
             segments,
             options: None,
+            callback_url: None,
         };
 
         let result = validate_verify_request(&request);
@@ -273,6 +277,7 @@ mod tests {
             manifest: serde_json::json!({}),
             segments,
             options: None,
+            callback_url: None,
         };
 
         let result = validate_verify_request(&request);
@@ -293,6 +298,7 @@ mod tests {
             manifest: serde_json::json!({"version": "1.0"}),
             segments: vec![],
             options: None,
+            callback_url: None,
         };
 
         let result = validate_verify_request_full(&request);
@@ -309,6 +315,7 @@ mod tests {
             manifest: serde_json::json!({"version": "1.0"}),
             segments: vec![create_test_segment(0, valid_hash())],
             options: None,
+            callback_url: None,
         };
 
         let result = validate_verify_request_full(&request);
@@ -325,6 +332,7 @@ mod tests {
             manifest: serde_json::Value::Null,
             segments: vec![create_test_segment(0, valid_hash())],
             options: None,
+            callback_url: None,
         };
 
         let result = validate_verify_request_full(&request);
@@ -341,6 +349,7 @@ mod tests {
             manifest: serde_json::json!({}),
             segments: vec![create_test_segment(0, valid_hash())],
             options: None,
+            callback_url: None,
         };
 
         let result = validate_verify_request_full(&request);
@@ -363,6 +372,7 @@ mod tests {
                 ),
             ],
             options: None,
+            callback_url: None,
         };
 
         let result = validate_verify_request_full(&request);
@@ -377,6 +387,7 @@ mod tests {
             manifest: serde_json::json!({"version": "1.0"}),
             segments: vec![],
             options: None,
+            callback_url: None,
         };
 
         let result = validate_verify_request_full(&request);
@@ -25,9 +25,13 @@
 
 use axum_test::TestServer;
 use sealedge_platform::{
-    database::{create_api_key, create_connection_pool, create_organization, run_migrations},
+    database::{
+        create_api_key, create_connection_pool, create_organization, get_receipt_by_id,
+        list_receipts_by_device, run_migrations, store_receipt,
+    },
     http::{auth::generate_token, auth::hash_token_for_storage, handlers::create_test_app},
 };
+use sealedge_types::receipt::VerificationReceipt;
 use serde_json::json;
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -87,6 +91,19 @@ async fn test_auth_middleware() {
         .await;
 
     assert_eq!(response.status_code(), 200);
+
+    // The audit log is a read API like receipts and needs the token too
+    let response = server.get("/v1/audit").await;
+    assert_eq!(response.status_code(), 401);
+
+    let response = server
+        .get("/v1/audit")
+        .add_header(
+            "Authorization".parse().unwrap(),
+            format!("Bearer {}", token).parse().unwrap(),
+        )
+        .await;
+    assert_eq!(response.status_code(), 200);
 }
 
 #[tokio::test]
@@ -375,3 +392,97 @@ async fn test_verify_unknown_fields_returns_400() {
 
     assert_eq!(response.status_code(), 400);
 }
+
+fn sample_receipt(device_id: &str) -> VerificationReceipt {
+    VerificationReceipt {
+        verification_id: format!("v_{}", Uuid::new_v4().simple()),
+        profile: "cam.video".to_string(),
+        device_id: device_id.to_string(),
+        manifest_digest: format!("b3:{}", "ab".repeat(32)),
+        segments: 3,
+        duration_s: 6.5,
+        signature: "pass".to_string(),
+        continuity: "pass".to_string(),
+        issued_at: "2025-01-01T00:00:00Z".to_string(),
+        service_kid: "kid-1".to_string(),
+        chain_tip: format!("b3:{}", "cd".repeat(32)),
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_receipt_store_and_lookup_round_trip() {
+    let (pool, org_id, _token) = setup_test_db().await;
+
+    let receipt = sample_receipt(&format!("device-{}", Uuid::new_v4()));
+    store_receipt(&pool, org_id, &receipt).await.unwrap();
+
+    let stored = get_receipt_by_id(&pool, org_id, &receipt.verification_id)
+        .await
+        .unwrap()
+        .expect("stored receipt must be found");
+    assert_eq!(
+        serde_json::to_value(&stored).unwrap(),
+        serde_json::to_value(&receipt).unwrap()
+    );
+
+    // Duplicate verification IDs are rejected
+    assert!(store_receipt(&pool, org_id, &receipt).await.is_err());
+
+    // Receipts are scoped to their organization
+    let other_org = create_organization(&pool, "Other Org", "free")
+        .await
+        .unwrap();
+    assert!(
+        get_receipt_by_id(&pool, other_org, &receipt.verification_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_list_receipts_by_device_paginates() {
+    let (pool, org_id, _token) = setup_test_db().await;
+
+    let device_id = format!("device-{}", Uuid::new_v4());
+    let mut stored_ids = Vec::new();
+    for _ in 0..5 {
+        let receipt = sample_receipt(&device_id);
+        store_receipt(&pool, org_id, &receipt).await.unwrap();
+        stored_ids.push(receipt.verification_id);
+    }
+    // Another device's receipt must not show up
+    store_receipt(&pool, org_id, &sample_receipt("some-other-device"))
+        .await
+        .unwrap();
+
+    let first = list_receipts_by_device(&pool, org_id, &device_id, 2, 0)
+        .await
+        .unwrap();
+    let second = list_receipts_by_device(&pool, org_id, &device_id, 2, 2)
+        .await
+        .unwrap();
+    let third = list_receipts_by_device(&pool, org_id, &device_id, 2, 4)
+        .await
+        .unwrap();
+    assert_eq!(
+        (first.len(), second.len(), third.len()),
+        (2, 2, 1),
+        "5 receipts must page as 2 + 2 + 1"
+    );
+
+    let mut listed_ids: Vec<String> = first
+        .iter()
+        .chain(&second)
+        .chain(&third)
+        .map(|r| {
+            assert_eq!(r.device_id, device_id);
+            r.verification_id.clone()
+        })
+        .collect();
+    listed_ids.sort();
+    stored_ids.sort();
+    assert_eq!(listed_ids, stored_ids, "pages must not overlap or skip");
+}
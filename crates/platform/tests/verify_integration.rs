@@ -46,6 +46,8 @@ mod http_tests {
         AppState {
             keys: Arc::new(RwLock::new(key_manager)),
             receipt_ttl_secs: 3600,
+            audit: Default::default(),
+            webhooks: Default::default(),
         }
     }
 
@@ -1370,6 +1372,437 @@ mod http_tests {
             "with no trusted proxies, peer IP 127.0.0.1 must be rate-limited regardless of XFF"
         );
     }
+
+    // -----------------------------------------------------------------------
+    // Test 19: /metrics counts verifications, signature failures and
+    //          continuity failures.
+    //
+    // The registry is process-wide and other tests verify concurrently, so the
+    // assertions are on lower bounds of the deltas between two scrapes.
+    // -----------------------------------------------------------------------
+
+    async fn scrape_metrics(app: &axum::Router) -> String {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        assert!(response.headers()[CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain"));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    fn metric_value(text: &str, name: &str) -> f64 {
+        text.lines()
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix(' '))
+            .unwrap_or_else(|| panic!("metric {name} missing from scrape"))
+            .parse()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_metrics_reflect_verifications() -> Result<()> {
+        // Keep the rate limiter at its default while this test issues requests
+        let _lock = ENV_MUTEX.lock().await;
+        let app = create_test_app().await;
+        let before = scrape_metrics(&app).await;
+
+        let signing_key = ed25519_dalek::SigningKey::generate(&mut rand_core::OsRng);
+        let wrong_key = ed25519_dalek::SigningKey::generate(&mut rand_core::OsRng);
+        let (signed_manifest, device_pub) = build_signed_manifest(&signing_key);
+        let wrong_pub = format!(
+            "ed25519:{}",
+            BASE64.encode(wrong_key.verifying_key().as_bytes())
+        );
+
+        // Segment index 1 without index 0 breaks continuity
+        let mut gapped: serde_json::Value =
+            serde_json::from_slice(&build_verify_body(&signed_manifest, &device_pub, false))?;
+        gapped["segments"][0]["index"] = json!(1);
+
+        let bodies = [
+            build_verify_body(&signed_manifest, &device_pub, false),
+            build_verify_body(&signed_manifest, &wrong_pub, false),
+            serde_json::to_vec(&gapped)?,
+        ];
+        for body in bodies {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/v1/verify")
+                        .header(CONTENT_TYPE, "application/json")
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), axum::http::StatusCode::OK);
+        }
+
+        let after = scrape_metrics(&app).await;
+        let delta = |name: &str| metric_value(&after, name) - metric_value(&before, name);
+        assert!(delta("sealedge_verifications_total") >= 3.0);
+        assert!(delta("sealedge_signature_failures_total") >= 1.0);
+        assert!(delta("sealedge_continuity_failures_total") >= 1.0);
+        assert!(delta("sealedge_verify_time_ms_count") >= 3.0);
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Audit log: every verification is appended to the state's log and can be
+    // read back, with the hash chain checked, from GET /v1/audit.
+    // -----------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn test_audit_log_records_verifications() -> Result<()> {
+        // Keep the rate limiter at its default while this test issues requests
+        let _lock = ENV_MUTEX.lock().await;
+        let state = make_state();
+        let app = create_router(state.clone());
+
+        let signing_key = ed25519_dalek::SigningKey::generate(&mut rand_core::OsRng);
+        let wrong_key = ed25519_dalek::SigningKey::generate(&mut rand_core::OsRng);
+        let (signed_manifest, device_pub) = build_signed_manifest(&signing_key);
+        let wrong_pub = format!(
+            "ed25519:{}",
+            BASE64.encode(wrong_key.verifying_key().as_bytes())
+        );
+        for body in [
+            build_verify_body(&signed_manifest, &device_pub, false),
+            build_verify_body(&signed_manifest, &wrong_pub, false),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/v1/verify")
+                        .header(CONTENT_TYPE, "application/json")
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), axum::http::StatusCode::OK);
+        }
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/audit?from=1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let audit: serde_json::Value = serde_json::from_slice(&body)?;
+
+        assert_eq!(audit["intact"], json!(true));
+        assert_eq!(audit["next_sequence"], json!(2));
+        assert_eq!(audit["entries"].as_array().unwrap().len(), 1);
+        assert_eq!(audit["entries"][0]["result"], json!("fail"));
+        assert_eq!(audit["head"], json!(state.audit.lock().unwrap().head()));
+
+        // A checkpoint past the end of the log reports it as truncated
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/audit?next_sequence=3&head=b3:x")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let audit: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(audit["intact"], json!(false));
+        assert!(audit["error"].as_str().unwrap().contains("truncated"));
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Test 20: Batch verify — per-item results in request order, with an empty
+    //          device key rejected in its own item only.
+    // -----------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn test_verify_batch_preserves_order_and_isolates_failures() -> Result<()> {
+        let app = create_test_app().await;
+
+        let first_key = ed25519_dalek::SigningKey::generate(&mut rand_core::OsRng);
+        let second_key = ed25519_dalek::SigningKey::generate(&mut rand_core::OsRng);
+        let (first_manifest, first_pub) = build_signed_manifest(&first_key);
+        let (second_manifest, second_pub) = build_signed_manifest(&second_key);
+
+        let request = |manifest: &serde_json::Value, device_pub: &str| -> serde_json::Value {
+            serde_json::from_slice(&build_verify_body(manifest, device_pub, false)).unwrap()
+        };
+        // A second segment tells the last item's report apart from the first
+        let mut two_segments = request(&second_manifest, &second_pub);
+        two_segments["segments"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({
+                "index": 1,
+                "hash": "b3:abcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890"
+            }));
+        let batch = json!([
+            request(&first_manifest, &first_pub),
+            request(&first_manifest, ""),
+            two_segments,
+        ]);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/verify/batch")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(serde_json::to_vec(&batch)?))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let items: serde_json::Value = serde_json::from_slice(&body)?;
+        let items = items.as_array().expect("batch response must be an array");
+        assert_eq!(items.len(), 3);
+
+        let statuses: Vec<u64> = items
+            .iter()
+            .map(|i| i["status"].as_u64().unwrap())
+            .collect();
+        assert_eq!(statuses, [200, 400, 200]);
+
+        for item in [&items[0], &items[2]] {
+            assert!(
+                item["response"]["result"]["signature_verification"]["passed"]
+                    .as_bool()
+                    .unwrap()
+            );
+            assert!(item.get("error").is_none());
+        }
+        assert_eq!(items[1]["error"]["error"], "invalid_device_pub");
+        assert!(items[1].get("response").is_none());
+
+        assert_eq!(
+            items[0]["response"]["result"]["metadata"]["total_segments"],
+            1
+        );
+        assert_eq!(
+            items[2]["response"]["result"]["metadata"]["total_segments"],
+            2
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify_batch_rejects_empty_batch() {
+        let app = create_test_app().await;
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/verify/batch")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from("[]"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_verify_batch_charges_rate_limit_per_item() {
+        let _lock = ENV_MUTEX.lock().await;
+        unsafe {
+            std::env::set_var("RATE_LIMIT_RPS", "1");
+            std::env::set_var("RATE_LIMIT_BURST", "4");
+        }
+        let app = create_router(make_state());
+        unsafe {
+            std::env::remove_var("RATE_LIMIT_RPS");
+            std::env::remove_var("RATE_LIMIT_BURST");
+        }
+
+        let item = json!({"manifest": {}, "segments": [], "device_pub": "ed25519:x"});
+        let send_batch = |count: usize| {
+            let batch = serde_json::Value::Array(vec![item.clone(); count]);
+            app.clone().oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/verify/batch")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(serde_json::to_vec(&batch).unwrap()))
+                    .unwrap(),
+            )
+        };
+
+        // More items than the bucket can ever hold is refused outright
+        let response = send_batch(5).await.unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+
+        // Three items cost three of the four tokens; a second batch is throttled
+        let response = send_batch(3).await.unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        let response = send_batch(3).await.unwrap();
+        assert_eq!(response.status(), 429);
+        assert!(response.headers().contains_key("retry-after"));
+    }
+
+    // -----------------------------------------------------------------------
+    // Test 21: Webhook — a request with callback_url gets its VerifyResponse
+    //          POSTed there, signed with the service key, and a failed first
+    //          delivery is retried.
+    // -----------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn test_verify_callback_url_receives_signed_webhook() -> Result<()> {
+        use axum::{body::Bytes, http::HeaderMap, routing::post};
+        use ed25519_dalek::Verifier;
+        use sealedge_platform::http::webhook::{
+            WebhookDispatcher, KEY_ID_HEADER, SIGNATURE_HEADER,
+        };
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Mock receiver: fails the first delivery, then records the retry
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<(HeaderMap, Bytes)>();
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let receiver = axum::Router::new().route(
+            "/hook",
+            post({
+                let attempts = attempts.clone();
+                move |headers: HeaderMap, body: Bytes| async move {
+                    if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                        return axum::http::StatusCode::SERVICE_UNAVAILABLE;
+                    }
+                    tx.send((headers, body)).unwrap();
+                    axum::http::StatusCode::OK
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let callback_url = format!("http://{}/hook", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, receiver).await.unwrap() });
+
+        // The receiver listens on loopback, which the default dispatcher refuses
+        let mut state = make_state();
+        state.webhooks = WebhookDispatcher::default().allow_private_targets();
+        let (service_kid, service_key) = {
+            let keys = state.keys.read().await;
+            (
+                keys.current_kid(),
+                keys.current_signing_key().verifying_key(),
+            )
+        };
+        let app = create_router(state);
+
+        let signing_key = ed25519_dalek::SigningKey::generate(&mut rand_core::OsRng);
+        let (signed_manifest, device_pub) = build_signed_manifest(&signing_key);
+        let mut body: serde_json::Value =
+            serde_json::from_slice(&build_verify_body(&signed_manifest, &device_pub, true))?;
+        body["callback_url"] = json!(callback_url);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/verify")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(serde_json::to_vec(&body)?))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        let response_body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response_json: serde_json::Value = serde_json::from_slice(&response_body)?;
+
+        let (headers, delivered) =
+            tokio::time::timeout(std::time::Duration::from_secs(10), rx.recv())
+                .await
+                .expect("webhook was not delivered")
+                .unwrap();
+        assert!(
+            attempts.load(Ordering::SeqCst) >= 2,
+            "first failure must be retried"
+        );
+
+        assert_eq!(headers[KEY_ID_HEADER].to_str()?, service_kid);
+        let signature = BASE64.decode(headers[SIGNATURE_HEADER].to_str()?)?;
+        let signature = ed25519_dalek::Signature::from_slice(&signature)?;
+        service_key
+            .verify(&delivered, &signature)
+            .expect("webhook signature must verify against the service key");
+
+        let delivered_json: serde_json::Value = serde_json::from_slice(&delivered)?;
+        assert_eq!(delivered_json, response_json);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify_rejects_non_http_callback_url() -> Result<()> {
+        let signing_key = ed25519_dalek::SigningKey::generate(&mut rand_core::OsRng);
+        let (signed_manifest, device_pub) = build_signed_manifest(&signing_key);
+
+        for callback_url in [
+            "file:///etc/passwd",
+            "http://127.0.0.1:8080/hook",
+            "http://169.254.169.254/latest/meta-data/",
+        ] {
+            let app = create_test_app().await;
+            let mut body: serde_json::Value =
+                serde_json::from_slice(&build_verify_body(&signed_manifest, &device_pub, false))?;
+            body["callback_url"] = json!(callback_url);
+
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/v1/verify")
+                        .header(CONTENT_TYPE, "application/json")
+                        .body(Body::from(serde_json::to_vec(&body)?))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                response.status(),
+                axum::http::StatusCode::BAD_REQUEST,
+                "{callback_url} must be rejected"
+            );
+        }
+
+        Ok(())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -0,0 +1,30 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! BLAKE3 continuity chain hashing.
+
+/// Genesis seed for the continuity chain
+const GENESIS_SEED: &[u8] = b"sealedge:genesis";
+
+/// Compute BLAKE3 hash of segment ciphertext
+pub fn segment_hash(ciphertext: &[u8]) -> [u8; 32] {
+    blake3::hash(ciphertext).into()
+}
+
+/// Compute next continuity chain hash: BLAKE3(prev||curr)
+pub fn chain_next(prev: &[u8; 32], curr: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(prev);
+    hasher.update(curr);
+    hasher.finalize().into()
+}
+
+/// Compute genesis continuity hash: BLAKE3(GENESIS_SEED)
+pub fn genesis() -> [u8; 32] {
+    blake3::hash(GENESIS_SEED).into()
+}
@@ -0,0 +1,17 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Injected time source.
+//!
+//! Devices implement [`Clock`] over their RTC; `sealedge-core` supplies the
+//! system wall clock and a mock clock for tests.
+
+/// Source of the current time in seconds since the UNIX epoch
+pub trait Clock: Send + Sync {
+    fn now_secs(&self) -> u64;
+}
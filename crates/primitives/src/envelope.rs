@@ -0,0 +1,443 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Envelope key schedule and chunk crypto.
+//!
+//! One X25519 + HKDF-SHA256 derivation per envelope yields the chunk key and an
+//! 8-byte nonce prefix. Each chunk is then encrypted under a counter nonce
+//! `nonce_prefix[0..8] || chunk_index (3 bytes BE) || last_flag` with AAD from
+//! [`build_aad`], and its manifest is signed over [`manifest_signing_digest`].
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use aes_gcm::aead::{AeadInPlace, KeyInit};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use hkdf::Hkdf;
+use rand_core::{CryptoRng, RngCore};
+use sha2::Sha256;
+
+use crate::{PrimitiveError, NONCE_LEN};
+
+/// Maximum chunk index that fits in the 3-byte nonce field (2^24 - 1).
+pub const MAX_CHUNK_INDEX: u64 = 16_777_215;
+
+/// AAD length: header hash, sequence, nonce, manifest hash and chunk length
+pub const AAD_LEN: usize = 32 + 8 + NONCE_LEN + 32 + 4;
+
+/// HKDF info binding derived keys to the sealedge envelope context
+const HKDF_INFO: &[u8] = b"SEALEDGE_ENVELOPE_V1";
+
+/// BLAKE3 key-derivation context for chunk signatures that carry a binding
+const BOUND_MANIFEST_CONTEXT: &str = "SEALEDGE_ENVELOPE_V3_CHUNK_MANIFEST";
+
+/// Draw a per-envelope HKDF salt from the caller's RNG
+pub fn random_salt<R: RngCore + CryptoRng>(rng: &mut R) -> [u8; 32] {
+    let mut salt = [0u8; 32];
+    rng.fill_bytes(&mut salt);
+    salt
+}
+
+/// Derive shared encryption key material via X25519 ECDH key agreement and HKDF-SHA256.
+///
+/// Converts Ed25519 keys to X25519 using the standard conversion path
+/// documented by `ed25519-dalek`: `SigningKey::to_scalar_bytes()` →
+/// `x25519_dalek::StaticSecret`, and `VerifyingKey::to_montgomery()` →
+/// `x25519_dalek::PublicKey`, then derives as [`derive_x25519_encryption_key`].
+///
+/// Returns `(encryption_key, nonce_prefix)`.
+pub fn derive_shared_encryption_key(
+    my_private_key: &SigningKey,
+    their_public_key: &VerifyingKey,
+    salt: &[u8; 32],
+) -> Result<([u8; 32], [u8; 8]), PrimitiveError> {
+    // Convert Ed25519 keys to X25519 using the standard conversion path
+    let x25519_secret = x25519_dalek::StaticSecret::from(my_private_key.to_scalar_bytes());
+    let x25519_public = x25519_dalek::PublicKey::from(their_public_key.to_montgomery().to_bytes());
+
+    derive_x25519_encryption_key(&x25519_secret, &x25519_public, salt)
+}
+
+/// Derive shared encryption key material from an X25519 key pair via ECDH and HKDF-SHA256.
+///
+/// The raw ECDH shared secret is fed as IKM into HKDF-Extract (RFC 5869), then
+/// HKDF-Expand derives 40 bytes of output key material:
+///   - bytes 0..32 → AES-256-GCM encryption key
+///   - bytes 32..40 → 8-byte nonce prefix for deterministic per-chunk nonce construction
+///
+/// DH commutativity guarantees both sides derive the same key:
+///   sender_secret.diffie_hellman(recipient_pub) == recipient_secret.diffie_hellman(sender_pub)
+///
+/// Returns `(encryption_key, nonce_prefix)`.
+pub fn derive_x25519_encryption_key(
+    x25519_secret: &x25519_dalek::StaticSecret,
+    x25519_public: &x25519_dalek::PublicKey,
+    salt: &[u8; 32],
+) -> Result<([u8; 32], [u8; 8]), PrimitiveError> {
+    use zeroize::Zeroize;
+
+    // Standard X25519 Diffie-Hellman key agreement
+    let shared_secret = x25519_secret.diffie_hellman(x25519_public);
+
+    // Reject low-order points (all-zero shared secret = contributory behavior failure)
+    if shared_secret.as_bytes().iter().all(|&b| b == 0) {
+        return Err(PrimitiveError::KeyAgreement);
+    }
+
+    // HKDF-Extract: extract pseudorandom key from ECDH shared secret
+    // Salt provides randomness; IKM is the raw ECDH output (NOT concatenated with other data)
+    let hkdf = Hkdf::<Sha256>::new(Some(salt), shared_secret.as_bytes());
+
+    // HKDF-Expand: derive 40 bytes of output key material with domain separation.
+    // Layout: bytes 0..32 = AES-256-GCM encryption key, bytes 32..40 = 8-byte nonce prefix.
+    let mut okm = [0u8; 40];
+    hkdf.expand(HKDF_INFO, &mut okm)
+        .map_err(|_| PrimitiveError::KeyDerivation)?;
+
+    let mut encryption_key = [0u8; 32];
+    let mut nonce_prefix = [0u8; 8];
+    encryption_key.copy_from_slice(&okm[0..32]);
+    nonce_prefix.copy_from_slice(&okm[32..40]);
+    okm.zeroize();
+
+    Ok((encryption_key, nonce_prefix))
+}
+
+/// Build the counter nonce for chunk `sequence`
+///
+/// Layout: bytes 0..8 = `nonce_prefix`, bytes 8..11 = low 3 bytes of the index as
+/// BE u32, byte 11 = `0xFF` on the last chunk and `0x00` otherwise.
+pub fn chunk_nonce(
+    nonce_prefix: &[u8; 8],
+    sequence: u64,
+    is_last_chunk: bool,
+) -> Result<[u8; NONCE_LEN], PrimitiveError> {
+    if sequence > MAX_CHUNK_INDEX {
+        return Err(PrimitiveError::ChunkIndexOverflow(sequence));
+    }
+
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce[0..8].copy_from_slice(nonce_prefix);
+    let idx_be = (sequence as u32).to_be_bytes();
+    nonce[8..11].copy_from_slice(&idx_be[1..4]);
+    nonce[11] = if is_last_chunk { 0xFF } else { 0x00 };
+    Ok(nonce)
+}
+
+/// Build Additional Authenticated Data (AAD) for encryption
+/// AAD = header_hash(32) || seq_be(8) || nonce(12) || manifest_hash(32) || chunk_len_be(4)
+pub fn build_aad(
+    header_hash: &[u8; 32],
+    seq: u64,
+    nonce: &[u8; NONCE_LEN],
+    manifest_hash: &[u8; 32],
+    chunk_len: u32,
+) -> [u8; AAD_LEN] {
+    let mut aad = [0u8; AAD_LEN];
+    let mut off = 0;
+    aad[off..off + 32].copy_from_slice(header_hash);
+    off += 32;
+    aad[off..off + 8].copy_from_slice(&seq.to_be_bytes());
+    off += 8;
+    aad[off..off + NONCE_LEN].copy_from_slice(nonce);
+    off += NONCE_LEN;
+    aad[off..off + 32].copy_from_slice(manifest_hash);
+    off += 32;
+    aad[off..off + 4].copy_from_slice(&chunk_len.to_be_bytes());
+    aad
+}
+
+/// AEAD used for envelope chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkAead {
+    Aes256Gcm,
+    ChaCha20Poly1305,
+}
+
+/// A chunk AEAD keyed with the envelope encryption key
+pub enum ChunkCipher {
+    // Boxed: the expanded AES key schedule is ~1 KB
+    Aes256Gcm(Box<aes_gcm::Aes256Gcm>),
+    ChaCha20Poly1305(chacha20poly1305::ChaCha20Poly1305),
+}
+
+impl ChunkCipher {
+    pub fn new(aead: ChunkAead, key: &[u8; 32]) -> Self {
+        match aead {
+            ChunkAead::Aes256Gcm => Self::Aes256Gcm(Box::new(aes_gcm::Aes256Gcm::new(key.into()))),
+            ChunkAead::ChaCha20Poly1305 => {
+                Self::ChaCha20Poly1305(chacha20poly1305::ChaCha20Poly1305::new(key.into()))
+            }
+        }
+    }
+
+    /// Encrypt `buffer` in place, appending the tag
+    pub fn encrypt_in_place(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        aad: &[u8],
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), PrimitiveError> {
+        match self {
+            Self::Aes256Gcm(cipher) => cipher.encrypt_in_place(nonce.into(), aad, buffer),
+            Self::ChaCha20Poly1305(cipher) => cipher.encrypt_in_place(nonce.into(), aad, buffer),
+        }
+        .map_err(|_| PrimitiveError::Encryption)
+    }
+
+    /// Authenticate and decrypt `buffer` in place, stripping the tag
+    pub fn decrypt_in_place(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        aad: &[u8],
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), PrimitiveError> {
+        match self {
+            Self::Aes256Gcm(cipher) => cipher.decrypt_in_place(nonce.into(), aad, buffer),
+            Self::ChaCha20Poly1305(cipher) => cipher.decrypt_in_place(nonce.into(), aad, buffer),
+        }
+        .map_err(|_| PrimitiveError::Decryption)
+    }
+}
+
+/// Digest signed for each chunk manifest
+///
+/// With a binding (every version 3 envelope) the digest covers the manifest and
+/// the envelope's metadata binding, so no metadata field can be edited without
+/// breaking every chunk signature. Legacy version 2 envelopes and streams sign the
+/// plain manifest hash.
+pub fn manifest_signing_digest(manifest_bytes: &[u8], binding: Option<&[u8; 32]>) -> blake3::Hash {
+    match binding {
+        None => blake3::hash(manifest_bytes),
+        Some(binding) => blake3::Hasher::new_derive_key(BOUND_MANIFEST_CONTEXT)
+            .update(binding)
+            .update(manifest_bytes)
+            .finalize(),
+    }
+}
+
+/// Sign a chunk manifest over [`manifest_signing_digest`]
+pub fn sign_manifest(
+    signing_key: &SigningKey,
+    manifest_bytes: &[u8],
+    binding: Option<&[u8; 32]>,
+) -> Signature {
+    signing_key.sign(manifest_signing_digest(manifest_bytes, binding).as_bytes())
+}
+
+/// Check a chunk manifest signature made by [`sign_manifest`]
+pub fn verify_manifest(
+    verifying_key: &VerifyingKey,
+    manifest_bytes: &[u8],
+    binding: Option<&[u8; 32]>,
+    signature: &Signature,
+) -> bool {
+    verifying_key
+        .verify(
+            manifest_signing_digest(manifest_bytes, binding).as_bytes(),
+            signature,
+        )
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Clock;
+
+    /// Deterministic stand-in for a device hardware RNG
+    struct CounterRng(u8);
+
+    impl RngCore for CounterRng {
+        fn next_u32(&mut self) -> u32 {
+            rand_core::impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            rand_core::impls::next_u64_via_fill(self)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for byte in dest {
+                self.0 = self.0.wrapping_add(1);
+                *byte = self.0;
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for CounterRng {}
+
+    /// Stand-in for a device RTC
+    struct FixedClock(u64);
+
+    impl Clock for FixedClock {
+        fn now_secs(&self) -> u64 {
+            self.0
+        }
+    }
+
+    const PAYLOAD: &[u8] = b"frame 0001: 1920x1080 @ 30fps";
+    const CHUNK_SIZE: usize = 8;
+
+    /// Manifest bytes standing in for the envelope's bincode chunk manifest
+    fn manifest(sequence: u64, created_at: u64) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[0..8].copy_from_slice(&sequence.to_le_bytes());
+        bytes[8..16].copy_from_slice(&created_at.to_le_bytes());
+        bytes
+    }
+
+    struct SealedChunk {
+        sequence: u64,
+        nonce: [u8; NONCE_LEN],
+        manifest: [u8; 16],
+        signature: Signature,
+        ciphertext: Vec<u8>,
+    }
+
+    fn seal(
+        aead: ChunkAead,
+        sender: &SigningKey,
+        recipient: &VerifyingKey,
+        salt: &[u8; 32],
+        clock: &dyn Clock,
+    ) -> Vec<SealedChunk> {
+        let (key, nonce_prefix) = derive_shared_encryption_key(sender, recipient, salt).unwrap();
+        let cipher = ChunkCipher::new(aead, &key);
+        let header_hash = *blake3::hash(b"ENVELOPE_V1").as_bytes();
+        let chunk_count = PAYLOAD.len().div_ceil(CHUNK_SIZE);
+
+        PAYLOAD
+            .chunks(CHUNK_SIZE)
+            .enumerate()
+            .map(|(i, data)| {
+                let sequence = i as u64;
+                let nonce = chunk_nonce(&nonce_prefix, sequence, i + 1 == chunk_count).unwrap();
+                let manifest = manifest(sequence, clock.now_secs());
+                let aad = build_aad(
+                    &header_hash,
+                    sequence,
+                    &nonce,
+                    blake3::hash(&manifest).as_bytes(),
+                    data.len() as u32,
+                );
+                let mut ciphertext = data.to_vec();
+                cipher
+                    .encrypt_in_place(&nonce, &aad, &mut ciphertext)
+                    .unwrap();
+                SealedChunk {
+                    sequence,
+                    nonce,
+                    signature: sign_manifest(sender, &manifest, None),
+                    manifest,
+                    ciphertext,
+                }
+            })
+            .collect()
+    }
+
+    fn open(
+        chunks: &[SealedChunk],
+        aead: ChunkAead,
+        recipient: &SigningKey,
+        sender: &VerifyingKey,
+        salt: &[u8; 32],
+    ) -> Result<Vec<u8>, PrimitiveError> {
+        let (key, nonce_prefix) = derive_shared_encryption_key(recipient, sender, salt)?;
+        let cipher = ChunkCipher::new(aead, &key);
+        let header_hash = *blake3::hash(b"ENVELOPE_V1").as_bytes();
+
+        let mut payload = Vec::new();
+        for (i, chunk) in chunks.iter().enumerate() {
+            if !verify_manifest(sender, &chunk.manifest, None, &chunk.signature) {
+                return Err(PrimitiveError::Decryption);
+            }
+            let nonce = chunk_nonce(&nonce_prefix, chunk.sequence, i + 1 == chunks.len())?;
+            if nonce != chunk.nonce {
+                return Err(PrimitiveError::Decryption);
+            }
+            let mut plaintext = chunk.ciphertext.clone();
+            let aad = build_aad(
+                &header_hash,
+                chunk.sequence,
+                &nonce,
+                blake3::hash(&chunk.manifest).as_bytes(),
+                (plaintext.len() - 16) as u32,
+            );
+            cipher.decrypt_in_place(&nonce, &aad, &mut plaintext)?;
+            payload.extend_from_slice(&plaintext);
+        }
+        Ok(payload)
+    }
+
+    fn keys() -> (SigningKey, SigningKey) {
+        (
+            SigningKey::from_bytes(&[1u8; 32]),
+            SigningKey::from_bytes(&[2u8; 32]),
+        )
+    }
+
+    #[test]
+    fn test_seal_open_with_injected_rng_and_clock() {
+        let (camera, verifier) = keys();
+        let clock = FixedClock(1_700_000_000);
+
+        for aead in [ChunkAead::Aes256Gcm, ChunkAead::ChaCha20Poly1305] {
+            let salt = random_salt(&mut CounterRng(0));
+            let chunks = seal(aead, &camera, &verifier.verifying_key(), &salt, &clock);
+            assert!(chunks.len() > 1);
+            assert_eq!(chunks[0].manifest, manifest(0, 1_700_000_000));
+            assert_eq!(
+                open(&chunks, aead, &verifier, &camera.verifying_key(), &salt).unwrap(),
+                PAYLOAD
+            );
+
+            // The same RNG state and clock reproduce the same ciphertext
+            let again = seal(aead, &camera, &verifier.verifying_key(), &salt, &clock);
+            assert_eq!(chunks[0].ciphertext, again[0].ciphertext);
+        }
+    }
+
+    #[test]
+    fn test_open_rejects_tampering_and_wrong_recipient() {
+        let (camera, verifier) = keys();
+        let clock = FixedClock(1_700_000_000);
+        let salt = random_salt(&mut CounterRng(0));
+        let aead = ChunkAead::Aes256Gcm;
+
+        let mut chunks = seal(aead, &camera, &verifier.verifying_key(), &salt, &clock);
+        assert_eq!(
+            open(&chunks, aead, &camera, &camera.verifying_key(), &salt),
+            Err(PrimitiveError::Decryption)
+        );
+
+        chunks[0].ciphertext[0] ^= 0x01;
+        assert_eq!(
+            open(&chunks, aead, &verifier, &camera.verifying_key(), &salt),
+            Err(PrimitiveError::Decryption)
+        );
+    }
+
+    #[test]
+    fn test_chunk_nonce_layout_and_bound() {
+        let prefix = [0xAB; 8];
+        let nonce = chunk_nonce(&prefix, 0x0102_0304, true);
+        assert_eq!(nonce, Err(PrimitiveError::ChunkIndexOverflow(0x0102_0304)));
+
+        let nonce = chunk_nonce(&prefix, MAX_CHUNK_INDEX, true).unwrap();
+        assert_eq!(&nonce[0..8], &prefix);
+        assert_eq!(&nonce[8..11], &[0xFF, 0xFF, 0xFF]);
+        assert_eq!(nonce[11], 0xFF);
+        assert_eq!(chunk_nonce(&prefix, 1, false).unwrap()[8..12], [0, 0, 1, 0]);
+    }
+}
@@ -0,0 +1,41 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+use core::fmt;
+
+/// Errors from the sealing primitives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrimitiveError {
+    /// X25519 agreement produced an all-zero shared secret
+    KeyAgreement,
+    /// HKDF could not expand the requested output length
+    KeyDerivation,
+    /// The chunk index does not fit the 3-byte nonce counter
+    ChunkIndexOverflow(u64),
+    /// AEAD encryption failed
+    Encryption,
+    /// AEAD authentication failed
+    Decryption,
+}
+
+impl fmt::Display for PrimitiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrimitiveError::KeyAgreement => write!(f, "ECDH produced zero shared secret"),
+            PrimitiveError::KeyDerivation => write!(f, "HKDF expand failed"),
+            PrimitiveError::ChunkIndexOverflow(index) => write!(
+                f,
+                "Chunk index {index} exceeds maximum (16,777,215) — envelope too large for current nonce format"
+            ),
+            PrimitiveError::Encryption => write!(f, "Encryption failed"),
+            PrimitiveError::Decryption => write!(f, "Decryption failed"),
+        }
+    }
+}
+
+impl core::error::Error for PrimitiveError {}
@@ -0,0 +1,43 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! # Sealedge Primitives
+//!
+//! The pure cryptographic core of sealedge envelopes and archives, built with
+//! only `core` and `alloc` so camera MCUs can seal data on-device.
+//!
+//! `sealedge-core` re-exports these items and builds its std-only envelope,
+//! archive and transport layers on top of them, so a chunk sealed here opens
+//! with [`sealedge_core::Envelope`]'s key schedule and vice versa. Nothing in
+//! this crate reads the system clock or an OS RNG: time comes from a
+//! [`Clock`] and randomness from a caller-supplied [`rand_core`] RNG.
+//!
+//! ## Modules
+//!
+//! - `envelope` - X25519 + HKDF key schedule, counter nonces, chunk AEAD and
+//!   manifest signing digests
+//! - `chain` - BLAKE3 continuity chain hashing
+//! - `clock` - Injected time source
+//!
+//! [`sealedge_core::Envelope`]: https://docs.rs/sealedge-core/latest/sealedge_core/envelope/struct.Envelope.html
+
+#![no_std]
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc)]
+
+extern crate alloc;
+
+pub mod chain;
+pub mod clock;
+pub mod envelope;
+pub mod error;
+
+pub use clock::Clock;
+pub use error::PrimitiveError;
+
+/// AEAD nonce length shared by AES-256-GCM and ChaCha20-Poly1305
+pub const NONCE_LEN: usize = 12;
@@ -12,20 +12,15 @@ use std::fs;
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
 
-use base64::Engine as _;
-
 use anyhow::{Context, Result};
 use blake3::Hasher;
 use chrono::{DateTime, SecondsFormat, Utc};
 use clap::{Args, Parser, Subcommand};
-use rand::prelude::*;
-use rand_chacha::ChaCha20Rng;
 use sealedge_core::{
-    chain_next, decrypt_segment, derive_chunk_key, encrypt_segment, generate_aad, genesis,
-    is_encrypted_key_file, read_archive, segment_hash, sign_manifest, validate_archive,
-    verify_manifest, write_archive, AudioMetadata, CamVideoMetadata, ChunkInfo, DeviceInfo,
-    DeviceKeypair, GenericMetadata, LogMetadata, PointAttestation, ProfileMetadata, SegmentInfo,
-    SensorMetadata, TrstManifest,
+    check_archive, decrypt_segment, derive_chunk_key, generate_aad, is_encrypted_key_file,
+    read_archive, validate_archive, verify_manifest, wrap_file, ArchiveCheckFailure, AudioMetadata,
+    CamVideoMetadata, ClaimSet, DeviceKeypair, GenericMetadata, LogMetadata, PointAttestation,
+    ProfileMetadata, SensorMetadata, WrapConfig,
 };
 use serde::Serialize;
 use std::time::Instant;
@@ -33,6 +28,8 @@ use std::time::Instant;
 // SegmentRef, VerifyOptions, VerifyRequest use the shared canonical definitions.
 use sealedge_types::verification::{SegmentRef, VerifyOptions, VerifyRequest};
 
+#[cfg(feature = "yubikey")]
+use base64::Engine as _;
 #[cfg(feature = "yubikey")]
 use p256::pkcs8::DecodePublicKey;
 #[cfg(feature = "yubikey")]
@@ -43,6 +40,8 @@ use sealedge_core::backends::universal::{
 use sealedge_core::backends::yubikey::YubiKeyConfig;
 #[cfg(feature = "yubikey")]
 use sealedge_core::backends::YubiKeyBackend;
+#[cfg(feature = "yubikey")]
+use sealedge_core::{wrap_file_with_signer, TrstManifest};
 
 /// Emit a security warning when --unencrypted is used.
 fn warn_unencrypted() {
@@ -120,7 +119,7 @@ struct WrapCmd {
     input: PathBuf,
     #[arg(long = "out", value_name = "PATH", help = "Output .seal directory")]
     output: PathBuf,
-    /// Archive profile. Defaults to "generic". Use "cam.video" for video capture archives.
+    /// Archive profile. Defaults to "generic". Use "cam.video" or "cam.audio" for capture archives.
     #[arg(long, default_value = "generic")]
     profile: String,
     #[arg(long, default_value_t = 1_048_576)]
@@ -164,8 +163,8 @@ struct WrapCmd {
     /// MIME type for generic profile
     #[arg(long, help = "MIME type (generic profile)")]
     mime_type: Option<String>,
-    /// Sample rate in Hz (sensor or audio profile)
-    #[arg(long, help = "Sample rate in Hz (sensor or audio profile)")]
+    /// Sample rate in Hz (sensor or cam.audio profile)
+    #[arg(long, help = "Sample rate in Hz (sensor or cam.audio profile)")]
     sample_rate: Option<f64>,
     /// Measurement unit (sensor profile: celsius, psi, rpm, etc.)
     #[arg(long, help = "Measurement unit (sensor profile)")]
@@ -182,14 +181,14 @@ struct WrapCmd {
     /// Altitude for geo-tagged sensor data
     #[arg(long, help = "Altitude in meters (sensor profile, optional)")]
     altitude: Option<f64>,
-    /// Bit depth (audio profile: 16, 24, 32)
-    #[arg(long, help = "Bit depth (audio profile)")]
+    /// Bit depth (cam.audio profile: 16, 24, 32)
+    #[arg(long, help = "Bit depth (cam.audio profile)")]
     bit_depth: Option<u16>,
-    /// Number of audio channels (audio profile: 1=mono, 2=stereo)
-    #[arg(long, help = "Number of channels (audio profile)")]
+    /// Number of audio channels (cam.audio profile: 1=mono, 2=stereo)
+    #[arg(long, help = "Number of channels (cam.audio profile)")]
     channels: Option<u8>,
-    /// Audio codec (audio profile: pcm, opus, aac)
-    #[arg(long, help = "Audio codec (audio profile)")]
+    /// Audio codec (cam.audio profile: pcm, opus, aac)
+    #[arg(long, help = "Audio codec (cam.audio profile)")]
     codec: Option<String>,
     /// Application name (log profile: nginx, syslog, etc.)
     #[arg(long, help = "Application name (log profile)")]
@@ -350,36 +349,6 @@ struct VerifyAttestationCmd {
     sbom: Option<PathBuf>,
 }
 
-fn generate_seeded_nonce24(rng: &mut dyn RngCore) -> [u8; 24] {
-    let mut nonce = [0u8; 24];
-    rng.fill_bytes(&mut nonce);
-    nonce
-}
-
-/// Derive a device_id string from a prefixed public key string.
-///
-/// Extracts the first 6 bytes of the raw key bytes (after the prefix) and formats
-/// them as "te:cam:<hex>". Works for both "ed25519:<base64>" and "ecdsa-p256:<base64>" formats.
-fn pub_key_to_device_id(pub_key_str: &str) -> Result<String> {
-    let raw_b64 = if let Some(rest) = pub_key_str.strip_prefix("ed25519:") {
-        rest
-    } else if let Some(rest) = pub_key_str.strip_prefix("ecdsa-p256:") {
-        rest
-    } else {
-        anyhow::bail!("Unrecognized public key prefix in: {}", pub_key_str);
-    };
-    let key_bytes = base64::engine::general_purpose::STANDARD
-        .decode(raw_b64)
-        .with_context(|| "Failed to decode public key bytes for device_id")?;
-    if key_bytes.len() < 6 {
-        anyhow::bail!(
-            "Public key bytes too short for device_id (got {} bytes)",
-            key_bytes.len()
-        );
-    }
-    Ok(format!("te:cam:{}", hex::encode(&key_bytes[..6])))
-}
-
 #[tokio::main]
 async fn main() {
     // run() returns before std::process::exit, so all local variables (including key
@@ -489,26 +458,31 @@ fn handle_wrap(args: WrapCmd) -> Result<()> {
             args.chunk_size
         );
     }
+    if args.chunk_size == 0 {
+        anyhow::bail!("--chunk-size must be greater than zero");
+    }
 
     // Validate backend-specific requirements up front
-    if args.backend == "yubikey" && args.device_key.is_none() {
-        anyhow::bail!(
+    match args.backend.as_str() {
+        "software" => {}
+        "yubikey" if args.device_key.is_none() => anyhow::bail!(
             "--device-key is required with --backend yubikey (used for chunk encryption)"
-        );
+        ),
+        "yubikey" => {}
+        other => anyhow::bail!("Unknown backend '{}'. Use 'software' or 'yubikey'", other),
     }
 
     let (device_keypair, secret_path, public_path, generated) =
         load_or_generate_keypair(args.device_key.as_deref(), args.unencrypted)?;
 
-    // Read input file
-    let input_data = fs::read(&args.input)
-        .with_context(|| format!("Failed to read input file: {}", args.input.display()))?;
-
-    if input_data.is_empty() {
+    let input_len = fs::metadata(&args.input)
+        .with_context(|| format!("Failed to read input file: {}", args.input.display()))?
+        .len();
+    if input_len == 0 {
         anyhow::bail!("Input file is empty");
     }
+    let chunk_count = input_len.div_ceil(args.chunk_size as u64) as usize;
 
-    // Create output directory
     let archive_name = args
         .output
         .file_name()
@@ -519,32 +493,12 @@ fn handle_wrap(args: WrapCmd) -> Result<()> {
         anyhow::bail!("Output directory must end with .seal");
     }
 
-    fs::create_dir_all(&args.output)?;
-    fs::create_dir_all(args.output.join("chunks"))?;
-    fs::create_dir_all(args.output.join("signatures"))?;
-
-    // Initialize RNG - seeded if provided, otherwise use default
-    let mut rng: Box<dyn RngCore> = match args.seed {
-        Some(seed) => Box::new(ChaCha20Rng::seed_from_u64(seed)),
-        None => Box::new(rand::rng()),
-    };
-
     // Resolve chunk_seconds: cam.video default 2.0, generic default 0.0
     let chunk_seconds = match args.profile.as_str() {
         "cam.video" => args.chunk_seconds.unwrap_or(2.0),
         _ => args.chunk_seconds.unwrap_or(0.0),
     };
 
-    // Process chunks
-    let chunks = input_data.chunks(args.chunk_size).collect::<Vec<_>>();
-    let mut segments = Vec::new();
-    let mut chain_state = genesis();
-    let mut encrypted_chunks = Vec::new();
-
-    // Derive encryption key from device signing key via HKDF-SHA256.
-    // For yubikey backend, the software device key is still used for chunk encryption.
-    let encryption_key = derive_chunk_key(device_keypair.secret_bytes());
-
     // Create timestamp for all operations - deterministic if seeded
     let started_at = if args.seed.is_some() {
         // Use deterministic timestamp for seeded runs
@@ -553,56 +507,12 @@ fn handle_wrap(args: WrapCmd) -> Result<()> {
         current_timestamp()?
     };
 
-    // Compute device_id from the initial software public key (will be overridden for yubikey
-    // after we obtain the hardware public key below)
-    let initial_pub_str = &device_keypair.public;
-    let device_id = pub_key_to_device_id(initial_pub_str)?;
-
-    for (i, chunk_data) in chunks.iter().enumerate() {
-        let chunk_id = i as u32;
-
-        // Generate nonce - seeded if provided
-        let nonce = generate_seeded_nonce24(&mut *rng);
-        let aad = generate_aad("0.1.0", &args.profile, &device_id, &started_at);
-        let encrypted_data = encrypt_segment(&encryption_key, &nonce, chunk_data, &aad)?;
-
-        // Prepend the 24-byte nonce to the ciphertext so unwrap can decrypt:
-        // on-disk format is [nonce:24][ciphertext:N]
-        let mut chunk_with_nonce = Vec::with_capacity(24 + encrypted_data.len());
-        chunk_with_nonce.extend_from_slice(&nonce);
-        chunk_with_nonce.extend_from_slice(&encrypted_data);
-
-        // Hash nonce+ciphertext to match what validate_archive reads from disk
-        let hash = segment_hash(&chunk_with_nonce);
-        encrypted_chunks.push(chunk_with_nonce);
-        let next_state = chain_next(&chain_state, &hash);
-
-        // Build start_time: time-based for cam.video, index-based for generic
-        let start_time = if args.profile == "cam.video" {
-            format!("{:.3}s", i as f64 * chunk_seconds)
-        } else {
-            format!("segment-{}", i)
-        };
-        let chunk_filename = format!("{:05}.bin", chunk_id);
-
-        let segment = SegmentInfo {
-            chunk_file: chunk_filename,
-            blake3_hash: hex::encode(hash),
-            start_time,
-            duration_seconds: chunk_seconds,
-            continuity_hash: hex::encode(next_state),
-        };
-
-        segments.push(segment);
-        chain_state = next_state;
-    }
-
     // Build profile metadata and compute end time
     let metadata = match args.profile.as_str() {
         "cam.video" => {
             let fps = args.fps.unwrap_or(30);
-            let capture_end_time = if !chunks.is_empty() {
-                let last_chunk_start = (chunks.len() - 1) as f64 * chunk_seconds;
+            let capture_end_time = if chunk_count > 0 {
+                let last_chunk_start = (chunk_count - 1) as f64 * chunk_seconds;
                 let end_timestamp = chrono::DateTime::parse_from_rfc3339(&started_at)?
                     + chrono::Duration::milliseconds((last_chunk_start * 1000.0) as i64)
                     + chrono::Duration::milliseconds((chunk_seconds * 1000.0) as i64);
@@ -641,19 +551,19 @@ fn handle_wrap(args: WrapCmd) -> Result<()> {
                 labels: BTreeMap::new(),
             })
         }
-        "audio" => {
-            let sample_rate = args
-                .sample_rate
-                .ok_or_else(|| anyhow::anyhow!("--sample-rate is required for audio profile"))?;
+        "cam.audio" => {
+            let sample_rate = args.sample_rate.ok_or_else(|| {
+                anyhow::anyhow!("--sample-rate is required for cam.audio profile")
+            })?;
             let bit_depth = args
                 .bit_depth
-                .ok_or_else(|| anyhow::anyhow!("--bit-depth is required for audio profile"))?;
+                .ok_or_else(|| anyhow::anyhow!("--bit-depth is required for cam.audio profile"))?;
             let channels = args
                 .channels
-                .ok_or_else(|| anyhow::anyhow!("--channels is required for audio profile"))?;
+                .ok_or_else(|| anyhow::anyhow!("--channels is required for cam.audio profile"))?;
             let codec = args
                 .codec
-                .ok_or_else(|| anyhow::anyhow!("--codec is required for audio profile"))?;
+                .ok_or_else(|| anyhow::anyhow!("--codec is required for cam.audio profile"))?;
             ProfileMetadata::Audio(AudioMetadata {
                 started_at: started_at.clone(),
                 ended_at: started_at.clone(),
@@ -699,158 +609,37 @@ fn handle_wrap(args: WrapCmd) -> Result<()> {
         }
     };
 
-    // Determine signing public key string and signature based on backend
-    let (signing_public_key, signature) = match args.backend.as_str() {
-        "software" => {
-            let pub_key = device_keypair.public.clone();
-            let canonical_bytes = {
-                // Build a temporary manifest to get canonical bytes for signing
-                let tmp = TrstManifest {
-                    trst_version: "0.1.0".to_string(),
-                    profile: args.profile.clone(),
-                    device: DeviceInfo {
-                        id: device_id.clone(),
-                        model: "TrustEdgeRefCam".to_string(),
-                        firmware_version: "1.0.0".to_string(),
-                        public_key: pub_key.clone(),
-                    },
-                    metadata: metadata.clone(),
-                    chunk: ChunkInfo {
-                        size_bytes: args.chunk_size as u64,
-                        duration_seconds: chunk_seconds,
-                    },
-                    segments: segments.clone(),
-                    claims: vec!["location:unknown".to_string()],
-                    prev_archive_hash: None,
-                    signature: None,
-                };
-                tmp.to_canonical_bytes()?
-            };
-            let sig = sign_manifest(&device_keypair, &canonical_bytes)?;
-            (pub_key, sig)
-        }
+    let mut config = WrapConfig::new(metadata);
+    config.chunk_size = args.chunk_size;
+    config.chunk_seconds = chunk_seconds;
+    config.seed = args.seed;
+
+    // Chunks are always encrypted under a key derived from the software device key;
+    // the yubikey backend only moves the manifest signature into hardware.
+    let manifest = match args.backend.as_str() {
         "yubikey" => {
             #[cfg(feature = "yubikey")]
             {
-                // Prompt for PIN interactively without echoing
-                let pin =
-                    rpassword::prompt_password("YubiKey PIN: ").context("Failed to read PIN")?;
-                let config = YubiKeyConfig::builder()
-                    .pin(pin)
-                    .default_slot(args.slot.clone())
-                    .build();
-                let backend = YubiKeyBackend::with_config(config)
-                    .map_err(|e| anyhow::anyhow!("Failed to connect to YubiKey: {}", e))?;
-
-                // Extract public key from hardware slot (DER-encoded SPKI)
-                let pub_key_result = backend
-                    .perform_operation(&args.slot, CryptoOperation::GetPublicKey)
-                    .map_err(|e| anyhow::anyhow!("Failed to get YubiKey public key: {}", e))?;
-                let der_bytes = match pub_key_result {
-                    CryptoResult::PublicKey(b) => b,
-                    _ => anyhow::bail!("Unexpected result from GetPublicKey"),
-                };
-
-                // Parse SPKI DER to SEC1 uncompressed point bytes
-                let p256_pub = p256::PublicKey::from_public_key_der(&der_bytes)
-                    .map_err(|e| anyhow::anyhow!("Failed to parse P-256 public key: {}", e))?;
-                let sec1_bytes = p256_pub.to_sec1_bytes();
-                let pub_key_str = format!(
-                    "ecdsa-p256:{}",
-                    base64::engine::general_purpose::STANDARD.encode(sec1_bytes.as_ref())
-                );
-
-                // Build canonical bytes with the P-256 public key and updated device_id
-                let yk_device_id = pub_key_to_device_id(&pub_key_str)?;
-                let canonical_bytes = {
-                    let tmp = TrstManifest {
-                        trst_version: "0.1.0".to_string(),
-                        profile: args.profile.clone(),
-                        device: DeviceInfo {
-                            id: yk_device_id,
-                            model: "TrustEdgeRefCam".to_string(),
-                            firmware_version: "1.0.0".to_string(),
-                            public_key: pub_key_str.clone(),
-                        },
-                        metadata: metadata.clone(),
-                        chunk: ChunkInfo {
-                            size_bytes: args.chunk_size as u64,
-                            duration_seconds: chunk_seconds,
-                        },
-                        segments: segments.clone(),
-                        claims: vec!["location:unknown".to_string()],
-                        prev_archive_hash: None,
-                        signature: None,
-                    };
-                    tmp.to_canonical_bytes()?
-                };
-
-                // Sign with YubiKey hardware (ECDSA P-256)
-                let sign_result = backend
-                    .perform_operation(
-                        &args.slot,
-                        CryptoOperation::Sign {
-                            data: canonical_bytes,
-                            algorithm: SignatureAlgorithm::EcdsaP256,
-                        },
-                    )
-                    .map_err(|e| anyhow::anyhow!("YubiKey signing failed: {}", e))?;
-                let sig_bytes = match sign_result {
-                    CryptoResult::Signed(b) => b,
-                    _ => anyhow::bail!("Unexpected result from Sign operation"),
-                };
-                let sig_str = format!(
-                    "ecdsa-p256:{}",
-                    base64::engine::general_purpose::STANDARD.encode(&sig_bytes)
-                );
-
-                (pub_key_str, sig_str)
+                wrap_with_yubikey(
+                    &args.input,
+                    &args.output,
+                    &args.slot,
+                    &device_keypair,
+                    &config,
+                )?
             }
             #[cfg(not(feature = "yubikey"))]
             {
                 anyhow::bail!("YubiKey support requires building with --features yubikey");
             }
         }
-        other => anyhow::bail!("Unknown backend '{}'. Use 'software' or 'yubikey'", other),
-    };
-
-    // Recompute device_id from the final signing public key (handles yubikey override)
-    let final_device_id = pub_key_to_device_id(&signing_public_key)?;
-
-    // Build the TrstManifest with the final public key and device_id
-    let signed_manifest = TrstManifest {
-        trst_version: "0.1.0".to_string(),
-        profile: args.profile.clone(),
-        device: DeviceInfo {
-            id: final_device_id,
-            model: "TrustEdgeRefCam".to_string(),
-            firmware_version: "1.0.0".to_string(),
-            public_key: signing_public_key,
-        },
-        metadata,
-        chunk: ChunkInfo {
-            size_bytes: args.chunk_size as u64,
-            duration_seconds: chunk_seconds,
-        },
-        segments,
-        claims: vec!["location:unknown".to_string()],
-        prev_archive_hash: None,
-        signature: Some(signature.clone()),
+        _ => wrap_file(&args.input, &args.output, &device_keypair, &config)?,
     };
 
-    // Write archive
-    let detached_sig = signature.as_bytes();
-    write_archive(
-        &args.output,
-        &signed_manifest,
-        encrypted_chunks,
-        detached_sig,
-    )?;
-
     let result = WrapResult {
         output_dir: args.output,
-        signature,
-        chunk_count: chunks.len(),
+        signature: manifest.signature.unwrap_or_default(),
+        chunk_count: manifest.segments.len(),
     };
 
     println!("Archive: {}", result.output_dir.display());
@@ -864,6 +653,71 @@ fn handle_wrap(args: WrapCmd) -> Result<()> {
     Ok(())
 }
 
+/// Wrap with the manifest signed by the YubiKey PIV key in `slot` (ECDSA P-256)
+#[cfg(feature = "yubikey")]
+fn wrap_with_yubikey(
+    input: &Path,
+    output: &Path,
+    slot: &str,
+    device_keypair: &DeviceKeypair,
+    config: &WrapConfig,
+) -> Result<TrstManifest> {
+    // Prompt for PIN interactively without echoing
+    let pin = rpassword::prompt_password("YubiKey PIN: ").context("Failed to read PIN")?;
+    let yubikey_config = YubiKeyConfig::builder()
+        .pin(pin)
+        .default_slot(slot.to_string())
+        .build();
+    let backend = YubiKeyBackend::with_config(yubikey_config)
+        .map_err(|e| anyhow::anyhow!("Failed to connect to YubiKey: {}", e))?;
+
+    // Extract public key from hardware slot (DER-encoded SPKI)
+    let pub_key_result = backend
+        .perform_operation(slot, CryptoOperation::GetPublicKey)
+        .map_err(|e| anyhow::anyhow!("Failed to get YubiKey public key: {}", e))?;
+    let der_bytes = match pub_key_result {
+        CryptoResult::PublicKey(b) => b,
+        _ => anyhow::bail!("Unexpected result from GetPublicKey"),
+    };
+
+    // Parse SPKI DER to SEC1 uncompressed point bytes
+    let p256_pub = p256::PublicKey::from_public_key_der(&der_bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to parse P-256 public key: {}", e))?;
+    let sec1_bytes = p256_pub.to_sec1_bytes();
+    let pub_key_str = format!(
+        "ecdsa-p256:{}",
+        base64::engine::general_purpose::STANDARD.encode(sec1_bytes.as_ref())
+    );
+
+    wrap_file_with_signer(
+        input,
+        output,
+        device_keypair,
+        &pub_key_str,
+        config,
+        |canonical_bytes| {
+            // Sign with YubiKey hardware (ECDSA P-256)
+            let sign_result = backend
+                .perform_operation(
+                    slot,
+                    CryptoOperation::Sign {
+                        data: canonical_bytes.to_vec(),
+                        algorithm: SignatureAlgorithm::EcdsaP256,
+                    },
+                )
+                .map_err(|e| anyhow::anyhow!("YubiKey signing failed: {}", e))?;
+            let sig_bytes = match sign_result {
+                CryptoResult::Signed(b) => b,
+                _ => anyhow::bail!("Unexpected result from Sign operation"),
+            };
+            Ok(format!(
+                "ecdsa-p256:{}",
+                base64::engine::general_purpose::STANDARD.encode(&sig_bytes)
+            ))
+        },
+    )
+}
+
 fn handle_verify(args: VerifyCmd) -> Result<()> {
     let start_time = Instant::now();
 
@@ -871,32 +725,12 @@ fn handle_verify(args: VerifyCmd) -> Result<()> {
     let mut report = VerifyReport::default();
 
     // Handle IO/Schema errors (exit 12)
-    let (manifest, _chunks) = match read_archive(&args.archive) {
-        Ok(data) => data,
+    let check = match check_archive(&args.archive, &args.device_pub) {
+        Ok(check) => check,
         Err(e) => {
             report.error = Some(format!("Archive read failed: {}", e));
             report.verify_time_ms = start_time.elapsed().as_millis() as u64;
-
-            // Map error types to human messages
-            let first_line = match e {
-                sealedge_core::archive::ArchiveError::MissingChunk(_) => "Missing chunk file",
-                sealedge_core::archive::ArchiveError::UnreferencedChunk(_) => {
-                    "Unreferenced chunk file"
-                }
-                sealedge_core::archive::ArchiveError::InvalidChunkIndex { .. } => {
-                    "Missing chunk file"
-                }
-                sealedge_core::archive::ArchiveError::Json(_) => "Invalid manifest format",
-                sealedge_core::archive::ArchiveError::SignatureMismatch => {
-                    "Signature verification failed"
-                }
-                sealedge_core::archive::ArchiveError::Io(_) => "Archive read error",
-                sealedge_core::archive::ArchiveError::SchemaMismatch(_) => "Schema error",
-                sealedge_core::archive::ArchiveError::Manifest(_) => "Manifest error",
-                sealedge_core::archive::ArchiveError::Chain(_) => "Continuity chain error",
-                sealedge_core::archive::ArchiveError::ValidationFailed(_) => "Validation error",
-            };
-
+            let first_line = e.summary();
             output_error(&args, &report, first_line)?;
             return Err(CliExitError {
                 code: 12,
@@ -905,14 +739,7 @@ fn handle_verify(args: VerifyCmd) -> Result<()> {
             .into());
         }
     };
-
-    // Parse device public key: pass through recognized prefixes, default bare keys to ed25519
-    let device_pub_key =
-        if args.device_pub.starts_with("ed25519:") || args.device_pub.starts_with("ecdsa-p256:") {
-            args.device_pub.clone()
-        } else {
-            format!("ed25519:{}", args.device_pub)
-        };
+    let manifest = check.manifest;
 
     // Populate report with manifest data
     report.profile = manifest.profile.clone();
@@ -924,108 +751,66 @@ fn handle_verify(args: VerifyCmd) -> Result<()> {
         .map(|s| s.duration_seconds as f32)
         .sum();
 
-    // Check for signature presence (schema error)
-    let signature = match manifest.signature.as_ref() {
-        Some(sig) => sig,
+    let (code, first_line) = match check.failure {
         None => {
-            report.signature = "fail".to_string();
-            report.continuity = "skip".to_string();
-            report.error = Some("Manifest missing signature".to_string());
+            report.signature = "pass".to_string();
+            report.continuity = "pass".to_string();
             report.verify_time_ms = start_time.elapsed().as_millis() as u64;
-            output_error(&args, &report, "Manifest missing signature")?;
-            return Err(CliExitError {
-                code: 12,
-                message: "Manifest missing signature".to_string(),
-            }
-            .into());
+            output_success(&args, &report, &manifest.claims)?;
+            return Ok(());
         }
-    };
-
-    // Get canonical bytes (internal error if this fails)
-    let canonical_bytes = match manifest.to_canonical_bytes() {
-        Ok(bytes) => bytes,
-        Err(e) => {
-            report.signature = "fail".to_string();
-            report.continuity = "skip".to_string();
+        // Missing signature is a schema error (exit 12)
+        Some(ArchiveCheckFailure::MissingSignature) => {
+            report.error = Some("Manifest missing signature".to_string());
+            (12, "Manifest missing signature")
+        }
+        Some(ArchiveCheckFailure::Canonicalization(e)) => {
             report.error = Some(format!("Canonical serialization failed: {}", e));
-            report.verify_time_ms = start_time.elapsed().as_millis() as u64;
-            output_error(&args, &report, "Internal canonicalization error")?;
-            return Err(CliExitError {
-                code: 14,
-                message: "Internal canonicalization error".to_string(),
-            }
-            .into());
+            (14, "Internal canonicalization error")
         }
-    };
-
-    // Verify signature (exit 10 on failure)
-    match verify_manifest(&device_pub_key, &canonical_bytes, signature) {
-        Ok(true) => {
+        // Signature failure (exit 10)
+        Some(ArchiveCheckFailure::Signature(error)) => {
+            report.error = Some(error);
+            (10, "Signature verification failed")
+        }
+        // Archive structure and continuity failure (exit 11)
+        Some(ArchiveCheckFailure::Continuity(e)) => {
             report.signature = "pass".to_string();
-
-            // Validate archive structure and continuity (exit 11 on failure)
-            match validate_archive(&args.archive) {
-                Ok(()) => {
-                    report.continuity = "pass".to_string();
-                }
-                Err(e) => {
-                    report.continuity = "fail".to_string();
-                    let error_msg = format!("{}", e);
-                    report.error = Some(error_msg.clone());
-
-                    // Extract structured information from chain errors
-                    if let sealedge_core::archive::ArchiveError::Chain(chain_err) = &e {
-                        match chain_err {
-                            sealedge_core::chain::ChainError::Gap(index) => {
-                                report.first_gap_index = Some(*index as u32);
-                            }
-                            sealedge_core::chain::ChainError::OutOfOrder { .. } => {
-                                report.out_of_order = Some(true);
-                            }
-                            _ => {} // Other chain errors don't have specific structured data
-                        }
+            report.continuity = "fail".to_string();
+            report.error = Some(e.to_string());
+
+            // Extract structured information from chain errors
+            if let sealedge_core::archive::ArchiveError::Chain(chain_err) = &e {
+                match chain_err {
+                    sealedge_core::chain::ChainError::Gap(index) => {
+                        report.first_gap_index = Some(*index as u32);
                     }
-
-                    report.verify_time_ms = start_time.elapsed().as_millis() as u64;
-                    output_continuity_error(&args, &report)?;
-                    return Err(CliExitError {
-                        code: 11,
-                        message: "Continuity chain verification failed".to_string(),
+                    sealedge_core::chain::ChainError::OutOfOrder { .. } => {
+                        report.out_of_order = Some(true);
                     }
-                    .into());
+                    _ => {} // Other chain errors don't have specific structured data
                 }
             }
-        }
-        Ok(false) => {
-            report.signature = "fail".to_string();
-            report.continuity = "skip".to_string();
-            report.error = Some("Signature verification failed".to_string());
-            report.verify_time_ms = start_time.elapsed().as_millis() as u64;
-            output_error(&args, &report, "Signature verification failed")?;
-            return Err(CliExitError {
-                code: 10,
-                message: "Signature verification failed".to_string(),
-            }
-            .into());
-        }
-        Err(e) => {
-            report.signature = "fail".to_string();
-            report.continuity = "skip".to_string();
-            report.error = Some(format!("Signature verification error: {}", e));
+
             report.verify_time_ms = start_time.elapsed().as_millis() as u64;
-            output_error(&args, &report, "Signature verification failed")?;
+            output_continuity_error(&args, &report)?;
             return Err(CliExitError {
-                code: 10,
-                message: "Signature verification failed".to_string(),
+                code: 11,
+                message: "Continuity chain verification failed".to_string(),
             }
             .into());
         }
-    }
+    };
 
-    // Success case
+    report.signature = "fail".to_string();
+    report.continuity = "skip".to_string();
     report.verify_time_ms = start_time.elapsed().as_millis() as u64;
-    output_success(&args, &report)?;
-    Ok(())
+    output_error(&args, &report, first_line)?;
+    Err(CliExitError {
+        code,
+        message: first_line.to_string(),
+    }
+    .into())
 }
 
 fn handle_unwrap(args: UnwrapCmd) -> Result<()> {
@@ -1136,7 +921,7 @@ fn handle_unwrap(args: UnwrapCmd) -> Result<()> {
     Ok(())
 }
 
-fn output_success(args: &VerifyCmd, report: &VerifyReport) -> Result<()> {
+fn output_success(args: &VerifyCmd, report: &VerifyReport, claims: &[String]) -> Result<()> {
     if args.json {
         let json_output = serde_json::to_string(report)?;
         println!("{}", json_output);
@@ -1153,6 +938,9 @@ fn output_success(args: &VerifyCmd, report: &VerifyReport) -> Result<()> {
                 0.0
             }
         );
+        if let Some(line) = claims_line(claims) {
+            println!("{}", line);
+        }
     }
 
     // Emit receipt if requested
@@ -1164,6 +952,18 @@ fn output_success(args: &VerifyCmd, report: &VerifyReport) -> Result<()> {
     Ok(())
 }
 
+/// Summary line for the manifest claims, `None` when there are none
+///
+/// Claims are checked at wrap time; a manifest whose claims do not parse is
+/// reported here but does not fail verification.
+fn claims_line(claims: &[String]) -> Option<String> {
+    match ClaimSet::parse(claims) {
+        Ok(set) if set.is_empty() => None,
+        Ok(set) => Some(format!("Claims: {}", set)),
+        Err(e) => Some(format!("Claims: invalid ({})", e)),
+    }
+}
+
 fn output_error(args: &VerifyCmd, report: &VerifyReport, first_line: &str) -> Result<()> {
     if args.json {
         let json_output = serde_json::to_string(report)?;
@@ -564,7 +564,7 @@ fn acceptance_audio_wrap_verify() {
         .args([
             "wrap",
             "--profile",
-            "audio",
+            "cam.audio",
             "--in",
             input.to_str().unwrap(),
             "--out",
@@ -586,7 +586,7 @@ fn acceptance_audio_wrap_verify() {
 
     let manifest_json = fs::read_to_string(archive_dir.join("manifest.json")).unwrap();
     let manifest_value: serde_json::Value = serde_json::from_str(&manifest_json).unwrap();
-    assert_eq!(manifest_value["profile"], "audio");
+    assert_eq!(manifest_value["profile"], "cam.audio");
     assert_eq!(
         manifest_value["metadata"]["sample_rate_hz"], 44100,
         "sample_rate_hz must be present in audio manifest metadata"
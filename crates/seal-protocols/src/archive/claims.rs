@@ -0,0 +1,226 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Typed view of the manifest `claims` list.
+//!
+//! On the wire each claim stays a `kind:value` string, so the manifest's
+//! canonical bytes are unchanged. [`ClaimSet::parse`] checks the known kinds
+//! and accepts extensions whose kind starts with `x-`; any other kind is
+//! rejected so a typo like `locaton:lab` cannot slip into a signed manifest.
+
+use std::fmt;
+use thiserror::Error;
+
+/// Prefix that marks a free-form extension claim kind
+pub const EXTENSION_PREFIX: &str = "x-";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ClaimError {
+    #[error("Claim '{0}' is not of the form kind:value")]
+    Malformed(String),
+    #[error("Unknown claim kind '{0}' (extensions must start with '{EXTENSION_PREFIX}')")]
+    UnknownKind(String),
+    #[error("Invalid value for claim '{kind}': {reason}")]
+    InvalidValue { kind: String, reason: String },
+    #[error("Claim '{0}' appears more than once")]
+    Duplicate(String),
+}
+
+/// One parsed manifest claim
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Claim {
+    /// Where the capture took place, e.g. `location:lab`
+    Location(String),
+    /// URL of the RFC 3161 timestamp authority that stamped the archive
+    TimestampAuthority(String),
+    /// Identifier of the operator running the device
+    OperatorId(String),
+    /// Free-form claim with an `x-` kind, passed through unchecked
+    Extension { kind: String, value: String },
+}
+
+impl Claim {
+    /// The `kind` part of the wire form
+    pub fn kind(&self) -> &str {
+        match self {
+            Claim::Location(_) => "location",
+            Claim::TimestampAuthority(_) => "timestamp_authority",
+            Claim::OperatorId(_) => "operator_id",
+            Claim::Extension { kind, .. } => kind,
+        }
+    }
+
+    /// The `value` part of the wire form
+    pub fn value(&self) -> &str {
+        match self {
+            Claim::Location(value)
+            | Claim::TimestampAuthority(value)
+            | Claim::OperatorId(value)
+            | Claim::Extension { value, .. } => value,
+        }
+    }
+
+    /// Parse one `kind:value` string
+    pub fn parse(claim: &str) -> Result<Self, ClaimError> {
+        let (kind, value) = claim
+            .split_once(':')
+            .filter(|(kind, value)| !kind.is_empty() && !value.is_empty())
+            .ok_or_else(|| ClaimError::Malformed(claim.to_string()))?;
+        let invalid = |reason: &str| ClaimError::InvalidValue {
+            kind: kind.to_string(),
+            reason: reason.to_string(),
+        };
+
+        match kind {
+            "location" => Ok(Claim::Location(value.to_string())),
+            "timestamp_authority" => {
+                if !(value.starts_with("https://") || value.starts_with("http://")) {
+                    return Err(invalid("must be an http(s) URL"));
+                }
+                Ok(Claim::TimestampAuthority(value.to_string()))
+            }
+            "operator_id" => {
+                if value.chars().any(char::is_whitespace) {
+                    return Err(invalid("must not contain whitespace"));
+                }
+                Ok(Claim::OperatorId(value.to_string()))
+            }
+            _ if is_extension_kind(kind) => Ok(Claim::Extension {
+                kind: kind.to_string(),
+                value: value.to_string(),
+            }),
+            _ => Err(ClaimError::UnknownKind(kind.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Claim {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.kind(), self.value())
+    }
+}
+
+/// `x-` followed by lowercase letters, digits, `-`, `_` or `.`
+fn is_extension_kind(kind: &str) -> bool {
+    kind.strip_prefix(EXTENSION_PREFIX).is_some_and(|name| {
+        !name.is_empty()
+            && name.chars().all(|c| {
+                c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_' | '.')
+            })
+    })
+}
+
+/// Validated set of manifest claims, in manifest order
+///
+/// Each kind may appear at most once.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ClaimSet {
+    claims: Vec<Claim>,
+}
+
+impl ClaimSet {
+    /// Parse and validate a manifest `claims` list
+    pub fn parse<S: AsRef<str>>(claims: &[S]) -> Result<Self, ClaimError> {
+        let mut parsed: Vec<Claim> = Vec::with_capacity(claims.len());
+        for claim in claims {
+            let claim = Claim::parse(claim.as_ref())?;
+            if parsed.iter().any(|c| c.kind() == claim.kind()) {
+                return Err(ClaimError::Duplicate(claim.kind().to_string()));
+            }
+            parsed.push(claim);
+        }
+        Ok(Self { claims: parsed })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Claim> {
+        self.claims.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.claims.is_empty()
+    }
+
+    pub fn location(&self) -> Option<&str> {
+        self.find(|c| matches!(c, Claim::Location(_)))
+    }
+
+    pub fn timestamp_authority(&self) -> Option<&str> {
+        self.find(|c| matches!(c, Claim::TimestampAuthority(_)))
+    }
+
+    pub fn operator_id(&self) -> Option<&str> {
+        self.find(|c| matches!(c, Claim::OperatorId(_)))
+    }
+
+    /// Value of the extension claim `kind`, e.g. `x-project`
+    pub fn extension(&self, kind: &str) -> Option<&str> {
+        self.find(|c| matches!(c, Claim::Extension { kind: k, .. } if k == kind))
+    }
+
+    /// Claims in their `kind:value` wire form
+    pub fn to_strings(&self) -> Vec<String> {
+        self.claims.iter().map(Claim::to_string).collect()
+    }
+
+    fn find(&self, predicate: impl Fn(&Claim) -> bool) -> Option<&str> {
+        self.claims.iter().find(|c| predicate(c)).map(Claim::value)
+    }
+}
+
+impl fmt::Display for ClaimSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_strings().join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_well_formed_claim_set() {
+        let claims = [
+            "location:lab",
+            "timestamp_authority:https://tsa.example.com",
+            "operator_id:op-7",
+        ];
+        let set = ClaimSet::parse(&claims).unwrap();
+
+        assert_eq!(set.location(), Some("lab"));
+        assert_eq!(set.timestamp_authority(), Some("https://tsa.example.com"));
+        assert_eq!(set.operator_id(), Some("op-7"));
+        assert_eq!(set.to_strings(), claims);
+    }
+
+    #[test]
+    fn test_extension_claim_is_allowed() {
+        let set = ClaimSet::parse(&["location:lab", "x-project:alpha:2"]).unwrap();
+        assert_eq!(set.extension("x-project"), Some("alpha:2"));
+        assert_eq!(set.to_string(), "location:lab, x-project:alpha:2");
+    }
+
+    #[test]
+    fn test_malformed_claims_are_rejected() {
+        assert_eq!(
+            ClaimSet::parse(&["location"]),
+            Err(ClaimError::Malformed("location".to_string()))
+        );
+        assert_eq!(
+            ClaimSet::parse(&["locaton:lab"]),
+            Err(ClaimError::UnknownKind("locaton".to_string()))
+        );
+        assert!(matches!(
+            ClaimSet::parse(&["timestamp_authority:tsa.example.com"]),
+            Err(ClaimError::InvalidValue { .. })
+        ));
+        assert_eq!(
+            ClaimSet::parse(&["location:lab", "location:field"]),
+            Err(ClaimError::Duplicate("location".to_string()))
+        );
+    }
+}
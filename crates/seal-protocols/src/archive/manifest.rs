@@ -22,6 +22,8 @@ pub enum ManifestFormatError {
     Serialization(#[from] serde_json::Error),
     #[error("Invalid field value: {0}")]
     InvalidField(String),
+    #[error("Profile mismatch: expected '{expected}', found '{found}'")]
+    ProfileMismatch { expected: String, found: String },
 }
 
 // ─── Profile metadata variants ───────────────────────────────────────────────
@@ -77,7 +79,7 @@ pub struct SensorMetadata {
     pub labels: BTreeMap<String, String>,
 }
 
-/// Metadata for the `audio` profile.
+/// Metadata for the `cam.audio` profile.
 ///
 /// The `bit_depth` and `channels` fields are required and unambiguously
 /// distinguish this variant from `Generic` during untagged deserialization.
@@ -125,6 +127,30 @@ pub enum ProfileMetadata {
     Generic(GenericMetadata),
 }
 
+impl ProfileMetadata {
+    /// The `profile` name whose schema this metadata variant implements.
+    pub fn profile(&self) -> &'static str {
+        match self {
+            ProfileMetadata::CamVideo(_) => "cam.video",
+            ProfileMetadata::Sensor(_) => "sensor",
+            ProfileMetadata::Audio(_) => "cam.audio",
+            ProfileMetadata::Log(_) => "log",
+            ProfileMetadata::Generic(_) => "generic",
+        }
+    }
+
+    /// Capture start timestamp, present in every profile.
+    pub fn started_at(&self) -> &str {
+        match self {
+            ProfileMetadata::CamVideo(m) => &m.started_at,
+            ProfileMetadata::Sensor(m) => &m.started_at,
+            ProfileMetadata::Audio(m) => &m.started_at,
+            ProfileMetadata::Log(m) => &m.started_at,
+            ProfileMetadata::Generic(m) => &m.started_at,
+        }
+    }
+}
+
 // ─── Supporting structs (unchanged) ──────────────────────────────────────────
 
 /// Device information embedded in the manifest.
@@ -151,6 +177,10 @@ pub struct SegmentInfo {
     pub start_time: String,
     pub duration_seconds: f64,
     pub continuity_hash: String,
+    /// `xchacha20:<base64>` nonce for chunks sealed under a per-archive key.
+    /// `None` when the nonce is stored as the chunk file's first 24 bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
 }
 
 // ─── Main manifest type ───────────────────────────────────────────────────────
@@ -169,6 +199,12 @@ pub struct TrstManifest {
     pub claims: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prev_archive_hash: Option<String>,
+    /// Base64 hardware attestation of the signing device key, covered by the signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_attestation: Option<String>,
+    /// Hex Merkle root over the segments' BLAKE3 hashes, covered by the signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segments_root: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signature: Option<String>,
 }
@@ -203,6 +239,8 @@ impl TrstManifest {
             segments: Vec::new(),
             claims: Vec::new(),
             prev_archive_hash: None,
+            device_attestation: None,
+            segments_root: None,
             signature: None,
         }
     }
@@ -233,6 +271,8 @@ impl TrstManifest {
             segments: Vec::new(),
             claims: Vec::new(),
             prev_archive_hash: None,
+            device_attestation: None,
+            segments_root: None,
             signature: None,
         }
     }
@@ -467,6 +507,9 @@ impl TrstManifest {
                 ",\"continuity_hash\":{}",
                 serde_json::to_string(&segment.continuity_hash)?
             ));
+            if let Some(ref nonce) = segment.nonce {
+                result.push_str(&format!(",\"nonce\":{}", serde_json::to_string(nonce)?));
+            }
             result.push('}');
         }
         result.push(']');
@@ -485,6 +528,22 @@ impl TrstManifest {
             ));
         }
 
+        // Optional device_attestation
+        if let Some(ref attestation) = manifest.device_attestation {
+            result.push_str(&format!(
+                ",\"device_attestation\":{}",
+                serde_json::to_string(attestation)?
+            ));
+        }
+
+        // Optional segments_root
+        if let Some(ref root) = manifest.segments_root {
+            result.push_str(&format!(
+                ",\"segments_root\":{}",
+                serde_json::to_string(root)?
+            ));
+        }
+
         // Note: signature is explicitly excluded from canonicalization
 
         result.push('}');
@@ -520,15 +579,17 @@ impl TrstManifest {
             segments: Vec::new(),
             claims: Vec::new(),
             prev_archive_hash: None,
+            device_attestation: None,
+            segments_root: None,
             signature: None,
         }
     }
 
-    /// Create a new manifest pre-configured for the `audio` profile.
+    /// Create a new manifest pre-configured for the `cam.audio` profile.
     pub fn new_audio() -> Self {
         Self {
             trst_version: "0.1.0".to_string(),
-            profile: "audio".to_string(),
+            profile: "cam.audio".to_string(),
             device: DeviceInfo {
                 id: String::new(),
                 model: "TrustEdgeRefAudio".to_string(),
@@ -550,6 +611,8 @@ impl TrstManifest {
             segments: Vec::new(),
             claims: Vec::new(),
             prev_archive_hash: None,
+            device_attestation: None,
+            segments_root: None,
             signature: None,
         }
     }
@@ -580,6 +643,8 @@ impl TrstManifest {
             segments: Vec::new(),
             claims: Vec::new(),
             prev_archive_hash: None,
+            device_attestation: None,
+            segments_root: None,
             signature: None,
         }
     }
@@ -589,6 +654,20 @@ impl TrstManifest {
         self.signature = Some(signature);
     }
 
+    /// Check that this manifest is of the `expected` profile.
+    ///
+    /// Lets a profile-specific verifier (e.g. one that only understands
+    /// `cam.video`) reject other archives before looking at their metadata.
+    pub fn expect_profile(&self, expected: &str) -> Result<(), ManifestFormatError> {
+        if self.profile != expected {
+            return Err(ManifestFormatError::ProfileMismatch {
+                expected: expected.to_string(),
+                found: self.profile.clone(),
+            });
+        }
+        Ok(())
+    }
+
     /// Validate manifest structure and required fields.
     pub fn validate(&self) -> Result<(), ManifestFormatError> {
         if self.trst_version.is_empty() {
@@ -597,10 +676,11 @@ impl TrstManifest {
             ));
         }
 
-        // Accept "generic", "cam.video", "sensor", "audio", "log"
-        if !["generic", "cam.video", "sensor", "audio", "log"].contains(&self.profile.as_str()) {
+        // Accept "generic", "cam.video", "sensor", "cam.audio", "log"
+        if !["generic", "cam.video", "sensor", "cam.audio", "log"].contains(&self.profile.as_str())
+        {
             return Err(ManifestFormatError::InvalidField(format!(
-                "profile must be 'generic', 'cam.video', 'sensor', 'audio', or 'log', got '{}'",
+                "profile must be 'generic', 'cam.video', 'sensor', 'cam.audio', or 'log', got '{}'",
                 self.profile
             )));
         }
@@ -617,6 +697,15 @@ impl TrstManifest {
             ));
         }
 
+        // The metadata schema must be the one the profile declares. A generic
+        // profile may carry any metadata, since Generic is the untagged catch-all.
+        if self.profile != "generic" && self.metadata.profile() != self.profile {
+            return Err(ManifestFormatError::ProfileMismatch {
+                expected: self.profile.clone(),
+                found: self.metadata.profile().to_string(),
+            });
+        }
+
         // Validate metadata timestamps and required fields based on variant
         match &self.metadata {
             ProfileMetadata::CamVideo(m) => {
@@ -793,6 +882,7 @@ mod tests {
             start_time: "2025-01-15T10:30:00Z".to_string(),
             duration_seconds: 2.0,
             continuity_hash: "def456".to_string(),
+            nonce: None,
         });
         m
     }
@@ -811,6 +901,7 @@ mod tests {
             start_time: "2025-01-15T10:30:00Z".to_string(),
             duration_seconds: 2.0,
             continuity_hash: "def456".to_string(),
+            nonce: None,
         });
         m
     }
@@ -839,6 +930,7 @@ mod tests {
             start_time: "2025-01-15T10:00:00Z".to_string(),
             duration_seconds: 10.0,
             continuity_hash: "def".to_string(),
+            nonce: None,
         });
 
         let bytes = m.to_canonical_bytes().unwrap();
@@ -946,6 +1038,7 @@ mod tests {
             start_time: "t".to_string(),
             duration_seconds: 1.0,
             continuity_hash: "c".to_string(),
+            nonce: None,
         });
         assert!(m.validate().is_err());
     }
@@ -996,6 +1089,7 @@ mod tests {
             start_time: "t".to_string(),
             duration_seconds: 1.0,
             continuity_hash: "c".to_string(),
+            nonce: None,
         });
 
         let bytes = m.to_canonical_bytes().unwrap();
@@ -1036,6 +1130,31 @@ mod tests {
         assert_eq!(bytes_without_sig, bytes_with_sig);
     }
 
+    #[test]
+    fn test_canonical_bytes_include_device_attestation() {
+        let mut manifest = cam_video_manifest();
+        let without = manifest.to_canonical_bytes().unwrap();
+        assert!(!String::from_utf8(without.clone())
+            .unwrap()
+            .contains("device_attestation"));
+
+        manifest.device_attestation = Some("YXR0ZXN0YXRpb24=".to_string());
+        let with = String::from_utf8(manifest.to_canonical_bytes().unwrap()).unwrap();
+        assert!(with.ends_with(",\"device_attestation\":\"YXR0ZXN0YXRpb24=\"}"));
+    }
+
+    #[test]
+    fn test_canonical_bytes_include_segments_root() {
+        let mut manifest = cam_video_manifest();
+        manifest.device_attestation = Some("YXR0ZXN0YXRpb24=".to_string());
+        manifest.segments_root = Some("ab".repeat(32));
+        let canonical = String::from_utf8(manifest.to_canonical_bytes().unwrap()).unwrap();
+        assert!(canonical.ends_with(&format!(
+            ",\"device_attestation\":\"YXR0ZXN0YXRpb24=\",\"segments_root\":\"{}\"}}",
+            "ab".repeat(32)
+        )));
+    }
+
     #[test]
     fn test_key_ordering() {
         let manifest = cam_video_manifest();
@@ -1085,6 +1204,7 @@ mod tests {
             start_time: "2025-06-01T00:00:00Z".to_string(),
             duration_seconds: 1.0,
             continuity_hash: "def456".to_string(),
+            nonce: None,
         }
     }
 
@@ -1151,6 +1271,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validation_rejects_metadata_for_other_profile() {
+        let mut m = TrstManifest::new_audio();
+        m.device.id = "AUDIO001".to_string();
+        m.device.public_key = "ed25519:audio_key".to_string();
+        if let ProfileMetadata::Audio(ref mut meta) = m.metadata {
+            meta.started_at = "2025-06-01T00:00:00Z".to_string();
+            meta.ended_at = "2025-06-01T00:01:00Z".to_string();
+        }
+        m.segments.push(make_segment());
+        assert!(m.validate().is_ok());
+
+        // Relabelling audio metadata as cam.video must not pass validation
+        m.profile = "cam.video".to_string();
+        match m.validate() {
+            Err(ManifestFormatError::ProfileMismatch { expected, found }) => {
+                assert_eq!(expected, "cam.video");
+                assert_eq!(found, "cam.audio");
+            }
+            other => panic!("Expected ProfileMismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_expect_profile() {
+        let m = TrstManifest::new_audio();
+        assert!(m.expect_profile("cam.audio").is_ok());
+        let err = m.expect_profile("cam.video").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Profile mismatch: expected 'cam.video', found 'cam.audio'"
+        );
+    }
+
     #[test]
     fn test_log_metadata_round_trip() {
         let mut m = TrstManifest::new_log();
@@ -1319,7 +1473,7 @@ mod tests {
         m.segments.push(make_segment());
         assert!(
             m.validate().is_ok(),
-            "validate() must accept profile='audio'"
+            "validate() must accept profile='cam.audio'"
         );
     }
 
@@ -1407,10 +1561,28 @@ mod tests {
             start_time: "2025-01-15T10:30:00Z".to_string(),
             duration_seconds: 2.0,
             continuity_hash: "def456".to_string(),
+            nonce: None,
         });
 
         let canonical_bytes = manifest.to_canonical_bytes().unwrap();
         let json_str = String::from_utf8(canonical_bytes).unwrap();
         assert!(json_str.contains("29.97"));
     }
+
+    #[test]
+    fn test_extreme_floats_survive_json_round_trip() {
+        let mut manifest = cam_video_manifest();
+        manifest.chunk.duration_seconds = -9.643915712060553e-233;
+        if let ProfileMetadata::CamVideo(ref mut m) = manifest.metadata {
+            m.fps = 5e-324;
+        }
+        let canonical = manifest.to_canonical_bytes().unwrap();
+
+        let json = serde_json::to_string(&manifest).unwrap();
+        let reparsed: TrstManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(reparsed.to_canonical_bytes().unwrap(), canonical);
+
+        let from_canonical: TrstManifest = serde_json::from_slice(&canonical).unwrap();
+        assert_eq!(from_canonical.to_canonical_bytes().unwrap(), canonical);
+    }
 }
@@ -9,11 +9,13 @@
 //! Archive format types for .trst archives.
 
 pub mod chunks;
+pub mod claims;
 pub mod manifest;
 pub mod signatures;
 
 // Re-export commonly used items at domain level
 pub use chunks::ChunkFormatError;
+pub use claims::{Claim, ClaimError, ClaimSet};
 pub use manifest::{
     CamVideoManifest, CaptureInfo, ChunkInfo, DeviceInfo, ManifestFormatError, SegmentInfo,
 };
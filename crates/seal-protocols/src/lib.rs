@@ -38,6 +38,7 @@ pub mod archive;
 pub mod capture;
 
 // Re-export all manifest types at crate root for convenience
+pub use archive::claims::{Claim, ClaimError, ClaimSet};
 pub use archive::manifest::{
     AudioMetadata, CamVideoManifest, CamVideoMetadata, CaptureInfo, ChunkInfo, DeviceInfo,
     GenericMetadata, LogMetadata, ManifestFormatError, ProfileMetadata, SegmentInfo,
@@ -0,0 +1,217 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Single-file archive blobs for in-memory verification.
+//!
+//! A blob is a plain (uncompressed) tar of a `.trst` directory, as produced by
+//! `tar cf clip.trst.tar clip.trst`. The archive may sit at the tar root or inside
+//! one top-level directory; `manifest.json` marks the archive root and chunk files
+//! are looked up under `chunks/` next to it.
+
+use std::collections::HashMap;
+
+const BLOCK_SIZE: usize = 512;
+const MANIFEST_FILE: &str = "manifest.json";
+const CHUNKS_DIR: &str = "chunks/";
+
+/// The files of a `.trst` archive, borrowed from the blob
+pub(crate) struct ArchiveBlob<'a> {
+    pub(crate) manifest: &'a [u8],
+    chunks: HashMap<String, &'a [u8]>,
+}
+
+impl<'a> ArchiveBlob<'a> {
+    /// Split a tar blob into the manifest and its chunk files
+    pub(crate) fn parse(bytes: &'a [u8]) -> Result<Self, String> {
+        let files = read_tar(bytes)?;
+
+        // The shallowest manifest.json is the archive root
+        let (manifest_path, manifest) = files
+            .iter()
+            .filter(|(path, _)| path.rsplit('/').next() == Some(MANIFEST_FILE))
+            .min_by_key(|(path, _)| path.matches('/').count())
+            .ok_or_else(|| format!("Archive blob has no {}", MANIFEST_FILE))?;
+        let root = &manifest_path[..manifest_path.len() - MANIFEST_FILE.len()];
+        let chunks_prefix = format!("{}{}", root, CHUNKS_DIR);
+
+        let chunks = files
+            .iter()
+            .filter_map(|(path, data)| {
+                path.strip_prefix(&chunks_prefix)
+                    .map(|name| (name.to_string(), *data))
+            })
+            .collect();
+
+        Ok(Self { manifest, chunks })
+    }
+
+    /// Bytes of a chunk file, by its name inside `chunks/`
+    pub(crate) fn chunk(&self, chunk_file: &str) -> Option<&'a [u8]> {
+        self.chunks.get(chunk_file).copied()
+    }
+}
+
+/// Read every regular file in a tar, keyed by normalized path
+///
+/// Supports ustar path prefixes and GNU long names; other entry types are skipped.
+fn read_tar(bytes: &[u8]) -> Result<Vec<(String, &[u8])>, String> {
+    let mut files = Vec::new();
+    let mut long_name: Option<String> = None;
+    let mut offset = 0;
+
+    while offset + BLOCK_SIZE <= bytes.len() {
+        let header = &bytes[offset..offset + BLOCK_SIZE];
+        if header.iter().all(|&b| b == 0) {
+            break;
+        }
+        verify_checksum(header, offset)?;
+
+        let size = parse_octal(&header[124..136])
+            .ok_or_else(|| format!("Invalid tar entry size at offset {}", offset))?;
+        let data_start = offset + BLOCK_SIZE;
+        let data_end = data_start
+            .checked_add(size)
+            .filter(|&end| end <= bytes.len())
+            .ok_or_else(|| format!("Truncated tar entry at offset {}", offset))?;
+        let data = &bytes[data_start..data_end];
+
+        match header[156] {
+            b'0' | 0 => {
+                let path = match long_name.take() {
+                    Some(name) => name,
+                    None => header_path(header)?,
+                };
+                files.push((normalize_path(&path), data));
+            }
+            b'L' => long_name = Some(field_str(data)?.to_string()),
+            _ => long_name = None,
+        }
+
+        offset = data_start + size.div_ceil(BLOCK_SIZE) * BLOCK_SIZE;
+    }
+
+    Ok(files)
+}
+
+/// Entry path from the ustar `prefix` and `name` fields
+fn header_path(header: &[u8]) -> Result<String, String> {
+    let name = field_str(&header[0..100])?;
+    let prefix = if &header[257..262] == b"ustar" {
+        field_str(&header[345..500])?
+    } else {
+        ""
+    };
+    Ok(if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", prefix, name)
+    })
+}
+
+/// Header checksum: sum of all header bytes with the checksum field read as spaces
+fn verify_checksum(header: &[u8], offset: usize) -> Result<(), String> {
+    let expected = parse_octal(&header[148..156])
+        .ok_or_else(|| format!("Invalid tar checksum at offset {}", offset))?;
+    let actual: usize = header
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| {
+            if (148..156).contains(&i) {
+                32
+            } else {
+                b as usize
+            }
+        })
+        .sum();
+    if actual != expected {
+        return Err(format!("Corrupt tar header at offset {}", offset));
+    }
+    Ok(())
+}
+
+fn field_str(field: &[u8]) -> Result<&str, String> {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    std::str::from_utf8(&field[..end]).map_err(|_| "Non UTF-8 path in tar entry".to_string())
+}
+
+fn parse_octal(field: &[u8]) -> Option<usize> {
+    let digits = std::str::from_utf8(field)
+        .ok()?
+        .trim_matches(|c: char| c == '\0' || c == ' ');
+    if digits.is_empty() {
+        return Some(0);
+    }
+    usize::from_str_radix(digits, 8).ok()
+}
+
+/// Drop `./` components so `./clip.trst/manifest.json` matches `clip.trst/manifest.json`
+fn normalize_path(path: &str) -> String {
+    path.split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Build an uncompressed ustar archive from `(path, contents)` pairs
+    pub(crate) fn build_tar(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for (path, data) in entries {
+            let mut header = [0u8; BLOCK_SIZE];
+            header[..path.len()].copy_from_slice(path.as_bytes());
+            header[100..107].copy_from_slice(b"0000644");
+            header[108..115].copy_from_slice(b"0000000");
+            header[116..123].copy_from_slice(b"0000000");
+            header[124..135].copy_from_slice(format!("{:011o}", data.len()).as_bytes());
+            header[136..147].copy_from_slice(b"00000000000");
+            header[156] = b'0';
+            header[257..263].copy_from_slice(b"ustar\0");
+            header[263..265].copy_from_slice(b"00");
+            header[148..156].copy_from_slice(b"        ");
+            let sum: u32 = header.iter().map(|&b| b as u32).sum();
+            header[148..155].copy_from_slice(format!("{:06o}\0", sum).as_bytes());
+
+            out.extend_from_slice(&header);
+            out.extend_from_slice(data);
+            out.resize(out.len().div_ceil(BLOCK_SIZE) * BLOCK_SIZE, 0);
+        }
+        out.resize(out.len() + 2 * BLOCK_SIZE, 0);
+        out
+    }
+
+    #[test]
+    fn test_parse_nested_archive() {
+        let blob = build_tar(&[
+            ("./clip.trst/manifest.json", b"{}"),
+            ("./clip.trst/chunks/00000.bin", b"first"),
+            ("./clip.trst/chunks/00001.bin", b"second"),
+        ]);
+
+        let archive = ArchiveBlob::parse(&blob).unwrap();
+        assert_eq!(archive.manifest, b"{}");
+        assert_eq!(archive.chunk("00000.bin"), Some(&b"first"[..]));
+        assert_eq!(archive.chunk("00001.bin"), Some(&b"second"[..]));
+        assert_eq!(archive.chunk("00002.bin"), None);
+    }
+
+    #[test]
+    fn test_missing_manifest_rejected() {
+        let blob = build_tar(&[("chunks/00000.bin", b"data")]);
+        assert!(ArchiveBlob::parse(&blob).is_err());
+    }
+
+    #[test]
+    fn test_corrupt_header_rejected() {
+        let mut blob = build_tar(&[("manifest.json", b"{}")]);
+        blob[0] ^= 0x01;
+        assert!(ArchiveBlob::parse(&blob).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Chunk hash and continuity chain verification for the WASM verifier.
+//!
+//! Mirrors `sealedge_core::chain`: each segment hash is BLAKE3 over the chunk bytes,
+//! and each continuity hash is BLAKE3(prev || segment_hash), starting from
+//! BLAKE3("sealedge:genesis"). Kept free of JS types so it can be tested natively.
+
+use std::fmt;
+
+use sealedge_seal_protocols::SegmentInfo;
+
+/// Genesis seed for the continuity chain (must match `sealedge_core::chain`)
+const GENESIS_SEED: &[u8] = b"sealedge:genesis";
+
+/// The first segment that failed continuity verification, and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ContinuityFailure {
+    /// The chunk file for a segment could not be read
+    ChunkMissing { index: usize, chunk_file: String },
+    /// The chunk bytes do not hash to the segment's `blake3_hash`
+    HashMismatch { index: usize },
+    /// The recomputed chain does not match the segment's `continuity_hash`
+    ContinuityMismatch { index: usize },
+}
+
+impl ContinuityFailure {
+    /// Index of the offending segment
+    pub(crate) fn index(&self) -> usize {
+        match self {
+            ContinuityFailure::ChunkMissing { index, .. }
+            | ContinuityFailure::HashMismatch { index }
+            | ContinuityFailure::ContinuityMismatch { index } => *index,
+        }
+    }
+
+    /// Machine-readable category reported to JS as `error_code`
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            ContinuityFailure::ChunkMissing { .. } => "chunk_missing",
+            ContinuityFailure::HashMismatch { .. } => "hash_mismatch",
+            ContinuityFailure::ContinuityMismatch { .. } => "continuity_gap",
+        }
+    }
+}
+
+impl fmt::Display for ContinuityFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContinuityFailure::ChunkMissing { index, chunk_file } => {
+                write!(f, "Segment {}: missing chunk file {}", index, chunk_file)
+            }
+            ContinuityFailure::HashMismatch { index } => {
+                write!(f, "Segment {}: chunk hash mismatch", index)
+            }
+            ContinuityFailure::ContinuityMismatch { index } => {
+                write!(f, "Segment {}: continuity chain mismatch", index)
+            }
+        }
+    }
+}
+
+/// Walks the continuity chain one segment at a time, in manifest order
+///
+/// Chunks are fed individually so callers never need to hold the whole archive in memory.
+pub(crate) struct ContinuityVerifier {
+    previous: [u8; 32],
+    next_index: usize,
+}
+
+impl ContinuityVerifier {
+    pub(crate) fn new() -> Self {
+        Self {
+            previous: blake3::hash(GENESIS_SEED).into(),
+            next_index: 0,
+        }
+    }
+
+    /// Check the next segment against its chunk bytes and advance the chain
+    pub(crate) fn verify_segment(
+        &mut self,
+        segment: &SegmentInfo,
+        chunk_bytes: &[u8],
+    ) -> Result<(), ContinuityFailure> {
+        let index = self.next_index;
+
+        let segment_hash: [u8; 32] = blake3::hash(chunk_bytes).into();
+        if !hex_eq(&segment.blake3_hash, &segment_hash) {
+            return Err(ContinuityFailure::HashMismatch { index });
+        }
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&self.previous);
+        hasher.update(&segment_hash);
+        let continuity: [u8; 32] = hasher.finalize().into();
+        if !hex_eq(&segment.continuity_hash, &continuity) {
+            return Err(ContinuityFailure::ContinuityMismatch { index });
+        }
+
+        self.previous = continuity;
+        self.next_index += 1;
+        Ok(())
+    }
+
+    /// Index of the segment the next call to `verify_segment` will check
+    pub(crate) fn next_index(&self) -> usize {
+        self.next_index
+    }
+}
+
+/// Compare a stored hex digest with computed bytes (case-insensitive)
+fn hex_eq(stored: &str, computed: &[u8; 32]) -> bool {
+    hex::decode(stored).is_ok_and(|bytes| bytes.as_slice() == computed)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Build segments with correct hashes and continuity for the given chunks
+    pub(crate) fn build_segments(chunks: &[Vec<u8>]) -> Vec<SegmentInfo> {
+        let mut previous: [u8; 32] = blake3::hash(GENESIS_SEED).into();
+        chunks
+            .iter()
+            .enumerate()
+            .map(|(i, chunk)| {
+                let hash: [u8; 32] = blake3::hash(chunk).into();
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(&previous);
+                hasher.update(&hash);
+                previous = hasher.finalize().into();
+                SegmentInfo {
+                    chunk_file: format!("{:05}.bin", i),
+                    blake3_hash: hex::encode(hash),
+                    start_time: format!("{}.000s", i * 2),
+                    duration_seconds: 2.0,
+                    continuity_hash: hex::encode(previous),
+                    nonce: None,
+                }
+            })
+            .collect()
+    }
+
+    fn sample_chunks() -> Vec<Vec<u8>> {
+        (0..4u8).map(|i| vec![i; 256]).collect()
+    }
+
+    #[test]
+    fn test_intact_chain_passes() {
+        let chunks = sample_chunks();
+        let segments = build_segments(&chunks);
+
+        let mut verifier = ContinuityVerifier::new();
+        for (segment, chunk) in segments.iter().zip(&chunks) {
+            verifier.verify_segment(segment, chunk).unwrap();
+        }
+        assert_eq!(verifier.next_index(), 4);
+    }
+
+    #[test]
+    fn test_byte_flipped_chunk_reports_index() {
+        let mut chunks = sample_chunks();
+        let segments = build_segments(&chunks);
+        chunks[2][17] ^= 0x01;
+
+        let mut verifier = ContinuityVerifier::new();
+        let failure = segments
+            .iter()
+            .zip(&chunks)
+            .try_for_each(|(segment, chunk)| verifier.verify_segment(segment, chunk))
+            .unwrap_err();
+        assert_eq!(failure, ContinuityFailure::HashMismatch { index: 2 });
+        assert_eq!(failure.index(), 2);
+    }
+
+    #[test]
+    fn test_reordered_segments_break_continuity() {
+        let chunks = sample_chunks();
+        let mut segments = build_segments(&chunks);
+        segments.swap(1, 2);
+        let reordered = vec![
+            chunks[0].clone(),
+            chunks[2].clone(),
+            chunks[1].clone(),
+            chunks[3].clone(),
+        ];
+
+        let mut verifier = ContinuityVerifier::new();
+        let failure = segments
+            .iter()
+            .zip(&reordered)
+            .try_for_each(|(segment, chunk)| verifier.verify_segment(segment, chunk))
+            .unwrap_err();
+        assert_eq!(failure, ContinuityFailure::ContinuityMismatch { index: 1 });
+    }
+}
@@ -23,19 +23,148 @@ use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 // Import canonical manifest types from trst-protocols
 use sealedge_seal_protocols::TrstManifest;
 
+mod blob;
+mod continuity;
 pub mod crypto;
 
+use blob::ArchiveBlob;
+use continuity::{ContinuityFailure, ContinuityVerifier};
+
 // Initialize panic hook for better error messages in debug
 #[wasm_bindgen(start)]
 pub fn main() {
     console_error_panic_hook::set_once();
 }
 
+/// Status of one check, serialized as `"pass"`, `"fail"` or `"skipped"`
+///
+/// Mirrors `sealedge_core::CheckStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Pass,
+    Fail,
+    /// Not run, e.g. continuity when only the manifest was supplied
+    Skipped,
+}
+
 #[derive(Serialize)]
 struct VerificationResult {
-    signature: String,  // "pass" | "fail"
-    continuity: String, // "pass" | "fail"
+    signature: CheckStatus,
+    continuity: CheckStatus,
     segment_count: u32,
+    /// Checks that were not run, with the reason for each
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    skipped_checks: Vec<SkippedCheck>,
+    /// Index of the first segment that failed continuity verification
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failed_segment: Option<u32>,
+    /// Machine-readable failure category, see [`VerificationError`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_code: Option<String>,
+    /// Human-readable failure detail for display
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_message: Option<String>,
+}
+
+/// A check that was not run, surfaced to JS as `{ check, reason }`
+///
+/// Mirrors `sealedge_core::SkippedCheck`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct SkippedCheck {
+    check: &'static str,
+    reason: &'static str,
+}
+
+/// Checks manifest-only verification cannot run without the chunk files
+const MANIFEST_ONLY_SKIPPED: [SkippedCheck; 2] = [
+    SkippedCheck {
+        check: "chunk_hashes",
+        reason: "chunk hashes not checked: no chunk files supplied",
+    },
+    SkippedCheck {
+        check: "continuity",
+        reason: "continuity chain not checked: no chunk files supplied",
+    },
+];
+
+impl VerificationResult {
+    /// Signature failed, so continuity was never checked
+    fn signature_failed(manifest: &TrstManifest, error: VerificationError) -> Self {
+        Self {
+            signature: CheckStatus::Fail,
+            continuity: CheckStatus::Fail,
+            segment_count: manifest.segments.len() as u32,
+            skipped_checks: Vec::new(),
+            failed_segment: None,
+            error_code: Some(error.code.to_string()),
+            error_message: Some(error.message),
+        }
+    }
+
+    /// Signature passed; `continuity` is the outcome of the chunk checks
+    fn signature_passed(
+        manifest: &TrstManifest,
+        continuity: Result<(), ContinuityFailure>,
+    ) -> Self {
+        let (failed_segment, error) = match continuity {
+            Ok(()) => (None, None),
+            Err(failure) => (
+                Some(failure.index() as u32),
+                Some(VerificationError::from(failure)),
+            ),
+        };
+        Self {
+            signature: CheckStatus::Pass,
+            continuity: if error.is_none() {
+                CheckStatus::Pass
+            } else {
+                CheckStatus::Fail
+            },
+            segment_count: manifest.segments.len() as u32,
+            skipped_checks: Vec::new(),
+            failed_segment,
+            error_code: error.as_ref().map(|e| e.code.to_string()),
+            error_message: error.map(|e| e.message),
+        }
+    }
+
+    /// Only the manifest was checked; continuity is skipped whatever the signature says
+    fn manifest_only(manifest: &TrstManifest, signature: Result<(), VerificationError>) -> Self {
+        let mut result = match signature {
+            Ok(()) => Self::signature_passed(manifest, Ok(())),
+            Err(error) => Self::signature_failed(manifest, error),
+        };
+        result.continuity = CheckStatus::Skipped;
+        result.skipped_checks = MANIFEST_ONLY_SKIPPED.to_vec();
+        result
+    }
+}
+
+/// Why verification failed, surfaced to JS as `error_code` / `error_message`
+///
+/// Codes: `bad_public_key`, `missing_signature`, `bad_signature`,
+/// `signature_mismatch`, `bad_manifest`, `chunk_missing`, `hash_mismatch`,
+/// `continuity_gap`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VerificationError {
+    code: &'static str,
+    message: String,
+}
+
+impl VerificationError {
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<ContinuityFailure> for VerificationError {
+    fn from(failure: ContinuityFailure) -> Self {
+        Self::new(failure.code(), failure.to_string())
+    }
 }
 
 /// Verify a manifest directly from bytes
@@ -45,54 +174,28 @@ pub fn verify_manifest(manifest_bytes: Vec<u8>, device_pub: String) -> Result<Js
     let manifest: TrstManifest = serde_json::from_slice(&manifest_bytes)
         .map_err(|e| JsValue::from_str(&format!("Failed to parse manifest: {}", e)))?;
 
-    // Get signature
-    let signature_str = manifest
-        .signature
-        .as_ref()
-        .ok_or_else(|| JsValue::from_str("Manifest has no signature"))?;
-
-    // Get canonical bytes using trst-protocols's canonicalization
-    let canonical_bytes = manifest
-        .to_canonical_bytes()
-        .map_err(|e| JsValue::from_str(&format!("Canonicalization failed: {}", e)))?;
-
-    // Ensure device public key has proper format
-    let device_pub_key = if device_pub.starts_with("ed25519:") {
-        device_pub
-    } else {
-        format!("ed25519:{}", device_pub)
-    };
-
-    // Verify signature
-    let signature_result =
-        match verify_ed25519_signature(&device_pub_key, &canonical_bytes, signature_str) {
-            Ok(true) => "pass",
-            Ok(false) => "fail",
-            Err(_) => "fail",
-        };
-
-    // For manifest-only verification, we can't verify continuity without chunk files
-    // So we'll mark continuity as "pass" if signature passes (basic validation)
-    let continuity_result = if signature_result == "pass" {
-        "pass"
-    } else {
-        "fail"
-    };
-
-    let result = VerificationResult {
-        signature: signature_result.to_string(),
-        continuity: continuity_result.to_string(),
-        segment_count: manifest.segments.len() as u32,
-    };
+    let result = verify_manifest_only(&manifest, &device_pub);
 
     to_value(&result).map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
 }
 
+/// Signature check for a manifest on its own
+///
+/// Without chunk files neither chunk hashes nor continuity can be checked, so
+/// continuity is `"skipped"` and `skipped_checks` says why.
+fn verify_manifest_only(manifest: &TrstManifest, device_pub: &str) -> VerificationResult {
+    VerificationResult::manifest_only(manifest, check_manifest_signature(manifest, device_pub))
+}
+
 /// Verify a complete .trst archive from a directory handle
+///
+/// If `progress` is given it is called as `progress(index, total)` after each
+/// segment passes continuity verification.
 #[wasm_bindgen]
 pub async fn verify_archive(
     dir_handle: FileSystemDirectoryHandle,
     device_pub: String,
+    progress: Option<js_sys::Function>,
 ) -> Result<JsValue, JsValue> {
     // Read manifest.json from the directory
     let manifest_content = read_file_from_directory(&dir_handle, "manifest.json").await?;
@@ -101,48 +204,120 @@ pub async fn verify_archive(
     let manifest: TrstManifest = serde_json::from_slice(&manifest_content)
         .map_err(|e| JsValue::from_str(&format!("Failed to parse manifest: {}", e)))?;
 
-    // Get signature
+    // Verify signature, then continuity by hashing every chunk and recomputing the chain
+    let result = match check_manifest_signature(&manifest, &device_pub) {
+        Ok(()) => {
+            let mut on_progress =
+                |index: u32, total: u32| report_progress(progress.as_ref(), index, total);
+            let continuity =
+                verify_archive_continuity(&dir_handle, &manifest, &mut on_progress).await;
+            VerificationResult::signature_passed(&manifest, continuity)
+        }
+        Err(error) => VerificationResult::signature_failed(&manifest, error),
+    };
+
+    to_value(&result).map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
+/// Verify a complete .trst archive packed into a single tar blob
+///
+/// For environments without the File System Access API: the archive directory is
+/// tarred (`tar cf clip.trst.tar clip.trst`) and passed in as bytes. `progress`
+/// behaves as in [`verify_archive`].
+#[wasm_bindgen]
+pub fn verify_archive_blob(
+    bytes: Vec<u8>,
+    device_pub: String,
+    progress: Option<js_sys::Function>,
+) -> Result<JsValue, JsValue> {
+    let mut on_progress = |index: u32, total: u32| report_progress(progress.as_ref(), index, total);
+    let result =
+        verify_blob(&bytes, &device_pub, &mut on_progress).map_err(|e| JsValue::from_str(&e))?;
+
+    to_value(&result).map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
+/// Signature and continuity checks over an in-memory archive blob
+fn verify_blob(
+    bytes: &[u8],
+    device_pub: &str,
+    on_progress: &mut dyn FnMut(u32, u32),
+) -> Result<VerificationResult, String> {
+    let archive = ArchiveBlob::parse(bytes)?;
+
+    let manifest: TrstManifest = serde_json::from_slice(archive.manifest)
+        .map_err(|e| format!("Failed to parse manifest: {}", e))?;
+
+    Ok(match check_manifest_signature(&manifest, device_pub) {
+        Ok(()) => {
+            let continuity = verify_blob_continuity(&archive, &manifest, on_progress);
+            VerificationResult::signature_passed(&manifest, continuity)
+        }
+        Err(error) => VerificationResult::signature_failed(&manifest, error),
+    })
+}
+
+/// Verify archive continuity against chunks held in a blob
+fn verify_blob_continuity(
+    archive: &ArchiveBlob<'_>,
+    manifest: &TrstManifest,
+    on_progress: &mut dyn FnMut(u32, u32),
+) -> Result<(), ContinuityFailure> {
+    let total = manifest.segments.len() as u32;
+    let mut verifier = ContinuityVerifier::new();
+    for segment in &manifest.segments {
+        let chunk_bytes =
+            archive
+                .chunk(&segment.chunk_file)
+                .ok_or_else(|| ContinuityFailure::ChunkMissing {
+                    index: verifier.next_index(),
+                    chunk_file: segment.chunk_file.clone(),
+                })?;
+        verifier.verify_segment(segment, chunk_bytes)?;
+        on_progress(verifier.next_index() as u32 - 1, total);
+    }
+    Ok(())
+}
+
+/// Invoke an optional JS progress callback
+///
+/// A throwing callback is logged and ignored so a buggy progress bar can never
+/// change the verification verdict.
+fn report_progress(callback: Option<&js_sys::Function>, index: u32, total: u32) {
+    let Some(callback) = callback else {
+        return;
+    };
+    if let Err(e) = callback.call2(&JsValue::NULL, &JsValue::from(index), &JsValue::from(total)) {
+        web_sys::console::warn_2(&JsValue::from_str("Progress callback threw:"), &e);
+    }
+}
+
+/// Check a manifest signature against the device public key
+///
+/// The signed bytes come from `TrstManifest::to_canonical_bytes` in
+/// `sealedge-seal-protocols`, the same canonicalization core and the CLI sign with,
+/// so the browser and the CLI always agree on the verdict.
+fn check_manifest_signature(
+    manifest: &TrstManifest,
+    device_pub: &str,
+) -> Result<(), VerificationError> {
     let signature_str = manifest
         .signature
         .as_ref()
-        .ok_or_else(|| JsValue::from_str("Manifest has no signature"))?;
+        .ok_or_else(|| VerificationError::new("missing_signature", "Manifest has no signature"))?;
 
-    // Get canonical bytes using trst-protocols's canonicalization
-    let canonical_bytes = manifest
-        .to_canonical_bytes()
-        .map_err(|e| JsValue::from_str(&format!("Canonicalization failed: {}", e)))?;
+    let canonical_bytes = manifest.to_canonical_bytes().map_err(|e| {
+        VerificationError::new("bad_manifest", format!("Canonicalization failed: {}", e))
+    })?;
 
     // Ensure device public key has proper format
     let device_pub_key = if device_pub.starts_with("ed25519:") {
-        device_pub
+        device_pub.to_string()
     } else {
         format!("ed25519:{}", device_pub)
     };
 
-    // Verify signature
-    let signature_result =
-        match verify_ed25519_signature(&device_pub_key, &canonical_bytes, signature_str) {
-            Ok(true) => "pass",
-            Ok(false) => "fail",
-            Err(_) => "fail",
-        };
-
-    // Verify continuity by checking chunk files exist and match manifest
-    let mut continuity_result = "fail";
-    if signature_result == "pass" {
-        continuity_result = match verify_archive_continuity(&dir_handle, &manifest).await {
-            Ok(()) => "pass",
-            Err(_) => "fail",
-        };
-    }
-
-    let result = VerificationResult {
-        signature: signature_result.to_string(),
-        continuity: continuity_result.to_string(),
-        segment_count: manifest.segments.len() as u32,
-    };
-
-    to_value(&result).map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    verify_ed25519_signature(&device_pub_key, &canonical_bytes, signature_str)
 }
 
 /// Verify Ed25519 signature
@@ -150,39 +325,44 @@ fn verify_ed25519_signature(
     device_pub: &str,
     canonical_bytes: &[u8],
     signature_str: &str,
-) -> Result<bool, String> {
+) -> Result<(), VerificationError> {
+    let bad_key = |message: String| VerificationError::new("bad_public_key", message);
+    let bad_signature = |message: String| VerificationError::new("bad_signature", message);
+
     // Parse public key
     if !device_pub.starts_with("ed25519:") {
-        return Err("Public key must start with 'ed25519:'".to_string());
+        return Err(bad_key("Public key must start with 'ed25519:'".to_string()));
     }
 
     let b64_part = &device_pub[8..];
     let pub_bytes = general_purpose::STANDARD
         .decode(b64_part)
-        .map_err(|e| format!("Invalid public key base64: {}", e))?;
+        .map_err(|e| bad_key(format!("Invalid public key base64: {}", e)))?;
 
     if pub_bytes.len() != 32 {
-        return Err("Public key must be 32 bytes".to_string());
+        return Err(bad_key("Public key must be 32 bytes".to_string()));
     }
 
     let mut key_bytes = [0u8; 32];
     key_bytes.copy_from_slice(&pub_bytes);
 
-    let verifying_key =
-        VerifyingKey::from_bytes(&key_bytes).map_err(|e| format!("Invalid public key: {}", e))?;
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| bad_key(format!("Invalid public key: {}", e)))?;
 
     // Parse signature
     if !signature_str.starts_with("ed25519:") {
-        return Err("Signature must start with 'ed25519:'".to_string());
+        return Err(bad_signature(
+            "Signature must start with 'ed25519:'".to_string(),
+        ));
     }
 
     let sig_b64_part = &signature_str[8..];
     let sig_bytes = general_purpose::STANDARD
         .decode(sig_b64_part)
-        .map_err(|e| format!("Invalid signature base64: {}", e))?;
+        .map_err(|e| bad_signature(format!("Invalid signature base64: {}", e)))?;
 
     if sig_bytes.len() != 64 {
-        return Err("Signature must be 64 bytes".to_string());
+        return Err(bad_signature("Signature must be 64 bytes".to_string()));
     }
 
     let mut signature_bytes = [0u8; 64];
@@ -191,10 +371,14 @@ fn verify_ed25519_signature(
     let signature = Signature::from_bytes(&signature_bytes);
 
     // Verify signature
-    match verifying_key.verify(canonical_bytes, &signature) {
-        Ok(()) => Ok(true),
-        Err(_) => Ok(false),
-    }
+    verifying_key
+        .verify(canonical_bytes, &signature)
+        .map_err(|_| {
+            VerificationError::new(
+                "signature_mismatch",
+                "Signature does not match the device public key",
+            )
+        })
 }
 
 /// Helper function to read a file from a directory handle
@@ -230,37 +414,219 @@ async fn read_file_from_directory(
     Ok(uint8_array.to_vec())
 }
 
-/// Verify archive continuity by checking that chunks exist and match the manifest
+/// Verify archive continuity by hashing each chunk and recomputing the chain
+///
+/// Chunks are read one at a time in manifest order; the first missing chunk, hash
+/// mismatch, or continuity break is reported with its segment index.
 async fn verify_archive_continuity(
     dir_handle: &FileSystemDirectoryHandle,
     manifest: &TrstManifest,
-) -> Result<(), JsValue> {
-    // Get the chunks directory handle
-    let chunks_handle = JsFuture::from(dir_handle.get_directory_handle("chunks"))
-        .await
-        .map_err(|_| JsValue::from_str("Failed to access chunks directory"))?;
+    on_progress: &mut dyn FnMut(u32, u32),
+) -> Result<(), ContinuityFailure> {
+    let Some(first_segment) = manifest.segments.first() else {
+        return Ok(());
+    };
 
-    let chunks_handle: FileSystemDirectoryHandle = chunks_handle
-        .dyn_into()
-        .map_err(|_| JsValue::from_str("Failed to cast chunks to DirectoryHandle"))?;
+    // Get the chunks directory handle; without it the very first chunk is missing
+    let chunks_handle: FileSystemDirectoryHandle =
+        match JsFuture::from(dir_handle.get_directory_handle("chunks")).await {
+            Ok(handle) => handle
+                .dyn_into()
+                .map_err(|_| ContinuityFailure::ChunkMissing {
+                    index: 0,
+                    chunk_file: first_segment.chunk_file.clone(),
+                })?,
+            Err(_) => {
+                return Err(ContinuityFailure::ChunkMissing {
+                    index: 0,
+                    chunk_file: first_segment.chunk_file.clone(),
+                })
+            }
+        };
 
-    // Check that all expected chunk files exist
+    let total = manifest.segments.len() as u32;
+    let mut verifier = ContinuityVerifier::new();
     for segment in &manifest.segments {
-        let chunk_exists = check_file_exists(&chunks_handle, &segment.chunk_file).await;
-        if !chunk_exists {
-            return Err(JsValue::from_str(&format!(
-                "Missing chunk file: {}",
-                segment.chunk_file
-            )));
-        }
+        let chunk_bytes = read_file_from_directory(&chunks_handle, &segment.chunk_file)
+            .await
+            .map_err(|_| ContinuityFailure::ChunkMissing {
+                index: verifier.next_index(),
+                chunk_file: segment.chunk_file.clone(),
+            })?;
+        verifier.verify_segment(segment, &chunk_bytes)?;
+        on_progress(verifier.next_index() as u32 - 1, total);
     }
 
-    // For P0, we'll do basic existence checking. Full hash verification would require
-    // reading and decrypting each chunk, which is more complex for the WASM demo.
     Ok(())
 }
 
-/// Check if a file exists in a directory handle
-async fn check_file_exists(dir_handle: &FileSystemDirectoryHandle, filename: &str) -> bool {
-    (JsFuture::from(dir_handle.get_file_handle(filename)).await).is_ok()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sealedge_core::{sign_manifest, DeviceKeypair, ProfileMetadata};
+
+    /// A manifest signed by core the same way `seal wrap` does
+    fn core_signed_manifest(keypair: &DeviceKeypair) -> (TrstManifest, Vec<u8>) {
+        let chunks = sample_chunks();
+        let mut manifest = sealedge_core::TrstManifest::new_cam_video();
+        manifest.device.id = "cam-01".to_string();
+        manifest.device.public_key = keypair.public.clone();
+        if let ProfileMetadata::CamVideo(ref mut meta) = manifest.metadata {
+            meta.started_at = "2025-01-01T00:00:00Z".to_string();
+            meta.ended_at = "2025-01-01T00:00:06Z".to_string();
+        }
+        manifest.segments = continuity::tests::build_segments(&chunks);
+        manifest.claims.push("location:lab".to_string());
+
+        let canonical = manifest.to_canonical_bytes().unwrap();
+        manifest.signature = Some(sign_manifest(keypair, &canonical).unwrap());
+        (manifest, canonical)
+    }
+
+    fn sample_chunks() -> Vec<Vec<u8>> {
+        (0..3u8).map(|i| vec![i; 64]).collect()
+    }
+
+    /// Tar up a signed archive the way a user would before drag-and-drop
+    fn archive_blob(manifest: &TrstManifest, chunks: &[Vec<u8>]) -> Vec<u8> {
+        let manifest_json = serde_json::to_vec_pretty(manifest).unwrap();
+        let names: Vec<String> = (0..chunks.len())
+            .map(|i| format!("clip.trst/chunks/{:05}.bin", i))
+            .collect();
+        let mut entries: Vec<(&str, &[u8])> = vec![("clip.trst/manifest.json", &manifest_json)];
+        entries.extend(
+            names
+                .iter()
+                .map(String::as_str)
+                .zip(chunks.iter().map(Vec::as_slice)),
+        );
+        blob::tests::build_tar(&entries)
+    }
+
+    #[test]
+    fn test_canonical_bytes_match_core() {
+        let keypair = DeviceKeypair::generate().unwrap();
+        let (manifest, core_canonical) = core_signed_manifest(&keypair);
+
+        // The browser only ever sees the manifest.json bytes written to disk
+        let manifest_json = serde_json::to_vec_pretty(&manifest).unwrap();
+        let parsed: TrstManifest = serde_json::from_slice(&manifest_json).unwrap();
+        assert_eq!(parsed.to_canonical_bytes().unwrap(), core_canonical);
+
+        assert_eq!(check_manifest_signature(&parsed, &keypair.public), Ok(()));
+    }
+
+    #[test]
+    fn test_core_signature_rejected_after_edit() {
+        let keypair = DeviceKeypair::generate().unwrap();
+        let (mut manifest, _) = core_signed_manifest(&keypair);
+        manifest.device.id = "cam-02".to_string();
+
+        let error = check_manifest_signature(&manifest, &keypair.public).unwrap_err();
+        assert_eq!(error.code, "signature_mismatch");
+    }
+
+    #[test]
+    fn test_verify_valid_blob() {
+        let keypair = DeviceKeypair::generate().unwrap();
+        let (manifest, _) = core_signed_manifest(&keypair);
+        let blob = archive_blob(&manifest, &sample_chunks());
+
+        let result = verify_blob(&blob, &keypair.public, &mut |_, _| {}).unwrap();
+        assert_eq!(result.segment_count, 3);
+        assert_eq!(result.signature, CheckStatus::Pass);
+        assert_eq!(result.continuity, CheckStatus::Pass);
+        assert_eq!(result.failed_segment, None);
+        assert_eq!(result.error_code, None);
+        assert_eq!(result.error_message, None);
+    }
+
+    #[test]
+    fn test_verify_blob_with_tampered_chunk() {
+        let keypair = DeviceKeypair::generate().unwrap();
+        let (manifest, _) = core_signed_manifest(&keypair);
+        let mut chunks = sample_chunks();
+        chunks[1][0] ^= 0xff;
+        let blob = archive_blob(&manifest, &chunks);
+
+        let result = verify_blob(&blob, &keypair.public, &mut |_, _| {}).unwrap();
+        assert_eq!(result.signature, CheckStatus::Pass);
+        assert_eq!(result.continuity, CheckStatus::Fail);
+        assert_eq!(result.failed_segment, Some(1));
+        assert_eq!(result.error_code.as_deref(), Some("hash_mismatch"));
+    }
+
+    #[test]
+    fn test_progress_reported_per_segment() {
+        let keypair = DeviceKeypair::generate().unwrap();
+        let (manifest, _) = core_signed_manifest(&keypair);
+        let blob = archive_blob(&manifest, &sample_chunks());
+
+        let mut calls = Vec::new();
+        let result = verify_blob(&blob, &keypair.public, &mut |index, total| {
+            calls.push((index, total))
+        })
+        .unwrap();
+        assert_eq!(result.continuity, CheckStatus::Pass);
+        assert_eq!(calls.len() as u32, result.segment_count);
+        assert_eq!(calls, vec![(0, 3), (1, 3), (2, 3)]);
+    }
+
+    #[test]
+    fn test_unsigned_manifest_reports_missing_signature() {
+        let keypair = DeviceKeypair::generate().unwrap();
+        let (mut manifest, _) = core_signed_manifest(&keypair);
+        manifest.signature = None;
+
+        let result = verify_manifest_only(&manifest, &keypair.public);
+        assert_eq!(result.signature, CheckStatus::Fail);
+        assert_eq!(result.continuity, CheckStatus::Skipped);
+        assert_eq!(result.error_code.as_deref(), Some("missing_signature"));
+        assert!(result.error_message.is_some());
+    }
+
+    #[test]
+    fn test_manifest_only_never_claims_continuity_pass() {
+        let keypair = DeviceKeypair::generate().unwrap();
+        let (manifest, _) = core_signed_manifest(&keypair);
+
+        let result = verify_manifest_only(&manifest, &keypair.public);
+        assert_eq!(result.signature, CheckStatus::Pass);
+        assert_eq!(result.continuity, CheckStatus::Skipped);
+        assert_eq!(result.error_code, None);
+        assert_eq!(result.skipped_checks, MANIFEST_ONLY_SKIPPED.to_vec());
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["continuity"], "skipped");
+        assert_eq!(json["skipped_checks"][1]["check"], "continuity");
+        assert!(json["skipped_checks"][1]["reason"]
+            .as_str()
+            .unwrap()
+            .contains("no chunk files"));
+    }
+
+    #[test]
+    fn test_malformed_device_key_reports_bad_public_key() {
+        let keypair = DeviceKeypair::generate().unwrap();
+        let (manifest, _) = core_signed_manifest(&keypair);
+
+        for device_pub in ["ed25519:not-base64!", "ed25519:AAAA", "rsa:AAAA"] {
+            let result = verify_manifest_only(&manifest, device_pub);
+            assert_eq!(result.signature, CheckStatus::Fail);
+            assert_eq!(result.error_code.as_deref(), Some("bad_public_key"));
+        }
+    }
+
+    #[test]
+    fn test_missing_chunk_reports_chunk_missing() {
+        let keypair = DeviceKeypair::generate().unwrap();
+        let (manifest, _) = core_signed_manifest(&keypair);
+        let blob = archive_blob(&manifest, &sample_chunks()[..2]);
+
+        let result = verify_blob(&blob, &keypair.public, &mut |_, _| {}).unwrap();
+        assert_eq!(result.signature, CheckStatus::Pass);
+        assert_eq!(result.continuity, CheckStatus::Fail);
+        assert_eq!(result.failed_segment, Some(2));
+        assert_eq!(result.error_code.as_deref(), Some("chunk_missing"));
+    }
 }
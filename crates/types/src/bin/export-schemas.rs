@@ -0,0 +1,31 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+//! Write JSON schemas for all sealedge-types wire types to a directory
+//!
+//! Usage: cargo run -p sealedge-types --bin export-schemas -- <output-dir>
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 2 {
+        eprintln!("Usage: {} <output-dir>", args[0]);
+        std::process::exit(1);
+    }
+
+    let out_dir = Path::new(&args[1]);
+    fs::create_dir_all(out_dir)?;
+    for (name, schema) in sealedge_types::schema::export_all() {
+        let path = out_dir.join(format!("{name}.json"));
+        fs::write(&path, serde_json::to_string_pretty(&schema)? + "\n")?;
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
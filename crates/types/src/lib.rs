@@ -18,6 +18,7 @@
 //! use sealedge_types::prelude::*;
 //!
 //! let report = VerifyReport {
+//!     schema_version: VERIFY_REPORT_SCHEMA_VERSION,
 //!     signature: "pass".to_string(),
 //!     continuity: "pass".to_string(),
 //!     segments: 10,
@@ -44,10 +45,12 @@ pub use uuid::Uuid;
 
 /// Prelude module for convenient glob imports.
 pub mod prelude {
-    pub use crate::policy::PolicyV0;
-    pub use crate::receipt::VerificationReceipt;
+    pub use crate::policy::{ManifestFacts, PolicyDecision, PolicyV0, PolicyV1, PolicyViolation};
+    pub use crate::receipt::{ReceiptSignatureError, VerificationReceipt};
     pub use crate::verification::{SegmentRef, VerifyOptions, VerifyRequest, VerifyResponse};
-    pub use crate::verify_report::{OutOfOrder, VerifyReport};
+    pub use crate::verify_report::{
+        OutOfOrder, ReportUpgradeError, VerifyReport, VERIFY_REPORT_SCHEMA_VERSION,
+    };
     pub use crate::{DateTime, Utc, Uuid};
 }
 
@@ -58,6 +61,7 @@ mod tests {
     #[test]
     fn test_verify_report_round_trip() {
         let original = VerifyReport {
+            schema_version: VERIFY_REPORT_SCHEMA_VERSION,
             signature: "pass".to_string(),
             continuity: "pass".to_string(),
             segments: 150,
@@ -101,6 +105,7 @@ mod tests {
     #[test]
     fn test_verify_report_minimal_round_trip() {
         let original = VerifyReport {
+            schema_version: VERIFY_REPORT_SCHEMA_VERSION,
             signature: "fail".to_string(),
             continuity: "skip".to_string(),
             segments: 0,
@@ -166,6 +171,90 @@ mod tests {
         assert_eq!(original.chain_tip, deserialized.chain_tip);
     }
 
+    fn sample_receipt() -> VerificationReceipt {
+        VerificationReceipt {
+            verification_id: "verify_abc123def456".to_string(),
+            profile: "cam.video".to_string(),
+            device_id: "device_12345".to_string(),
+            manifest_digest: "b3:e3b0c442".to_string(),
+            segments: 150,
+            duration_s: 45.2,
+            signature: "pass".to_string(),
+            continuity: "pass".to_string(),
+            issued_at: "2023-12-01T10:30:00Z".to_string(),
+            service_kid: "service_key_001".to_string(),
+            chain_tip: "b3:a1b2c3d4".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_receipt_canonical_bytes_are_stable() {
+        // Pinned so a serde_json feature (e.g. preserve_order) or a reordered
+        // struct cannot silently change what signatures cover
+        let expected = concat!(
+            r#"{"chain_tip":"b3:a1b2c3d4","continuity":"pass","device_id":"device_12345","#,
+            r#""duration_s":45.2,"issued_at":"2023-12-01T10:30:00Z","#,
+            r#""manifest_digest":"b3:e3b0c442","profile":"cam.video","segments":150,"#,
+            r#""service_kid":"service_key_001","signature":"pass","#,
+            r#""verification_id":"verify_abc123def456"}"#
+        );
+        let receipt = sample_receipt();
+        assert_eq!(
+            String::from_utf8(receipt.canonical_bytes().unwrap()).unwrap(),
+            expected
+        );
+
+        // Keys are sorted and a JSON round trip does not change the bytes
+        let value: serde_json::Value = serde_json::from_str(expected).unwrap();
+        let keys: Vec<&String> = value.as_object().unwrap().keys().collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+        let reparsed: VerificationReceipt =
+            serde_json::from_str(&serde_json::to_string_pretty(&receipt).unwrap()).unwrap();
+        assert_eq!(
+            reparsed.canonical_bytes().unwrap(),
+            receipt.canonical_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_signed_receipt_rejects_any_field_edit() {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let receipt = sample_receipt();
+        let signature = receipt.sign(&key).unwrap();
+        receipt.verify(&key.verifying_key(), &signature).unwrap();
+
+        let other = ed25519_dalek::SigningKey::from_bytes(&[8u8; 32]);
+        assert!(receipt.verify(&other.verifying_key(), &signature).is_err());
+
+        let edits: Vec<fn(&mut VerificationReceipt)> = vec![
+            |r| r.verification_id.push('x'),
+            |r| r.profile = "cam.audio".to_string(),
+            |r| r.device_id.push('x'),
+            |r| r.manifest_digest.push('0'),
+            |r| r.segments += 1,
+            |r| r.duration_s += 0.1,
+            |r| r.signature = "fail".to_string(),
+            |r| r.continuity = "fail".to_string(),
+            |r| r.issued_at = "2023-12-01T10:30:01Z".to_string(),
+            |r| r.service_kid.push('2'),
+            |r| r.chain_tip.push('0'),
+        ];
+        for (i, edit) in edits.into_iter().enumerate() {
+            let mut edited = receipt.clone();
+            edit(&mut edited);
+            assert!(
+                matches!(
+                    edited.verify(&key.verifying_key(), &signature),
+                    Err(ReceiptSignatureError::InvalidSignature)
+                ),
+                "edit {} did not invalidate the signature",
+                i
+            );
+        }
+    }
+
     #[test]
     fn test_policy_v0_round_trip() {
         let original = PolicyV0 {
@@ -203,6 +292,226 @@ mod tests {
         assert_eq!(original.allowed_codecs, deserialized.allowed_codecs);
     }
 
+    fn policy_report(profile: &str, segments: u32) -> VerifyReport {
+        VerifyReport {
+            schema_version: VERIFY_REPORT_SCHEMA_VERSION,
+            signature: "pass".to_string(),
+            continuity: "pass".to_string(),
+            segments,
+            duration_s: segments as f32 * 2.0,
+            profile: profile.to_string(),
+            device_id: "device_12345".to_string(),
+            first_gap_index: None,
+            out_of_order: None,
+            error: None,
+            verify_time_ms: 10,
+            chain_tip: None,
+        }
+    }
+
+    fn strict_policy() -> PolicyV0 {
+        PolicyV0 {
+            required_profile: Some("cam.video".to_string()),
+            min_segments: Some(10),
+            chunk_seconds_range: Some((1.0, 5.0)),
+            allowed_codecs: Some(vec!["h264".to_string(), "raw".to_string()]),
+        }
+    }
+
+    fn video_facts() -> ManifestFacts {
+        ManifestFacts {
+            chunk_seconds: 2.0,
+            codec: Some("raw".to_string()),
+            fps: Some(30.0),
+            resolution: Some("1920x1080".to_string()),
+        }
+    }
+
+    fn violated_rules(decision: &PolicyDecision) -> Vec<&str> {
+        decision
+            .violations
+            .iter()
+            .map(|v| v.rule.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_policy_evaluate_passes() {
+        let decision =
+            strict_policy().evaluate(&policy_report("cam.video", 12), Some(&video_facts()));
+        assert!(decision.allowed);
+        assert!(decision.violations.is_empty());
+
+        // An empty policy allows anything, even without a manifest
+        assert!(
+            PolicyV0::default()
+                .evaluate(&policy_report("sensor", 0), None)
+                .allowed
+        );
+    }
+
+    #[test]
+    fn test_policy_evaluate_profile_mismatch() {
+        let decision =
+            strict_policy().evaluate(&policy_report("cam.audio", 12), Some(&video_facts()));
+        assert!(!decision.allowed);
+        assert_eq!(violated_rules(&decision), vec!["required_profile"]);
+        assert!(decision.violations[0].reason.contains("cam.audio"));
+    }
+
+    #[test]
+    fn test_policy_evaluate_below_min_segments() {
+        let decision =
+            strict_policy().evaluate(&policy_report("cam.video", 3), Some(&video_facts()));
+        assert!(!decision.allowed);
+        assert_eq!(violated_rules(&decision), vec!["min_segments"]);
+        assert!(decision.violations[0]
+            .reason
+            .contains("below the minimum of 10"));
+    }
+
+    #[test]
+    fn test_policy_evaluate_manifest_rules() {
+        let facts = ManifestFacts {
+            chunk_seconds: 30.0,
+            codec: Some("vp9".to_string()),
+            ..video_facts()
+        };
+        let decision = strict_policy().evaluate(&policy_report("cam.video", 12), Some(&facts));
+        assert_eq!(
+            violated_rules(&decision),
+            vec!["chunk_seconds_range", "allowed_codecs"]
+        );
+
+        // Manifest-dependent rules fail closed without a manifest
+        let decision = strict_policy().evaluate(&policy_report("cam.video", 12), None);
+        assert!(!decision.allowed);
+        assert_eq!(
+            violated_rules(&decision),
+            vec!["chunk_seconds_range", "allowed_codecs"]
+        );
+    }
+
+    fn quality_policy() -> PolicyV1 {
+        PolicyV1 {
+            base: strict_policy(),
+            min_resolution: Some((1280, 720)),
+            min_fps: Some(24.0),
+        }
+    }
+
+    #[test]
+    fn test_policy_v1_quality_minimums_met() {
+        let decision =
+            quality_policy().evaluate(&policy_report("cam.video", 12), Some(&video_facts()));
+        assert!(decision.allowed);
+        assert!(decision.violations.is_empty());
+
+        // A PolicyV0 document parses as a PolicyV1 with no quality rules
+        let json = serde_json::to_string(&strict_policy()).unwrap();
+        let upgraded: PolicyV1 = serde_json::from_str(&json).unwrap();
+        assert_eq!(upgraded.base.min_segments, Some(10));
+        assert_eq!(upgraded.min_fps, None);
+        assert_eq!(upgraded.min_resolution, None);
+    }
+
+    #[test]
+    fn test_policy_v1_below_min_fps() {
+        let facts = ManifestFacts {
+            fps: Some(15.0),
+            ..video_facts()
+        };
+        let decision = quality_policy().evaluate(&policy_report("cam.video", 12), Some(&facts));
+        assert!(!decision.allowed);
+        assert_eq!(violated_rules(&decision), vec!["min_fps"]);
+        assert!(decision.violations[0]
+            .reason
+            .contains("15 fps is below the minimum of 24"));
+    }
+
+    #[test]
+    fn test_policy_v1_unparseable_resolution() {
+        let facts = ManifestFacts {
+            resolution: Some("full-hd".to_string()),
+            ..video_facts()
+        };
+        let decision = quality_policy().evaluate(&policy_report("cam.video", 12), Some(&facts));
+        assert!(!decision.allowed);
+        assert_eq!(violated_rules(&decision), vec!["min_resolution"]);
+        assert!(decision.violations[0].reason.contains("'full-hd'"));
+
+        // Each dimension must meet its minimum
+        let facts = ManifestFacts {
+            resolution: Some("1920x480".to_string()),
+            ..video_facts()
+        };
+        let decision = quality_policy().evaluate(&policy_report("cam.video", 12), Some(&facts));
+        assert_eq!(violated_rules(&decision), vec!["min_resolution"]);
+    }
+
+    const V1_REPORT_JSON: &str = r#"{
+        "signature": "pass",
+        "continuity": "pass",
+        "segments": 12,
+        "duration_s": 24.0,
+        "profile": "cam.video",
+        "device_id": "device_legacy",
+        "verify_time_ms": 80
+    }"#;
+
+    #[test]
+    fn test_verify_report_v1_defaults_schema_version() {
+        let report: VerifyReport = serde_json::from_str(V1_REPORT_JSON).unwrap();
+        assert_eq!(report.schema_version, 1);
+        assert_eq!(report.segments, 12);
+        assert_eq!(report.device_id, "device_legacy");
+    }
+
+    #[test]
+    fn test_verify_report_serializes_schema_version_first() {
+        let report = VerifyReport {
+            signature: "pass".to_string(),
+            continuity: "pass".to_string(),
+            ..VerifyReport::default()
+        };
+        assert_eq!(report.schema_version, VERIFY_REPORT_SCHEMA_VERSION);
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.starts_with(&format!(
+            "{{\"schema_version\":{}",
+            VERIFY_REPORT_SCHEMA_VERSION
+        )));
+
+        let round_trip: VerifyReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_trip.schema_version, VERIFY_REPORT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_verify_report_upgrade() {
+        let v1: serde_json::Value = serde_json::from_str(V1_REPORT_JSON).unwrap();
+        let upgraded = VerifyReport::upgrade(v1).unwrap();
+        assert_eq!(upgraded.schema_version, VERIFY_REPORT_SCHEMA_VERSION);
+        assert_eq!(upgraded.profile, "cam.video");
+
+        let current = serde_json::to_value(&upgraded).unwrap();
+        let again = VerifyReport::upgrade(current).unwrap();
+        assert_eq!(again.schema_version, VERIFY_REPORT_SCHEMA_VERSION);
+
+        let mut future: serde_json::Value = serde_json::from_str(V1_REPORT_JSON).unwrap();
+        future["schema_version"] = serde_json::json!(VERIFY_REPORT_SCHEMA_VERSION + 1);
+        assert!(matches!(
+            VerifyReport::upgrade(future),
+            Err(ReportUpgradeError::UnsupportedVersion(_))
+        ));
+
+        let mut bogus: serde_json::Value = serde_json::from_str(V1_REPORT_JSON).unwrap();
+        bogus["schema_version"] = serde_json::json!("two");
+        assert!(matches!(
+            VerifyReport::upgrade(bogus),
+            Err(ReportUpgradeError::InvalidVersion)
+        ));
+    }
+
     #[test]
     fn test_json_key_preservation() {
         let json_input = r#"{
@@ -310,6 +619,7 @@ mod tests {
         let original = VerifyResponse {
             verification_id: "verify_abc123def456".to_string(),
             result: VerifyReport {
+                schema_version: VERIFY_REPORT_SCHEMA_VERSION,
                 signature: "pass".to_string(),
                 continuity: "pass".to_string(),
                 segments: 150,
@@ -9,6 +9,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::verify_report::VerifyReport;
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
 pub struct PolicyV0 {
     pub required_profile: Option<String>,
@@ -16,3 +18,196 @@ pub struct PolicyV0 {
     pub chunk_seconds_range: Option<(f32, f32)>,
     pub allowed_codecs: Option<Vec<String>>,
 }
+
+/// [`PolicyV0`] plus minimum capture quality, for evidentiary video
+///
+/// Serializes as the `PolicyV0` fields alongside the new ones, so every `PolicyV0`
+/// document is also a valid `PolicyV1`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+pub struct PolicyV1 {
+    #[serde(flatten)]
+    pub base: PolicyV0,
+    /// Minimum `(width, height)` in pixels; both must be met
+    pub min_resolution: Option<(u32, u32)>,
+    pub min_fps: Option<f64>,
+}
+
+/// Manifest details needed by rules a [`VerifyReport`] cannot answer
+///
+/// Filled from the archive manifest: `chunk.duration_seconds` and, for profiles
+/// that record them, `metadata.codec`, `metadata.fps`, and `metadata.resolution`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ManifestFacts {
+    pub chunk_seconds: f32,
+    pub codec: Option<String>,
+    pub fps: Option<f64>,
+    /// Capture resolution as recorded, e.g. `"1920x1080"`
+    pub resolution: Option<String>,
+}
+
+/// Outcome of evaluating a [`PolicyV0`] or [`PolicyV1`]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PolicyDecision {
+    pub allowed: bool,
+    pub violations: Vec<PolicyViolation>,
+}
+
+/// A policy rule that was not satisfied
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PolicyViolation {
+    /// Name of the violated policy field, e.g. `"min_segments"`
+    pub rule: String,
+    pub reason: String,
+}
+
+impl PolicyV0 {
+    /// Check a verification report (and optionally manifest details) against this policy
+    ///
+    /// Every rule is evaluated so all violations are reported together. Rules on
+    /// chunk duration and codec fail closed when `manifest` is `None`.
+    pub fn evaluate(
+        &self,
+        report: &VerifyReport,
+        manifest: Option<&ManifestFacts>,
+    ) -> PolicyDecision {
+        let mut violations = Vec::new();
+        let mut violate = |rule: &str, reason: String| {
+            violations.push(PolicyViolation {
+                rule: rule.to_string(),
+                reason,
+            })
+        };
+
+        if let Some(required) = &self.required_profile {
+            if &report.profile != required {
+                violate(
+                    "required_profile",
+                    format!(
+                        "profile '{}' is not the required '{}'",
+                        report.profile, required
+                    ),
+                );
+            }
+        }
+
+        if let Some(min) = self.min_segments {
+            if report.segments < min {
+                violate(
+                    "min_segments",
+                    format!(
+                        "{} segments is below the minimum of {}",
+                        report.segments, min
+                    ),
+                );
+            }
+        }
+
+        if let Some((low, high)) = self.chunk_seconds_range {
+            match manifest {
+                Some(facts) if (low..=high).contains(&facts.chunk_seconds) => {}
+                Some(facts) => violate(
+                    "chunk_seconds_range",
+                    format!(
+                        "chunk duration {}s is outside {}s..={}s",
+                        facts.chunk_seconds, low, high
+                    ),
+                ),
+                None => violate(
+                    "chunk_seconds_range",
+                    "chunk duration unknown without manifest".to_string(),
+                ),
+            }
+        }
+
+        if let Some(allowed) = &self.allowed_codecs {
+            match manifest.map(|facts| facts.codec.as_deref()) {
+                Some(Some(codec)) if allowed.iter().any(|a| a == codec) => {}
+                Some(Some(codec)) => violate(
+                    "allowed_codecs",
+                    format!("codec '{}' is not in {:?}", codec, allowed),
+                ),
+                Some(None) => violate("allowed_codecs", "archive records no codec".to_string()),
+                None => violate(
+                    "allowed_codecs",
+                    "codec unknown without manifest".to_string(),
+                ),
+            }
+        }
+
+        PolicyDecision {
+            allowed: violations.is_empty(),
+            violations,
+        }
+    }
+}
+
+impl PolicyV1 {
+    /// Check a verification report and manifest details against this policy
+    ///
+    /// Evaluates the [`PolicyV0`] rules first, then the capture quality minimums.
+    /// Quality rules fail closed when `manifest` is `None`, when the archive does not
+    /// record the value, or when its resolution is not `WIDTHxHEIGHT`.
+    pub fn evaluate(
+        &self,
+        report: &VerifyReport,
+        manifest: Option<&ManifestFacts>,
+    ) -> PolicyDecision {
+        let mut violations = self.base.evaluate(report, manifest).violations;
+        let mut violate = |rule: &str, reason: String| {
+            violations.push(PolicyViolation {
+                rule: rule.to_string(),
+                reason,
+            })
+        };
+
+        if let Some((min_width, min_height)) = self.min_resolution {
+            match manifest.map(|facts| facts.resolution.as_deref()) {
+                Some(Some(resolution)) => match parse_resolution(resolution) {
+                    Some((width, height)) if width >= min_width && height >= min_height => {}
+                    Some((width, height)) => violate(
+                        "min_resolution",
+                        format!(
+                            "resolution {}x{} is below the minimum of {}x{}",
+                            width, height, min_width, min_height
+                        ),
+                    ),
+                    None => violate(
+                        "min_resolution",
+                        format!("resolution '{}' is not WIDTHxHEIGHT", resolution),
+                    ),
+                },
+                Some(None) => violate(
+                    "min_resolution",
+                    "archive records no resolution".to_string(),
+                ),
+                None => violate(
+                    "min_resolution",
+                    "resolution unknown without manifest".to_string(),
+                ),
+            }
+        }
+
+        if let Some(min) = self.min_fps {
+            match manifest.map(|facts| facts.fps) {
+                Some(Some(fps)) if fps >= min => {}
+                Some(Some(fps)) => violate(
+                    "min_fps",
+                    format!("{} fps is below the minimum of {}", fps, min),
+                ),
+                Some(None) => violate("min_fps", "archive records no fps".to_string()),
+                None => violate("min_fps", "fps unknown without manifest".to_string()),
+            }
+        }
+
+        PolicyDecision {
+            allowed: violations.is_empty(),
+            violations,
+        }
+    }
+}
+
+/// Parse a `WIDTHxHEIGHT` resolution such as `"1920x1080"`
+fn parse_resolution(resolution: &str) -> Option<(u32, u32)> {
+    let (width, height) = resolution.trim().split_once(['x', 'X'])?;
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
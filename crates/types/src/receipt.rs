@@ -6,6 +6,7 @@
 // Project: sealedge — Privacy and trust at the edge.
 //
 
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -23,3 +24,62 @@ pub struct VerificationReceipt {
     pub service_kid: String,
     pub chain_tip: String,
 }
+
+/// Why a receipt signature could not be produced or checked
+#[derive(Debug, thiserror::Error)]
+pub enum ReceiptSignatureError {
+    #[error("Receipt canonicalization failed: {0}")]
+    Canonicalization(#[from] serde_json::Error),
+    #[error("Receipt signature does not match the key")]
+    InvalidSignature,
+}
+
+impl VerificationReceipt {
+    /// Deterministic JSON for signing: compact, keys in sorted order
+    ///
+    /// Built field by field, like the manifest canonicalization in
+    /// `sealedge-seal-protocols`, so the bytes do not depend on struct field order
+    /// or on serde_json's `preserve_order` feature.
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>, serde_json::Error> {
+        let fields = [
+            ("chain_tip", serde_json::to_string(&self.chain_tip)?),
+            ("continuity", serde_json::to_string(&self.continuity)?),
+            ("device_id", serde_json::to_string(&self.device_id)?),
+            ("duration_s", serde_json::to_string(&self.duration_s)?),
+            ("issued_at", serde_json::to_string(&self.issued_at)?),
+            (
+                "manifest_digest",
+                serde_json::to_string(&self.manifest_digest)?,
+            ),
+            ("profile", serde_json::to_string(&self.profile)?),
+            ("segments", serde_json::to_string(&self.segments)?),
+            ("service_kid", serde_json::to_string(&self.service_kid)?),
+            ("signature", serde_json::to_string(&self.signature)?),
+            (
+                "verification_id",
+                serde_json::to_string(&self.verification_id)?,
+            ),
+        ];
+
+        let body: Vec<String> = fields
+            .iter()
+            .map(|(name, value)| format!("\"{}\":{}", name, value))
+            .collect();
+        Ok(format!("{{{}}}", body.join(",")).into_bytes())
+    }
+
+    /// Ed25519 signature over [`VerificationReceipt::canonical_bytes`]
+    pub fn sign(&self, key: &SigningKey) -> Result<Signature, ReceiptSignatureError> {
+        Ok(key.sign(&self.canonical_bytes()?))
+    }
+
+    /// Check a signature from [`VerificationReceipt::sign`] against the signer's key
+    pub fn verify(
+        &self,
+        key: &VerifyingKey,
+        signature: &Signature,
+    ) -> Result<(), ReceiptSignatureError> {
+        key.verify(&self.canonical_bytes()?, signature)
+            .map_err(|_| ReceiptSignatureError::InvalidSignature)
+    }
+}
@@ -11,12 +11,20 @@
 //! Uses `schemars` to generate JSON Schema (draft-07) representations of
 //! all wire types. Schema output is pinned against fixture files to prevent
 //! inadvertent drift.
+//!
+//! [`export_all`] returns every schema; the `export-schemas` binary writes them
+//! to a directory as `<name>.json`:
+//!
+//! ```text
+//! cargo run -p sealedge-types --bin export-schemas -- <output-dir>
+//! ```
 
 use std::collections::BTreeMap;
 
 use schemars::{schema::RootSchema, schema_for};
 use serde_json::Value;
 
+use crate::policy::{PolicyV0, PolicyV1};
 use crate::receipt::VerificationReceipt;
 use crate::verification::{VerifyRequest, VerifyResponse};
 use crate::verify_report::VerifyReport;
@@ -46,6 +54,22 @@ pub fn generate() -> BTreeMap<String, Value> {
     map
 }
 
+/// Generate JSON schemas for every exported type: the 4 wire types plus both policies.
+///
+/// Keys double as file stems for the `export-schemas` binary.
+pub fn export_all() -> BTreeMap<String, Value> {
+    let mut map = generate();
+    map.insert(
+        "policy.v0".to_string(),
+        serde_json::to_value(policy_schema()).expect("schema serialization failed"),
+    );
+    map.insert(
+        "policy.v1".to_string(),
+        serde_json::to_value(policy_v1_schema()).expect("schema serialization failed"),
+    );
+    map
+}
+
 /// Generate the JSON schema for `VerifyReport`.
 pub fn verify_report_schema() -> RootSchema {
     schema_for!(VerifyReport)
@@ -65,3 +89,13 @@ pub fn verify_request_schema() -> RootSchema {
 pub fn verify_response_schema() -> RootSchema {
     schema_for!(VerifyResponse)
 }
+
+/// Generate the JSON schema for `PolicyV0`.
+pub fn policy_schema() -> RootSchema {
+    schema_for!(PolicyV0)
+}
+
+/// Generate the JSON schema for `PolicyV1`.
+pub fn policy_v1_schema() -> RootSchema {
+    schema_for!(PolicyV1)
+}
@@ -8,10 +8,24 @@
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema)]
+/// `VerifyReport` shape produced by this version of the crate
+///
+/// - 1: original report, no `schema_version` field on the wire
+/// - 2: adds `schema_version`
+pub const VERIFY_REPORT_SCHEMA_VERSION: u32 = 2;
+
+/// Outcome of verifying an archive.
+///
+/// Fields serialize in declaration order: `schema_version` comes first so
+/// consumers can dispatch on it before reading the rest.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub struct VerifyReport {
+    /// Report shape version; absent in version 1 payloads
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
     pub signature: String,
     pub continuity: String,
     pub segments: u32,
@@ -35,3 +49,68 @@ pub struct OutOfOrder {
     pub expected: u32,
     pub found: u32,
 }
+
+fn legacy_schema_version() -> u32 {
+    1
+}
+
+impl Default for VerifyReport {
+    fn default() -> Self {
+        Self {
+            schema_version: VERIFY_REPORT_SCHEMA_VERSION,
+            signature: String::new(),
+            continuity: String::new(),
+            segments: 0,
+            duration_s: 0.0,
+            profile: String::new(),
+            device_id: String::new(),
+            first_gap_index: None,
+            out_of_order: None,
+            error: None,
+            verify_time_ms: 0,
+            chain_tip: None,
+        }
+    }
+}
+
+/// Why a report payload could not be upgraded
+#[derive(Debug, thiserror::Error)]
+pub enum ReportUpgradeError {
+    #[error(
+        "VerifyReport schema_version {0} is newer than supported ({VERIFY_REPORT_SCHEMA_VERSION})"
+    )]
+    UnsupportedVersion(u32),
+    #[error("VerifyReport schema_version must be an unsigned integer")]
+    InvalidVersion,
+    #[error("Invalid VerifyReport payload: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl VerifyReport {
+    /// Parse report JSON of any known version into the current shape
+    ///
+    /// Each migration step rewrites the JSON from one version to the next, so
+    /// the result always has `schema_version == VERIFY_REPORT_SCHEMA_VERSION`.
+    pub fn upgrade(mut value: Value) -> Result<Self, ReportUpgradeError> {
+        let mut version = match value.get("schema_version") {
+            None => legacy_schema_version(),
+            Some(v) => v
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or(ReportUpgradeError::InvalidVersion)?,
+        };
+        if version > VERIFY_REPORT_SCHEMA_VERSION {
+            return Err(ReportUpgradeError::UnsupportedVersion(version));
+        }
+
+        while version < VERIFY_REPORT_SCHEMA_VERSION {
+            // 1 -> 2: only the version field itself was added
+            version += 1;
+            if let Value::Object(fields) = &mut value {
+                fields.insert("schema_version".to_string(), Value::from(version));
+            }
+        }
+
+        Ok(serde_json::from_value(value)?)
+    }
+}
@@ -71,6 +71,22 @@ fn schema_verify_response_matches_fixture() {
     assert_schema_matches(&schema_value, "verify_response.v1.json");
 }
 
+#[test]
+fn schema_policy_matches_fixture() {
+    let root_schema = schema::policy_schema();
+    let schema_value =
+        serde_json::to_value(&root_schema).expect("Failed to serialize PolicyV0 schema");
+    assert_schema_matches(&schema_value, "policy.v0.json");
+}
+
+#[test]
+fn schema_policy_v1_matches_fixture() {
+    let root_schema = schema::policy_v1_schema();
+    let schema_value =
+        serde_json::to_value(&root_schema).expect("Failed to serialize PolicyV1 schema");
+    assert_schema_matches(&schema_value, "policy.v1.json");
+}
+
 #[test]
 fn schema_generate_returns_all_four_schemas() {
     let schemas = schema::generate();
@@ -97,3 +113,87 @@ fn schema_generate_returns_all_four_schemas() {
         "generate() should return exactly 4 schemas"
     );
 }
+
+#[test]
+fn schema_export_all_has_required_fields() {
+    let schemas = schema::export_all();
+    assert_eq!(
+        schemas.len(),
+        6,
+        "export_all() should return exactly 6 schemas"
+    );
+
+    let expected: [(&str, &[&str]); 6] = [
+        (
+            "verify_report.v1",
+            &[
+                "signature",
+                "continuity",
+                "segments",
+                "profile",
+                "device_id",
+            ],
+        ),
+        (
+            "receipt.v1",
+            &[
+                "verification_id",
+                "manifest_digest",
+                "service_kid",
+                "chain_tip",
+            ],
+        ),
+        ("verify_request.v1", &["device_pub", "manifest", "segments"]),
+        ("verify_response.v1", &["verification_id", "result"]),
+        ("policy.v0", &[]),
+        ("policy.v1", &[]),
+    ];
+
+    for (name, fields) in expected {
+        let schema = schemas
+            .get(name)
+            .unwrap_or_else(|| panic!("export_all() missing {name}"));
+        let required: Vec<&str> = schema["required"]
+            .as_array()
+            .map(|r| r.iter().filter_map(|f| f.as_str()).collect())
+            .unwrap_or_default();
+        for field in fields {
+            assert!(
+                required.contains(field),
+                "{name} should require {field}, required = {required:?}"
+            );
+        }
+    }
+
+    // Every PolicyV0 rule is optional
+    let policy = &schemas["policy.v0"];
+    assert!(policy["required"].as_array().is_none_or(|r| r.is_empty()));
+    for rule in [
+        "required_profile",
+        "min_segments",
+        "chunk_seconds_range",
+        "allowed_codecs",
+    ] {
+        assert!(
+            policy["properties"].get(rule).is_some(),
+            "policy.v0 missing property {rule}"
+        );
+    }
+
+    // PolicyV1 carries every PolicyV0 rule plus the quality minimums, all optional
+    let policy = &schemas["policy.v1"];
+    assert!(policy["required"].as_array().is_none_or(|r| r.is_empty()));
+    for rule in [
+        "required_profile",
+        "min_segments",
+        "chunk_seconds_range",
+        "allowed_codecs",
+        "min_resolution",
+        "min_fps",
+    ] {
+        assert!(
+            policy["properties"].get(rule).is_some(),
+            "policy.v1 missing property {rule}"
+        );
+    }
+}
@@ -95,6 +95,7 @@ fn main() -> Result<(), Box<dyn Error>> {
             start_time,
             duration_seconds: chunk_seconds,
             continuity_hash: hex::encode(next_state),
+            nonce: None,
         };
 
         segments.push(segment);
@@ -133,6 +134,8 @@ fn main() -> Result<(), Box<dyn Error>> {
         segments,
         claims: vec!["location:example".to_string()],
         prev_archive_hash: None,
+        device_attestation: None,
+        segments_root: None,
         signature: None,
     };
 
@@ -0,0 +1,91 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Arbitrary `cam.video` manifests through canonicalize, sign and verify.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use sealedge_seal_protocols::archive::manifest::{
+    CamVideoManifest, CamVideoMetadata, ProfileMetadata, SegmentInfo,
+};
+
+#[derive(Debug, Arbitrary)]
+struct Segment {
+    chunk_file: String,
+    blake3_hash: String,
+    start_time: String,
+    duration_seconds: f64,
+    continuity_hash: String,
+    nonce: Option<String>,
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    trst_version: String,
+    device_id: String,
+    model: String,
+    firmware_version: String,
+    public_key: String,
+    started_at: String,
+    ended_at: String,
+    timezone: String,
+    fps: f64,
+    resolution: String,
+    codec: String,
+    chunk_size_bytes: u64,
+    chunk_duration_seconds: f64,
+    segments: Vec<Segment>,
+    claims: Vec<String>,
+    prev_archive_hash: Option<String>,
+    device_attestation: Option<String>,
+    segments_root: Option<String>,
+}
+
+impl From<Input> for CamVideoManifest {
+    fn from(input: Input) -> Self {
+        let mut manifest = CamVideoManifest::new_cam_video();
+        manifest.trst_version = input.trst_version;
+        manifest.device.id = input.device_id;
+        manifest.device.model = input.model;
+        manifest.device.firmware_version = input.firmware_version;
+        manifest.device.public_key = input.public_key;
+        manifest.metadata = ProfileMetadata::CamVideo(CamVideoMetadata {
+            started_at: input.started_at,
+            ended_at: input.ended_at,
+            timezone: input.timezone,
+            fps: input.fps,
+            resolution: input.resolution,
+            codec: input.codec,
+        });
+        manifest.chunk.size_bytes = input.chunk_size_bytes;
+        manifest.chunk.duration_seconds = input.chunk_duration_seconds;
+        manifest.segments = input
+            .segments
+            .into_iter()
+            .map(|s| SegmentInfo {
+                chunk_file: s.chunk_file,
+                blake3_hash: s.blake3_hash,
+                start_time: s.start_time,
+                duration_seconds: s.duration_seconds,
+                continuity_hash: s.continuity_hash,
+                nonce: s.nonce,
+            })
+            .collect();
+        manifest.claims = input.claims;
+        manifest.prev_archive_hash = input.prev_archive_hash;
+        manifest.device_attestation = input.device_attestation;
+        manifest.segments_root = input.segments_root;
+        manifest
+    }
+}
+
+fuzz_target!(|input: Input| {
+    sealedge_fuzz::check_manifest(&input.into());
+});
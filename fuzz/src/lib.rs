@@ -0,0 +1,109 @@
+//
+// Copyright (c) 2025 TRUSTEDGE LABS LLC
+// This source code is subject to the terms of the Mozilla Public License, v. 2.0.
+// If a copy of the MPL was not distributed with this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Project: sealedge — Privacy and trust at the edge.
+//
+
+//! Properties shared by the manifest fuzz targets.
+//!
+//! Signatures cover `TrstManifest::to_canonical_bytes`, so the canonical form must
+//! be deterministic and must survive the JSON round trip a manifest takes between
+//! the signer and the verifier.
+
+use sealedge_core::{sign_manifest, verify_manifest, DeviceKeypair};
+use sealedge_seal_protocols::archive::manifest::{ProfileMetadata, TrstManifest};
+use std::sync::OnceLock;
+
+/// Fixed device key, so signatures are reproducible across runs
+fn device_keypair() -> &'static DeviceKeypair {
+    static KEYPAIR: OnceLock<DeviceKeypair> = OnceLock::new();
+    KEYPAIR.get_or_init(|| {
+        DeviceKeypair::import_secret(&"42".repeat(32)).expect("fixed fuzz key is valid")
+    })
+}
+
+/// Whether every float in `manifest` is finite
+///
+/// JSON has no NaN or infinity, so only finite manifests can round-trip.
+pub fn floats_are_finite(manifest: &TrstManifest) -> bool {
+    let metadata_floats = match &manifest.metadata {
+        ProfileMetadata::CamVideo(m) => vec![m.fps],
+        ProfileMetadata::Sensor(m) => [Some(m.sample_rate_hz), m.latitude, m.longitude, m.altitude]
+            .into_iter()
+            .flatten()
+            .collect(),
+        _ => Vec::new(),
+    };
+    metadata_floats
+        .into_iter()
+        .chain(std::iter::once(manifest.chunk.duration_seconds))
+        .chain(manifest.segments.iter().map(|s| s.duration_seconds))
+        .all(f64::is_finite)
+}
+
+/// Canonicalize, sign and verify `manifest`, then check the canonical bytes
+/// survive re-parsing
+///
+/// Panics on any violated property.
+pub fn check_manifest(manifest: &TrstManifest) {
+    let canonical = manifest
+        .to_canonical_bytes()
+        .expect("canonicalization must not fail");
+    assert_eq!(
+        canonical,
+        manifest.to_canonical_bytes().unwrap(),
+        "canonical bytes must be deterministic"
+    );
+
+    let keypair = device_keypair();
+    let signature = sign_manifest(keypair, &canonical).expect("signing must not fail");
+    assert_eq!(
+        signature,
+        sign_manifest(keypair, &canonical).unwrap(),
+        "Ed25519 signatures must be deterministic"
+    );
+    assert!(
+        verify_manifest(&keypair.public, &canonical, &signature).unwrap(),
+        "signature must verify over the bytes it was made from"
+    );
+
+    // Attaching the signature must not change what it covers
+    let mut signed = manifest.clone();
+    signed.set_signature(signature.clone());
+    assert_eq!(signed.to_canonical_bytes().unwrap(), canonical);
+
+    if !floats_are_finite(manifest) {
+        return;
+    }
+
+    // The verifier sees the manifest only after a JSON round trip
+    let json = serde_json::to_string(&signed).expect("finite manifest serializes");
+    let reparsed: TrstManifest = serde_json::from_str(&json).expect("serialized manifest parses");
+    let reparsed_canonical = reparsed.to_canonical_bytes().unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&reparsed_canonical),
+        String::from_utf8_lossy(&canonical),
+        "canonical bytes changed across a JSON round trip"
+    );
+    assert!(verify_manifest(&keypair.public, &reparsed_canonical, &signature).unwrap());
+
+    // The canonical form is itself a manifest, and a fixed point
+    let from_canonical: TrstManifest =
+        serde_json::from_slice(&canonical).expect("canonical bytes are valid manifest JSON");
+    assert_eq!(
+        String::from_utf8_lossy(&from_canonical.to_canonical_bytes().unwrap()),
+        String::from_utf8_lossy(&canonical),
+        "canonicalization is not idempotent"
+    );
+}
+
+/// Parse arbitrary bytes as a manifest; anything that parses must satisfy
+/// [`check_manifest`]
+pub fn check_bytes(data: &[u8]) {
+    if let Ok(manifest) = serde_json::from_slice::<TrstManifest>(data) {
+        let _ = manifest.validate();
+        check_manifest(&manifest);
+    }
+}
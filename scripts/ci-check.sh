@@ -106,10 +106,14 @@ step "Step 4: Clippy"
 
 HAS_ALSA=false
 HAS_PCSC=false
+HAS_TSS=false
+HAS_OPUS=false
 pkg-config --exists alsa 2>/dev/null && HAS_ALSA=true
 pkg-config --exists libpcsclite 2>/dev/null && HAS_PCSC=true
+pkg-config --exists tss2-esys 2>/dev/null && HAS_TSS=true
+pkg-config --exists opus 2>/dev/null && HAS_OPUS=true
 
-if $HAS_ALSA && $HAS_PCSC; then
+if $HAS_ALSA && $HAS_PCSC && $HAS_TSS && $HAS_OPUS; then
     # All system deps available — per-crate clippy (postgres excluded: sqlx removed)
     if cargo clippy -p sealedge-core --all-targets --all-features -- -D warnings && \
        cargo clippy -p sealedge-platform --all-targets --features "http,ca,openapi,yubikey" -- -D warnings && \
@@ -142,6 +146,14 @@ else
         fi
     }
 
+    $HAS_OPUS && {
+        if cargo clippy -p sealedge-core --all-targets --features opus -- -D warnings; then
+            pass "clippy opus"
+        else
+            fail "clippy opus"
+        fi
+    }
+
     $HAS_PCSC && {
         if cargo clippy -p sealedge-core --all-targets --features yubikey -- -D warnings; then
             pass "clippy yubikey"
@@ -163,7 +175,9 @@ fi
 # ── Step 5: Feature compatibility (cargo-hack) ─────────────────────
 step "Step 5: Feature compatibility (cargo-hack)"
 if command -v cargo-hack &> /dev/null; then
-    if cargo hack check --each-feature --no-dev-deps --exclude-features audio --package sealedge-core && \
+    HACK_EXCLUDE="audio"
+    $HAS_OPUS || HACK_EXCLUDE="$HACK_EXCLUDE,opus"
+    if cargo hack check --each-feature --no-dev-deps --exclude-features "$HACK_EXCLUDE" --package sealedge-core && \
        cargo hack check --each-feature --no-dev-deps --package sealedge-cli; then
         pass "cargo-hack each-feature"
     else
@@ -41,6 +41,7 @@ FIXTURE_FILES=(
   "${FIXTURES_DIR}/receipt.v1.json"
   "${FIXTURES_DIR}/verify_request.v1.json"
   "${FIXTURES_DIR}/verify_response.v1.json"
+  "${FIXTURES_DIR}/policy.v0.json"
 )
 
 for f in "${FIXTURE_FILES[@]}"; do
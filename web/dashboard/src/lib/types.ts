@@ -4,6 +4,11 @@
 // Source: crates/types/tests/fixtures/*.json
 // Generator: json-schema-to-typescript (via npx)
 
+/**
+ * Outcome of verifying an archive.
+ *
+ * Fields serialize in declaration order: `schema_version` comes first so consumers can dispatch on it before reading the rest.
+ */
 export interface VerifyReport {
   chain_tip?: string | null;
   continuity: string;
@@ -13,6 +18,10 @@ export interface VerifyReport {
   first_gap_index?: number | null;
   out_of_order?: OutOfOrder | null;
   profile: string;
+  /**
+   * Report shape version; absent in version 1 payloads
+   */
+  schema_version?: number;
   segments: number;
   signature: string;
   verify_time_ms: number;